@@ -0,0 +1,385 @@
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::audit::AuditLog;
+use crate::config::ModelRegistry;
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::core::types::{Message, Usage};
+use crate::core::{AgentObserver, AugmentedLLM, LoopConfig, RunState};
+use crate::permission::{HeadlessPermissionUI, PermissionManager, PermissionMode, PermissionUI};
+use crate::providers::factory::create_provider;
+use crate::tools::result::ToolResult;
+use crate::tools::{Tool, ToolEventEmitter, default_tools};
+
+#[derive(Debug, Clone)]
+pub enum AgentEvent {
+    TextDelta(String),
+    ToolStarted { name: String, input: String },
+    ToolCompleted { name: String, output: String },
+    ToolFailed { name: String, error: String },
+    Usage(Usage),
+    StateChanged(RunState),
+}
+
+#[derive(Clone)]
+pub enum PermissionPolicy {
+    AllowAll,
+    DenyAll,
+    Prompt(Arc<dyn PermissionUI>),
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentOutput {
+    pub message: Message,
+    pub usage: Usage,
+}
+
+impl AgentOutput {
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.message
+            .content
+            .iter()
+            .filter_map(|block| block.as_text())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+pub struct AgentBuilder {
+    llm: Option<Arc<dyn LLM>>,
+    model_id: Option<String>,
+    tools: Option<Vec<Arc<dyn Tool>>>,
+    permission_policy: PermissionPolicy,
+    permission_mode: PermissionMode,
+    system_prompt: Option<String>,
+    loop_config: LoopConfig,
+    observers: Vec<Arc<dyn AgentObserver>>,
+    audit_log: Option<AuditLog>,
+}
+
+impl Default for AgentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            llm: None,
+            model_id: None,
+            tools: None,
+            permission_policy: PermissionPolicy::DenyAll,
+            permission_mode: PermissionMode::default(),
+            system_prompt: None,
+            loop_config: LoopConfig::default(),
+            observers: Vec::new(),
+            audit_log: None,
+        }
+    }
+
+    #[must_use]
+    pub fn audit_log(mut self, log: AuditLog) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    #[must_use]
+    pub fn llm(mut self, llm: Arc<dyn LLM>) -> Self {
+        self.llm = Some(llm);
+        self
+    }
+
+    #[must_use]
+    pub fn model(mut self, model_id: impl Into<String>) -> Self {
+        self.model_id = Some(model_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn tool(mut self, tool: Arc<dyn Tool>) -> Self {
+        self.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
+    #[must_use]
+    pub fn tools(mut self, tools: Vec<Arc<dyn Tool>>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    #[must_use]
+    pub fn permission_policy(mut self, policy: PermissionPolicy) -> Self {
+        self.permission_policy = policy;
+        self
+    }
+
+    #[must_use]
+    pub const fn permission_mode(mut self, mode: PermissionMode) -> Self {
+        self.permission_mode = mode;
+        self
+    }
+
+    #[must_use]
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    #[must_use]
+    pub fn loop_config(mut self, config: LoopConfig) -> Self {
+        self.loop_config = config;
+        self
+    }
+
+    #[must_use]
+    pub const fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.loop_config.max_iterations = max_iterations;
+        self
+    }
+
+    #[must_use]
+    pub fn observer(mut self, observer: Arc<dyn AgentObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    #[must_use]
+    pub fn events(self, tx: mpsc::UnboundedSender<AgentEvent>) -> Self {
+        self.observer(Arc::new(ChannelObserver { tx }))
+    }
+
+    pub fn build(self) -> Result<Agent> {
+        let llm = match (self.llm, self.model_id) {
+            (Some(llm), _) => llm,
+            (None, Some(id)) => {
+                let registry = ModelRegistry::load();
+                let model = registry.get_model(&id).ok_or_else(|| {
+                    AgentError::Config(format!("Model '{id}' not found in registry"))
+                })?;
+                create_provider(model)?
+            }
+            (None, None) => {
+                return Err(AgentError::Config(
+                    "Agent requires an LLM or a model id".to_string(),
+                ));
+            }
+        };
+
+        let mut inner = AugmentedLLM::with_config(llm, self.loop_config, ToolEventEmitter::new())?;
+
+        for tool in self.tools.unwrap_or_else(default_tools) {
+            inner.tools_mut().register(tool);
+        }
+
+        let permission_ui: Arc<dyn PermissionUI> = match self.permission_policy {
+            PermissionPolicy::AllowAll => Arc::new(HeadlessPermissionUI::allow_session()),
+            PermissionPolicy::DenyAll => Arc::new(HeadlessPermissionUI::deny()),
+            PermissionPolicy::Prompt(ui) => ui,
+        };
+        let mut permission_manager =
+            PermissionManager::new(permission_ui)?.with_mode(self.permission_mode);
+        if let Some(log) = self.audit_log {
+            permission_manager = permission_manager.with_audit_log(log.clone());
+            inner.set_audit_log(log);
+        }
+        inner.set_permission_manager(Arc::new(permission_manager));
+
+        for observer in self.observers {
+            inner.add_observer(observer);
+        }
+
+        match self.system_prompt {
+            Some(prompt) => inner.set_system_prompt(prompt),
+            None => inner.regenerate_system_prompt(),
+        }
+
+        Ok(Agent { inner })
+    }
+}
+
+pub struct Agent {
+    inner: AugmentedLLM,
+}
+
+impl Agent {
+    #[must_use]
+    pub fn builder() -> AgentBuilder {
+        AgentBuilder::new()
+    }
+
+    pub async fn run(&mut self, prompt: impl Into<String>) -> Result<AgentOutput> {
+        let (message, usage) = self.inner.run(prompt, |_| {}).await?;
+        Ok(AgentOutput { message, usage })
+    }
+
+    #[must_use]
+    pub fn llm(&self) -> &dyn LLM {
+        self.inner.llm()
+    }
+
+    #[must_use]
+    pub const fn inner(&self) -> &AugmentedLLM {
+        &self.inner
+    }
+
+    pub const fn inner_mut(&mut self) -> &mut AugmentedLLM {
+        &mut self.inner
+    }
+}
+
+struct ChannelObserver {
+    tx: mpsc::UnboundedSender<AgentEvent>,
+}
+
+impl AgentObserver for ChannelObserver {
+    fn on_text_delta(&self, text: &str) {
+        let _ = self.tx.send(AgentEvent::TextDelta(text.to_string()));
+    }
+
+    fn on_tool_start(&self, name: &str, input: &serde_json::Value) {
+        let _ = self.tx.send(AgentEvent::ToolStarted {
+            name: name.to_string(),
+            input: input.to_string(),
+        });
+    }
+
+    fn on_tool_end(&self, name: &str, result: &ToolResult) {
+        let name = name.to_string();
+        let event = if result.is_success() {
+            AgentEvent::ToolCompleted {
+                name,
+                output: result.to_llm_string(),
+            }
+        } else {
+            AgentEvent::ToolFailed {
+                name,
+                error: result.to_llm_string(),
+            }
+        };
+        let _ = self.tx.send(event);
+    }
+
+    fn on_token_usage(&self, usage: &Usage) {
+        let _ = self.tx.send(AgentEvent::Usage(*usage));
+    }
+
+    fn on_state_change(&self, state: RunState) {
+        let _ = self.tx.send(AgentEvent::StateChanged(state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{MockLLM, MockResponse};
+
+    #[test]
+    fn test_build_requires_provider() {
+        let result = Agent::builder().build();
+        assert!(matches!(result, Err(AgentError::Config(_))));
+    }
+
+    #[test]
+    fn test_build_registers_default_tools() {
+        let agent = Agent::builder()
+            .llm(Arc::new(MockLLM::new()))
+            .build()
+            .unwrap();
+        assert_eq!(agent.inner().tools().len(), default_tools().len());
+    }
+
+    #[test]
+    fn test_build_with_custom_tools() {
+        let agent = Agent::builder()
+            .llm(Arc::new(MockLLM::new()))
+            .tool(Arc::new(crate::tools::GlobTool::new()))
+            .system_prompt("You are a test agent")
+            .build()
+            .unwrap();
+        assert_eq!(agent.inner().tools().names(), vec!["glob"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_returns_output() {
+        let mock = MockLLM::new()
+            .with_response(MockResponse::text("Hi there").with_usage(Usage::new(12, 3)));
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut agent = Agent::builder()
+            .llm(Arc::new(mock.clone()))
+            .events(tx)
+            .build()
+            .unwrap();
+
+        let output = agent.run("hello").await.unwrap();
+
+        assert_eq!(mock.request_count(), 1);
+        assert_eq!(output.text(), "Hi there");
+        assert_eq!(output.usage.input_tokens, 12);
+        assert_eq!(output.usage.output_tokens, 3);
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        messages: std::sync::Mutex<Vec<Message>>,
+    }
+
+    impl AgentObserver for RecordingObserver {
+        fn on_message(&self, message: &Message) {
+            self.messages.lock().unwrap().push(message.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_messages() {
+        let mock = MockLLM::new().with_response(MockResponse::text("unused"));
+        let observer = Arc::new(RecordingObserver::default());
+        let mut agent = Agent::builder()
+            .llm(Arc::new(mock))
+            .observer(observer.clone())
+            .build()
+            .unwrap();
+
+        agent.run("hello").await.unwrap();
+
+        let messages = observer.messages.lock().unwrap().clone();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].first_text(), Some("hello"));
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_run_until_resumed() {
+        let mock = MockLLM::new().with_response(MockResponse::text("done"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut agent = Agent::builder()
+            .llm(Arc::new(mock.clone()))
+            .events(tx)
+            .build()
+            .unwrap();
+        let control = agent.inner().run_control().clone();
+        control.pause();
+
+        let run = tokio::spawn(async move { agent.run("hello").await });
+        while !matches!(
+            rx.recv().await,
+            Some(AgentEvent::StateChanged(RunState::Paused))
+        ) {}
+        assert_eq!(mock.request_count(), 0);
+
+        control.resume();
+        run.await.unwrap().unwrap();
+        let mut states = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AgentEvent::StateChanged(state) = event {
+                states.push(state);
+            }
+        }
+        assert_eq!(
+            states,
+            [RunState::Planning, RunState::Streaming, RunState::Idle]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::get_config_dir;
+use crate::core::error::Result;
+use crate::permission::PermissionRequest;
+use crate::tools::ToolType;
+use crate::tools::result::ToolResult;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    FileWrite {
+        tool: String,
+        path: String,
+        success: bool,
+    },
+    Command {
+        command: String,
+        exit_code: Option<i32>,
+        success: bool,
+    },
+    Permission {
+        operation: String,
+        target: String,
+        decision: String,
+    },
+}
+
+impl AuditEvent {
+    #[must_use]
+    pub fn permission(request: &PermissionRequest, decision: impl Into<String>) -> Self {
+        Self::Permission {
+            operation: request.operation_type.to_string(),
+            target: request.target.clone(),
+            decision: decision.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn for_tool(
+        tool_type: &ToolType,
+        input: &serde_json::Value,
+        result: &ToolResult,
+    ) -> Option<Self> {
+        let field = |key: &str| {
+            input
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        match tool_type {
+            ToolType::WriteFile | ToolType::UpdateFile => Some(Self::FileWrite {
+                tool: tool_type.name().to_string(),
+                path: field("path"),
+                success: result.is_success(),
+            }),
+            ToolType::Bash => Some(Self::Command {
+                command: field("command"),
+                exit_code: exit_code(&result.to_llm_string()),
+                success: result.is_success(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FileWrite {
+                tool,
+                path,
+                success,
+            } => write!(
+                f,
+                "{tool} {path}{}",
+                if *success { "" } else { " (failed)" }
+            ),
+            Self::Command {
+                command, exit_code, ..
+            } => match exit_code {
+                Some(code) => write!(f, "bash `{command}` exited {code}"),
+                None => write!(f, "bash `{command}` did not finish"),
+            },
+            Self::Permission {
+                operation,
+                target,
+                decision,
+            } => write!(f, "{operation} {target}: {decision}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    #[must_use]
+    pub fn with_default_path() -> Option<Self> {
+        get_config_dir().map(|dir| Self::new(dir.join("audit.jsonl")))
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, event: AuditEvent) -> Result<()> {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            event,
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn record(&self, event: AuditEvent) {
+        if let Err(e) = self.append(event) {
+            tracing::warn!("Failed to write audit log {}: {e}", self.path.display());
+        }
+    }
+
+    pub fn recent(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.path)?;
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let skip = entries.len().saturating_sub(limit);
+        Ok(entries.into_iter().skip(skip).collect())
+    }
+}
+
+fn exit_code(output: &str) -> Option<i32> {
+    output
+        .rsplit_once("Exit code: ")
+        .and_then(|(_, code)| code.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permission::PermissionType;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tool_events() {
+        let event = AuditEvent::for_tool(
+            &ToolType::Bash,
+            &json!({"command": "exit 3"}),
+            &ToolResult::error("[No output]\n\nExit code: 3"),
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            AuditEvent::Command {
+                command: "exit 3".to_string(),
+                exit_code: Some(3),
+                success: false,
+            }
+        );
+
+        let write = AuditEvent::for_tool(
+            &ToolType::WriteFile,
+            &json!({"path": "a.txt", "content": "x"}),
+            &ToolResult::success("ok"),
+        );
+        assert!(matches!(
+            write,
+            Some(AuditEvent::FileWrite { success: true, .. })
+        ));
+
+        let read = AuditEvent::for_tool(
+            &ToolType::ReadFile,
+            &json!({"path": "a.txt"}),
+            &ToolResult::success("x"),
+        );
+        assert!(read.is_none());
+    }
+
+    #[test]
+    fn test_append_and_recent() {
+        let temp = TempDir::new().unwrap();
+        let log = AuditLog::new(temp.path().join("logs").join("audit.jsonl"));
+        let request = PermissionRequest::new(PermissionType::FileWrite, "a.txt");
+
+        for decision in ["Allow once", "Deny", "Allow for session"] {
+            log.append(AuditEvent::permission(&request, decision))
+                .unwrap();
+        }
+
+        let recent = log.recent(2).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(
+            recent[1].event.to_string(),
+            "write file a.txt: Allow for session"
+        );
+
+        let raw = std::fs::read_to_string(log.path()).unwrap();
+        assert_eq!(raw.lines().count(), 3);
+        assert!(
+            raw.lines()
+                .all(|line| line.contains("\"kind\":\"permission\""))
+        );
+    }
+}
@@ -4,14 +4,15 @@ use crate::config::AppConfig;
 use crate::core::prompt::{PromptBuilder, TemplateType};
 use crate::core::{AugmentedLLM, LLM, LoopConfig, Result};
 use crate::tools::{self, ToolEventEmitter};
+use crate::ui::i18n::language_name;
 
 use super::Cli;
 
 pub fn create_agent(llm: &Arc<dyn LLM>, cli: &Cli, config: &AppConfig) -> Result<AugmentedLLM> {
-    let loop_config = LoopConfig {
-        max_iterations: cli.max_iterations,
-        ..Default::default()
-    };
+    let mut loop_config = LoopConfig::default();
+    if let Some(max_iterations) = cli.max_iterations {
+        loop_config.max_iterations = max_iterations;
+    }
     let event_emitter = ToolEventEmitter::new();
 
     let mut agent = AugmentedLLM::with_config(llm.clone(), loop_config, event_emitter)?;
@@ -19,7 +20,7 @@ pub fn create_agent(llm: &Arc<dyn LLM>, cli: &Cli, config: &AppConfig) -> Result
     let system_prompt = build_system_prompt(llm, cli, config, &agent);
     agent.set_system_prompt(&system_prompt);
 
-    register_tools(&mut agent);
+    register_tools(&mut agent, config);
 
     Ok(agent)
 }
@@ -39,15 +40,24 @@ fn build_system_prompt(
     let base_prompt = PromptBuilder::new()
         .with_template(template_type)
         .with_model(llm.name(), llm.model())
+        .with_language(config.language.as_deref().map(language_name))
         .build(agent.tools());
 
-    match config
+    let prompt = match config
         .custom_system_prompt
         .as_deref()
         .filter(|s| !s.is_empty())
     {
         Some(custom) => format!("{base_prompt}\n\n# Custom Instructions\n\n{custom}"),
         None => base_prompt,
+    };
+    if config.tools.scratch.enabled {
+        format!(
+            "{prompt}\n\n{}",
+            agent.tool_context().scratch.prompt_section()
+        )
+    } else {
+        prompt
     }
 }
 
@@ -63,18 +73,8 @@ fn infer_template_type(llm: &Arc<dyn LLM>) -> TemplateType {
     }
 }
 
-fn register_tools(agent: &mut AugmentedLLM) {
-    let tools: Vec<Arc<dyn tools::Tool>> = vec![
-        Arc::new(tools::ReadFileTool::new()),
-        Arc::new(tools::WriteFileTool::new()),
-        Arc::new(tools::UpdateFileTool::new()),
-        Arc::new(tools::ListDirTool::new()),
-        Arc::new(tools::GlobTool::new()),
-        Arc::new(tools::GrepTool::new()),
-        Arc::new(tools::BashTool::new()),
-    ];
-
-    for tool in tools {
+fn register_tools(agent: &mut AugmentedLLM, config: &AppConfig) {
+    for tool in tools::configured_tools(&config.tools, agent.tool_context()) {
         agent.tools_mut().register(tool);
     }
 }
@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Parser, Debug)]
 #[command(name = "smith")]
 #[command(author, version, about, long_about = None)]
@@ -9,13 +11,21 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub model: Option<String>,
 
+    /// Cheaper model for the tool-heavy turns after the first reply to each message
+    #[arg(long, global = true)]
+    pub execution_model: Option<String>,
+
     /// System prompt
     #[arg(short, long, global = true)]
     pub system: Option<String>,
 
-    /// Maximum iterations for the agentic loop
-    #[arg(long, default_value = "10", global = true)]
-    pub max_iterations: usize,
+    /// Maximum iterations for the agentic loop [default: 10]
+    #[arg(long, global = true)]
+    pub max_iterations: Option<usize>,
+
+    /// Start as a named agent preset from the [agents] config section
+    #[arg(long, global = true)]
+    pub agent: Option<String>,
 
     /// Enable verbose logging
     #[arg(short, long, global = true)]
@@ -25,10 +35,18 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_stream: bool,
 
-    /// Disable colored output
+    /// Disable colored output (also honours `NO_COLOR`)
     #[arg(long, global = true)]
     pub no_color: bool,
 
+    /// Draw borders, bullets and spinners with plain ASCII characters
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Print failures as a JSON object with a stable error code on stderr
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+
     /// Load a previous session from file
     #[arg(long, global = true)]
     pub load_session: Option<PathBuf>,
@@ -37,6 +55,22 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub save_session: Option<PathBuf>,
 
+    /// Cache LLM responses on disk and replay identical requests
+    #[arg(long, global = true)]
+    pub cache: bool,
+
+    /// Replay a scripted mock scenario (JSON or YAML) instead of calling a provider
+    #[arg(long, global = true)]
+    pub scenario: Option<PathBuf>,
+
+    /// Approve every tool call without asking (requires the audit log)
+    #[arg(long, global = true)]
+    pub yolo: bool,
+
+    /// Work in a temporary git worktree on its own branch, then merge it or open a PR
+    #[arg(long)]
+    pub worktree: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -48,6 +82,58 @@ pub enum Commands {
         #[command(subcommand)]
         command: ConfigSubcommands,
     },
+    /// Serve the agent over a local HTTP + SSE API
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: SocketAddr,
+    },
+    /// Browse saved sessions and resume one
+    Sessions,
+    /// Re-render a saved session in the TUI without calling any provider
+    Replay {
+        /// Session id (or a unique prefix of it), or a path to a session JSON file
+        session: String,
+        /// Playback speed multiplier
+        #[arg(long, default_value = "1.0")]
+        speed: f64,
+    },
+    /// Compare latency and throughput across configured models
+    Bench {
+        /// Models to benchmark (defaults to every model with credentials)
+        models: Vec<String>,
+        /// Number of requests per model
+        #[arg(short = 'n', long, default_value = "3")]
+        runs: usize,
+        /// Prompt to send instead of the standard one
+        #[arg(long)]
+        prompt: Option<String>,
+        /// Maximum output tokens per request
+        #[arg(long, default_value = "256")]
+        max_tokens: u32,
+    },
+    /// Run the prompts in a task file headlessly and write per-task transcripts
+    Batch {
+        /// Task file (YAML or JSON)
+        file: PathBuf,
+        /// Number of tasks to run at once (overrides the task file)
+        #[arg(short = 'j', long)]
+        parallel: Option<usize>,
+        /// Directory for transcripts and the summary report
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Copy each task's artifacts (scratch files and marked outputs) into <dir>/<task>/
+        #[arg(long)]
+        artifacts_dir: Option<PathBuf>,
+        #[arg(long, hide = true)]
+        task: Option<usize>,
+    },
+    /// Update smith to the latest release
+    Update {
+        /// Only report whether a newer version is available
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -56,4 +142,28 @@ pub enum ConfigSubcommands {
     Init,
     /// Print config file location
     Where,
+    /// Show where each provider's API key comes from and manage stored keys
+    Keys {
+        #[command(subcommand)]
+        command: Option<KeysSubcommands>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum KeysSubcommands {
+    /// Store a provider's API key in the smith credentials file (read from stdin)
+    Set {
+        /// Provider id, e.g. anthropic or openrouter
+        provider: String,
+    },
+    /// Remove a provider's key from the smith credentials file
+    Remove {
+        /// Provider id, e.g. anthropic or openrouter
+        provider: String,
+    },
+    /// Send a one-token request with each key and record whether it worked
+    Check {
+        /// Only check this provider
+        provider: Option<String>,
+    },
 }
@@ -0,0 +1,708 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, mpsc};
+
+use crate::agent::{Agent, AgentEvent, PermissionPolicy};
+use crate::audit::AuditLog;
+use crate::config::{BashConfig, ModelRegistry};
+use crate::core::types::Usage;
+use crate::core::{AgentError, AugmentedLLM, LLM, Result};
+use crate::permission::PermissionMode;
+use crate::providers::factory::create_provider;
+use crate::providers::mock::{Scenario, ScenarioLLM};
+use crate::tools::artifacts::export_artifacts;
+use crate::tools::{BashTool, MarkArtifactTool};
+
+pub const SUMMARY_FILE: &str = "summary.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolPolicy {
+    #[default]
+    ReadOnly,
+    Edit,
+    Full,
+}
+
+impl ToolPolicy {
+    const fn permissions(self) -> (PermissionPolicy, PermissionMode) {
+        match self {
+            Self::ReadOnly => (PermissionPolicy::DenyAll, PermissionMode::ReadOnly),
+            Self::Edit => (PermissionPolicy::DenyAll, PermissionMode::AcceptEdits),
+            Self::Full => (PermissionPolicy::AllowAll, PermissionMode::Ask),
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Edit => "edit",
+            Self::Full => "full",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskDefaults {
+    pub model: Option<String>,
+    pub tools: Option<ToolPolicy>,
+    pub max_iterations: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTask {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub prompt: String,
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub tools: Option<ToolPolicy>,
+    #[serde(default)]
+    pub max_iterations: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskFile {
+    #[serde(default)]
+    pub defaults: TaskDefaults,
+    #[serde(default)]
+    pub parallel: Option<usize>,
+    pub tasks: Vec<BatchTask>,
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTask {
+    pub index: usize,
+    pub name: String,
+    pub prompt: String,
+    pub dir: PathBuf,
+    pub model: Option<String>,
+    pub tools: ToolPolicy,
+    pub max_iterations: Option<usize>,
+}
+
+impl ResolvedTask {
+    #[must_use]
+    pub fn transcript_name(&self) -> String {
+        format!("{}.md", self.slug())
+    }
+
+    #[must_use]
+    pub fn slug(&self) -> String {
+        let slug: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        format!("{:02}-{}", self.index + 1, slug.trim_matches('-'))
+    }
+}
+
+impl TaskFile {
+    pub fn parse(content: &str, yaml: bool) -> Result<Self> {
+        let file: Self = if yaml {
+            config::Config::builder()
+                .add_source(config::File::from_str(content, config::FileFormat::Yaml))
+                .build()
+                .and_then(config::Config::try_deserialize)
+                .map_err(|e| AgentError::Config(format!("Invalid task file: {e}")))?
+        } else {
+            serde_json::from_str(content)
+                .map_err(|e| AgentError::Config(format!("Invalid task file: {e}")))?
+        };
+        if file.tasks.is_empty() {
+            return Err(AgentError::Config("Task file has no tasks".to_string()));
+        }
+        if let Some(i) = file.tasks.iter().position(|t| t.prompt.trim().is_empty()) {
+            return Err(AgentError::Config(format!("Task {} has no prompt", i + 1)));
+        }
+        Ok(file)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AgentError::Config(format!("Failed to read task file {}: {e}", path.display()))
+        })?;
+        let yaml = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml" | "yml")
+        );
+        let mut file = Self::parse(&content, yaml)?;
+        file.base_dir = path
+            .canonicalize()?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Ok(file)
+    }
+
+    #[must_use]
+    pub fn resolve(&self, index: usize) -> Option<ResolvedTask> {
+        let task = self.tasks.get(index)?;
+        Some(ResolvedTask {
+            index,
+            name: task
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("task-{}", index + 1)),
+            prompt: task.prompt.clone(),
+            dir: task
+                .dir
+                .as_ref()
+                .map_or_else(|| self.base_dir.clone(), |dir| self.base_dir.join(dir)),
+            model: task.model.clone().or_else(|| self.defaults.model.clone()),
+            tools: task.tools.or(self.defaults.tools).unwrap_or_default(),
+            max_iterations: task.max_iterations.or(self.defaults.max_iterations),
+        })
+    }
+
+    #[must_use]
+    pub fn resolved(&self) -> Vec<ResolvedTask> {
+        (0..self.tasks.len())
+            .filter_map(|i| self.resolve(i))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskReport {
+    pub index: usize,
+    pub name: String,
+    pub dir: PathBuf,
+    pub model: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    pub usage: Usage,
+    pub transcript: Option<PathBuf>,
+    #[serde(default)]
+    pub artifacts: Vec<PathBuf>,
+}
+
+impl TaskReport {
+    fn failed(task: &ResolvedTask, error: impl Into<String>, elapsed: Duration) -> Self {
+        Self {
+            index: task.index,
+            name: task.name.clone(),
+            dir: task.dir.clone(),
+            model: task.model.clone().unwrap_or_default(),
+            error: Some(error.into()),
+            duration_ms: millis(elapsed),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    pub file: PathBuf,
+    pub parallel: Option<usize>,
+    pub out: Option<PathBuf>,
+    pub artifacts_dir: Option<PathBuf>,
+    pub model: Option<String>,
+    pub system_prompt: Option<String>,
+    pub scenario: Option<PathBuf>,
+    pub audit: Option<AuditLog>,
+}
+
+impl BatchOptions {
+    fn out_dir(&self, file: &TaskFile) -> PathBuf {
+        self.out.clone().unwrap_or_else(|| {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            file.base_dir
+                .join(".smith")
+                .join("batch")
+                .join(stamp.to_string())
+        })
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn task_llm(task: &ResolvedTask, options: &BatchOptions) -> Result<Arc<dyn LLM>> {
+    if let Some(path) = &options.scenario {
+        return Ok(Arc::new(ScenarioLLM::new(Scenario::load(path)?)));
+    }
+    let registry = ModelRegistry::load();
+    let model = match task.model.as_ref().or(options.model.as_ref()) {
+        Some(id) => registry
+            .get_model(id)
+            .ok_or_else(|| AgentError::Config(format!("Model '{id}' not found in registry")))?,
+        None => registry
+            .default_model()
+            .ok_or_else(|| AgentError::Config("No default model configured".to_string()))?,
+    };
+    create_provider(model)
+}
+
+pub async fn run_task(
+    task: &ResolvedTask,
+    llm: Arc<dyn LLM>,
+    options: &BatchOptions,
+    transcript: &Path,
+) -> TaskReport {
+    let started = Instant::now();
+    let model = llm.model().to_string();
+    let (permission_policy, permission_mode) = task.tools.permissions();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let mut builder = Agent::builder()
+        .llm(llm)
+        .permission_policy(permission_policy)
+        .permission_mode(permission_mode)
+        .events(tx);
+    if let Some(max_iterations) = task.max_iterations {
+        builder = builder.max_iterations(max_iterations);
+    }
+    if let Some(prompt) = &options.system_prompt {
+        builder = builder.system_prompt(prompt);
+    }
+    if let Some(audit) = &options.audit {
+        builder = builder.audit_log(audit.clone());
+    }
+
+    let mut artifacts = Vec::new();
+    let result = match builder.build() {
+        Ok(mut agent) => {
+            if options.artifacts_dir.is_some() {
+                enable_artifacts(agent.inner_mut());
+            }
+            let result = agent.run(&task.prompt).await;
+            if let Some(dir) = &options.artifacts_dir {
+                let collected = agent.inner().tool_context().artifacts();
+                match export_artifacts(&collected, &dir.join(task.slug())) {
+                    Ok(written) => artifacts = written,
+                    Err(e) => tracing::warn!("Failed to collect artifacts for {}: {e}", task.name),
+                }
+            }
+            result
+        }
+        Err(e) => Err(e),
+    };
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+
+    let (answer, usage, error) = match result {
+        Ok(output) => (output.text(), output.usage, None),
+        Err(e) => (String::new(), Usage::default(), Some(e.to_string())),
+    };
+    let content = format_transcript(task, &model, &events, &answer, error.as_deref());
+    let transcript = match std::fs::write(transcript, content) {
+        Ok(()) => Some(transcript.to_path_buf()),
+        Err(e) => {
+            tracing::warn!("Failed to write transcript {}: {e}", transcript.display());
+            None
+        }
+    };
+
+    TaskReport {
+        index: task.index,
+        name: task.name.clone(),
+        dir: task.dir.clone(),
+        model,
+        success: error.is_none(),
+        error,
+        duration_ms: millis(started.elapsed()),
+        usage,
+        transcript,
+        artifacts,
+    }
+}
+
+fn enable_artifacts(agent: &mut AugmentedLLM) {
+    let context = agent.tool_context().clone();
+    agent.tools_mut().register(Arc::new(
+        BashTool::with_config(BashConfig::default(), context.cwd.clone())
+            .with_output_store(context.outputs.clone())
+            .with_scratch(context.scratch.clone()),
+    ));
+    agent.tools_mut().register(Arc::new(MarkArtifactTool::new(
+        context.artifacts.clone(),
+        context.cwd.clone(),
+    )));
+    let prompt = format!(
+        "{}\n\n{}",
+        agent.system_prompt().unwrap_or_default(),
+        context.scratch.prompt_section()
+    );
+    agent.set_system_prompt(prompt);
+}
+
+fn format_transcript(
+    task: &ResolvedTask,
+    model: &str,
+    events: &[AgentEvent],
+    answer: &str,
+    error: Option<&str>,
+) -> String {
+    let mut out = format!("# {}\n\n", task.name);
+    let _ = writeln!(out, "- Directory: {}", task.dir.display());
+    let _ = writeln!(out, "- Model: {model}");
+    let _ = writeln!(out, "- Tools: {}", task.tools.as_str());
+    let _ = write!(out, "\n## Prompt\n\n{}\n", task.prompt.trim());
+
+    let tools: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            AgentEvent::ToolStarted { name, input } => Some(format!("- {name} {input}")),
+            AgentEvent::ToolFailed { name, error } => Some(format!(
+                "- {name} failed: {}",
+                error.lines().next().unwrap_or("")
+            )),
+            _ => None,
+        })
+        .collect();
+    if !tools.is_empty() {
+        let _ = write!(out, "\n## Tools\n\n{}\n", tools.join("\n"));
+    }
+
+    match error {
+        Some(error) => {
+            let _ = write!(out, "\n## Error\n\n{error}\n");
+        }
+        None => {
+            let _ = write!(out, "\n## Response\n\n{}\n", answer.trim());
+        }
+    }
+    out
+}
+
+pub async fn run_child(options: &BatchOptions, index: usize) -> Result<TaskReport> {
+    let file = TaskFile::load(&options.file)?;
+    let task = file
+        .resolve(index)
+        .ok_or_else(|| AgentError::Config(format!("Task {index} not found")))?;
+    let out = options
+        .out
+        .clone()
+        .ok_or_else(|| AgentError::Config("Missing output directory".to_string()))?;
+    let transcript = out.join(task.transcript_name());
+
+    let started = Instant::now();
+    match task_llm(&task, options) {
+        Ok(llm) => Ok(run_task(&task, llm, options, &transcript).await),
+        Err(e) => Ok(TaskReport::failed(&task, e.to_string(), started.elapsed())),
+    }
+}
+
+async fn spawn_child(task: &ResolvedTask, options: &BatchOptions, out: &Path) -> TaskReport {
+    let started = Instant::now();
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return TaskReport::failed(task, e.to_string(), started.elapsed()),
+    };
+
+    let mut command = tokio::process::Command::new(exe);
+    if let Some(model) = &options.model {
+        command.arg("--model").arg(model);
+    }
+    if let Some(prompt) = &options.system_prompt {
+        command.arg("--system").arg(prompt);
+    }
+    if let Some(scenario) = &options.scenario {
+        command.arg("--scenario").arg(scenario);
+    }
+    command
+        .arg("batch")
+        .arg(&options.file)
+        .arg("--out")
+        .arg(out);
+    if let Some(dir) = &options.artifacts_dir {
+        command.arg("--artifacts-dir").arg(dir);
+    }
+    command
+        .arg("--task")
+        .arg(task.index.to_string())
+        .current_dir(&task.dir)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let output = match command.output().await {
+        Ok(output) => output,
+        Err(e) => {
+            return TaskReport::failed(
+                task,
+                format!("Failed to start task in {}: {e}", task.dir.display()),
+                started.elapsed(),
+            );
+        }
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line).ok())
+        .unwrap_or_else(|| {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("task exited without a report");
+            TaskReport::failed(task, reason.trim(), started.elapsed())
+        })
+}
+
+pub async fn run_batch(options: &BatchOptions) -> Result<(PathBuf, Vec<TaskReport>)> {
+    let options = BatchOptions {
+        file: options.file.canonicalize()?,
+        scenario: match &options.scenario {
+            Some(path) => Some(path.canonicalize()?),
+            None => None,
+        },
+        artifacts_dir: match &options.artifacts_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                Some(dir.canonicalize()?)
+            }
+            None => None,
+        },
+        ..options.clone()
+    };
+    let file = TaskFile::load(&options.file)?;
+    let out = options.out_dir(&file);
+    std::fs::create_dir_all(&out)?;
+    let out = out.canonicalize()?;
+
+    let parallel = options.parallel.or(file.parallel).unwrap_or(1).max(1);
+    let semaphore = Arc::new(Semaphore::new(parallel));
+    let options = Arc::new(options);
+
+    let handles: Vec<_> = file
+        .resolved()
+        .into_iter()
+        .map(|task| {
+            let semaphore = semaphore.clone();
+            let options = options.clone();
+            let out = out.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                eprintln!("[{}] {} started", task.index + 1, task.name);
+                let report = spawn_child(&task, &options, &out).await;
+                let status = if report.success { "done" } else { "failed" };
+                eprintln!("[{}] {} {status}", task.index + 1, task.name);
+                report
+            })
+        })
+        .collect();
+
+    let mut reports = Vec::with_capacity(handles.len());
+    for handle in handles {
+        reports.push(
+            handle
+                .await
+                .map_err(|e| AgentError::InvalidState(format!("Batch task panicked: {e}")))?,
+        );
+    }
+
+    std::fs::write(
+        out.join(SUMMARY_FILE),
+        serde_json::to_string_pretty(&reports)?,
+    )?;
+    Ok((out, reports))
+}
+
+#[must_use]
+pub fn format_summary(reports: &[TaskReport]) -> String {
+    let name_width = reports
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut out = format!(
+        "{:<name_width$}  Status  {:>8}  {:>8}\n",
+        "Task", "Time", "Tokens"
+    );
+    for report in reports {
+        let status = if report.success { "ok" } else { "failed" };
+        let _ = writeln!(
+            out,
+            "{:<name_width$}  {status:<6}  {:>7.1}s  {:>8}",
+            report.name,
+            Duration::from_millis(report.duration_ms).as_secs_f64(),
+            report.usage.total()
+        );
+    }
+    let failed: Vec<&TaskReport> = reports.iter().filter(|r| !r.success).collect();
+    for report in &failed {
+        let _ = writeln!(
+            out,
+            "\n{} error: {}",
+            report.name,
+            report.error.as_deref().unwrap_or("unknown")
+        );
+    }
+    let _ = write!(
+        out,
+        "\n{} of {} tasks succeeded",
+        reports.len() - failed.len(),
+        reports.len()
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::ScenarioTurn;
+    use tempfile::TempDir;
+
+    const TASKS: &str = r"
+parallel: 2
+defaults:
+  model: claude-haiku-4-5
+  tools: edit
+tasks:
+  - name: Bump deps
+    prompt: Update dependencies
+    dir: repo-a
+  - prompt: Summarize the README
+    model: gpt-5.2
+    tools: read_only
+    max_iterations: 3
+";
+
+    #[test]
+    fn test_task_file_defaults() {
+        let file = TaskFile::parse(TASKS, true).unwrap();
+        assert_eq!(file.parallel, Some(2));
+
+        let tasks = file.resolved();
+        assert_eq!(tasks[0].name, "Bump deps");
+        assert_eq!(tasks[0].model.as_deref(), Some("claude-haiku-4-5"));
+        assert_eq!(tasks[0].tools, ToolPolicy::Edit);
+        assert_eq!(tasks[0].dir, PathBuf::from("repo-a"));
+        assert_eq!(tasks[0].transcript_name(), "01-bump-deps.md");
+
+        assert_eq!(tasks[1].name, "task-2");
+        assert_eq!(tasks[1].model.as_deref(), Some("gpt-5.2"));
+        assert_eq!(tasks[1].tools, ToolPolicy::ReadOnly);
+        assert_eq!(tasks[1].max_iterations, Some(3));
+
+        assert!(TaskFile::parse("tasks: []", true).is_err());
+        assert!(TaskFile::parse(r#"{"tasks": [{"prompt": " "}]}"#, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_task_writes_transcript() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("out.txt");
+        let notes = temp.path().join("notes.txt");
+        std::fs::write(&notes, "hello").unwrap();
+        let task = ResolvedTask {
+            index: 0,
+            name: "write".to_string(),
+            prompt: "write a file".to_string(),
+            dir: temp.path().to_path_buf(),
+            model: None,
+            tools: ToolPolicy::ReadOnly,
+            max_iterations: None,
+        };
+        let llm = ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::tool_call(
+                "write_file",
+                serde_json::json!({"path": target.to_string_lossy(), "content": "x"}),
+            ),
+            ScenarioTurn::tool_call(
+                "read_file",
+                serde_json::json!({"path": notes.to_string_lossy()}),
+            ),
+            ScenarioTurn::text("Could not write it."),
+        ]));
+        let transcript = temp.path().join(task.transcript_name());
+
+        let report = run_task(&task, Arc::new(llm), &BatchOptions::default(), &transcript).await;
+
+        assert!(report.success);
+        assert!(!target.exists());
+        let content = std::fs::read_to_string(&transcript).unwrap();
+        assert!(content.contains("- Tools: read_only"));
+        assert!(content.contains("- read_file"));
+        assert!(!content.contains("- write_file"));
+        assert!(content.contains("## Response\n\nCould not write it."));
+    }
+
+    #[tokio::test]
+    async fn test_run_task_collects_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let report_file = temp.path().join("report.md");
+        std::fs::write(&report_file, "# Findings").unwrap();
+        let task = ResolvedTask {
+            index: 1,
+            name: "Audit deps".to_string(),
+            prompt: "write a report".to_string(),
+            dir: temp.path().to_path_buf(),
+            model: None,
+            tools: ToolPolicy::ReadOnly,
+            max_iterations: None,
+        };
+        let llm = ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::tool_call(
+                "mark_artifact",
+                serde_json::json!({"path": report_file.to_string_lossy()}),
+            ),
+            ScenarioTurn::text("Report ready."),
+        ]));
+        let options = BatchOptions {
+            artifacts_dir: Some(temp.path().join("artifacts")),
+            ..BatchOptions::default()
+        };
+
+        let report = run_task(
+            &task,
+            Arc::new(llm),
+            &options,
+            &temp.path().join(task.transcript_name()),
+        )
+        .await;
+
+        let collected = temp.path().join("artifacts/02-audit-deps/report.md");
+        assert_eq!(report.artifacts, vec![collected.clone()]);
+        assert_eq!(std::fs::read_to_string(collected).unwrap(), "# Findings");
+    }
+
+    #[test]
+    fn test_summary_lists_failures() {
+        let reports = vec![
+            TaskReport {
+                name: "alpha".to_string(),
+                success: true,
+                duration_ms: 1500,
+                usage: Usage::new(10, 5),
+                ..TaskReport::default()
+            },
+            TaskReport {
+                index: 1,
+                name: "beta".to_string(),
+                error: Some("Model 'x' not found in registry".to_string()),
+                ..TaskReport::default()
+            },
+        ];
+        let summary = format_summary(&reports);
+        assert!(summary.contains("alpha  ok"));
+        assert!(summary.contains("beta   failed"));
+        assert!(summary.contains("beta error: Model 'x' not found"));
+        assert!(summary.ends_with("1 of 2 tasks succeeded"));
+    }
+}
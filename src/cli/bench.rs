@@ -0,0 +1,324 @@
+use futures::StreamExt;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+use crate::config::{ModelInfo, ModelRegistry, resolve_key};
+use crate::core::types::{
+    CompletionRequest, ContentDelta, Message, MessageDelta, StreamEvent, Usage,
+};
+use crate::core::{AgentError, LLM, Result};
+use crate::providers::factory::create_provider;
+
+pub const DEFAULT_PROMPT: &str = "Write a Rust function that returns the n-th Fibonacci number iteratively, then explain in two sentences why it runs in linear time.";
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub models: Vec<String>,
+    pub runs: usize,
+    pub prompt: String,
+    pub max_tokens: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            runs: 3,
+            prompt: DEFAULT_PROMPT.to_string(),
+            max_tokens: 256,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchSample {
+    pub time_to_first_token: Option<Duration>,
+    pub total: Duration,
+    pub output_tokens: u32,
+}
+
+impl BenchSample {
+    #[must_use]
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        let generating = self
+            .total
+            .saturating_sub(self.time_to_first_token.unwrap_or_default());
+        (self.output_tokens > 0 && !generating.is_zero())
+            .then(|| f64::from(self.output_tokens) / generating.as_secs_f64())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub model: String,
+    pub provider: String,
+    pub samples: Vec<BenchSample>,
+    pub errors: Vec<String>,
+}
+
+impl BenchResult {
+    #[must_use]
+    pub fn median_time_to_first_token(&self) -> Option<Duration> {
+        median(
+            self.samples
+                .iter()
+                .filter_map(|s| s.time_to_first_token)
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub fn median_total(&self) -> Option<Duration> {
+        median(self.samples.iter().map(|s| s.total).collect())
+    }
+
+    #[must_use]
+    pub fn mean_tokens_per_second(&self) -> Option<f64> {
+        let (sum, count) = self
+            .samples
+            .iter()
+            .filter_map(BenchSample::tokens_per_second)
+            .fold((0.0, 0_u32), |(sum, count), rate| (sum + rate, count + 1));
+        (count > 0).then(|| sum / f64::from(count))
+    }
+}
+
+fn median(mut values: Vec<Duration>) -> Option<Duration> {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    match values.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((values[mid - 1] + values[mid]) / 2),
+        _ => Some(values[mid]),
+    }
+}
+
+#[must_use]
+pub fn has_credentials(model: &ModelInfo) -> bool {
+    model
+        .api_key_env()
+        .is_none_or(|env| resolve_key(env).is_some())
+}
+
+pub fn select_models<'a>(
+    registry: &'a ModelRegistry,
+    requested: &[String],
+) -> Result<Vec<&'a ModelInfo>> {
+    if requested.is_empty() {
+        return Ok(registry
+            .all_models()
+            .iter()
+            .filter(|model| has_credentials(model))
+            .collect());
+    }
+    requested
+        .iter()
+        .map(|id| {
+            registry
+                .get_model(id)
+                .ok_or_else(|| AgentError::Config(format!("Model '{id}' not found in registry")))
+        })
+        .collect()
+}
+
+pub async fn measure(llm: &dyn LLM, prompt: &str, max_tokens: u32) -> Result<BenchSample> {
+    let request = CompletionRequest::new(vec![Message::user(prompt)])
+        .with_max_tokens(max_tokens)
+        .with_temperature(0.0);
+
+    let started = Instant::now();
+    let mut stream = llm.stream(request).await?;
+    let mut time_to_first_token = None;
+    let mut usage = Usage::default();
+    let mut output_chars = 0;
+
+    while let Some(event) = stream.next().await {
+        match event? {
+            StreamEvent::ContentBlockDelta { delta, .. } => {
+                let chunk = match &delta {
+                    ContentDelta::TextDelta { text } => text.len(),
+                    ContentDelta::ThinkingDelta { thinking } => thinking.len(),
+                    ContentDelta::InputJsonDelta { partial_json } => partial_json.len(),
+                    ContentDelta::SignatureDelta { .. } => 0,
+                };
+                if chunk > 0 {
+                    time_to_first_token.get_or_insert_with(|| started.elapsed());
+                    output_chars += chunk;
+                }
+            }
+            StreamEvent::MessageStart { usage: Some(u), .. }
+            | StreamEvent::MessageDelta {
+                delta: MessageDelta { usage: Some(u), .. },
+            } => usage.add(&u),
+            StreamEvent::MessageStop => break,
+            _ => {}
+        }
+    }
+
+    let output_tokens = if usage.output_tokens > 0 {
+        usage.output_tokens
+    } else {
+        u32::try_from(output_chars.div_ceil(4)).unwrap_or(u32::MAX)
+    };
+    Ok(BenchSample {
+        time_to_first_token,
+        total: started.elapsed(),
+        output_tokens,
+    })
+}
+
+pub async fn bench_model(model: &ModelInfo, options: &BenchOptions) -> BenchResult {
+    let mut result = BenchResult {
+        model: model.id.clone(),
+        provider: model.provider.display_name().to_string(),
+        samples: Vec::new(),
+        errors: Vec::new(),
+    };
+    let llm = match create_provider(model) {
+        Ok(llm) => llm,
+        Err(e) => {
+            result.errors.push(e.to_string());
+            return result;
+        }
+    };
+    for _ in 0..options.runs.max(1) {
+        match measure(llm.as_ref(), &options.prompt, options.max_tokens).await {
+            Ok(sample) => result.samples.push(sample),
+            Err(e) => result.errors.push(e.to_string()),
+        }
+    }
+    result
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), |d| format!("{}ms", d.as_millis()))
+}
+
+#[must_use]
+pub fn format_table(results: &[BenchResult]) -> String {
+    let mut sorted: Vec<&BenchResult> = results.iter().collect();
+    sorted.sort_by_key(|r| r.median_total().unwrap_or(Duration::MAX));
+
+    let rows: Vec<[String; 6]> = sorted
+        .iter()
+        .map(|r| {
+            [
+                r.model.clone(),
+                r.provider.clone(),
+                format_duration(r.median_time_to_first_token()),
+                r.mean_tokens_per_second()
+                    .map_or_else(|| "-".to_string(), |rate| format!("{rate:.1}")),
+                format_duration(r.median_total()),
+                format!("{}/{}", r.samples.len(), r.samples.len() + r.errors.len()),
+            ]
+        })
+        .collect();
+    let header = ["Model", "Provider", "TTFT p50", "Tok/s", "Total p50", "OK"].map(String::from);
+
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                if i < 2 {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{}", line.join("  ").trim_end());
+    }
+
+    for r in sorted.iter().filter(|r| !r.errors.is_empty()) {
+        let _ = writeln!(out, "\n{} errors:", r.model);
+        for error in &r.errors {
+            let _ = writeln!(out, "  - {error}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+
+    fn sample(ttft_ms: u64, total_ms: u64, tokens: u32) -> BenchSample {
+        BenchSample {
+            time_to_first_token: Some(Duration::from_millis(ttft_ms)),
+            total: Duration::from_millis(total_ms),
+            output_tokens: tokens,
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let result = BenchResult {
+            model: "fast".to_string(),
+            provider: "Mock".to_string(),
+            samples: vec![
+                sample(100, 1100, 100),
+                sample(300, 1300, 50),
+                sample(200, 1200, 100),
+            ],
+            errors: Vec::new(),
+        };
+        assert_eq!(
+            result.median_time_to_first_token(),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(result.median_total(), Some(Duration::from_millis(1200)));
+        let rate = result.mean_tokens_per_second().unwrap();
+        assert!((rate - 250.0 / 3.0).abs() < 1e-9);
+        assert_eq!(median(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_table_sorted_by_latency() {
+        let slow = BenchResult {
+            model: "slow-model".to_string(),
+            provider: "OpenAI".to_string(),
+            samples: vec![sample(900, 3000, 100)],
+            errors: vec!["timeout".to_string()],
+        };
+        let fast = BenchResult {
+            model: "fast-model".to_string(),
+            provider: "Anthropic".to_string(),
+            samples: vec![sample(200, 1200, 100)],
+            errors: Vec::new(),
+        };
+        let table = format_table(&[slow, fast]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines[0].starts_with("Model"));
+        assert!(lines[1].starts_with("fast-model"));
+        assert!(lines[1].ends_with("1/1"));
+        assert!(lines[2].starts_with("slow-model"));
+        assert!(lines[2].ends_with("1/2"));
+        assert!(table.contains("slow-model errors:\n  - timeout"));
+    }
+
+    #[tokio::test]
+    async fn test_measure_streamed_reply() {
+        let llm = ScenarioLLM::new(Scenario::new(vec![ScenarioTurn {
+            delay_ms: 20,
+            ..ScenarioTurn::text("fn fib(n: u64) -> u64 { todo!() }")
+        }]));
+        let sample = measure(&llm, DEFAULT_PROMPT, 64).await.unwrap();
+
+        assert!(sample.time_to_first_token.unwrap() >= Duration::from_millis(20));
+        assert!(sample.total >= sample.time_to_first_token.unwrap());
+        assert_eq!(sample.output_tokens, 9);
+        assert_eq!(llm.request_history()[0].max_tokens, 64);
+    }
+}
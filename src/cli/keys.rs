@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io::{IsTerminal, Write};
+
+use crate::config::{
+    CredentialStore, KeySource, ModelInfo, ModelRegistry, ProviderType, mask_key, resolve_key,
+};
+use crate::core::types::{CompletionRequest, Message};
+use crate::core::{AgentError, Result};
+use crate::providers::factory::create_provider;
+
+#[derive(Debug, Clone)]
+pub struct KeyStatus {
+    pub provider: ProviderType,
+    pub env: String,
+    pub source: Option<KeySource>,
+    pub preview: Option<String>,
+    pub validated: Option<DateTime<Utc>>,
+}
+
+#[must_use]
+pub fn key_statuses(registry: &ModelRegistry, store: Option<&CredentialStore>) -> Vec<KeyStatus> {
+    let mut statuses: Vec<KeyStatus> = Vec::new();
+    for model in registry.all_models() {
+        let Some(env) = model.api_key_env() else {
+            continue;
+        };
+        if statuses
+            .iter()
+            .any(|status| status.provider == model.provider && status.env == env)
+        {
+            continue;
+        }
+        let resolved = resolve_key(env);
+        statuses.push(KeyStatus {
+            provider: model.provider.clone(),
+            env: env.to_string(),
+            source: resolved.as_ref().map(|(_, source)| *source),
+            preview: resolved.map(|(key, _)| mask_key(&key)),
+            validated: store.and_then(|store| store.validated(env)),
+        });
+    }
+    statuses
+}
+
+#[must_use]
+pub fn format_key_table(statuses: &[KeyStatus]) -> String {
+    let rows: Vec<[String; 5]> = statuses
+        .iter()
+        .map(|status| {
+            [
+                status.provider.id().to_string(),
+                status.env.clone(),
+                status
+                    .source
+                    .map_or_else(|| "not set".to_string(), |source| source.to_string()),
+                status.preview.clone().unwrap_or_else(|| "-".to_string()),
+                status.validated.map_or_else(
+                    || "never".to_string(),
+                    |at| at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                ),
+            ]
+        })
+        .collect();
+    let header = ["Provider", "Variable", "Source", "Key", "Last validated"].map(String::from);
+
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+pub fn provider_model<'a>(
+    registry: &'a ModelRegistry,
+    provider: &str,
+) -> Result<(&'a ModelInfo, &'a str)> {
+    registry
+        .all_models()
+        .iter()
+        .filter(|model| model.provider.id() == provider)
+        .find_map(|model| Some((model, model.api_key_env()?)))
+        .ok_or_else(|| {
+            AgentError::Config(format!(
+                "No configured provider '{provider}' takes an API key. Run `smith config keys` to see them."
+            ))
+        })
+}
+
+pub async fn check_key(model: &ModelInfo) -> Result<()> {
+    let llm = create_provider(model)?;
+    let request = CompletionRequest::new(vec![Message::user("ping")]).with_max_tokens(1);
+    llm.complete(request).await.map(|_| ())
+}
+
+pub fn read_key(env: &str) -> Result<String> {
+    let mut key = String::new();
+    if std::io::stdin().is_terminal() {
+        eprint!("{env}: ");
+        std::io::stderr().flush()?;
+        terminal::enable_raw_mode()?;
+        let read = read_hidden(&mut key);
+        terminal::disable_raw_mode()?;
+        eprintln!();
+        read?;
+    } else {
+        std::io::stdin().read_line(&mut key)?;
+    }
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err(AgentError::Config(format!("No key given for {env}")));
+    }
+    Ok(key)
+}
+
+fn read_hidden(key: &mut String) -> Result<()> {
+    loop {
+        let Event::Key(event) = event::read()? else {
+            continue;
+        };
+        if event.kind != KeyEventKind::Press {
+            continue;
+        }
+        match event.code {
+            KeyCode::Enter => return Ok(()),
+            KeyCode::Esc => key.clear(),
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(AgentError::InvalidState("Cancelled".to_string()));
+            }
+            KeyCode::Char(c) => key.push(c),
+            KeyCode::Backspace => {
+                key.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_key_table() {
+        let statuses = [
+            KeyStatus {
+                provider: ProviderType::Anthropic,
+                env: "ANTHROPIC_API_KEY".to_string(),
+                source: Some(KeySource::CredentialsFile),
+                preview: Some(mask_key("sk-ant-0123456789abcdef")),
+                validated: DateTime::parse_from_rfc3339("2026-10-01T09:30:00Z")
+                    .ok()
+                    .map(|at| at.with_timezone(&Utc)),
+            },
+            KeyStatus {
+                provider: ProviderType::Groq,
+                env: "GROQ_API_KEY".to_string(),
+                source: None,
+                preview: None,
+                validated: None,
+            },
+        ];
+        let table = format_key_table(&statuses);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("Provider   Variable"));
+        assert_eq!(
+            lines[1],
+            "anthropic  ANTHROPIC_API_KEY  credentials file  sk-a\u{2026}cdef  2026-10-01 09:30 UTC"
+        );
+        assert_eq!(
+            lines[2],
+            "groq       GROQ_API_KEY       not set           -          never"
+        );
+    }
+}
@@ -1,7 +1,11 @@
 mod agent;
 mod args;
+pub mod batch;
+pub mod bench;
+pub mod keys;
 mod provider;
+pub mod worktree;
 
 pub use agent::create_agent;
-pub use args::{Cli, Commands, ConfigSubcommands};
+pub use args::{Cli, Commands, ConfigSubcommands, KeysSubcommands};
 pub use provider::create_provider_for_cli;
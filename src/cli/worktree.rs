@@ -0,0 +1,66 @@
+use std::io::{IsTerminal, Write};
+
+use crate::core::{AgentError, Result};
+use crate::git::{Git, Worktree};
+
+pub fn enter() -> Result<Worktree> {
+    let worktree = Git::new(std::env::current_dir()?)
+        .create_worktree()
+        .map_err(|e| AgentError::Config(format!("--worktree needs a git repository: {e}")))?;
+    std::env::set_current_dir(worktree.working_dir())?;
+    eprintln!(
+        "Working in {} on branch {}",
+        worktree.dir().display(),
+        worktree.branch()
+    );
+    Ok(worktree)
+}
+
+pub fn finish(worktree: &Worktree) -> Result<()> {
+    std::env::set_current_dir(worktree.root())?;
+    let failed = |e| AgentError::InvalidState(format!("Worktree {}: {e}", worktree.branch()));
+    worktree.commit_pending().map_err(failed)?;
+    let summary = worktree.summary().map_err(failed)?;
+    if summary.is_empty() {
+        worktree.remove(true).map_err(failed)?;
+        eprintln!("No changes in the worktree; removed it");
+        return Ok(());
+    }
+
+    eprintln!("Changes on {}:\n{summary}", worktree.branch());
+    match choose()?.as_str() {
+        "m" | "merge" => {
+            let head = worktree.merge().map_err(failed)?;
+            worktree.remove(true).map_err(failed)?;
+            eprintln!("Merged {}: {head}", worktree.branch());
+        }
+        "p" | "pr" => {
+            let url = worktree.open_pr().map_err(failed)?;
+            worktree.remove(false).map_err(failed)?;
+            eprintln!("Opened {url}");
+        }
+        "d" | "discard" => {
+            worktree.remove(true).map_err(failed)?;
+            eprintln!("Discarded {}", worktree.branch());
+        }
+        _ => {
+            worktree.remove(false).map_err(failed)?;
+            eprintln!(
+                "Kept branch {}; git merge {0} brings it in later",
+                worktree.branch()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn choose() -> Result<String> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(String::new());
+    }
+    eprint!("[m]erge, open a [p]ull request, [k]eep the branch or [d]iscard? [k] ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_lowercase())
+}
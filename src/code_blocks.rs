@@ -0,0 +1,183 @@
+use std::fmt::Write as _;
+
+const PATH_LABELS: &[&str] = &["file:", "filename:", "path:"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub path: String,
+    pub language: Option<String>,
+    pub content: String,
+}
+
+struct Fence<'a> {
+    info: &'a str,
+    previous: &'a str,
+    content: String,
+}
+
+#[must_use]
+pub fn extract_code_blocks(text: &str) -> Vec<CodeBlock> {
+    fences(text)
+        .into_iter()
+        .filter_map(|fence| {
+            let (language, hinted) = parse_info(fence.info);
+            let path = hinted.or_else(|| path_from_line(fence.previous))?;
+            Some(CodeBlock {
+                path,
+                language,
+                content: fence.content,
+            })
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn last_code_block(text: &str) -> Option<String> {
+    fences(text).pop().map(|fence| fence.content)
+}
+
+fn fences(text: &str) -> Vec<Fence<'_>> {
+    let mut fences = Vec::new();
+    let mut previous = "";
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let Some((fence, info)) = opening_fence(line) else {
+            if !line.trim().is_empty() {
+                previous = line;
+            }
+            continue;
+        };
+        let mut content = String::new();
+        for line in lines.by_ref() {
+            if is_closing_fence(line, fence) {
+                break;
+            }
+            content.push_str(line);
+            content.push('\n');
+        }
+        fences.push(Fence {
+            info,
+            previous,
+            content,
+        });
+        previous = "";
+    }
+    fences
+}
+
+#[must_use]
+pub fn format_code_blocks(blocks: &[CodeBlock]) -> String {
+    let mut output = format!("{} code block(s) with a file path:", blocks.len());
+    for (index, block) in blocks.iter().enumerate() {
+        let _ = write!(
+            output,
+            "\n{:>3}. {} ({} lines)",
+            index + 1,
+            block.path,
+            block.content.lines().count()
+        );
+    }
+    output.push_str("\n\n/apply <n> writes one, /apply all writes every block");
+    output
+}
+
+fn opening_fence(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    if length < 3 {
+        return None;
+    }
+    let (fence, info) = trimmed.split_at(length);
+    (marker == '~' || !info.contains('`')).then_some((fence, info.trim()))
+}
+
+fn is_closing_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with(fence) && trimmed.chars().all(|c| fence.starts_with(c))
+}
+
+fn parse_info(info: &str) -> (Option<String>, Option<String>) {
+    let mut language = None;
+    let mut path = None;
+    for token in info.split_whitespace() {
+        let token = token
+            .split_once('=')
+            .map_or(token, |(_, value)| value.trim_matches('"'));
+        let (head, tail) = token.split_once(':').unwrap_or((token, ""));
+        if !head.contains(['/', '.']) && is_path(tail) {
+            language.get_or_insert_with(|| head.to_string());
+            path.get_or_insert_with(|| tail.to_string());
+        } else if is_path(token) {
+            path.get_or_insert_with(|| token.to_string());
+        } else if language.is_none() && path.is_none() {
+            language = Some(token.to_string());
+        }
+    }
+    (language, path)
+}
+
+fn path_from_line(line: &str) -> Option<String> {
+    let mut text = line.trim().trim_start_matches('#').trim();
+    text = text.trim_end_matches(':').trim();
+    text = text.trim_matches(|c| matches!(c, '*' | '_' | '`')).trim();
+    for label in PATH_LABELS {
+        if let Some(head) = text.get(..label.len())
+            && head.eq_ignore_ascii_case(label)
+        {
+            text = text[label.len()..].trim();
+        }
+    }
+    text = text.trim_matches(|c| matches!(c, '*' | '_' | '`')).trim();
+    (!text.contains(char::is_whitespace) && is_path(text)).then(|| text.to_string())
+}
+
+fn is_path(token: &str) -> bool {
+    if token.is_empty() || token.contains("://") || token.contains(char::is_whitespace) {
+        return false;
+    }
+    let name = token.rsplit('/').next().unwrap_or(token);
+    let has_extension = name.rsplit_once('.').is_some_and(|(stem, ext)| {
+        !stem.is_empty() && ext.len() <= 10 && ext.starts_with(char::is_alphabetic)
+    });
+    has_extension
+        || (token.contains('/')
+            && !name.is_empty()
+            && name.chars().next().is_some_and(char::is_alphanumeric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_code_blocks_with_path_hints() {
+        let text = "Here is the fix.\n\n```rust src/main.rs\nfn main() {}\n```\n\n**`Cargo.toml`**:\n```toml\n[package]\nname = \"demo\"\n```\n\n```python:scripts/run.py\nprint(1)\n```\n\nRun it:\n```sh\ncargo run\n```\n\n````markdown\n```\nnested\n```\n````";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(
+            blocks,
+            vec![
+                CodeBlock {
+                    path: "src/main.rs".to_string(),
+                    language: Some("rust".to_string()),
+                    content: "fn main() {}\n".to_string(),
+                },
+                CodeBlock {
+                    path: "Cargo.toml".to_string(),
+                    language: Some("toml".to_string()),
+                    content: "[package]\nname = \"demo\"\n".to_string(),
+                },
+                CodeBlock {
+                    path: "scripts/run.py".to_string(),
+                    language: Some("python".to_string()),
+                    content: "print(1)\n".to_string(),
+                },
+            ]
+        );
+        assert!(format_code_blocks(&blocks).contains("  2. Cargo.toml (2 lines)"));
+        assert_eq!(last_code_block(text).as_deref(), Some("```\nnested\n```\n"));
+    }
+}
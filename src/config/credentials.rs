@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::get_config_dir;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialsFile {
+    #[serde(default)]
+    keys: BTreeMap<String, String>,
+    #[serde(default)]
+    validated: BTreeMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    Env,
+    CredentialsFile,
+}
+
+impl fmt::Display for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Env => "env",
+            Self::CredentialsFile => "credentials file",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CredentialStore {
+    path: PathBuf,
+}
+
+impl CredentialStore {
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        get_config_dir().map(|dir| Self::at(dir.join("credentials.toml")))
+    }
+
+    #[must_use]
+    pub const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn load(&self) -> CredentialsFile {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, file: &CredentialsFile) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string(file).map_err(io::Error::other)?;
+        std::fs::write(&self.path, content)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn key(&self, env: &str) -> Option<String> {
+        self.load()
+            .keys
+            .remove(env)
+            .filter(|key| !key.trim().is_empty())
+    }
+
+    pub fn set(&self, env: &str, key: &str) -> io::Result<()> {
+        let mut file = self.load();
+        file.keys.insert(env.to_string(), key.trim().to_string());
+        file.validated.remove(env);
+        self.save(&file)
+    }
+
+    pub fn remove(&self, env: &str) -> io::Result<bool> {
+        let mut file = self.load();
+        let removed = file.keys.remove(env).is_some();
+        file.validated.remove(env);
+        if removed {
+            self.save(&file)?;
+        }
+        Ok(removed)
+    }
+
+    #[must_use]
+    pub fn validated(&self, env: &str) -> Option<DateTime<Utc>> {
+        self.load().validated.get(env).copied()
+    }
+
+    pub fn mark_validated(&self, env: &str, at: DateTime<Utc>) -> io::Result<()> {
+        let mut file = self.load();
+        file.validated.insert(env.to_string(), at);
+        self.save(&file)
+    }
+}
+
+#[must_use]
+pub fn resolve_key(env: &str) -> Option<(String, KeySource)> {
+    if let Ok(key) = std::env::var(env)
+        && !key.trim().is_empty()
+    {
+        return Some((key, KeySource::Env));
+    }
+    CredentialStore::open()?
+        .key(env)
+        .map(|key| (key, KeySource::CredentialsFile))
+}
+
+#[must_use]
+pub fn mask_key(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len().min(8));
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}\u{2026}{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_keys_and_validation() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = CredentialStore::at(temp.path().join("smith/credentials.toml"));
+        assert_eq!(store.key("GROQ_API_KEY"), None);
+
+        store
+            .set("GROQ_API_KEY", " gsk_0123456789abcdef\n")
+            .unwrap();
+        assert_eq!(
+            store.key("GROQ_API_KEY").as_deref(),
+            Some("gsk_0123456789abcdef")
+        );
+        let now = Utc::now();
+        store.mark_validated("GROQ_API_KEY", now).unwrap();
+        assert_eq!(store.validated("GROQ_API_KEY"), Some(now));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(store.path())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert!(store.remove("GROQ_API_KEY").unwrap());
+        assert!(!store.remove("GROQ_API_KEY").unwrap());
+        assert_eq!(store.validated("GROQ_API_KEY"), None);
+
+        assert_eq!(mask_key("gsk_0123456789abcdef"), "gsk_\u{2026}cdef");
+        assert_eq!(mask_key("short"), "*****");
+    }
+}
@@ -6,6 +6,7 @@ use tracing::warn;
 #[derive(Debug, Clone)]
 pub enum ConfigEvent {
     ModelChanged { provider: String, model: String },
+    SettingsChanged(ConfigPatch),
 }
 
 pub type ConfigEventSender = mpsc::UnboundedSender<ConfigEvent>;
@@ -47,6 +48,7 @@ impl ConfigEventHandler {
                 self.persister.apply_patch(&patch)?;
                 Ok(())
             }
+            ConfigEvent::SettingsChanged(patch) => self.persister.apply_patch(patch),
         }
     }
 }
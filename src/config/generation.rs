@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::models::{ModelInfo, ProviderType};
+use super::persistence::{ConfigError, ConfigResult};
+use crate::core::augmented_llm::LoopConfig;
+use crate::core::types::{ReasoningEffort, Verbosity};
+
+pub const GENERATION_KEYS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "max_output",
+    "stop",
+    "reasoning",
+    "verbosity",
+    "thinking",
+    "thinking_budget",
+];
+
+pub const DEFAULT_THINKING_BUDGET: u32 = 8000;
+const MIN_THINKING_BUDGET: u32 = 1024;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenerationParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+}
+
+impl GenerationParams {
+    #[must_use]
+    pub fn merged(self, overrides: &Self) -> Self {
+        Self {
+            temperature: overrides.temperature.or(self.temperature),
+            top_p: overrides.top_p.or(self.top_p),
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            stop_sequences: overrides.stop_sequences.clone().or(self.stop_sequences),
+            reasoning_effort: overrides.reasoning_effort.or(self.reasoning_effort),
+            verbosity: overrides.verbosity.or(self.verbosity),
+            thinking: overrides.thinking.or(self.thinking),
+            thinking_budget: overrides.thinking_budget.or(self.thinking_budget),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> ConfigResult<()> {
+        let reset = matches!(value, "default" | "reset");
+        match key {
+            "temperature" => {
+                self.temperature = if reset {
+                    None
+                } else {
+                    Some(parse_in_range(key, value, 0.0, 2.0)?)
+                };
+            }
+            "top_p" => {
+                self.top_p = if reset {
+                    None
+                } else {
+                    Some(parse_in_range(key, value, 0.0, 1.0)?)
+                };
+            }
+            "max_tokens" | "max_output" => {
+                self.max_tokens = if reset {
+                    None
+                } else {
+                    match value.parse::<u32>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(invalid(key, "expected a positive integer")),
+                    }
+                };
+            }
+            "stop" => {
+                self.stop_sequences = if reset {
+                    None
+                } else {
+                    Some(
+                        value
+                            .split(',')
+                            .filter(|s| !s.is_empty() && *s != "none")
+                            .map(String::from)
+                            .collect(),
+                    )
+                };
+            }
+            "reasoning" | "reasoning_effort" => {
+                self.reasoning_effort = if reset {
+                    None
+                } else {
+                    Some(parse_choice(
+                        key,
+                        value,
+                        &ReasoningEffort::ALL,
+                        ReasoningEffort::as_str,
+                    )?)
+                };
+            }
+            "verbosity" => {
+                self.verbosity = if reset {
+                    None
+                } else {
+                    Some(parse_choice(
+                        key,
+                        value,
+                        &Verbosity::ALL,
+                        Verbosity::as_str,
+                    )?)
+                };
+            }
+            "thinking" => {
+                self.thinking = match value {
+                    _ if reset => None,
+                    "on" | "true" => Some(true),
+                    "off" | "false" => Some(false),
+                    _ => return Err(invalid(key, "expected on or off")),
+                };
+            }
+            "thinking_budget" => {
+                self.thinking_budget = if reset {
+                    None
+                } else {
+                    match value.parse::<u32>() {
+                        Ok(n) if n >= MIN_THINKING_BUDGET => Some(n),
+                        _ => {
+                            return Err(invalid(
+                                key,
+                                &format!("expected at least {MIN_THINKING_BUDGET} tokens"),
+                            ));
+                        }
+                    }
+                };
+            }
+            _ => return Err(ConfigError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+
+    pub fn validate_max_tokens(&self, limit: u32) -> ConfigResult<()> {
+        match self.max_tokens {
+            Some(max_tokens) if max_tokens > limit => Err(invalid(
+                "max_output",
+                &format!("{max_tokens} exceeds the model limit of {limit} tokens"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn thinking_budget_tokens(&self) -> Option<u32> {
+        self.thinking
+            .unwrap_or_else(|| self.thinking_budget.is_some())
+            .then(|| self.thinking_budget.unwrap_or(DEFAULT_THINKING_BUDGET))
+    }
+
+    #[must_use]
+    pub fn effective(config: &LoopConfig) -> Self {
+        Self {
+            temperature: Some(config.temperature),
+            top_p: config.top_p,
+            max_tokens: Some(config.max_tokens),
+            stop_sequences: Some(config.stop_sequences.clone()),
+            reasoning_effort: config.reasoning_effort,
+            verbosity: config.verbosity,
+            thinking: Some(config.thinking_budget.is_some()),
+            thinking_budget: config.thinking_budget,
+        }
+    }
+
+    pub fn apply_to(&self, config: &mut LoopConfig) {
+        let defaults = LoopConfig::default();
+        config.temperature = self.temperature.unwrap_or(defaults.temperature);
+        config.top_p = self.top_p.or(defaults.top_p);
+        config.max_tokens = self.max_tokens.unwrap_or(defaults.max_tokens);
+        config.stop_sequences = self
+            .stop_sequences
+            .clone()
+            .unwrap_or(defaults.stop_sequences);
+        config.reasoning_effort = self.reasoning_effort;
+        config.verbosity = self.verbosity;
+        config.thinking_budget = self.thinking_budget_tokens();
+    }
+}
+
+impl fmt::Display for GenerationParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn show<T: fmt::Display>(value: Option<T>) -> String {
+            value.map_or_else(|| "default".to_string(), |v| v.to_string())
+        }
+        let stop = match self.stop_sequences.as_deref() {
+            None => "default".to_string(),
+            Some([]) => "none".to_string(),
+            Some(sequences) => format!("{sequences:?}"),
+        };
+        let thinking = match self.thinking_budget_tokens() {
+            Some(budget) => format!("{budget} tokens"),
+            None if self.thinking.is_some() => "off".to_string(),
+            None => "default".to_string(),
+        };
+        write!(
+            f,
+            "temperature = {}, top_p = {}, max_output = {}, stop = {stop}, reasoning = {}, verbosity = {}, thinking = {thinking}",
+            show(self.temperature),
+            show(self.top_p),
+            show(self.max_tokens),
+            show(self.reasoning_effort),
+            show(self.verbosity)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerationConfig {
+    #[serde(flatten)]
+    pub defaults: GenerationParams,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub models: HashMap<String, GenerationParams>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub provider_limits: HashMap<ProviderType, u32>,
+}
+
+impl GenerationConfig {
+    #[must_use]
+    pub fn for_model(&self, model_id: &str) -> GenerationParams {
+        self.models.get(model_id).map_or_else(
+            || self.defaults.clone(),
+            |overrides| self.defaults.clone().merged(overrides),
+        )
+    }
+
+    #[must_use]
+    pub fn max_output_limit(&self, model: &ModelInfo) -> u32 {
+        let model_limit = u32::try_from(model.max_tokens).unwrap_or(u32::MAX);
+        self.provider_limits
+            .get(&model.provider)
+            .map_or(model_limit, |&ceiling| ceiling.min(model_limit))
+    }
+}
+
+fn invalid(key: &str, reason: &str) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: key.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn parse_in_range(key: &str, value: &str, min: f32, max: f32) -> ConfigResult<f32> {
+    match value.parse::<f32>() {
+        Ok(v) if (min..=max).contains(&v) => Ok(v),
+        _ => Err(invalid(
+            key,
+            &format!("expected a number between {min} and {max}"),
+        )),
+    }
+}
+
+fn parse_choice<T: Copy>(
+    key: &str,
+    value: &str,
+    choices: &[T],
+    name: impl Fn(T) -> &'static str,
+) -> ConfigResult<T> {
+    choices
+        .iter()
+        .copied()
+        .find(|&choice| name(choice).eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            let names: Vec<&str> = choices.iter().map(|&choice| name(choice)).collect();
+            invalid(key, &format!("expected one of {}", names.join(", ")))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_model_override() {
+        let config: GenerationConfig = toml::from_str(
+            "temperature = 0.7\nmax_tokens = 2048\n[models.\"gpt-5.2\"]\ntemperature = 0.2",
+        )
+        .unwrap();
+
+        let params = config.for_model("gpt-5.2");
+        assert_eq!(params.temperature, Some(0.2));
+        assert_eq!(params.max_tokens, Some(2048));
+        assert_eq!(config.for_model("other").temperature, Some(0.7));
+    }
+
+    #[test]
+    fn test_set_validates_values() {
+        let mut params = GenerationParams::default();
+        params.set("temperature", "0.2").unwrap();
+        params.set("max_tokens", "1000").unwrap();
+        assert_eq!(params.temperature, Some(0.2));
+        assert_eq!(params.max_tokens, Some(1000));
+
+        assert!(params.set("temperature", "3").is_err());
+        assert!(params.set("top_p", "abc").is_err());
+        assert!(params.set("max_tokens", "0").is_err());
+        assert!(matches!(
+            params.set("seed", "1"),
+            Err(ConfigError::UnknownKey(_))
+        ));
+
+        params.set("temperature", "default").unwrap();
+        assert_eq!(params.temperature, None);
+
+        params.set("reasoning", "high").unwrap();
+        params.set("verbosity", "Low").unwrap();
+        assert_eq!(params.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(params.verbosity, Some(Verbosity::Low));
+        assert!(params.set("reasoning", "extreme").is_err());
+    }
+
+    #[test]
+    fn test_apply_to_loop_config() {
+        let mut config = LoopConfig::default();
+        let params = GenerationParams {
+            temperature: Some(0.1),
+            top_p: Some(0.9),
+            max_tokens: None,
+            stop_sequences: Some(vec!["END".to_string()]),
+            reasoning_effort: Some(ReasoningEffort::High),
+            verbosity: None,
+            thinking: None,
+            thinking_budget: Some(16000),
+        };
+
+        params.apply_to(&mut config);
+
+        assert!((config.temperature - 0.1).abs() < f32::EPSILON);
+        assert_eq!(config.top_p, Some(0.9));
+        assert_eq!(config.max_tokens, LoopConfig::default().max_tokens);
+        assert_eq!(config.stop_sequences, vec!["END".to_string()]);
+        assert_eq!(config.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(config.thinking_budget, Some(16000));
+    }
+
+    #[test]
+    fn test_max_output_and_stop() {
+        let mut params = GenerationParams::default();
+        params.set("max_output", "8000").unwrap();
+        params.set("stop", "</done>,###").unwrap();
+
+        assert_eq!(params.max_tokens, Some(8000));
+        assert_eq!(
+            params.stop_sequences,
+            Some(vec!["</done>".to_string(), "###".to_string()])
+        );
+        assert!(params.validate_max_tokens(16000).is_ok());
+        assert!(params.validate_max_tokens(4096).is_err());
+
+        params.set("stop", "none").unwrap();
+        assert_eq!(params.stop_sequences, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_provider_limit_caps_model_limit() {
+        let config: GenerationConfig =
+            toml::from_str("[provider_limits]\nanthropic = 16000").unwrap();
+        let registry = crate::config::ModelRegistry::load_default();
+        let sonnet = registry.get_model("claude-sonnet-4-5").unwrap();
+        let gpt = registry.get_model("gpt-5.2").unwrap();
+
+        assert_eq!(config.max_output_limit(sonnet), 16000);
+        assert_eq!(
+            config.max_output_limit(gpt),
+            u32::try_from(gpt.max_tokens).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_thinking_toggle_and_budget() {
+        let config: GenerationConfig =
+            toml::from_str("[models.\"claude-opus-4-5\"]\nthinking_budget = 16000").unwrap();
+        let mut params = config.for_model("claude-opus-4-5");
+        assert_eq!(params.thinking_budget_tokens(), Some(16000));
+        assert_eq!(config.for_model("other").thinking_budget_tokens(), None);
+
+        params.set("thinking", "off").unwrap();
+        assert_eq!(params.thinking_budget_tokens(), None);
+
+        let mut session = GenerationParams::default();
+        session.set("thinking", "on").unwrap();
+        assert_eq!(
+            session.thinking_budget_tokens(),
+            Some(DEFAULT_THINKING_BUDGET)
+        );
+        assert!(session.set("thinking_budget", "100").is_err());
+        assert!(session.set("thinking", "maybe").is_err());
+    }
+}
@@ -1,15 +1,36 @@
+pub mod credentials;
 pub mod event_handler;
+pub mod generation;
 pub mod models;
 pub mod persistence;
+pub mod tools;
 
 use config::{Config, Environment, File};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{fs, io};
 
+use crate::audit::AuditLog;
+use crate::memories::MemoryStore;
+use crate::permission::PermissionMode;
+use crate::providers::http::RateLimit;
+use crate::providers::openai_compat::OpenRouterConfig;
+use crate::session::SessionStore;
+use crate::ui::theme::ThemeName;
+
+pub use credentials::{CredentialStore, KeySource, mask_key, resolve_key};
 pub use event_handler::{ConfigEvent, ConfigEventHandler, ConfigEventSender};
+pub use generation::{GENERATION_KEYS, GenerationConfig, GenerationParams};
 pub use models::{ModelInfo, ModelRegistry, ProviderType};
-pub use persistence::{ConfigError, ConfigPatch, ConfigPersister, ConfigResult};
+pub use persistence::{
+    ConfigEdit, ConfigError, ConfigPatch, ConfigPersister, ConfigResult, MAX_CUSTOM_PROMPT_CHARS,
+};
+pub use tools::{
+    BackupConfig, BashConfig, FilesConfig, GitHubConfig, ProjectSandbox, SandboxBackend,
+    SandboxConfig, SandboxOverride, ScratchConfig, ScreeningAction, ScreeningConfig, Severity,
+    SqlConfig, SqlConnectionConfig, SqlDriver, SymlinkPolicy, ToolsConfig,
+};
 
 pub fn get_config_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
@@ -46,10 +67,292 @@ pub fn get_config_dir() -> Option<PathBuf> {
 pub struct AppConfig {
     pub provider: Option<String>,
     pub model: Option<String>,
+    pub execution_model: Option<String>,
     pub custom_system_prompt: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub permission_mode: PermissionMode,
+    #[serde(default)]
+    pub theme: ThemeName,
+    #[serde(default)]
+    pub model_badges: bool,
+    #[serde(default)]
+    pub generation: GenerationConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub rate_limits: HashMap<String, RateLimit>,
+    #[serde(default)]
+    pub openrouter: OpenRouterConfig,
+    #[serde(default)]
+    pub updates: UpdateConfig,
+    #[serde(default)]
+    pub input: InputConfig,
+    #[serde(default)]
+    pub snippets: HashMap<String, String>,
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    #[serde(default)]
+    pub recent_models: Vec<String>,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    #[serde(default)]
+    pub clipboard: ClipboardConfig,
+    #[serde(default)]
+    pub checkpoints: CheckpointsConfig,
+    #[serde(default)]
+    pub fix_tests: FixTestsConfig,
+    #[serde(default)]
+    pub duo: DuoConfig,
+    #[serde(default)]
+    pub agents: HashMap<String, AgentPreset>,
+    #[serde(default)]
+    pub memories: MemoriesConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+    pub filter: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: None,
+            service_name: "smith".to_string(),
+            filter: "smith=info".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionsConfig {
+    pub enabled: bool,
+    pub dir: Option<PathBuf>,
+    pub llm_titles: bool,
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            llm_titles: true,
+        }
+    }
+}
+
+impl SessionsConfig {
+    #[must_use]
+    pub fn store(&self) -> Option<SessionStore> {
+        if !self.enabled {
+            return None;
+        }
+        self.dir
+            .clone()
+            .map(SessionStore::new)
+            .or_else(SessionStore::with_default_dir)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoriesConfig {
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+    pub save_tool: bool,
+}
+
+impl Default for MemoriesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+            save_tool: true,
+        }
+    }
+}
+
+impl MemoriesConfig {
+    #[must_use]
+    pub fn store(&self) -> Option<MemoryStore> {
+        if !self.enabled {
+            return None;
+        }
+        self.path
+            .clone()
+            .map(MemoryStore::new)
+            .or_else(MemoryStore::with_default_path)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConfig {
+    pub check: bool,
+    pub interval_hours: u32,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            check: true,
+            interval_hours: 24,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputConfig {
+    pub warn_tokens: usize,
+    pub attachment_tokens: usize,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            warn_tokens: 20_000,
+            attachment_tokens: 8_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub cycle_model: String,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            cycle_model: "ctrl+p".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoCopy {
+    #[default]
+    Off,
+    Reply,
+    Code,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    pub auto_copy: AutoCopy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckpointsConfig {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixTestsConfig {
+    pub command: Option<String>,
+    pub max_rounds: usize,
+}
+
+impl Default for FixTestsConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            max_rounds: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentPreset {
+    pub description: Option<String>,
+    pub system_prompt: Option<String>,
+    pub tools: Option<Vec<String>>,
+    pub model: Option<String>,
+    pub max_iterations: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuoRole {
+    pub model: Option<String>,
+    pub system_prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuoConfig {
+    pub implementer: DuoRole,
+    pub reviewer: DuoRole,
+    pub max_rounds: usize,
+}
+
+impl Default for DuoConfig {
+    fn default() -> Self {
+        Self {
+            implementer: DuoRole::default(),
+            reviewer: DuoRole::default(),
+            max_rounds: 3,
+        }
+    }
+}
+
+impl AuditConfig {
+    #[must_use]
+    pub fn log(&self) -> Option<AuditLog> {
+        if !self.enabled {
+            return None;
+        }
+        self.path
+            .clone()
+            .map(AuditLog::new)
+            .or_else(AuditLog::with_default_path)
+    }
 }
 
 impl AppConfig {
+    #[must_use]
+    pub fn resolve_model(&self, registry: &ModelRegistry, name: &str) -> String {
+        registry
+            .resolve(name, &self.aliases)
+            .map_or_else(|| name.to_string(), |model| model.id.clone())
+    }
+
     #[must_use]
     pub fn load() -> Self {
         let mut builder = Config::builder();
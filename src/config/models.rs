@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::providers::types::ExtraBody;
+
 const DEFAULT_MODELS_TOML: &str = include_str!("models.toml");
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -71,6 +73,23 @@ impl ProviderType {
         )
     }
 
+    #[must_use]
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::Anthropic => "anthropic",
+            Self::OpenAI => "openai",
+            Self::Gemini => "gemini",
+            Self::OpenRouter => "openrouter",
+            Self::Together => "together",
+            Self::Groq => "groq",
+            Self::Fireworks => "fireworks",
+            Self::Ollama => "ollama",
+            Self::Vllm => "vllm",
+            Self::Azure => "azure",
+            Self::Custom => "custom",
+        }
+    }
+
     #[must_use]
     pub const fn display_name(&self) -> &'static str {
         match self {
@@ -110,6 +129,8 @@ pub struct ModelProviderConfig {
     pub auth_header: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub extra_headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_body: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,6 +160,14 @@ impl ModelInfo {
             .or_else(|| self.provider.default_api_key_env())
     }
 
+    #[must_use]
+    pub fn extra_body(&self) -> ExtraBody {
+        self.config
+            .as_ref()
+            .map(|c| ExtraBody::new(c.extra_body.clone()))
+            .unwrap_or_default()
+    }
+
     #[must_use]
     pub fn base_url(&self) -> Option<&str> {
         self.config
@@ -191,6 +220,17 @@ impl ModelRegistry {
         self.by_id.get(id).map(|&idx| &self.models[idx])
     }
 
+    #[must_use]
+    pub fn resolve(&self, name: &str, aliases: &HashMap<String, String>) -> Option<&ModelInfo> {
+        let target = aliases.get(name).map_or(name, String::as_str);
+        self.get_model(target).or_else(|| {
+            let (provider, id) = target.split_once('/')?;
+            self.models
+                .iter()
+                .find(|model| model.provider.id() == provider && model.id == id)
+        })
+    }
+
     #[must_use]
     pub fn default_model(&self) -> Option<&ModelInfo> {
         self.default_model_idx.map(|idx| &self.models[idx])
@@ -269,3 +309,56 @@ impl ModelRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_aliases_and_provider_prefixes() {
+        let registry = ModelRegistry::load_default();
+        let aliases = HashMap::from([
+            (
+                "smart".to_string(),
+                "anthropic/claude-sonnet-4-5".to_string(),
+            ),
+            ("quick".to_string(), "gpt-5-mini".to_string()),
+        ]);
+
+        let id = |name: &str| registry.resolve(name, &aliases).map(|m| m.id.as_str());
+        assert_eq!(id("smart"), Some("claude-sonnet-4-5"));
+        assert_eq!(id("quick"), Some("gpt-5-mini"));
+        assert_eq!(id("claude-haiku-4-5"), Some("claude-haiku-4-5"));
+        assert_eq!(id("openai/gpt-5.2"), Some("gpt-5.2"));
+        assert_eq!(id("minimax/minimax-m2.1"), Some("minimax/minimax-m2.1"));
+        assert_eq!(id("openai/claude-sonnet-4-5"), None);
+        assert_eq!(id("fast"), None);
+    }
+
+    #[test]
+    fn test_extra_body_from_toml() {
+        let config: ModelsConfig = toml::from_str(
+            r#"
+            [[models]]
+            id = "qwen"
+            name = "Qwen"
+            provider = "openrouter"
+            max_tokens = 8000
+
+            [models.config.extra_body]
+            top_k = 20
+            provider = { order = ["deepinfra", "together"] }
+            "#,
+        )
+        .unwrap();
+
+        let body = config.models[0]
+            .extra_body()
+            .encode(&serde_json::json!({}))
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"provider":{"order":["deepinfra","together"]},"top_k":20}"#
+        );
+    }
+}
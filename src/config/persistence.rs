@@ -1,12 +1,20 @@
 use crate::config::{AppConfig, get_config_dir};
+use crate::permission::PermissionMode;
+use crate::ui::theme::ThemeName;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io;
 use std::path::PathBuf;
+use toml::{Table, Value};
 
 pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
 
+pub const MAX_CUSTOM_PROMPT_CHARS: usize = 8000;
+
+const DEFAULT_HEADER: &str =
+    "# Smith Configuration\n# This file is automatically managed by smith.";
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("IO error: {0}")]
@@ -20,12 +28,128 @@ pub enum ConfigError {
 
     #[error("Config directory not found")]
     NoConfigDir,
+
+    #[error("Unknown setting: {0}")]
+    UnknownKey(String),
+
+    #[error("Invalid value for {key}: {reason}")]
+    InvalidValue { key: String, reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ConfigEdit {
+    Set { key: String, value: Value },
+    Remove { key: String },
+    Append { key: String, value: Value },
+    RemoveItem { key: String, value: Value },
+}
+
+impl ConfigEdit {
+    #[must_use]
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Set { key, .. }
+            | Self::Remove { key }
+            | Self::Append { key, .. }
+            | Self::RemoveItem { key, .. } => key,
+        }
+    }
+
+    pub fn apply(&self, root: &mut Table) -> ConfigResult<()> {
+        let key = self.key();
+        let mut path = parse_key_path(key)?;
+        let name = path.pop().unwrap_or_default();
+        let create = matches!(self, Self::Set { .. } | Self::Append { .. });
+        let Some(table) = table_at(root, &path, key, create)? else {
+            return Ok(());
+        };
+
+        match self {
+            Self::Set { value, .. } => {
+                table.insert(name, value.clone());
+            }
+            Self::Remove { .. } => {
+                table.remove(&name);
+            }
+            Self::Append { value, .. } => {
+                let Value::Array(items) = table.entry(name).or_insert(Value::Array(Vec::new()))
+                else {
+                    return Err(invalid(key, "is not an array"));
+                };
+                if !items.contains(value) {
+                    items.push(value.clone());
+                }
+            }
+            Self::RemoveItem { value, .. } => match table.get_mut(&name) {
+                Some(Value::Array(items)) => items.retain(|item| item != value),
+                Some(_) => return Err(invalid(key, "is not an array")),
+                None => {}
+            },
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_key_path(key: &str) -> ConfigResult<Vec<String>> {
+    let malformed = || invalid(key, "is not a valid dotted key");
+    let mut segments = Vec::new();
+    let mut rest = key.trim();
+    loop {
+        let (segment, tail) = if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').ok_or_else(malformed)?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            let end = rest.find('.').unwrap_or(rest.len());
+            (rest[..end].trim(), &rest[end..])
+        };
+        if segment.is_empty() {
+            return Err(malformed());
+        }
+        segments.push(segment.to_string());
+        if tail.is_empty() {
+            return Ok(segments);
+        }
+        rest = tail.strip_prefix('.').ok_or_else(malformed)?;
+    }
+}
+
+fn table_at<'a>(
+    root: &'a mut Table,
+    path: &[String],
+    key: &str,
+    create: bool,
+) -> ConfigResult<Option<&'a mut Table>> {
+    let mut table = root;
+    for segment in path {
+        if create && !table.contains_key(segment) {
+            table.insert(segment.clone(), Value::Table(Table::new()));
+        }
+        table = match table.get_mut(segment) {
+            Some(Value::Table(inner)) => inner,
+            Some(_) => return Err(invalid(key, &format!("'{segment}' is not a table"))),
+            None => return Ok(None),
+        };
+    }
+    Ok(Some(table))
+}
+
+fn invalid(key: &str, reason: &str) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: key.to_string(),
+        reason: reason.to_string(),
+    }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ConfigPatch {
     pub provider: Option<String>,
     pub model: Option<String>,
+    pub custom_system_prompt: Option<String>,
+    pub permission_mode: Option<PermissionMode>,
+    pub theme: Option<ThemeName>,
+    #[serde(default)]
+    pub edits: Vec<ConfigEdit>,
 }
 
 impl ConfigPatch {
@@ -38,9 +162,95 @@ impl ConfigPatch {
         }
     }
 
+    #[must_use]
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.edits.push(ConfigEdit::Set {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn remove(mut self, key: impl Into<String>) -> Self {
+        self.edits.push(ConfigEdit::Remove { key: key.into() });
+        self
+    }
+
+    #[must_use]
+    pub fn append(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.edits.push(ConfigEdit::Append {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn remove_item(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.edits.push(ConfigEdit::RemoveItem {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
     #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.provider.is_none() && self.model.is_none()
+        self.provider.is_none()
+            && self.model.is_none()
+            && self.custom_system_prompt.is_none()
+            && self.permission_mode.is_none()
+            && self.theme.is_none()
+            && self.edits.is_empty()
+    }
+
+    pub fn validate(&self) -> ConfigResult<()> {
+        if let Some(prompt) = &self.custom_system_prompt
+            && prompt.chars().count() > MAX_CUSTOM_PROMPT_CHARS
+        {
+            return Err(ConfigError::InvalidValue {
+                key: "custom_system_prompt".to_string(),
+                reason: format!("must be at most {MAX_CUSTOM_PROMPT_CHARS} characters"),
+            });
+        }
+        if self.model.as_deref().is_some_and(|m| m.trim().is_empty()) {
+            return Err(ConfigError::InvalidValue {
+                key: "model".to_string(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+        for edit in &self.edits {
+            parse_key_path(edit.key())?;
+        }
+        Ok(())
+    }
+
+    pub fn apply_to(&self, table: &mut Table) -> ConfigResult<()> {
+        if let Some(provider) = &self.provider {
+            table.insert("provider".to_string(), Value::from(provider.as_str()));
+        }
+        if let Some(model) = &self.model {
+            table.insert("model".to_string(), Value::from(model.as_str()));
+        }
+        if let Some(prompt) = &self.custom_system_prompt {
+            let prompt = prompt.trim();
+            if prompt.is_empty() {
+                table.remove("custom_system_prompt");
+            } else {
+                table.insert("custom_system_prompt".to_string(), Value::from(prompt));
+            }
+        }
+        if let Some(mode) = self.permission_mode {
+            table.insert("permission_mode".to_string(), Value::try_from(mode)?);
+        }
+        if let Some(theme) = self.theme {
+            table.insert("theme".to_string(), Value::try_from(theme)?);
+        }
+        for edit in &self.edits {
+            edit.apply(table)?;
+        }
+        Ok(())
     }
 }
 
@@ -67,47 +277,38 @@ impl ConfigPersister {
         if patch.is_empty() {
             return Ok(());
         }
+        patch.validate()?;
 
         let _lock = self.write_lock.lock();
 
-        let existing = self.read_existing_config()?;
+        let content = self.read_existing_content()?;
+        let mut table: Table = toml::from_str(&content)?;
+        patch.apply_to(&mut table)?;
+        Value::Table(table.clone()).try_into::<AppConfig>()?;
 
-        let merged = Self::merge_config(existing, patch);
-
-        self.atomic_write(&merged)
+        self.atomic_write(&leading_comments(&content), &table)
     }
 
-    fn read_existing_config(&self) -> ConfigResult<AppConfig> {
+    fn read_existing_content(&self) -> ConfigResult<String> {
         if self.config_path.exists() {
-            let content = fs::read_to_string(&self.config_path)?;
-            let config: AppConfig = toml::from_str(&content)?;
-            Ok(config)
+            Ok(fs::read_to_string(&self.config_path)?)
         } else {
-            Ok(AppConfig::default())
-        }
-    }
-
-    fn merge_config(mut existing: AppConfig, patch: &ConfigPatch) -> AppConfig {
-        if let Some(ref provider) = patch.provider {
-            existing.provider = Some(provider.clone());
+            Ok(String::new())
         }
-        if let Some(ref model) = patch.model {
-            existing.model = Some(model.clone());
-        }
-        existing
     }
 
-    fn atomic_write(&self, config: &AppConfig) -> ConfigResult<()> {
+    fn atomic_write(&self, header: &str, table: &Table) -> ConfigResult<()> {
         if let Some(parent) = self.config_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        let toml_content = toml::to_string_pretty(config)?;
-        let content = format!(
-            "# Smith Configuration\n\
-             # This file is automatically managed by smith.\n\n\
-             {toml_content}"
-        );
+        let toml_content = toml::to_string_pretty(table)?;
+        let header = if header.is_empty() {
+            DEFAULT_HEADER
+        } else {
+            header
+        };
+        let content = format!("{header}\n\n{toml_content}");
 
         let temp_path = self.config_path.with_extension("toml.tmp");
         fs::write(&temp_path, &content)?;
@@ -118,6 +319,16 @@ impl ConfigPersister {
     }
 }
 
+fn leading_comments(content: &str) -> String {
+    content
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +379,150 @@ mod tests {
         assert!(content.contains("model = \"claude-opus-4-5\""));
     }
 
+    #[test]
+    fn test_persister_applies_settings() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        let persister = ConfigPersister::new(config_path.clone());
+
+        let patch = ConfigPatch {
+            custom_system_prompt: Some("Answer tersely.".to_string()),
+            permission_mode: Some(PermissionMode::AcceptEdits),
+            theme: Some(ThemeName::Light),
+            ..Default::default()
+        };
+        persister
+            .apply_patch(&patch)
+            .expect("Failed to apply patch");
+
+        let config: AppConfig = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(
+            config.custom_system_prompt.as_deref(),
+            Some("Answer tersely.")
+        );
+        assert_eq!(config.permission_mode, PermissionMode::AcceptEdits);
+        assert_eq!(config.theme, ThemeName::Light);
+
+        let clear = ConfigPatch {
+            custom_system_prompt: Some(String::new()),
+            ..Default::default()
+        };
+        persister
+            .apply_patch(&clear)
+            .expect("Failed to clear prompt");
+        let config: AppConfig = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config.custom_system_prompt, None);
+        assert_eq!(config.theme, ThemeName::Light);
+    }
+
+    #[test]
+    fn test_persister_rejects_invalid_patch() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        let persister = ConfigPersister::new(config_path.clone());
+
+        let patch = ConfigPatch {
+            custom_system_prompt: Some("x".repeat(MAX_CUSTOM_PROMPT_CHARS + 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            persister.apply_patch(&patch),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+        assert!(!config_path.exists());
+    }
+
+    #[test]
+    fn test_parse_key_path() {
+        assert_eq!(
+            parse_key_path(r#"generation.models."minimax/minimax-m2.1".temperature"#).unwrap(),
+            vec![
+                "generation",
+                "models",
+                "minimax/minimax-m2.1",
+                "temperature"
+            ]
+        );
+        assert_eq!(parse_key_path("theme").unwrap(), vec!["theme"]);
+        assert!(parse_key_path("tools..bash").is_err());
+        assert!(parse_key_path(r#"models."unterminated"#).is_err());
+        assert!(parse_key_path("").is_err());
+    }
+
+    #[test]
+    fn test_persister_nested_keys_and_arrays() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "# My settings\n# keep this\n\nmodel = \"claude-opus-4-5\"\n\n[tools.bash]\nenv_allow = [\"PATH\"]\n",
+        )
+        .unwrap();
+        let persister = ConfigPersister::new(config_path.clone());
+
+        let patch = ConfigPatch::default()
+            .set("rate_limits.anthropic.requests_per_minute", 50)
+            .set(r#"generation.models."claude-sonnet-4-5".temperature"#, 0.2)
+            .append("tools.bash.env_allow", "HOME")
+            .append("tools.bash.env_allow", "PATH")
+            .append("tools.bash.env_deny", "AWS_SECRET_ACCESS_KEY");
+        persister
+            .apply_patch(&patch)
+            .expect("Failed to apply patch");
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("# My settings\n# keep this\n\n"));
+        let config: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.model.as_deref(), Some("claude-opus-4-5"));
+        assert_eq!(config.tools.bash.env_allow, vec!["PATH", "HOME"]);
+        assert_eq!(config.tools.bash.env_deny, vec!["AWS_SECRET_ACCESS_KEY"]);
+        assert_eq!(
+            config.rate_limits["anthropic"].requests_per_minute,
+            Some(50)
+        );
+        assert_eq!(
+            config.generation.for_model("claude-sonnet-4-5").temperature,
+            Some(0.2)
+        );
+
+        let patch = ConfigPatch::default()
+            .remove_item("tools.bash.env_allow", "PATH")
+            .remove("rate_limits.anthropic")
+            .remove("tools.missing.key");
+        persister
+            .apply_patch(&patch)
+            .expect("Failed to apply patch");
+
+        let config: AppConfig = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config.tools.bash.env_allow, vec!["HOME"]);
+        assert!(config.rate_limits.is_empty());
+    }
+
+    #[test]
+    fn test_persister_rejects_mistyped_edits() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        let persister = ConfigPersister::new(config_path.clone());
+        persister
+            .apply_patch(&ConfigPatch::model("anthropic", "claude-sonnet-4"))
+            .unwrap();
+        let before = fs::read_to_string(&config_path).unwrap();
+
+        let wrong_type = ConfigPatch::default().set("tools.bash.env_allow", true);
+        assert!(persister.apply_patch(&wrong_type).is_err());
+
+        let through_scalar = ConfigPatch::default().set("model.name", "x");
+        assert!(matches!(
+            persister.apply_patch(&through_scalar),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+
+        let append_to_scalar = ConfigPatch::default().append("provider", "openai");
+        assert!(persister.apply_patch(&append_to_scalar).is_err());
+
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+    }
+
     #[test]
     fn test_persister_empty_patch_noop() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::permission::CloudPolicy;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    pub bash: BashConfig,
+    pub backups: BackupConfig,
+    pub screening: ScreeningConfig,
+    pub github: GitHubConfig,
+    pub sql: SqlConfig,
+    pub scratch: ScratchConfig,
+    pub files: FilesConfig,
+    #[serde(skip)]
+    pub allowed: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreeningAction {
+    Off,
+    #[default]
+    Wrap,
+    Strip,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkPolicy {
+    Follow,
+    #[default]
+    Workspace,
+    Refuse,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilesConfig {
+    pub symlinks: SymlinkPolicy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningConfig {
+    pub action: ScreeningAction,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubConfig {
+    pub enabled: bool,
+    pub api_url: String,
+    pub token_env: String,
+    pub repo: Option<String>,
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            api_url: "https://api.github.com".to_string(),
+            token_env: "GITHUB_TOKEN".to_string(),
+            repo: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SqlConfig {
+    pub max_rows: usize,
+    pub connections: BTreeMap<String, SqlConnectionConfig>,
+}
+
+impl Default for SqlConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: 100,
+            connections: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SqlDriver {
+    #[default]
+    Sqlite,
+    Postgres,
+    Mysql,
+}
+
+impl SqlDriver {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sqlite => "sqlite",
+            Self::Postgres => "postgres",
+            Self::Mysql => "mysql",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlConnectionConfig {
+    #[serde(default)]
+    pub driver: SqlDriver,
+    pub url: String,
+    #[serde(default = "default_read_only")]
+    pub read_only: bool,
+}
+
+const fn default_read_only() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScratchConfig {
+    pub enabled: bool,
+    pub keep: bool,
+}
+
+impl Default for ScratchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keep: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    pub retention: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: PathBuf::from(".smith/backups"),
+            retention: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BashConfig {
+    pub working_dir: Option<PathBuf>,
+    pub env_allow: Vec<String>,
+    pub env_deny: Vec<String>,
+    pub sandbox: SandboxConfig,
+    pub cloud: CloudPolicy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxBackend {
+    #[default]
+    Host,
+    Docker,
+    Podman,
+}
+
+impl SandboxBackend {
+    #[must_use]
+    pub const fn runtime(self) -> Option<&'static str> {
+        match self {
+            Self::Host => None,
+            Self::Docker => Some("docker"),
+            Self::Podman => Some("podman"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub backend: SandboxBackend,
+    pub image: String,
+    pub network: bool,
+    pub args: Vec<String>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            backend: SandboxBackend::Host,
+            image: "debian:stable-slim".to_string(),
+            network: false,
+            args: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxOverride {
+    pub config: SandboxConfig,
+    pub path: PathBuf,
+    pub fingerprint: String,
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSandbox {
+    pub config: SandboxConfig,
+    pub untrusted: Option<SandboxOverride>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectSandboxFile {
+    backend: Option<SandboxBackend>,
+    image: Option<String>,
+    network: Option<bool>,
+    args: Option<Vec<String>>,
+}
+
+impl SandboxConfig {
+    pub const PROJECT_FILE: &str = ".smith/sandbox.toml";
+
+    #[must_use]
+    pub fn for_project(&self, workspace: &Path) -> ProjectSandbox {
+        let mut project = ProjectSandbox {
+            config: self.clone(),
+            untrusted: None,
+        };
+        let path = workspace.join(Self::PROJECT_FILE);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return project;
+        };
+        let file: ProjectSandboxFile = match toml::from_str(&content) {
+            Ok(file) => file,
+            Err(e) => {
+                tracing::warn!("Ignoring invalid sandbox config {}: {e}", path.display());
+                return project;
+            }
+        };
+        let refuse = |field: &str| {
+            tracing::warn!(
+                "Ignoring `{field}` in {}: a project sandbox file may only tighten the sandbox",
+                path.display()
+            );
+        };
+
+        if file.args.is_some_and(|args| !args.is_empty()) {
+            refuse("args");
+        }
+        match file.network {
+            Some(true) if !self.network => refuse("network = true"),
+            Some(network) => project.config.network = network,
+            None => {}
+        }
+
+        let mut trusted = project.config.clone();
+        let mut changes = Vec::new();
+        match file.backend {
+            Some(SandboxBackend::Host) if self.backend != SandboxBackend::Host => {
+                refuse("backend = \"host\"");
+            }
+            Some(backend) if self.backend == SandboxBackend::Host => {
+                project.config.backend = backend;
+                trusted.backend = backend;
+            }
+            Some(backend) if backend != self.backend => {
+                trusted.backend = backend;
+                changes.push(format!("backend = {}", backend.runtime().unwrap_or("host")));
+            }
+            _ => {}
+        }
+        if let Some(image) = file
+            .image
+            .filter(|image| *image != self.image && trusted.backend != SandboxBackend::Host)
+        {
+            changes.push(format!("image = {image:?}"));
+            trusted.image = image;
+        }
+
+        if !changes.is_empty() {
+            project.untrusted = Some(SandboxOverride {
+                config: trusted,
+                path,
+                fingerprint: fingerprint(&content),
+                changes,
+            });
+        }
+        project
+    }
+}
+
+fn fingerprint(content: &str) -> String {
+    Sha256::digest(content.as_bytes()).iter().take(8).fold(
+        String::with_capacity(16),
+        |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        },
+    )
+}
+
+impl BashConfig {
+    #[must_use]
+    pub fn allows_env(&self, key: &str) -> bool {
+        let matches =
+            |pattern: &String| match (pattern.strip_suffix('*'), pattern.strip_prefix('*')) {
+                (Some(prefix), _) => key.starts_with(prefix),
+                (None, Some(suffix)) => key.ends_with(suffix),
+                (None, None) => key == pattern,
+            };
+
+        (self.env_allow.is_empty() || self.env_allow.iter().any(matches))
+            && !self.env_deny.iter().any(matches)
+    }
+
+    #[must_use]
+    pub const fn filters_env(&self) -> bool {
+        !self.env_allow.is_empty() || !self.env_deny.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_allow_and_deny_patterns() {
+        let config: ToolsConfig =
+            toml::from_str("[bash]\nenv_allow = [\"PATH\", \"CARGO_*\"]\nenv_deny = [\"*_TOKEN\"]")
+                .unwrap();
+        let bash = config.bash;
+
+        assert!(bash.allows_env("PATH"));
+        assert!(bash.allows_env("CARGO_HOME"));
+        assert!(!bash.allows_env("CARGO_REGISTRY_TOKEN"));
+        assert!(!bash.allows_env("AWS_SECRET_ACCESS_KEY"));
+    }
+
+    #[test]
+    fn test_default_inherits_everything() {
+        let bash = BashConfig::default();
+        assert!(!bash.filters_env());
+        assert!(bash.allows_env("HOME"));
+    }
+
+    fn project_with(sandbox_toml: &str) -> tempfile::TempDir {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::create_dir(project.path().join(".smith")).unwrap();
+        std::fs::write(
+            project.path().join(SandboxConfig::PROJECT_FILE),
+            sandbox_toml,
+        )
+        .unwrap();
+        project
+    }
+
+    fn podman() -> SandboxConfig {
+        let config: ToolsConfig =
+            toml::from_str("[bash.sandbox]\nbackend = \"podman\"\nimage = \"rust:1\"").unwrap();
+        config.bash.sandbox
+    }
+
+    #[test]
+    fn test_sandbox_config_and_project_override() {
+        let sandbox = podman();
+        assert_eq!(sandbox.backend.runtime(), Some("podman"));
+        assert_eq!(sandbox.image, "rust:1");
+        assert!(!sandbox.network);
+
+        let project = tempfile::tempdir().unwrap();
+        let resolved = sandbox.for_project(project.path());
+        assert_eq!(resolved.config, sandbox);
+        assert!(resolved.untrusted.is_none());
+
+        let project = project_with("backend = \"docker\"\nimage = \"node:22\"");
+        let resolved = sandbox.for_project(project.path());
+        assert_eq!(resolved.config, sandbox);
+        let untrusted = resolved.untrusted.unwrap();
+        assert_eq!(untrusted.config.backend, SandboxBackend::Docker);
+        assert_eq!(untrusted.config.image, "node:22");
+        assert_eq!(
+            untrusted.changes,
+            ["backend = docker", "image = \"node:22\""]
+        );
+        assert_eq!(untrusted.fingerprint.len(), 16);
+    }
+
+    #[test]
+    fn test_project_sandbox_may_tighten() {
+        let host = SandboxConfig {
+            network: true,
+            ..SandboxConfig::default()
+        };
+        let project = project_with("backend = \"docker\"\nnetwork = false");
+        let resolved = host.for_project(project.path());
+        assert_eq!(resolved.config.backend, SandboxBackend::Docker);
+        assert!(!resolved.config.network);
+        assert!(resolved.untrusted.is_none());
+    }
+
+    #[test]
+    fn test_project_sandbox_refuses_loosening() {
+        let sandbox = podman();
+        for refused in [
+            "backend = \"host\"",
+            "network = true",
+            "args = [\"--privileged\", \"-v\", \"/:/host\"]",
+        ] {
+            let project = project_with(refused);
+            let resolved = sandbox.for_project(project.path());
+            assert_eq!(resolved.config, sandbox, "{refused}");
+            assert!(resolved.untrusted.is_none(), "{refused}");
+        }
+
+        let project = project_with("network = true\nimage = \"node:22\"");
+        let untrusted = sandbox.for_project(project.path()).untrusted.unwrap();
+        assert!(!untrusted.config.network);
+        assert!(untrusted.config.args.is_empty());
+    }
+
+    #[test]
+    fn test_project_sandbox_rejects_unknown_keys() {
+        let project = project_with("volumes = [\"/:/host\"]");
+        let sandbox = podman();
+        assert_eq!(sandbox.for_project(project.path()).config, sandbox);
+    }
+}
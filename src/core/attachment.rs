@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use crate::core::memory::{CHARS_PER_TOKEN, estimate_tokens};
+
+const MIN_OUTLINE_SYMBOLS: usize = 3;
+const MODIFIERS: &[&str] = &[
+    "pub ",
+    "pub(crate) ",
+    "pub(super) ",
+    "export ",
+    "default ",
+    "async ",
+    "unsafe ",
+    "public ",
+    "private ",
+    "protected ",
+    "static ",
+    "abstract ",
+];
+const DEFINITIONS: &[&str] = &[
+    "fn ",
+    "struct ",
+    "enum ",
+    "trait ",
+    "impl ",
+    "impl<",
+    "mod ",
+    "def ",
+    "class ",
+    "function ",
+    "interface ",
+    "type ",
+    "func ",
+];
+
+#[must_use]
+pub fn fit_to_budget(content: &str, budget_tokens: usize) -> Cow<'_, str> {
+    if budget_tokens == 0 || estimate_tokens(content.len()) <= budget_tokens {
+        return Cow::Borrowed(content);
+    }
+    let budget = budget_tokens * CHARS_PER_TOKEN;
+    if let Some(outline) = outline(content, budget_tokens) {
+        if outline.len() <= budget {
+            return Cow::Owned(outline);
+        }
+        return Cow::Owned(head_and_tail(&outline, budget, budget_tokens));
+    }
+    Cow::Owned(head_and_tail(content, budget, budget_tokens))
+}
+
+fn is_definition(line: &str) -> bool {
+    let mut rest = line.trim_start();
+    while let Some(stripped) = MODIFIERS.iter().find_map(|m| rest.strip_prefix(m)) {
+        rest = stripped;
+    }
+    DEFINITIONS.iter().any(|keyword| rest.starts_with(keyword))
+}
+
+fn outline(content: &str, budget_tokens: usize) -> Option<String> {
+    let symbols: Vec<(usize, &str)> = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_definition(line))
+        .collect();
+    if symbols.len() < MIN_OUTLINE_SYMBOLS {
+        return None;
+    }
+    let mut output = format!(
+        "[outline of a {}-line attachment; bodies omitted to fit the {budget_tokens}-token attachment budget]",
+        content.lines().count()
+    );
+    for (index, line) in symbols {
+        let _ = write!(output, "\n{:>5}: {}", index + 1, line.trim_end());
+    }
+    Some(output)
+}
+
+fn head_and_tail(content: &str, budget: usize, budget_tokens: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let head_budget = budget * 2 / 3;
+    let mut used = 0;
+    let head = lines
+        .iter()
+        .take_while(|line| {
+            used += line.len() + 1;
+            used <= head_budget
+        })
+        .count();
+    let mut used = 0;
+    let tail = lines[head..]
+        .iter()
+        .rev()
+        .take_while(|line| {
+            used += line.len() + 1;
+            used <= budget - head_budget
+        })
+        .count();
+
+    if head == 0 && tail == 0 {
+        let end = content
+            .char_indices()
+            .map(|(i, _)| i)
+            .take_while(|&i| i <= budget)
+            .last()
+            .unwrap_or(0);
+        return format!(
+            "{}\n[... {} more characters omitted to fit the {budget_tokens}-token attachment budget ...]",
+            &content[..end],
+            content.len() - end
+        );
+    }
+
+    let mut output = lines[..head].join("\n");
+    let _ = write!(
+        output,
+        "\n[... {} lines omitted to fit the {budget_tokens}-token attachment budget ...]\n",
+        lines.len() - head - tail
+    );
+    output.push_str(&lines[lines.len() - tail..].join("\n"));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_to_budget() {
+        let mut log = String::new();
+        for i in 0..500 {
+            let _ = writeln!(log, "line {i}");
+        }
+        assert_eq!(fit_to_budget(&log, 10_000), log);
+        assert_eq!(fit_to_budget(&log, 0), log);
+
+        let truncated = fit_to_budget(&log, 100);
+        assert!(truncated.len() <= 100 * CHARS_PER_TOKEN + 100);
+        assert!(truncated.starts_with("line 0\n"));
+        assert!(truncated.ends_with("line 499"));
+        assert!(truncated.contains("lines omitted to fit the 100-token attachment budget"));
+
+        let mut code = String::new();
+        for i in 0..200 {
+            let _ = writeln!(
+                code,
+                "pub fn handler_{i}(input: &str) -> bool {{\n    input.is_empty()\n}}"
+            );
+        }
+        let outlined = fit_to_budget(&code, 3_000);
+        assert!(outlined.starts_with("[outline of a 600-line attachment"));
+        assert!(outlined.contains("\n    4: pub fn handler_1(input: &str) -> bool {"));
+        assert!(!outlined.contains("is_empty"));
+
+        let minified = "x".repeat(5_000);
+        assert!(fit_to_budget(&minified, 100).starts_with(&"x".repeat(400)));
+    }
+}
@@ -1,8 +1,21 @@
+use std::time::Duration;
+
+use crate::core::memory::MemoryStrategy;
+use crate::core::types::{ReasoningEffort, Verbosity};
+
 #[derive(Debug, Clone)]
 pub struct LoopConfig {
     pub max_iterations: usize,
     pub max_tokens: u32,
     pub temperature: f32,
+    pub top_p: Option<f32>,
+    pub stop_sequences: Vec<String>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub verbosity: Option<Verbosity>,
+    pub thinking_budget: Option<u32>,
+    pub memory_strategy: MemoryStrategy,
+    pub stream_retries: u32,
+    pub stream_retry_delay: Duration,
 }
 
 impl Default for LoopConfig {
@@ -11,6 +24,14 @@ impl Default for LoopConfig {
             max_iterations: 10,
             max_tokens: 4096,
             temperature: 1.0,
+            top_p: None,
+            stop_sequences: Vec::new(),
+            reasoning_effort: None,
+            verbosity: None,
+            thinking_budget: None,
+            memory_strategy: MemoryStrategy::default(),
+            stream_retries: 3,
+            stream_retry_delay: Duration::from_millis(500),
         }
     }
 }
@@ -3,22 +3,32 @@ use std::sync::Arc;
 use super::error::Result;
 use super::llm::LLM;
 use super::memory::Memory;
+use super::observer::AgentObserver;
 use super::prompt::PromptBuilder;
+use super::run_state::RunControl;
+use super::types::{Message, ToolChoice};
+use crate::audit::AuditLog;
 use crate::permission::PermissionManager;
 use crate::tools::{ToolContext, ToolEngine, ToolEventEmitter, ToolEventHandler, ToolRegistry};
 
 mod config;
 mod runner;
-mod stream_accumulator;
+pub(crate) mod stream_accumulator;
 
 pub use config::LoopConfig;
 pub struct AugmentedLLM {
     llm: Arc<dyn LLM>,
+    execution_llm: Option<Arc<dyn LLM>>,
+    last_llm: Option<Arc<dyn LLM>>,
     memory: Memory,
     tools: ToolRegistry,
     config: LoopConfig,
     permission_manager: Option<Arc<PermissionManager>>,
+    audit_log: Option<AuditLog>,
     tool_engine: ToolEngine,
+    observers: Vec<Arc<dyn AgentObserver>>,
+    tool_choice: ToolChoice,
+    run_control: RunControl,
 }
 
 impl AugmentedLLM {
@@ -32,29 +42,70 @@ impl AugmentedLLM {
 
         Ok(Self {
             llm,
+            execution_llm: None,
+            last_llm: None,
             memory: Memory::new(),
             tools: ToolRegistry::new(),
             config,
             permission_manager: None,
+            audit_log: None,
             tool_engine,
+            observers: Vec::new(),
+            tool_choice: ToolChoice::Auto,
+            run_control: RunControl::new(),
         })
     }
 
+    pub fn set_tool_choice(&mut self, choice: ToolChoice) {
+        self.tool_choice = choice;
+    }
+
+    pub fn set_run_control(&mut self, control: RunControl) {
+        self.run_control = control;
+    }
+
+    #[must_use]
+    pub const fn run_control(&self) -> &RunControl {
+        &self.run_control
+    }
+
     pub fn set_permission_manager(&mut self, manager: Arc<PermissionManager>) {
         self.permission_manager = Some(manager);
     }
 
+    pub fn set_audit_log(&mut self, audit_log: AuditLog) {
+        self.audit_log = Some(audit_log);
+    }
+
+    #[must_use]
+    pub const fn tool_context(&self) -> &ToolContext {
+        self.tool_engine.context()
+    }
+
     pub fn register_tool_event_handler(&mut self, handler: Arc<dyn ToolEventHandler>) {
         self.tool_engine.register_handler(handler);
     }
 
+    pub fn add_observer(&mut self, observer: Arc<dyn AgentObserver>) {
+        self.observers.push(observer);
+    }
+
     pub fn set_system_prompt(&mut self, prompt: impl Into<String>) {
         self.memory.set_system_prompt(prompt);
     }
 
+    #[must_use]
+    pub fn system_prompt(&self) -> Option<&str> {
+        self.memory.system_prompt()
+    }
+
     pub fn set_llm(&mut self, llm: Arc<dyn LLM>) {
         self.llm = llm;
     }
+
+    pub fn set_execution_llm(&mut self, llm: Option<Arc<dyn LLM>>) {
+        self.execution_llm = llm;
+    }
     pub fn regenerate_system_prompt(&mut self) {
         use crate::core::prompt::TemplateType;
 
@@ -82,8 +133,40 @@ impl AugmentedLLM {
     pub const fn tools_mut(&mut self) -> &mut ToolRegistry {
         &mut self.tools
     }
+    #[must_use]
+    pub const fn config(&self) -> &LoopConfig {
+        &self.config
+    }
+
+    pub const fn config_mut(&mut self) -> &mut LoopConfig {
+        &mut self.config
+    }
+
+    #[must_use]
+    pub fn messages(&self) -> &[Message] {
+        self.memory.messages()
+    }
+
+    pub fn restore_messages(&mut self, messages: Vec<Message>) {
+        self.memory.replace_messages(messages);
+    }
+
+    pub fn forget_turn(&mut self, turns_back: usize) -> bool {
+        self.memory.remove_turn(turns_back)
+    }
+
     #[must_use]
     pub fn llm(&self) -> &dyn LLM {
         self.llm.as_ref()
     }
+
+    #[must_use]
+    pub fn execution_llm(&self) -> Option<&dyn LLM> {
+        self.execution_llm.as_deref()
+    }
+
+    #[must_use]
+    pub fn last_llm(&self) -> Option<&dyn LLM> {
+        self.last_llm.as_deref()
+    }
 }
@@ -1,11 +1,39 @@
 use super::AugmentedLLM;
 use super::stream_accumulator::StreamAccumulator;
 use crate::core::error::{AgentError, Result};
-use crate::core::types::{CompletionRequest, Message, Role, StreamEvent, Usage};
+use crate::core::llm::LLM;
+use crate::core::memory::MemoryStrategy;
+use crate::core::run_state::RunState;
+use crate::core::types::{
+    CompletionRequest, ContentBlock, ContentDelta, Message, MessageDelta, Role, StreamEvent,
+    ToolChoice, Usage,
+};
+use crate::providers::schema::sanitize_tools;
+use crate::telemetry;
 use crate::tools::ToolExecutor;
 use futures::StreamExt;
+use std::time::Instant;
+
+const SUMMARY_PROMPT: &str = "Summarize the conversation so far in a few concise paragraphs. Keep file paths, decisions, open tasks and any facts needed to continue the work. Reply with the summary only.";
+const CONTINUE_PROMPT: &str = "Your previous response was interrupted by a connection error after the text above. Continue exactly where it stopped, without repeating anything already written.";
+
+enum StreamAttempt {
+    Complete(Vec<ContentBlock>),
+    Interrupted { error: AgentError, text: String },
+}
+
+fn prepend_text(blocks: &mut Vec<ContentBlock>, prefix: &str) {
+    if prefix.is_empty() {
+        return;
+    }
+    match blocks.first_mut() {
+        Some(ContentBlock::Text { text }) => text.insert_str(0, prefix),
+        _ => blocks.insert(0, ContentBlock::text(prefix)),
+    }
+}
 
 impl AugmentedLLM {
+    #[tracing::instrument(name = "agent.run", skip_all, fields(model = self.llm.model()))]
     pub async fn run<F>(
         &mut self,
         user_message: impl Into<String>,
@@ -14,20 +42,53 @@ impl AugmentedLLM {
     where
         F: FnMut(&StreamEvent),
     {
-        self.memory.push(Message::user(user_message));
+        let result = self.run_loop(user_message.into(), &mut on_event).await;
+        self.set_state(RunState::Idle);
+        result
+    }
+
+    async fn run_loop<F>(
+        &mut self,
+        user_message: String,
+        on_event: &mut F,
+    ) -> Result<(Message, Usage)>
+    where
+        F: FnMut(&StreamEvent),
+    {
+        let user_message = match self.tool_context().files.stale_notice() {
+            Some(notice) => format!("{notice}{user_message}"),
+            None => user_message,
+        };
+        self.record_message(Message::user(user_message));
+        self.tool_engine.begin_turn();
         let mut total_usage = Usage::default();
 
-        for _ in 0..self.config.max_iterations {
-            let (assistant_message, turn_usage) = self.process_single_turn(&mut on_event).await?;
+        for iteration in 0..self.config.max_iterations {
+            self.pause_point().await;
+            self.set_state(RunState::Planning);
+            self.apply_memory_strategy().await;
+            let llm = match &self.execution_llm {
+                Some(execution) if iteration > 0 => execution.clone(),
+                _ => self.llm.clone(),
+            };
+            let (assistant_message, turn_usage) =
+                self.process_single_turn(llm.as_ref(), on_event).await?;
+            self.last_llm = Some(llm);
+            self.tool_choice = ToolChoice::Auto;
             if let Some(usage) = turn_usage {
+                for observer in &self.observers {
+                    observer.on_token_usage(&usage);
+                }
                 total_usage.add(&usage);
             }
-            self.memory.push(assistant_message.clone());
+            self.record_message(assistant_message.clone());
 
             if !assistant_message.has_tool_use() {
                 return Ok((assistant_message, total_usage));
             }
 
+            self.pause_point().await;
+            self.set_state(RunState::ExecutingTools);
             self.execute_and_record_tools(&assistant_message).await;
         }
 
@@ -36,18 +97,129 @@ impl AugmentedLLM {
         ))
     }
 
-    async fn process_single_turn<F>(&self, on_event: &mut F) -> Result<(Message, Option<Usage>)>
+    fn set_state(&self, state: RunState) {
+        for observer in &self.observers {
+            observer.on_state_change(state);
+        }
+    }
+
+    async fn pause_point(&self) {
+        if self.run_control.is_paused() {
+            self.set_state(RunState::Paused);
+            self.run_control.wait_until_resumed().await;
+        }
+    }
+
+    #[tracing::instrument(
+        name = "llm.request",
+        skip_all,
+        fields(provider = llm.name(), model = llm.model())
+    )]
+    async fn process_single_turn<F>(
+        &self,
+        llm: &dyn LLM,
+        on_event: &mut F,
+    ) -> Result<(Message, Option<Usage>)>
+    where
+        F: FnMut(&StreamEvent),
+    {
+        let started = Instant::now();
+        let result = self.stream_turn(llm, on_event).await;
+        let usage = result
+            .as_ref()
+            .ok()
+            .and_then(|(_, usage)| *usage)
+            .unwrap_or_default();
+        telemetry::record_llm_request(
+            llm.name(),
+            llm.model(),
+            &usage,
+            started.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    async fn stream_turn<F>(
+        &self,
+        llm: &dyn LLM,
+        on_event: &mut F,
+    ) -> Result<(Message, Option<Usage>)>
+    where
+        F: FnMut(&StreamEvent),
+    {
+        let mut partial = String::new();
+        let mut usage: Option<Usage> = None;
+        let mut attempt = 0;
+
+        loop {
+            let mut request = self.build_completion_request(llm);
+            if !partial.is_empty() {
+                request.messages.push(Message::assistant(partial.clone()));
+                request.messages.push(Message::user(CONTINUE_PROMPT));
+            }
+
+            match self
+                .stream_attempt(llm, request, on_event, &mut usage)
+                .await?
+            {
+                StreamAttempt::Complete(mut blocks) => {
+                    prepend_text(&mut blocks, &partial);
+                    return Ok((Message::new(Role::Assistant, blocks), usage));
+                }
+                StreamAttempt::Interrupted { error, text } => {
+                    if attempt >= self.config.stream_retries {
+                        return Err(error);
+                    }
+                    let delay = self.config.stream_retry_delay * 2_u32.saturating_pow(attempt);
+                    attempt += 1;
+                    tracing::warn!(
+                        attempt,
+                        delay_ms = delay.as_millis(),
+                        error = %error,
+                        "Stream interrupted, retrying"
+                    );
+                    partial.push_str(&text);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    async fn stream_attempt<F>(
+        &self,
+        llm: &dyn LLM,
+        request: CompletionRequest,
+        on_event: &mut F,
+        usage: &mut Option<Usage>,
+    ) -> Result<StreamAttempt>
     where
         F: FnMut(&StreamEvent),
     {
-        let request = self.build_completion_request();
-        let mut stream = self.llm.stream(request).await?;
+        let mut stream = llm.stream(request).await?;
+        self.set_state(RunState::Streaming);
         let mut accumulator = StreamAccumulator::default();
-        let mut final_usage: Option<Usage> = None;
 
         while let Some(event_result) = stream.next().await {
-            let event = event_result?;
+            let event = match event_result {
+                Ok(event) => event,
+                Err(error) => {
+                    return Ok(StreamAttempt::Interrupted {
+                        error,
+                        text: accumulator.partial_text(),
+                    });
+                }
+            };
             on_event(&event);
+            if let StreamEvent::ContentBlockDelta {
+                delta: ContentDelta::TextDelta { text },
+                ..
+            } = &event
+            {
+                for observer in &self.observers {
+                    observer.on_text_delta(text);
+                }
+            }
 
             match event {
                 StreamEvent::ContentBlockStart {
@@ -59,61 +231,331 @@ impl AugmentedLLM {
                 StreamEvent::ContentBlockDelta { index, delta } => {
                     accumulator.handle_delta(index, delta);
                 }
-                StreamEvent::MessageStart { usage, .. } => {
-                    if let Some(u) = usage {
-                        final_usage = Some(final_usage.map_or(u, |mut existing| {
-                            existing.add(&u);
-                            existing
-                        }));
-                    }
-                }
-                StreamEvent::MessageDelta { delta } => {
-                    if let Some(u) = delta.usage {
-                        final_usage = Some(final_usage.map_or(u, |mut existing| {
-                            existing.add(&u);
-                            existing
-                        }));
-                    }
+                StreamEvent::MessageStart { usage: Some(u), .. }
+                | StreamEvent::MessageDelta {
+                    delta: MessageDelta { usage: Some(u), .. },
+                } => {
+                    usage.get_or_insert_with(Usage::default).add(&u);
                 }
                 StreamEvent::MessageStop => break,
                 _ => {}
             }
         }
 
-        Ok((
-            Message::new(Role::Assistant, accumulator.into_content_blocks()),
-            final_usage,
-        ))
+        Ok(StreamAttempt::Complete(accumulator.into_content_blocks()))
+    }
+
+    async fn apply_memory_strategy(&mut self) {
+        match self.config.memory_strategy.clone() {
+            MemoryStrategy::Unbounded => {}
+            MemoryStrategy::SlidingWindow { max_turns } => self.memory.keep_last_turns(max_turns),
+            MemoryStrategy::DropToolOutputs { token_budget } => {
+                self.memory.trim_to_budget(token_budget);
+            }
+            MemoryStrategy::Summarize {
+                token_budget,
+                keep_turns,
+            } => {
+                if self.memory.estimated_tokens() <= token_budget {
+                    return;
+                }
+                let older = self.memory.take_older_turns(keep_turns);
+                if !older.is_empty() {
+                    match self.summarize(older.clone()).await {
+                        Ok(summary) => self.memory.prepend_summary(&summary),
+                        Err(e) => {
+                            tracing::warn!("Failed to summarize conversation: {e}");
+                            let mut messages = older;
+                            messages.extend_from_slice(self.memory.messages());
+                            self.memory.replace_messages(messages);
+                        }
+                    }
+                }
+                self.memory.trim_to_budget(token_budget);
+            }
+        }
     }
 
-    fn build_completion_request(&self) -> CompletionRequest {
+    async fn summarize(&self, mut messages: Vec<Message>) -> Result<String> {
+        messages.push(Message::user(SUMMARY_PROMPT));
+        let mut request = CompletionRequest::new(messages)
+            .with_max_tokens(self.config.max_tokens)
+            .with_temperature(0.0);
+        if !self.tools.is_empty() {
+            let defs = sanitize_tools(self.tools.definitions(), &self.llm.schema_support());
+            request = request.with_tools(defs).with_tool_choice(ToolChoice::None);
+        }
+        let response = self.llm.complete(request).await?;
+        response
+            .message
+            .first_text()
+            .map(ToString::to_string)
+            .ok_or_else(|| AgentError::InvalidState("Summary response had no text".to_string()))
+    }
+
+    fn build_completion_request(&self, llm: &dyn LLM) -> CompletionRequest {
         let mut request = CompletionRequest::new(self.memory.messages().to_vec())
             .with_max_tokens(self.config.max_tokens)
-            .with_temperature(self.config.temperature);
+            .with_temperature(self.config.temperature)
+            .with_top_p(self.config.top_p)
+            .with_reasoning_effort(self.config.reasoning_effort)
+            .with_verbosity(self.config.verbosity)
+            .with_thinking_budget(self.config.thinking_budget)
+            .with_stop_sequences(self.config.stop_sequences.clone());
 
-        if let Some(prompt) = self.memory.system_prompt() {
-            request = request.with_system_prompt(prompt);
+        let pins = self.tool_context().pins.prompt_section();
+        match (self.memory.system_prompt(), pins) {
+            (Some(prompt), Some(pins)) => {
+                request = request.with_system_prompt(format!("{prompt}\n\n{pins}"));
+            }
+            (Some(prompt), None) => request = request.with_system_prompt(prompt),
+            (None, Some(pins)) => request = request.with_system_prompt(pins),
+            (None, None) => {}
         }
 
         if !self.tools.is_empty() {
-            let defs = self.tools.definitions();
-            request = request.with_tools(defs);
+            let defs = sanitize_tools(self.tools.definitions(), &llm.schema_support());
+            request = request
+                .with_tools(defs)
+                .with_tool_choice(self.tool_choice.clone());
         }
 
         request
     }
 
+    #[tracing::instrument(name = "agent.tools", skip_all)]
     async fn execute_and_record_tools(&mut self, assistant_message: &Message) {
         let executor = ToolExecutor::new(
             &self.tools,
             self.permission_manager.as_ref(),
             &self.tool_engine,
-        );
+        )
+        .with_observers(&self.observers)
+        .with_audit_log(self.audit_log.as_ref());
 
         let tool_results = executor.execute_tools(assistant_message).await;
 
         for result in tool_results {
-            self.memory.push(result);
+            self.record_message(result);
+        }
+    }
+
+    pub async fn rerun_tool(&self, name: &str, input: serde_json::Value) -> Vec<Message> {
+        let request = Message::new(Role::Assistant, vec![ContentBlock::tool_use(name, input)]);
+        ToolExecutor::new(
+            &self.tools,
+            self.permission_manager.as_ref(),
+            &self.tool_engine,
+        )
+        .with_observers(&self.observers)
+        .with_audit_log(self.audit_log.as_ref())
+        .execute_tools(&request)
+        .await
+    }
+
+    fn record_message(&mut self, message: Message) {
+        for observer in &self.observers {
+            observer.on_message(&message);
+        }
+        self.memory.push(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::augmented_llm::LoopConfig;
+    use crate::core::llm::LLM;
+    use crate::core::types::{CompletionResponse, StreamResponse};
+    use crate::tools::ToolEventEmitter;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct FlakyLLM {
+        failures: Mutex<usize>,
+        requests: Mutex<Vec<CompletionRequest>>,
+    }
+
+    fn text_events(text: &str) -> Vec<Result<StreamEvent>> {
+        vec![
+            Ok(StreamEvent::ContentBlockStart {
+                index: 0,
+                content_block: ContentBlock::text(""),
+            }),
+            Ok(StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: ContentDelta::TextDelta {
+                    text: text.to_string(),
+                },
+            }),
+        ]
+    }
+
+    #[async_trait]
+    impl LLM for FlakyLLM {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn model(&self) -> &'static str {
+            "flaky-model"
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            Err(AgentError::Provider("unused".to_string()))
         }
+
+        async fn stream(&self, request: CompletionRequest) -> Result<StreamResponse> {
+            self.requests.lock().unwrap().push(request);
+            let failed = {
+                let mut failures = self.failures.lock().unwrap();
+                let failed = *failures > 0;
+                if failed {
+                    *failures -= 1;
+                }
+                failed
+            };
+            let events = if failed {
+                let mut events = text_events("Hello");
+                events.push(Err(AgentError::Provider(
+                    "Stream error: connection reset".to_string(),
+                )));
+                events
+            } else {
+                let mut events = text_events(" world");
+                events.push(Ok(StreamEvent::MessageStop));
+                events
+            };
+            Ok(Box::pin(futures::stream::iter(events)))
+        }
+    }
+
+    fn agent(failures: usize, retries: u32) -> (AugmentedLLM, Arc<FlakyLLM>) {
+        let llm = Arc::new(FlakyLLM {
+            failures: Mutex::new(failures),
+            requests: Mutex::new(Vec::new()),
+        });
+        let config = LoopConfig {
+            stream_retries: retries,
+            stream_retry_delay: Duration::ZERO,
+            ..LoopConfig::default()
+        };
+        let agent =
+            AugmentedLLM::with_config(llm.clone(), config, ToolEventEmitter::new()).unwrap();
+        (agent, llm)
+    }
+
+    #[tokio::test]
+    async fn test_stream_interruption_is_retried_with_continuation() {
+        let (mut agent, llm) = agent(1, 2);
+
+        let (message, _) = agent.run("hi", |_| {}).await.unwrap();
+
+        assert_eq!(message.first_text(), Some("Hello world"));
+        let requests = llm.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        let retry = &requests[1].messages;
+        assert_eq!(retry[retry.len() - 2].first_text(), Some("Hello"));
+        assert_eq!(retry[retry.len() - 1].first_text(), Some(CONTINUE_PROMPT));
+        assert_eq!(agent.memory.messages().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_interruption_gives_up_after_retries() {
+        let (mut agent, llm) = agent(3, 1);
+
+        let result = agent.run("hi", |_| {}).await;
+
+        assert!(matches!(result, Err(AgentError::Provider(_))));
+        assert_eq!(llm.requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_summary_keeps_history() {
+        let (mut agent, _) = agent(0, 0);
+        agent.config.memory_strategy = MemoryStrategy::Summarize {
+            token_budget: 200,
+            keep_turns: 1,
+        };
+        let mut assistant = Message::assistant("");
+        assistant.add_content(ContentBlock::tool_use(
+            "read_file",
+            serde_json::json!({"path": "a"}),
+        ));
+        for message in [
+            Message::user("first"),
+            assistant,
+            Message::new(
+                Role::Tool,
+                vec![ContentBlock::tool_result("id", "x".repeat(4000))],
+            ),
+            Message::assistant("a"),
+            Message::user("second"),
+        ] {
+            agent.memory.push(message);
+        }
+
+        agent.apply_memory_strategy().await;
+
+        let messages = agent.memory.messages();
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[0].first_text(), Some("first"));
+        assert_eq!(messages[4].first_text(), Some("second"));
+        assert!(agent.memory.estimated_tokens() <= 200);
+    }
+
+    #[tokio::test]
+    async fn test_tool_choice_applies_to_first_request_only() {
+        use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+        use crate::tools::ReadFileTool;
+
+        let llm = Arc::new(ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::tool_call("missing_tool", serde_json::json!({})),
+            ScenarioTurn::text("done"),
+        ])));
+        let mut agent =
+            AugmentedLLM::with_config(llm.clone(), LoopConfig::default(), ToolEventEmitter::new())
+                .unwrap();
+        agent.tools_mut().register(Arc::new(ReadFileTool::new()));
+        agent.set_tool_choice(ToolChoice::Required);
+
+        agent.run("extract", |_| {}).await.unwrap();
+
+        let requests = llm.request_history();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].tool_choice, ToolChoice::Required);
+        assert_eq!(requests[1].tool_choice, ToolChoice::Auto);
+    }
+
+    #[tokio::test]
+    async fn test_follow_up_turns_use_execution_llm() {
+        use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+
+        let planner = Arc::new(ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::tool_call("missing_tool", serde_json::json!({})),
+            ScenarioTurn::text("second plan"),
+        ])));
+        let executor = Arc::new(ScenarioLLM::new(Scenario {
+            model: Some("executor".to_string()),
+            ..Scenario::new(vec![ScenarioTurn::text("done")])
+        }));
+        let mut agent = AugmentedLLM::with_config(
+            planner.clone(),
+            LoopConfig::default(),
+            ToolEventEmitter::new(),
+        )
+        .unwrap();
+        agent.set_execution_llm(Some(executor.clone()));
+
+        let (first, _) = agent.run("fix it", |_| {}).await.unwrap();
+        assert_eq!(agent.last_llm().map(LLM::model), Some("executor"));
+        let (second, _) = agent.run("and again", |_| {}).await.unwrap();
+        assert_eq!(agent.last_llm().map(LLM::model), Some("mock-scenario"));
+
+        assert_eq!(first.first_text(), Some("done"));
+        assert_eq!(second.first_text(), Some("second plan"));
+        assert_eq!(planner.request_history().len(), 2);
+        assert_eq!(executor.request_history().len(), 1);
     }
 }
@@ -1,7 +1,7 @@
 use crate::core::types::{ContentBlock, ContentDelta};
 use std::collections::HashMap;
 #[derive(Default)]
-pub(super) struct StreamAccumulator {
+pub struct StreamAccumulator {
     blocks: HashMap<usize, ContentBlock>,
     tool_inputs: HashMap<usize, String>,
 }
@@ -25,6 +25,18 @@ impl StreamAccumulator {
         }
     }
 
+    pub fn partial_text(&self) -> String {
+        let mut indices: Vec<_> = self.blocks.keys().copied().collect();
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .filter_map(|index| match &self.blocks[&index] {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn into_content_blocks(mut self) -> Vec<ContentBlock> {
         self.merge_tool_inputs();
         self.into_sorted_blocks()
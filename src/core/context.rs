@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+use std::path::PathBuf;
+
+use super::memory::{ELIDED_TOOL_OUTPUT, estimate_message_tokens, estimate_tokens, turn_starts};
+use super::types::{ContentBlock, Message};
+
+const PREAMBLE: &str = "Preamble";
+const PREVIEW_CHARS: usize = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextSource {
+    Section(String),
+    Pin(PathBuf),
+    Tools,
+    Turn { start: usize, end: usize },
+    ToolOutput { message: usize, block: usize },
+    Shared(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextItem {
+    pub source: ContextSource,
+    pub label: String,
+    pub tokens: usize,
+}
+
+impl ContextItem {
+    #[must_use]
+    pub fn new(source: ContextSource, label: impl Into<String>, tokens: usize) -> Self {
+        Self {
+            source,
+            label: label.into(),
+            tokens,
+        }
+    }
+
+    #[must_use]
+    pub const fn droppable(&self) -> bool {
+        !matches!(self.source, ContextSource::Tools)
+    }
+
+    #[must_use]
+    pub const fn is_nested(&self) -> bool {
+        matches!(self.source, ContextSource::ToolOutput { .. })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextSnapshot {
+    pub items: Vec<ContextItem>,
+    pub messages: usize,
+}
+
+impl ContextSnapshot {
+    #[must_use]
+    pub fn total_tokens(&self) -> usize {
+        self.kept_tokens(&HashSet::new())
+    }
+
+    #[must_use]
+    pub fn kept_tokens(&self, dropped: &HashSet<usize>) -> usize {
+        let mut turn_dropped = false;
+        self.items
+            .iter()
+            .enumerate()
+            .fold(0, |total, (index, item)| {
+                let dropped = dropped.contains(&index);
+                match item.source {
+                    ContextSource::ToolOutput { .. } if dropped && !turn_dropped => {
+                        total.saturating_sub(item.tokens)
+                    }
+                    ContextSource::ToolOutput { .. } => total,
+                    ContextSource::Turn { .. } => {
+                        turn_dropped = dropped;
+                        if dropped { total } else { total + item.tokens }
+                    }
+                    _ if dropped => total,
+                    _ => total + item.tokens,
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptSection<'a> {
+    pub title: &'a str,
+    pub text: &'a str,
+}
+
+#[must_use]
+pub fn prompt_sections(prompt: &str) -> Vec<PromptSection<'_>> {
+    let mut sections = Vec::new();
+    let mut title = PREAMBLE;
+    let mut start = 0;
+    let mut offset = 0;
+    let mut in_fence = false;
+    for line in prompt.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence && let Some(heading) = line.strip_prefix("# ") {
+            if !prompt[start..offset].trim().is_empty() {
+                sections.push(PromptSection {
+                    title,
+                    text: &prompt[start..offset],
+                });
+            }
+            title = heading.trim();
+            start = offset;
+        }
+        offset += line.len();
+    }
+    if !prompt[start..].trim().is_empty() {
+        sections.push(PromptSection {
+            title,
+            text: &prompt[start..],
+        });
+    }
+    sections
+}
+
+#[must_use]
+pub fn without_sections<S: BuildHasher>(prompt: &str, dropped: &HashSet<String, S>) -> String {
+    if dropped.is_empty() {
+        return prompt.to_string();
+    }
+    prompt_sections(prompt)
+        .iter()
+        .filter(|section| !dropped.contains(section.title))
+        .map(|section| section.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[must_use]
+pub fn prompt_items(prompt: &str) -> Vec<ContextItem> {
+    prompt_sections(prompt)
+        .iter()
+        .map(|section| {
+            ContextItem::new(
+                ContextSource::Section(section.title.to_string()),
+                format!("System: {}", section.title),
+                estimate_tokens(section.text.trim().len()),
+            )
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn conversation_items(messages: &[Message]) -> Vec<ContextItem> {
+    let mut starts = turn_starts(messages);
+    if !messages.is_empty() && starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+    let mut items = Vec::new();
+    for (number, &start) in starts.iter().enumerate() {
+        let end = starts.get(number + 1).copied().unwrap_or(messages.len());
+        let turn = &messages[start..end];
+        let prompt = turn[0].first_text().map_or_else(String::new, preview);
+        items.push(ContextItem::new(
+            ContextSource::Turn { start, end },
+            format!("Turn {}: {prompt}", number + 1),
+            turn.iter().map(estimate_message_tokens).sum(),
+        ));
+        for (index, message) in turn.iter().enumerate() {
+            for (block, content) in message.content.iter().enumerate() {
+                if let ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    ..
+                } = content
+                    && content != ELIDED_TOOL_OUTPUT
+                {
+                    items.push(ContextItem::new(
+                        ContextSource::ToolOutput {
+                            message: start + index,
+                            block,
+                        },
+                        format!("{} output", tool_label(turn, tool_use_id)),
+                        estimate_tokens(content.len()),
+                    ));
+                }
+            }
+        }
+    }
+    items
+}
+
+pub fn drop_conversation(messages: &mut Vec<Message>, sources: &[ContextSource]) {
+    for source in sources {
+        if let ContextSource::ToolOutput { message, block } = source
+            && let Some(ContentBlock::ToolResult { content, .. }) = messages
+                .get_mut(*message)
+                .and_then(|message| message.content.get_mut(*block))
+        {
+            ELIDED_TOOL_OUTPUT.clone_into(content);
+        }
+    }
+
+    let mut turns: Vec<(usize, usize)> = sources
+        .iter()
+        .filter_map(|source| match source {
+            ContextSource::Turn { start, end } => Some((*start, *end)),
+            _ => None,
+        })
+        .collect();
+    turns.sort_unstable();
+    for (start, end) in turns.into_iter().rev() {
+        if start < end && end <= messages.len() {
+            messages.drain(start..end);
+        }
+    }
+}
+
+#[must_use]
+pub fn preview(text: &str) -> String {
+    let line = text
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    let line = line.trim();
+    if line.chars().count() > PREVIEW_CHARS {
+        let cut: String = line.chars().take(PREVIEW_CHARS - 1).collect();
+        format!("{cut}…")
+    } else {
+        line.to_string()
+    }
+}
+
+fn tool_label(turn: &[Message], tool_use_id: &str) -> String {
+    turn.iter()
+        .flat_map(|message| &message.content)
+        .find_map(|block| match block {
+            ContentBlock::ToolUse {
+                id, name, input, ..
+            } if id == tool_use_id => {
+                let argument = input
+                    .as_object()
+                    .and_then(|args| args.values().find_map(serde_json::Value::as_str))
+                    .map(preview);
+                Some(argument.map_or_else(|| name.clone(), |arg| format!("{name} {arg}")))
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| "tool".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Role;
+
+    #[test]
+    fn test_context_items_and_drops() {
+        let prompt = "You are smith.\n\n# Code style\nNo comments.\n```sh\n# not a heading\n```\n\n# Language\nReply in Spanish.";
+        let titles: Vec<&str> = prompt_sections(prompt)
+            .iter()
+            .map(|section| section.title)
+            .collect();
+        assert_eq!(titles, vec!["Preamble", "Code style", "Language"]);
+        let dropped = HashSet::from(["Code style".to_string()]);
+        assert_eq!(
+            without_sections(prompt, &dropped),
+            "You are smith.\n\n# Language\nReply in Spanish."
+        );
+
+        let mut assistant = Message::assistant("");
+        assistant.add_content(ContentBlock::tool_use(
+            "read_file",
+            serde_json::json!({"path": "src/main.rs"}),
+        ));
+        let id = match &assistant.content[1] {
+            ContentBlock::ToolUse { id, .. } => id.clone(),
+            _ => unreachable!(),
+        };
+        let mut messages = vec![
+            Message::user("first question"),
+            assistant,
+            Message::new(
+                Role::Tool,
+                vec![ContentBlock::tool_result(id, "x".repeat(400))],
+            ),
+            Message::assistant("done"),
+            Message::user("second question"),
+            Message::assistant("ok"),
+        ];
+
+        let items = conversation_items(&messages);
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Turn 1: first question",
+                "read_file src/main.rs output",
+                "Turn 2: second question"
+            ]
+        );
+        let snapshot = ContextSnapshot {
+            items: items.clone(),
+            messages: messages.len(),
+        };
+        let total = snapshot.total_tokens();
+        assert_eq!(
+            snapshot.kept_tokens(&HashSet::from([1])),
+            total - items[1].tokens
+        );
+        assert_eq!(
+            snapshot.kept_tokens(&HashSet::from([0, 1])),
+            total - items[0].tokens
+        );
+
+        drop_conversation(
+            &mut messages,
+            &[items[1].source.clone(), items[2].source.clone()],
+        );
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(
+            &messages[2].content[0],
+            ContentBlock::ToolResult { content, .. } if content == ELIDED_TOOL_OUTPUT
+        ));
+        assert_eq!(conversation_items(&messages).len(), 1);
+    }
+}
@@ -1,5 +1,10 @@
+use serde::Serialize;
+use std::fmt;
 use thiserror::Error;
 
+use crate::config::ProviderType;
+use crate::providers::error::ProviderError;
+
 #[derive(Error, Debug)]
 pub enum AgentError {
     #[error("Provider error: {0}")]
@@ -31,13 +36,116 @@ pub enum AgentError {
 
     #[error("Invalid state: {0}")]
     InvalidState(String),
+
+    #[error("Provider error: {0}")]
+    Api(#[from] ProviderError),
+
+    #[error("{path} changed on disk since you last read it. Changes since then:\n{diff}")]
+    FileConflict { path: String, diff: String },
+
+    #[error("{path} is a binary file and cannot be handled as text")]
+    BinaryFile { path: String },
 }
 
 pub type Result<T> = std::result::Result<T, AgentError>;
 
-impl From<crate::providers::error::ProviderError> for AgentError {
-    fn from(err: crate::providers::error::ProviderError) -> Self {
-        Self::Provider(err.to_string())
+impl AgentError {
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::Provider(_) => "ERR_PROVIDER",
+            Self::ToolExecution(_) => "ERR_TOOL",
+            Self::ToolNotFound(_) => "ERR_TOOL_NOT_FOUND",
+            Self::InvalidToolInput { .. } => "ERR_TOOL_INPUT",
+            Self::MaxIterationsExceeded(_) => "ERR_MAX_ITERATIONS",
+            Self::Io(_) => "ERR_IO",
+            Self::Json(_) => "ERR_JSON",
+            Self::Config(_) => "ERR_CONFIG",
+            Self::Timeout(_) => "ERR_TIMEOUT",
+            Self::InvalidState(_) => "ERR_STATE",
+            Self::Api(err) => err.code(),
+            Self::FileConflict { .. } => "ERR_FILE_CONFLICT",
+            Self::BinaryFile { .. } => "ERR_BINARY_FILE",
+        }
+    }
+
+    #[must_use]
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::Api(err) => err.hint(),
+            Self::MaxIterationsExceeded(_) => {
+                Some("Raise the limit with --max-iterations".to_string())
+            }
+            Self::FileConflict { .. } => Some(
+                "Read the file again, then redo the edit against its current content".to_string(),
+            ),
+            Self::BinaryFile { .. } => Some(
+                "Use read_document for PDF and Office files, or a shell tool like xxd for raw bytes"
+                    .to_string(),
+            ),
+            Self::Config(_) => {
+                Some("Check the config file shown by `smith config where`".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        match self {
+            Self::Api(err) => err.is_retryable(),
+            Self::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    pub retryable: bool,
+}
+
+impl ErrorReport {
+    #[must_use]
+    pub fn new(err: &AgentError) -> Self {
+        Self {
+            code: err.code().to_string(),
+            message: err.to_string(),
+            hint: err.hint(),
+            retryable: err.is_retryable(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_provider(mut self, provider: &ProviderType) -> Self {
+        if self.code == "ERR_AUTH" {
+            self.code = format!("ERR_AUTH_{}", provider.id().to_ascii_uppercase());
+            if self.hint.is_none() {
+                self.hint = provider
+                    .default_api_key_env()
+                    .map(|var| format!("Set the {var} environment variable"));
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::json!({ "error": self }).to_string()
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.code)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\nHint: {hint}")?;
+        }
+        Ok(())
     }
 }
 
@@ -64,4 +172,35 @@ mod tests {
         let agent_err: AgentError = json_err.into();
         assert!(matches!(agent_err, AgentError::Json(_)));
     }
+
+    #[test]
+    fn test_error_report_codes_and_hints() {
+        let err: AgentError = ProviderError::Authentication {
+            message: "Invalid API key".to_string(),
+            hint: None,
+        }
+        .into();
+        assert_eq!(
+            err.to_string(),
+            "Provider error: Authentication failed: Invalid API key"
+        );
+
+        let report = ErrorReport::new(&err).with_provider(&ProviderType::OpenAI);
+        assert_eq!(report.code, "ERR_AUTH_OPENAI");
+        assert_eq!(
+            report.hint.as_deref(),
+            Some("Set the OPENAI_API_KEY environment variable")
+        );
+        assert!(!report.retryable);
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["error"]["code"], "ERR_AUTH_OPENAI");
+
+        let report = ErrorReport::new(&ProviderError::rate_limit("slow down").into())
+            .with_provider(&ProviderType::OpenAI);
+        assert_eq!(report.code, "ERR_RATE_LIMIT");
+        assert!(report.retryable);
+        assert!(report.to_string().starts_with(
+            "Provider error: Rate limit exceeded: slow down [ERR_RATE_LIMIT]\nHint: "
+        ));
+    }
 }
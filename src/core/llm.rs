@@ -3,11 +3,15 @@ use async_trait::async_trait;
 
 use super::error::Result;
 use super::types::{CompletionRequest, CompletionResponse, StreamResponse};
+use crate::providers::schema::SchemaSupport;
 
 #[async_trait]
 pub trait LLM: Send + Sync {
     fn name(&self) -> &str;
     fn model(&self) -> &str;
+    fn schema_support(&self) -> SchemaSupport {
+        SchemaSupport::FULL
+    }
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse>;
     async fn stream(&self, request: CompletionRequest) -> Result<StreamResponse>;
 }
@@ -1,4 +1,23 @@
-use super::types::Message;
+use super::types::{ContentBlock, Message, Role};
+
+pub(crate) const CHARS_PER_TOKEN: usize = 4;
+pub(crate) const ELIDED_TOOL_OUTPUT: &str = "[tool output removed to save context]";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MemoryStrategy {
+    #[default]
+    Unbounded,
+    SlidingWindow {
+        max_turns: usize,
+    },
+    DropToolOutputs {
+        token_budget: usize,
+    },
+    Summarize {
+        token_budget: usize,
+        keep_turns: usize,
+    },
+}
 
 #[derive(Debug, Clone, Default)]
 pub struct Memory {
@@ -32,4 +51,211 @@ impl Memory {
     pub fn messages(&self) -> &[Message] {
         &self.messages
     }
+
+    pub fn replace_messages(&mut self, messages: Vec<Message>) {
+        self.messages = messages;
+    }
+
+    #[must_use]
+    pub fn estimated_tokens(&self) -> usize {
+        self.messages.iter().map(estimate_message_tokens).sum()
+    }
+
+    #[must_use]
+    pub fn turn_count(&self) -> usize {
+        self.turn_starts().len()
+    }
+
+    pub fn keep_last_turns(&mut self, max_turns: usize) {
+        let starts = self.turn_starts();
+        if starts.len() <= max_turns.max(1) {
+            return;
+        }
+        let cut = starts[starts.len() - max_turns.max(1)];
+        self.messages.drain(..cut);
+    }
+
+    pub fn remove_turn(&mut self, turns_back: usize) -> bool {
+        let starts = self.turn_starts();
+        let Some(index) = starts.len().checked_sub(turns_back + 1) else {
+            return false;
+        };
+        let end = starts
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.messages.len());
+        self.messages.drain(starts[index]..end);
+        true
+    }
+
+    pub fn trim_to_budget(&mut self, token_budget: usize) {
+        if self.estimated_tokens() <= token_budget {
+            return;
+        }
+
+        let current_turn = self.turn_starts().last().copied().unwrap_or(0);
+        let mut total = self.estimated_tokens();
+        for message in &mut self.messages[..current_turn] {
+            if total <= token_budget {
+                break;
+            }
+            let before = estimate_message_tokens(message);
+            for block in &mut message.content {
+                if let ContentBlock::ToolResult { content, .. } = block {
+                    ELIDED_TOOL_OUTPUT.clone_into(content);
+                }
+            }
+            total = total - before + estimate_message_tokens(message);
+        }
+
+        while self.estimated_tokens() > token_budget && self.turn_count() > 1 {
+            let turns = self.turn_count();
+            self.keep_last_turns(turns - 1);
+        }
+    }
+
+    #[must_use]
+    pub fn take_older_turns(&mut self, keep_turns: usize) -> Vec<Message> {
+        let starts = self.turn_starts();
+        if starts.len() <= keep_turns.max(1) {
+            return Vec::new();
+        }
+        let cut = starts[starts.len() - keep_turns.max(1)];
+        self.messages.drain(..cut).collect()
+    }
+
+    pub fn prepend_summary(&mut self, summary: &str) {
+        let block = ContentBlock::text(format!("Summary of the earlier conversation:\n{summary}"));
+        match self.messages.first_mut() {
+            Some(first) if first.role == Role::User => first.content.insert(0, block),
+            _ => self
+                .messages
+                .insert(0, Message::new(Role::User, vec![block])),
+        }
+    }
+
+    fn turn_starts(&self) -> Vec<usize> {
+        turn_starts(&self.messages)
+    }
+}
+
+pub(crate) fn turn_starts(messages: &[Message]) -> Vec<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.role == Role::User)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[must_use]
+pub const fn estimate_tokens(bytes: usize) -> usize {
+    bytes.div_ceil(CHARS_PER_TOKEN)
+}
+
+pub(crate) fn estimate_message_tokens(message: &Message) -> usize {
+    let chars: usize = message
+        .content
+        .iter()
+        .map(|block| match block {
+            ContentBlock::Text { text } => text.len(),
+            ContentBlock::Thinking { thinking, .. } => thinking.len(),
+            ContentBlock::RedactedThinking { data } => data.len(),
+            ContentBlock::ToolUse { name, input, .. } => name.len() + input.to_string().len(),
+            ContentBlock::ToolResult { content, .. } => content.len(),
+            ContentBlock::Image { .. } | ContentBlock::Document { .. } => 0,
+        })
+        .sum();
+    estimate_tokens(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(memory: &mut Memory, user: &str, tool_output: &str) {
+        memory.push(Message::user(user));
+        let mut assistant = Message::assistant("");
+        assistant.add_content(ContentBlock::tool_use(
+            "read_file",
+            serde_json::json!({"path": "a"}),
+        ));
+        memory.push(assistant);
+        memory.push(Message::new(
+            Role::Tool,
+            vec![ContentBlock::tool_result("id", tool_output)],
+        ));
+        memory.push(Message::assistant("done"));
+    }
+
+    #[test]
+    fn test_keep_last_turns() {
+        let mut memory = Memory::new();
+        turn(&mut memory, "first", "a");
+        turn(&mut memory, "second", "b");
+        turn(&mut memory, "third", "c");
+
+        memory.keep_last_turns(2);
+
+        assert_eq!(memory.turn_count(), 2);
+        assert_eq!(memory.messages()[0].first_text(), Some("second"));
+    }
+
+    #[test]
+    fn test_remove_turn_counts_from_latest() {
+        let mut memory = Memory::new();
+        turn(&mut memory, "first", "a");
+        turn(&mut memory, "second", "b");
+        turn(&mut memory, "third", "c");
+
+        assert!(memory.remove_turn(1));
+
+        assert_eq!(memory.turn_count(), 2);
+        assert_eq!(memory.messages()[4].first_text(), Some("third"));
+        assert!(!memory.remove_turn(2));
+    }
+
+    #[test]
+    fn test_trim_drops_old_tool_outputs_first() {
+        let mut memory = Memory::new();
+        turn(&mut memory, "first", &"x".repeat(4000));
+        turn(&mut memory, "second", "small");
+        let budget = memory.estimated_tokens() - 100;
+
+        memory.trim_to_budget(budget);
+
+        assert_eq!(memory.turn_count(), 2);
+        assert!(memory.estimated_tokens() <= budget);
+        assert!(matches!(
+            &memory.messages()[2].content[0],
+            ContentBlock::ToolResult { content, .. } if content == ELIDED_TOOL_OUTPUT
+        ));
+    }
+
+    #[test]
+    fn test_trim_drops_turns_when_still_over_budget() {
+        let mut memory = Memory::new();
+        turn(&mut memory, &"y".repeat(4000), "a");
+        turn(&mut memory, "second", "b");
+
+        memory.trim_to_budget(100);
+
+        assert_eq!(memory.turn_count(), 1);
+        assert_eq!(memory.messages()[0].first_text(), Some("second"));
+    }
+
+    #[test]
+    fn test_take_older_turns_and_prepend_summary() {
+        let mut memory = Memory::new();
+        turn(&mut memory, "first", "a");
+        turn(&mut memory, "second", "b");
+
+        let older = memory.take_older_turns(1);
+        assert_eq!(older.len(), 4);
+
+        memory.prepend_summary("user asked about a");
+        let first = &memory.messages()[0];
+        assert!(first.first_text().unwrap().contains("user asked about a"));
+        assert_eq!(first.content.len(), 2);
+    }
 }
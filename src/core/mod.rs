@@ -1,11 +1,19 @@
+pub mod attachment;
 pub mod augmented_llm;
+pub mod context;
 pub mod error;
 pub mod llm;
 pub mod memory;
 pub mod metadata;
+pub mod observer;
 pub mod prompt;
+pub mod run_state;
+pub mod structured;
 pub mod types;
 
 pub use augmented_llm::{AugmentedLLM, LoopConfig};
 pub use error::{AgentError, Result};
 pub use llm::LLM;
+pub use observer::AgentObserver;
+pub use run_state::{RunControl, RunState};
+pub use structured::{complete_json, complete_structured};
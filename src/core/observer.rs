@@ -0,0 +1,20 @@
+use super::run_state::RunState;
+use super::types::{Message, Usage};
+use crate::permission::PermissionRequest;
+use crate::tools::result::ToolResult;
+
+pub trait AgentObserver: Send + Sync {
+    fn on_message(&self, _message: &Message) {}
+
+    fn on_text_delta(&self, _text: &str) {}
+
+    fn on_tool_start(&self, _name: &str, _input: &serde_json::Value) {}
+
+    fn on_tool_end(&self, _name: &str, _result: &ToolResult) {}
+
+    fn on_token_usage(&self, _usage: &Usage) {}
+
+    fn on_permission_request(&self, _request: &PermissionRequest) {}
+
+    fn on_state_change(&self, _state: RunState) {}
+}
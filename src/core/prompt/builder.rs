@@ -1,12 +1,14 @@
 use super::env::EnvironmentInfo;
 use super::template::TemplateType;
 use crate::tools::ToolRegistry;
+use crate::tools::screening::UNTRUSTED_CONTENT_NOTE;
 
 pub struct PromptBuilder {
     env_info: EnvironmentInfo,
     model_name: Option<String>,
     model_id: Option<String>,
     template_type: TemplateType,
+    language: Option<String>,
     include_git_status: bool,
 }
 
@@ -18,6 +20,7 @@ impl PromptBuilder {
             model_name: None,
             model_id: None,
             template_type: TemplateType::default(),
+            language: None,
             include_git_status: true,
         }
     }
@@ -35,6 +38,12 @@ impl PromptBuilder {
         self
     }
 
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language.filter(|language| !language.trim().is_empty());
+        self
+    }
+
     #[must_use]
     pub fn build(&self, _tools: &ToolRegistry) -> String {
         let mut prompt = String::new();
@@ -46,6 +55,9 @@ impl PromptBuilder {
         prompt.push_str("<env>\n");
         prompt.push_str(&self.env_info.format());
         prompt.push_str("\n</env>\n");
+        prompt.push('\n');
+        prompt.push_str(UNTRUSTED_CONTENT_NOTE);
+        prompt.push('\n');
 
         if let (Some(name), Some(id)) = (&self.model_name, &self.model_id) {
             match self.template_type {
@@ -63,6 +75,12 @@ impl PromptBuilder {
             }
         }
 
+        if let Some(language) = &self.language {
+            prompt.push_str("\n# Language\nRespond to the user in ");
+            prompt.push_str(language);
+            prompt.push_str(", whatever language their messages or the code are written in. Keep code, identifiers, file paths, commands and tool inputs unchanged.\n");
+        }
+
         if self.include_git_status
             && let Some(git_status) = self.env_info.format_git_status()
         {
@@ -132,6 +150,20 @@ mod tests {
         assert!(prompt.contains("gpt-4-turbo"));
     }
 
+    #[test]
+    fn test_prompt_builder_language() {
+        let tools = ToolRegistry::new();
+        let prompt = PromptBuilder::new()
+            .with_language(Some("Spanish".to_string()))
+            .build(&tools);
+        assert!(prompt.contains("# Language\nRespond to the user in Spanish"));
+
+        let prompt = PromptBuilder::new()
+            .with_language(Some(" ".to_string()))
+            .build(&tools);
+        assert!(!prompt.contains("# Language"));
+    }
+
     #[test]
     fn test_template_type_selection() {
         let claude_builder = PromptBuilder::new().with_template(TemplateType::Claude);
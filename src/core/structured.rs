@@ -0,0 +1,119 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use super::error::{AgentError, Result};
+use super::llm::LLM;
+use super::types::{CompletionRequest, CompletionResponse, ContentBlock, ResponseFormat};
+
+pub async fn complete_json(
+    llm: &dyn LLM,
+    request: CompletionRequest,
+    format: impl Into<ResponseFormat>,
+) -> Result<serde_json::Value> {
+    let format = format.into();
+    let name = format.name.clone();
+    let response = llm.complete(request.with_response_format(format)).await?;
+    extract_json(&response, &name)
+}
+
+pub async fn complete_structured<T>(llm: &dyn LLM, request: CompletionRequest) -> Result<T>
+where
+    T: JsonSchema + DeserializeOwned,
+{
+    let value = complete_json(llm, request, ResponseFormat::for_type::<T>()).await?;
+    serde_json::from_value(value)
+        .map_err(|e| AgentError::Provider(format!("Structured output does not match schema: {e}")))
+}
+
+pub fn extract_json(response: &CompletionResponse, name: &str) -> Result<serde_json::Value> {
+    let forced_tool = response
+        .message
+        .content
+        .iter()
+        .find_map(|block| match block {
+            ContentBlock::ToolUse {
+                name: tool, input, ..
+            } if tool == name => Some(input.clone()),
+            _ => None,
+        });
+    if let Some(value) = forced_tool {
+        return Ok(value);
+    }
+
+    let text: String = response
+        .message
+        .content
+        .iter()
+        .filter_map(ContentBlock::as_text)
+        .collect();
+
+    serde_json::from_str(strip_code_fence(&text))
+        .map_err(|e| AgentError::Provider(format!("Model did not return valid JSON output: {e}")))
+}
+
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map_or(trimmed, str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Message;
+    use crate::providers::mock::{MockLLM, MockResponse};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Verdict {
+        ok: bool,
+        reason: String,
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new(vec![Message::user("Is it ok?")])
+    }
+
+    #[tokio::test]
+    async fn test_structured_from_forced_tool() {
+        let mock = MockLLM::new().with_response(MockResponse::tool_use(
+            ResponseFormat::DEFAULT_NAME,
+            serde_json::json!({"ok": true, "reason": "fine"}),
+        ));
+
+        let verdict: Verdict = complete_structured(&mock, request()).await.unwrap();
+
+        assert_eq!(
+            verdict,
+            Verdict {
+                ok: true,
+                reason: "fine".to_string()
+            }
+        );
+        let sent = &mock.request_history()[0];
+        assert!(sent.response_format.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_structured_from_fenced_text() {
+        let mock = MockLLM::new().with_response(MockResponse::text(
+            "```json\n{\"ok\": false, \"reason\": \"broken\"}\n```",
+        ));
+
+        let verdict: Verdict = complete_structured(&mock, request()).await.unwrap();
+
+        assert!(!verdict.ok);
+    }
+
+    #[tokio::test]
+    async fn test_structured_schema_mismatch() {
+        let mock = MockLLM::new().with_response(MockResponse::text(r#"{"ok": "yes"}"#));
+
+        let result: Result<Verdict> = complete_structured(&mock, request()).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -41,6 +42,11 @@ pub enum ContentBlock {
     Image {
         source: ImageSource,
     },
+    Document {
+        source: ImageSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -101,6 +107,21 @@ impl ContentBlock {
         }
     }
 
+    #[must_use]
+    pub fn document_base64(
+        media_type: impl Into<String>,
+        data: impl Into<String>,
+        title: impl Into<String>,
+    ) -> Self {
+        Self::Document {
+            source: ImageSource::Base64 {
+                media_type: media_type.into(),
+                data: data.into(),
+            },
+            title: Some(title.into()),
+        }
+    }
+
     #[must_use]
     pub const fn is_text(&self) -> bool {
         matches!(self, Self::Text { .. })
@@ -214,6 +235,51 @@ impl ToolDefinition {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseFormat {
+    pub name: String,
+    pub schema: serde_json::Value,
+    pub strict: bool,
+}
+
+impl ResponseFormat {
+    pub const DEFAULT_NAME: &'static str = "structured_output";
+
+    #[must_use]
+    pub fn new(schema: serde_json::Value) -> Self {
+        Self {
+            name: Self::DEFAULT_NAME.to_string(),
+            schema,
+            strict: false,
+        }
+    }
+
+    #[must_use]
+    pub fn for_type<T: schemars::JsonSchema>() -> Self {
+        let schema = serde_json::to_value(schemars::schema_for!(T))
+            .unwrap_or_else(|_| serde_json::json!({}));
+        Self::new(schema)
+    }
+
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+impl From<serde_json::Value> for ResponseFormat {
+    fn from(schema: serde_json::Value) -> Self {
+        Self::new(schema)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StopReason {
@@ -223,10 +289,12 @@ pub enum StopReason {
     StopSequence,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
 }
 
 impl Usage {
@@ -235,17 +303,100 @@ impl Usage {
         Self {
             input_tokens,
             output_tokens,
+            cost: None,
         }
     }
 
+    #[must_use]
+    pub const fn with_cost(mut self, cost: Option<f64>) -> Self {
+        self.cost = cost;
+        self
+    }
+
     #[must_use]
     pub const fn total(&self) -> u32 {
         self.input_tokens + self.output_tokens
     }
 
-    pub const fn add(&mut self, other: &Self) {
+    pub fn add(&mut self, other: &Self) {
         self.input_tokens += other.input_tokens;
         self.output_tokens += other.output_tokens;
+        if let Some(cost) = other.cost {
+            self.cost = Some(self.cost.unwrap_or_default() + cost);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub const ALL: [Self; 4] = [Self::Minimal, Self::Low, Self::Medium, Self::High];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+impl fmt::Display for ReasoningEffort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Verbosity {
+    pub const ALL: [Self; 3] = [Self::Low, Self::Medium, Self::High];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolChoice {
+    #[default]
+    Auto,
+    None,
+    Required,
+    Tool(String),
+}
+
+impl ToolChoice {
+    #[must_use]
+    pub const fn forces_tool(&self) -> bool {
+        matches!(self, Self::Required | Self::Tool(_))
     }
 }
 
@@ -254,9 +405,15 @@ pub struct CompletionRequest {
     pub messages: Vec<Message>,
     pub system_prompt: Option<String>,
     pub tools: Vec<ToolDefinition>,
+    pub tool_choice: ToolChoice,
     pub max_tokens: u32,
     pub temperature: f32,
+    pub top_p: Option<f32>,
     pub stop_sequences: Vec<String>,
+    pub response_format: Option<ResponseFormat>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub verbosity: Option<Verbosity>,
+    pub thinking_budget: Option<u32>,
 }
 
 impl CompletionRequest {
@@ -266,9 +423,15 @@ impl CompletionRequest {
             messages,
             system_prompt: None,
             tools: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             max_tokens: 4096,
             temperature: 1.0,
+            top_p: None,
             stop_sequences: Vec::new(),
+            response_format: None,
+            reasoning_effort: None,
+            verbosity: None,
+            thinking_budget: None,
         }
     }
 
@@ -284,6 +447,12 @@ impl CompletionRequest {
         self
     }
 
+    #[must_use]
+    pub fn with_tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.tool_choice = tool_choice;
+        self
+    }
+
     #[must_use]
     pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
         self.max_tokens = max_tokens;
@@ -295,6 +464,42 @@ impl CompletionRequest {
         self.temperature = temperature;
         self
     }
+
+    #[must_use]
+    pub const fn with_top_p(mut self, top_p: Option<f32>) -> Self {
+        self.top_p = top_p;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_reasoning_effort(mut self, effort: Option<ReasoningEffort>) -> Self {
+        self.reasoning_effort = effort;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_verbosity(mut self, verbosity: Option<Verbosity>) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_thinking_budget(mut self, budget: Option<u32>) -> Self {
+        self.thinking_budget = budget;
+        self
+    }
+
+    #[must_use]
+    pub fn with_stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.stop_sequences = stop_sequences;
+        self
+    }
+
+    #[must_use]
+    pub fn with_response_format(mut self, format: impl Into<ResponseFormat>) -> Self {
+        self.response_format = Some(format.into());
+        self
+    }
 }
 #[derive(Debug, Clone)]
 pub struct CompletionResponse {
@@ -415,6 +620,11 @@ mod tests {
         usage1.add(&usage2);
         assert_eq!(usage1.input_tokens, 120);
         assert_eq!(usage1.output_tokens, 80);
+        assert_eq!(usage1.cost, None);
+
+        usage1.add(&Usage::new(1, 1).with_cost(Some(0.25)));
+        usage1.add(&Usage::new(1, 1).with_cost(Some(0.5)));
+        assert_eq!(usage1.cost, Some(0.75));
     }
 
     #[test]
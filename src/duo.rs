@@ -0,0 +1,374 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::core::structured::complete_structured;
+use crate::core::types::{CompletionRequest, Message};
+use crate::ui::diff::unified_diff;
+
+const MAX_LISTED_FILES: usize = 400;
+const MAX_FILE_BYTES: usize = 100_000;
+const MAX_FILE_REQUESTS: usize = 2;
+const DUO_MAX_TOKENS: u32 = 16_000;
+
+pub const IMPLEMENTER_PROMPT: &str = "You are the implementer in a two-agent pair. Propose the smallest complete change that accomplishes the task. Return the full new content of every file you change or create; files you leave out stay as they are. If you need to see files that are not shown, list their paths in needs_files and leave changes empty. When the reviewer has comments, address every one of them in your next proposal.";
+pub const REVIEWER_PROMPT: &str = "You are the reviewer in a two-agent pair. Check the proposed diff against the task for bugs, missing cases, unintended changes and unclear code. Approve only when the change is correct and complete; otherwise list concrete, actionable comments for the implementer.";
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FileChange {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct Proposal {
+    pub summary: String,
+    #[serde(default)]
+    pub needs_files: Vec<String>,
+    #[serde(default)]
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct Verdict {
+    pub approved: bool,
+    #[serde(default)]
+    pub comments: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuoEvent {
+    Proposed {
+        round: usize,
+        summary: String,
+        files: Vec<String>,
+    },
+    Reviewed {
+        round: usize,
+        approved: bool,
+        comments: String,
+    },
+}
+
+impl DuoEvent {
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Proposed {
+                round,
+                summary,
+                files,
+            } => format!(
+                "Round {round}: implementer proposes changes to {}\n{summary}",
+                files.join(", ")
+            ),
+            Self::Reviewed {
+                round,
+                approved: true,
+                ..
+            } => format!("Round {round}: reviewer approved"),
+            Self::Reviewed {
+                round, comments, ..
+            } => format!("Round {round}: reviewer requests changes\n{comments}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFile {
+    pub path: String,
+    pub old_content: String,
+    pub new_content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuoOutcome {
+    pub approved: bool,
+    pub rounds: usize,
+    pub applied: Vec<AppliedFile>,
+}
+
+impl DuoOutcome {
+    #[must_use]
+    pub fn render(&self) -> String {
+        let rounds = if self.rounds == 1 { "round" } else { "rounds" };
+        if !self.approved {
+            return format!(
+                "No approved change after {} {rounds}; nothing was applied",
+                self.rounds
+            );
+        }
+        let paths: Vec<&str> = self.applied.iter().map(|f| f.path.as_str()).collect();
+        format!(
+            "Approved after {} {rounds}; applied changes to {}",
+            self.rounds,
+            paths.join(", ")
+        )
+    }
+}
+
+pub struct Duo<'a> {
+    implementer: &'a dyn LLM,
+    reviewer: &'a dyn LLM,
+    root: PathBuf,
+    max_rounds: usize,
+    implementer_prompt: String,
+    reviewer_prompt: String,
+    listing: Vec<String>,
+}
+
+impl<'a> Duo<'a> {
+    #[must_use]
+    pub fn new(implementer: &'a dyn LLM, reviewer: &'a dyn LLM, root: impl Into<PathBuf>) -> Self {
+        Self {
+            implementer,
+            reviewer,
+            root: root.into(),
+            max_rounds: 3,
+            implementer_prompt: IMPLEMENTER_PROMPT.to_string(),
+            reviewer_prompt: REVIEWER_PROMPT.to_string(),
+            listing: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn with_prompts(mut self, implementer: Option<String>, reviewer: Option<String>) -> Self {
+        if let Some(prompt) = implementer {
+            self.implementer_prompt = prompt;
+        }
+        if let Some(prompt) = reviewer {
+            self.reviewer_prompt = prompt;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_listing(mut self, mut listing: Vec<String>) -> Self {
+        listing.truncate(MAX_LISTED_FILES);
+        self.listing = listing;
+        self
+    }
+
+    pub async fn run(&self, task: &str, mut on_event: impl FnMut(DuoEvent)) -> Result<DuoOutcome> {
+        let mut context: BTreeMap<String, String> = BTreeMap::new();
+        for path in self
+            .listing
+            .iter()
+            .filter(|path| task.contains(path.as_str()))
+        {
+            self.load(&mut context, path);
+        }
+
+        let mut feedback: Option<String> = None;
+        for round in 1..=self.max_rounds {
+            let mut proposal = self.propose(task, &context, feedback.as_deref()).await?;
+            for _ in 0..MAX_FILE_REQUESTS {
+                if !proposal.changes.is_empty() || proposal.needs_files.is_empty() {
+                    break;
+                }
+                for path in &proposal.needs_files {
+                    self.load(&mut context, path);
+                }
+                proposal = self.propose(task, &context, feedback.as_deref()).await?;
+            }
+
+            let patch = self.patch(&proposal.changes)?;
+            on_event(DuoEvent::Proposed {
+                round,
+                summary: proposal.summary.clone(),
+                files: proposal.changes.iter().map(|c| c.path.clone()).collect(),
+            });
+
+            let verdict = self.review(task, &proposal.summary, &patch).await?;
+            on_event(DuoEvent::Reviewed {
+                round,
+                approved: verdict.approved,
+                comments: verdict.comments.clone(),
+            });
+            if verdict.approved {
+                return Ok(DuoOutcome {
+                    approved: true,
+                    rounds: round,
+                    applied: self.apply(&proposal.changes)?,
+                });
+            }
+
+            feedback = Some(format!(
+                "Your previous proposal:\n{patch}\nReviewer comments:\n{}",
+                verdict.comments
+            ));
+        }
+
+        Ok(DuoOutcome {
+            approved: false,
+            rounds: self.max_rounds,
+            applied: Vec::new(),
+        })
+    }
+
+    async fn propose(
+        &self,
+        task: &str,
+        files: &BTreeMap<String, String>,
+        feedback: Option<&str>,
+    ) -> Result<Proposal> {
+        let mut content = format!("Task:\n{task}\n");
+        if !self.listing.is_empty() {
+            let _ = write!(
+                content,
+                "\nFiles in the project:\n{}\n",
+                self.listing.join("\n")
+            );
+        }
+        for (path, text) in files {
+            let _ = write!(content, "\n--- {path}\n{text}\n");
+        }
+        if let Some(feedback) = feedback {
+            let _ = write!(content, "\n{feedback}\n");
+        }
+        let request = CompletionRequest::new(vec![Message::user(content)])
+            .with_system_prompt(self.implementer_prompt.clone())
+            .with_max_tokens(DUO_MAX_TOKENS);
+        complete_structured(self.implementer, request).await
+    }
+
+    async fn review(&self, task: &str, summary: &str, patch: &str) -> Result<Verdict> {
+        let content = format!("Task:\n{task}\n\nImplementer summary:\n{summary}\n\nDiff:\n{patch}");
+        let request = CompletionRequest::new(vec![Message::user(content)])
+            .with_system_prompt(self.reviewer_prompt.clone())
+            .with_max_tokens(DUO_MAX_TOKENS)
+            .with_temperature(0.0);
+        complete_structured(self.reviewer, request).await
+    }
+
+    fn resolve(&self, path: &str) -> Result<PathBuf> {
+        let relative = Path::new(path);
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(AgentError::InvalidToolInput {
+                tool: "duo".to_string(),
+                reason: format!("Proposed path {path} is outside the project"),
+            });
+        }
+        Ok(self.root.join(relative))
+    }
+
+    fn load(&self, files: &mut BTreeMap<String, String>, path: &str) {
+        if files.contains_key(path) {
+            return;
+        }
+        let content = self
+            .resolve(path)
+            .ok()
+            .and_then(|full| std::fs::read_to_string(full).ok())
+            .filter(|text| text.len() <= MAX_FILE_BYTES)
+            .unwrap_or_else(|| "[file does not exist or is too large to show]".to_string());
+        files.insert(path.to_string(), content);
+    }
+
+    fn patch(&self, changes: &[FileChange]) -> Result<String> {
+        let mut patch = String::new();
+        for change in changes {
+            let old = std::fs::read_to_string(self.resolve(&change.path)?).unwrap_or_default();
+            patch.push_str(&unified_diff(&change.path, &old, &change.content));
+        }
+        Ok(patch)
+    }
+
+    fn apply(&self, changes: &[FileChange]) -> Result<Vec<AppliedFile>> {
+        let mut applied = Vec::new();
+        for change in changes {
+            let full = self.resolve(&change.path)?;
+            let old_content = std::fs::read_to_string(&full).unwrap_or_default();
+            if let Some(parent) = full.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&full, &change.content)?;
+            applied.push(AppliedFile {
+                path: change.path.clone(),
+                old_content,
+                new_content: change.content.clone(),
+            });
+        }
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_duo_loops_until_approved_then_applies() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.txt"), "one\n").unwrap();
+        let implementer = ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::text(
+                r#"{"summary":"first try","changes":[{"path":"a.txt","content":"two\n"}]}"#,
+            ),
+            ScenarioTurn::text(
+                r#"{"summary":"second try","changes":[{"path":"a.txt","content":"three\n"}]}"#,
+            ),
+        ]));
+        let reviewer = ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn::text(r#"{"approved":false,"comments":"use three"}"#),
+            ScenarioTurn::text(r#"{"approved":true}"#),
+        ]));
+        let duo =
+            Duo::new(&implementer, &reviewer, temp.path()).with_listing(vec!["a.txt".to_string()]);
+
+        let mut events = Vec::new();
+        let outcome = duo
+            .run("change a.txt", |event| events.push(event))
+            .await
+            .unwrap();
+
+        assert!(outcome.approved);
+        assert_eq!(outcome.rounds, 2);
+        assert_eq!(outcome.applied[0].old_content, "one\n");
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a.txt")).unwrap(),
+            "three\n"
+        );
+        assert_eq!(events.len(), 4);
+        let first = implementer.request_history()[0].messages[0]
+            .first_text()
+            .unwrap()
+            .to_string();
+        assert!(first.contains("--- a.txt\none"));
+        let second = implementer.request_history()[1].messages[0]
+            .first_text()
+            .unwrap()
+            .to_string();
+        assert!(second.contains("use three") && second.contains("+two"));
+    }
+
+    #[tokio::test]
+    async fn test_duo_rejects_paths_outside_project() {
+        let temp = TempDir::new().unwrap();
+        let implementer = ScenarioLLM::new(Scenario::new(vec![ScenarioTurn::text(
+            r#"{"summary":"escape","changes":[{"path":"../x","content":""}]}"#,
+        )]));
+        let reviewer = ScenarioLLM::new(Scenario::new(Vec::new()));
+
+        let result = Duo::new(&implementer, &reviewer, temp.path())
+            .run("task", |_| {})
+            .await;
+
+        assert!(result.is_err());
+    }
+}
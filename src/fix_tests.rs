@@ -0,0 +1,280 @@
+use regex::Regex;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::sync::LazyLock;
+
+const MAX_FAILURES: usize = 20;
+const MAX_DETAIL_CHARS: usize = 2_000;
+const MAX_TAIL_CHARS: usize = 4_000;
+
+#[allow(clippy::expect_used)]
+static FAILURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:test (\S+) \.\.\. FAILED|--- FAIL: (\S+)(?: \(.*\))?|FAILED (\S+?)(?: - .*)?|\s*● (.+))\s*$",
+    )
+    .expect("valid failure regex")
+});
+
+#[allow(clippy::expect_used)]
+static DETAIL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^---- (\S+) stdout ----$").expect("valid detail regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    pub name: String,
+    pub details: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRun {
+    pub passed: bool,
+    pub output: String,
+    pub failures: Vec<TestFailure>,
+}
+
+impl TestRun {
+    #[must_use]
+    pub fn from_output(passed: bool, output: String) -> Self {
+        let failures = if passed {
+            Vec::new()
+        } else {
+            parse_failures(&output)
+        };
+        Self {
+            passed,
+            output,
+            failures,
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.passed {
+            return "all tests pass".to_string();
+        }
+        match self.failures.len() {
+            0 => "tests failed".to_string(),
+            1 => "1 failing test".to_string(),
+            n => format!("{n} failing tests"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixTestsReport {
+    pub command: String,
+    pub passed: bool,
+    pub rounds: usize,
+    pub remaining: Vec<String>,
+    pub diff: String,
+}
+
+impl FixTestsReport {
+    #[must_use]
+    pub fn render(&self) -> String {
+        let rounds = if self.rounds == 1 { "round" } else { "rounds" };
+        let mut text = if self.passed && self.rounds == 0 {
+            format!("`{}` already passes", self.command)
+        } else if self.passed {
+            format!(
+                "`{}` passes after {} fix {rounds}",
+                self.command, self.rounds
+            )
+        } else {
+            format!(
+                "`{}` still fails after {} fix {rounds}",
+                self.command, self.rounds
+            )
+        };
+        if !self.remaining.is_empty() {
+            let _ = write!(text, "\nStill failing: {}", self.remaining.join(", "));
+        }
+        if self.diff.trim().is_empty() {
+            text.push_str("\nNo files changed");
+        } else {
+            let _ = write!(text, "\nChanges:\n{}", self.diff.trim_end());
+        }
+        text
+    }
+}
+
+#[must_use]
+pub fn detect_test_command(dir: &Path) -> Option<String> {
+    [
+        ("Cargo.toml", "cargo test"),
+        ("package.json", "npm test"),
+        ("go.mod", "go test ./..."),
+        ("pyproject.toml", "pytest"),
+        ("pytest.ini", "pytest"),
+        ("Makefile", "make test"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| dir.join(marker).exists())
+    .map(|(_, command)| command.to_string())
+}
+
+pub async fn run_tests(command: &str, dir: &Path) -> io::Result<TestRun> {
+    let output = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(TestRun::from_output(output.status.success(), text))
+}
+
+#[must_use]
+pub fn parse_failures(output: &str) -> Vec<TestFailure> {
+    let mut failures: Vec<TestFailure> = Vec::new();
+    for line in output.lines() {
+        if let Some(caps) = FAILURE_RE.captures(line)
+            && let Some(name) = (1..=4).find_map(|i| caps.get(i))
+        {
+            let name = name.as_str().trim().to_string();
+            if !failures.iter().any(|f| f.name == name) {
+                failures.push(TestFailure {
+                    name,
+                    details: String::new(),
+                });
+            }
+        }
+    }
+
+    let mut current: Option<usize> = None;
+    for line in output.lines() {
+        if let Some(caps) = DETAIL_RE.captures(line) {
+            current = failures.iter().position(|f| f.name == caps[1]);
+            continue;
+        }
+        if line.starts_with("failures:") || line.starts_with("test result:") {
+            current = None;
+        }
+        if let Some(failure) = current.and_then(|i| failures.get_mut(i))
+            && failure.details.len() < MAX_DETAIL_CHARS
+        {
+            failure.details.push_str(line);
+            failure.details.push('\n');
+        }
+    }
+
+    failures.truncate(MAX_FAILURES);
+    failures
+}
+
+fn tail(text: &str, max_chars: usize) -> &str {
+    if text.len() <= max_chars {
+        return text;
+    }
+    let start = text.ceil_char_boundary(text.len() - max_chars);
+    &text[start..]
+}
+
+#[must_use]
+pub fn fix_prompt(command: &str, run: &TestRun, round: usize, max_rounds: usize) -> String {
+    let mut prompt = format!(
+        "The test command `{command}` is failing (fix round {round} of {max_rounds}). \
+         Find the root cause of each failure below and fix it with the file editing tools. \
+         Fix the code under test rather than weakening or deleting tests, unless a test is \
+         clearly wrong. Do not run the full test suite yourself; it is re-run automatically \
+         after you finish. Reply with a short summary of what you changed.\n"
+    );
+    if run.failures.is_empty() {
+        let _ = write!(
+            prompt,
+            "\nNo individual failures could be identified. End of the output:\n```\n{}\n```",
+            tail(&run.output, MAX_TAIL_CHARS).trim_end()
+        );
+        return prompt;
+    }
+    for failure in &run.failures {
+        let _ = write!(prompt, "\n## {}\n", failure.name);
+        if !failure.details.trim().is_empty() {
+            let _ = write!(prompt, "```\n{}\n```\n", failure.details.trim_end());
+        }
+    }
+    if run.failures.iter().all(|f| f.details.trim().is_empty()) {
+        let _ = write!(
+            prompt,
+            "\nEnd of the output:\n```\n{}\n```",
+            tail(&run.output, MAX_TAIL_CHARS).trim_end()
+        );
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_OUTPUT: &str = "running 2 tests
+test parser::tests::test_ok ... ok
+test parser::tests::test_empty ... FAILED
+
+failures:
+
+---- parser::tests::test_empty stdout ----
+thread 'parser::tests::test_empty' panicked at src/parser.rs:10:5:
+assertion `left == right` failed
+  left: 1
+ right: 0
+
+failures:
+    parser::tests::test_empty
+
+test result: FAILED. 1 passed; 1 failed
+";
+
+    #[test]
+    fn test_parse_cargo_failures() {
+        let failures = parse_failures(CARGO_OUTPUT);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "parser::tests::test_empty");
+        assert!(failures[0].details.contains("src/parser.rs:10:5"));
+        assert!(failures[0].details.contains("right: 0"));
+        assert!(!failures[0].details.contains("test result"));
+    }
+
+    #[test]
+    fn test_parse_other_runners() {
+        let output = "--- FAIL: TestAdd (0.00s)\nFAILED tests/test_a.py::test_b - AssertionError\n  ● math › adds numbers\n";
+        let names: Vec<String> = parse_failures(output).into_iter().map(|f| f.name).collect();
+        assert_eq!(
+            names,
+            vec!["TestAdd", "tests/test_a.py::test_b", "math › adds numbers"]
+        );
+    }
+
+    #[test]
+    fn test_fix_prompt_falls_back_to_output_tail() {
+        let run = TestRun::from_output(false, "error[E0425]: cannot find value `x`".to_string());
+        assert!(run.failures.is_empty());
+        let prompt = fix_prompt("cargo test", &run, 1, 3);
+        assert!(prompt.contains("round 1 of 3"));
+        assert!(prompt.contains("cannot find value"));
+
+        let run = TestRun::from_output(false, CARGO_OUTPUT.to_string());
+        let prompt = fix_prompt("cargo test", &run, 2, 3);
+        assert!(prompt.contains("## parser::tests::test_empty"));
+        assert!(!prompt.contains("End of the output"));
+        assert_eq!(run.summary(), "1 failing test");
+    }
+
+    #[test]
+    fn test_report_render() {
+        let report = FixTestsReport {
+            command: "cargo test".to_string(),
+            passed: false,
+            rounds: 3,
+            remaining: vec!["a::b".to_string()],
+            diff: " src/a.rs | 2 +-\n".to_string(),
+        };
+        assert_eq!(
+            report.render(),
+            "`cargo test` still fails after 3 fix rounds\nStill failing: a::b\nChanges:\n src/a.rs | 2 +-"
+        );
+    }
+}
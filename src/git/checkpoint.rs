@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::{Git, GitResult};
+use crate::tools::fs::format_time_ago;
+
+const CHECKPOINT_REF: &str = "refs/smith/checkpoints";
+const IDENTITY: [(&str, &str); 4] = [
+    ("GIT_AUTHOR_NAME", "smith"),
+    ("GIT_AUTHOR_EMAIL", "smith@localhost"),
+    ("GIT_COMMITTER_NAME", "smith"),
+    ("GIT_COMMITTER_EMAIL", "smith@localhost"),
+];
+const MAX_LISTED: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub id: String,
+    pub created: u64,
+    pub message: String,
+}
+
+impl Git {
+    fn with_scratch_index<T>(
+        &self,
+        f: impl FnOnce(&[(&str, &OsStr)]) -> GitResult<T>,
+    ) -> GitResult<T> {
+        let name = format!("smith-checkpoint-{}.index", std::process::id());
+        let path = self
+            .dir
+            .join(self.run(&["rev-parse", "--git-path", &name])?.trim());
+        let index = self
+            .dir
+            .join(self.run(&["rev-parse", "--git-path", "index"])?.trim());
+        if index.exists() {
+            fs::copy(&index, &path)?;
+        }
+        let result = f(&[("GIT_INDEX_FILE", path.as_os_str())]);
+        let _ = fs::remove_file(&path);
+        result
+    }
+
+    pub fn worktree_tree(&self) -> GitResult<String> {
+        let root = self.root()?;
+        root.with_scratch_index(|env| {
+            root.run_with_env("git", &["add", "-A"], env)?;
+            Ok(root
+                .run_with_env("git", &["write-tree"], env)?
+                .trim()
+                .to_string())
+        })
+    }
+
+    fn checkpoint_head(&self) -> Option<String> {
+        self.run(&["rev-parse", "--verify", "--quiet", CHECKPOINT_REF])
+            .ok()
+            .map(|id| id.trim().to_string())
+    }
+
+    pub fn save_checkpoint(&self, tree: &str, message: &str) -> GitResult<bool> {
+        let previous = self.checkpoint_head();
+        if let Some(previous) = &previous
+            && self
+                .run(&["rev-parse", &format!("{previous}^{{tree}}")])?
+                .trim()
+                == tree
+        {
+            return Ok(false);
+        }
+        let mut args = vec!["commit-tree", tree, "-m", message];
+        if let Some(previous) = &previous {
+            args.extend(["-p", previous]);
+        }
+        let env: Vec<(&str, &OsStr)> = IDENTITY
+            .iter()
+            .map(|(name, value)| (*name, OsStr::new(value)))
+            .collect();
+        let id = self.run_with_env("git", &args, &env)?;
+        self.run(&["update-ref", CHECKPOINT_REF, id.trim()])?;
+        Ok(true)
+    }
+
+    pub fn checkpoints(&self) -> GitResult<Vec<Checkpoint>> {
+        if self.checkpoint_head().is_none() {
+            return Ok(Vec::new());
+        }
+        let log = self.run(&["log", "--format=%H%x09%ct%x09%s", CHECKPOINT_REF])?;
+        Ok(log
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some(Checkpoint {
+                    id: parts.next()?.to_string(),
+                    created: parts.next()?.parse().ok()?,
+                    message: parts.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    pub fn restore_checkpoint(&self, id: &str) -> GitResult<String> {
+        let root = self.root()?;
+        let current = root.worktree_tree()?;
+        let stat = root.run(&["diff", "--stat", &current, id])?;
+        if stat.trim().is_empty() {
+            return Ok(stat);
+        }
+        root.save_checkpoint(
+            &current,
+            &format!("before restoring {}", &id[..id.len().min(8)]),
+        )?;
+        root.with_scratch_index(|env| {
+            root.run_with_env("git", &["read-tree", id], env)?;
+            root.run_with_env("git", &["checkout-index", "--all", "--force"], env)
+        })?;
+
+        let kept = root.run(&["ls-tree", "-r", "-z", "--name-only", id])?;
+        let kept: HashSet<&str> = kept.split('\0').collect();
+        let previous = root.run(&["ls-tree", "-r", "-z", "--name-only", &current])?;
+        for path in previous
+            .split('\0')
+            .filter(|path| !path.is_empty() && !kept.contains(path))
+        {
+            let _ = fs::remove_file(root.dir.join(path));
+        }
+        Ok(stat)
+    }
+}
+
+#[must_use]
+pub fn format_checkpoints(checkpoints: &[Checkpoint]) -> String {
+    let mut output = format!("{} checkpoint(s), newest first:", checkpoints.len());
+    for (index, checkpoint) in checkpoints.iter().take(MAX_LISTED).enumerate() {
+        let _ = write!(
+            output,
+            "\n{:>3}. {:<8} {} {}",
+            index + 1,
+            format_time_ago(UNIX_EPOCH + Duration::from_secs(checkpoint.created)),
+            &checkpoint.id[..checkpoint.id.len().min(8)],
+            checkpoint.message
+        );
+    }
+    if checkpoints.len() > MAX_LISTED {
+        let _ = write!(output, "\n  … {} older", checkpoints.len() - MAX_LISTED);
+    }
+    output.push_str("\n\n/checkpoints restore <n> puts the working tree back to that point");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::repo;
+
+    #[test]
+    fn test_restore_checkpoint_rolls_back_worktree() {
+        let (temp, git) = repo();
+        fs::write(temp.path().join("a.txt"), "one\n").unwrap();
+        let tree = git.worktree_tree().unwrap();
+        assert!(git.save_checkpoint(&tree, "before: edit a").unwrap());
+        assert!(!git.save_checkpoint(&tree, "again").unwrap());
+
+        fs::write(temp.path().join("a.txt"), "two\n").unwrap();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("src/b.txt"), "new\n").unwrap();
+        let checkpoints = git.checkpoints().unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].message, "before: edit a");
+
+        let stat = git.restore_checkpoint(&checkpoints[0].id).unwrap();
+        assert!(stat.contains("a.txt"));
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).unwrap(),
+            "one\n"
+        );
+        assert!(!temp.path().join("src/b.txt").exists());
+        assert!(
+            git.run(&["status", "--porcelain"])
+                .unwrap()
+                .contains("?? a.txt")
+        );
+
+        let checkpoints = git.checkpoints().unwrap();
+        assert_eq!(checkpoints.len(), 2);
+        assert!(checkpoints[0].message.starts_with("before restoring"));
+        assert!(format_checkpoints(&checkpoints).contains("  2. "));
+    }
+}
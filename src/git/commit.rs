@@ -0,0 +1,93 @@
+use std::fmt::Write as _;
+
+use super::Changes;
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::core::types::{CompletionRequest, Message};
+
+const MAX_DIFF_CHARS: usize = 24_000;
+const COMMIT_MAX_TOKENS: u32 = 400;
+const COMMIT_PROMPT: &str = "Write a git commit message in the Conventional Commits format for the changes below. The first line is `type(optional scope): summary` in the imperative mood, at most 72 characters, using one of feat, fix, refactor, perf, docs, test, build, ci, chore or style. Add a blank line and a short wrapped body only when the change needs explaining. Reply with the commit message only.";
+
+pub async fn generate_commit_message(llm: &dyn LLM, changes: &Changes) -> Result<String> {
+    let mut diff: String = changes.diff.chars().take(MAX_DIFF_CHARS).collect();
+    if diff.len() < changes.diff.len() {
+        diff.push_str("\n[diff truncated]");
+    }
+    let mut content = format!("Files changed:\n{}\n\n", changes.summary());
+    if !diff.is_empty() {
+        let _ = write!(content, "Diff:\n{diff}");
+    }
+
+    let request = CompletionRequest::new(vec![Message::user(content)])
+        .with_system_prompt(COMMIT_PROMPT)
+        .with_max_tokens(COMMIT_MAX_TOKENS)
+        .with_temperature(0.0);
+    let response = llm.complete(request).await?;
+    response
+        .message
+        .first_text()
+        .map(clean_commit_message)
+        .filter(|message| !message.is_empty())
+        .ok_or_else(|| AgentError::Provider("Model returned an empty commit message".to_string()))
+}
+
+#[must_use]
+pub fn clean_commit_message(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let unfenced = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map_or(trimmed, |inner| {
+            inner.split_once('\n').map_or(inner, |(_, body)| body)
+        });
+    unfenced
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+
+    #[test]
+    fn test_clean_commit_message() {
+        assert_eq!(
+            clean_commit_message("```text\nfix(tui): keep scroll position  \n\nBody.\n```\n"),
+            "fix(tui): keep scroll position\n\nBody."
+        );
+        assert_eq!(clean_commit_message("  feat: add x \n"), "feat: add x");
+    }
+
+    #[tokio::test]
+    async fn test_generate_commit_message_sends_diff() {
+        let llm = ScenarioLLM::new(Scenario::new(vec![ScenarioTurn::text(
+            "```\nfeat: add greeting\n```",
+        )]));
+        let changes = Changes {
+            staged: true,
+            diff: "+hello".to_string(),
+            stat: " greet.rs | 1 +".to_string(),
+            untracked: Vec::new(),
+        };
+
+        let message = generate_commit_message(&llm, &changes).await.unwrap();
+
+        assert_eq!(message, "feat: add greeting");
+        let request = &llm.request_history()[0];
+        assert!(
+            request
+                .system_prompt
+                .as_deref()
+                .unwrap()
+                .contains("Conventional Commits")
+        );
+        let sent = request.messages[0].first_text().unwrap();
+        assert!(sent.contains("greet.rs | 1 +") && sent.contains("+hello"));
+    }
+}
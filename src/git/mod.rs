@@ -0,0 +1,273 @@
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+mod checkpoint;
+mod commit;
+mod review;
+mod worktree;
+
+pub use checkpoint::{Checkpoint, format_checkpoints};
+pub use commit::{clean_commit_message, generate_commit_message};
+pub use review::{
+    FilePatch, Finding, ReviewTarget, chunk_patches, parse_findings, review_diff, split_diff,
+};
+pub use worktree::Worktree;
+
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("{0} is not inside a git repository")]
+    NotARepository(PathBuf),
+
+    #[error("No changes to commit")]
+    NoChanges,
+
+    #[error("No changes to review")]
+    EmptyDiff,
+
+    #[error("Could not find a base branch (main or master); pass one explicitly")]
+    NoBaseBranch,
+
+    #[error("{program} {command} failed: {stderr}")]
+    Command {
+        program: String,
+        command: String,
+        stderr: String,
+    },
+}
+
+pub type GitResult<T> = std::result::Result<T, GitError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changes {
+    pub staged: bool,
+    pub diff: String,
+    pub stat: String,
+    pub untracked: Vec<String>,
+}
+
+impl Changes {
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut lines: Vec<String> = self.stat.lines().map(str::to_string).collect();
+        lines.extend(self.untracked.iter().map(|path| format!(" {path} (new)")));
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Git {
+    dir: PathBuf,
+}
+
+impl Git {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn run(&self, args: &[&str]) -> GitResult<String> {
+        self.run_program("git", args)
+    }
+
+    fn run_program(&self, program: &str, args: &[&str]) -> GitResult<String> {
+        self.run_with_env(program, args, &[])
+    }
+
+    fn run_with_env(
+        &self,
+        program: &str,
+        args: &[&str],
+        env: &[(&str, &OsStr)],
+    ) -> GitResult<String> {
+        let output = Command::new(program)
+            .args(args)
+            .envs(env.iter().copied())
+            .current_dir(&self.dir)
+            .output()?;
+        if !output.status.success() {
+            return Err(GitError::Command {
+                program: program.to_string(),
+                command: args.first().copied().unwrap_or_default().to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub fn default_base(&self) -> GitResult<String> {
+        ["main", "master", "origin/main", "origin/master"]
+            .into_iter()
+            .find(|branch| {
+                self.run(&["rev-parse", "--verify", "--quiet", branch])
+                    .is_ok()
+            })
+            .map(str::to_string)
+            .ok_or(GitError::NoBaseBranch)
+    }
+
+    pub fn diff_from(&self, base: &str) -> GitResult<String> {
+        let diff = self.run(&["diff", &format!("{base}...HEAD")])?;
+        if diff.trim().is_empty() {
+            return Err(GitError::EmptyDiff);
+        }
+        Ok(diff)
+    }
+
+    pub fn pr_diff(&self, number: u64) -> GitResult<String> {
+        let diff = self.run_program("gh", &["pr", "diff", &number.to_string()])?;
+        if diff.trim().is_empty() {
+            return Err(GitError::EmptyDiff);
+        }
+        Ok(diff)
+    }
+
+    pub fn snapshot(&self) -> GitResult<String> {
+        let stash = self.run(&["stash", "create"])?;
+        let rev = if stash.trim().is_empty() {
+            self.run(&["rev-parse", "HEAD"])?
+        } else {
+            stash
+        };
+        Ok(rev.trim().to_string())
+    }
+
+    pub fn diff_stat_since(&self, rev: &str) -> GitResult<String> {
+        self.run(&["diff", "--stat", rev])
+    }
+
+    pub fn tracked_files(&self) -> GitResult<Vec<String>> {
+        Ok(self
+            .run(&["ls-files", "--cached", "--others", "--exclude-standard"])?
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn root(&self) -> GitResult<Self> {
+        Ok(Self::new(
+            self.run(&["rev-parse", "--show-toplevel"])?.trim(),
+        ))
+    }
+
+    #[must_use]
+    pub fn is_repository(&self) -> bool {
+        self.run(&["rev-parse", "--git-dir"]).is_ok()
+    }
+
+    pub fn changes(&self) -> GitResult<Changes> {
+        if !self.is_repository() {
+            return Err(GitError::NotARepository(self.dir.clone()));
+        }
+
+        let staged = self.run(&["diff", "--cached"])?;
+        if !staged.trim().is_empty() {
+            return Ok(Changes {
+                staged: true,
+                diff: staged,
+                stat: self.run(&["diff", "--cached", "--stat"])?,
+                untracked: Vec::new(),
+            });
+        }
+
+        let diff = self.run(&["diff"])?;
+        let untracked: Vec<String> = self
+            .run(&["ls-files", "--others", "--exclude-standard"])?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if diff.trim().is_empty() && untracked.is_empty() {
+            return Err(GitError::NoChanges);
+        }
+        Ok(Changes {
+            staged: false,
+            stat: self.run(&["diff", "--stat"])?,
+            diff,
+            untracked,
+        })
+    }
+
+    pub fn commit(&self, message: &str, stage_all: bool) -> GitResult<String> {
+        if stage_all {
+            self.run(&["add", "-A"])?;
+        }
+        self.run(&["commit", "-q", "-m", message])?;
+        Ok(self.run(&["log", "-1", "--oneline"])?.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    pub(super) fn repo() -> (TempDir, Git) {
+        let temp = TempDir::new().unwrap();
+        let git = Git::new(temp.path());
+        git.run(&["init", "-q"]).unwrap();
+        git.run(&["config", "user.name", "Test"]).unwrap();
+        git.run(&["config", "user.email", "test@example.com"])
+            .unwrap();
+        (temp, git)
+    }
+
+    #[test]
+    fn test_changes_prefer_staged() {
+        let (temp, git) = repo();
+        assert!(matches!(git.changes(), Err(GitError::NoChanges)));
+
+        std::fs::write(temp.path().join("a.txt"), "one\n").unwrap();
+        std::fs::write(temp.path().join("b.txt"), "two\n").unwrap();
+        let changes = git.changes().unwrap();
+        assert!(!changes.staged);
+        assert_eq!(changes.untracked, vec!["a.txt", "b.txt"]);
+        assert_eq!(git.tracked_files().unwrap(), vec!["a.txt", "b.txt"]);
+        assert!(changes.summary().contains("a.txt (new)"));
+
+        git.run(&["add", "a.txt"]).unwrap();
+        let changes = git.changes().unwrap();
+        assert!(changes.staged);
+        assert!(changes.diff.contains("+one"));
+        assert!(!changes.diff.contains("+two"));
+    }
+
+    #[test]
+    fn test_commit_stages_dirty_tree() {
+        let (temp, git) = repo();
+        std::fs::write(temp.path().join("a.txt"), "one\n").unwrap();
+
+        let summary = git.commit("feat: add a\n\nFirst file.", true).unwrap();
+
+        assert!(summary.ends_with("feat: add a"));
+        assert!(matches!(git.changes(), Err(GitError::NoChanges)));
+        git.run(&["branch", "-M", "main"]).unwrap();
+        git.run(&["checkout", "-q", "-b", "feature"]).unwrap();
+        std::fs::write(temp.path().join("a.txt"), "two\n").unwrap();
+        git.commit("fix: change a", true).unwrap();
+        assert_eq!(git.default_base().unwrap(), "main");
+        assert!(git.diff_from("main").unwrap().contains("+two"));
+        assert!(matches!(git.diff_from("HEAD"), Err(GitError::EmptyDiff)));
+
+        std::fs::write(temp.path().join("a.txt"), "three\n").unwrap();
+        let snapshot = git.snapshot().unwrap();
+        assert!(git.diff_stat_since(&snapshot).unwrap().is_empty());
+        std::fs::write(temp.path().join("a.txt"), "four\n").unwrap();
+        assert!(git.diff_stat_since(&snapshot).unwrap().contains("a.txt"));
+
+        let outside = TempDir::new().unwrap();
+        assert!(matches!(
+            Git::new(outside.path()).changes(),
+            Err(GitError::NotARepository(_))
+        ));
+    }
+}
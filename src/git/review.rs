@@ -0,0 +1,241 @@
+use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+
+use super::{Git, GitResult};
+use crate::config::Severity;
+use crate::core::error::Result;
+use crate::core::llm::LLM;
+use crate::core::types::{CompletionRequest, Message};
+
+const MAX_CHUNK_CHARS: usize = 30_000;
+const REVIEW_MAX_TOKENS: u32 = 2000;
+const REVIEW_PROMPT: &str = "You are reviewing a code change. Report bugs, security problems, missing error handling, risky behaviour changes and unclear code in the diff below. Write one finding per line in the form `SEVERITY path:line - explanation`, where SEVERITY is HIGH, MEDIUM or LOW and line is a line number in the new version of the file. Only report problems in the changed lines, skip praise and style nits a formatter would fix, and reply with `NONE` if there is nothing worth raising.";
+
+#[allow(clippy::expect_used)]
+static FINDING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:[-*]\s*)?\**\[?(high|medium|low)\]?\**:?\s+`?([^\s`:]+)`?(?::(\d+))?\s*(?:[-:–—]\s*)?(.+)$",
+    )
+    .expect("valid finding regex")
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewTarget {
+    Base(Option<String>),
+    PullRequest(u64),
+}
+
+impl ReviewTarget {
+    #[must_use]
+    pub fn parse(args: &str) -> Self {
+        let mut words = args.split_whitespace();
+        let first = words.next();
+        let pr_number = match first {
+            Some("pr") => words.next(),
+            Some(arg) => arg.strip_prefix('#'),
+            None => None,
+        };
+        if let Some(number) = pr_number.and_then(|n| n.trim_start_matches('#').parse().ok()) {
+            return Self::PullRequest(number);
+        }
+        Self::Base(first.map(str::to_string))
+    }
+
+    pub fn diff(&self, git: &Git) -> GitResult<String> {
+        match self {
+            Self::Base(base) => {
+                let base = match base {
+                    Some(base) => base.clone(),
+                    None => git.default_base()?,
+                };
+                git.diff_from(&base)
+            }
+            Self::PullRequest(number) => git.pr_diff(*number),
+        }
+    }
+}
+
+impl fmt::Display for ReviewTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Base(Some(base)) => write!(f, "changes since {base}"),
+            Self::Base(None) => write!(f, "changes on this branch"),
+            Self::PullRequest(number) => write!(f, "pull request #{number}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    pub path: String,
+    pub patch: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub path: String,
+    pub line: Option<u32>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[must_use]
+pub fn split_diff(diff: &str) -> Vec<FilePatch> {
+    let mut files: Vec<FilePatch> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            let path = header
+                .trim_end()
+                .rsplit_once(" b/")
+                .map_or_else(|| header.trim_end(), |(_, path)| path);
+            files.push(FilePatch {
+                path: path.to_string(),
+                patch: String::new(),
+            });
+        }
+        if let Some(file) = files.last_mut() {
+            file.patch.push_str(line);
+        }
+    }
+    files
+}
+
+#[must_use]
+pub fn chunk_patches(files: Vec<FilePatch>, max_chars: usize) -> Vec<Vec<FilePatch>> {
+    let mut chunks: Vec<Vec<FilePatch>> = Vec::new();
+    let mut size = 0;
+    for mut file in files {
+        if file.patch.len() > max_chars {
+            let cut = file.patch.floor_char_boundary(max_chars);
+            file.patch.truncate(cut);
+            file.patch.push_str("\n[patch truncated]\n");
+        }
+        match chunks.last_mut() {
+            Some(chunk) if size + file.patch.len() <= max_chars => {
+                size += file.patch.len();
+                chunk.push(file);
+            }
+            _ => {
+                size = file.patch.len();
+                chunks.push(vec![file]);
+            }
+        }
+    }
+    chunks
+}
+
+#[must_use]
+pub fn parse_findings(reply: &str) -> Vec<Finding> {
+    reply
+        .lines()
+        .filter_map(|line| FINDING_RE.captures(line))
+        .map(|caps| Finding {
+            severity: match caps[1].to_ascii_lowercase().as_str() {
+                "high" => Severity::High,
+                "medium" => Severity::Medium,
+                _ => Severity::Low,
+            },
+            path: caps[2].to_string(),
+            line: caps.get(3).and_then(|m| m.as_str().parse().ok()),
+            message: caps[4].trim().to_string(),
+        })
+        .collect()
+}
+
+pub async fn review_diff(llm: &dyn LLM, diff: &str) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    for chunk in chunk_patches(split_diff(diff), MAX_CHUNK_CHARS) {
+        let patch: String = chunk.iter().map(|file| file.patch.as_str()).collect();
+        let request = CompletionRequest::new(vec![Message::user(patch)])
+            .with_system_prompt(REVIEW_PROMPT)
+            .with_max_tokens(REVIEW_MAX_TOKENS)
+            .with_temperature(0.0);
+        let response = llm.complete(request).await?;
+        if let Some(reply) = response.message.first_text() {
+            findings.extend(parse_findings(reply));
+        }
+    }
+    findings.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then(b.severity.cmp(&a.severity))
+            .then(a.line.cmp(&b.line))
+    });
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+
+    const DIFF: &str = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-old\n+new\ndiff --git a/b.txt b/b.txt\n+hello\n";
+
+    #[test]
+    fn test_review_target_parse() {
+        assert_eq!(ReviewTarget::parse(""), ReviewTarget::Base(None));
+        assert_eq!(
+            ReviewTarget::parse("origin/main"),
+            ReviewTarget::Base(Some("origin/main".to_string()))
+        );
+        assert_eq!(ReviewTarget::parse("#42"), ReviewTarget::PullRequest(42));
+        assert_eq!(ReviewTarget::parse("pr 7"), ReviewTarget::PullRequest(7));
+    }
+
+    #[test]
+    fn test_split_and_chunk() {
+        let files = split_diff(DIFF);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert!(files[0].patch.ends_with("+new\n"));
+        assert_eq!(files[1].path, "b.txt");
+
+        assert_eq!(chunk_patches(files.clone(), 10_000).len(), 1);
+        let chunks = chunk_patches(files, 40);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0][0].patch.ends_with("[patch truncated]\n"));
+    }
+
+    #[test]
+    fn test_parse_findings() {
+        let reply = "Here is what I found:\n- HIGH src/a.rs:12 - unwrap on user input panics\n**MEDIUM** `b.txt`: missing newline\n[low] src/a.rs:3 – rename `x`\nNONE";
+        let findings = parse_findings(reply);
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0].severity, Severity::High);
+        assert_eq!(findings[0].path, "src/a.rs");
+        assert_eq!(findings[0].line, Some(12));
+        assert_eq!(findings[0].message, "unwrap on user input panics");
+        assert_eq!(findings[1].path, "b.txt");
+        assert_eq!(findings[1].line, None);
+        assert_eq!(findings[2].message, "rename `x`");
+    }
+
+    #[tokio::test]
+    async fn test_review_diff_sorts_by_file_and_severity() {
+        let llm = ScenarioLLM::new(Scenario::new(vec![ScenarioTurn::text(
+            "LOW src/a.rs:1 - naming\nHIGH src/a.rs:1 - wrong value\nMEDIUM b.txt:1 - typo",
+        )]));
+
+        let findings = review_diff(&llm, DIFF).await.unwrap();
+
+        let order: Vec<(&str, Severity)> = findings
+            .iter()
+            .map(|f| (f.path.as_str(), f.severity))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("b.txt", Severity::Medium),
+                ("src/a.rs", Severity::High),
+                ("src/a.rs", Severity::Low)
+            ]
+        );
+        assert!(
+            llm.request_history()[0].messages[0]
+                .first_text()
+                .unwrap()
+                .contains("+new")
+        );
+    }
+}
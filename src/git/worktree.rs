@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use super::{Git, GitResult};
+
+const PENDING_MESSAGE: &str = "smith: uncommitted worktree changes";
+
+#[derive(Debug, Clone)]
+pub struct Worktree {
+    root: Git,
+    git: Git,
+    prefix: String,
+    branch: String,
+    base: String,
+}
+
+impl Git {
+    pub fn create_worktree(&self) -> GitResult<Worktree> {
+        let root = self.root()?;
+        let prefix = self
+            .run(&["rev-parse", "--show-prefix"])?
+            .trim()
+            .to_string();
+        let base = root.run(&["rev-parse", "HEAD"])?.trim().to_string();
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let branch = format!("smith/{stamp}");
+        let dir =
+            std::env::temp_dir().join(format!("smith-worktree-{stamp}-{}", std::process::id()));
+        let path = dir.to_string_lossy();
+        root.run(&["worktree", "add", "-q", "-b", &branch, &path, &base])?;
+        Ok(Worktree {
+            root,
+            git: Self::new(dir),
+            prefix,
+            branch,
+            base,
+        })
+    }
+}
+
+impl Worktree {
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        self.root.dir()
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        self.git.dir()
+    }
+
+    #[must_use]
+    pub fn working_dir(&self) -> PathBuf {
+        self.git.dir().join(&self.prefix)
+    }
+
+    #[must_use]
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    pub fn commit_pending(&self) -> GitResult<bool> {
+        if self.git.run(&["status", "--porcelain"])?.trim().is_empty() {
+            return Ok(false);
+        }
+        self.git.commit(PENDING_MESSAGE, true)?;
+        Ok(true)
+    }
+
+    pub fn summary(&self) -> GitResult<String> {
+        let range = format!("{}..{}", self.base, self.branch);
+        let log = self.git.run(&["log", "--oneline", &range])?;
+        if log.trim().is_empty() {
+            return Ok(String::new());
+        }
+        let stat = self
+            .git
+            .run(&["diff", "--stat", &self.base, &self.branch])?;
+        Ok(format!("{}\n{}", log.trim_end(), stat.trim_end()))
+    }
+
+    pub fn merge(&self) -> GitResult<String> {
+        self.root.run(&["merge", "--no-edit", &self.branch])?;
+        Ok(self
+            .root
+            .run(&["log", "-1", "--oneline"])?
+            .trim()
+            .to_string())
+    }
+
+    pub fn open_pr(&self) -> GitResult<String> {
+        self.git
+            .run(&["push", "-q", "-u", "origin", &self.branch])?;
+        Ok(self
+            .git
+            .run_program("gh", &["pr", "create", "--fill", "--head", &self.branch])?
+            .trim()
+            .to_string())
+    }
+
+    pub fn remove(&self, delete_branch: bool) -> GitResult<()> {
+        let path = self.dir().to_string_lossy();
+        self.root.run(&["worktree", "remove", "--force", &path])?;
+        if delete_branch {
+            self.root.run(&["branch", "-q", "-D", &self.branch])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::git::tests::repo;
+
+    #[test]
+    fn test_worktree_changes_merge_back() {
+        let (temp, git) = repo();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("src/a.txt"), "one\n").unwrap();
+        git.commit("initial", true).unwrap();
+
+        let worktree = crate::git::Git::new(temp.path().join("src"))
+            .create_worktree()
+            .unwrap();
+        assert!(worktree.branch().starts_with("smith/"));
+        assert_eq!(worktree.working_dir(), worktree.dir().join("src/"));
+        assert!(worktree.summary().unwrap().is_empty());
+
+        fs::write(worktree.working_dir().join("a.txt"), "two\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(temp.path().join("src/a.txt")).unwrap(),
+            "one\n"
+        );
+        assert!(worktree.commit_pending().unwrap());
+        assert!(!worktree.commit_pending().unwrap());
+        assert!(worktree.summary().unwrap().contains("src/a.txt"));
+
+        worktree.merge().unwrap();
+        assert_eq!(
+            fs::read_to_string(temp.path().join("src/a.txt")).unwrap(),
+            "two\n"
+        );
+        worktree.remove(true).unwrap();
+        assert!(!worktree.dir().exists());
+    }
+}
@@ -1,9 +1,22 @@
+pub mod agent;
+pub mod audit;
 pub mod cli;
+pub mod code_blocks;
 pub mod config;
 pub mod core;
+pub mod duo;
+pub mod fix_tests;
+pub mod git;
 pub mod logging;
+pub mod memories;
 pub mod permission;
 pub mod providers;
+pub mod server;
+pub mod session;
+pub mod shell;
+pub mod snippets;
+pub mod telemetry;
 pub mod tools;
 pub mod tui;
 pub mod ui;
+pub mod update;
@@ -1,15 +1,21 @@
 use std::path::PathBuf;
+use tracing_subscriber::prelude::*;
+
+use crate::config::TelemetryConfig;
+use crate::telemetry::{self, BoxedLayer, TelemetryGuard};
 
 #[cfg(feature = "debug-log")]
 mod inner {
-    use super::*;
+    use super::{BoxedLayer, PathBuf};
     use std::fs;
     use tracing_appender::non_blocking::WorkerGuard;
-    use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+    use tracing_subscriber::{EnvFilter, Layer, fmt};
+
+    pub type FileGuard = WorkerGuard;
 
     static LOG_PATH: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
 
-    pub fn init() -> Option<(PathBuf, WorkerGuard)> {
+    pub fn file_layer() -> Option<(BoxedLayer, FileGuard)> {
         let log_path = PathBuf::from("smith-debug.log");
 
         let file = match fs::OpenOptions::new()
@@ -28,25 +34,18 @@ mod inner {
 
         let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
 
-        let subscriber = tracing_subscriber::registry().with(filter).with(
-            fmt::layer()
-                .json()
-                .with_writer(non_blocking)
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true),
-        );
-
-        if tracing::subscriber::set_global_default(subscriber).is_err() {
-            eprintln!("Failed to set tracing subscriber");
-            return None;
-        }
-
-        LOG_PATH.set(log_path.clone()).ok();
+        let layer = fmt::layer()
+            .json()
+            .with_writer(non_blocking)
+            .with_target(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_filter(filter)
+            .boxed();
 
-        tracing::info!("Debug logging initialized");
+        LOG_PATH.set(log_path).ok();
 
-        Some((log_path, guard))
+        Some((layer, guard))
     }
 
     pub fn log_file_path() -> Option<&'static PathBuf> {
@@ -56,11 +55,13 @@ mod inner {
 
 #[cfg(not(feature = "debug-log"))]
 mod inner {
-    use super::PathBuf;
+    use super::{BoxedLayer, PathBuf};
+
+    pub type FileGuard = ();
 
     #[inline(always)]
     #[must_use]
-    pub const fn init() -> Option<(PathBuf, ())> {
+    pub const fn file_layer() -> Option<(BoxedLayer, FileGuard)> {
         None
     }
 
@@ -71,4 +72,36 @@ mod inner {
     }
 }
 
-pub use inner::*;
+pub use inner::log_file_path;
+
+pub struct LogGuard {
+    _file: Option<inner::FileGuard>,
+    _telemetry: Option<TelemetryGuard>,
+}
+
+#[must_use]
+pub fn init(telemetry: &TelemetryConfig) -> LogGuard {
+    let (file_layer, file) = inner::file_layer().unzip();
+    let (telemetry_layers, telemetry) = telemetry::layers(telemetry).unzip();
+
+    let layers: Vec<BoxedLayer> = file_layer
+        .into_iter()
+        .chain(telemetry_layers.into_iter().flatten())
+        .collect();
+
+    if !layers.is_empty() {
+        let subscriber = tracing_subscriber::registry().with(layers);
+        if tracing::subscriber::set_global_default(subscriber).is_err() {
+            eprintln!("Failed to set tracing subscriber");
+        }
+    }
+
+    if file.is_some() {
+        tracing::info!("Debug logging initialized");
+    }
+
+    LogGuard {
+        _file: file,
+        _telemetry: telemetry,
+    }
+}
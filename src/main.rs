@@ -1,25 +1,114 @@
-use smith::cli::{Cli, Commands, ConfigSubcommands};
-use smith::config::AppConfig;
-use smith::core::Result;
+use smith::cli::batch::{self, BatchOptions};
+use smith::cli::bench::{self, BenchOptions};
+use smith::cli::keys;
+use smith::cli::worktree;
+use smith::cli::{Cli, Commands, ConfigSubcommands, KeysSubcommands};
+use smith::config::{AppConfig, CredentialStore, ModelRegistry};
+use smith::core::error::ErrorReport;
+use smith::core::{AgentError, Result};
+use smith::providers::http::configure_rate_limits;
+use smith::providers::openai_compat::configure_openrouter;
+use smith::server::{self, ServeOptions};
 use smith::tui::{self, AgentConfig};
+use smith::ui::hyperlink;
+use smith::ui::i18n::set_language;
+use smith::ui::theme::{Capabilities, set_capabilities, set_theme};
+use smith::update::{self, CURRENT_VERSION, UpdateChecker};
 
 use clap::Parser;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let _log_guard = smith::logging::init();
+async fn main() -> ExitCode {
+    let mut cli = Cli::parse();
+    let mut config = AppConfig::load();
+    if let Err(e) = apply_agent_preset(&mut cli, &mut config) {
+        return report_error(&e, &cli, &config);
+    }
+    resolve_model_aliases(&mut cli, &mut config);
 
-    let cli = Cli::parse();
-    let config = AppConfig::load();
+    let _log_guard = smith::logging::init(&config.telemetry);
+    configure_rate_limits(config.rate_limits.clone());
+    configure_openrouter(config.openrouter.clone());
+    set_theme(config.theme);
+    if let Some(language) = &config.language {
+        set_language(language);
+    }
+    set_capabilities(Capabilities::detect(cli.ascii, cli.no_color));
+    hyperlink::set_enabled(!cli.ascii && hyperlink::detect());
 
-    if let Some(command) = cli.command {
-        return handle_command(command);
+    let result = match cli.command.take() {
+        Some(command) => handle_command(command, &cli, &config).await,
+        None => run_interactive(&cli, &config, false).await,
+    };
+    result.map_or_else(|e| report_error(&e, &cli, &config), |()| ExitCode::SUCCESS)
+}
+
+fn report_error(err: &AgentError, cli: &Cli, config: &AppConfig) -> ExitCode {
+    let mut report = ErrorReport::new(err);
+    if let Some(name) = cli.model.as_ref().or(config.model.as_ref())
+        && let Some(model) = ModelRegistry::load().resolve(name, &config.aliases)
+    {
+        report = report.with_provider(&model.provider);
+    }
+    if cli.json_errors {
+        eprintln!("{}", report.to_json());
+    } else {
+        eprintln!("Error: {report}");
     }
+    ExitCode::FAILURE
+}
 
-    run_interactive(&cli, &config).await
+fn apply_agent_preset(cli: &mut Cli, config: &mut AppConfig) -> Result<()> {
+    let Some(name) = &cli.agent else {
+        return Ok(());
+    };
+    let Some(preset) = config.agents.get(name).cloned() else {
+        let mut names: Vec<&str> = config.agents.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let known = if names.is_empty() {
+            "none configured".to_string()
+        } else {
+            names.join(", ")
+        };
+        return Err(AgentError::Config(format!(
+            "Unknown agent '{name}' (available: {known})"
+        )));
+    };
+    cli.model = cli.model.take().or(preset.model);
+    cli.system = cli.system.take().or(preset.system_prompt);
+    cli.max_iterations = cli.max_iterations.or(preset.max_iterations);
+    if preset.tools.is_some() {
+        config.tools.allowed = preset.tools;
+    }
+    Ok(())
 }
 
-fn handle_command(command: Commands) -> Result<()> {
+fn resolve_model_aliases(cli: &mut Cli, config: &mut AppConfig) {
+    if config.aliases.is_empty() {
+        return;
+    }
+    let registry = ModelRegistry::load();
+    cli.model = cli
+        .model
+        .take()
+        .map(|name| config.resolve_model(&registry, &name));
+    config.model = config
+        .model
+        .take()
+        .map(|name| config.resolve_model(&registry, &name));
+    config.execution_model = config
+        .execution_model
+        .take()
+        .map(|name| config.resolve_model(&registry, &name));
+    if let Some(Commands::Bench { models, .. }) = &mut cli.command {
+        for model in models {
+            *model = config.resolve_model(&registry, model);
+        }
+    }
+}
+
+async fn handle_command(command: Commands, cli: &Cli, config: &AppConfig) -> Result<()> {
     match command {
         Commands::Config { command } => match command {
             ConfigSubcommands::Init => {
@@ -30,22 +119,255 @@ fn handle_command(command: Commands) -> Result<()> {
                 Some(path) => println!("{}", path.display()),
                 None => eprintln!("Could not determine config path"),
             },
+            ConfigSubcommands::Keys { command } => run_keys(command).await?,
         },
+        Commands::Serve { addr } => {
+            let options = ServeOptions {
+                model_id: cli.model.clone().or_else(|| config.model.clone()),
+                system_prompt: cli.system.clone(),
+                max_iterations: cli.max_iterations,
+            };
+            server::run(addr, options).await?;
+        }
+        Commands::Sessions => run_interactive(cli, config, true).await?,
+        Commands::Replay { session, speed } => run_replay(cli, config, &session, speed).await?,
+        Commands::Bench {
+            models,
+            runs,
+            prompt,
+            max_tokens,
+        } => {
+            let options = BenchOptions {
+                models,
+                runs,
+                prompt: prompt.unwrap_or_else(|| bench::DEFAULT_PROMPT.to_string()),
+                max_tokens,
+            };
+            run_bench(&options).await?;
+        }
+        Commands::Batch {
+            file,
+            parallel,
+            out,
+            artifacts_dir,
+            task,
+        } => {
+            let options = BatchOptions {
+                file,
+                parallel,
+                out,
+                artifacts_dir,
+                model: cli.model.clone().or_else(|| config.model.clone()),
+                system_prompt: cli.system.clone(),
+                scenario: cli.scenario.clone(),
+                audit: config.audit.log(),
+            };
+            run_batch(&options, task).await?;
+        }
+        Commands::Update { check } => run_update(config, check).await?,
     }
     Ok(())
 }
 
-async fn run_interactive(cli: &Cli, config: &AppConfig) -> Result<()> {
-    let model_specified = cli.model.is_some() || config.model.is_some();
+async fn run_update(config: &AppConfig, check_only: bool) -> Result<()> {
+    let checker = UpdateChecker::new(&config.updates);
+    let release = checker
+        .latest_release()
+        .await
+        .map_err(|e| AgentError::InvalidState(format!("Update check failed: {e}")))?;
+    if !update::is_newer(release.version(), CURRENT_VERSION) {
+        println!("smith {CURRENT_VERSION} is up to date");
+        return Ok(());
+    }
+    println!(
+        "smith {} is available (installed: {CURRENT_VERSION})",
+        release.version()
+    );
+    if check_only {
+        return Ok(());
+    }
+    let path = checker
+        .install(&release)
+        .await
+        .map_err(|e| AgentError::InvalidState(format!("Update failed: {e}")))?;
+    println!("Updated {} to {}", path.display(), release.version());
+    Ok(())
+}
+
+async fn run_keys(command: Option<KeysSubcommands>) -> Result<()> {
+    let registry = ModelRegistry::load();
+    let store = CredentialStore::open()
+        .ok_or_else(|| AgentError::Config("Could not determine config directory".to_string()))?;
+    match command {
+        None => print!(
+            "{}",
+            keys::format_key_table(&keys::key_statuses(&registry, Some(&store)))
+        ),
+        Some(KeysSubcommands::Set { provider }) => {
+            let (_, env) = keys::provider_model(&registry, &provider)?;
+            let key = keys::read_key(env)?;
+            store.set(env, &key)?;
+            println!("Stored {env} in {}", store.path().display());
+            if std::env::var(env).is_ok_and(|value| !value.trim().is_empty()) {
+                eprintln!("Note: {env} is also set in the environment, which takes precedence");
+            }
+        }
+        Some(KeysSubcommands::Remove { provider }) => {
+            let (_, env) = keys::provider_model(&registry, &provider)?;
+            if store.remove(env)? {
+                println!("Removed {env} from {}", store.path().display());
+            } else {
+                println!("No stored key for {env}");
+            }
+        }
+        Some(KeysSubcommands::Check { provider }) => {
+            let providers: Vec<String> = provider.map_or_else(
+                || {
+                    keys::key_statuses(&registry, None)
+                        .into_iter()
+                        .filter(|status| status.source.is_some())
+                        .map(|status| status.provider.id().to_string())
+                        .collect()
+                },
+                |provider| vec![provider],
+            );
+            let mut failed = 0;
+            for provider in providers {
+                let (model, env) = keys::provider_model(&registry, &provider)?;
+                match keys::check_key(model).await {
+                    Ok(()) => {
+                        store.mark_validated(env, chrono::Utc::now())?;
+                        println!("{provider}: ok ({env})");
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        println!("{provider}: failed ({env}): {e}");
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(AgentError::InvalidState(format!(
+                    "{failed} key check(s) failed"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_batch(options: &BatchOptions, task: Option<usize>) -> Result<()> {
+    if let Some(index) = task {
+        let report = batch::run_child(options, index).await?;
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+
+    let (out, reports) = batch::run_batch(options).await?;
+    println!("{}", batch::format_summary(&reports));
+    println!("Transcripts written to {}", out.display());
+    let failed = reports.iter().filter(|r| !r.success).count();
+    if failed > 0 {
+        return Err(AgentError::InvalidState(format!(
+            "{failed} batch task(s) failed"
+        )));
+    }
+    Ok(())
+}
+
+async fn run_bench(options: &BenchOptions) -> Result<()> {
+    let registry = ModelRegistry::load();
+    let models = bench::select_models(&registry, &options.models)?;
+    if models.is_empty() {
+        eprintln!("No models with credentials found. Set an API key or pass model ids.");
+        return Ok(());
+    }
+
+    let mut results = Vec::with_capacity(models.len());
+    for (i, model) in models.iter().enumerate() {
+        eprintln!(
+            "[{}/{}] {} ({} runs)",
+            i + 1,
+            models.len(),
+            model.id,
+            options.runs
+        );
+        results.push(bench::bench_model(model, options).await);
+    }
+    println!("\n{}", bench::format_table(&results));
+    Ok(())
+}
+
+async fn run_interactive(cli: &Cli, config: &AppConfig, show_sessions: bool) -> Result<()> {
+    let model_specified = cli.model.is_some() || config.model.is_some() || cli.scenario.is_some();
 
     let model_id = cli.model.clone().or_else(|| config.model.clone());
 
-    let agent_config = AgentConfig {
+    if cli.yolo && !config.audit.enabled {
+        return Err(AgentError::Config(
+            "--yolo requires the audit log ([audit] enabled = true)".to_string(),
+        ));
+    }
+
+    let worktree = if cli.worktree {
+        Some(worktree::enter()?)
+    } else {
+        None
+    };
+    let result = tui::run_tui(
+        agent_config(cli, config, model_id),
+        !model_specified && !show_sessions,
+        show_sessions,
+    )
+    .await;
+    if let Some(worktree) = &worktree {
+        worktree::finish(worktree)?;
+    }
+    result
+}
+
+async fn run_replay(cli: &Cli, config: &AppConfig, session: &str, speed: f64) -> Result<()> {
+    if speed <= 0.0 {
+        return Err(AgentError::Config("--speed must be positive".to_string()));
+    }
+    let store = config.sessions.store().ok_or_else(|| {
+        AgentError::Config("Session storage is disabled or has no directory".to_string())
+    })?;
+    let record = store.find(session)?;
+    let agent_config = agent_config(cli, config, Some(record.model.clone()));
+    tui::run_replay(agent_config, &record, speed).await
+}
+
+fn agent_config(cli: &Cli, config: &AppConfig, model_id: Option<String>) -> AgentConfig {
+    AgentConfig {
         model_id,
-        max_iterations: Some(cli.max_iterations),
+        execution_model: cli
+            .execution_model
+            .clone()
+            .or_else(|| config.execution_model.clone()),
+        max_iterations: cli.max_iterations,
         system_prompt: cli.system.clone(),
         custom_system_prompt: config.custom_system_prompt.clone(),
-    };
-
-    tui::run_tui(agent_config, !model_specified).await
+        language: config.language.clone(),
+        permission_mode: config.permission_mode,
+        generation: config.generation.clone(),
+        tools: config.tools.clone(),
+        sessions: config.sessions.clone(),
+        audit: config.audit.clone(),
+        updates: config.updates.clone(),
+        input: config.input.clone(),
+        snippets: config.snippets.clone(),
+        aliases: config.aliases.clone(),
+        recent_models: config.recent_models.clone(),
+        keys: config.keys.clone(),
+        clipboard: config.clipboard.clone(),
+        checkpoints: config.checkpoints.clone(),
+        model_badges: config.model_badges,
+        fix_tests: config.fix_tests.clone(),
+        duo: config.duo.clone(),
+        agents: config.agents.clone(),
+        memories: config.memories.clone(),
+        response_cache: cli.cache,
+        auto_approve: cli.yolo,
+        scenario: cli.scenario.clone(),
+    }
 }
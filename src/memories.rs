@@ -0,0 +1,171 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::get_config_dir;
+
+const MEMORIES_FILE: &str = "memories.md";
+const HEADER: &str = "# Memories\n\n";
+
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    path: PathBuf,
+}
+
+impl MemoryStore {
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    #[must_use]
+    pub fn with_default_path() -> Option<Self> {
+        get_config_dir().map(|dir| Self::new(dir.join(MEMORIES_FILE)))
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn list(&self) -> Vec<String> {
+        std::fs::read_to_string(&self.path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("- "))
+                    .map(|entry| entry.trim().to_string())
+                    .filter(|entry| !entry.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn add(&self, text: &str) -> io::Result<bool> {
+        let entry = normalize(text);
+        let mut entries = self.list();
+        if entry.is_empty() || entries.contains(&entry) {
+            return Ok(false);
+        }
+        entries.push(entry);
+        self.save(&entries)?;
+        Ok(true)
+    }
+
+    pub fn update(&self, index: usize, text: &str) -> io::Result<bool> {
+        let entry = normalize(text);
+        let mut entries = self.list();
+        let Some(slot) = entries.get_mut(index) else {
+            return Ok(false);
+        };
+        if entry.is_empty() {
+            entries.remove(index);
+        } else {
+            *slot = entry;
+        }
+        self.save(&entries)?;
+        Ok(true)
+    }
+
+    pub fn remove(&self, index: usize) -> io::Result<Option<String>> {
+        let mut entries = self.list();
+        if index >= entries.len() {
+            return Ok(None);
+        }
+        let removed = entries.remove(index);
+        self.save(&entries)?;
+        Ok(Some(removed))
+    }
+
+    fn save(&self, entries: &[String]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = HEADER.to_string();
+        for entry in entries {
+            content.push_str("- ");
+            content.push_str(entry);
+            content.push('\n');
+        }
+        std::fs::write(&self.path, content)
+    }
+
+    #[must_use]
+    pub fn prompt_section(&self) -> Option<String> {
+        let entries = self.list();
+        if entries.is_empty() {
+            return None;
+        }
+        let mut section = "# Remembered Preferences\n\nThe user asked you to keep these in mind in every session:\n".to_string();
+        for entry in entries {
+            section.push_str("\n- ");
+            section.push_str(&entry);
+        }
+        Some(section)
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[must_use]
+pub fn parse_remember(input: &str) -> Option<&str> {
+    let rest = input.trim_start().strip_prefix('#')?.trim_start();
+    let keyword = rest.get(..9)?;
+    if !keyword.eq_ignore_ascii_case("remember:") {
+        return None;
+    }
+    Some(rest[9..].trim()).filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_add_update_remove() {
+        let temp = TempDir::new().unwrap();
+        let store = MemoryStore::new(temp.path().join("nested/memories.md"));
+        assert!(store.list().is_empty());
+        assert!(store.prompt_section().is_none());
+
+        assert!(store.add("prefer   snake_case\nnames").unwrap());
+        assert!(!store.add("prefer snake_case names").unwrap());
+        assert!(store.add("answer in British English").unwrap());
+        assert_eq!(
+            store.list(),
+            vec!["prefer snake_case names", "answer in British English"]
+        );
+
+        assert!(store.update(1, "answer tersely").unwrap());
+        assert!(!store.update(5, "nothing").unwrap());
+        assert_eq!(
+            store.remove(0).unwrap().as_deref(),
+            Some("prefer snake_case names")
+        );
+        assert_eq!(store.list(), vec!["answer tersely"]);
+        assert!(
+            store
+                .prompt_section()
+                .unwrap()
+                .ends_with("\n- answer tersely")
+        );
+
+        let content = std::fs::read_to_string(store.path()).unwrap();
+        assert_eq!(content, "# Memories\n\n- answer tersely\n");
+    }
+
+    #[test]
+    fn test_parse_remember() {
+        assert_eq!(
+            parse_remember("# remember: prefer snake_case"),
+            Some("prefer snake_case")
+        );
+        assert_eq!(parse_remember("#Remember:  tabs"), Some("tabs"));
+        assert_eq!(parse_remember("# remember:"), None);
+        assert_eq!(parse_remember("#tests foo"), None);
+        assert_eq!(parse_remember("remember: x"), None);
+    }
+}
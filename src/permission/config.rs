@@ -74,13 +74,17 @@ impl PermissionConfig {
             PermissionType::FileDelete => {
                 self.matches_any_pattern(target, &self.allowed_delete_paths)
             }
-            PermissionType::CommandExecute => {
+            PermissionType::CommandExecute
+            | PermissionType::CloudRead
+            | PermissionType::CloudMutate => {
                 self.matches_any_pattern(target, &self.allowed_commands)
             }
             PermissionType::NetworkAccess => {
                 self.matches_any_pattern(target, &self.allowed_network_hosts)
             }
-            PermissionType::SystemModification => Ok(false),
+            PermissionType::SystemModification
+            | PermissionType::MemoryWrite
+            | PermissionType::DatabaseWrite => Ok(false),
         }
     }
 
@@ -2,14 +2,22 @@ use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::config::PermissionConfig;
 use super::security::SecurityValidator;
-use super::types::{PermissionCheckResult, PermissionRequest, PermissionResponse, PermissionType};
+use super::types::{
+    CloudPolicy, PermissionCheckResult, PermissionMode, PermissionRequest, PermissionResponse,
+    PermissionType, PolicyAction,
+};
 use super::ui_trait::PermissionUI;
 #[cfg(test)]
 use super::ui_trait::test_utils::HeadlessPermissionUI;
-use crate::core::error::Result;
+use super::workspace::{Grant, WorkspacePermissions};
+use crate::audit::{AuditEvent, AuditLog};
+use crate::core::error::{AgentError, Result};
+
+const WORKSPACE_PERMISSIONS_FILE: &str = "workspace_permissions.json";
 
 #[derive(Debug, Default)]
 struct SessionPermissions {
@@ -32,8 +40,13 @@ impl SessionPermissions {
 pub struct PermissionManager {
     config: Arc<RwLock<PermissionConfig>>,
     session: Arc<RwLock<SessionPermissions>>,
+    workspace: Arc<RwLock<WorkspacePermissions>>,
     validator: SecurityValidator,
     ui: Arc<dyn PermissionUI>,
+    audit: Option<AuditLog>,
+    mode: RwLock<PermissionMode>,
+    auto_approve: AtomicBool,
+    cloud_policy: CloudPolicy,
 }
 
 impl PermissionManager {
@@ -49,45 +62,276 @@ impl PermissionManager {
             PermissionConfig::new()
         };
 
+        let workspace_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let workspace = WorkspacePermissions::load(
+            config_path.with_file_name(WORKSPACE_PERMISSIONS_FILE),
+            &workspace_dir,
+        )?;
         let validator = SecurityValidator::new()?;
 
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
             session: Arc::new(RwLock::new(SessionPermissions::default())),
+            workspace: Arc::new(RwLock::new(workspace)),
             validator,
             ui,
+            audit: None,
+            mode: RwLock::new(PermissionMode::default()),
+            auto_approve: AtomicBool::new(false),
+            cloud_policy: CloudPolicy::default(),
         })
     }
 
+    #[must_use]
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_cloud_policy(mut self, policy: CloudPolicy) -> Self {
+        self.cloud_policy = policy;
+        self
+    }
+
+    #[must_use]
+    pub fn with_mode(self, mode: PermissionMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    pub fn set_mode(&self, mode: PermissionMode) {
+        *self.mode.write() = mode;
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> PermissionMode {
+        *self.mode.read()
+    }
+
+    pub fn set_auto_approve(&self, enabled: bool) -> Result<()> {
+        if enabled && self.audit.is_none() {
+            return Err(AgentError::Config(
+                "Auto-approve requires the audit log ([audit] enabled = true)".to_string(),
+            ));
+        }
+        self.auto_approve.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn auto_approve(&self) -> bool {
+        self.auto_approve.load(Ordering::Relaxed)
+    }
+
     pub fn check_permission(&self, request: &PermissionRequest) -> Result<PermissionCheckResult> {
+        let outcome = self.decide(request);
+        self.record(request, outcome)
+    }
+
+    pub fn check_batch(
+        &self,
+        requests: &[PermissionRequest],
+    ) -> Vec<Result<PermissionCheckResult>> {
+        let mut outcomes: Vec<Option<Result<(PermissionCheckResult, String)>>> = requests
+            .iter()
+            .map(|request| self.precheck(request).transpose())
+            .collect();
+        let pending: Vec<usize> = (0..requests.len())
+            .filter(|&index| outcomes[index].is_none())
+            .collect();
+
+        if pending.len() == 1 {
+            outcomes[pending[0]] = Some(self.decide(&requests[pending[0]]));
+        } else if !pending.is_empty() {
+            let batch: Vec<PermissionRequest> = pending
+                .iter()
+                .map(|&index| requests[index].clone())
+                .collect();
+            match self.ui.prompt_batch(&batch) {
+                Ok(responses) if responses.len() == pending.len() => {
+                    for (&index, response) in pending.iter().zip(responses) {
+                        outcomes[index] = Some(self.apply_response(&requests[index], response));
+                    }
+                }
+                Ok(_) => {
+                    for &index in &pending {
+                        outcomes[index] = Some(Err(AgentError::InvalidState(
+                            "Batch approval returned the wrong number of responses".to_string(),
+                        )));
+                    }
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    for &index in &pending {
+                        outcomes[index] = Some(Err(AgentError::InvalidState(message.clone())));
+                    }
+                }
+            }
+        }
+
+        requests
+            .iter()
+            .zip(outcomes)
+            .map(|(request, outcome)| {
+                let outcome = outcome.unwrap_or_else(|| self.decide(request));
+                self.record(request, outcome)
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn requires_prompt(&self, request: &PermissionRequest) -> bool {
+        matches!(self.precheck(request), Ok(None))
+    }
+
+    fn record(
+        &self,
+        request: &PermissionRequest,
+        outcome: Result<(PermissionCheckResult, String)>,
+    ) -> Result<PermissionCheckResult> {
+        if let Some(audit) = &self.audit
+            && request.operation_type != PermissionType::FileRead
+        {
+            let decision = match &outcome {
+                Ok((_, decision)) => decision.clone(),
+                Err(e) => format!("Blocked: {e}"),
+            };
+            audit.record(AuditEvent::permission(request, decision));
+        }
+        outcome.map(|(result, _)| result)
+    }
+
+    fn decide(&self, request: &PermissionRequest) -> Result<(PermissionCheckResult, String)> {
+        if let Some(outcome) = self.precheck(request)? {
+            return Ok(outcome);
+        }
+        let response = self.ui.prompt_user(request)?;
+        self.apply_response(request, response)
+    }
+
+    fn precheck(
+        &self,
+        request: &PermissionRequest,
+    ) -> Result<Option<(PermissionCheckResult, String)>> {
         self.validate_request(request)?;
 
+        match self.cloud_policy.action(request.operation_type) {
+            Some(PolicyAction::Deny) => {
+                return Ok(Some((
+                    PermissionCheckResult::DeniedWithFeedback(format!(
+                        "Not allowed to {} by the cloud command policy",
+                        request.operation_type
+                    )),
+                    "Denied by cloud policy".into(),
+                )));
+            }
+            Some(PolicyAction::Allow) if self.mode() != PermissionMode::ReadOnly => {
+                return Ok(Some((
+                    PermissionCheckResult::Allowed,
+                    "Allowed by cloud policy".into(),
+                )));
+            }
+            _ => {}
+        }
+
+        match (self.mode(), request.operation_type) {
+            (_, PermissionType::FileRead) | (PermissionMode::Ask, _) => {}
+            (PermissionMode::ReadOnly, _) => {
+                return Ok(Some((
+                    PermissionCheckResult::DeniedWithFeedback(format!(
+                        "Not allowed to {} in read-only mode",
+                        request.operation_type
+                    )),
+                    "Denied by read-only mode".into(),
+                )));
+            }
+            (PermissionMode::AcceptEdits, PermissionType::FileWrite) => {
+                return Ok(Some((
+                    PermissionCheckResult::Allowed,
+                    "Allowed by accept-edits mode".into(),
+                )));
+            }
+            (PermissionMode::AcceptEdits, _) => {}
+        }
+
         {
             let config = self.config.read();
             if config.is_allowed(request.operation_type, &request.target)? {
-                return Ok(PermissionCheckResult::Allowed);
+                return Ok(Some((
+                    PermissionCheckResult::Allowed,
+                    "Allowed by config".into(),
+                )));
             }
         }
 
         {
             let session = self.session.read();
             if session.is_allowed(request.operation_type, &request.target) {
-                return Ok(PermissionCheckResult::Allowed);
+                return Ok(Some((
+                    PermissionCheckResult::Allowed,
+                    "Allowed for session".into(),
+                )));
             }
         }
 
-        let response = self.ui.prompt_user(request)?;
+        if self
+            .workspace
+            .read()
+            .is_allowed(request.operation_type, &request.target)
+        {
+            return Ok(Some((
+                PermissionCheckResult::Allowed,
+                "Allowed for workspace".into(),
+            )));
+        }
 
-        match response {
-            PermissionResponse::AllowOnce => Ok(PermissionCheckResult::Allowed),
+        if self.auto_approve() {
+            return Ok(Some((
+                PermissionCheckResult::Allowed,
+                "Auto-approved".into(),
+            )));
+        }
+
+        Ok(None)
+    }
+
+    fn apply_response(
+        &self,
+        request: &PermissionRequest,
+        response: PermissionResponse,
+    ) -> Result<(PermissionCheckResult, String)> {
+        let decision = format!("User: {response}");
+
+        let result = match response {
+            PermissionResponse::AllowOnce => PermissionCheckResult::Allowed,
             PermissionResponse::AllowSession => {
                 self.add_session_permission(request);
-                Ok(PermissionCheckResult::Allowed)
+                PermissionCheckResult::Allowed
+            }
+            PermissionResponse::AllowAlways => {
+                self.workspace
+                    .write()
+                    .remember(Grant::for_request(request))?;
+                PermissionCheckResult::Allowed
+            }
+            PermissionResponse::AllowEdited(_) if !request.editable => {
+                PermissionCheckResult::DeniedWithFeedback(format!(
+                    "The target of this {} request cannot be edited",
+                    request.operation_type
+                ))
+            }
+            PermissionResponse::AllowEdited(target) => {
+                let mut edited = request.clone();
+                edited.target.clone_from(&target);
+                self.validate_request(&edited)?;
+                PermissionCheckResult::AllowedEdited(target)
             }
             PermissionResponse::TellModelDifferently(feedback) => {
-                Ok(PermissionCheckResult::DeniedWithFeedback(feedback))
+                PermissionCheckResult::DeniedWithFeedback(feedback)
             }
-        }
+        };
+        Ok((result, decision))
     }
 
     fn validate_request(&self, request: &PermissionRequest) -> Result<()> {
@@ -113,6 +357,7 @@ impl PermissionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::AtomicUsize;
     use tempfile::TempDir;
 
     fn create_test_manager() -> (PermissionManager, TempDir) {
@@ -157,4 +402,238 @@ mod tests {
         assert!(session.is_allowed(PermissionType::CommandExecute, "npm install"));
         assert!(session.is_allowed(PermissionType::CommandExecute, "any command"));
     }
+
+    #[tokio::test]
+    async fn test_permission_modes() {
+        let (manager, _temp) = create_test_manager();
+        let write = PermissionRequest::new(PermissionType::FileWrite, "test.txt");
+        let command = PermissionRequest::new(PermissionType::CommandExecute, "make");
+        let read = PermissionRequest::new(PermissionType::FileRead, "test.txt");
+
+        manager.set_mode(PermissionMode::AcceptEdits);
+        assert_eq!(
+            manager.check_permission(&write).unwrap(),
+            PermissionCheckResult::Allowed
+        );
+        assert!(matches!(
+            manager.check_permission(&command).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+
+        let manager = manager.with_mode(PermissionMode::ReadOnly);
+        assert_eq!(manager.mode(), PermissionMode::ReadOnly);
+        assert_eq!(
+            manager.check_permission(&read).unwrap(),
+            PermissionCheckResult::Allowed
+        );
+        let PermissionCheckResult::DeniedWithFeedback(feedback) =
+            manager.check_permission(&write).unwrap()
+        else {
+            panic!("write allowed in read-only mode");
+        };
+        assert!(feedback.contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn test_cloud_policy() {
+        let (manager, _temp) = create_test_manager();
+        let read = PermissionRequest::new(PermissionType::CloudRead, "kubectl get pods");
+        let mutate = PermissionRequest::new(PermissionType::CloudMutate, "kubectl delete ns prod");
+
+        assert_eq!(
+            manager.check_permission(&read).unwrap(),
+            PermissionCheckResult::Allowed
+        );
+
+        let manager = manager.with_mode(PermissionMode::ReadOnly);
+        assert!(matches!(
+            manager.check_permission(&read).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+        assert!(matches!(
+            manager.check_permission(&mutate).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+
+        let manager = manager
+            .with_mode(PermissionMode::Ask)
+            .with_cloud_policy(CloudPolicy {
+                read: PolicyAction::Ask,
+                mutate: PolicyAction::Deny,
+            });
+        assert!(matches!(
+            manager.check_permission(&read).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+        let PermissionCheckResult::DeniedWithFeedback(feedback) =
+            manager.check_permission(&mutate).unwrap()
+        else {
+            panic!("mutation allowed by deny policy");
+        };
+        assert!(feedback.contains("cloud command policy"));
+    }
+
+    #[tokio::test]
+    async fn test_allow_edited_only_for_editable_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = PermissionManager::with_config_path(
+            temp_dir.path().join("permissions.json"),
+            Arc::new(HeadlessPermissionUI::new(PermissionResponse::AllowEdited(
+                "cargo test -p smith".to_string(),
+            ))),
+        )
+        .unwrap();
+
+        let request = PermissionRequest::new(PermissionType::CommandExecute, "cargo test");
+        assert!(matches!(
+            manager.check_permission(&request).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+        assert_eq!(
+            manager.check_permission(&request.editable()).unwrap(),
+            PermissionCheckResult::AllowedEdited("cargo test -p smith".to_string())
+        );
+    }
+
+    struct BatchUI(AtomicUsize);
+
+    impl PermissionUI for BatchUI {
+        fn prompt_user(&self, _request: &PermissionRequest) -> Result<PermissionResponse> {
+            panic!("batched requests should not be prompted one by one");
+        }
+
+        fn prompt_batch(&self, requests: &[PermissionRequest]) -> Result<Vec<PermissionResponse>> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Ok(requests
+                .iter()
+                .map(|request| {
+                    if Path::new(&request.target)
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("lock"))
+                    {
+                        PermissionResponse::TellModelDifferently("skipped".to_string())
+                    } else {
+                        PermissionResponse::AllowOnce
+                    }
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_prompts_once_for_pending_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let ui = Arc::new(BatchUI(AtomicUsize::new(0)));
+        let manager = PermissionManager::with_config_path(
+            temp_dir.path().join("permissions.json"),
+            ui.clone(),
+        )
+        .unwrap();
+
+        let requests = [
+            PermissionRequest::new(PermissionType::FileWrite, "src/a.rs"),
+            PermissionRequest::new(PermissionType::FileRead, "src/b.rs"),
+            PermissionRequest::new(PermissionType::FileWrite, "Cargo.lock"),
+        ];
+        let results = manager.check_batch(&requests);
+        assert_eq!(ui.0.load(Ordering::Relaxed), 1);
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap(),
+            &PermissionCheckResult::Allowed
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap(),
+            &PermissionCheckResult::Allowed
+        );
+        assert!(matches!(
+            results[2].as_ref().unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_allow_always_restored_for_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("permissions.json");
+        let request = PermissionRequest::new(PermissionType::CommandExecute, "cargo build");
+
+        let manager = PermissionManager::with_config_path(
+            config_path.clone(),
+            Arc::new(HeadlessPermissionUI::allow_always()),
+        )
+        .unwrap();
+        let result = manager.check_permission(&request).unwrap();
+        assert!(matches!(result, PermissionCheckResult::Allowed));
+
+        let restored = PermissionManager::with_config_path(
+            config_path,
+            Arc::new(HeadlessPermissionUI::deny()),
+        )
+        .unwrap();
+        let later = PermissionRequest::new(PermissionType::CommandExecute, "cargo test");
+        let result = restored.check_permission(&later).unwrap();
+        assert!(matches!(result, PermissionCheckResult::Allowed));
+    }
+
+    #[tokio::test]
+    async fn test_decisions_are_audited() {
+        let temp_dir = TempDir::new().unwrap();
+        let audit = AuditLog::new(temp_dir.path().join("audit.jsonl"));
+        let manager = PermissionManager::with_config_path(
+            temp_dir.path().join("permissions.json"),
+            Arc::new(HeadlessPermissionUI::allow_session()),
+        )
+        .unwrap()
+        .with_audit_log(audit.clone());
+
+        let write = PermissionRequest::new(PermissionType::CommandExecute, "make");
+        manager.check_permission(&write).unwrap();
+        manager.check_permission(&write).unwrap();
+        manager
+            .check_permission(&PermissionRequest::new(PermissionType::FileRead, "a.txt"))
+            .unwrap();
+
+        let decisions: Vec<String> = audit
+            .recent(10)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.event.to_string())
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                "execute command make: User: Allow for session",
+                "execute command make: Allowed for session",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auto_approve_requires_audit_log() {
+        let (manager, temp) = create_test_manager();
+        assert!(manager.set_auto_approve(true).is_err());
+        assert!(!manager.auto_approve());
+
+        let audit = AuditLog::new(temp.path().join("audit.jsonl"));
+        let manager = manager
+            .with_audit_log(audit.clone())
+            .with_mode(PermissionMode::ReadOnly);
+        manager.set_auto_approve(true).unwrap();
+        let command = PermissionRequest::new(PermissionType::CommandExecute, "make");
+        assert!(matches!(
+            manager.check_permission(&command).unwrap(),
+            PermissionCheckResult::DeniedWithFeedback(_)
+        ));
+
+        manager.set_mode(PermissionMode::Ask);
+        assert!(matches!(
+            manager.check_permission(&command).unwrap(),
+            PermissionCheckResult::Allowed
+        ));
+        assert_eq!(
+            audit.recent(1).unwrap()[0].event.to_string(),
+            "execute command make: Auto-approved"
+        );
+    }
 }
@@ -3,6 +3,12 @@ pub mod manager;
 pub mod security;
 pub mod types;
 pub mod ui_trait;
+pub mod workspace;
 
 pub use manager::PermissionManager;
-pub use types::{PermissionCheckResult, PermissionRequest, PermissionType};
+pub use types::{
+    CloudPolicy, PermissionCheckResult, PermissionMode, PermissionRequest, PermissionType,
+    PolicyAction, ProposedChange,
+};
+pub use ui_trait::{HeadlessPermissionUI, PermissionUI};
+pub use workspace::{Grant, WorkspacePermissions};
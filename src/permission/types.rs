@@ -19,6 +19,10 @@ pub enum PermissionType {
     CommandExecute,
     NetworkAccess,
     SystemModification,
+    MemoryWrite,
+    DatabaseWrite,
+    CloudRead,
+    CloudMutate,
 }
 
 impl fmt::Display for PermissionType {
@@ -30,6 +34,68 @@ impl fmt::Display for PermissionType {
             Self::CommandExecute => write!(f, "execute command"),
             Self::NetworkAccess => write!(f, "network access"),
             Self::SystemModification => write!(f, "system modification"),
+            Self::MemoryWrite => write!(f, "save memory"),
+            Self::DatabaseWrite => write!(f, "write database"),
+            Self::CloudRead => write!(f, "read cloud resources"),
+            Self::CloudMutate => write!(f, "modify cloud resources"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionMode {
+    #[default]
+    Ask,
+    AcceptEdits,
+    ReadOnly,
+}
+
+impl PermissionMode {
+    pub const ALL: [Self; 3] = [Self::Ask, Self::AcceptEdits, Self::ReadOnly];
+}
+
+impl fmt::Display for PermissionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ask => write!(f, "ask"),
+            Self::AcceptEdits => write!(f, "accept edits"),
+            Self::ReadOnly => write!(f, "read-only"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    Allow,
+    Ask,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloudPolicy {
+    pub read: PolicyAction,
+    pub mutate: PolicyAction,
+}
+
+impl Default for CloudPolicy {
+    fn default() -> Self {
+        Self {
+            read: PolicyAction::Allow,
+            mutate: PolicyAction::Ask,
+        }
+    }
+}
+
+impl CloudPolicy {
+    #[must_use]
+    pub const fn action(&self, perm_type: PermissionType) -> Option<PolicyAction> {
+        match perm_type {
+            PermissionType::CloudRead => Some(self.read),
+            PermissionType::CloudMutate => Some(self.mutate),
+            _ => None,
         }
     }
 }
@@ -39,6 +105,8 @@ impl fmt::Display for PermissionType {
 pub enum PermissionResponse {
     AllowOnce,
     AllowSession,
+    AllowAlways,
+    AllowEdited(String),
     TellModelDifferently(String),
 }
 
@@ -47,6 +115,8 @@ impl fmt::Display for PermissionResponse {
         match self {
             Self::AllowOnce => write!(f, "Allow once"),
             Self::AllowSession => write!(f, "Allow for session"),
+            Self::AllowAlways => write!(f, "Always allow in this directory"),
+            Self::AllowEdited(target) => write!(f, "Allow edited: {target}"),
             Self::TellModelDifferently(msg) => write!(f, "Tell model: {msg}"),
         }
     }
@@ -55,14 +125,25 @@ impl fmt::Display for PermissionResponse {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PermissionCheckResult {
     Allowed,
+    AllowedEdited(String),
     DeniedWithFeedback(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedChange {
+    pub old_content: String,
+    pub new_content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PermissionRequest {
     pub operation_type: PermissionType,
     pub target: String,
     pub context: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<ProposedChange>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub editable: bool,
 }
 
 impl PermissionRequest {
@@ -72,6 +153,8 @@ impl PermissionRequest {
             operation_type,
             target: target.into(),
             context: None,
+            change: None,
+            editable: false,
         }
     }
 
@@ -80,6 +163,18 @@ impl PermissionRequest {
         self.context = Some(context.into());
         self
     }
+
+    #[must_use]
+    pub fn with_change(mut self, change: Option<ProposedChange>) -> Self {
+        self.change = change;
+        self
+    }
+
+    #[must_use]
+    pub const fn editable(mut self) -> Self {
+        self.editable = true;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3,49 +3,68 @@ use crate::core::error::Result;
 
 pub trait PermissionUI: Send + Sync {
     fn prompt_user(&self, request: &PermissionRequest) -> Result<PermissionResponse>;
+
+    fn prompt_batch(&self, requests: &[PermissionRequest]) -> Result<Vec<PermissionResponse>> {
+        requests
+            .iter()
+            .map(|request| self.prompt_user(request))
+            .collect()
+    }
 }
 
-#[cfg(test)]
-pub mod test_utils {
-    use super::*;
+#[derive(Debug, Clone)]
+pub struct HeadlessPermissionUI {
+    response: PermissionResponse,
+}
 
-    #[derive(Debug, Clone)]
-    pub struct HeadlessPermissionUI {
-        response: PermissionResponse,
+impl HeadlessPermissionUI {
+    #[must_use]
+    pub const fn new(response: PermissionResponse) -> Self {
+        Self { response }
     }
 
-    impl HeadlessPermissionUI {
-        #[must_use]
-        pub fn deny() -> Self {
-            Self {
-                response: PermissionResponse::TellModelDifferently(
-                    "Permission denied (prompts disabled)".to_string(),
-                ),
-            }
+    #[must_use]
+    pub fn deny() -> Self {
+        Self {
+            response: PermissionResponse::TellModelDifferently(
+                "Permission denied (prompts disabled)".to_string(),
+            ),
         }
+    }
 
-        #[must_use]
-        pub const fn allow_once() -> Self {
-            Self {
-                response: PermissionResponse::AllowOnce,
-            }
+    #[must_use]
+    pub const fn allow_once() -> Self {
+        Self {
+            response: PermissionResponse::AllowOnce,
         }
+    }
 
-        #[must_use]
-        pub const fn allow_session() -> Self {
-            Self {
-                response: PermissionResponse::AllowSession,
-            }
+    #[must_use]
+    pub const fn allow_session() -> Self {
+        Self {
+            response: PermissionResponse::AllowSession,
         }
     }
 
-    impl PermissionUI for HeadlessPermissionUI {
-        fn prompt_user(&self, _request: &PermissionRequest) -> Result<PermissionResponse> {
-            Ok(self.response.clone())
+    #[must_use]
+    pub const fn allow_always() -> Self {
+        Self {
+            response: PermissionResponse::AllowAlways,
         }
     }
 }
 
+impl PermissionUI for HeadlessPermissionUI {
+    fn prompt_user(&self, _request: &PermissionRequest) -> Result<PermissionResponse> {
+        Ok(self.response.clone())
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    pub use super::HeadlessPermissionUI;
+}
+
 #[cfg(test)]
 mod tests {
     use super::test_utils::HeadlessPermissionUI;
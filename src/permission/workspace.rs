@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::types::{Pattern, PermissionRequest, PermissionType};
+use crate::core::error::Result;
+use crate::tools::fs::atomic_write;
+
+const SHELL_OPERATORS: &[char] = &[';', '&', '|', '$', '`', '<', '>', '\n'];
+
+const EXACT_PROGRAMS: &[&str] = &[
+    "rm", "rmdir", "shred", "dd", "mkfs", "truncate", "sudo", "su", "doas", "chmod", "chown",
+    "kill", "pkill", "killall", "sh", "bash", "zsh", "fish", "dash", "env", "xargs", "eval",
+    "exec", "python", "node", "deno", "bun", "npx", "ruby", "perl", "php", "lua", "curl", "wget",
+    "ssh", "scp", "rsync",
+];
+const SUBCOMMAND_PROGRAMS: &[&str] = &["git"];
+const EXACT_SUBCOMMANDS: &[&str] = &["push", "reset", "clean", "filter-branch"];
+
+fn needs_exact_grant(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    let name = name
+        .split('.')
+        .next()
+        .unwrap_or(name)
+        .trim_end_matches(|c: char| c.is_ascii_digit());
+    EXACT_PROGRAMS.contains(&name)
+}
+
+fn command_pattern(command: &str) -> Pattern {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let prefix = match words.as_slice() {
+        _ if command.contains(SHELL_OPERATORS) => None,
+        [program, ..] if needs_exact_grant(program) => None,
+        [program, rest @ ..] if SUBCOMMAND_PROGRAMS.contains(program) => match rest.first() {
+            Some(sub) if !sub.starts_with('-') && !EXACT_SUBCOMMANDS.contains(sub) => {
+                Some(format!("{program} {sub}"))
+            }
+            _ => None,
+        },
+        [program, ..] => Some((*program).to_string()),
+        [] => None,
+    };
+    prefix.map_or_else(
+        || Pattern::Exact(command.to_string()),
+        |prefix| Pattern::Glob(format!("{prefix} *")),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grant {
+    pub permission: PermissionType,
+    pub pattern: Pattern,
+}
+
+impl Grant {
+    #[must_use]
+    pub fn for_request(request: &PermissionRequest) -> Self {
+        let target = request.target.trim();
+        let pattern = match request.operation_type {
+            PermissionType::CommandExecute => command_pattern(target),
+            _ => Pattern::Exact(target.to_string()),
+        };
+        Self {
+            permission: request.operation_type,
+            pattern,
+        }
+    }
+
+    #[must_use]
+    pub fn matches(&self, perm_type: PermissionType, target: &str) -> bool {
+        if self.permission != perm_type {
+            return false;
+        }
+        let target = target.trim();
+        match &self.pattern {
+            Pattern::Glob(_) if perm_type == PermissionType::CommandExecute => {
+                command_pattern(target) == self.pattern
+            }
+            pattern => pattern.matches(target).unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GrantFile {
+    #[serde(default)]
+    workspaces: BTreeMap<PathBuf, Vec<Grant>>,
+}
+
+#[derive(Debug)]
+pub struct WorkspacePermissions {
+    state_file: PathBuf,
+    workspace: PathBuf,
+    grants: Vec<Grant>,
+}
+
+impl WorkspacePermissions {
+    pub fn load(state_file: PathBuf, workspace: &Path) -> Result<Self> {
+        let workspace = workspace
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.to_path_buf());
+        let grants = read_grants(&state_file)?
+            .workspaces
+            .remove(&workspace)
+            .unwrap_or_default();
+        Ok(Self {
+            state_file,
+            workspace,
+            grants,
+        })
+    }
+
+    #[must_use]
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    #[must_use]
+    pub fn grants(&self) -> &[Grant] {
+        &self.grants
+    }
+
+    #[must_use]
+    pub fn is_allowed(&self, perm_type: PermissionType, target: &str) -> bool {
+        self.grants
+            .iter()
+            .any(|grant| grant.matches(perm_type, target))
+    }
+
+    pub fn remember(&mut self, grant: Grant) -> Result<()> {
+        if self.grants.contains(&grant) {
+            return Ok(());
+        }
+        self.grants.push(grant);
+
+        let mut file = read_grants(&self.state_file)?;
+        file.workspaces
+            .insert(self.workspace.clone(), self.grants.clone());
+        if let Some(parent) = self.state_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        atomic_write(&self.state_file, &serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+}
+
+fn read_grants(path: &Path) -> Result<GrantFile> {
+    if !path.exists() {
+        return Ok(GrantFile::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_command_grant_covers_program() {
+        let grant = Grant::for_request(&PermissionRequest::new(
+            PermissionType::CommandExecute,
+            "cargo build --release",
+        ));
+
+        assert_eq!(grant.pattern, Pattern::Glob("cargo *".to_string()));
+        assert!(grant.matches(PermissionType::CommandExecute, "cargo test"));
+        assert!(grant.matches(PermissionType::CommandExecute, "cargo"));
+        assert!(!grant.matches(PermissionType::CommandExecute, "cargo-audit"));
+        assert!(!grant.matches(PermissionType::CommandExecute, "cargo test && rm -rf /"));
+        assert!(!grant.matches(PermissionType::FileWrite, "cargo test"));
+    }
+
+    #[test]
+    fn test_risky_command_grants_are_exact() {
+        for command in [
+            "rm old.log",
+            "/bin/rm old.log",
+            "sudo apt update",
+            "bash build.sh",
+            "python x.py",
+            "python3.12 x.py",
+            "mkfs.ext4 /dev/sdb1",
+            "node index.js",
+            "curl https://example.com",
+            "git push origin main",
+            "git -C web status",
+            "git",
+        ] {
+            let grant = Grant::for_request(&PermissionRequest::new(
+                PermissionType::CommandExecute,
+                command,
+            ));
+            assert_eq!(grant.pattern, Pattern::Exact(command.to_string()));
+            assert!(grant.matches(PermissionType::CommandExecute, command));
+        }
+
+        let rm = Grant::for_request(&PermissionRequest::new(
+            PermissionType::CommandExecute,
+            "rm old.log",
+        ));
+        assert!(!rm.matches(PermissionType::CommandExecute, "rm -rf ~"));
+        let python = Grant::for_request(&PermissionRequest::new(
+            PermissionType::CommandExecute,
+            "python x.py",
+        ));
+        assert!(!python.matches(PermissionType::CommandExecute, "python -c 'import os'"));
+    }
+
+    #[test]
+    fn test_git_grant_covers_subcommand() {
+        let grant = Grant::for_request(&PermissionRequest::new(
+            PermissionType::CommandExecute,
+            "git status",
+        ));
+
+        assert_eq!(grant.pattern, Pattern::Glob("git status *".to_string()));
+        assert!(grant.matches(PermissionType::CommandExecute, "git status --short"));
+        assert!(!grant.matches(PermissionType::CommandExecute, "git push --force"));
+    }
+
+    #[test]
+    fn test_stored_program_glob_never_covers_risky_commands() {
+        let legacy = Grant {
+            permission: PermissionType::CommandExecute,
+            pattern: Pattern::Glob("rm *".to_string()),
+        };
+        assert!(!legacy.matches(PermissionType::CommandExecute, "rm -rf ~"));
+    }
+
+    #[test]
+    fn test_compound_command_grant_is_exact() {
+        let grant = Grant::for_request(&PermissionRequest::new(
+            PermissionType::CommandExecute,
+            "cd web && npm install",
+        ));
+
+        assert!(grant.matches(PermissionType::CommandExecute, "cd web && npm install"));
+        assert!(!grant.matches(PermissionType::CommandExecute, "cd web"));
+    }
+
+    #[test]
+    fn test_grants_persist_per_workspace() {
+        let temp = TempDir::new().unwrap();
+        let state_file = temp.path().join("state").join("workspace_permissions.json");
+        let project = temp.path().join("project");
+        let other = temp.path().join("other");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+
+        let mut permissions = WorkspacePermissions::load(state_file.clone(), &project).unwrap();
+        permissions
+            .remember(Grant::for_request(&PermissionRequest::new(
+                PermissionType::CommandExecute,
+                "cargo build",
+            )))
+            .unwrap();
+
+        let restored = WorkspacePermissions::load(state_file.clone(), &project).unwrap();
+        assert!(restored.is_allowed(PermissionType::CommandExecute, "cargo clippy"));
+        assert_eq!(restored.grants().len(), 1);
+
+        let elsewhere = WorkspacePermissions::load(state_file, &other).unwrap();
+        assert!(!elsewhere.is_allowed(PermissionType::CommandExecute, "cargo clippy"));
+    }
+}
@@ -1,14 +1,16 @@
 use crate::core::types::{
     CompletionRequest, CompletionResponse, ContentBlock, ContentDelta, ImageSource, Message,
-    MessageDelta, Role, StopReason, StreamEvent, ToolDefinition, Usage,
+    MessageDelta, Role, StopReason, StreamEvent, ToolChoice, ToolDefinition, Usage,
 };
 use crate::providers::types::ModelId;
 
 use super::types::{
-    ApiContentBlock, ApiImageSource, ApiMessage, ApiRequest, ApiResponse, ApiToolDefinition,
-    ApiUsage, SseDelta, SseEventData,
+    ApiContentBlock, ApiImageSource, ApiMessage, ApiRequest, ApiResponse, ApiThinking,
+    ApiToolChoice, ApiToolDefinition, ApiUsage, SseDelta, SseEventData,
 };
 
+const MIN_THINKING_BUDGET: u32 = 1024;
+
 pub fn to_api_request(model: &ModelId, request: &CompletionRequest) -> ApiRequest {
     let messages: Vec<ApiMessage> = request
         .messages
@@ -17,20 +19,52 @@ pub fn to_api_request(model: &ModelId, request: &CompletionRequest) -> ApiReques
         .filter(|m| !m.content.is_empty())
         .collect();
 
-    let tools: Option<Vec<ApiToolDefinition>> = if request.tools.is_empty() {
-        None
-    } else {
-        Some(request.tools.iter().map(to_api_tool).collect())
+    let mut tools: Vec<ApiToolDefinition> = request.tools.iter().map(to_api_tool).collect();
+    let tool_choice = match &request.response_format {
+        Some(format) => {
+            tools.push(ApiToolDefinition {
+                name: format.name.clone(),
+                description: "Respond with structured output matching this schema.".to_string(),
+                input_schema: format.schema.clone(),
+            });
+            Some(ApiToolChoice::Tool {
+                name: format.name.clone(),
+            })
+        }
+        None if tools.is_empty() => None,
+        None => match &request.tool_choice {
+            ToolChoice::Auto => None,
+            ToolChoice::None => Some(ApiToolChoice::None),
+            ToolChoice::Required => Some(ApiToolChoice::Any),
+            ToolChoice::Tool(name) => Some(ApiToolChoice::Tool { name: name.clone() }),
+        },
     };
+    let forces_tool = matches!(
+        tool_choice,
+        Some(ApiToolChoice::Any | ApiToolChoice::Tool { .. })
+    );
+
+    let tools = (!tools.is_empty()).then_some(tools);
+    let thinking = request
+        .thinking_budget
+        .filter(|_| !forces_tool)
+        .map(|budget| budget.min(request.max_tokens.saturating_sub(1)))
+        .filter(|&budget| budget >= MIN_THINKING_BUDGET)
+        .map(|budget_tokens| ApiThinking::Enabled { budget_tokens });
 
     ApiRequest {
         model: model.as_str().to_string(),
         messages,
         max_tokens: request.max_tokens,
         system: request.system_prompt.clone(),
-        temperature: Some(request.temperature),
+        temperature: thinking.is_none().then_some(request.temperature),
+        top_p: request.top_p.filter(|_| thinking.is_none()),
+        stop_sequences: (!request.stop_sequences.is_empty())
+            .then(|| request.stop_sequences.clone()),
         tools,
+        tool_choice,
         stream: None,
+        thinking,
     }
 }
 
@@ -80,17 +114,25 @@ fn to_api_content_block(block: &ContentBlock) -> ApiContentBlock {
             is_error: *is_error,
         },
         ContentBlock::Image { source } => ApiContentBlock::Image {
-            source: match source {
-                ImageSource::Base64 { media_type, data } => ApiImageSource::Base64 {
-                    media_type: media_type.clone(),
-                    data: data.clone(),
-                },
-                ImageSource::Url { url } => ApiImageSource::Url { url: url.clone() },
-            },
+            source: to_api_source(source),
+        },
+        ContentBlock::Document { source, title } => ApiContentBlock::Document {
+            source: to_api_source(source),
+            title: title.clone(),
         },
     }
 }
 
+fn to_api_source(source: &ImageSource) -> ApiImageSource {
+    match source {
+        ImageSource::Base64 { media_type, data } => ApiImageSource::Base64 {
+            media_type: media_type.clone(),
+            data: data.clone(),
+        },
+        ImageSource::Url { url } => ApiImageSource::Url { url: url.clone() },
+    }
+}
+
 fn to_api_tool(tool: &ToolDefinition) -> ApiToolDefinition {
     ApiToolDefinition {
         name: tool.name.clone(),
@@ -152,6 +194,9 @@ fn from_api_content_block(block: ApiContentBlock) -> ContentBlock {
         ApiContentBlock::Image { .. } => ContentBlock::Text {
             text: "[Image content]".to_string(),
         },
+        ApiContentBlock::Document { .. } => ContentBlock::Text {
+            text: "[Document content]".to_string(),
+        },
     }
 }
 
@@ -268,6 +313,106 @@ mod tests {
         assert_eq!(api_request.model, "claude-sonnet-4");
         assert_eq!(api_request.system, Some("Be helpful".to_string()));
         assert_eq!(api_request.messages.len(), 1);
+        assert!(api_request.stop_sequences.is_none());
+    }
+
+    #[test]
+    fn test_to_api_request_with_stop_sequences() {
+        let model = ModelId::new("claude-sonnet-4");
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_stop_sequences(vec!["</answer>".to_string()]);
+
+        let api_request = to_api_request(&model, &request);
+
+        assert_eq!(
+            api_request.stop_sequences,
+            Some(vec!["</answer>".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_to_api_request_with_thinking_budget() {
+        let model = ModelId::new("claude-sonnet-4");
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_max_tokens(16000)
+            .with_temperature(0.2)
+            .with_thinking_budget(Some(8000));
+
+        let json = serde_json::to_value(to_api_request(&model, &request)).expect("serialize");
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["thinking"]["budget_tokens"], 8000);
+        assert!(json.get("temperature").is_none());
+
+        let capped = to_api_request(&model, &request.clone().with_max_tokens(4096));
+        assert!(matches!(
+            capped.thinking,
+            Some(ApiThinking::Enabled {
+                budget_tokens: 4095
+            })
+        ));
+        let too_small = to_api_request(&model, &request.with_max_tokens(1000));
+        assert!(too_small.thinking.is_none());
+        assert_eq!(too_small.temperature, Some(0.2));
+    }
+
+    #[test]
+    fn test_to_api_request_with_response_format() {
+        let model = ModelId::new("claude-sonnet-4");
+        let schema =
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}});
+        let request =
+            CompletionRequest::new(vec![Message::user("Hello")]).with_response_format(schema);
+
+        let api_request = to_api_request(&model, &request);
+
+        let tools = api_request.tools.expect("forced tool");
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "structured_output");
+        assert!(matches!(
+            api_request.tool_choice,
+            Some(ApiToolChoice::Tool { ref name }) if name == "structured_output"
+        ));
+    }
+
+    #[test]
+    fn test_to_api_request_with_tool_choice() {
+        let model = ModelId::new("claude-sonnet-4");
+        let tool = ToolDefinition::new("read_file", "Read a file", serde_json::json!({}));
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_tools(vec![tool])
+            .with_thinking_budget(Some(4000))
+            .with_max_tokens(8000);
+
+        let auto = to_api_request(&model, &request);
+        assert!(auto.tool_choice.is_none());
+        assert!(auto.thinking.is_some());
+
+        let none = to_api_request(&model, &request.clone().with_tool_choice(ToolChoice::None));
+        assert!(matches!(none.tool_choice, Some(ApiToolChoice::None)));
+        assert!(none.thinking.is_some());
+
+        let required = to_api_request(
+            &model,
+            &request.clone().with_tool_choice(ToolChoice::Required),
+        );
+        assert!(matches!(required.tool_choice, Some(ApiToolChoice::Any)));
+        assert!(required.thinking.is_none());
+
+        let specific = to_api_request(
+            &model,
+            &request.with_tool_choice(ToolChoice::Tool("read_file".to_string())),
+        );
+        assert!(matches!(
+            specific.tool_choice,
+            Some(ApiToolChoice::Tool { ref name }) if name == "read_file"
+        ));
+
+        let without_tools = to_api_request(
+            &model,
+            &CompletionRequest::new(vec![Message::user("Hello")])
+                .with_tool_choice(ToolChoice::Required),
+        );
+        assert!(without_tools.tool_choice.is_none());
     }
 
     #[test]
@@ -338,4 +483,34 @@ mod tests {
             ApiContentBlock::Text { text } if text == "Hello"
         ));
     }
+
+    #[test]
+    fn test_parse_stream_event_tool_input_and_thinking_deltas() {
+        let start = r#"{"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "write_file", "input": {}}}"#;
+        assert!(matches!(
+            parse_stream_event(start),
+            Some(StreamEvent::ContentBlockStart {
+                index: 1,
+                content_block: ContentBlock::ToolUse { .. }
+            })
+        ));
+
+        let json = r#"{"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"path\": \"a.t"}}"#;
+        assert!(matches!(
+            parse_stream_event(json),
+            Some(StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: ContentDelta::InputJsonDelta { ref partial_json }
+            }) if partial_json == r#"{"path": "a.t"#
+        ));
+
+        let json = r#"{"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Let me check"}}"#;
+        assert!(matches!(
+            parse_stream_event(json),
+            Some(StreamEvent::ContentBlockDelta {
+                delta: ContentDelta::ThinkingDelta { ref thinking },
+                ..
+            }) if thinking == "Let me check"
+        ));
+    }
 }
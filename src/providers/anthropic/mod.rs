@@ -11,18 +11,24 @@ use crate::core::llm::LLM;
 use crate::core::types::{CompletionRequest, CompletionResponse, StreamResponse};
 use crate::providers::error::ProviderError;
 use crate::providers::http::{AuthStrategy, HttpClient, HttpConfig, SseParser};
-use crate::providers::types::{ApiKey, BaseUrl, ModelId};
+use crate::providers::types::{ApiKey, BaseUrl, ExtraBody, ModelId};
 
 const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
 const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 const API_VERSION: &str = "2023-06-01";
 
+pub const FINE_GRAINED_TOOL_STREAMING: &str = "fine-grained-tool-streaming-2025-05-14";
+pub const INTERLEAVED_THINKING: &str = "interleaved-thinking-2025-05-14";
+const DEFAULT_BETAS: &[&str] = &[FINE_GRAINED_TOOL_STREAMING, INTERLEAVED_THINKING];
+
 #[derive(Clone)]
 pub struct AnthropicProvider {
     http: HttpClient,
     auth: AuthStrategy,
     model: ModelId,
     base_url: BaseUrl,
+    extra_body: ExtraBody,
+    betas: Vec<String>,
 }
 
 impl std::fmt::Debug for AnthropicProvider {
@@ -30,6 +36,7 @@ impl std::fmt::Debug for AnthropicProvider {
         f.debug_struct("AnthropicProvider")
             .field("model", &self.model)
             .field("base_url", &self.base_url)
+            .field("betas", &self.betas)
             .finish_non_exhaustive()
     }
 }
@@ -37,10 +44,12 @@ impl std::fmt::Debug for AnthropicProvider {
 impl AnthropicProvider {
     pub fn new(api_key: ApiKey) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::new()?,
+            http: HttpClient::with_config(HttpConfig::default().for_provider("anthropic"))?,
             auth: AuthStrategy::anthropic(api_key),
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
+            betas: default_betas(),
         })
     }
 
@@ -54,10 +63,12 @@ impl AnthropicProvider {
         http_config: HttpConfig,
     ) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::with_config(http_config)?,
+            http: HttpClient::with_config(http_config.or_provider("anthropic"))?,
             auth: AuthStrategy::anthropic(api_key),
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
+            betas: default_betas(),
         })
     }
 
@@ -73,11 +84,47 @@ impl AnthropicProvider {
         self
     }
 
+    #[must_use]
+    pub fn with_extra_body(mut self, extra_body: ExtraBody) -> Self {
+        self.extra_body = extra_body;
+        self
+    }
+
+    #[must_use]
+    pub fn with_betas<I, S>(mut self, betas: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.betas = betas.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn beta_header(&self) -> Option<String> {
+        (!self.betas.is_empty()).then(|| self.betas.join(","))
+    }
+
+    fn post(&self, url: &str, body: String) -> reqwest_middleware::RequestBuilder {
+        let mut builder = self
+            .http
+            .post(url, &self.auth)
+            .header("anthropic-version", API_VERSION)
+            .header("content-type", "application/json");
+        if let Some(betas) = self.beta_header() {
+            builder = builder.header("anthropic-beta", betas);
+        }
+        builder.body(body)
+    }
+
     fn parse_error(status: reqwest::StatusCode, body: &str) -> ProviderError {
         ProviderError::from_status(status.as_u16(), body, "ANTHROPIC_API_KEY")
     }
 }
 
+fn default_betas() -> Vec<String> {
+    DEFAULT_BETAS.iter().map(ToString::to_string).collect()
+}
+
 impl From<&str> for ModelId {
     fn from(s: &str) -> Self {
         Self::new(s.to_string())
@@ -122,15 +169,13 @@ impl LLM for AnthropicProvider {
         let api_request = convert::to_api_request(&self.model, &request);
         let url = self.base_url.join("/v1/messages");
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
-            .http
-            .post(&url, &self.auth)
-            .header("anthropic-version", API_VERSION)
-            .header("content-type", "application/json")
-            .body(body)
+            .post(&url, body)
             .send()
             .await
             .map_err(|e| ProviderError::Connection(e.to_string()))?;
@@ -155,15 +200,13 @@ impl LLM for AnthropicProvider {
 
         let url = self.base_url.join("/v1/messages");
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
-            .http
-            .post(&url, &self.auth)
-            .header("anthropic-version", API_VERSION)
-            .header("content-type", "application/json")
-            .body(body)
+            .post(&url, body)
             .send()
             .await
             .map_err(|e| ProviderError::Connection(e.to_string()))?;
@@ -220,6 +263,18 @@ mod tests {
         assert!(!debug.contains("secret-key"));
     }
 
+    #[test]
+    fn test_beta_header() {
+        let provider = AnthropicProvider::new(ApiKey::new("test-key")).expect("create provider");
+        assert_eq!(
+            provider.beta_header().as_deref(),
+            Some("fine-grained-tool-streaming-2025-05-14,interleaved-thinking-2025-05-14")
+        );
+
+        let provider = provider.with_betas(Vec::<String>::new());
+        assert!(provider.beta_header().is_none());
+    }
+
     #[test]
     fn test_parse_error_401() {
         let body = r#"{"error": {"message": "Invalid API key"}}"#;
@@ -11,9 +11,32 @@ pub struct ApiRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ApiToolDefinition>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ApiToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ApiThinking>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiThinking {
+    Enabled { budget_tokens: u32 },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiToolChoice {
+    Auto,
+    Any,
+    None,
+    Tool { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,6 +76,11 @@ pub enum ApiContentBlock {
     Image {
         source: ApiImageSource,
     },
+    Document {
+        source: ApiImageSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,8 +193,12 @@ mod tests {
             max_tokens: 4096,
             system: Some("You are helpful".to_string()),
             temperature: Some(0.7),
+            top_p: None,
+            stop_sequences: None,
             tools: None,
+            tool_choice: None,
             stream: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&request).expect("serialize");
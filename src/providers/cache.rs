@@ -0,0 +1,388 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::config::get_config_dir;
+use crate::core::augmented_llm::stream_accumulator::StreamAccumulator;
+use crate::core::error::Result;
+use crate::core::llm::LLM;
+use crate::core::types::{
+    CompletionRequest, CompletionResponse, ContentBlock, ContentDelta, Message, MessageDelta, Role,
+    StopReason, StreamEvent, StreamResponse, Usage,
+};
+use crate::providers::schema::SchemaSupport;
+use crate::tools::fs::atomic_write;
+
+pub type CacheHitHandler = Arc<dyn Fn() + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    message: Message,
+    stop_reason: StopReason,
+    usage: Usage,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    #[must_use]
+    pub fn with_default_dir() -> Option<Self> {
+        get_config_dir().map(|dir| Self::new(dir.join("cache").join("responses")))
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn key(provider: &str, model: &str, request: &CompletionRequest) -> Result<String> {
+        let payload = serde_json::to_vec(&serde_json::json!({
+            "provider": provider,
+            "model": model,
+            "messages": &request.messages,
+            "system_prompt": &request.system_prompt,
+            "tools": &request.tools,
+            "tool_choice": &request.tool_choice,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "top_p": request.top_p,
+            "stop_sequences": &request.stop_sequences,
+            "response_format": &request.response_format,
+            "reasoning_effort": request.reasoning_effort,
+            "verbosity": request.verbosity,
+            "thinking_budget": request.thinking_budget,
+        }))?;
+        Ok(Sha256::digest(payload)
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            }))
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<CompletionResponse> {
+        let content = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&content).ok()?;
+        Some(CompletionResponse::new(
+            cached.message,
+            cached.stop_reason,
+            cached.usage,
+        ))
+    }
+
+    pub fn put(&self, key: &str, response: &CompletionResponse) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let cached = CachedResponse {
+            message: response.message.clone(),
+            stop_reason: response.stop_reason,
+            usage: response.usage,
+        };
+        atomic_write(
+            &self.entry_path(key),
+            &serde_json::to_string_pretty(&cached)?,
+        )?;
+        Ok(())
+    }
+
+    fn store(&self, key: &str, response: &CompletionResponse) {
+        if let Err(e) = self.put(key, response) {
+            tracing::warn!("Failed to write response cache {}: {e}", self.dir.display());
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+pub struct CachedLLM {
+    inner: Arc<dyn LLM>,
+    cache: ResponseCache,
+    on_hit: Option<CacheHitHandler>,
+}
+
+impl CachedLLM {
+    #[must_use]
+    pub fn new(inner: Arc<dyn LLM>, cache: ResponseCache) -> Self {
+        Self {
+            inner,
+            cache,
+            on_hit: None,
+        }
+    }
+
+    #[must_use]
+    pub fn on_hit(mut self, handler: CacheHitHandler) -> Self {
+        self.on_hit = Some(handler);
+        self
+    }
+
+    fn lookup(&self, request: &CompletionRequest) -> Result<(String, Option<CompletionResponse>)> {
+        let key = ResponseCache::key(self.inner.name(), self.inner.model(), request)?;
+        let hit = self.cache.get(&key);
+        if hit.is_some() {
+            tracing::debug!(key, "Response cache hit");
+            if let Some(handler) = &self.on_hit {
+                handler();
+            }
+        }
+        Ok((key, hit))
+    }
+}
+
+#[async_trait]
+impl LLM for CachedLLM {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn schema_support(&self) -> SchemaSupport {
+        self.inner.schema_support()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let (key, hit) = self.lookup(&request)?;
+        if let Some(response) = hit {
+            return Ok(CompletionResponse {
+                usage: Usage::default(),
+                ..response
+            });
+        }
+        let response = self.inner.complete(request).await?;
+        self.cache.store(&key, &response);
+        Ok(response)
+    }
+
+    async fn stream(&self, request: CompletionRequest) -> Result<StreamResponse> {
+        let (key, hit) = self.lookup(&request)?;
+        if let Some(response) = hit {
+            return Ok(Box::pin(futures::stream::iter(
+                replay_events(response).into_iter().map(Ok),
+            )));
+        }
+
+        let cache = self.cache.clone();
+        let mut recorder = Some(StreamRecorder::default());
+        let stream = self.inner.stream(request).await?.map(move |event| {
+            match &event {
+                Ok(StreamEvent::MessageStop) => {
+                    if let Some(recorder) = recorder.take() {
+                        cache.store(&key, &recorder.finish());
+                    }
+                }
+                Ok(event) => {
+                    if let Some(recorder) = &mut recorder {
+                        recorder.record(event.clone());
+                    }
+                }
+                Err(_) => recorder = None,
+            }
+            event
+        });
+        Ok(Box::pin(stream))
+    }
+}
+
+#[derive(Default)]
+struct StreamRecorder {
+    accumulator: StreamAccumulator,
+    stop_reason: Option<StopReason>,
+    usage: Usage,
+}
+
+impl StreamRecorder {
+    fn record(&mut self, event: StreamEvent) {
+        match event {
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => self.accumulator.handle_block_start(index, content_block),
+            StreamEvent::ContentBlockDelta { index, delta } => {
+                self.accumulator.handle_delta(index, delta);
+            }
+            StreamEvent::MessageStart { usage: Some(u), .. } => self.usage.add(&u),
+            StreamEvent::MessageDelta { delta } => {
+                if let Some(u) = delta.usage {
+                    self.usage.add(&u);
+                }
+                self.stop_reason = delta.stop_reason.or(self.stop_reason);
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> CompletionResponse {
+        let content = self.accumulator.into_content_blocks();
+        let stop_reason = self.stop_reason.unwrap_or_else(|| {
+            if content
+                .iter()
+                .any(|block| matches!(block, ContentBlock::ToolUse { .. }))
+            {
+                StopReason::ToolUse
+            } else {
+                StopReason::EndTurn
+            }
+        });
+        CompletionResponse::new(
+            Message::new(Role::Assistant, content),
+            stop_reason,
+            self.usage,
+        )
+    }
+}
+
+fn replay_events(response: CompletionResponse) -> Vec<StreamEvent> {
+    let mut events = vec![StreamEvent::MessageStart {
+        message: Message::new(Role::Assistant, Vec::new()),
+        usage: None,
+    }];
+    for (index, block) in response.message.content.into_iter().enumerate() {
+        let (start, delta) = match block {
+            ContentBlock::Text { text } => (
+                ContentBlock::text(""),
+                Some(ContentDelta::TextDelta { text }),
+            ),
+            ContentBlock::Thinking {
+                thinking,
+                signature,
+            } => (
+                ContentBlock::Thinking {
+                    thinking: String::new(),
+                    signature,
+                },
+                Some(ContentDelta::ThinkingDelta { thinking }),
+            ),
+            block => (block, None),
+        };
+        events.push(StreamEvent::ContentBlockStart {
+            index,
+            content_block: start,
+        });
+        if let Some(delta) = delta {
+            events.push(StreamEvent::ContentBlockDelta { index, delta });
+        }
+        events.push(StreamEvent::ContentBlockStop { index });
+    }
+    events.push(StreamEvent::MessageDelta {
+        delta: MessageDelta {
+            stop_reason: Some(response.stop_reason),
+            usage: None,
+        },
+    });
+    events.push(StreamEvent::MessageStop);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{MockLLM, MockResponse};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    fn request(prompt: &str) -> CompletionRequest {
+        CompletionRequest::new(vec![Message::user(prompt)]).with_system_prompt("be brief")
+    }
+
+    #[test]
+    fn test_key_depends_on_request() {
+        let key = ResponseCache::key("mock", "mock-model", &request("hi")).unwrap();
+        assert_eq!(key.len(), 64);
+        assert_eq!(
+            key,
+            ResponseCache::key("mock", "mock-model", &request("hi")).unwrap()
+        );
+        assert_ne!(
+            key,
+            ResponseCache::key("mock", "mock-model", &request("hello")).unwrap()
+        );
+        assert_ne!(
+            key,
+            ResponseCache::key("mock", "other-model", &request("hi")).unwrap()
+        );
+        assert_ne!(
+            key,
+            ResponseCache::key("mock", "mock-model", &request("hi").with_temperature(0.5)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_served_from_cache() {
+        let temp = TempDir::new().unwrap();
+        let mock = MockLLM::new().with_response(MockResponse::text("cached answer"));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let llm = CachedLLM::new(
+            Arc::new(mock.clone()),
+            ResponseCache::new(temp.path().to_path_buf()),
+        )
+        .on_hit(Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        let first = llm.complete(request("hi")).await.unwrap();
+        let second = llm.complete(request("hi")).await.unwrap();
+
+        assert_eq!(mock.request_count(), 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(second.message.first_text(), first.message.first_text());
+        assert_eq!(second.usage.total(), 0);
+        assert!(llm.complete(request("other")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_replays_cached_blocks() {
+        let temp = TempDir::new().unwrap();
+        let cache = ResponseCache::new(temp.path().to_path_buf());
+        let key = ResponseCache::key("mock", "mock-model", &request("list")).unwrap();
+        let message = Message::new(
+            Role::Assistant,
+            vec![
+                ContentBlock::text("Listing files"),
+                ContentBlock::tool_use("bash", serde_json::json!({"command": "ls"})),
+            ],
+        );
+        cache
+            .put(
+                &key,
+                &CompletionResponse::new(message.clone(), StopReason::ToolUse, Usage::new(3, 4)),
+            )
+            .unwrap();
+
+        let mock = MockLLM::new();
+        let llm = CachedLLM::new(Arc::new(mock.clone()), cache);
+        let events: Vec<StreamEvent> = llm
+            .stream(request("list"))
+            .await
+            .unwrap()
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        let mut recorder = StreamRecorder::default();
+        for event in events {
+            recorder.record(event);
+        }
+        let replayed = recorder.finish();
+        assert_eq!(mock.request_count(), 0);
+        assert_eq!(replayed.message, message);
+        assert_eq!(replayed.stop_reason, StopReason::ToolUse);
+    }
+}
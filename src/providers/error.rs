@@ -54,6 +54,45 @@ impl ProviderError {
         )
     }
 
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::Authentication { .. } => "ERR_AUTH",
+            Self::RateLimit { .. } => "ERR_RATE_LIMIT",
+            Self::ContextWindowExceeded { .. } => "ERR_CONTEXT_WINDOW",
+            Self::Connection(_) => "ERR_CONNECTION",
+            Self::Timeout(_) => "ERR_TIMEOUT",
+            Self::Server { .. } => "ERR_SERVER",
+            Self::InvalidRequest(_) => "ERR_INVALID_REQUEST",
+            Self::ParseError(_) => "ERR_PARSE",
+            Self::StreamError(_) => "ERR_STREAM",
+            Self::Configuration(_) => "ERR_PROVIDER_CONFIG",
+            Self::ModelNotFound(_) => "ERR_MODEL_NOT_FOUND",
+        }
+    }
+
+    #[must_use]
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            Self::Authentication { hint, .. } => return hint.clone(),
+            Self::RateLimit { .. } => {
+                "Wait a moment and retry, or set [rate_limits] in the config to pace requests"
+            }
+            Self::ContextWindowExceeded { .. } => {
+                "Start a fresh conversation with /clear or attach less content"
+            }
+            Self::Connection(_) => "Check your network connection and the provider base_url",
+            Self::Timeout(_) => "Retry, or switch to a faster model with /model",
+            Self::Server { .. } => "The provider is having trouble; retry shortly",
+            Self::ModelNotFound(_) => "Pick an available model with /model",
+            Self::InvalidRequest(_)
+            | Self::ParseError(_)
+            | Self::StreamError(_)
+            | Self::Configuration(_) => return None,
+        };
+        Some(hint.to_string())
+    }
+
     #[must_use]
     pub const fn retry_after(&self) -> Option<Duration> {
         match self {
@@ -31,7 +31,8 @@ fn create_anthropic_provider(model: &ModelInfo) -> Result<Arc<dyn LLM>> {
 
     let mut provider = AnthropicProvider::new(api_key)
         .map_err(|e| AgentError::Config(e.to_string()))?
-        .with_model(&model.id);
+        .with_model(&model.id)
+        .with_extra_body(model.extra_body());
 
     if let Some(base_url) = model.base_url() {
         provider = provider.with_base_url(base_url);
@@ -45,7 +46,8 @@ fn create_openai_provider(model: &ModelInfo) -> Result<Arc<dyn LLM>> {
 
     let mut provider = OpenAIProvider::new(api_key)
         .map_err(|e| AgentError::Config(e.to_string()))?
-        .with_model(&model.id);
+        .with_model(&model.id)
+        .with_extra_body(model.extra_body());
 
     if let Some(base_url) = model.base_url() {
         provider = provider.with_base_url(base_url);
@@ -59,7 +61,8 @@ fn create_gemini_provider(model: &ModelInfo) -> Result<Arc<dyn LLM>> {
 
     let mut provider = GeminiProvider::new(api_key)
         .map_err(|e| AgentError::Config(e.to_string()))?
-        .with_model(&model.id);
+        .with_model(&model.id)
+        .with_extra_body(model.extra_body());
 
     if let Some(base_url) = model.base_url() {
         provider = provider.with_base_url(base_url);
@@ -214,6 +217,8 @@ fn apply_config_overrides(config: &mut OpenAICompatConfig, model: &ModelInfo) {
         }
     }
 
+    config.extra_body.merge(&model.extra_body());
+
     if !model.supports_tools {
         config.capabilities.tools = false;
     }
@@ -223,9 +228,10 @@ fn get_api_key(model: &ModelInfo, default_env: &str) -> Result<ApiKey> {
     let env_var = model.api_key_env().unwrap_or(default_env);
     ApiKey::from_env(env_var).map_err(|_| {
         AgentError::Config(format!(
-            "API key not found. Set {} environment variable for {} provider.",
+            "API key not found. Set {} environment variable for {} provider, or run `smith config keys set {}`.",
             env_var,
-            model.provider.display_name()
+            model.provider.display_name(),
+            model.provider.id()
         ))
     })
 }
@@ -1,18 +1,26 @@
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::core::types::{
     CompletionRequest, CompletionResponse, ContentBlock, ContentDelta as CoreContentDelta,
     ImageSource, Message, MessageDelta, Role, StopReason, StreamEvent as CoreStreamEvent,
-    ToolDefinition, Usage,
+    ToolChoice, ToolDefinition, Usage,
 };
+use crate::providers::schema::{SchemaSupport, sanitize};
 
 use super::types::{
-    ApiRequest, ApiResponse, Content, FunctionCall, FunctionDeclaration, FunctionResponse,
-    FunctionResponseContent, GenerationConfig, InlineData, Part, Tool,
+    ApiRequest, ApiResponse, Content, FunctionCall, FunctionCallingConfig, FunctionDeclaration,
+    FunctionResponse, FunctionResponseContent, GenerationConfig, InlineData, Part, Tool,
+    ToolConfig,
 };
 
 pub fn to_api_request(request: &CompletionRequest) -> ApiRequest {
-    let contents: Vec<Content> = request.messages.iter().filter_map(to_content).collect();
+    let call_names = tool_call_names(&request.messages);
+    let contents: Vec<Content> = request
+        .messages
+        .iter()
+        .filter_map(|message| to_content(message, &call_names))
+        .collect();
 
     let system_instruction = request.system_prompt.as_ref().map(|prompt| Content {
         role: "user".to_string(),
@@ -29,29 +37,69 @@ pub fn to_api_request(request: &CompletionRequest) -> ApiRequest {
         }])
     };
 
+    let tool_config = tools
+        .as_ref()
+        .and_then(|_| to_tool_config(&request.tool_choice));
+
     let generation_config = Some(GenerationConfig {
         max_output_tokens: Some(request.max_tokens),
         temperature: Some(request.temperature),
+        top_p: request.top_p,
         stop_sequences: if request.stop_sequences.is_empty() {
             None
         } else {
             Some(request.stop_sequences.clone())
         },
+        response_mime_type: request
+            .response_format
+            .as_ref()
+            .map(|_| "application/json".to_string()),
+        response_schema: request
+            .response_format
+            .as_ref()
+            .map(|format| convert_to_gemini_schema(&format.schema)),
     });
 
     ApiRequest {
         contents,
         system_instruction,
         tools,
+        tool_config,
         generation_config,
     }
 }
 
-fn to_content(message: &Message) -> Option<Content> {
+fn to_tool_config(choice: &ToolChoice) -> Option<ToolConfig> {
+    let (mode, allowed_function_names) = match choice {
+        ToolChoice::Auto => return None,
+        ToolChoice::None => ("NONE", None),
+        ToolChoice::Required => ("ANY", None),
+        ToolChoice::Tool(name) => ("ANY", Some(vec![name.clone()])),
+    };
+    Some(ToolConfig {
+        function_calling_config: FunctionCallingConfig {
+            mode,
+            allowed_function_names,
+        },
+    })
+}
+
+fn tool_call_names(messages: &[Message]) -> HashMap<&str, &str> {
+    messages
+        .iter()
+        .flat_map(|message| &message.content)
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse { id, name, .. } => Some((id.as_str(), name.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn to_content(message: &Message, call_names: &HashMap<&str, &str>) -> Option<Content> {
     let role = match message.role {
         Role::User => "user",
         Role::Assistant => "model",
-        Role::Tool => return Some(to_tool_response_content(message)),
+        Role::Tool => return Some(to_tool_response_content(message, call_names)),
         Role::System => return None,
     };
 
@@ -75,18 +123,19 @@ fn to_part(block: &ContentBlock, _role: Role) -> Option<Part> {
     match block {
         ContentBlock::Text { text } => Some(Part::Text { text: text.clone() }),
         ContentBlock::ToolUse {
-            id: _,
+            id,
             name,
             input,
             signature,
         } => Some(Part::FunctionCall {
             function_call: FunctionCall {
+                id: Some(id.clone()),
                 name: name.clone(),
                 args: input.clone(),
             },
             thought_signature: signature.clone(),
         }),
-        ContentBlock::Image { source } => match source {
+        ContentBlock::Image { source } | ContentBlock::Document { source, .. } => match source {
             ImageSource::Base64 { media_type, data } => Some(Part::InlineData {
                 inline_data: InlineData {
                     mime_type: media_type.clone(),
@@ -101,7 +150,7 @@ fn to_part(block: &ContentBlock, _role: Role) -> Option<Part> {
     }
 }
 
-fn to_tool_response_content(message: &Message) -> Content {
+fn to_tool_response_content(message: &Message, call_names: &HashMap<&str, &str>) -> Content {
     let parts: Vec<Part> = message
         .content
         .iter()
@@ -118,14 +167,20 @@ fn to_tool_response_content(message: &Message) -> Content {
                     serde_json::json!({ "result": content })
                 };
 
+                let name = call_names
+                    .get(tool_use_id.as_str())
+                    .map_or_else(|| tool_use_id.clone(), ToString::to_string);
                 Some(Part::FunctionResponse {
                     function_response: FunctionResponse {
-                        name: tool_use_id.clone(),
+                        id: Some(tool_use_id.clone()),
+                        name,
                         response: FunctionResponseContent {
                             content: response_content,
                         },
                     },
                 })
+            } else if let ContentBlock::Document { .. } = block {
+                to_part(block, Role::Tool)
             } else {
                 None
             }
@@ -148,81 +203,8 @@ fn to_function_declaration(tool: &ToolDefinition) -> FunctionDeclaration {
     }
 }
 
-const SUPPORTED_SCHEMA_FIELDS: &[&str] = &[
-    "type",
-    "nullable",
-    "required",
-    "format",
-    "description",
-    "properties",
-    "items",
-    "enum",
-];
-
 fn convert_to_gemini_schema(value: &serde_json::Value) -> serde_json::Value {
-    convert_schema_internal(value, false)
-}
-
-fn convert_schema_internal(
-    value: &serde_json::Value,
-    is_properties_map: bool,
-) -> serde_json::Value {
-    match value {
-        serde_json::Value::Object(map) => {
-            let mut result: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
-
-            for (key, val) in map {
-                if is_properties_map {
-                    result.insert(key.clone(), convert_schema_internal(val, false));
-                    continue;
-                }
-
-                if !SUPPORTED_SCHEMA_FIELDS.contains(&key.as_str()) {
-                    continue;
-                }
-
-                if key == "type" {
-                    if let serde_json::Value::Array(types) = val {
-                        let mut actual_type = None;
-                        let mut is_nullable = false;
-
-                        for t in types {
-                            if let serde_json::Value::String(s) = t {
-                                if s == "null" {
-                                    is_nullable = true;
-                                } else {
-                                    actual_type = Some(s.clone());
-                                }
-                            }
-                        }
-
-                        if let Some(t) = actual_type {
-                            result.insert("type".to_string(), serde_json::json!(t));
-                        }
-                        if is_nullable {
-                            result.insert("nullable".to_string(), serde_json::json!(true));
-                        }
-                    } else {
-                        result.insert(key.clone(), val.clone());
-                    }
-                } else if key == "properties" {
-                    result.insert(key.clone(), convert_schema_internal(val, true));
-                } else if key == "items" {
-                    result.insert(key.clone(), convert_schema_internal(val, false));
-                } else {
-                    result.insert(key.clone(), val.clone());
-                }
-            }
-
-            serde_json::Value::Object(result)
-        }
-        serde_json::Value::Array(arr) => serde_json::Value::Array(
-            arr.iter()
-                .map(|v| convert_schema_internal(v, false))
-                .collect(),
-        ),
-        other => other.clone(),
-    }
+    sanitize(value, &SchemaSupport::OPENAPI)
 }
 
 pub fn from_api_response(response: ApiResponse) -> CompletionResponse {
@@ -242,12 +224,7 @@ pub fn from_api_response(response: ApiResponse) -> CompletionResponse {
     } else {
         candidate
             .and_then(|c| c.finish_reason.as_ref())
-            .map_or(StopReason::EndTurn, |reason| match reason.as_str() {
-                "STOP" => StopReason::EndTurn,
-                "MAX_TOKENS" => StopReason::MaxTokens,
-                "STOP_SEQUENCE" => StopReason::StopSequence,
-                _ => StopReason::EndTurn,
-            })
+            .map_or(StopReason::EndTurn, |reason| stop_reason(reason))
     };
 
     let usage = response.usage_metadata.map_or_else(Usage::default, |u| {
@@ -274,74 +251,187 @@ fn from_content(content: &Content) -> (Vec<ContentBlock>, bool) {
             } => {
                 has_function_calls = true;
                 blocks.push(ContentBlock::ToolUse {
-                    id: Uuid::new_v4().to_string(),
+                    id: call_id(function_call),
                     name: function_call.name.clone(),
                     input: function_call.args.clone(),
                     signature: thought_signature.clone(),
                 });
             }
-            Part::InlineData { .. } | Part::FunctionResponse { .. } => {}
+            Part::InlineData { .. } | Part::FileData { .. } | Part::FunctionResponse { .. } => {}
         }
     }
 
     (blocks, has_function_calls)
 }
 
-#[must_use]
-pub fn parse_stream_event(data: &str) -> Option<CoreStreamEvent> {
-    let response: ApiResponse = serde_json::from_str(data).ok()?;
-    let candidate = response.candidates.first()?;
+fn call_id(call: &FunctionCall) -> String {
+    call.id
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
 
-    if candidate.finish_reason.is_some() {
-        let stop_reason = candidate
-            .finish_reason
-            .as_ref()
-            .map_or(StopReason::EndTurn, |reason| match reason.as_str() {
-                "STOP" => StopReason::EndTurn,
-                "MAX_TOKENS" => StopReason::MaxTokens,
-                "STOP_SEQUENCE" => StopReason::StopSequence,
-                _ => StopReason::EndTurn,
-            });
+fn stop_reason(finish_reason: &str) -> StopReason {
+    match finish_reason {
+        "MAX_TOKENS" => StopReason::MaxTokens,
+        "STOP_SEQUENCE" => StopReason::StopSequence,
+        _ => StopReason::EndTurn,
+    }
+}
 
-        return Some(CoreStreamEvent::MessageDelta {
-            delta: MessageDelta {
-                stop_reason: Some(stop_reason),
-                usage: response
-                    .usage_metadata
-                    .map(|u| Usage::new(u.prompt_token_count, u.candidates_token_count)),
+#[derive(Debug, Default)]
+pub struct StreamState {
+    next_index: usize,
+    text_index: Option<usize>,
+    last_call: Option<StreamedCall>,
+    saw_function_call: bool,
+}
+
+#[derive(Debug)]
+struct StreamedCall {
+    index: usize,
+    call: FunctionCall,
+    content_block: ContentBlock,
+}
+
+impl StreamState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    const fn start_block(&mut self) -> usize {
+        let index = self.next_index;
+        self.next_index += 1;
+        index
+    }
+
+    fn text(&mut self, text: &str, events: &mut Vec<CoreStreamEvent>) {
+        let index = if let Some(index) = self.text_index {
+            index
+        } else {
+            let index = self.start_block();
+            self.text_index = Some(index);
+            self.last_call = None;
+            events.push(CoreStreamEvent::ContentBlockStart {
+                index,
+                content_block: ContentBlock::text(""),
+            });
+            index
+        };
+        events.push(CoreStreamEvent::ContentBlockDelta {
+            index,
+            delta: CoreContentDelta::TextDelta {
+                text: text.to_string(),
             },
         });
     }
 
+    fn function_call(
+        &mut self,
+        call: &FunctionCall,
+        signature: Option<&String>,
+        events: &mut Vec<CoreStreamEvent>,
+    ) {
+        self.saw_function_call = true;
+        self.text_index = None;
+        let continues_last = self.last_call.as_ref().is_some_and(|last| {
+            call.name.is_empty() || (call.id.is_some() && call.id == last.call.id)
+        });
+
+        let streamed = if continues_last && let Some(mut last) = self.last_call.take() {
+            merge_args(&mut last.call.args, &call.args);
+            if let ContentBlock::ToolUse {
+                input,
+                signature: slot,
+                ..
+            } = &mut last.content_block
+            {
+                input.clone_from(&last.call.args);
+                if slot.is_none() {
+                    *slot = signature.cloned();
+                }
+            }
+            last
+        } else {
+            let index = self.start_block();
+            StreamedCall {
+                index,
+                call: call.clone(),
+                content_block: ContentBlock::ToolUse {
+                    id: call_id(call),
+                    name: call.name.clone(),
+                    input: call.args.clone(),
+                    signature: signature.cloned(),
+                },
+            }
+        };
+
+        events.push(CoreStreamEvent::ContentBlockStart {
+            index: streamed.index,
+            content_block: streamed.content_block.clone(),
+        });
+        self.last_call = Some(streamed);
+    }
+}
+
+fn merge_args(target: &mut serde_json::Value, extra: &serde_json::Value) {
+    match (target, extra) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(extra)) => {
+            for (key, value) in extra {
+                match target.get_mut(key) {
+                    Some(existing) if !existing.is_null() => merge_args(existing, value),
+                    _ => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (serde_json::Value::String(target), serde_json::Value::String(extra)) => {
+            target.push_str(extra);
+        }
+        (target, extra) => target.clone_from(extra),
+    }
+}
+
+pub fn parse_stream_event(data: &str, state: &mut StreamState) -> Vec<CoreStreamEvent> {
+    let mut events = Vec::new();
+    let Ok(response) = serde_json::from_str::<ApiResponse>(data) else {
+        return events;
+    };
+    let Some(candidate) = response.candidates.first() else {
+        return events;
+    };
+
     if let Some(content) = &candidate.content {
-        for (idx, part) in content.parts.iter().enumerate() {
+        for part in &content.parts {
             match part {
-                Part::Text { text } if !text.is_empty() => {
-                    return Some(CoreStreamEvent::ContentBlockDelta {
-                        index: idx,
-                        delta: CoreContentDelta::TextDelta { text: text.clone() },
-                    });
-                }
+                Part::Text { text } if !text.is_empty() => state.text(text, &mut events),
                 Part::FunctionCall {
                     function_call,
                     thought_signature,
-                } => {
-                    return Some(CoreStreamEvent::ContentBlockStart {
-                        index: idx,
-                        content_block: ContentBlock::ToolUse {
-                            id: Uuid::new_v4().to_string(),
-                            name: function_call.name.clone(),
-                            input: function_call.args.clone(),
-                            signature: thought_signature.clone(),
-                        },
-                    });
-                }
+                } => state.function_call(function_call, thought_signature.as_ref(), &mut events),
                 _ => {}
             }
         }
     }
 
-    None
+    if let Some(reason) = &candidate.finish_reason {
+        let stop_reason = if state.saw_function_call {
+            StopReason::ToolUse
+        } else {
+            stop_reason(reason)
+        };
+        events.push(CoreStreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: Some(stop_reason),
+                usage: response
+                    .usage_metadata
+                    .map(|u| Usage::new(u.prompt_token_count, u.candidates_token_count)),
+            },
+        });
+    }
+
+    events
 }
 
 #[cfg(test)]
@@ -384,6 +474,37 @@ mod tests {
 
         assert!(api_request.tools.is_some());
         assert_eq!(api_request.tools.unwrap()[0].function_declarations.len(), 1);
+        assert!(api_request.tool_config.is_none());
+
+        let forced =
+            to_api_request(&request.with_tool_choice(ToolChoice::Tool("read_file".into())));
+        let json = serde_json::to_value(&forced).unwrap();
+        assert_eq!(json["toolConfig"]["functionCallingConfig"]["mode"], "ANY");
+        assert_eq!(
+            json["toolConfig"]["functionCallingConfig"]["allowedFunctionNames"][0],
+            "read_file"
+        );
+    }
+
+    #[test]
+    fn test_to_api_request_with_response_format() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {"name": {"type": ["string", "null"]}}
+        });
+        let request =
+            CompletionRequest::new(vec![Message::user("Hello")]).with_response_format(schema);
+        let api_request = to_api_request(&request);
+        let config = api_request.generation_config.expect("generation config");
+
+        assert_eq!(
+            config.response_mime_type.as_deref(),
+            Some("application/json")
+        );
+        let response_schema = config.response_schema.expect("schema");
+        assert!(response_schema.get("additionalProperties").is_none());
+        assert_eq!(response_schema["properties"]["name"]["nullable"], true);
     }
 
     #[test]
@@ -417,6 +538,7 @@ mod tests {
                     role: "model".to_string(),
                     parts: vec![Part::FunctionCall {
                         function_call: FunctionCall {
+                            id: Some("call-1".to_string()),
                             name: "read_file".to_string(),
                             args: serde_json::json!({"path": "/tmp/test.txt"}),
                         },
@@ -434,16 +556,62 @@ mod tests {
         let completion = from_api_response(response);
         assert!(completion.message.has_tool_use());
         assert_eq!(completion.stop_reason, StopReason::ToolUse);
+        assert!(matches!(
+            &completion.message.content[0],
+            ContentBlock::ToolUse { id, .. } if id == "call-1"
+        ));
+    }
+
+    #[test]
+    fn test_tool_results_carry_call_id_and_function_name() {
+        let request = CompletionRequest::new(vec![
+            Message::user("Read both"),
+            Message {
+                role: Role::Assistant,
+                content: vec![
+                    ContentBlock::ToolUse {
+                        id: "a1".to_string(),
+                        name: "read_file".to_string(),
+                        input: serde_json::json!({"path": "a"}),
+                        signature: None,
+                    },
+                    ContentBlock::ToolUse {
+                        id: "b2".to_string(),
+                        name: "glob".to_string(),
+                        input: serde_json::json!({"pattern": "*"}),
+                        signature: None,
+                    },
+                ],
+            },
+            Message {
+                role: Role::Tool,
+                content: vec![
+                    ContentBlock::tool_result("b2", "x.rs"),
+                    ContentBlock::tool_result("a1", "contents"),
+                ],
+            },
+        ]);
+
+        let json = serde_json::to_value(to_api_request(&request)).expect("serialize");
+        let calls = &json["contents"][1]["parts"];
+        assert_eq!(calls[0]["functionCall"]["id"], "a1");
+        let responses = &json["contents"][2]["parts"];
+        assert_eq!(responses[0]["functionResponse"]["id"], "b2");
+        assert_eq!(responses[0]["functionResponse"]["name"], "glob");
+        assert_eq!(responses[1]["functionResponse"]["name"], "read_file");
     }
 
     #[test]
     fn test_parse_stream_event_text() {
         let data =
             r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Hello"}]},"index":0}]}"#;
-        let event = parse_stream_event(data);
+        let events = parse_stream_event(data, &mut StreamState::new());
 
-        assert!(event.is_some());
-        if let Some(CoreStreamEvent::ContentBlockDelta { delta, .. }) = event {
+        assert!(matches!(
+            &events[0],
+            CoreStreamEvent::ContentBlockStart { index: 0, .. }
+        ));
+        if let Some(CoreStreamEvent::ContentBlockDelta { delta, .. }) = events.get(1) {
             if let CoreContentDelta::TextDelta { text } = delta {
                 assert_eq!(text, "Hello");
             } else {
@@ -453,4 +621,41 @@ mod tests {
             panic!("Expected ContentBlockDelta");
         }
     }
+
+    #[test]
+    fn test_parse_stream_event_multiple_and_partial_calls() {
+        let mut state = StreamState::new();
+        let first = r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Checking"},{"functionCall":{"id":"c1","name":"read_file","args":{"path":"a.rs"}}},{"functionCall":{"id":"c2","name":"bash","args":{"command":"ls"}}}]},"index":0}]}"#;
+        let second = r#"{"candidates":[{"content":{"role":"model","parts":[{"functionCall":{"id":"c2","name":"bash","args":{"command":" -la"}}}]},"finishReason":"STOP","index":0}]}"#;
+
+        let mut accumulator =
+            crate::core::augmented_llm::stream_accumulator::StreamAccumulator::default();
+        let mut stop = None;
+        for event in parse_stream_event(first, &mut state)
+            .into_iter()
+            .chain(parse_stream_event(second, &mut state))
+        {
+            match event {
+                CoreStreamEvent::ContentBlockStart {
+                    index,
+                    content_block,
+                } => accumulator.handle_block_start(index, content_block),
+                CoreStreamEvent::ContentBlockDelta { index, delta } => {
+                    accumulator.handle_delta(index, delta);
+                }
+                CoreStreamEvent::MessageDelta { delta } => stop = delta.stop_reason,
+                _ => {}
+            }
+        }
+
+        let blocks = accumulator.into_content_blocks();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0], ContentBlock::text("Checking"));
+        assert!(matches!(&blocks[1], ContentBlock::ToolUse { id, .. } if id == "c1"));
+        assert!(matches!(
+            &blocks[2],
+            ContentBlock::ToolUse { id, input, .. } if id == "c2" && input["command"] == "ls -la"
+        ));
+        assert_eq!(stop, Some(StopReason::ToolUse));
+    }
 }
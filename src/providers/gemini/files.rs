@@ -0,0 +1,232 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::providers::error::ProviderError;
+use crate::providers::http::{AuthStrategy, HttpClient};
+use crate::providers::types::{ApiKey, BaseUrl};
+
+use super::types::{ApiRequest, FileData, Part, UploadResponse, UploadedFile};
+
+pub const INLINE_LIMIT: usize = 4 * 1024 * 1024;
+const PROCESSING_POLLS: u32 = 30;
+const PROCESSING_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Default)]
+pub struct FileUploads {
+    uploaded: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl FileUploads {
+    pub async fn replace_large_inline_data(
+        &self,
+        request: &mut ApiRequest,
+        http: &HttpClient,
+        base_url: &BaseUrl,
+        api_key: &ApiKey,
+    ) -> Result<(), ProviderError> {
+        let parts = request
+            .contents
+            .iter_mut()
+            .flat_map(|content| content.parts.iter_mut());
+        for part in parts {
+            let Part::InlineData { inline_data } = part else {
+                continue;
+            };
+            if inline_data.data.len() <= INLINE_LIMIT {
+                continue;
+            }
+            let key = content_key(&inline_data.data);
+            let cached = self.uploaded.lock().get(&key).cloned();
+            let file_uri = if let Some(uri) = cached {
+                uri
+            } else {
+                let uri = upload(
+                    http,
+                    base_url,
+                    api_key,
+                    &inline_data.mime_type,
+                    &inline_data.data,
+                )
+                .await?;
+                self.uploaded.lock().insert(key, uri.clone());
+                uri
+            };
+            *part = Part::FileData {
+                file_data: FileData {
+                    mime_type: inline_data.mime_type.clone(),
+                    file_uri,
+                },
+            };
+        }
+        Ok(())
+    }
+}
+
+fn content_key(data: &str) -> String {
+    Sha256::digest(data.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+async fn upload(
+    http: &HttpClient,
+    base_url: &BaseUrl,
+    api_key: &ApiKey,
+    mime_type: &str,
+    data: &str,
+) -> Result<String, ProviderError> {
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|e| ProviderError::InvalidRequest(format!("Attachment is not base64: {e}")))?;
+
+    let start = http
+        .post(
+            &format!(
+                "{}/upload/v1beta/files?key={}",
+                base_url.as_str(),
+                api_key.as_str()
+            ),
+            &AuthStrategy::None,
+        )
+        .header("X-Goog-Upload-Protocol", "resumable")
+        .header("X-Goog-Upload-Command", "start")
+        .header("X-Goog-Upload-Header-Content-Length", bytes.len())
+        .header("X-Goog-Upload-Header-Content-Type", mime_type)
+        .header("content-type", "application/json")
+        .body(r#"{"file":{"display_name":"smith-attachment"}}"#)
+        .send()
+        .await
+        .map_err(|e| ProviderError::Connection(e.to_string()))?;
+    let status = start.status();
+    if !status.is_success() {
+        let body = start.text().await.unwrap_or_default();
+        return Err(ProviderError::from_status(
+            status.as_u16(),
+            &body,
+            "GEMINI_API_KEY",
+        ));
+    }
+    let upload_url = start
+        .headers()
+        .get("x-goog-upload-url")
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ProviderError::ParseError("Files API returned no upload URL".to_string()))?
+        .to_string();
+
+    let response = http
+        .post(&upload_url, &AuthStrategy::None)
+        .header("X-Goog-Upload-Offset", 0)
+        .header("X-Goog-Upload-Command", "upload, finalize")
+        .body(bytes)
+        .send()
+        .await
+        .map_err(|e| ProviderError::Connection(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::from_status(
+            status.as_u16(),
+            &body,
+            "GEMINI_API_KEY",
+        ));
+    }
+    let uploaded: UploadResponse = response
+        .json()
+        .await
+        .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+    wait_until_active(http, base_url, api_key, uploaded.file).await
+}
+
+async fn wait_until_active(
+    http: &HttpClient,
+    base_url: &BaseUrl,
+    api_key: &ApiKey,
+    mut file: UploadedFile,
+) -> Result<String, ProviderError> {
+    for _ in 0..PROCESSING_POLLS {
+        match file.state.as_deref() {
+            Some("PROCESSING") => {}
+            Some("FAILED") => {
+                return Err(ProviderError::InvalidRequest(format!(
+                    "Gemini could not process the uploaded file {}",
+                    file.name
+                )));
+            }
+            _ => return Ok(file.uri),
+        }
+        tokio::time::sleep(PROCESSING_POLL_INTERVAL).await;
+        file = http
+            .get(
+                &format!(
+                    "{}/v1beta/{}?key={}",
+                    base_url.as_str(),
+                    file.name,
+                    api_key.as_str()
+                ),
+                &AuthStrategy::None,
+            )
+            .send()
+            .await
+            .map_err(|e| ProviderError::Connection(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+    }
+    Err(ProviderError::Timeout(
+        PROCESSING_POLL_INTERVAL * PROCESSING_POLLS,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{CompletionRequest, ContentBlock, Message, Role};
+    use crate::providers::gemini::convert::to_api_request;
+    use crate::providers::http::HttpConfig;
+
+    #[tokio::test]
+    async fn test_large_inline_data_uses_uploaded_file() {
+        let large = "A".repeat(INLINE_LIMIT + 4);
+        let message = Message {
+            role: Role::User,
+            content: vec![
+                ContentBlock::text("Describe this"),
+                ContentBlock::image_base64("image/png", large.clone()),
+                ContentBlock::image_base64("image/png", "iVBORw0KGgo="),
+            ],
+        };
+        let mut request = to_api_request(&CompletionRequest::new(vec![message]));
+
+        let uploads = FileUploads::default();
+        uploads
+            .uploaded
+            .lock()
+            .insert(content_key(&large), "https://files.example/abc".to_string());
+        let http = HttpClient::with_config(HttpConfig::default()).unwrap();
+        uploads
+            .replace_large_inline_data(
+                &mut request,
+                &http,
+                &BaseUrl::new("http://127.0.0.1:9"),
+                &ApiKey::new("key"),
+            )
+            .await
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        let parts = &json["contents"][0]["parts"];
+        assert_eq!(parts[1]["fileData"]["fileUri"], "https://files.example/abc");
+        assert_eq!(parts[1]["fileData"]["mimeType"], "image/png");
+        assert_eq!(parts[2]["inlineData"]["data"], "iVBORw0KGgo=");
+    }
+}
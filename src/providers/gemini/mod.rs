@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 pub mod convert;
+pub mod files;
 pub mod types;
 
 use async_trait::async_trait;
@@ -11,7 +12,8 @@ use crate::core::llm::LLM;
 use crate::core::types::{CompletionRequest, CompletionResponse, StreamResponse};
 use crate::providers::error::ProviderError;
 use crate::providers::http::{AuthStrategy, HttpClient, HttpConfig, SseParser};
-use crate::providers::types::{ApiKey, BaseUrl, ModelId};
+use crate::providers::schema::SchemaSupport;
+use crate::providers::types::{ApiKey, BaseUrl, ExtraBody, ModelId};
 
 const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
 const DEFAULT_MODEL: &str = "gemini-2.0-flash";
@@ -22,6 +24,8 @@ pub struct GeminiProvider {
     api_key: ApiKey,
     model: ModelId,
     base_url: BaseUrl,
+    extra_body: ExtraBody,
+    files: files::FileUploads,
 }
 
 impl std::fmt::Debug for GeminiProvider {
@@ -36,10 +40,12 @@ impl std::fmt::Debug for GeminiProvider {
 impl GeminiProvider {
     pub fn new(api_key: ApiKey) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::new()?,
+            http: HttpClient::with_config(HttpConfig::default().for_provider("gemini"))?,
             api_key,
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
+            files: files::FileUploads::default(),
         })
     }
 
@@ -53,10 +59,12 @@ impl GeminiProvider {
         http_config: HttpConfig,
     ) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::with_config(http_config)?,
+            http: HttpClient::with_config(http_config.or_provider("gemini"))?,
             api_key,
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
+            files: files::FileUploads::default(),
         })
     }
 
@@ -72,6 +80,12 @@ impl GeminiProvider {
         self
     }
 
+    #[must_use]
+    pub fn with_extra_body(mut self, extra_body: ExtraBody) -> Self {
+        self.extra_body = extra_body;
+        self
+    }
+
     fn generate_content_url(&self) -> String {
         format!(
             "{}/v1beta/models/{}:generateContent?key={}",
@@ -105,12 +119,21 @@ impl LLM for GeminiProvider {
         self.model.as_str()
     }
 
+    fn schema_support(&self) -> SchemaSupport {
+        SchemaSupport::OPENAPI
+    }
+
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
-        let api_request = convert::to_api_request(&request);
+        let mut api_request = convert::to_api_request(&request);
+        self.files
+            .replace_large_inline_data(&mut api_request, &self.http, &self.base_url, &self.api_key)
+            .await?;
         let url = self.generate_content_url();
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
             .http
@@ -136,11 +159,16 @@ impl LLM for GeminiProvider {
     }
 
     async fn stream(&self, request: CompletionRequest) -> Result<StreamResponse> {
-        let api_request = convert::to_api_request(&request);
+        let mut api_request = convert::to_api_request(&request);
+        self.files
+            .replace_large_inline_data(&mut api_request, &self.http, &self.base_url, &self.api_key)
+            .await?;
         let url = self.stream_generate_content_url();
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
             .http
@@ -160,11 +188,16 @@ impl LLM for GeminiProvider {
         let byte_stream = response.bytes_stream();
         let sse_stream = SseParser::parse_stream(byte_stream);
 
-        let event_stream = sse_stream.filter_map(|result| async move {
-            match result {
-                Ok(sse_event) => convert::parse_stream_event(&sse_event.data).map(Ok),
-                Err(e) => Some(Err(e.into())),
-            }
+        let mut state = convert::StreamState::new();
+        let event_stream = sse_stream.flat_map(move |result| {
+            let events: Vec<Result<_>> = match result {
+                Ok(sse_event) => convert::parse_stream_event(&sse_event.data, &mut state)
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                Err(e) => vec![Err(e.into())],
+            };
+            futures::stream::iter(events)
         });
 
         Ok(Box::pin(event_stream))
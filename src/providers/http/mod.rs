@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
 pub mod auth;
+pub mod rate_limit;
 pub mod sse;
 
 pub use auth::AuthStrategy;
+pub use rate_limit::{RateLimit, RateLimitMiddleware, configure_rate_limits};
 pub use sse::SseParser;
 
 use reqwest::Client;
@@ -21,6 +23,7 @@ pub struct HttpConfig {
     pub retry_min_delay: Duration,
     pub retry_max_delay: Duration,
     pub user_agent: Option<String>,
+    pub provider: Option<String>,
 }
 
 impl Default for HttpConfig {
@@ -31,6 +34,7 @@ impl Default for HttpConfig {
             retry_min_delay: Duration::from_millis(500),
             retry_max_delay: Duration::from_secs(30),
             user_agent: None,
+            provider: None,
         }
     }
 }
@@ -59,6 +63,21 @@ impl HttpConfig {
         self
     }
 
+    #[must_use]
+    pub fn for_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    #[must_use]
+    pub fn or_provider(self, provider: &str) -> Self {
+        if self.provider.is_some() {
+            self
+        } else {
+            self.for_provider(provider)
+        }
+    }
+
     #[must_use]
     pub const fn without_retries(mut self) -> Self {
         self.max_retries = 0;
@@ -93,9 +112,12 @@ impl HttpClient {
             ProviderError::Configuration(format!("Failed to build HTTP client: {e}"))
         })?;
 
-        let client_with_middleware = ClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
+        let mut middleware = ClientBuilder::new(client)
+            .with(RetryTransientMiddleware::new_with_policy(retry_policy));
+        if let Some(provider) = &config.provider {
+            middleware = middleware.with(RateLimitMiddleware::new(provider.clone()));
+        }
+        let client_with_middleware = middleware.build();
 
         Ok(Self {
             inner: client_with_middleware,
@@ -156,6 +178,14 @@ mod tests {
         assert_eq!(config.user_agent, Some("smith/0.1.0".to_string()));
     }
 
+    #[test]
+    fn test_http_config_provider() {
+        let config = HttpConfig::new().or_provider("anthropic");
+        assert_eq!(config.provider.as_deref(), Some("anthropic"));
+        let config = config.or_provider("openai");
+        assert_eq!(config.provider.as_deref(), Some("anthropic"));
+    }
+
     #[test]
     fn test_http_config_without_retries() {
         let config = HttpConfig::new().without_retries();
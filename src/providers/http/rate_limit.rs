@@ -0,0 +1,181 @@
+use http::Extensions;
+use parking_lot::{Mutex, RwLock};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+const WINDOW: Duration = Duration::from_mins(1);
+
+static LIMITERS: LazyLock<RwLock<HashMap<String, Arc<RateLimiter>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimit {
+    pub requests_per_minute: Option<u32>,
+    pub max_concurrent: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    concurrency: Option<Arc<Semaphore>>,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            concurrency: limit
+                .max_concurrent
+                .map(|n| Arc::new(Semaphore::new(n.max(1)))),
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    #[must_use]
+    pub const fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    pub async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = match &self.concurrency {
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(rpm) = self.limit.requests_per_minute {
+            while let Some(wait) = self.reserve(rpm.max(1) as usize) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        permit
+    }
+
+    fn reserve(&self, rpm: usize) -> Option<Duration> {
+        let now = Instant::now();
+        let mut sent = self.sent.lock();
+        while sent
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= WINDOW)
+        {
+            sent.pop_front();
+        }
+        if sent.len() < rpm {
+            sent.push_back(now);
+            return None;
+        }
+        sent.front()
+            .map(|&oldest| WINDOW.saturating_sub(now.duration_since(oldest)))
+    }
+}
+
+pub fn configure_rate_limits(limits: impl IntoIterator<Item = (String, RateLimit)>) {
+    let limiters = limits
+        .into_iter()
+        .filter(|(_, limit)| *limit != RateLimit::default())
+        .map(|(provider, limit)| (provider, Arc::new(RateLimiter::new(limit))))
+        .collect();
+    *LIMITERS.write() = limiters;
+}
+
+#[must_use]
+pub fn rate_limiter(provider: &str) -> Option<Arc<RateLimiter>> {
+    LIMITERS.read().get(provider).cloned()
+}
+
+pub struct RateLimitMiddleware {
+    provider: String,
+}
+
+impl RateLimitMiddleware {
+    #[must_use]
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(limiter) = rate_limiter(&self.provider) else {
+            return next.run(req, extensions).await;
+        };
+        let _permit = limiter.acquire().await;
+        next.run(req, extensions).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_per_minute_waits_for_window() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_minute: Some(2),
+            max_concurrent: None,
+        });
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        limiter.acquire().await;
+        assert!(start.elapsed() >= WINDOW);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_budget() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_minute: None,
+            max_concurrent: Some(1),
+        });
+
+        let first = limiter.acquire().await;
+        assert!(first.is_some());
+        let blocked = tokio::time::timeout(Duration::from_millis(20), limiter.acquire())
+            .await
+            .is_err();
+        assert!(blocked);
+
+        drop(first);
+        assert!(limiter.acquire().await.is_some());
+    }
+
+    #[test]
+    fn test_configure_skips_empty_limits() {
+        configure_rate_limits([
+            ("test-empty".to_string(), RateLimit::default()),
+            (
+                "test-limited".to_string(),
+                RateLimit {
+                    requests_per_minute: Some(10),
+                    max_concurrent: None,
+                },
+            ),
+        ]);
+        assert!(rate_limiter("test-empty").is_none());
+        assert_eq!(
+            rate_limiter("test-limited")
+                .unwrap()
+                .limit()
+                .requests_per_minute,
+            Some(10)
+        );
+    }
+}
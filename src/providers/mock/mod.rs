@@ -1,13 +1,17 @@
 #![allow(clippy::expect_used, dead_code)]
 
+mod scenario;
+
+pub use scenario::{Scenario, ScenarioLLM, ScenarioTurn, ScriptedEvent};
+
 use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
 
 use crate::core::error::{AgentError, Result};
 use crate::core::llm::LLM;
 use crate::core::types::{
-    CompletionRequest, CompletionResponse, ContentBlock, Message, Role, StopReason, StreamResponse,
-    Usage,
+    CompletionRequest, CompletionResponse, ContentBlock, ContentDelta, Message, MessageDelta, Role,
+    StopReason, StreamEvent, StreamResponse, Usage,
 };
 
 #[derive(Debug, Clone)]
@@ -163,9 +167,54 @@ impl LLM for MockLLM {
             .lock()
             .expect("MockLLM mutex poisoned")
             .push(request);
-        let _response = self.next_response()?;
+        let response = self.next_response()?;
 
-        Ok(Box::pin(stream::empty()))
+        let mut events = vec![Ok(StreamEvent::MessageStart {
+            message: Message::new(Role::Assistant, Vec::new()),
+            usage: None,
+        })];
+        for (index, block) in response.content.into_iter().enumerate() {
+            let (start, delta) = match block {
+                ContentBlock::Text { text } => (
+                    ContentBlock::text(""),
+                    Some(ContentDelta::TextDelta { text }),
+                ),
+                ContentBlock::ToolUse {
+                    id,
+                    name,
+                    input,
+                    signature,
+                } => (
+                    ContentBlock::ToolUse {
+                        id,
+                        name,
+                        input: serde_json::Value::Object(serde_json::Map::new()),
+                        signature,
+                    },
+                    Some(ContentDelta::InputJsonDelta {
+                        partial_json: input.to_string(),
+                    }),
+                ),
+                block => (block, None),
+            };
+            events.push(Ok(StreamEvent::ContentBlockStart {
+                index,
+                content_block: start,
+            }));
+            if let Some(delta) = delta {
+                events.push(Ok(StreamEvent::ContentBlockDelta { index, delta }));
+            }
+            events.push(Ok(StreamEvent::ContentBlockStop { index }));
+        }
+        events.push(Ok(StreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: Some(response.stop_reason),
+                usage: Some(response.usage),
+            },
+        }));
+        events.push(Ok(StreamEvent::MessageStop));
+
+        Ok(Box::pin(stream::iter(events)))
     }
 }
 
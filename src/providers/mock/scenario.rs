@@ -0,0 +1,476 @@
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::augmented_llm::stream_accumulator::StreamAccumulator;
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::core::types::{
+    CompletionRequest, CompletionResponse, ContentBlock, ContentDelta, Message, MessageDelta, Role,
+    StopReason, StreamEvent, StreamResponse, Usage,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptedEvent {
+    Text {
+        text: String,
+    },
+    Thinking {
+        thinking: String,
+    },
+    ToolCall {
+        name: String,
+        #[serde(default)]
+        input: serde_json::Value,
+        #[serde(default)]
+        id: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioTurn {
+    #[serde(default)]
+    pub expect: Option<String>,
+    #[serde(default)]
+    pub events: Vec<ScriptedEvent>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub stop_reason: Option<StopReason>,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+impl ScenarioTurn {
+    #[must_use]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            events: vec![ScriptedEvent::Text { text: text.into() }],
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn tool_call(name: impl Into<String>, input: serde_json::Value) -> Self {
+        Self {
+            events: vec![ScriptedEvent::ToolCall {
+                name: name.into(),
+                input,
+                id: None,
+            }],
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn expecting(mut self, text: impl Into<String>) -> Self {
+        self.expect = Some(text.into());
+        self
+    }
+
+    fn stop_reason(&self) -> StopReason {
+        self.stop_reason.unwrap_or_else(|| {
+            if self
+                .events
+                .iter()
+                .any(|event| matches!(event, ScriptedEvent::ToolCall { .. }))
+            {
+                StopReason::ToolUse
+            } else {
+                StopReason::EndTurn
+            }
+        })
+    }
+
+    fn stream_events(&self, turn: usize) -> Vec<Result<StreamEvent>> {
+        let mut events = vec![Ok(StreamEvent::MessageStart {
+            message: Message::new(Role::Assistant, Vec::new()),
+            usage: None,
+        })];
+        let mut open: Option<(usize, &ScriptedEvent)> = None;
+        let mut next_index = 0;
+
+        for (position, event) in self.events.iter().enumerate() {
+            let (start, delta) = match event {
+                ScriptedEvent::Error { message } => {
+                    events.push(Err(AgentError::Provider(message.clone())));
+                    return events;
+                }
+                ScriptedEvent::Text { text } => (
+                    ContentBlock::text(""),
+                    ContentDelta::TextDelta { text: text.clone() },
+                ),
+                ScriptedEvent::Thinking { thinking } => (
+                    ContentBlock::Thinking {
+                        thinking: String::new(),
+                        signature: None,
+                    },
+                    ContentDelta::ThinkingDelta {
+                        thinking: thinking.clone(),
+                    },
+                ),
+                ScriptedEvent::ToolCall { name, input, id } => (
+                    ContentBlock::ToolUse {
+                        id: id
+                            .clone()
+                            .unwrap_or_else(|| format!("call_{turn}_{position}")),
+                        name: name.clone(),
+                        input: serde_json::Value::Object(serde_json::Map::new()),
+                        signature: None,
+                    },
+                    ContentDelta::InputJsonDelta {
+                        partial_json: input.to_string(),
+                    },
+                ),
+            };
+
+            let continues = matches!(
+                (open, event),
+                (
+                    Some((_, ScriptedEvent::Text { .. })),
+                    ScriptedEvent::Text { .. }
+                ) | (
+                    Some((_, ScriptedEvent::Thinking { .. })),
+                    ScriptedEvent::Thinking { .. }
+                )
+            );
+            if !continues && let Some((index, _)) = open.take() {
+                events.push(Ok(StreamEvent::ContentBlockStop { index }));
+            }
+
+            let index = if continues {
+                open.map_or(next_index, |(index, _)| index)
+            } else {
+                let index = next_index;
+                next_index += 1;
+                events.push(Ok(StreamEvent::ContentBlockStart {
+                    index,
+                    content_block: start,
+                }));
+                open = Some((index, event));
+                index
+            };
+            events.push(Ok(StreamEvent::ContentBlockDelta { index, delta }));
+        }
+
+        if let Some((index, _)) = open {
+            events.push(Ok(StreamEvent::ContentBlockStop { index }));
+        }
+        events.push(Ok(StreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: Some(self.stop_reason()),
+                usage: Some(self.usage.unwrap_or_default()),
+            },
+        }));
+        events.push(Ok(StreamEvent::MessageStop));
+        events
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub turns: Vec<ScenarioTurn>,
+}
+
+impl Scenario {
+    #[must_use]
+    pub fn new(turns: Vec<ScenarioTurn>) -> Self {
+        Self {
+            turns,
+            ..Self::default()
+        }
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| AgentError::Config(format!("Invalid scenario: {e}")))
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        config::Config::builder()
+            .add_source(config::File::from_str(content, config::FileFormat::Yaml))
+            .build()
+            .and_then(config::Config::try_deserialize)
+            .map_err(|e| AgentError::Config(format!("Invalid scenario: {e}")))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AgentError::Config(format!("Failed to read scenario {}: {e}", path.display()))
+        })?;
+        let mut scenario = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => Self::from_yaml(&content)?,
+            _ => Self::from_json(&content)?,
+        };
+        if scenario.name.is_empty()
+            && let Some(stem) = path.file_stem()
+        {
+            scenario.name = stem.to_string_lossy().into_owned();
+        }
+        Ok(scenario)
+    }
+}
+
+#[derive(Clone)]
+pub struct ScenarioLLM {
+    name: String,
+    model: String,
+    turns: Arc<Mutex<VecDeque<ScenarioTurn>>>,
+    request_history: Arc<Mutex<Vec<CompletionRequest>>>,
+}
+
+impl ScenarioLLM {
+    #[must_use]
+    pub fn new(scenario: Scenario) -> Self {
+        let name = if scenario.name.is_empty() {
+            "scenario".to_string()
+        } else {
+            scenario.name
+        };
+        Self {
+            name,
+            model: scenario
+                .model
+                .unwrap_or_else(|| "mock-scenario".to_string()),
+            turns: Arc::new(Mutex::new(scenario.turns.into())),
+            request_history: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    #[must_use]
+    pub fn remaining_turns(&self) -> usize {
+        self.turns.lock().len()
+    }
+
+    #[must_use]
+    pub fn request_history(&self) -> Vec<CompletionRequest> {
+        self.request_history.lock().clone()
+    }
+
+    async fn next_turn(&self, request: CompletionRequest) -> Result<(usize, ScenarioTurn)> {
+        let (index, last_message) = {
+            let mut history = self.request_history.lock();
+            let text = request
+                .messages
+                .last()
+                .map(message_text)
+                .unwrap_or_default();
+            history.push(request);
+            (history.len() - 1, text)
+        };
+        let turn = self.turns.lock().pop_front().ok_or_else(|| {
+            AgentError::Provider(format!(
+                "Scenario '{}' has no turn left for request {}",
+                self.name,
+                index + 1
+            ))
+        })?;
+
+        if let Some(expected) = &turn.expect
+            && !last_message.contains(expected.as_str())
+        {
+            return Err(AgentError::Provider(format!(
+                "Scenario '{}' turn {} expected the last message to contain {expected:?}, got {last_message:?}",
+                self.name,
+                index + 1
+            )));
+        }
+        if turn.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(turn.delay_ms)).await;
+        }
+        if let Some(error) = &turn.error {
+            return Err(AgentError::Provider(error.clone()));
+        }
+        Ok((index, turn))
+    }
+}
+
+fn message_text(message: &Message) -> String {
+    message
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text } => Some(text.as_str()),
+            ContentBlock::ToolResult { content, .. } => Some(content.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[async_trait]
+impl LLM for ScenarioLLM {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let (index, turn) = self.next_turn(request).await?;
+        let mut accumulator = StreamAccumulator::default();
+        for event in turn.stream_events(index) {
+            match event? {
+                StreamEvent::ContentBlockStart {
+                    index,
+                    content_block,
+                } => accumulator.handle_block_start(index, content_block),
+                StreamEvent::ContentBlockDelta { index, delta } => {
+                    accumulator.handle_delta(index, delta);
+                }
+                _ => {}
+            }
+        }
+        Ok(CompletionResponse::new(
+            Message::new(Role::Assistant, accumulator.into_content_blocks()),
+            turn.stop_reason(),
+            turn.usage.unwrap_or_default(),
+        ))
+    }
+
+    async fn stream(&self, request: CompletionRequest) -> Result<StreamResponse> {
+        let (index, turn) = self.next_turn(request).await?;
+        Ok(Box::pin(futures::stream::iter(turn.stream_events(index))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    const YAML: &str = r#"
+name: list-files
+turns:
+  - expect: list
+    events:
+      - type: text
+        text: "Let me "
+      - type: text
+        text: "look."
+      - type: tool_call
+        name: bash
+        input:
+          command: ls -la
+  - expect: Cargo.toml
+    events:
+      - type: text
+        text: Found Cargo.toml.
+"#;
+
+    #[test]
+    fn test_yaml_and_json_fixtures() {
+        let scenario = Scenario::from_yaml(YAML).unwrap();
+        assert_eq!(scenario.name, "list-files");
+        assert_eq!(scenario.turns.len(), 2);
+        assert_eq!(
+            scenario.turns[0].events[2],
+            ScriptedEvent::ToolCall {
+                name: "bash".to_string(),
+                input: serde_json::json!({"command": "ls -la"}),
+                id: None,
+            }
+        );
+
+        let json = serde_json::to_string(&scenario).unwrap();
+        assert_eq!(Scenario::from_json(&json).unwrap(), scenario);
+        assert!(
+            Scenario::from_json("{\"turns\": [{\"events\": [{\"type\": \"nope\"}]}]}").is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_merges_text_chunks_and_tool_calls() {
+        let llm = ScenarioLLM::new(Scenario::from_yaml(YAML).unwrap());
+        let request = CompletionRequest::new(vec![Message::user("list the files")]);
+        let events: Vec<StreamEvent> = llm
+            .stream(request)
+            .await
+            .unwrap()
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        let deltas = events
+            .iter()
+            .filter(|event| matches!(event, StreamEvent::ContentBlockDelta { index: 0, .. }))
+            .count();
+        assert_eq!(deltas, 2);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_block: ContentBlock::ToolUse { id, .. },
+            } if id == "call_0_2"
+        )));
+        assert_eq!(llm.remaining_turns(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_complete_checks_expectations() {
+        let llm = ScenarioLLM::new(Scenario::from_yaml(YAML).unwrap());
+        let response = llm
+            .complete(CompletionRequest::new(vec![Message::user("list files")]))
+            .await
+            .unwrap();
+        assert_eq!(response.message.first_text(), Some("Let me look."));
+        assert_eq!(response.stop_reason, StopReason::ToolUse);
+
+        let err = llm
+            .complete(CompletionRequest::new(vec![Message::user(
+                "something else",
+            )]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Cargo.toml"));
+        assert!(
+            llm.complete(CompletionRequest::new(vec![Message::user("x")]))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scripted_errors() {
+        let llm = ScenarioLLM::new(Scenario::new(vec![
+            ScenarioTurn {
+                error: Some("rate limited".to_string()),
+                ..ScenarioTurn::default()
+            },
+            ScenarioTurn {
+                events: vec![
+                    ScriptedEvent::Text {
+                        text: "partial".to_string(),
+                    },
+                    ScriptedEvent::Error {
+                        message: "connection reset".to_string(),
+                    },
+                ],
+                ..ScenarioTurn::default()
+            },
+        ]));
+        let request = CompletionRequest::new(vec![Message::user("hi")]);
+
+        assert!(llm.stream(request.clone()).await.is_err());
+        let events: Vec<_> = llm.stream(request).await.unwrap().collect().await;
+        assert!(events.last().unwrap().is_err());
+        assert_eq!(events.iter().filter(|event| event.is_ok()).count(), 3);
+    }
+}
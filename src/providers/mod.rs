@@ -1,6 +1,8 @@
+pub mod cache;
 pub mod error;
 pub mod factory;
 pub mod http;
+pub mod schema;
 pub mod types;
 
 pub mod anthropic;
@@ -9,6 +11,7 @@ pub mod mock;
 pub mod openai;
 pub mod openai_compat;
 
+pub use cache::{CachedLLM, ResponseCache};
 pub use factory::create_provider;
 pub use types::ApiKey;
 
@@ -1,12 +1,14 @@
 use crate::core::types::{
-    CompletionRequest, CompletionResponse, ContentBlock, ContentDelta as CoreContentDelta, Message,
-    MessageDelta, Role, StopReason, StreamEvent as CoreStreamEvent, ToolDefinition, Usage,
+    CompletionRequest, CompletionResponse, ContentBlock, ContentDelta as CoreContentDelta,
+    ImageSource, Message, MessageDelta, Role, StopReason, StreamEvent as CoreStreamEvent,
+    ToolChoice, ToolDefinition, Usage,
 };
 use crate::providers::types::ModelId;
 
 use super::types::{
-    ApiRequest, ApiResponse, ApiTool, FunctionCall, FunctionCallOutput, InputContent,
-    InputFunctionCall, InputItem, InputMessage, OutputContent, OutputItem, OutputMessage,
+    ApiReasoning, ApiRequest, ApiResponse, ApiTextConfig, ApiTextFormat, ApiTool, ApiToolChoice,
+    FunctionCall, FunctionCallOutput, InputContent, InputFunctionCall, InputItem, InputMessage,
+    OutputContent, OutputItem, OutputMessage,
 };
 
 pub fn to_api_request(model: &ModelId, request: &CompletionRequest) -> ApiRequest {
@@ -22,18 +24,49 @@ pub fn to_api_request(model: &ModelId, request: &CompletionRequest) -> ApiReques
         Some(request.tools.iter().map(to_api_tool).collect())
     };
 
+    let tool_choice = tools.as_ref().and_then(|_| match &request.tool_choice {
+        ToolChoice::Auto => None,
+        ToolChoice::None => Some(ApiToolChoice::Mode("none")),
+        ToolChoice::Required => Some(ApiToolChoice::Mode("required")),
+        ToolChoice::Tool(name) => Some(ApiToolChoice::Function {
+            tool_type: "function",
+            name: name.clone(),
+        }),
+    });
+
     ApiRequest {
         model: model.as_str().to_string(),
         input,
         instructions: request.system_prompt.clone(),
         max_output_tokens: Some(request.max_tokens),
         temperature: Some(request.temperature),
+        top_p: request.top_p,
         tools,
+        tool_choice,
         stream: None,
         previous_response_id: None,
+        text: to_api_text_config(request),
+        reasoning: request
+            .reasoning_effort
+            .map(|effort| ApiReasoning { effort }),
     }
 }
 
+fn to_api_text_config(request: &CompletionRequest) -> Option<ApiTextConfig> {
+    let format = request
+        .response_format
+        .as_ref()
+        .map(|format| ApiTextFormat::JsonSchema {
+            name: format.name.clone(),
+            schema: format.schema.clone(),
+            strict: format.strict,
+        });
+    (format.is_some() || request.verbosity.is_some()).then_some(ApiTextConfig {
+        format,
+        verbosity: request.verbosity,
+    })
+}
+
 fn to_input_items(message: &Message) -> Vec<InputItem> {
     match message.role {
         Role::User => {
@@ -98,32 +131,58 @@ fn to_input_items(message: &Message) -> Vec<InputItem> {
 
             items
         }
-        Role::Tool => message
-            .content
-            .iter()
-            .filter_map(|b| {
-                if let ContentBlock::ToolResult {
-                    tool_use_id,
-                    content,
-                    ..
-                } = b
-                {
-                    Some(InputItem::FunctionCallOutput(FunctionCallOutput {
-                        call_id: tool_use_id.clone(),
-                        output: if content.is_empty() {
-                            "[No output]".to_string()
-                        } else {
-                            content.clone()
-                        },
-                    }))
-                } else {
-                    None
-                }
-            })
-            .collect(),
+        Role::Tool => {
+            let mut items: Vec<InputItem> = message
+                .content
+                .iter()
+                .filter_map(|b| {
+                    if let ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        ..
+                    } = b
+                    {
+                        Some(InputItem::FunctionCallOutput(FunctionCallOutput {
+                            call_id: tool_use_id.clone(),
+                            output: if content.is_empty() {
+                                "[No output]".to_string()
+                            } else {
+                                content.clone()
+                            },
+                        }))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            let files: Vec<InputContent> =
+                message.content.iter().filter_map(to_input_file).collect();
+            if !files.is_empty() {
+                items.push(InputItem::Message(InputMessage {
+                    role: "user".to_string(),
+                    content: files,
+                }));
+            }
+            items
+        }
     }
 }
 
+fn to_input_file(block: &ContentBlock) -> Option<InputContent> {
+    let ContentBlock::Document {
+        source: ImageSource::Base64 { media_type, data },
+        title,
+    } = block
+    else {
+        return None;
+    };
+    Some(InputContent::InputFile {
+        filename: title.clone().unwrap_or_else(|| "document".to_string()),
+        file_data: format!("data:{media_type};base64,{data}"),
+    })
+}
+
 fn to_api_tool(tool: &ToolDefinition) -> ApiTool {
     ApiTool {
         tool_type: "function".to_string(),
@@ -296,6 +355,7 @@ pub fn parse_stream_event(event_type: Option<&str>, data: &str) -> Option<CoreSt
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::types::{ReasoningEffort, Verbosity};
 
     #[test]
     fn test_to_input_items_user() {
@@ -310,6 +370,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_api_request_with_response_format() {
+        let model = ModelId::new("gpt-4o");
+        let schema = serde_json::json!({"type": "object"});
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_response_format(schema.clone());
+
+        let api_request = to_api_request(&model, &request);
+        let json = serde_json::to_value(&api_request).expect("serialize");
+
+        assert_eq!(json["text"]["format"]["type"], "json_schema");
+        assert_eq!(json["text"]["format"]["name"], "structured_output");
+        assert_eq!(json["text"]["format"]["schema"], schema);
+        assert!(json.get("reasoning").is_none());
+    }
+
+    #[test]
+    fn test_to_api_request_with_reasoning_and_verbosity() {
+        let model = ModelId::new("gpt-5.2");
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_reasoning_effort(Some(ReasoningEffort::High))
+            .with_verbosity(Some(Verbosity::Low));
+
+        let api_request = to_api_request(&model, &request);
+        let json = serde_json::to_value(&api_request).expect("serialize");
+
+        assert_eq!(json["reasoning"]["effort"], "high");
+        assert_eq!(json["text"]["verbosity"], "low");
+        assert!(json["text"].get("format").is_none());
+    }
+
     #[test]
     fn test_to_api_tool() {
         let tool = ToolDefinition {
@@ -11,7 +11,7 @@ use crate::core::llm::LLM;
 use crate::core::types::{CompletionRequest, CompletionResponse, StreamResponse};
 use crate::providers::error::ProviderError;
 use crate::providers::http::{AuthStrategy, HttpClient, HttpConfig, SseParser};
-use crate::providers::types::{ApiKey, BaseUrl, ModelId};
+use crate::providers::types::{ApiKey, BaseUrl, ExtraBody, ModelId};
 
 const DEFAULT_BASE_URL: &str = "https://api.openai.com";
 const DEFAULT_MODEL: &str = "gpt-4o";
@@ -22,6 +22,7 @@ pub struct OpenAIProvider {
     auth: AuthStrategy,
     model: ModelId,
     base_url: BaseUrl,
+    extra_body: ExtraBody,
 }
 
 impl std::fmt::Debug for OpenAIProvider {
@@ -36,10 +37,11 @@ impl std::fmt::Debug for OpenAIProvider {
 impl OpenAIProvider {
     pub fn new(api_key: ApiKey) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::new()?,
+            http: HttpClient::with_config(HttpConfig::default().for_provider("openai"))?,
             auth: AuthStrategy::bearer(api_key),
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
         })
     }
 
@@ -53,10 +55,11 @@ impl OpenAIProvider {
         http_config: HttpConfig,
     ) -> std::result::Result<Self, ProviderError> {
         Ok(Self {
-            http: HttpClient::with_config(http_config)?,
+            http: HttpClient::with_config(http_config.or_provider("openai"))?,
             auth: AuthStrategy::bearer(api_key),
             model: ModelId::new(DEFAULT_MODEL),
             base_url: BaseUrl::new(DEFAULT_BASE_URL),
+            extra_body: ExtraBody::default(),
         })
     }
 
@@ -72,6 +75,12 @@ impl OpenAIProvider {
         self
     }
 
+    #[must_use]
+    pub fn with_extra_body(mut self, extra_body: ExtraBody) -> Self {
+        self.extra_body = extra_body;
+        self
+    }
+
     fn parse_error(status: reqwest::StatusCode, body: &str) -> ProviderError {
         ProviderError::from_status(status.as_u16(), body, "OPENAI_API_KEY")
     }
@@ -91,8 +100,10 @@ impl LLM for OpenAIProvider {
         let api_request = convert::to_api_request(&self.model, &request);
         let url = self.base_url.join("/v1/responses");
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
             .http
@@ -123,8 +134,10 @@ impl LLM for OpenAIProvider {
 
         let url = self.base_url.join("/v1/responses");
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let response = self
             .http
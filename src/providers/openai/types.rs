@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::types::{ReasoningEffort, Verbosity};
+
 #[derive(Debug, Serialize)]
 pub struct ApiRequest {
     pub model: String,
@@ -13,11 +15,54 @@ pub struct ApiRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ApiTool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ApiToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub previous_response_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<ApiTextConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ApiReasoning>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ApiToolChoice {
+    Mode(&'static str),
+    Function {
+        #[serde(rename = "type")]
+        tool_type: &'static str,
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiReasoning {
+    pub effort: ReasoningEffort,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiTextConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ApiTextFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiTextFormat {
+    Text,
+    JsonSchema {
+        name: String,
+        schema: serde_json::Value,
+        strict: bool,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -51,6 +96,8 @@ pub enum InputContent {
     InputText { text: String },
     #[serde(rename = "input_image")]
     InputImage { image_url: String },
+    #[serde(rename = "input_file")]
+    InputFile { filename: String, file_data: String },
     #[serde(rename = "output_text")]
     OutputText { text: String },
 }
@@ -1,4 +1,6 @@
-use crate::providers::types::{ApiKey, BaseUrl, ModelId};
+use super::openrouter::openrouter_config;
+use crate::providers::schema::SchemaSupport;
+use crate::providers::types::{ApiKey, BaseUrl, ExtraBody, ModelId};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Default)]
@@ -8,6 +10,7 @@ pub struct ProviderCapabilities {
     pub streaming: bool,
     pub parallel_tool_calls: bool,
     pub json_mode: bool,
+    pub schema: SchemaSupport,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -47,6 +50,7 @@ pub struct OpenAICompatConfig {
     pub provider_name: String,
     pub api_key_env_var: String,
     pub extra_headers: ExtraHeaders,
+    pub extra_body: ExtraBody,
     pub capabilities: ProviderCapabilities,
     pub model_aliases: HashMap<String, String>,
 }
@@ -60,6 +64,7 @@ impl OpenAICompatConfig {
             provider_name: provider_name.into(),
             api_key_env_var: String::new(),
             extra_headers: ExtraHeaders::new(),
+            extra_body: ExtraBody::default(),
             capabilities: ProviderCapabilities::default(),
             model_aliases: HashMap::new(),
         }
@@ -116,15 +121,14 @@ impl OpenAICompatConfig {
                 streaming: true,
                 parallel_tool_calls: true,
                 json_mode: true,
+                schema: SchemaSupport::FULL,
             });
 
-        config.extra_headers.insert(
-            "HTTP-Referer".to_string(),
-            "https://github.com/anthropics/smith".to_string(),
-        );
-        config
-            .extra_headers
-            .insert("X-Title".to_string(), "Smith CLI".to_string());
+        let settings = openrouter_config();
+        for (name, value) in settings.headers() {
+            config.extra_headers.insert(name.to_string(), value);
+        }
+        config.extra_body = settings.extra_body();
 
         config
     }
@@ -140,6 +144,7 @@ impl OpenAICompatConfig {
                 streaming: true,
                 parallel_tool_calls: true,
                 json_mode: true,
+                schema: SchemaSupport::FULL,
             })
     }
 
@@ -151,6 +156,7 @@ impl OpenAICompatConfig {
             streaming: true,
             parallel_tool_calls: false,
             json_mode: true,
+            schema: SchemaSupport::BASIC,
         })
     }
 
@@ -165,6 +171,7 @@ impl OpenAICompatConfig {
                 streaming: true,
                 parallel_tool_calls: true,
                 json_mode: false,
+                schema: SchemaSupport::BASIC,
             })
     }
 
@@ -175,6 +182,7 @@ impl OpenAICompatConfig {
             streaming: true,
             parallel_tool_calls: true,
             json_mode: false,
+            schema: SchemaSupport::BASIC,
         })
     }
 
@@ -199,6 +207,7 @@ impl OpenAICompatConfig {
             streaming: true,
             parallel_tool_calls: true,
             json_mode: true,
+            schema: SchemaSupport::FULL,
         };
 
         config
@@ -215,6 +224,7 @@ impl OpenAICompatConfig {
                 streaming: true,
                 parallel_tool_calls: true,
                 json_mode: true,
+                schema: SchemaSupport::FULL,
             })
     }
 }
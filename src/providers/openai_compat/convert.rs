@@ -3,14 +3,15 @@ use std::collections::HashMap;
 use crate::core::types::{
     CompletionRequest, CompletionResponse, ContentBlock, ContentDelta, ImageSource, Message,
     MessageDelta as CoreMessageDelta, Role, StopReason, StreamEvent as CoreStreamEvent,
-    ToolDefinition, Usage,
+    ToolChoice as CoreToolChoice, ToolDefinition, Usage,
 };
 use crate::providers::types::ModelId;
 
 use super::config::OpenAICompatConfig;
 use super::types::{
     ChatCompletionRequest, ChatCompletionResponse, ChatMessage, ContentPart, FunctionCall,
-    FunctionDefinition, ImageUrl, MessageContent, Tool, ToolCall,
+    FunctionDefinition, ImageUrl, JsonSchemaFormat, MessageContent, ResponseFormat, Tool, ToolCall,
+    ToolChoice, ToolChoiceFunction,
 };
 
 pub fn to_api_request(
@@ -39,6 +40,10 @@ pub fn to_api_request(
         None
     };
 
+    let tool_choice = tools
+        .as_ref()
+        .and_then(|_| to_tool_choice(&request.tool_choice));
+
     let stop = if request.stop_sequences.is_empty() {
         None
     } else {
@@ -53,15 +58,45 @@ pub fn to_api_request(
         stream: None,
         stream_options: None,
         tools,
-        tool_choice: None,
+        tool_choice,
         stop,
-        top_p: None,
+        top_p: request.top_p,
         frequency_penalty: None,
         presence_penalty: None,
         user: None,
+        response_format: to_response_format(config, request),
+    }
+}
+
+fn to_tool_choice(choice: &CoreToolChoice) -> Option<ToolChoice> {
+    match choice {
+        CoreToolChoice::Auto => None,
+        CoreToolChoice::None => Some(ToolChoice::String("none".to_string())),
+        CoreToolChoice::Required => Some(ToolChoice::String("required".to_string())),
+        CoreToolChoice::Tool(name) => Some(ToolChoice::Specific {
+            tool_type: "function".to_string(),
+            function: ToolChoiceFunction { name: name.clone() },
+        }),
     }
 }
 
+fn to_response_format(
+    config: &OpenAICompatConfig,
+    request: &CompletionRequest,
+) -> Option<ResponseFormat> {
+    let format = request.response_format.as_ref()?;
+    if !config.capabilities.json_mode {
+        return None;
+    }
+    Some(ResponseFormat::JsonSchema {
+        json_schema: JsonSchemaFormat {
+            name: format.name.clone(),
+            schema: format.schema.clone(),
+            strict: format.strict,
+        },
+    })
+}
+
 fn to_chat_messages(message: &Message) -> Vec<ChatMessage> {
     match message.role {
         Role::System => {
@@ -272,7 +307,7 @@ pub fn from_api_response(response: ChatCompletionResponse) -> CompletionResponse
     };
 
     let usage = response.usage.map_or_else(Usage::default, |u| {
-        Usage::new(u.prompt_tokens, u.completion_tokens)
+        Usage::new(u.prompt_tokens, u.completion_tokens).with_cost(u.cost)
     });
 
     CompletionResponse::new(message, stop_reason, usage)
@@ -283,6 +318,7 @@ pub struct StreamState {
     tool_calls: HashMap<usize, PartialToolCall>,
     message_started: bool,
     current_index: usize,
+    usage: Option<Usage>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -303,6 +339,17 @@ impl StreamState {
     fn get_or_create_tool_call(&mut self, index: usize) -> &mut PartialToolCall {
         self.tool_calls.entry(index).or_default()
     }
+
+    fn take_usage_delta(&mut self) -> Option<CoreStreamEvent> {
+        self.usage
+            .take()
+            .map(|usage| CoreStreamEvent::MessageDelta {
+                delta: CoreMessageDelta {
+                    stop_reason: None,
+                    usage: Some(usage),
+                },
+            })
+    }
 }
 
 pub fn parse_stream_event(data: &str, state: &mut StreamState) -> Option<CoreStreamEvent> {
@@ -317,7 +364,13 @@ pub fn parse_stream_event(data: &str, state: &mut StreamState) -> Option<CoreStr
         }
     };
 
-    let choice = chunk.choices.first()?;
+    if let Some(u) = &chunk.usage {
+        state.usage = Some(Usage::new(u.prompt_tokens, u.completion_tokens).with_cost(u.cost));
+    }
+
+    let Some(choice) = chunk.choices.first() else {
+        return state.take_usage_delta();
+    };
 
     if !state.message_started {
         state.message_started = true;
@@ -330,15 +383,6 @@ pub fn parse_stream_event(data: &str, state: &mut StreamState) -> Option<CoreStr
         });
     }
 
-    if let Some(u) = &chunk.usage {
-        return Some(CoreStreamEvent::MessageDelta {
-            delta: CoreMessageDelta {
-                stop_reason: None,
-                usage: Some(Usage::new(u.prompt_tokens, u.completion_tokens)),
-            },
-        });
-    }
-
     if let Some(content) = &choice.delta.content {
         return Some(CoreStreamEvent::ContentBlockDelta {
             index: choice.index as usize,
@@ -414,25 +458,22 @@ pub fn parse_stream_event(data: &str, state: &mut StreamState) -> Option<CoreStr
             _ => StopReason::EndTurn,
         };
 
-        let usage = chunk
-            .usage
-            .map(|u| Usage::new(u.prompt_tokens, u.completion_tokens));
-
         return Some(CoreStreamEvent::MessageDelta {
             delta: CoreMessageDelta {
                 stop_reason: Some(stop_reason),
-                usage,
+                usage: state.usage.take(),
             },
         });
     }
 
-    None
+    state.take_usage_delta()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::providers::openai_compat::config::ProviderCapabilities;
+    use crate::providers::schema::SchemaSupport;
     use crate::providers::types::ApiKey;
 
     fn test_config() -> OpenAICompatConfig {
@@ -444,6 +485,7 @@ mod tests {
                 streaming: true,
                 parallel_tool_calls: true,
                 json_mode: true,
+                schema: SchemaSupport::FULL,
             })
     }
 
@@ -530,7 +572,8 @@ mod tests {
         let request = CompletionRequest::new(vec![Message::user("Hello")])
             .with_system_prompt("Be helpful")
             .with_max_tokens(1000)
-            .with_temperature(0.7);
+            .with_temperature(0.7)
+            .with_top_p(Some(0.9));
 
         let api_request = to_api_request(&config, &request);
 
@@ -539,6 +582,7 @@ mod tests {
         assert_eq!(api_request.messages[1].role, "user");
         assert_eq!(api_request.max_tokens, Some(1000));
         assert_eq!(api_request.temperature, Some(0.7));
+        assert_eq!(api_request.top_p, Some(0.9));
     }
 
     #[test]
@@ -566,6 +610,27 @@ mod tests {
         assert_eq!(tools[0].tool_type, "function");
     }
 
+    #[test]
+    fn test_to_api_request_with_response_format() {
+        let config = test_config();
+        let request = CompletionRequest::new(vec![Message::user("Hello")])
+            .with_response_format(serde_json::json!({"type": "object"}));
+
+        let api_request = to_api_request(&config, &request);
+
+        assert!(matches!(
+            api_request.response_format,
+            Some(ResponseFormat::JsonSchema { ref json_schema }) if json_schema.name == "structured_output"
+        ));
+
+        let no_json_mode = test_config().with_capabilities(ProviderCapabilities::default());
+        assert!(
+            to_api_request(&no_json_mode, &request)
+                .response_format
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_from_api_response_text_only() {
         let response = ChatCompletionResponse {
@@ -588,6 +653,7 @@ mod tests {
                 prompt_tokens: 10,
                 completion_tokens: 5,
                 total_tokens: 15,
+                cost: None,
             }),
             system_fingerprint: None,
         };
@@ -630,6 +696,7 @@ mod tests {
                 prompt_tokens: 20,
                 completion_tokens: 10,
                 total_tokens: 30,
+                cost: None,
             }),
             system_fingerprint: None,
         };
@@ -678,7 +745,7 @@ mod tests {
         let event = parse_stream_event(json, &mut state);
 
         if let Some(CoreStreamEvent::MessageDelta { delta }) = event {
-            assert_eq!(delta.stop_reason, None);
+            assert_eq!(delta.stop_reason, Some(StopReason::EndTurn));
             assert!(delta.usage.is_some());
             assert_eq!(delta.usage.unwrap().input_tokens, 10);
         } else {
@@ -721,4 +788,34 @@ mod tests {
             panic!("Expected ContentBlockDelta with InputJsonDelta");
         }
     }
+
+    #[test]
+    fn test_parse_stream_event_trailing_usage_chunk() {
+        let mut state = StreamState::new();
+        state.message_started = true;
+
+        let finish = r#"{"id":"test","object":"chat.completion.chunk","created":123,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#;
+        let Some(CoreStreamEvent::MessageDelta { delta }) = parse_stream_event(finish, &mut state)
+        else {
+            panic!("Expected MessageDelta");
+        };
+        assert_eq!(delta.stop_reason, Some(StopReason::ToolUse));
+        assert!(delta.usage.is_none());
+
+        let usage = r#"{"id":"test","object":"chat.completion.chunk","created":123,"model":"test","choices":[],"usage":{"prompt_tokens":42,"completion_tokens":7,"total_tokens":49,"cost":0.0021}}"#;
+        let Some(CoreStreamEvent::MessageDelta { delta }) = parse_stream_event(usage, &mut state)
+        else {
+            panic!("Expected usage MessageDelta");
+        };
+        assert_eq!(delta.stop_reason, None);
+        let usage = delta.usage.unwrap();
+        assert_eq!(usage.input_tokens, 42);
+        assert_eq!(usage.output_tokens, 7);
+        assert_eq!(usage.cost, Some(0.0021));
+
+        assert!(matches!(
+            parse_stream_event("[DONE]", &mut state),
+            Some(CoreStreamEvent::MessageStop)
+        ));
+    }
 }
@@ -2,6 +2,7 @@
 
 pub mod config;
 pub mod convert;
+pub mod openrouter;
 pub mod types;
 
 use async_trait::async_trait;
@@ -13,9 +14,11 @@ use crate::core::llm::LLM;
 use crate::core::types::{CompletionRequest, CompletionResponse, StreamResponse};
 use crate::providers::error::ProviderError;
 use crate::providers::http::{HttpClient, HttpConfig, SseParser};
+use crate::providers::schema::SchemaSupport;
 use crate::providers::types::{ApiKey, BaseUrl, ModelId};
 
 pub use config::{OpenAICompatAuth, OpenAICompatConfig, ProviderCapabilities};
+pub use openrouter::{OpenRouterConfig, ProviderRouting, configure_openrouter};
 
 #[derive(Clone)]
 pub struct OpenAICompatProvider {
@@ -38,7 +41,9 @@ impl OpenAICompatProvider {
     pub fn new(config: OpenAICompatConfig) -> std::result::Result<Self, ProviderError> {
         let model = config.default_model.clone();
         Ok(Self {
-            http: HttpClient::new()?,
+            http: HttpClient::with_config(
+                HttpConfig::default().for_provider(config.provider_name.clone()),
+            )?,
             config,
             model,
         })
@@ -50,7 +55,7 @@ impl OpenAICompatProvider {
     ) -> std::result::Result<Self, ProviderError> {
         let model = config.default_model.clone();
         Ok(Self {
-            http: HttpClient::with_config(http_config)?,
+            http: HttpClient::with_config(http_config.or_provider(&config.provider_name))?,
             config,
             model,
         })
@@ -213,14 +218,21 @@ impl LLM for OpenAICompatProvider {
         self.model.as_str()
     }
 
+    fn schema_support(&self) -> SchemaSupport {
+        self.config.capabilities.schema
+    }
+
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
         let mut api_request = convert::to_api_request(&self.config, &request);
         api_request.model = self.config.resolve_model(self.model.as_str());
 
         let url = self.endpoint();
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .config
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let builder = self.http.inner().post(&url);
         let builder = self.apply_auth(builder);
@@ -258,8 +270,11 @@ impl LLM for OpenAICompatProvider {
 
         let url = self.endpoint();
 
-        let body =
-            serde_json::to_string(&api_request).map_err(crate::core::error::AgentError::Json)?;
+        let body = self
+            .config
+            .extra_body
+            .encode(&api_request)
+            .map_err(crate::core::error::AgentError::Json)?;
 
         let builder = self.http.inner().post(&url);
         let builder = self.apply_auth(builder);
@@ -420,6 +435,7 @@ mod tests {
                 streaming: true,
                 parallel_tool_calls: false,
                 json_mode: false,
+                schema: SchemaSupport::FULL,
             });
 
         let provider = OpenAICompatProvider::new(config).expect("create provider");
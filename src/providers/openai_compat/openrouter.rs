@@ -0,0 +1,113 @@
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::LazyLock;
+
+use crate::providers::types::ExtraBody;
+
+static SETTINGS: LazyLock<RwLock<OpenRouterConfig>> =
+    LazyLock::new(|| RwLock::new(OpenRouterConfig::default()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterConfig {
+    pub referer: String,
+    pub title: String,
+    pub include_usage: bool,
+    pub provider: Option<ProviderRouting>,
+}
+
+impl Default for OpenRouterConfig {
+    fn default() -> Self {
+        Self {
+            referer: "https://github.com/anthropics/smith".to_string(),
+            title: "Smith CLI".to_string(),
+            include_usage: true,
+            provider: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProviderRouting {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fallbacks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_parameters: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_collection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
+
+impl OpenRouterConfig {
+    #[must_use]
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        [("HTTP-Referer", &self.referer), ("X-Title", &self.title)]
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (name, value.clone()))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn extra_body(&self) -> ExtraBody {
+        let mut fields = Map::new();
+        if self.include_usage {
+            fields.insert("usage".to_string(), serde_json::json!({ "include": true }));
+        }
+        if let Some(routing) = &self.provider
+            && let Ok(value @ Value::Object(_)) = serde_json::to_value(routing)
+        {
+            fields.insert("provider".to_string(), value);
+        }
+        ExtraBody::new(fields)
+    }
+}
+
+pub fn configure_openrouter(config: OpenRouterConfig) {
+    *SETTINGS.write() = config;
+}
+
+#[must_use]
+pub fn openrouter_config() -> OpenRouterConfig {
+    SETTINGS.read().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_and_usage_body() {
+        let config: OpenRouterConfig = toml::from_str(
+            r#"
+            title = ""
+            [provider]
+            order = ["anthropic", "amazon-bedrock"]
+            allow_fallbacks = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.headers(),
+            vec![(
+                "HTTP-Referer",
+                "https://github.com/anthropics/smith".to_string()
+            )]
+        );
+        let body = config.extra_body().encode(&serde_json::json!({})).unwrap();
+        assert_eq!(
+            body,
+            r#"{"provider":{"allow_fallbacks":false,"order":["anthropic","amazon-bedrock"]},"usage":{"include":true}}"#
+        );
+    }
+}
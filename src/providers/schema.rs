@@ -0,0 +1,348 @@
+use serde_json::{Map, Value, json};
+
+use crate::core::types::ToolDefinition;
+
+const MAX_REF_DEPTH: usize = 8;
+
+const OPENAPI_KEYWORDS: &[&str] = &[
+    "type",
+    "nullable",
+    "required",
+    "format",
+    "description",
+    "properties",
+    "items",
+    "enum",
+];
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaSupport {
+    pub combinators: bool,
+    pub const_keyword: bool,
+    pub formats: bool,
+    pub refs: bool,
+    pub type_arrays: bool,
+    pub additional_properties: bool,
+    pub allowed_keywords: Option<&'static [&'static str]>,
+}
+
+impl SchemaSupport {
+    pub const FULL: Self = Self {
+        combinators: true,
+        const_keyword: true,
+        formats: true,
+        refs: true,
+        type_arrays: true,
+        additional_properties: true,
+        allowed_keywords: None,
+    };
+
+    pub const BASIC: Self = Self {
+        combinators: false,
+        const_keyword: false,
+        formats: false,
+        refs: false,
+        type_arrays: true,
+        additional_properties: true,
+        allowed_keywords: None,
+    };
+
+    pub const OPENAPI: Self = Self {
+        combinators: false,
+        const_keyword: false,
+        formats: true,
+        refs: false,
+        type_arrays: false,
+        additional_properties: false,
+        allowed_keywords: Some(OPENAPI_KEYWORDS),
+    };
+}
+
+impl Default for SchemaSupport {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+#[must_use]
+pub fn sanitize(schema: &Value, support: &SchemaSupport) -> Value {
+    if *support == SchemaSupport::FULL {
+        return schema.clone();
+    }
+
+    let mut defs = Map::new();
+    if !support.refs {
+        for key in ["$defs", "definitions"] {
+            if let Some(Value::Object(map)) = schema.get(key) {
+                defs.extend(map.clone());
+            }
+        }
+    }
+
+    let mut sanitized = Sanitizer { support, defs }.node(schema, 0);
+    if !support.refs
+        && let Value::Object(map) = &mut sanitized
+    {
+        map.remove("$defs");
+        map.remove("definitions");
+    }
+    sanitized
+}
+
+#[must_use]
+pub fn sanitize_tools(tools: Vec<ToolDefinition>, support: &SchemaSupport) -> Vec<ToolDefinition> {
+    if *support == SchemaSupport::FULL {
+        return tools;
+    }
+    tools
+        .into_iter()
+        .map(|mut tool| {
+            tool.input_schema = sanitize(&tool.input_schema, support);
+            tool
+        })
+        .collect()
+}
+
+struct Sanitizer<'a> {
+    support: &'a SchemaSupport,
+    defs: Map<String, Value>,
+}
+
+impl Sanitizer<'_> {
+    fn node(&self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(self.object(map.clone(), depth)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.node(v, depth)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+
+    fn object(&self, mut map: Map<String, Value>, depth: usize) -> Map<String, Value> {
+        let mut depth = depth;
+        if !self.support.refs
+            && let Some(Value::String(reference)) = map.remove("$ref")
+            && depth < MAX_REF_DEPTH
+            && let Some(Value::Object(target)) = reference
+                .rsplit('/')
+                .next()
+                .and_then(|name| self.defs.get(name))
+        {
+            let mut resolved = target.clone();
+            resolved.extend(map);
+            map = resolved;
+            depth += 1;
+        }
+
+        if !self.support.const_keyword
+            && let Some(value) = map.remove("const")
+        {
+            if !map.contains_key("type")
+                && let Some(kind) = json_type(&value)
+            {
+                map.insert("type".to_string(), json!(kind));
+            }
+            map.entry("enum").or_insert_with(|| json!([value]));
+        }
+
+        if !self.support.combinators {
+            for key in ["oneOf", "anyOf"] {
+                if let Some(Value::Array(variants)) = map.remove(key) {
+                    self.downgrade_union(&mut map, &variants, depth);
+                }
+            }
+            if let Some(Value::Array(parts)) = map.remove("allOf") {
+                for part in parts {
+                    if let Value::Object(part) = self.node(&part, depth) {
+                        merge_missing(&mut map, part);
+                    }
+                }
+            }
+        }
+
+        if !self.support.type_arrays
+            && let Some(Value::Array(types)) = map.get("type").cloned()
+        {
+            let nullable = types.iter().any(|t| t == "null");
+            match types.into_iter().find(|t| t != "null") {
+                Some(kind) => map.insert("type".to_string(), kind),
+                None => map.remove("type"),
+            };
+            if nullable {
+                map.insert("nullable".to_string(), json!(true));
+            }
+        }
+
+        if !self.support.formats {
+            map.remove("format");
+        }
+        if !self.support.additional_properties {
+            map.remove("additionalProperties");
+        }
+
+        for (key, value) in &mut map {
+            match key.as_str() {
+                "properties" | "$defs" | "definitions" | "patternProperties" => {
+                    if let Value::Object(properties) = value {
+                        for schema in properties.values_mut() {
+                            *schema = self.node(schema, depth);
+                        }
+                    }
+                }
+                "items" | "additionalProperties" | "not" | "oneOf" | "anyOf" | "allOf" => {
+                    *value = self.node(value, depth);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(allowed) = self.support.allowed_keywords {
+            map.retain(|key, _| allowed.contains(&key.as_str()));
+        }
+
+        map
+    }
+
+    fn downgrade_union(&self, map: &mut Map<String, Value>, variants: &[Value], depth: usize) {
+        let variants: Vec<Map<String, Value>> = variants
+            .iter()
+            .filter_map(|v| match self.node(v, depth) {
+                Value::Object(variant) => Some(variant),
+                _ => None,
+            })
+            .collect();
+        let nullable = variants
+            .iter()
+            .any(|v| v.get("type") == Some(&json!("null")));
+        let concrete: Vec<Map<String, Value>> = variants
+            .into_iter()
+            .filter(|v| v.get("type") != Some(&json!("null")))
+            .collect();
+
+        let enums: Option<Vec<Value>> = concrete
+            .iter()
+            .map(|v| v.get("enum").and_then(Value::as_array).cloned())
+            .collect::<Option<Vec<_>>>()
+            .map(|lists| lists.into_iter().flatten().collect());
+
+        match enums {
+            Some(values) if !values.is_empty() => {
+                if let Some(kind) = concrete.first().and_then(|v| v.get("type")) {
+                    map.entry("type").or_insert_with(|| kind.clone());
+                }
+                map.insert("enum".to_string(), Value::Array(values));
+            }
+            _ => {
+                if let Some(first) = concrete.into_iter().next() {
+                    merge_missing(map, first);
+                }
+            }
+        }
+
+        if nullable {
+            mark_nullable(map, self.support);
+        }
+    }
+}
+
+fn merge_missing(map: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (key, value) in other {
+        match (map.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(incoming)) if key == "properties" => {
+                existing.extend(incoming);
+            }
+            (Some(Value::Array(existing)), Value::Array(incoming)) if key == "required" => {
+                existing.extend(incoming);
+            }
+            (None, value) => {
+                map.insert(key, value);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn mark_nullable(map: &mut Map<String, Value>, support: &SchemaSupport) {
+    if support.type_arrays {
+        if let Some(Value::String(kind)) = map.get("type").cloned() {
+            map.insert("type".to_string(), json!([kind, "null"]));
+        }
+    } else {
+        map.insert("nullable".to_string(), json!(true));
+    }
+}
+
+const fn json_type(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::String(_) => Some("string"),
+        Value::Bool(_) => Some("boolean"),
+        Value::Number(_) => Some("number"),
+        Value::Array(_) => Some("array"),
+        Value::Object(_) => Some("object"),
+        Value::Null => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "path": {"type": "string", "format": "path"},
+                "limit": {"type": ["integer", "null"], "format": "uint"},
+                "sort": {"$ref": "#/$defs/Sort", "default": "name"},
+                "mode": {"oneOf": [{"const": "fast"}, {"const": "slow"}]},
+                "target": {"anyOf": [{"$ref": "#/$defs/Target"}, {"type": "null"}]}
+            },
+            "required": ["path"],
+            "additionalProperties": false,
+            "$defs": {
+                "Sort": {"type": "string", "enum": ["name", "size"]},
+                "Target": {"type": "object", "properties": {"id": {"type": "string"}}}
+            }
+        })
+    }
+
+    #[test]
+    fn test_full_support_is_identity() {
+        assert_eq!(sanitize(&schema(), &SchemaSupport::FULL), schema());
+    }
+
+    #[test]
+    fn test_basic_downgrades_constructs() {
+        let result = sanitize(&schema(), &SchemaSupport::BASIC);
+        let properties = &result["properties"];
+
+        assert!(result.get("$defs").is_none());
+        assert!(properties["path"].get("format").is_none());
+        assert_eq!(properties["limit"]["type"], json!(["integer", "null"]));
+        assert_eq!(properties["sort"]["enum"], json!(["name", "size"]));
+        assert_eq!(properties["sort"]["default"], "name");
+        assert_eq!(properties["mode"]["type"], "string");
+        assert_eq!(properties["mode"]["enum"], json!(["fast", "slow"]));
+        assert_eq!(properties["target"]["type"], json!(["object", "null"]));
+        assert_eq!(properties["target"]["properties"]["id"]["type"], "string");
+        assert_eq!(result["additionalProperties"], false);
+    }
+
+    #[test]
+    fn test_openapi_uses_nullable_and_allowlist() {
+        let result = sanitize(&schema(), &SchemaSupport::OPENAPI);
+        let properties = &result["properties"];
+
+        assert!(result.get("$schema").is_none());
+        assert!(result.get("additionalProperties").is_none());
+        assert_eq!(properties["limit"]["type"], "integer");
+        assert_eq!(properties["limit"]["nullable"], true);
+        assert!(properties["sort"].get("default").is_none());
+        assert_eq!(properties["sort"]["enum"], json!(["name", "size"]));
+        assert_eq!(properties["target"]["nullable"], true);
+        assert_eq!(properties["target"]["type"], "object");
+    }
+}
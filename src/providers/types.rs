@@ -1,10 +1,12 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::borrow::Cow;
 use std::fmt;
 
 use super::error::ProviderError;
+use crate::config::resolve_key;
 
 #[derive(Clone)]
 pub struct ApiKey(Cow<'static, str>);
@@ -16,16 +18,20 @@ impl ApiKey {
     }
 
     pub fn from_env(var_name: &str) -> Result<Self, ProviderError> {
-        std::env::var(var_name)
-            .map(|s| Self(Cow::Owned(s)))
-            .map_err(|_| {
+        resolve_key(var_name)
+            .map(|(key, _)| Self(Cow::Owned(key)))
+            .ok_or_else(|| {
                 ProviderError::Configuration(format!("Environment variable {var_name} not set"))
             })
     }
 
     #[must_use]
     pub fn from_env_or_empty(var_name: &str) -> Self {
-        Self(Cow::Owned(std::env::var(var_name).unwrap_or_default()))
+        Self(Cow::Owned(
+            resolve_key(var_name)
+                .map(|(key, _)| key)
+                .unwrap_or_default(),
+        ))
     }
 
     #[must_use]
@@ -150,6 +156,49 @@ impl AsRef<str> for BaseUrl {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtraBody(Map<String, Value>);
+
+impl ExtraBody {
+    #[must_use]
+    pub const fn new(fields: Map<String, Value>) -> Self {
+        Self(fields)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        merge_fields(&mut self.0, &other.0);
+    }
+
+    pub fn encode(&self, request: &impl Serialize) -> serde_json::Result<String> {
+        if self.0.is_empty() {
+            return serde_json::to_string(request);
+        }
+        let mut body = serde_json::to_value(request)?;
+        if let Value::Object(target) = &mut body {
+            merge_fields(target, &self.0);
+        }
+        serde_json::to_string(&body)
+    }
+}
+
+fn merge_fields(target: &mut Map<String, Value>, extra: &Map<String, Value>) {
+    for (key, value) in extra {
+        match (target.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge_fields(existing, nested);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +283,26 @@ mod tests {
         let key = ApiKey::from_env_or_empty("NONEXISTENT_VAR_12345");
         assert!(key.is_empty());
     }
+
+    #[test]
+    fn test_extra_body_merges_nested_fields() {
+        let extra: Map<String, Value> = serde_json::from_str(
+            r#"{"top_k": 40, "metadata": {"user_id": "u-1"}, "provider": {"order": ["a"]}}"#,
+        )
+        .unwrap();
+        let request = serde_json::json!({
+            "model": "m",
+            "metadata": {"trace": "t"},
+            "top_k": 1
+        });
+
+        let body = ExtraBody::new(extra).encode(&request).unwrap();
+        let body: Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(body["model"], "m");
+        assert_eq!(body["top_k"], 40);
+        assert_eq!(body["metadata"]["trace"], "t");
+        assert_eq!(body["metadata"]["user_id"], "u-1");
+        assert_eq!(body["provider"]["order"][0], "a");
+    }
 }
@@ -0,0 +1,368 @@
+mod session;
+
+pub use session::{ServerEvent, ServerObserver, ServerPermissionUI, Session};
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::Stream;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::agent::{Agent, PermissionPolicy};
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::permission::types::PermissionResponse;
+
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    pub model_id: Option<String>,
+    pub system_prompt: Option<String>,
+    pub max_iterations: Option<usize>,
+}
+
+pub struct ServerState {
+    options: ServeOptions,
+    llm: Option<Arc<dyn LLM>>,
+    sessions: RwLock<HashMap<Uuid, Arc<Session>>>,
+}
+
+impl ServerState {
+    #[must_use]
+    pub fn new(options: ServeOptions) -> Self {
+        Self {
+            options,
+            llm: None,
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_llm(mut self, llm: Arc<dyn LLM>) -> Self {
+        self.llm = Some(llm);
+        self
+    }
+
+    pub fn create_session(&self, request: CreateSessionRequest) -> Result<Arc<Session>> {
+        let (events, pending) = Session::channels();
+        let ui = Arc::new(ServerPermissionUI::new(events.clone(), pending.clone()));
+
+        let mut builder = Agent::builder()
+            .permission_policy(PermissionPolicy::Prompt(ui))
+            .observer(Arc::new(ServerObserver::new(events.clone())));
+
+        match (
+            &self.llm,
+            request.model.or_else(|| self.options.model_id.clone()),
+        ) {
+            (Some(llm), _) => builder = builder.llm(llm.clone()),
+            (None, Some(model_id)) => builder = builder.model(model_id),
+            (None, None) => {
+                return Err(AgentError::Config(
+                    "No model specified. Pass --model or set one in the config file".to_string(),
+                ));
+            }
+        }
+        if let Some(prompt) = request
+            .system_prompt
+            .or_else(|| self.options.system_prompt.clone())
+        {
+            builder = builder.system_prompt(prompt);
+        }
+        if let Some(max_iterations) = self.options.max_iterations {
+            builder = builder.max_iterations(max_iterations);
+        }
+
+        let session = Arc::new(Session::new(builder.build()?, events, pending));
+        self.sessions.write().insert(session.id(), session.clone());
+        Ok(session)
+    }
+
+    #[must_use]
+    pub fn session(&self, id: Uuid) -> Option<Arc<Session>> {
+        self.sessions.read().get(&id).cloned()
+    }
+
+    #[must_use]
+    pub fn session_ids(&self) -> Vec<Uuid> {
+        self.sessions.read().keys().copied().collect()
+    }
+
+    pub fn remove_session(&self, id: Uuid) -> bool {
+        self.sessions.write().remove(&id).is_some()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CreateSessionRequest {
+    pub model: Option<String>,
+    pub system_prompt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SendMessageRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionInfo {
+    id: Uuid,
+    pending_permissions: Vec<Uuid>,
+}
+
+impl From<&Session> for SessionInfo {
+    fn from(session: &Session) -> Self {
+        Self {
+            id: session.id(),
+            pending_permissions: session.pending_permissions(),
+        }
+    }
+}
+
+struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn not_found(what: &str) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            code: "ERR_NOT_FOUND",
+            message: format!("{what} not found"),
+        }
+    }
+}
+
+impl From<AgentError> for ApiError {
+    fn from(err: AgentError) -> Self {
+        let status = match err {
+            AgentError::Config(_) => StatusCode::BAD_REQUEST,
+            AgentError::InvalidState(_) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            code: err.code(),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.message, "code": self.code }));
+        (self.status, body).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<T, ApiError>;
+
+pub fn router(state: Arc<ServerState>) -> Router {
+    Router::new()
+        .route("/sessions", get(list_sessions).post(create_session))
+        .route("/sessions/{id}", get(get_session).delete(delete_session))
+        .route("/sessions/{id}/messages", post(send_message))
+        .route("/sessions/{id}/events", get(stream_events))
+        .route(
+            "/sessions/{id}/permissions/{request_id}",
+            post(answer_permission),
+        )
+        .with_state(state)
+}
+
+pub async fn serve(listener: TcpListener, state: Arc<ServerState>) -> Result<()> {
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+pub async fn run(addr: SocketAddr, options: ServeOptions) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    println!(
+        "smith server listening on http://{}",
+        listener.local_addr()?
+    );
+    serve(listener, Arc::new(ServerState::new(options))).await
+}
+
+fn find_session(state: &ServerState, id: Uuid) -> ApiResult<Arc<Session>> {
+    state
+        .session(id)
+        .ok_or_else(|| ApiError::not_found("Session"))
+}
+
+async fn list_sessions(State(state): State<Arc<ServerState>>) -> Json<Vec<Uuid>> {
+    Json(state.session_ids())
+}
+
+async fn create_session(
+    State(state): State<Arc<ServerState>>,
+    body: Option<Json<CreateSessionRequest>>,
+) -> ApiResult<(StatusCode, Json<SessionInfo>)> {
+    let request = body.map(|Json(request)| request).unwrap_or_default();
+    let session = state.create_session(request)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(SessionInfo::from(session.as_ref())),
+    ))
+}
+
+async fn get_session(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<SessionInfo>> {
+    let session = find_session(&state, id)?;
+    Ok(Json(SessionInfo::from(session.as_ref())))
+}
+
+async fn delete_session(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    if state.remove_session(id) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("Session"))
+    }
+}
+
+async fn send_message(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SendMessageRequest>,
+) -> ApiResult<StatusCode> {
+    find_session(&state, id)?.send_message(request.content)?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn stream_events(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Sse<impl Stream<Item = std::result::Result<Event, axum::Error>>>> {
+    let rx = find_session(&state, id)?.subscribe();
+
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((Event::default().json_data(&event), rx)),
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+async fn answer_permission(
+    State(state): State<Arc<ServerState>>,
+    Path((id, request_id)): Path<(Uuid, Uuid)>,
+    Json(response): Json<PermissionResponse>,
+) -> ApiResult<StatusCode> {
+    if find_session(&state, id)?.answer_permission(request_id, response) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("Permission request"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{MockLLM, MockResponse};
+    use futures::StreamExt;
+
+    async fn spawn_server(llm: MockLLM) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(ServerState::new(ServeOptions::default()).with_llm(Arc::new(llm)));
+        tokio::spawn(serve(listener, state));
+        format!("http://{addr}")
+    }
+
+    async fn create(client: &reqwest::Client, base: &str) -> Uuid {
+        let response = client
+            .post(format!("{base}/sessions"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let info: serde_json::Value = response.json().await.unwrap();
+        info["id"].as_str().unwrap().parse().unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_message_streams_completion_event() {
+        let mock = MockLLM::new().with_response(MockResponse::text("unused"));
+        let base = spawn_server(mock.clone()).await;
+        let client = reqwest::Client::new();
+        let id = create(&client, &base).await;
+
+        let events = client
+            .get(format!("{base}/sessions/{id}/events"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(events.status(), StatusCode::OK);
+
+        let response = client
+            .post(format!("{base}/sessions/{id}/messages"))
+            .json(&serde_json::json!({"content": "hello"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let mut body = events.bytes_stream();
+        let mut received = String::new();
+        while !received.contains("message_completed") {
+            let chunk = body.next().await.unwrap().unwrap();
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        assert_eq!(mock.request_count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unknown_session_and_permission() {
+        let base = spawn_server(MockLLM::new()).await;
+        let client = reqwest::Client::new();
+
+        let missing = client
+            .post(format!("{base}/sessions/{}/messages", Uuid::new_v4()))
+            .json(&serde_json::json!({"content": "hello"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let id = create(&client, &base).await;
+        let answer = client
+            .post(format!(
+                "{base}/sessions/{id}/permissions/{}",
+                Uuid::new_v4()
+            ))
+            .json(&PermissionResponse::AllowOnce)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(answer.status(), StatusCode::NOT_FOUND);
+
+        let deleted = client
+            .delete(format!("{base}/sessions/{id}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    }
+}
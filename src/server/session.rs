@@ -0,0 +1,241 @@
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex as AsyncMutex, broadcast, oneshot};
+use uuid::Uuid;
+
+use crate::agent::Agent;
+use crate::core::AgentObserver;
+use crate::core::error::{AgentError, Result};
+use crate::core::types::Usage;
+use crate::permission::types::{PermissionRequest, PermissionResponse};
+use crate::permission::ui_trait::PermissionUI;
+use crate::tools::result::ToolResult;
+
+const EVENT_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerEvent {
+    TextDelta {
+        text: String,
+    },
+    ToolStarted {
+        name: String,
+        input: serde_json::Value,
+    },
+    ToolCompleted {
+        name: String,
+        success: bool,
+        output: String,
+    },
+    Usage {
+        usage: Usage,
+    },
+    PermissionRequired {
+        id: Uuid,
+        request: PermissionRequest,
+    },
+    MessageCompleted {
+        text: String,
+        usage: Usage,
+    },
+    Error {
+        message: String,
+    },
+}
+
+type PendingPermissions = Arc<Mutex<HashMap<Uuid, oneshot::Sender<PermissionResponse>>>>;
+
+pub struct Session {
+    id: Uuid,
+    agent: Arc<AsyncMutex<Agent>>,
+    events: broadcast::Sender<ServerEvent>,
+    pending: PendingPermissions,
+}
+
+impl Session {
+    #[must_use]
+    pub fn channels() -> (broadcast::Sender<ServerEvent>, PendingPermissions) {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        (events, Arc::new(Mutex::new(HashMap::new())))
+    }
+
+    #[must_use]
+    pub fn new(
+        agent: Agent,
+        events: broadcast::Sender<ServerEvent>,
+        pending: PendingPermissions,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            agent: Arc::new(AsyncMutex::new(agent)),
+            events,
+            pending,
+        }
+    }
+
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<ServerEvent> {
+        self.events.subscribe()
+    }
+
+    #[must_use]
+    pub fn pending_permissions(&self) -> Vec<Uuid> {
+        self.pending.lock().keys().copied().collect()
+    }
+
+    #[must_use]
+    pub fn answer_permission(&self, id: Uuid, response: PermissionResponse) -> bool {
+        self.pending
+            .lock()
+            .remove(&id)
+            .is_some_and(|tx| tx.send(response).is_ok())
+    }
+
+    pub fn send_message(&self, content: String) -> Result<()> {
+        let mut agent = self.agent.clone().try_lock_owned().map_err(|_| {
+            AgentError::InvalidState("Session is already processing a message".to_string())
+        })?;
+        let events = self.events.clone();
+
+        tokio::spawn(async move {
+            let event = match agent.run(content).await {
+                Ok(output) => ServerEvent::MessageCompleted {
+                    text: output.text(),
+                    usage: output.usage,
+                },
+                Err(e) => ServerEvent::Error {
+                    message: e.to_string(),
+                },
+            };
+            let _ = events.send(event);
+        });
+
+        Ok(())
+    }
+}
+
+pub struct ServerObserver {
+    events: broadcast::Sender<ServerEvent>,
+}
+
+impl ServerObserver {
+    #[must_use]
+    pub const fn new(events: broadcast::Sender<ServerEvent>) -> Self {
+        Self { events }
+    }
+}
+
+impl AgentObserver for ServerObserver {
+    fn on_text_delta(&self, text: &str) {
+        let _ = self.events.send(ServerEvent::TextDelta {
+            text: text.to_string(),
+        });
+    }
+
+    fn on_tool_start(&self, name: &str, input: &serde_json::Value) {
+        let _ = self.events.send(ServerEvent::ToolStarted {
+            name: name.to_string(),
+            input: input.clone(),
+        });
+    }
+
+    fn on_tool_end(&self, name: &str, result: &ToolResult) {
+        let _ = self.events.send(ServerEvent::ToolCompleted {
+            name: name.to_string(),
+            success: result.is_success(),
+            output: result.to_llm_string(),
+        });
+    }
+
+    fn on_token_usage(&self, usage: &Usage) {
+        let _ = self.events.send(ServerEvent::Usage { usage: *usage });
+    }
+}
+
+pub struct ServerPermissionUI {
+    events: broadcast::Sender<ServerEvent>,
+    pending: PendingPermissions,
+}
+
+impl ServerPermissionUI {
+    #[must_use]
+    pub const fn new(events: broadcast::Sender<ServerEvent>, pending: PendingPermissions) -> Self {
+        Self { events, pending }
+    }
+}
+
+impl PermissionUI for ServerPermissionUI {
+    fn prompt_user(&self, request: &PermissionRequest) -> Result<PermissionResponse> {
+        let id = Uuid::new_v4();
+        let (response_tx, response_rx) = oneshot::channel();
+        self.pending.lock().insert(id, response_tx);
+
+        let event = ServerEvent::PermissionRequired {
+            id,
+            request: request.clone(),
+        };
+        if self.events.send(event).is_err() {
+            self.pending.lock().remove(&id);
+            return Ok(PermissionResponse::TellModelDifferently(
+                "No client is connected to approve this operation".to_string(),
+            ));
+        }
+
+        tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(response_rx))
+            .map_err(|e| {
+                AgentError::InvalidState(format!("Permission response channel closed: {e}"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permission::types::PermissionType;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_permission_round_trip() {
+        let (events, pending) = Session::channels();
+        let mut rx = events.subscribe();
+        let ui = ServerPermissionUI::new(events, pending.clone());
+
+        let prompt = tokio::task::spawn_blocking(move || {
+            ui.prompt_user(&PermissionRequest::new(PermissionType::FileWrite, "a.txt"))
+        });
+
+        let ServerEvent::PermissionRequired { id, request } = rx.recv().await.unwrap() else {
+            panic!("Expected permission event");
+        };
+        assert_eq!(request.target, "a.txt");
+
+        let tx = pending.lock().remove(&id).unwrap();
+        tx.send(PermissionResponse::AllowOnce).unwrap();
+
+        let response = prompt.await.unwrap().unwrap();
+        assert_eq!(response, PermissionResponse::AllowOnce);
+    }
+
+    #[test]
+    fn test_permission_denied_without_client() {
+        let (events, pending) = Session::channels();
+        let ui = ServerPermissionUI::new(events, pending.clone());
+
+        let response = ui
+            .prompt_user(&PermissionRequest::new(PermissionType::FileWrite, "a.txt"))
+            .unwrap();
+
+        assert!(matches!(
+            response,
+            PermissionResponse::TellModelDifferently(_)
+        ));
+        assert!(pending.lock().is_empty());
+    }
+}
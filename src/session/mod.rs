@@ -0,0 +1,5 @@
+mod store;
+mod title;
+
+pub use store::{SessionRecord, SessionStore};
+pub use title::{generate_title, heuristic_title};
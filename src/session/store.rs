@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::config::get_config_dir;
+use crate::core::error::{AgentError, Result};
+use crate::core::types::{ContentBlock, Message, Role, Usage};
+use crate::tools::fs::atomic_write;
+
+const UNTITLED: &str = "Untitled session";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub usage: Usage,
+    #[serde(default)]
+    pub messages: Vec<Message>,
+}
+
+impl SessionRecord {
+    #[must_use]
+    pub fn new(model: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            parent: None,
+            title: None,
+            model: model.into(),
+            created_at: now,
+            updated_at: now,
+            usage: Usage::default(),
+            messages: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(UNTITLED)
+    }
+
+    #[must_use]
+    pub fn branch(&self, turns_back: usize) -> Option<Self> {
+        let starts: Vec<usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.role == Role::User)
+            .map(|(i, _)| i)
+            .collect();
+        let cut = *starts.get(starts.len().checked_sub(turns_back + 1)?)?;
+        let mut branch = Self::new(self.model.clone());
+        branch.parent = Some(self.id);
+        branch.title.clone_from(&self.title);
+        branch.messages = self.messages[..cut].to_vec();
+        Some(branch)
+    }
+
+    pub fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+
+    #[must_use]
+    pub fn export_file_name(&self) -> String {
+        let slug: String = self
+            .display_title()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let slug = slug
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let id = self.id.simple().to_string();
+        format!("{slug}-{}.md", &id[..8])
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# {}
+
+_{} · {}_
+",
+            self.display_title(),
+            self.model,
+            self.created_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        for message in &self.messages {
+            let heading = match message.role {
+                Role::User => "User",
+                Role::Assistant => "Assistant",
+                Role::Tool | Role::System => continue,
+            };
+            let text: Vec<&str> = message
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text } if !text.trim().is_empty() => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if !text.is_empty() {
+                let _ = write!(out, "\n## {heading}\n\n{}\n", text.join("\n\n"));
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    #[must_use]
+    pub fn with_default_dir() -> Option<Self> {
+        get_config_dir().map(|dir| Self::new(dir.join("sessions")))
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    pub fn save(&self, record: &SessionRecord) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(record)?;
+        atomic_write(&self.path(record.id), &json)?;
+        Ok(())
+    }
+
+    pub fn load(&self, id: Uuid) -> Result<SessionRecord> {
+        let json = fs::read_to_string(self.path(id))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn list(&self) -> Result<Vec<SessionRecord>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records: Vec<SessionRecord> = entries
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let json = fs::read_to_string(&path).ok()?;
+                serde_json::from_str(&json)
+                    .inspect_err(|e| {
+                        tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable session");
+                    })
+                    .ok()
+            })
+            .collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.updated_at));
+        Ok(records)
+    }
+
+    pub fn find(&self, query: &str) -> Result<SessionRecord> {
+        let path = Path::new(query);
+        if path.is_file() {
+            return Ok(serde_json::from_str(&fs::read_to_string(path)?)?);
+        }
+        let mut matches: Vec<SessionRecord> = self
+            .list()?
+            .into_iter()
+            .filter(|record| record.id.to_string().starts_with(query))
+            .collect();
+        match matches.len() {
+            1 => Ok(matches.remove(0)),
+            0 => Err(AgentError::Config(format!(
+                "No saved session matches '{query}'"
+            ))),
+            n => Err(AgentError::Config(format!(
+                "'{query}' matches {n} sessions; use more of the id"
+            ))),
+        }
+    }
+
+    pub fn delete(&self, id: Uuid) -> Result<()> {
+        fs::remove_file(self.path(id))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_list_delete() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = SessionStore::new(temp.path().join("sessions"));
+        assert!(store.list().unwrap().is_empty());
+
+        let mut first = SessionRecord::new("gpt-5.2");
+        first.messages.push(Message::user("hello"));
+        store.save(&first).unwrap();
+
+        let mut second = SessionRecord::new("claude-sonnet-4-5");
+        second.title = Some("Fix the parser".to_string());
+        second.touch();
+        store.save(&second).unwrap();
+
+        let records = store.list().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].display_title(), "Fix the parser");
+        assert_eq!(records[1].display_title(), UNTITLED);
+
+        let loaded = store.load(first.id).unwrap();
+        assert_eq!(loaded.messages.len(), 1);
+        let found = store.find(&first.id.to_string()[..8]).unwrap();
+        assert_eq!(found.id, first.id);
+        assert!(store.find("zzz").is_err());
+
+        store.delete(first.id).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_branch_truncates_at_user_turn() {
+        let mut record = SessionRecord::new("gpt-5.2");
+        record.title = Some("Parser".to_string());
+        record.messages = vec![
+            Message::user("first"),
+            Message::assistant("one"),
+            Message::user("second"),
+            Message::new(Role::Tool, vec![ContentBlock::text("output")]),
+            Message::assistant("two"),
+        ];
+
+        let branch = record.branch(0).unwrap();
+        assert_ne!(branch.id, record.id);
+        assert_eq!(branch.parent, Some(record.id));
+        assert_eq!(branch.display_title(), "Parser");
+        assert_eq!(branch.messages.len(), 2);
+
+        assert!(record.branch(1).unwrap().messages.is_empty());
+        assert!(record.branch(2).is_none());
+    }
+}
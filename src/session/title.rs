@@ -0,0 +1,100 @@
+use crate::core::llm::LLM;
+use crate::core::types::{CompletionRequest, Message};
+
+const MAX_TITLE_CHARS: usize = 60;
+const TITLE_MAX_TOKENS: u32 = 24;
+const EXCERPT_CHARS: usize = 1500;
+const TITLE_PROMPT: &str = "Write a short title (at most six words) for the conversation below. Reply with the title only, without quotes or trailing punctuation.";
+
+pub async fn generate_title(llm: &dyn LLM, user: &str, reply: &str) -> String {
+    let conversation = format!(
+        "User: {}\n\nAssistant: {}",
+        excerpt(user, EXCERPT_CHARS),
+        excerpt(reply, EXCERPT_CHARS)
+    );
+    let request = CompletionRequest::new(vec![Message::user(conversation)])
+        .with_system_prompt(TITLE_PROMPT)
+        .with_max_tokens(TITLE_MAX_TOKENS)
+        .with_temperature(0.0);
+
+    match llm.complete(request).await {
+        Ok(response) => response
+            .message
+            .first_text()
+            .map(clean_title)
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| heuristic_title(user)),
+        Err(e) => {
+            tracing::debug!(error = %e, "Title generation failed, using heuristic");
+            heuristic_title(user)
+        }
+    }
+}
+
+#[must_use]
+pub fn heuristic_title(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    clean_title(line)
+}
+
+fn clean_title(raw: &str) -> String {
+    let title = raw
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_start_matches('#')
+        .trim_start_matches("Title:")
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`' | '*'))
+        .trim_end_matches(['.', ':', '!'])
+        .trim();
+
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        return title.to_string();
+    }
+    let cut: String = title.chars().take(MAX_TITLE_CHARS).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end())
+}
+
+fn excerpt(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{MockLLM, MockResponse};
+
+    #[test]
+    fn test_heuristic_title() {
+        assert_eq!(
+            heuristic_title("\n  Fix the failing parser test.\nIt breaks on CI"),
+            "Fix the failing parser test"
+        );
+        let long = "please refactor the configuration loader so that it reads every file lazily and caches results";
+        let title = heuristic_title(long);
+        assert!(title.ends_with('…'));
+        assert!(title.chars().count() <= MAX_TITLE_CHARS + 1);
+    }
+
+    #[tokio::test]
+    async fn test_generate_title_cleans_reply_and_falls_back() {
+        let llm = MockLLM::new().with_response(MockResponse::text("\"Parser Test Fix.\""));
+        assert_eq!(
+            generate_title(&llm, "fix the test", "done").await,
+            "Parser Test Fix"
+        );
+
+        let failing = MockLLM::new();
+        assert_eq!(
+            generate_title(&failing, "fix the test", "done").await,
+            "fix the test"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::core::attachment::fit_to_budget;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellInput<'a> {
+    pub command: &'a str,
+    pub share: bool,
+}
+
+#[must_use]
+pub fn parse_shell_input(text: &str) -> Option<ShellInput<'_>> {
+    let rest = text.strip_prefix('!')?;
+    let (rest, share) = rest
+        .strip_prefix('!')
+        .map_or((rest, false), |shared| (shared, true));
+    let command = rest.trim();
+    (!command.is_empty()).then_some(ShellInput { command, share })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellRun {
+    pub command: String,
+    pub output: String,
+    pub exit_code: Option<i32>,
+    pub elapsed: Duration,
+}
+
+impl ShellRun {
+    #[must_use]
+    pub const fn success(&self) -> bool {
+        matches!(self.exit_code, Some(0))
+    }
+
+    #[must_use]
+    pub fn status(&self) -> String {
+        match self.exit_code {
+            Some(0) => "exit 0".to_string(),
+            Some(code) => format!("exit {code}"),
+            None => "killed by a signal".to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn context(&self, budget_tokens: usize) -> String {
+        let output = self.output.trim_end();
+        let output = if output.is_empty() {
+            "(no output)".into()
+        } else {
+            fit_to_budget(output, budget_tokens)
+        };
+        format!(
+            "I ran `{}` in my shell ({}):\n```\n{output}\n```",
+            self.command,
+            self.status()
+        )
+    }
+}
+
+pub async fn run_shell(command: &str, dir: &Path) -> io::Result<ShellRun> {
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.trim().is_empty())
+        .unwrap_or_else(|| "sh".to_string());
+    let started = Instant::now();
+    let output = tokio::process::Command::new(shell)
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(ShellRun {
+        command: command.to_string(),
+        output: text,
+        exit_code: output.status.code(),
+        elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_and_run_shell_input() {
+        assert_eq!(
+            parse_shell_input("! git status "),
+            Some(ShellInput {
+                command: "git status",
+                share: false
+            })
+        );
+        assert_eq!(
+            parse_shell_input("!!cargo check"),
+            Some(ShellInput {
+                command: "cargo check",
+                share: true
+            })
+        );
+        assert_eq!(parse_shell_input("!!"), None);
+        assert_eq!(parse_shell_input("run !ls"), None);
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let run = run_shell("echo out; echo err >&2; exit 3", temp.path())
+            .await
+            .unwrap();
+        assert_eq!(run.output, "out\nerr\n");
+        assert!(!run.success());
+        assert_eq!(
+            run.context(0),
+            "I ran `echo out; echo err >&2; exit 3` in my shell (exit 3):\n```\nout\nerr\n```"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::config::get_config_dir;
+
+const SNIPPET_EXTENSIONS: &[&str] = &["md", "txt"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub name: String,
+    pub template: String,
+}
+
+impl Snippet {
+    #[must_use]
+    pub fn new(name: impl Into<String>, template: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            template: template.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + end].trim();
+            if !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+            rest = &rest[start + end + 2..];
+        }
+        names
+    }
+
+    #[must_use]
+    pub fn fill(&self, values: &[(String, String)]) -> String {
+        let mut filled = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            filled.push_str(&rest[..start]);
+            let name = rest[start + 2..start + end].trim();
+            match values.iter().find(|(n, _)| n == name) {
+                Some((_, value)) => filled.push_str(value),
+                None => filled.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        filled.push_str(rest);
+        filled
+    }
+
+    #[must_use]
+    pub fn values_from_args(&self, args: &str) -> Vec<(String, String)> {
+        let placeholders = self.placeholders();
+        let mut rest = args.trim();
+        let mut values = Vec::new();
+        for (i, name) in placeholders.iter().enumerate() {
+            if rest.is_empty() {
+                break;
+            }
+            let value = if i + 1 == placeholders.len() {
+                std::mem::take(&mut rest)
+            } else {
+                let (word, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                rest = tail.trim_start();
+                word
+            };
+            values.push((name.clone(), value.to_string()));
+        }
+        values
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SnippetLibrary {
+    snippets: BTreeMap<String, String>,
+}
+
+impl SnippetLibrary {
+    #[must_use]
+    pub fn load(config: &HashMap<String, String>) -> Self {
+        let library =
+            default_dir().map_or_else(Self::default, |dir| Self::default().with_dir(&dir));
+        library.with_templates(config.clone())
+    }
+
+    #[must_use]
+    pub fn with_dir(mut self, dir: &Path) -> Self {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return self;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let is_snippet = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SNIPPET_EXTENSIONS.contains(&ext));
+            if let (true, Some(name)) = (is_snippet, path.file_stem().and_then(|s| s.to_str()))
+                && let Ok(template) = std::fs::read_to_string(&path)
+            {
+                self.snippets
+                    .insert(name.to_string(), template.trim_end().to_string());
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_templates(mut self, templates: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.snippets.extend(templates);
+        self
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Snippet> {
+        self.snippets
+            .get(name)
+            .map(|template| Snippet::new(name, template.clone()))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.snippets.keys().map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+}
+
+#[must_use]
+pub fn default_dir() -> Option<PathBuf> {
+    get_config_dir().map(|dir| dir.join("snippets"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_placeholders_and_fill() {
+        let snippet = Snippet::new(
+            "tests",
+            "Write tests for {{ file }} covering {{cases}}. Keep {{file}} unchanged. {{",
+        );
+        assert_eq!(snippet.placeholders(), vec!["file", "cases"]);
+
+        let values = vec![("file".to_string(), "src/lib.rs".to_string())];
+        assert_eq!(
+            snippet.fill(&values),
+            "Write tests for src/lib.rs covering {{cases}}. Keep src/lib.rs unchanged. {{"
+        );
+    }
+
+    #[test]
+    fn test_values_from_args() {
+        let snippet = Snippet::new("t", "{{file}}: {{ask}}");
+        assert_eq!(
+            snippet.values_from_args("a.rs  add error handling"),
+            vec![
+                ("file".to_string(), "a.rs".to_string()),
+                ("ask".to_string(), "add error handling".to_string())
+            ]
+        );
+        assert_eq!(snippet.values_from_args("a.rs").len(), 1);
+        assert!(snippet.values_from_args("  ").is_empty());
+    }
+
+    #[test]
+    fn test_library_merges_dir_and_config() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("review.md"), "Review {{path}}\n").unwrap();
+        std::fs::write(temp.path().join("tests.txt"), "from dir").unwrap();
+        std::fs::write(temp.path().join("notes.json"), "{}").unwrap();
+
+        let library = SnippetLibrary::default()
+            .with_dir(temp.path())
+            .with_templates([("tests".to_string(), "from config".to_string())]);
+
+        assert_eq!(library.names().collect::<Vec<_>>(), vec!["review", "tests"]);
+        assert_eq!(library.get("review").unwrap().template, "Review {{path}}");
+        assert_eq!(library.get("tests").unwrap().template, "from config");
+        assert!(library.get("notes").is_none());
+    }
+}
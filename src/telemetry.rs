@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use crate::config::TelemetryConfig;
+use crate::core::types::Usage;
+
+pub type BoxedLayer =
+    Box<dyn tracing_subscriber::Layer<tracing_subscriber::Registry> + Send + Sync>;
+
+pub fn record_llm_request(
+    provider: &str,
+    model: &str,
+    usage: &Usage,
+    elapsed: Duration,
+    success: bool,
+) {
+    tracing::event!(
+        target: "smith::metrics",
+        tracing::Level::INFO,
+        monotonic_counter.smith.llm.requests = 1_u64,
+        monotonic_counter.smith.llm.errors = u64::from(!success),
+        monotonic_counter.smith.llm.input_tokens = u64::from(usage.input_tokens),
+        monotonic_counter.smith.llm.output_tokens = u64::from(usage.output_tokens),
+        histogram.smith.llm.duration_ms = elapsed.as_secs_f64() * 1000.0,
+        provider,
+        model,
+        success,
+    );
+}
+
+pub fn record_tool_execution(tool: &str, elapsed: Duration, success: bool) {
+    tracing::event!(
+        target: "smith::metrics",
+        tracing::Level::INFO,
+        monotonic_counter.smith.tool.executions = 1_u64,
+        monotonic_counter.smith.tool.errors = u64::from(!success),
+        histogram.smith.tool.duration_ms = elapsed.as_secs_f64() * 1000.0,
+        tool,
+        success,
+    );
+}
+
+#[cfg(feature = "otel")]
+mod otlp {
+    use super::{BoxedLayer, TelemetryConfig};
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::{EnvFilter, Layer};
+
+    pub struct TelemetryGuard {
+        tracer_provider: SdkTracerProvider,
+        meter_provider: SdkMeterProvider,
+    }
+
+    impl Drop for TelemetryGuard {
+        fn drop(&mut self) {
+            let _ = self.tracer_provider.shutdown();
+            let _ = self.meter_provider.shutdown();
+        }
+    }
+
+    fn signal_endpoint(config: &TelemetryConfig, path: &str) -> Option<String> {
+        config
+            .otlp_endpoint
+            .as_deref()
+            .map(|base| format!("{}/{path}", base.trim_end_matches('/')))
+    }
+
+    #[must_use]
+    pub fn layers(config: &TelemetryConfig) -> Option<(Vec<BoxedLayer>, TelemetryGuard)> {
+        if !config.enabled {
+            return None;
+        }
+
+        let mut spans = SpanExporter::builder().with_http();
+        if let Some(endpoint) = signal_endpoint(config, "v1/traces") {
+            spans = spans.with_endpoint(endpoint);
+        }
+        let mut metrics = MetricExporter::builder().with_http();
+        if let Some(endpoint) = signal_endpoint(config, "v1/metrics") {
+            metrics = metrics.with_endpoint(endpoint);
+        }
+        let (span_exporter, metric_exporter) = match (spans.build(), metrics.build()) {
+            (Ok(spans), Ok(metrics)) => (spans, metrics),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("Failed to create OTLP exporter: {e}");
+                return None;
+            }
+        };
+
+        let resource = Resource::builder()
+            .with_service_name(config.service_name.clone())
+            .build();
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(resource.clone())
+            .build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metric_exporter).build())
+            .with_resource(resource)
+            .build();
+
+        let layers = vec![
+            tracing_opentelemetry::layer()
+                .with_tracer(tracer_provider.tracer("smith"))
+                .with_filter(EnvFilter::new(&config.filter))
+                .boxed(),
+            tracing_opentelemetry::MetricsLayer::new(meter_provider.clone())
+                .with_filter(EnvFilter::new(&config.filter))
+                .boxed(),
+        ];
+
+        Some((
+            layers,
+            TelemetryGuard {
+                tracer_provider,
+                meter_provider,
+            },
+        ))
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod otlp {
+    use super::{BoxedLayer, TelemetryConfig};
+
+    pub struct TelemetryGuard;
+
+    #[must_use]
+    pub fn layers(config: &TelemetryConfig) -> Option<(Vec<BoxedLayer>, TelemetryGuard)> {
+        if config.enabled {
+            eprintln!("Telemetry is enabled but smith was built without the `otel` feature");
+        }
+        None
+    }
+}
+
+pub use otlp::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn test_telemetry_defaults() {
+        let config: AppConfig = toml::from_str("model = \"gpt-5.2\"").unwrap();
+        assert!(!config.telemetry.enabled);
+        assert_eq!(config.telemetry.service_name, "smith");
+        assert!(layers(&config.telemetry).is_none());
+    }
+
+    #[test]
+    fn test_telemetry_section() {
+        let config: AppConfig = toml::from_str(
+            "[telemetry]\nenabled = true\notlp_endpoint = \"http://collector:4318\"",
+        )
+        .unwrap();
+        assert!(config.telemetry.enabled);
+        assert_eq!(
+            config.telemetry.otlp_endpoint.as_deref(),
+            Some("http://collector:4318")
+        );
+        assert_eq!(config.telemetry.filter, "smith=info");
+    }
+}
@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::core::error::Result;
+use crate::tools::fs::{format_size, validate_is_file, validate_path_exists};
+use crate::tools::{ToolType, TypedTool, WorkingDirectory};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactStore(Arc<RwLock<Vec<PathBuf>>>);
+
+impl ArtifactStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn mark(&self, path: PathBuf) -> bool {
+        let mut marked = self.0.write();
+        if marked.contains(&path) {
+            return false;
+        }
+        marked.push(path);
+        true
+    }
+
+    #[must_use]
+    pub fn collect(&self, scratch: &Path) -> Vec<Artifact> {
+        let mut artifacts = Vec::new();
+        scratch_files(scratch, scratch, &mut artifacts);
+        artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for path in self.0.read().iter() {
+            if artifacts.iter().any(|artifact| artifact.path == *path) {
+                continue;
+            }
+            let Ok(metadata) = std::fs::metadata(path) else {
+                continue;
+            };
+            let file_name = path
+                .file_name()
+                .map_or_else(|| "artifact".into(), |name| name.to_string_lossy());
+            let mut name = file_name.to_string();
+            let mut n = 1;
+            while artifacts.iter().any(|artifact| artifact.name == name) {
+                n += 1;
+                name = format!("{n}-{file_name}");
+            }
+            artifacts.push(Artifact {
+                name,
+                path: path.clone(),
+                size: metadata.len(),
+            });
+        }
+        artifacts
+    }
+}
+
+fn scratch_files(root: &Path, dir: &Path, artifacts: &mut Vec<Artifact>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            scratch_files(root, &path, artifacts);
+        } else if metadata.is_file()
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            artifacts.push(Artifact {
+                name: relative.to_string_lossy().replace('\\', "/"),
+                path,
+                size: metadata.len(),
+            });
+        }
+    }
+}
+
+pub fn export_artifacts(artifacts: &[Artifact], dest: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::with_capacity(artifacts.len());
+    for artifact in artifacts {
+        let target = dest.join(&artifact.name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&artifact.path, &target)?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+#[must_use]
+pub fn format_artifacts(artifacts: &[Artifact]) -> String {
+    if artifacts.is_empty() {
+        return "No artifacts yet. Files the agent writes to its scratch directory or marks with mark_artifact show up here.".to_string();
+    }
+    let mut output = format!("{} artifact(s):", artifacts.len());
+    for (index, artifact) in artifacts.iter().enumerate() {
+        let _ = write!(
+            output,
+            "\n{:>3}. {} ({})  {}",
+            index + 1,
+            artifact.name,
+            format_size(artifact.size),
+            artifact.path.display()
+        );
+    }
+    output.push_str(
+        "\n\n/artifacts copy <n> <path> copies one, /artifacts export <dir> copies them all",
+    );
+    output
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MarkArtifactInput {
+    pub path: String,
+}
+
+pub struct MarkArtifactTool {
+    store: ArtifactStore,
+    cwd: WorkingDirectory,
+}
+
+impl MarkArtifactTool {
+    #[must_use]
+    pub const fn new(store: ArtifactStore, cwd: WorkingDirectory) -> Self {
+        Self { store, cwd }
+    }
+}
+
+#[async_trait]
+impl TypedTool for MarkArtifactTool {
+    type Input = MarkArtifactInput;
+
+    fn name(&self) -> &'static str {
+        "mark_artifact"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mark a file you produced (report, export, generated asset) as a deliverable so the user can collect it. Files in the scratch directory are collected automatically."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let path = self.cwd.resolve(Path::new(&input.path));
+        validate_path_exists(&path, &ToolType::MarkArtifact)?;
+        validate_is_file(&path, &ToolType::MarkArtifact)?;
+        let path = path.canonicalize()?;
+        if self.store.mark(path.clone()) {
+            Ok(format!("Marked {} as an artifact", path.display()))
+        } else {
+            Ok(format!("{} is already an artifact", path.display()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collect_mark_and_export() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let scratch = temp.path().join("scratch");
+        std::fs::create_dir_all(scratch.join("plots")).unwrap();
+        std::fs::write(scratch.join("report.md"), "# Report").unwrap();
+        std::fs::write(scratch.join("plots/latency.svg"), "<svg/>").unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("report.md"), "# Other").unwrap();
+
+        let store = ArtifactStore::new();
+        let tool = MarkArtifactTool::new(store.clone(), WorkingDirectory::new(repo.clone()));
+        let marked = tool
+            .execute_typed(MarkArtifactInput {
+                path: "report.md".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(marked.starts_with("Marked"));
+        assert!(
+            tool.execute_typed(MarkArtifactInput {
+                path: "missing.csv".to_string(),
+            })
+            .await
+            .is_err()
+        );
+
+        let artifacts = store.collect(&scratch);
+        let names: Vec<&str> = artifacts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["plots/latency.svg", "report.md", "2-report.md"]);
+        assert!(format_artifacts(&artifacts).starts_with("3 artifact(s):"));
+
+        let dest = temp.path().join("out");
+        export_artifacts(&artifacts, &dest).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dest.join("2-report.md")).unwrap(),
+            "# Other"
+        );
+        assert!(dest.join("plots/latency.svg").exists());
+    }
+}
@@ -1,10 +1,62 @@
-use std::path::PathBuf;
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::artifacts::{Artifact, ArtifactStore};
+use super::fs::{DocumentAttachments, FileVersions};
+use super::output::ToolOutputStore;
+use super::pins::PinnedFiles;
+use super::scratch::ScratchDir;
+use super::shell::SandboxTrust;
+use super::watcher::FileWatcher;
+
+#[derive(Debug, Clone)]
+pub struct WorkingDirectory(Arc<RwLock<PathBuf>>);
+
+impl WorkingDirectory {
+    #[must_use]
+    pub fn new(path: PathBuf) -> Self {
+        Self(Arc::new(RwLock::new(path)))
+    }
+
+    #[must_use]
+    pub fn current() -> Self {
+        Self::new(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    #[must_use]
+    pub fn get(&self) -> PathBuf {
+        self.0.read().clone()
+    }
+
+    pub fn set(&self, path: PathBuf) {
+        *self.0.write() = path;
+    }
+
+    #[must_use]
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.0.read().join(path)
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ToolContext {
     pub working_dir: PathBuf,
+    pub cwd: WorkingDirectory,
     pub max_output_size: usize,
     pub default_timeout_ms: u64,
+    pub outputs: ToolOutputStore,
+    pub files: FileWatcher,
+    pub versions: FileVersions,
+    pub documents: DocumentAttachments,
+    pub scratch: ScratchDir,
+    pub artifacts: ArtifactStore,
+    pub pins: PinnedFiles,
+    pub sandbox: SandboxTrust,
 }
 
 impl ToolContext {
@@ -12,37 +64,47 @@ impl ToolContext {
         let working_dir = std::env::current_dir().map_err(crate::core::error::AgentError::Io)?;
 
         Ok(Self {
+            cwd: WorkingDirectory::new(working_dir.clone()),
             working_dir,
             max_output_size: 10 * 1024 * 1024,
             default_timeout_ms: 120_000,
+            outputs: ToolOutputStore::new(),
+            files: FileWatcher::new(),
+            versions: FileVersions::new(),
+            documents: DocumentAttachments::new(),
+            scratch: ScratchDir::default(),
+            artifacts: ArtifactStore::new(),
+            pins: PinnedFiles::new(),
+            sandbox: SandboxTrust::default(),
         })
     }
 
     #[must_use]
-    pub const fn with_working_dir(working_dir: PathBuf) -> Self {
+    pub fn with_working_dir(working_dir: PathBuf) -> Self {
         Self {
+            cwd: WorkingDirectory::new(working_dir.clone()),
             working_dir,
             max_output_size: 10 * 1024 * 1024,
             default_timeout_ms: 120_000,
+            outputs: ToolOutputStore::new(),
+            files: FileWatcher::new(),
+            versions: FileVersions::new(),
+            documents: DocumentAttachments::new(),
+            scratch: ScratchDir::default(),
+            artifacts: ArtifactStore::new(),
+            pins: PinnedFiles::new(),
+            sandbox: SandboxTrust::default(),
         }
     }
 
+    #[must_use]
+    pub fn artifacts(&self) -> Vec<Artifact> {
+        self.artifacts.collect(self.scratch.path())
+    }
+
     #[must_use]
     pub fn truncate_output(&self, output: String) -> (String, bool) {
-        if output.len() > self.max_output_size {
-            let truncated = output
-                .chars()
-                .take(self.max_output_size)
-                .collect::<String>();
-            let message = format!(
-                "\n\n[Output truncated: {} bytes total, showing first {} bytes]",
-                output.len(),
-                self.max_output_size
-            );
-            (format!("{truncated}{message}"), true)
-        } else {
-            (output, false)
-        }
+        self.outputs.truncate(output, self.max_output_size)
     }
 }
 
@@ -67,6 +129,22 @@ mod tests {
     fn test_context_with_working_dir() {
         let ctx = ToolContext::with_working_dir(PathBuf::from("/tmp"));
         assert_eq!(ctx.working_dir, PathBuf::from("/tmp"));
+        assert_eq!(ctx.cwd.get(), PathBuf::from("/tmp"));
+    }
+
+    #[test]
+    fn test_cwd_is_shared_between_clones() {
+        let ctx = ToolContext::with_working_dir(PathBuf::from("/tmp"));
+        let tracker = ctx.cwd.clone();
+
+        tracker.set(PathBuf::from("/tmp/project"));
+
+        assert_eq!(ctx.cwd.get(), PathBuf::from("/tmp/project"));
+        assert_eq!(
+            ctx.cwd.resolve(Path::new("src")),
+            PathBuf::from("/tmp/project/src")
+        );
+        assert_eq!(ctx.cwd.resolve(Path::new("/etc")), PathBuf::from("/etc"));
     }
 
     #[test]
@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::config::{AgentPreset, ModelRegistry, ToolsConfig};
+use crate::core::augmented_llm::{AugmentedLLM, LoopConfig};
+use crate::core::error::{AgentError, Result};
+use crate::core::llm::LLM;
+use crate::permission::PermissionManager;
+use crate::providers::create_provider;
+use crate::tools::{ToolEventEmitter, ToolType, TypedTool, WorkingDirectory, configured_tools};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DelegateInput {
+    pub agent: String,
+    pub task: String,
+}
+
+pub struct DelegateTool {
+    presets: BTreeMap<String, AgentPreset>,
+    description: String,
+    llm: Arc<dyn LLM>,
+    tools: ToolsConfig,
+    cwd: WorkingDirectory,
+    aliases: HashMap<String, String>,
+    permission_manager: Option<Arc<PermissionManager>>,
+}
+
+impl DelegateTool {
+    #[must_use]
+    pub fn new(
+        presets: &HashMap<String, AgentPreset>,
+        llm: Arc<dyn LLM>,
+        tools: ToolsConfig,
+        cwd: WorkingDirectory,
+    ) -> Self {
+        let presets: BTreeMap<String, AgentPreset> = presets
+            .iter()
+            .map(|(name, preset)| (name.clone(), preset.clone()))
+            .collect();
+        let mut description = "Hand a self-contained task to a specialised agent and get its final answer back. The agent starts with no knowledge of this conversation, so include everything it needs in the task. Available agents:".to_string();
+        for (name, preset) in &presets {
+            let _ = write!(description, "\n- {name}");
+            if let Some(about) = &preset.description {
+                let _ = write!(description, ": {about}");
+            }
+        }
+        Self {
+            presets,
+            description,
+            llm,
+            tools,
+            cwd,
+            aliases: HashMap::new(),
+            permission_manager: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_aliases(mut self, aliases: HashMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    #[must_use]
+    pub fn with_permission_manager(mut self, manager: Arc<PermissionManager>) -> Self {
+        self.permission_manager = Some(manager);
+        self
+    }
+
+    fn agent_for(&self, preset: &AgentPreset) -> Result<AugmentedLLM> {
+        let llm = match &preset.model {
+            Some(name) => {
+                let registry = ModelRegistry::load();
+                let model = registry.resolve(name, &self.aliases).ok_or_else(|| {
+                    AgentError::Config(format!("Model '{name}' not found in registry"))
+                })?;
+                create_provider(model)?
+            }
+            None => self.llm.clone(),
+        };
+        let mut loop_config = LoopConfig::default();
+        if let Some(max_iterations) = preset.max_iterations {
+            loop_config.max_iterations = max_iterations;
+        }
+
+        let mut agent = AugmentedLLM::with_config(llm, loop_config, ToolEventEmitter::new())?;
+        agent.tool_context().cwd.set(self.cwd.get());
+        let tools = ToolsConfig {
+            allowed: preset.tools.clone(),
+            ..self.tools.clone()
+        };
+        for tool in configured_tools(&tools, agent.tool_context()) {
+            agent.tools_mut().register(tool);
+        }
+        if let Some(manager) = &self.permission_manager {
+            agent.set_permission_manager(manager.clone());
+        }
+        match &preset.system_prompt {
+            Some(prompt) => agent.set_system_prompt(prompt),
+            None => agent.regenerate_system_prompt(),
+        }
+        Ok(agent)
+    }
+}
+
+#[async_trait]
+impl TypedTool for DelegateTool {
+    type Input = DelegateInput;
+
+    fn name(&self) -> &'static str {
+        "delegate"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let preset =
+            self.presets
+                .get(&input.agent)
+                .ok_or_else(|| AgentError::InvalidToolInput {
+                    tool: ToolType::Delegate.name().to_string(),
+                    reason: format!(
+                        "Unknown agent \"{}\"; available: {}",
+                        input.agent,
+                        self.presets.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ),
+                })?;
+        let mut agent = self.agent_for(preset)?;
+        let (message, _usage) = agent.run(input.task, |_| {}).await?;
+        let reply = message.first_text().unwrap_or_default().trim().to_string();
+        if reply.is_empty() {
+            return Ok(format!("Agent {} finished without a reply", input.agent));
+        }
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{Scenario, ScenarioLLM, ScenarioTurn};
+
+    #[tokio::test]
+    async fn test_delegate_runs_preset_with_its_tools() {
+        let preset = AgentPreset {
+            description: Some("Writes docs".to_string()),
+            system_prompt: Some("You write docs.".to_string()),
+            tools: Some(vec!["read_file".to_string(), "glob".to_string()]),
+            ..AgentPreset::default()
+        };
+        let presets = HashMap::from([("docs-writer".to_string(), preset)]);
+        let llm = Arc::new(ScenarioLLM::new(Scenario::new(vec![ScenarioTurn::text(
+            "Docs drafted.",
+        )])));
+        let cwd = WorkingDirectory::new(std::env::temp_dir());
+        let tool = DelegateTool::new(&presets, llm.clone(), ToolsConfig::default(), cwd);
+        assert!(tool.description().contains("- docs-writer: Writes docs"));
+
+        let reply = tool
+            .execute_typed(DelegateInput {
+                agent: "docs-writer".to_string(),
+                task: "Document the config file".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(reply, "Docs drafted.");
+        let request = &llm.request_history()[0];
+        assert_eq!(request.system_prompt.as_deref(), Some("You write docs."));
+        let mut tools: Vec<&str> = request.tools.iter().map(|t| t.name.as_str()).collect();
+        tools.sort_unstable();
+        assert_eq!(tools, vec!["glob", "read_file"]);
+
+        let unknown = tool
+            .execute_typed(DelegateInput {
+                agent: "nobody".to_string(),
+                task: String::new(),
+            })
+            .await;
+        assert!(unknown.is_err());
+    }
+}
@@ -3,12 +3,25 @@ use super::error_hints::{ErrorContext, ErrorHintMatcher};
 use super::events::{ToolEventEmitter, ToolEventHandler};
 use super::registry::ToolRegistry;
 use super::result::ToolResult;
+use super::types::ToolType;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
+
+type FileStamp = (Option<SystemTime>, u64);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
 
 pub struct ToolEngine {
     context: ToolContext,
     events: ToolEventEmitter,
     error_hints: ErrorHintMatcher,
+    turn_reads: Mutex<HashMap<(PathBuf, String), FileStamp>>,
 }
 
 impl ToolEngine {
@@ -18,9 +31,24 @@ impl ToolEngine {
             context,
             events,
             error_hints: ErrorHintMatcher::new(),
+            turn_reads: Mutex::new(HashMap::new()),
         }
     }
 
+    pub fn begin_turn(&self) {
+        self.turn_reads.lock().clear();
+    }
+
+    fn repeated_read(&self, path: &Path, input: &str) -> bool {
+        let key = (path.to_path_buf(), input.to_string());
+        file_stamp(path).is_some_and(|stamp| self.turn_reads.lock().get(&key) == Some(&stamp))
+    }
+
+    #[must_use]
+    pub const fn context(&self) -> &ToolContext {
+        &self.context
+    }
+
     pub fn register_handler(&mut self, handler: Arc<dyn ToolEventHandler>) {
         self.events.add_handler(handler);
     }
@@ -34,8 +62,39 @@ impl ToolEngine {
         let input_str = serde_json::to_string(&input).unwrap_or_else(|_| "{}".to_string());
         self.events.emit_started(tool_name, &input_str);
 
+        let touched_file = match ToolType::from_name(tool_name) {
+            ToolType::ReadFile | ToolType::WriteFile | ToolType::UpdateFile => input
+                .get("path")
+                .and_then(|path| path.as_str())
+                .map(|path| self.context.cwd.resolve(Path::new(path))),
+            _ => None,
+        };
+        let is_read = ToolType::from_name(tool_name) == ToolType::ReadFile;
+
+        if is_read
+            && let Some(path) = &touched_file
+            && self.repeated_read(path, &input_str)
+        {
+            let result = ToolResult::success(format!(
+                "[Deduplicated: {} was already read with the same arguments this turn and has not changed; use the earlier result]",
+                path.display()
+            ));
+            self.events.emit_completed(tool_name, result.clone());
+            return result;
+        }
+
         match registry.execute(tool_name, input).await {
             Ok(output) => {
+                if let Some(path) = touched_file {
+                    self.context.files.track(&path);
+                    self.context.versions.record_from_disk(&path);
+                    let mut reads = self.turn_reads.lock();
+                    if is_read && let Some(stamp) = file_stamp(&path) {
+                        reads.insert((path, input_str), stamp);
+                    } else if !is_read {
+                        reads.retain(|(read, _), _| *read != path);
+                    }
+                }
                 let (final_output, _truncated) = self.context.truncate_output(output);
                 let result = ToolResult::success(final_output);
                 self.events.emit_completed(tool_name, result.clone());
@@ -147,6 +206,34 @@ mod tests {
         assert!(llm_output.contains("file path") || llm_output.contains("location"));
     }
 
+    #[tokio::test]
+    async fn test_engine_deduplicates_reads_within_turn() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("notes.txt");
+        std::fs::write(&path, "first\n").unwrap();
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(crate::tools::ReadFileTool::new()));
+        let engine = ToolEngine::new(
+            ToolContext::with_working_dir(temp.path().to_path_buf()),
+            ToolEventEmitter::new(),
+        );
+        let input = serde_json::json!({"path": path.display().to_string()});
+
+        let first = engine.execute(&registry, "read_file", input.clone()).await;
+        assert!(first.output().unwrap().contains("first"));
+        let repeated = engine.execute(&registry, "read_file", input.clone()).await;
+        assert!(repeated.output().unwrap().starts_with("[Deduplicated"));
+
+        std::fs::write(&path, "second, longer\n").unwrap();
+        let changed = engine.execute(&registry, "read_file", input.clone()).await;
+        assert!(changed.output().unwrap().contains("second"));
+
+        engine.begin_turn();
+        let next_turn = engine.execute(&registry, "read_file", input).await;
+        assert!(next_turn.output().unwrap().contains("second"));
+    }
+
     #[tokio::test]
     async fn test_engine_truncation() {
         struct LargeOutputTool;
@@ -1,11 +1,21 @@
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
+use tracing::Instrument;
 
+use crate::audit::{AuditEvent, AuditLog};
 use crate::core::error::Result;
+use crate::core::observer::AgentObserver;
+use crate::core::run_state::RunState;
 use crate::core::types::{ContentBlock, Message, Role};
+use crate::permission::security::{CloudAccess, cloud_access};
 use crate::permission::{
-    PermissionCheckResult, PermissionManager, PermissionRequest, PermissionType,
+    PermissionCheckResult, PermissionManager, PermissionRequest, PermissionType, ProposedChange,
 };
+use crate::telemetry;
+use crate::tools::fs::{match_line_endings, read_text};
 use crate::tools::{ToolEngine, ToolRegistry, ToolType};
 
 pub struct ToolExecutor<'a> {
@@ -13,6 +23,79 @@ pub struct ToolExecutor<'a> {
     permission_manager: Option<&'a Arc<PermissionManager>>,
     engine: &'a ToolEngine,
     parallel_lock: Arc<RwLock<()>>,
+    observers: &'a [Arc<dyn AgentObserver>],
+    audit: Option<&'a AuditLog>,
+}
+
+const MAX_PREVIEW_BYTES: u64 = 1024 * 1024;
+
+enum Approval {
+    Granted,
+    Edited(String),
+    Denied(String),
+}
+
+impl From<PermissionCheckResult> for Approval {
+    fn from(result: PermissionCheckResult) -> Self {
+        match result {
+            PermissionCheckResult::Allowed => Self::Granted,
+            PermissionCheckResult::AllowedEdited(target) => Self::Edited(target),
+            PermissionCheckResult::DeniedWithFeedback(feedback) => Self::Denied(feedback),
+        }
+    }
+}
+
+fn edited_input(
+    tool_type: &ToolType,
+    tool_input: &serde_json::Value,
+    target: &str,
+) -> (serde_json::Value, String) {
+    let (field, noun) = match tool_type {
+        ToolType::Bash => ("command", "command"),
+        _ => ("path", "path"),
+    };
+    let mut input = tool_input.clone();
+    input[field] = serde_json::Value::String(target.to_string());
+    let note = format!("The user edited the {noun} before approving. What actually ran: {target}");
+    (input, note)
+}
+
+fn proposed_change(
+    tool_type: &ToolType,
+    tool_input: &serde_json::Value,
+    working_dir: &Path,
+) -> Option<ProposedChange> {
+    let field = |name: &str| tool_input.get(name).and_then(|v| v.as_str());
+    let path = working_dir.join(field("path")?);
+    let old_content = match std::fs::metadata(&path) {
+        Ok(meta) if meta.len() > MAX_PREVIEW_BYTES => return None,
+        Ok(_) => read_text(&path).ok()?.0,
+        Err(_) => String::new(),
+    };
+    let new_content = match tool_type {
+        ToolType::WriteFile => field("content")?.to_string(),
+        ToolType::UpdateFile => {
+            let (old, new) =
+                match_line_endings(&old_content, field("old_string")?, field("new_string")?);
+            if !old_content.contains(old.as_ref()) {
+                return None;
+            }
+            let replace_all = tool_input
+                .get("replace_all")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            if replace_all {
+                old_content.replace(old.as_ref(), &new)
+            } else {
+                old_content.replacen(old.as_ref(), &new, 1)
+            }
+        }
+        _ => return None,
+    };
+    Some(ProposedChange {
+        old_content,
+        new_content,
+    })
 }
 
 impl<'a> ToolExecutor<'a> {
@@ -27,16 +110,30 @@ impl<'a> ToolExecutor<'a> {
             permission_manager,
             engine,
             parallel_lock: Arc::new(RwLock::new(())),
+            observers: &[],
+            audit: None,
         }
     }
 
+    #[must_use]
+    pub const fn with_observers(mut self, observers: &'a [Arc<dyn AgentObserver>]) -> Self {
+        self.observers = observers;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_audit_log(mut self, audit: Option<&'a AuditLog>) -> Self {
+        self.audit = audit;
+        self
+    }
+
     fn check_permission(
         &self,
         tool_type: &ToolType,
         tool_input: &serde_json::Value,
-    ) -> Result<Option<String>> {
+    ) -> Result<Approval> {
         let Some(manager) = self.permission_manager.as_ref() else {
-            return Ok(None);
+            return Ok(Approval::Granted);
         };
 
         let (perm_type, target) = match tool_type {
@@ -45,40 +142,269 @@ impl<'a> ToolExecutor<'a> {
                     .get("command")
                     .and_then(|v| v.as_str())
                     .unwrap_or("unknown command");
-                (PermissionType::CommandExecute, command.to_string())
+                let unsandboxed = tool_input
+                    .get("unsandboxed")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                if unsandboxed {
+                    let request =
+                        PermissionRequest::new(PermissionType::SystemModification, command)
+                            .with_context(
+                                "Run outside the sandbox with host filesystem and network access",
+                            )
+                            .editable();
+                    return self.request_permission(manager, &request);
+                }
+                self.check_sandbox_trust(manager)?;
+                let perm_type = match cloud_access(command) {
+                    Some(CloudAccess::Read) => PermissionType::CloudRead,
+                    Some(CloudAccess::Mutate) => PermissionType::CloudMutate,
+                    None => PermissionType::CommandExecute,
+                };
+                (perm_type, command.to_string())
             }
             ToolType::WriteFile | ToolType::UpdateFile => {
-                let path = tool_input
-                    .get("path")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown path");
-                (PermissionType::FileWrite, path.to_string())
+                let request = self.file_write_request(tool_type, tool_input);
+                return self.request_permission(manager, &request);
             }
-            ToolType::ReadFile | ToolType::ListDir | ToolType::Glob | ToolType::Grep => {
+            ToolType::ReadFile
+            | ToolType::ReadDocument
+            | ToolType::PreviewData
+            | ToolType::ListDir
+            | ToolType::Glob
+            | ToolType::Grep => {
                 let path = tool_input
                     .get("path")
                     .and_then(|v| v.as_str())
                     .unwrap_or(".");
                 (PermissionType::FileRead, path.to_string())
             }
+            ToolType::GetIssue | ToolType::ListPrComments => {
+                let number = tool_input
+                    .get("number")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or_default();
+                (PermissionType::NetworkAccess, format!("GitHub #{number}"))
+            }
+            ToolType::SaveMemory => {
+                let memory = tool_input
+                    .get("memory")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                let request = PermissionRequest::new(PermissionType::MemoryWrite, memory)
+                    .with_context("Remember this in every future session");
+                return self.request_permission(manager, &request);
+            }
+            ToolType::SqlQuery => return self.check_sql_permission(manager, tool_input),
+            ToolType::CreatePr => {
+                let title = tool_input
+                    .get("title")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("untitled");
+                let request = PermissionRequest::new(
+                    PermissionType::NetworkAccess,
+                    format!("open pull request \"{title}\""),
+                )
+                .with_context("Create a pull request on GitHub");
+                return self.request_permission(manager, &request);
+            }
             _ => {
-                return Ok(None);
+                return Ok(Approval::Granted);
             }
         };
 
-        let request = PermissionRequest::new(perm_type, target).with_context(format!(
+        let mut request = PermissionRequest::new(perm_type, target).with_context(format!(
             "Tool '{}' requested by AI assistant",
             tool_type.name()
         ));
+        if *tool_type == ToolType::Bash {
+            request = request.editable();
+        }
+        self.request_permission(manager, &request)
+    }
 
-        match manager.check_permission(&request)? {
-            PermissionCheckResult::Allowed => Ok(None),
-            PermissionCheckResult::DeniedWithFeedback(feedback) => Ok(Some(feedback)),
+    fn check_sandbox_trust(&self, manager: &PermissionManager) -> Result<()> {
+        let trust = &self.engine.context().sandbox;
+        let Some(sandbox) = trust.pending() else {
+            return Ok(());
+        };
+        let request = PermissionRequest::new(
+            PermissionType::SystemModification,
+            format!("{} ({})", sandbox.path.display(), sandbox.fingerprint),
+        )
+        .with_context(format!(
+            "Trust this project's sandbox settings: {}",
+            sandbox.changes.join(", ")
+        ));
+        let approval = self.request_permission(manager, &request)?;
+        trust.resolve(matches!(approval, Approval::Granted));
+        Ok(())
+    }
+
+    fn file_write_request(
+        &self,
+        tool_type: &ToolType,
+        tool_input: &serde_json::Value,
+    ) -> PermissionRequest {
+        let path = tool_input
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown path");
+        let change = proposed_change(tool_type, tool_input, &self.engine.context().working_dir);
+        PermissionRequest::new(PermissionType::FileWrite, path)
+            .with_context(format!(
+                "Tool '{}' requested by AI assistant",
+                tool_type.name()
+            ))
+            .with_change(change)
+            .editable()
+    }
+
+    fn check_file_writes(&self, assistant_message: &Message) -> HashMap<String, Result<Approval>> {
+        let Some(manager) = self.permission_manager.as_ref() else {
+            return HashMap::new();
+        };
+        let (ids, requests): (Vec<&str>, Vec<PermissionRequest>) = assistant_message
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse {
+                    id, name, input, ..
+                } => {
+                    let tool_type = ToolType::from_name(name);
+                    matches!(tool_type, ToolType::WriteFile | ToolType::UpdateFile)
+                        .then(|| (id.as_str(), self.file_write_request(&tool_type, input)))
+                }
+                _ => None,
+            })
+            .unzip();
+        if requests.len() < 2 {
+            return HashMap::new();
+        }
+
+        for request in &requests {
+            for observer in self.observers {
+                observer.on_permission_request(request);
+            }
+        }
+        let prompting = requests
+            .iter()
+            .any(|request| manager.requires_prompt(request));
+        if prompting {
+            self.notify_state(RunState::AwaitingPermission);
+        }
+        let approvals = ids
+            .into_iter()
+            .map(String::from)
+            .zip(
+                manager
+                    .check_batch(&requests)
+                    .into_iter()
+                    .map(|result| result.map(Approval::from)),
+            )
+            .collect();
+        if prompting {
+            self.notify_state(RunState::ExecutingTools);
+        }
+        approvals
+    }
+
+    fn check_sql_permission(
+        &self,
+        manager: &PermissionManager,
+        tool_input: &serde_json::Value,
+    ) -> Result<Approval> {
+        let query = tool_input
+            .get("query")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if crate::tools::sql::is_read_query(query) {
+            return Ok(Approval::Granted);
+        }
+        let connection = tool_input
+            .get("connection")
+            .and_then(|v| v.as_str())
+            .unwrap_or("default connection");
+        let request = PermissionRequest::new(PermissionType::DatabaseWrite, query)
+            .with_context(format!("Modify data through {connection}"));
+        self.request_permission(manager, &request)
+    }
+
+    fn request_permission(
+        &self,
+        manager: &PermissionManager,
+        request: &PermissionRequest,
+    ) -> Result<Approval> {
+        for observer in self.observers {
+            observer.on_permission_request(request);
+        }
+
+        let prompting = manager.requires_prompt(request);
+        if prompting {
+            self.notify_state(RunState::AwaitingPermission);
+        }
+        let approval = manager.check_permission(request).map(Approval::from);
+        if prompting {
+            self.notify_state(RunState::ExecutingTools);
+        }
+        approval
+    }
+
+    fn notify_state(&self, state: RunState) {
+        for observer in self.observers {
+            observer.on_state_change(state);
+        }
+    }
+
+    async fn run_tool(
+        &self,
+        id: &str,
+        tool_type: &ToolType,
+        name: &str,
+        input: &serde_json::Value,
+        note: Option<String>,
+    ) -> ContentBlock {
+        for observer in self.observers {
+            observer.on_tool_start(name, input);
+        }
+
+        let execution = self
+            .engine
+            .execute(self.tools, name, input.clone())
+            .instrument(tracing::info_span!("tool.execute", tool = name));
+        let started = Instant::now();
+        let result = if tool_type.is_read_only() {
+            let _read_guard = self.parallel_lock.read().await;
+            execution.await
+        } else {
+            let _write_guard = self.parallel_lock.write().await;
+            execution.await
+        };
+        telemetry::record_tool_execution(name, started.elapsed(), result.is_success());
+        if let Some(audit) = self.audit
+            && let Some(event) = AuditEvent::for_tool(tool_type, input, &result)
+        {
+            audit.record(event);
+        }
+
+        for observer in self.observers {
+            observer.on_tool_end(name, &result);
+        }
+
+        let output = note.map_or_else(
+            || result.to_llm_string(),
+            |note| format!("{note}\n\n{}", result.to_llm_string()),
+        );
+        if result.is_success() {
+            ContentBlock::tool_result(id, output)
+        } else {
+            ContentBlock::tool_error(id, output)
         }
     }
 
     pub async fn execute_tools(&self, assistant_message: &Message) -> Vec<Message> {
         let mut results = Vec::new();
+        let mut batched = self.check_file_writes(assistant_message);
 
         for content_block in &assistant_message.content {
             if let ContentBlock::ToolUse {
@@ -87,8 +413,11 @@ impl<'a> ToolExecutor<'a> {
             {
                 let tool_type = ToolType::from_name(name);
 
-                let permission_denial = match self.check_permission(&tool_type, input) {
-                    Ok(denial_feedback) => denial_feedback,
+                let approval = batched
+                    .remove(id)
+                    .unwrap_or_else(|| self.check_permission(&tool_type, input));
+                let approval = match approval {
+                    Ok(approval) => approval,
                     Err(e) => {
                         let error_msg = format!("Permission check failed: {e}");
                         results.push(Message::new(
@@ -99,30 +428,83 @@ impl<'a> ToolExecutor<'a> {
                     }
                 };
 
-                let tool_result = if let Some(user_feedback) = permission_denial {
-                    let error_message =
-                        format!("Operation blocked by user. User feedback: {user_feedback}");
-                    ContentBlock::tool_error(id, error_message)
-                } else {
-                    let result = if tool_type.is_read_only() {
-                        let _read_guard = self.parallel_lock.read().await;
-                        self.engine.execute(self.tools, name, input.clone()).await
-                    } else {
-                        let _write_guard = self.parallel_lock.write().await;
-                        self.engine.execute(self.tools, name, input.clone()).await
-                    };
-
-                    if result.is_success() {
-                        ContentBlock::tool_result(id, result.to_llm_string())
-                    } else {
-                        ContentBlock::tool_error(id, result.to_llm_string())
+                let tool_result = match approval {
+                    Approval::Denied(user_feedback) => {
+                        let error_message =
+                            format!("Operation blocked by user. User feedback: {user_feedback}");
+                        ContentBlock::tool_error(id, error_message)
+                    }
+                    Approval::Granted => self.run_tool(id, &tool_type, name, input, None).await,
+                    Approval::Edited(target) => {
+                        let (input, note) = edited_input(&tool_type, input, &target);
+                        self.run_tool(id, &tool_type, name, &input, Some(note))
+                            .await
                     }
                 };
 
-                results.push(Message::new(Role::Tool, vec![tool_result]));
+                let mut blocks = vec![tool_result];
+                blocks.extend(self.engine.context().documents.take());
+                results.push(Message::new(Role::Tool, blocks));
             }
         }
 
         results
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_edited_input_replaces_command_or_path() {
+        let (input, note) = edited_input(
+            &ToolType::Bash,
+            &json!({"command": "rm -rf build", "timeout": 5}),
+            "rm -rf build/tmp",
+        );
+        assert_eq!(input, json!({"command": "rm -rf build/tmp", "timeout": 5}));
+        assert!(note.ends_with("What actually ran: rm -rf build/tmp"));
+
+        let (input, _) = edited_input(
+            &ToolType::WriteFile,
+            &json!({"path": "a.txt", "content": "x"}),
+            "b.txt",
+        );
+        assert_eq!(input["path"], "b.txt");
+    }
+
+    #[test]
+    fn test_proposed_change_for_file_edits() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.txt"), "one\ntwo\n").unwrap();
+
+        let update = proposed_change(
+            &ToolType::UpdateFile,
+            &json!({"path": "a.txt", "old_string": "two", "new_string": "2"}),
+            temp.path(),
+        )
+        .unwrap();
+        assert_eq!(update.old_content, "one\ntwo\n");
+        assert_eq!(update.new_content, "one\n2\n");
+
+        let create = proposed_change(
+            &ToolType::WriteFile,
+            &json!({"path": "new.txt", "content": "hi\n"}),
+            temp.path(),
+        )
+        .unwrap();
+        assert!(create.old_content.is_empty());
+        assert_eq!(create.new_content, "hi\n");
+
+        assert!(
+            proposed_change(
+                &ToolType::UpdateFile,
+                &json!({"path": "a.txt", "old_string": "missing", "new_string": "x"}),
+                temp.path(),
+            )
+            .is_none()
+        );
+    }
+}
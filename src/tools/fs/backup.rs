@@ -0,0 +1,141 @@
+use chrono::{NaiveDateTime, Utc};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::BackupConfig;
+use crate::core::error::Result;
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+const BACKUP_EXTENSION: &str = "bak";
+
+#[derive(Debug, Clone)]
+pub struct BackupStore {
+    config: BackupConfig,
+    root: PathBuf,
+}
+
+impl BackupStore {
+    #[must_use]
+    pub const fn new(config: BackupConfig, root: PathBuf) -> Self {
+        Self { config, root }
+    }
+
+    fn mirrored_path(&self, path: &Path) -> PathBuf {
+        let relative = path.strip_prefix(&self.root).map_or_else(
+            |_| {
+                path.components()
+                    .filter(|c| matches!(c, Component::Normal(_)))
+                    .collect()
+            },
+            Path::to_path_buf,
+        );
+        self.root.join(&self.config.dir).join(relative)
+    }
+
+    pub fn backup(&self, path: &Path) -> Result<Option<PathBuf>> {
+        if !self.config.enabled || !path.is_file() {
+            return Ok(None);
+        }
+
+        let mirrored = self.mirrored_path(path);
+        let (Some(dir), Some(name)) = (mirrored.parent(), mirrored.file_name()) else {
+            return Ok(None);
+        };
+        let name = name.to_string_lossy();
+        fs::create_dir_all(dir)?;
+
+        let stamp = Utc::now().format(TIMESTAMP_FORMAT);
+        let backup = dir.join(format!("{name}.{stamp}.{BACKUP_EXTENSION}"));
+        fs::copy(path, &backup)?;
+
+        self.prune(dir, &name);
+        Ok(Some(backup))
+    }
+
+    fn prune(&self, dir: &Path, name: &str) {
+        if self.config.retention == 0 {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut backups: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|n| n.strip_prefix(name)?.strip_prefix('.'))
+                    .and_then(|n| n.strip_suffix(BACKUP_EXTENSION)?.strip_suffix('.'))
+                    .is_some_and(|stamp| {
+                        NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).is_ok()
+                    })
+            })
+            .collect();
+
+        backups.sort();
+        let excess = backups.len().saturating_sub(self.config.retention);
+        for old in &backups[..excess] {
+            let _ = fs::remove_file(old);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(root: &Path, retention: usize) -> BackupStore {
+        BackupStore::new(
+            BackupConfig {
+                enabled: true,
+                retention,
+                ..BackupConfig::default()
+            },
+            root.to_path_buf(),
+        )
+    }
+
+    #[test]
+    fn test_backup_mirrors_project_layout() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("src/main.rs");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let backup = store(temp.path(), 5).backup(&file).unwrap().unwrap();
+
+        assert!(backup.starts_with(temp.path().join(".smith/backups/src")));
+        assert_eq!(fs::read_to_string(backup).unwrap(), "fn main() {}");
+    }
+
+    #[test]
+    fn test_retention_prunes_oldest() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("a.txt");
+        fs::write(&file, "v").unwrap();
+        let dir = temp.path().join(".smith/backups");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt.20200101T000000.000Z.bak"), "old").unwrap();
+        fs::write(dir.join("a.txt.20200102T000000.000Z.bak"), "older").unwrap();
+        fs::write(dir.join("a.txt.notes.bak"), "unrelated").unwrap();
+
+        store(temp.path(), 2).backup(&file).unwrap();
+
+        assert!(!dir.join("a.txt.20200101T000000.000Z.bak").exists());
+        assert!(dir.join("a.txt.20200102T000000.000Z.bak").exists());
+        assert!(dir.join("a.txt.notes.bak").exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_disabled_store_is_noop() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("a.txt");
+        fs::write(&file, "v").unwrap();
+        let store = BackupStore::new(BackupConfig::default(), temp.path().to_path_buf());
+
+        assert!(store.backup(&file).unwrap().is_none());
+        assert!(!temp.path().join(".smith").exists());
+    }
+}
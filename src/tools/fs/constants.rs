@@ -1,17 +1,24 @@
+use std::time::Duration;
+
 pub const GLOB_DEFAULT_LIMIT: usize = 100;
 pub const GLOB_MAX_LIMIT: usize = 1000;
 
 pub const GREP_DEFAULT_LIMIT: usize = 50;
 pub const GREP_MAX_LIMIT: usize = 500;
 pub const GREP_MAX_CONTEXT: usize = 5;
+pub const GREP_MAX_THREADS: usize = 8;
+pub const GREP_TIME_BUDGET: Duration = Duration::from_secs(20);
 
 pub const READ_DEFAULT_OFFSET: usize = 1;
 pub const READ_DEFAULT_LIMIT: usize = 2000;
 pub const READ_MAX_LIMIT: usize = 10_000;
 pub const READ_MAX_LINE_LENGTH: usize = 500;
-pub const READ_BINARY_CHECK_SIZE: usize = 8192;
+
+pub const PREVIEW_DEFAULT_ROWS: usize = 10;
+pub const PREVIEW_MAX_ROWS: usize = 100;
 
 pub const LIST_MAX_DEPTH: usize = 5;
+pub const TREE_DEFAULT_DEPTH: usize = 1;
 
 pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 pub const MAX_WRITE_SIZE: usize = 10 * 1024 * 1024;
@@ -0,0 +1,339 @@
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::core::error::{AgentError, Result};
+use crate::core::types::ContentBlock;
+use crate::tools::{ToolType, TypedTool};
+
+use super::{
+    FilePolicy, validate_absolute_path, validate_file_size, validate_is_file, validate_not_ignored,
+    validate_path_exists,
+};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReadDocumentInput {
+    pub path: String,
+    #[serde(default)]
+    pub first_page: Option<usize>,
+    #[serde(default)]
+    pub last_page: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DocumentAttachments(Arc<Mutex<Vec<ContentBlock>>>);
+
+impl DocumentAttachments {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, block: ContentBlock) {
+        self.0.lock().push(block);
+    }
+
+    #[must_use]
+    pub fn take(&self) -> Vec<ContentBlock> {
+        std::mem::take(&mut *self.0.lock())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    Pdf,
+    Docx,
+}
+
+impl DocumentKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "pdf" => Some(Self::Pdf),
+            "docx" => Some(Self::Docx),
+            _ => None,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Pdf => "PDF",
+            Self::Docx => "DOCX",
+        }
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> AgentError {
+    AgentError::InvalidToolInput {
+        tool: ToolType::ReadDocument.name().to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn extract_pages(kind: DocumentKind, bytes: Vec<u8>) -> Result<Vec<String>> {
+    match kind {
+        DocumentKind::Pdf => pdf_extract::extract_text_from_mem_by_pages(&bytes)
+            .map_err(|e| invalid(format!("Could not extract text from PDF: {e}"))),
+        DocumentKind::Docx => {
+            let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+                .map_err(|e| invalid(format!("Not a valid docx archive: {e}")))?;
+            let mut xml = String::new();
+            archive
+                .by_name("word/document.xml")
+                .map_err(|e| invalid(format!("Missing word/document.xml: {e}")))?
+                .read_to_string(&mut xml)?;
+            Ok(docx_pages(&xml))
+        }
+    }
+}
+
+fn docx_pages(xml: &str) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut in_text = false;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if in_text {
+            page.push_str(&unescape_xml(&rest[..start]));
+        }
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        match name {
+            "w:t" => in_text = !closing && !tag.ends_with('/'),
+            "w:tab" if tag == "w:tab/" => page.push('\t'),
+            "w:br" if tag.contains("w:type=\"page\"") => pages.push(std::mem::take(&mut page)),
+            "w:br" | "w:cr" => page.push('\n'),
+            "w:p" if closing || tag.ends_with('/') => page.push('\n'),
+            _ => {}
+        }
+    }
+    pages.push(page);
+    pages
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn format_pages(
+    path: &Path,
+    kind: DocumentKind,
+    pages: &[String],
+    first: usize,
+    last: usize,
+) -> String {
+    let mut output = format!(
+        "Document: {} ({}, {} pages)\nPages {first}-{last} of {}\n",
+        path.display(),
+        kind.label(),
+        pages.len(),
+        pages.len()
+    );
+    for (index, text) in pages.iter().enumerate().take(last).skip(first - 1) {
+        let _ = write!(output, "\n--- Page {} ---\n{}\n", index + 1, text.trim());
+    }
+    output
+}
+
+#[derive(Default)]
+pub struct ReadDocumentTool {
+    attachments: DocumentAttachments,
+    policy: FilePolicy,
+}
+
+impl ReadDocumentTool {
+    #[must_use]
+    pub fn new(attachments: DocumentAttachments) -> Self {
+        Self {
+            attachments,
+            policy: FilePolicy::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_file_policy(mut self, policy: FilePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn attach_raw(&self, path: &Path, bytes: &[u8], reason: &str) -> String {
+        let title = path.file_name().map_or_else(
+            || "document.pdf".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        self.attachments.push(ContentBlock::document_base64(
+            "application/pdf",
+            STANDARD.encode(bytes),
+            title,
+        ));
+        format!(
+            "Document: {} (PDF)\n{reason}. The original file is attached to this result for models that accept document input; if you cannot see it, ask the user for a text version.",
+            path.display()
+        )
+    }
+}
+
+#[async_trait]
+impl TypedTool for ReadDocumentTool {
+    type Input = ReadDocumentInput;
+
+    fn name(&self) -> &'static str {
+        "read_document"
+    }
+
+    fn description(&self) -> &'static str {
+        "Extract the text of a PDF or .docx file, with \"--- Page N ---\" markers. Use first_page/last_page to read part of a long document. The path must be absolute."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let path = validate_absolute_path(&input.path, &ToolType::ReadDocument)?;
+        validate_path_exists(&path, &ToolType::ReadDocument)?;
+        validate_is_file(&path, &ToolType::ReadDocument)?;
+        validate_not_ignored(&path, &ToolType::ReadDocument)?;
+        self.policy.check(&path, &ToolType::ReadDocument)?;
+        validate_file_size(&path, &ToolType::ReadDocument)?;
+        let kind = DocumentKind::from_path(&path).ok_or_else(|| {
+            invalid(format!(
+                "Unsupported document type: {}. read_document handles .pdf and .docx; use read_file for text files.",
+                path.display()
+            ))
+        })?;
+
+        let bytes = tokio::fs::read(&path).await?;
+        let extraction = {
+            let bytes = bytes.clone();
+            tokio::task::spawn_blocking(move || extract_pages(kind, bytes))
+                .await
+                .unwrap_or_else(|e| Err(invalid(format!("Document extraction failed: {e}"))))
+        };
+
+        let pages = match extraction {
+            Ok(pages) => pages,
+            Err(e) if kind == DocumentKind::Pdf => {
+                return Ok(self.attach_raw(&path, &bytes, &e.to_string()));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let total = pages.len();
+        let first = input.first_page.unwrap_or(1).max(1);
+        let last = input.last_page.unwrap_or(total).min(total);
+        if first > last {
+            return Err(invalid(format!(
+                "Page range {first}-{last} is outside the document ({total} pages)"
+            )));
+        }
+
+        let selected = &pages[first - 1..last];
+        if selected.iter().all(|page| page.trim().is_empty()) {
+            if kind == DocumentKind::Pdf {
+                return Ok(self.attach_raw(
+                    &path,
+                    &bytes,
+                    "No extractable text layer (possibly a scanned PDF)",
+                ));
+            }
+            return Err(invalid(format!("No text found in {}", path.display())));
+        }
+
+        Ok(format_pages(&path, kind, &pages, first, last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_docx(path: &Path, body: &str) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        zip.start_file("word/document.xml", SimpleFileOptions::default())
+            .unwrap();
+        write!(
+            zip,
+            r#"<?xml version="1.0"?><w:document><w:body>{body}</w:body></w:document>"#
+        )
+        .unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_docx_pages() {
+        let xml = r#"<w:p><w:pPr><w:tabs><w:tab w:val="left"/></w:tabs></w:pPr><w:r><w:t>Title &amp; intro</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">a</w:t><w:tab/><w:t>b</w:t><w:br w:type="page"/><w:t>next</w:t></w:r></w:p>"#;
+        assert_eq!(docx_pages(xml), vec!["Title & intro\na\tb", "next\n"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_docx_with_page_range() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("spec.docx");
+        write_docx(
+            &path,
+            r#"<w:p><w:r><w:t>Overview</w:t></w:r></w:p><w:p><w:r><w:br w:type="page"/><w:t>Details</w:t></w:r></w:p>"#,
+        );
+
+        let attachments = DocumentAttachments::new();
+        let tool = ReadDocumentTool::new(attachments.clone());
+        let output = tool
+            .execute_typed(ReadDocumentInput {
+                path: path.display().to_string(),
+                first_page: Some(2),
+                last_page: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(output.contains("(DOCX, 2 pages)\nPages 2-2 of 2"));
+        assert!(output.contains("--- Page 2 ---\nDetails"));
+        assert!(!output.contains("Overview"));
+        assert!(attachments.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_pdf_is_attached_raw() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("scan.pdf");
+        std::fs::write(&path, b"%PDF-1.4 not really a pdf").unwrap();
+
+        let attachments = DocumentAttachments::new();
+        let tool = ReadDocumentTool::new(attachments.clone());
+        let output = tool
+            .execute_typed(ReadDocumentInput {
+                path: path.display().to_string(),
+                first_page: None,
+                last_page: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(output.contains("original file is attached"));
+        let blocks = attachments.take();
+        assert!(matches!(
+            blocks.as_slice(),
+            [ContentBlock::Document { title: Some(title), .. }] if title == "scan.pdf"
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use content_inspector::ContentType;
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::core::error::{AgentError, Result};
+use crate::tools::types::ToolType;
+
+use super::atomic_write;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const MAX_CONTROL_RATIO: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl TextEncoding {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 with BOM",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Latin1 => "Latin-1",
+        }
+    }
+
+    #[must_use]
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(text.as_bytes().to_vec()),
+            Self::Utf8Bom => Some([UTF8_BOM, text.as_bytes()].concat()),
+            Self::Utf16Le => Some(
+                [0xFF, 0xFE]
+                    .into_iter()
+                    .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                    .collect(),
+            ),
+            Self::Utf16Be => Some(
+                [0xFE, 0xFF]
+                    .into_iter()
+                    .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+                    .collect(),
+            ),
+            Self::Latin1 => text.chars().map(|c| u8::try_from(c).ok()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        if crlf > 0 && crlf * 2 >= text.matches('\n').count() {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+
+    #[must_use]
+    pub fn apply(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Self::CrLf if text.contains('\n') => {
+                Cow::Owned(text.replace("\r\n", "\n").replace('\n', "\r\n"))
+            }
+            _ => Cow::Borrowed(text),
+        }
+    }
+}
+
+#[must_use]
+pub fn match_line_endings<'a>(
+    content: &str,
+    old: &'a str,
+    new: &'a str,
+) -> (Cow<'a, str>, Cow<'a, str>) {
+    let line_ending = LineEnding::detect(content);
+    let old = if content.contains(old) {
+        Cow::Borrowed(old)
+    } else {
+        line_ending.apply(old)
+    };
+    (old, line_ending.apply(new))
+}
+
+#[must_use]
+pub fn format_label(encoding: TextEncoding, line_ending: LineEnding) -> Option<String> {
+    let mut parts = Vec::new();
+    if line_ending == LineEnding::CrLf {
+        parts.push("CRLF");
+    }
+    if encoding != TextEncoding::Utf8 {
+        parts.push(encoding.label());
+    }
+    (!parts.is_empty()).then(|| parts.join(" \u{b7} "))
+}
+
+#[must_use]
+pub fn decode(bytes: &[u8]) -> Option<(String, TextEncoding)> {
+    match content_inspector::inspect(bytes) {
+        ContentType::UTF_8 => std::str::from_utf8(bytes).map_or_else(
+            |_| decode_latin1(bytes).map(|text| (text, TextEncoding::Latin1)),
+            |text| Some((text.to_string(), TextEncoding::Utf8)),
+        ),
+        ContentType::UTF_8_BOM => Some((
+            String::from_utf8_lossy(&bytes[UTF8_BOM.len()..]).into_owned(),
+            TextEncoding::Utf8Bom,
+        )),
+        ContentType::UTF_16LE => Some((
+            decode_utf16(&bytes[2..], u16::from_le_bytes),
+            TextEncoding::Utf16Le,
+        )),
+        ContentType::UTF_16BE => Some((
+            decode_utf16(&bytes[2..], u16::from_be_bytes),
+            TextEncoding::Utf16Be,
+        )),
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn decode_line(bytes: &[u8]) -> String {
+    std::str::from_utf8(bytes).map_or_else(
+        |_| bytes.iter().copied().map(char::from).collect(),
+        ToString::to_string,
+    )
+}
+
+fn decode_latin1(bytes: &[u8]) -> Option<String> {
+    let controls = bytes
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0C))
+        .count();
+    (controls * MAX_CONTROL_RATIO <= bytes.len())
+        .then(|| bytes.iter().copied().map(char::from).collect())
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+pub fn read_text(path: &Path) -> Result<(String, TextEncoding)> {
+    let bytes = std::fs::read(path)?;
+    decode(&bytes).ok_or_else(|| AgentError::BinaryFile {
+        path: path.display().to_string(),
+    })
+}
+
+pub fn write_text(
+    path: &Path,
+    text: &str,
+    encoding: TextEncoding,
+    tool_type: &ToolType,
+) -> Result<()> {
+    let bytes = encoding
+        .encode(text)
+        .ok_or_else(|| AgentError::InvalidToolInput {
+            tool: tool_type.name().to_string(),
+            reason: format!(
+                "{} is {} encoded and the new content has characters it cannot represent",
+                path.display(),
+                encoding.label()
+            ),
+        })?;
+    atomic_write(path, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_and_encode_round_trip() {
+        for (text, encoding) in [
+            ("plain\n", TextEncoding::Utf8),
+            ("caf\u{e9}\n", TextEncoding::Utf8Bom),
+            ("\u{65e5}\u{672c}\n", TextEncoding::Utf16Le),
+            ("na\u{ef}ve\n", TextEncoding::Utf16Be),
+            ("d\u{e9}j\u{e0} vu\n", TextEncoding::Latin1),
+        ] {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(decode(&bytes), Some((text.to_string(), encoding)));
+        }
+
+        assert_eq!(TextEncoding::Latin1.encode("\u{65e5}"), None);
+        assert_eq!(decode(&[0x7F, 0x45, 0x4C, 0x46, 0, 0, 1, 2]), None);
+        assert_eq!(decode(&[0x01, 0x02, 0x03, 0xFF, 0x04, 0x05]), None);
+        assert_eq!(decode_line(b"caf\xe9"), "caf\u{e9}");
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let content = "fn main() {\r\n    run();\r\n}\r\n";
+        assert_eq!(LineEnding::detect(content), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\nb\r\nc\n"), LineEnding::Lf);
+
+        let (old, new) =
+            match_line_endings(content, "{\n    run();", "{\n    setup();\n    run();");
+        assert_eq!(old, "{\r\n    run();");
+        assert_eq!(new, "{\r\n    setup();\r\n    run();");
+        assert!(matches!(
+            match_line_endings(content, "run();", "go();").0,
+            Cow::Borrowed(_)
+        ));
+
+        assert_eq!(format_label(TextEncoding::Utf8, LineEnding::Lf), None);
+        assert_eq!(
+            format_label(TextEncoding::Utf8Bom, LineEnding::CrLf).as_deref(),
+            Some("CRLF \u{b7} UTF-8 with BOM")
+        );
+    }
+}
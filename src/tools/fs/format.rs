@@ -1,5 +1,6 @@
 use std::time::SystemTime;
 
+#[must_use]
 pub fn format_time_ago(modified: SystemTime) -> String {
     modified.elapsed().map_or_else(
         |_| "unknown".to_string(),
@@ -19,6 +20,7 @@ pub fn format_time_ago(modified: SystemTime) -> String {
 }
 
 #[allow(clippy::cast_precision_loss)]
+#[must_use]
 pub fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use globset::{Glob, GlobMatcher};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::cmp::Reverse;
 use std::fmt::{self, Display};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -10,7 +11,7 @@ use crate::core::error::{AgentError, Result};
 use crate::tools::{ToolType, TypedTool};
 
 use super::constants::{GLOB_DEFAULT_LIMIT, GLOB_MAX_LIMIT, default_respect_gitignore};
-use super::format::format_time_ago;
+use super::format::{format_size, format_time_ago};
 use super::{validate_absolute_path, validate_path_exists, walk_builder_with_gitignore};
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -23,6 +24,23 @@ pub struct GlobInput {
     #[serde(default = "default_respect_gitignore")]
     #[schemars(default = "default_respect_gitignore")]
     pub respect_gitignore: bool,
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    #[serde(default)]
+    pub sort: GlobSort,
+    #[serde(default)]
+    pub include_dirs: bool,
+    #[serde(default)]
+    pub count_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GlobSort {
+    #[default]
+    Mtime,
+    Name,
+    Size,
 }
 
 const fn glob_default_limit() -> usize {
@@ -31,6 +49,8 @@ const fn glob_default_limit() -> usize {
 struct FileMatch {
     path: PathBuf,
     modified: SystemTime,
+    size: u64,
+    is_dir: bool,
 }
 
 impl FileMatch {
@@ -44,55 +64,102 @@ fn matches_glob(path: &Path, base_dir: &Path, matcher: &GlobMatcher) -> bool {
     matcher.is_match(relative) || path.file_name().is_some_and(|name| matcher.is_match(name))
 }
 
-fn collect_matches(
-    base_dir: &Path,
-    matcher: &GlobMatcher,
-    respect_gitignore: bool,
-) -> Vec<FileMatch> {
-    walk_builder_with_gitignore(base_dir, respect_gitignore)
+fn collect_matches(base_dir: &Path, matcher: &GlobMatcher, input: &GlobInput) -> Vec<FileMatch> {
+    walk_builder_with_gitignore(base_dir, input.respect_gitignore)
+        .max_depth(input.max_depth)
         .build()
         .flatten()
-        .filter(|entry| !entry.path().is_dir())
+        .filter(|entry| entry.depth() > 0)
+        .filter(|entry| input.include_dirs || !entry.path().is_dir())
         .filter(|entry| matches_glob(entry.path(), base_dir, matcher))
         .filter_map(|entry| {
             entry.metadata().ok().map(|meta| FileMatch {
                 path: entry.path().to_path_buf(),
                 modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                size: if meta.is_dir() { 0 } else { meta.len() },
+                is_dir: meta.is_dir(),
             })
         })
         .collect()
 }
+
+fn sort_matches(results: &mut [FileMatch], sort: GlobSort) {
+    match sort {
+        GlobSort::Mtime => results.sort_unstable_by_key(|m| Reverse(m.modified)),
+        GlobSort::Name => results.sort_unstable_by(|a, b| a.path.cmp(&b.path)),
+        GlobSort::Size => results.sort_unstable_by_key(|m| Reverse(m.size)),
+    }
+}
+
+fn count_entries(dir: &Path) -> usize {
+    std::fs::read_dir(dir).map_or(0, Iterator::count)
+}
+struct MatchStats {
+    files: usize,
+    dirs: usize,
+    total_size: u64,
+}
+
+impl MatchStats {
+    fn from_matches(results: &[FileMatch]) -> Self {
+        let dirs = results.iter().filter(|m| m.is_dir).count();
+        Self {
+            files: results.len() - dirs,
+            dirs,
+            total_size: results.iter().map(|m| m.size).sum(),
+        }
+    }
+}
+
+impl Display for MatchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} files", self.files)?;
+        if self.dirs > 0 {
+            write!(f, ", {} directories", self.dirs)?;
+        }
+        write!(f, " ({} total)", format_size(self.total_size))
+    }
+}
+
 struct GlobOutput<'a> {
     pattern: &'a str,
     results: &'a [FileMatch],
     base_dir: &'a Path,
-    total_found: usize,
+    stats: MatchStats,
     respect_gitignore: bool,
 }
 
+impl GlobOutput<'_> {
+    const fn total_found(&self) -> usize {
+        self.stats.files + self.stats.dirs
+    }
+}
+
 impl Display for GlobOutput<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.results.is_empty() {
+        if self.total_found() == 0 {
             return write!(f, "No files found matching pattern: {}", self.pattern);
         }
 
-        writeln!(
-            f,
-            "Found {} files matching \"{}\":\n",
-            self.total_found, self.pattern
-        )?;
+        writeln!(f, "Found {} matching \"{}\":\n", self.stats, self.pattern)?;
 
         for file in self.results {
             let relative = file.relative_path(self.base_dir).display();
             let modified = format_time_ago(file.modified);
-            writeln!(f, "{relative} (modified {modified})")?;
+            if file.is_dir {
+                let entries = count_entries(&file.path);
+                writeln!(f, "{relative}/ ({entries} entries, modified {modified})")?;
+            } else {
+                let size = format_size(file.size);
+                writeln!(f, "{relative} ({size}, modified {modified})")?;
+            }
         }
 
         write!(
             f,
             "\n[Showing {} of {} results]",
             self.results.len(),
-            self.total_found
+            self.total_found()
         )?;
         write!(f, "\n[Pattern: {}]", self.pattern)?;
 
@@ -142,7 +209,10 @@ impl TypedTool for GlobTool {
     }
 
     fn description(&self) -> &'static str {
-        "Find files matching glob patterns. Supports wildcards (* and **) and brace expansion. Returns paths sorted by modification time (newest first). Respects .gitignore by default."
+        "Find files matching glob patterns. Supports wildcards (* and **) and brace expansion. \
+         Returns paths with sizes, sorted by modification time (newest first) unless sort is \"name\" or \"size\". \
+         Use max_depth to limit recursion, include_dirs to also match directories, and count_only to get totals without listing. \
+         Respects .gitignore by default."
     }
 
     async fn execute_typed(&self, input: Self::Input) -> Result<String> {
@@ -150,17 +220,25 @@ impl TypedTool for GlobTool {
         let matcher = Self::build_matcher(&input.pattern)?;
         let base_dir = Self::resolve_base_dir(input.base_dir.as_deref())?;
 
-        let mut results = collect_matches(&base_dir, &matcher, input.respect_gitignore);
-        let total_found = results.len();
+        let mut results = collect_matches(&base_dir, &matcher, &input);
+        let stats = MatchStats::from_matches(&results);
 
-        results.sort_unstable_by(|a, b| b.modified.cmp(&a.modified));
+        if input.count_only {
+            return Ok(format!(
+                "Found {stats} matching \"{}\" in {}",
+                input.pattern,
+                base_dir.display()
+            ));
+        }
+
+        sort_matches(&mut results, input.sort);
         results.truncate(limit);
 
         let output = GlobOutput {
             pattern: &input.pattern,
             results: &results,
             base_dir: &base_dir,
-            total_found,
+            stats,
             respect_gitignore: input.respect_gitignore,
         };
 
@@ -171,6 +249,7 @@ impl TypedTool for GlobTool {
 mod tests {
     use super::*;
     use crate::tools::Tool;
+    use crate::tools::fs::{SMITHIGNORE, is_smithignored};
 
     #[test]
     fn test_glob_tool_name() {
@@ -193,6 +272,10 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -216,6 +299,10 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -237,6 +324,10 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -259,10 +350,14 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 5,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
-        assert!(result.contains("Showing 5 of 5 results"));
+        assert!(result.contains("Showing 5 of 10 results"));
     }
 
     #[tokio::test]
@@ -276,6 +371,10 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -301,6 +400,10 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: true,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
@@ -311,9 +414,101 @@ mod tests {
             base_dir: Some(dir_path.to_str().unwrap().to_string()),
             limit: 100,
             respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
         assert!(result.contains("ignored.txt"));
     }
+
+    #[tokio::test]
+    async fn test_glob_respects_smithignore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_path = temp_dir.path();
+
+        std::fs::write(dir_path.join(SMITHIGNORE), "*.env\nsecrets/\n").unwrap();
+        std::fs::create_dir_all(dir_path.join("app/secrets")).unwrap();
+        std::fs::write(dir_path.join("app/main.rs"), "").unwrap();
+        std::fs::write(dir_path.join("app/prod.env"), "").unwrap();
+        std::fs::write(dir_path.join("app/secrets/key.rs"), "").unwrap();
+
+        let tool = GlobTool::new();
+        let input = GlobInput {
+            pattern: "*".to_string(),
+            base_dir: Some(dir_path.join("app").to_str().unwrap().to_string()),
+            limit: 100,
+            respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::default(),
+            include_dirs: false,
+            count_only: false,
+        };
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.contains("main.rs"));
+        assert!(!result.contains("prod.env"));
+        assert!(!result.contains("key.rs"));
+
+        assert!(is_smithignored(&dir_path.join("app/prod.env")));
+        assert!(is_smithignored(&dir_path.join("app/secrets/key.rs")));
+        assert!(!is_smithignored(&dir_path.join("app/main.rs")));
+    }
+
+    #[tokio::test]
+    async fn test_glob_depth_sort_and_dirs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_path = temp_dir.path();
+
+        std::fs::create_dir_all(dir_path.join("src/nested")).unwrap();
+        std::fs::write(dir_path.join("small.rs"), "a").unwrap();
+        std::fs::write(dir_path.join("src/large.rs"), "a".repeat(2048)).unwrap();
+        std::fs::write(dir_path.join("src/nested/deep.rs"), "a").unwrap();
+
+        let tool = GlobTool::new();
+        let input = GlobInput {
+            pattern: "*".to_string(),
+            base_dir: Some(dir_path.to_str().unwrap().to_string()),
+            limit: 100,
+            respect_gitignore: false,
+            max_depth: Some(2),
+            sort: GlobSort::Size,
+            include_dirs: true,
+            count_only: false,
+        };
+
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.contains("Found 2 files, 2 directories (2.0 KB total)"));
+        assert!(result.contains("src/large.rs (2.0 KB"));
+        assert!(result.contains("src/ (2 entries"));
+        assert!(!result.contains("deep.rs"));
+        assert!(result.find("large.rs").unwrap() < result.find("small.rs").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_glob_count_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_path = temp_dir.path();
+
+        for i in 1..=3 {
+            std::fs::write(dir_path.join(format!("test{i}.txt")), "hello").unwrap();
+        }
+
+        let tool = GlobTool::new();
+        let input = GlobInput {
+            pattern: "*.txt".to_string(),
+            base_dir: Some(dir_path.to_str().unwrap().to_string()),
+            limit: 100,
+            respect_gitignore: false,
+            max_depth: None,
+            sort: GlobSort::Name,
+            include_dirs: false,
+            count_only: true,
+        };
+
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.starts_with("Found 3 files (15 B total)"));
+        assert!(!result.contains("test1.txt"));
+    }
 }
@@ -1,20 +1,30 @@
 use async_trait::async_trait;
 use globset::{Glob, GlobMatcher};
 use grep_regex::RegexMatcherBuilder;
-use grep_searcher::sinks::UTF8;
+use grep_searcher::sinks::Bytes;
 use grep_searcher::{BinaryDetection, Searcher, SearcherBuilder};
+use ignore::WalkState;
+use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use std::fmt::{self};
+use std::num::NonZero;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
 use crate::core::error::{AgentError, Result};
 use crate::tools::{ToolType, TypedTool};
 
 use super::constants::{
-    GREP_DEFAULT_LIMIT, GREP_MAX_CONTEXT, GREP_MAX_LIMIT, default_respect_gitignore,
+    GREP_DEFAULT_LIMIT, GREP_MAX_CONTEXT, GREP_MAX_LIMIT, GREP_MAX_THREADS, GREP_TIME_BUDGET,
+    default_respect_gitignore,
+};
+use super::encoding::decode_line;
+use super::{
+    FilePolicy, read_text, special_file_kind, validate_absolute_path, validate_path_exists,
+    walk_builder_with_gitignore,
 };
-use super::{validate_absolute_path, validate_path_exists, walk_builder_with_gitignore};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrepInput {
@@ -45,9 +55,11 @@ struct MatchResult {
     context_after: Vec<String>,
 }
 
+#[derive(Clone, Copy)]
 struct SearchConfig {
     context: usize,
     limit: usize,
+    deadline: Instant,
 }
 
 struct MatchCollector {
@@ -76,10 +88,86 @@ impl MatchCollector {
         self.remaining > 0
     }
 
+    fn extend(&mut self, results: Vec<MatchResult>) {
+        for result in results {
+            if !self.push(result) {
+                break;
+            }
+        }
+    }
+
     fn into_vec(self) -> Vec<MatchResult> {
         self.matches
     }
 }
+
+fn build_searcher() -> Searcher {
+    SearcherBuilder::new()
+        .binary_detection(BinaryDetection::quit(0x00))
+        .line_number(true)
+        .build()
+}
+
+fn search_tree(
+    root: &Path,
+    respect_gitignore: bool,
+    glob: Option<&GlobMatcher>,
+    matcher: &grep_regex::RegexMatcher,
+    config: &SearchConfig,
+    policy: &FilePolicy,
+) -> (Vec<MatchResult>, bool) {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, NonZero::get)
+        .min(GREP_MAX_THREADS);
+    let shared = Mutex::new(MatchCollector::with_capacity(config.limit));
+    let timed_out = AtomicBool::new(false);
+
+    walk_builder_with_gitignore(root, respect_gitignore)
+        .threads(threads)
+        .build_parallel()
+        .run(|| {
+            let mut searcher = build_searcher();
+            let (shared, timed_out) = (&shared, &timed_out);
+            Box::new(move |entry| {
+                if Instant::now() >= config.deadline {
+                    timed_out.store(true, Ordering::Relaxed);
+                    return WalkState::Quit;
+                }
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                let path = entry.path();
+                if path.is_dir() || !matches_glob(path, glob) {
+                    return WalkState::Continue;
+                }
+                let regular = entry.file_type().is_some_and(|kind| kind.is_file());
+                if (entry.path_is_symlink() || !regular)
+                    && policy.check(path, &ToolType::Grep).is_err()
+                {
+                    return WalkState::Continue;
+                }
+
+                let remaining = shared.lock().remaining;
+                if remaining == 0 {
+                    return WalkState::Quit;
+                }
+                let mut local = MatchCollector::with_capacity(remaining);
+                search_file(path, matcher, &mut searcher, config, &mut local);
+
+                let mut collector = shared.lock();
+                collector.extend(local.into_vec());
+                if collector.is_full() {
+                    WalkState::Quit
+                } else {
+                    WalkState::Continue
+                }
+            })
+        });
+
+    let mut results = shared.into_inner().into_vec();
+    results.sort_by(|a, b| (&a.path, a.line_number).cmp(&(&b.path, b.line_number)));
+    (results, timed_out.into_inner())
+}
 fn search_file(
     path: &Path,
     matcher: &grep_regex::RegexMatcher,
@@ -88,9 +176,9 @@ fn search_file(
     collector: &mut MatchCollector,
 ) {
     let lines: Option<Vec<String>> = if config.context > 0 {
-        std::fs::read_to_string(path)
+        read_text(path)
             .ok()
-            .map(|contents| contents.lines().map(String::from).collect())
+            .map(|(contents, _)| contents.lines().map(String::from).collect())
     } else {
         None
     };
@@ -98,7 +186,7 @@ fn search_file(
     let _ = searcher.search_path(
         matcher,
         path,
-        UTF8(|line_num, line| {
+        Bytes(|line_num, line| {
             if collector.is_full() {
                 return Ok(false);
             }
@@ -111,7 +199,7 @@ fn search_file(
             let should_continue = collector.push(MatchResult {
                 path: path.to_path_buf(),
                 line_number: line_num,
-                line: line.trim_end().to_string(),
+                line: decode_line(line).trim_end().to_string(),
                 context_before,
                 context_after,
             });
@@ -148,6 +236,7 @@ struct SearchOutput<'a> {
     results: &'a [MatchResult],
     context: usize,
     respect_gitignore: bool,
+    timed_out: bool,
 }
 
 impl fmt::Display for SearchOutput<'_> {
@@ -188,6 +277,14 @@ impl fmt::Display for SearchOutput<'_> {
         )?;
         write!(f, "\n[Pattern: {}]", self.pattern)?;
 
+        if self.timed_out {
+            write!(
+                f,
+                "\n[Stopped after {}s; results are partial, narrow the path or glob]",
+                GREP_TIME_BUDGET.as_secs()
+            )?;
+        }
+
         if self.respect_gitignore {
             write!(f, "\n[Respecting .gitignore]")?;
         }
@@ -197,12 +294,20 @@ impl fmt::Display for SearchOutput<'_> {
 }
 
 #[derive(Default)]
-pub struct GrepTool;
+pub struct GrepTool {
+    policy: FilePolicy,
+}
 
 impl GrepTool {
     #[must_use]
-    pub const fn new() -> Self {
-        Self
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_file_policy(mut self, policy: FilePolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     fn build_matcher(pattern: &str, ignore_case: bool) -> Result<grep_regex::RegexMatcher> {
@@ -256,51 +361,49 @@ impl TypedTool for GrepTool {
         let config = SearchConfig {
             limit: input.limit.min(GREP_MAX_LIMIT),
             context: input.context.min(GREP_MAX_CONTEXT),
+            deadline: Instant::now() + GREP_TIME_BUDGET,
         };
 
         let matcher = Self::build_matcher(&input.pattern, input.ignore_case)?;
         let glob_matcher = Self::build_glob(input.glob.as_deref())?;
         let search_path = Self::resolve_search_path(input.path.as_deref())?;
-
-        let mut searcher = SearcherBuilder::new()
-            .binary_detection(BinaryDetection::quit(0x00))
-            .line_number(true)
-            .build();
-
-        let mut collector = MatchCollector::with_capacity(config.limit);
-
-        if search_path.is_file() {
-            let () = search_file(
-                &search_path,
-                &matcher,
-                &mut searcher,
-                &config,
-                &mut collector,
-            );
-        } else {
-            let walker = walk_builder_with_gitignore(&search_path, input.respect_gitignore).build();
-
-            for entry in walker.flatten() {
-                let path = entry.path();
-
-                if path.is_dir() || !matches_glob(path, glob_matcher.as_ref()) {
-                    continue;
-                }
-
-                let () = search_file(path, &matcher, &mut searcher, &config, &mut collector);
-
-                if collector.is_full() {
-                    break;
-                }
-            }
+        let respect_gitignore = input.respect_gitignore;
+        if search_path.is_file() || special_file_kind(&search_path).is_some() {
+            self.policy.check(&search_path, &ToolType::Grep)?;
         }
+        let policy = self.policy.clone();
+
+        let (results, timed_out) = tokio::task::spawn_blocking(move || {
+            if search_path.is_file() {
+                let mut collector = MatchCollector::with_capacity(config.limit);
+                search_file(
+                    &search_path,
+                    &matcher,
+                    &mut build_searcher(),
+                    &config,
+                    &mut collector,
+                );
+                (collector.into_vec(), false)
+            } else {
+                search_tree(
+                    &search_path,
+                    respect_gitignore,
+                    glob_matcher.as_ref(),
+                    &matcher,
+                    &config,
+                    &policy,
+                )
+            }
+        })
+        .await
+        .map_err(|e| AgentError::ToolExecution(format!("grep worker failed: {e}")))?;
 
-        let results = collector.into_vec();
         let output = SearchOutput {
             pattern: &input.pattern,
             results: &results,
             context: config.context,
-            respect_gitignore: input.respect_gitignore,
+            respect_gitignore,
+            timed_out,
         };
 
         Ok(output.to_string())
@@ -430,6 +533,52 @@ mod tests {
         assert!(result.contains("hello again"));
     }
 
+    #[test]
+    fn test_search_tree_stops_at_limit_and_deadline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for i in 0..40 {
+            fs::write(
+                temp_dir.path().join(format!("f{i:02}.txt")),
+                "needle\nneedle\n",
+            )
+            .unwrap();
+        }
+        let matcher = GrepTool::build_matcher("needle", false).unwrap();
+        let mut config = SearchConfig {
+            context: 0,
+            limit: 5,
+            deadline: Instant::now() + GREP_TIME_BUDGET,
+        };
+
+        let (results, timed_out) = search_tree(
+            temp_dir.path(),
+            false,
+            None,
+            &matcher,
+            &config,
+            &FilePolicy::default(),
+        );
+        assert_eq!(results.len(), 5);
+        assert!(!timed_out);
+        assert!(
+            results
+                .windows(2)
+                .all(|w| (&w[0].path, w[0].line_number) <= (&w[1].path, w[1].line_number))
+        );
+
+        config.deadline = Instant::now();
+        let (results, timed_out) = search_tree(
+            temp_dir.path(),
+            false,
+            None,
+            &matcher,
+            &config,
+            &FilePolicy::default(),
+        );
+        assert!(results.is_empty());
+        assert!(timed_out);
+    }
+
     #[tokio::test]
     async fn test_grep_no_matches() {
         let temp_dir = tempfile::tempdir().unwrap();
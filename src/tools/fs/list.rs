@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fmt::{self, Display};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -9,7 +10,7 @@ use crate::core::error::Result;
 use crate::tools::ToolType;
 use crate::tools::TypedTool;
 
-use super::constants::{LIST_MAX_DEPTH, default_respect_gitignore};
+use super::constants::{LIST_MAX_DEPTH, TREE_DEFAULT_DEPTH, default_respect_gitignore};
 use super::format::format_size;
 use super::{
     validate_absolute_path, validate_is_dir, validate_path_exists, walk_builder_with_gitignore,
@@ -36,11 +37,14 @@ pub struct ListDirInput {
     #[serde(default = "default_respect_gitignore")]
     #[schemars(default = "default_respect_gitignore")]
     pub respect_gitignore: bool,
+    #[serde(default)]
+    pub tree: bool,
 }
 
 #[derive(Debug)]
 struct DirEntry {
     name: String,
+    rel_path: PathBuf,
     depth: usize,
     kind: EntryKind,
     modified: SystemTime,
@@ -94,6 +98,7 @@ impl DirEntry {
 
         Some(Self {
             name,
+            rel_path: rel_path.to_path_buf(),
             depth,
             kind,
             modified,
@@ -193,6 +198,80 @@ impl Display for DirOutput<'_> {
     }
 }
 
+struct TreeOutput<'a> {
+    path: &'a Path,
+    entries: &'a [DirEntry],
+    depth: usize,
+}
+
+impl TreeOutput<'_> {
+    fn render_level(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        children: &HashMap<&Path, Vec<&DirEntry>>,
+        parent: &Path,
+        prefix: &str,
+    ) -> fmt::Result {
+        let Some(level) = children.get(parent) else {
+            return Ok(());
+        };
+
+        for (i, entry) in level.iter().enumerate() {
+            let (branch, indent) = if i + 1 == level.len() {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            match &entry.kind {
+                EntryKind::Directory if entry.depth == self.depth => {
+                    let count = std::fs::read_dir(self.path.join(&entry.rel_path))
+                        .map_or(0, Iterator::count);
+                    writeln!(f, "{prefix}{branch}{}/ ({count} entries)", entry.name)?;
+                }
+                EntryKind::Directory => {
+                    writeln!(f, "{prefix}{branch}{}/", entry.name)?;
+                    self.render_level(f, children, &entry.rel_path, &format!("{prefix}{indent}"))?;
+                }
+                EntryKind::File { size, executable } => {
+                    let suffix = if *executable { "*" } else { "" };
+                    writeln!(
+                        f,
+                        "{prefix}{branch}{}{suffix} ({})",
+                        entry.name,
+                        format_size(*size)
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TreeOutput<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}/", self.path.display())?;
+
+        let mut children: HashMap<&Path, Vec<&DirEntry>> = HashMap::new();
+        for entry in self.entries {
+            let parent = entry.rel_path.parent().unwrap_or_else(|| Path::new(""));
+            children.entry(parent).or_default().push(entry);
+        }
+        self.render_level(f, &children, Path::new(""), "")?;
+
+        let dirs = self.entries.iter().filter(|e| e.is_dir()).count();
+        let total_size: u64 = self.entries.iter().filter_map(DirEntry::size).sum();
+        write!(
+            f,
+            "\n{dirs} directories, {} files ({} total)",
+            self.entries.len() - dirs,
+            format_size(total_size)
+        )?;
+        write!(f, "\n[Depth: {}]", self.depth + 1)
+    }
+}
+
 impl Display for SortBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -222,7 +301,9 @@ impl TypedTool for ListDirTool {
     }
 
     fn description(&self) -> &'static str {
-        "List the contents of a directory with optional recursion and sorting. Shows file sizes and type indicators. Respects .gitignore by default. The path must be absolute."
+        "List the contents of a directory with optional recursion and sorting. Shows file sizes and type indicators. \
+         Set tree=true for a compact tree view (two levels unless depth is given), which is the cheapest way to explore a project layout. \
+         Respects .gitignore by default. The path must be absolute."
     }
 
     async fn execute_typed(&self, input: Self::Input) -> Result<String> {
@@ -230,12 +311,25 @@ impl TypedTool for ListDirTool {
         validate_path_exists(&path, &ToolType::ListDir)?;
         validate_is_dir(&path, &ToolType::ListDir)?;
 
-        let depth = input.depth.min(LIST_MAX_DEPTH);
+        let depth = if input.tree && input.depth == 0 {
+            TREE_DEFAULT_DEPTH
+        } else {
+            input.depth.min(LIST_MAX_DEPTH)
+        };
 
         let mut entries =
             collect_entries(&path, input.include_hidden, depth, input.respect_gitignore);
         sort_entries(&mut entries, input.sort_by);
 
+        if input.tree {
+            let output = TreeOutput {
+                path: &path,
+                entries: &entries,
+                depth,
+            };
+            return Ok(output.to_string());
+        }
+
         let output = DirOutput {
             path: &path,
             entries: &entries,
@@ -274,6 +368,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -298,6 +393,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
@@ -309,6 +405,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
@@ -331,6 +428,7 @@ mod tests {
             depth: 1,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -348,6 +446,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
 
         let result = tool.execute_typed(input).await;
@@ -366,6 +465,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -392,6 +492,7 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: true,
+            tree: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
@@ -403,9 +504,39 @@ mod tests {
             depth: 0,
             sort_by: SortBy::Name,
             respect_gitignore: false,
+            tree: false,
         };
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("visible.txt"));
         assert!(result.contains("ignored.txt"));
     }
+
+    #[tokio::test]
+    async fn test_list_dir_tree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_path = temp_dir.path();
+
+        std::fs::create_dir_all(dir_path.join("src/nested/deeper")).unwrap();
+        std::fs::write(dir_path.join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(dir_path.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir_path.join("src/nested/a.rs"), "").unwrap();
+
+        let tool = ListDirTool::new();
+        let input = ListDirInput {
+            path: dir_path.to_str().unwrap().to_string(),
+            include_hidden: false,
+            depth: 0,
+            sort_by: SortBy::Name,
+            respect_gitignore: false,
+            tree: true,
+        };
+
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.contains("├── Cargo.toml (9 B)"));
+        assert!(result.contains("└── src/"));
+        assert!(result.contains("    ├── main.rs (12 B)"));
+        assert!(result.contains("    └── nested/ (2 entries)"));
+        assert!(!result.contains("a.rs"));
+        assert!(result.contains("2 directories, 2 files (21 B total)"));
+    }
 }
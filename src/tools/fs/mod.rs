@@ -1,20 +1,36 @@
+mod backup;
 mod constants;
+mod document;
+mod encoding;
 mod format;
 mod glob;
 mod grep;
 mod list;
+mod policy;
+mod preview;
 mod read;
 mod update;
 mod utils;
+mod versions;
 mod write;
 
+pub use backup::BackupStore;
+pub use document::{DocumentAttachments, ReadDocumentTool};
+pub use encoding::{
+    LineEnding, TextEncoding, decode, format_label, match_line_endings, read_text, write_text,
+};
+pub use format::{format_size, format_time_ago};
 pub use glob::GlobTool;
 pub use grep::GrepTool;
 pub use list::ListDirTool;
+pub use policy::{FilePolicy, special_file_kind};
+pub use preview::PreviewDataTool;
 pub use read::ReadFileTool;
 pub use update::UpdateFileTool;
 pub use utils::{
-    atomic_write, validate_absolute_path, validate_file_size, validate_is_dir, validate_is_file,
-    validate_path_exists, walk_builder_with_gitignore,
+    SMITHIGNORE, atomic_write, is_smithignored, validate_absolute_path, validate_file_size,
+    validate_is_dir, validate_is_file, validate_not_ignored, validate_path_exists,
+    walk_builder_with_gitignore,
 };
+pub use versions::FileVersions;
 pub use write::WriteFileTool;
@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::SymlinkPolicy;
+use crate::core::error::{AgentError, Result};
+use crate::tools::types::ToolType;
+
+#[derive(Debug, Clone, Default)]
+pub struct FilePolicy {
+    symlinks: SymlinkPolicy,
+    root: Option<(PathBuf, PathBuf)>,
+}
+
+impl FilePolicy {
+    #[must_use]
+    pub fn new(symlinks: SymlinkPolicy, root: &Path) -> Self {
+        let canonical = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        Self {
+            symlinks,
+            root: Some((root.to_path_buf(), canonical)),
+        }
+    }
+
+    pub fn check(&self, path: &Path, tool_type: &ToolType) -> Result<()> {
+        let invalid = |reason: String| AgentError::InvalidToolInput {
+            tool: tool_type.name().to_string(),
+            reason,
+        };
+        if let Some(kind) = special_file_kind(path) {
+            return Err(invalid(format!(
+                "{} is a {kind}, not a regular file",
+                path.display()
+            )));
+        }
+        let Some(link) = self.first_symlink(path) else {
+            return Ok(());
+        };
+        match self.symlinks {
+            SymlinkPolicy::Follow => Ok(()),
+            SymlinkPolicy::Refuse => Err(invalid(format!(
+                "{} goes through the symlink {}, and tools.files.symlinks is \"refuse\"",
+                path.display(),
+                link.display()
+            ))),
+            SymlinkPolicy::Workspace => {
+                let Some((lexical, root)) = &self.root else {
+                    return Ok(());
+                };
+                if !path.starts_with(lexical) && !path.starts_with(root) {
+                    return Ok(());
+                }
+                match resolve(path) {
+                    Some(target) if target.starts_with(root) => Ok(()),
+                    Some(target) => Err(invalid(format!(
+                        "{} resolves through the symlink {} to {}, outside the workspace",
+                        path.display(),
+                        link.display(),
+                        target.display()
+                    ))),
+                    None => Err(invalid(format!(
+                        "{} goes through the dangling symlink {}",
+                        path.display(),
+                        link.display()
+                    ))),
+                }
+            }
+        }
+    }
+
+    fn first_symlink<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        let mut ancestors: Vec<&Path> = path.ancestors().collect();
+        ancestors.reverse();
+        ancestors.into_iter().find(|ancestor| {
+            let above_root = self.root.as_ref().is_some_and(|(lexical, canonical)| {
+                lexical.starts_with(ancestor) || canonical.starts_with(ancestor)
+            });
+            !above_root
+                && ancestor
+                    .symlink_metadata()
+                    .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        })
+    }
+}
+
+fn resolve(path: &Path) -> Option<PathBuf> {
+    if let Ok(canonical) = path.canonicalize() {
+        return Some(canonical);
+    }
+    if path.symlink_metadata().is_ok() {
+        return None;
+    }
+    Some(resolve(path.parent()?)?.join(path.file_name()?))
+}
+
+#[cfg(unix)]
+#[must_use]
+pub fn special_file_kind(path: &Path) -> Option<&'static str> {
+    use std::os::unix::fs::FileTypeExt;
+    let file_type = std::fs::metadata(path).ok()?.file_type();
+    if file_type.is_fifo() {
+        Some("FIFO")
+    } else if file_type.is_char_device() {
+        Some("character device")
+    } else if file_type.is_block_device() {
+        Some("block device")
+    } else if file_type.is_socket() {
+        Some("socket")
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+#[must_use]
+pub fn special_file_kind(_path: &Path) -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_policies_and_special_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let workspace = temp.path().join("workspace");
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(workspace.join("src")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(workspace.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(outside.join("secret.txt"), "token").unwrap();
+        std::os::unix::fs::symlink(workspace.join("src/main.rs"), workspace.join("main.rs"))
+            .unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.join("escape")).unwrap();
+
+        let inner = workspace.join("main.rs");
+        let escaping = workspace.join("escape/secret.txt");
+        let created = workspace.join("escape/new.txt");
+        let tool = ToolType::ReadFile;
+
+        let policy = FilePolicy::new(SymlinkPolicy::Workspace, &workspace);
+        assert!(policy.check(&workspace.join("src/main.rs"), &tool).is_ok());
+        assert!(policy.check(&inner, &tool).is_ok());
+        let err = policy.check(&escaping, &tool).unwrap_err();
+        assert!(err.to_string().contains("outside the workspace"));
+        assert!(policy.check(&created, &ToolType::WriteFile).is_err());
+
+        let refuse = FilePolicy::new(SymlinkPolicy::Refuse, &workspace);
+        assert!(refuse.check(&inner, &tool).is_err());
+        assert!(refuse.check(&workspace.join("src/main.rs"), &tool).is_ok());
+
+        let follow = FilePolicy::new(SymlinkPolicy::Follow, &workspace);
+        assert!(follow.check(&escaping, &tool).is_ok());
+
+        let err = follow.check(Path::new("/dev/null"), &tool).unwrap_err();
+        assert!(err.to_string().contains("character device"));
+    }
+}
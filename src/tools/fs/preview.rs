@@ -0,0 +1,372 @@
+use async_trait::async_trait;
+use parquet::basic::ConvertedType;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use crate::core::error::{AgentError, Result};
+use crate::tools::table::render_table;
+use crate::tools::{ToolType, TypedTool};
+
+use super::constants::{PREVIEW_DEFAULT_ROWS, PREVIEW_MAX_ROWS};
+use super::{validate_absolute_path, validate_is_file, validate_path_exists};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PreviewDataInput {
+    pub path: String,
+    #[serde(default = "preview_default_rows")]
+    pub rows: usize,
+}
+
+const fn preview_default_rows() -> usize {
+    PREVIEW_DEFAULT_ROWS
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataFormat {
+    Csv,
+    Tsv,
+    Parquet,
+}
+
+impl DataFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Tsv => "TSV",
+            Self::Parquet => "Parquet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Empty,
+    Boolean,
+    Integer,
+    Float,
+    Text,
+}
+
+impl ColumnKind {
+    fn observe(self, value: &str) -> Self {
+        let value = value.trim();
+        if value.is_empty() {
+            return self;
+        }
+        let kind = if value.parse::<i64>().is_ok() {
+            Self::Integer
+        } else if value.parse::<f64>().is_ok() {
+            Self::Float
+        } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            Self::Boolean
+        } else {
+            Self::Text
+        };
+        match (self, kind) {
+            (Self::Empty, kind) => kind,
+            (current, kind) if current == kind => current,
+            (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+            _ => Self::Text,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Text => "string",
+        }
+    }
+}
+
+struct DataPreview {
+    path: PathBuf,
+    format: DataFormat,
+    schema: Vec<(String, String)>,
+    total_rows: u64,
+    rows: Vec<Vec<String>>,
+}
+
+fn invalid(reason: impl Into<String>) -> AgentError {
+    AgentError::InvalidToolInput {
+        tool: ToolType::PreviewData.name().to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn preview_delimited(path: &Path, format: DataFormat, limit: usize) -> Result<DataPreview> {
+    let delimiter = if format == DataFormat::Tsv {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| invalid(format!("Could not open {}: {e}", path.display())))?;
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| invalid(format!("Could not read header row: {e}")))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut kinds = vec![ColumnKind::Empty; headers.len()];
+    let mut rows = Vec::new();
+    let mut total_rows = 0;
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| invalid(format!("Malformed row {}: {e}", total_rows + 1)))?;
+        total_rows += 1;
+        for (kind, value) in kinds.iter_mut().zip(record.iter()) {
+            *kind = kind.observe(value);
+        }
+        if rows.len() < limit {
+            rows.push(record.iter().map(str::to_string).collect());
+        }
+    }
+
+    Ok(DataPreview {
+        path: path.to_path_buf(),
+        format,
+        schema: headers
+            .into_iter()
+            .zip(kinds)
+            .map(|(name, kind)| (name, kind.name().to_string()))
+            .collect(),
+        total_rows,
+        rows,
+    })
+}
+
+fn preview_parquet(path: &Path, limit: usize) -> Result<DataPreview> {
+    let reader = SerializedFileReader::new(File::open(path)?)
+        .map_err(|e| invalid(format!("Not a readable Parquet file: {e}")))?;
+    let metadata = reader.metadata().file_metadata();
+    let schema = metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| {
+            let kind = match column.logical_type_ref() {
+                Some(logical) => format!("{logical:?}")
+                    .split([' ', '{', '('])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                None if column.converted_type() != ConvertedType::NONE => {
+                    format!("{:?}", column.converted_type())
+                }
+                None => format!("{:?}", column.physical_type()),
+            };
+            (column.path().string(), kind)
+        })
+        .collect();
+    let total_rows = u64::try_from(metadata.num_rows()).unwrap_or_default();
+
+    let mut rows = Vec::new();
+    let iter = reader
+        .get_row_iter(None)
+        .map_err(|e| invalid(format!("Could not read rows: {e}")))?;
+    for row in iter.take(limit) {
+        let row = row.map_err(|e| invalid(format!("Could not read row: {e}")))?;
+        rows.push(
+            row.get_column_iter()
+                .map(|(_, field)| field_text(field))
+                .collect(),
+        );
+    }
+
+    Ok(DataPreview {
+        path: path.to_path_buf(),
+        format: DataFormat::Parquet,
+        schema,
+        total_rows,
+        rows,
+    })
+}
+
+fn field_text(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        Field::Str(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl DataPreview {
+    fn render(&self) -> String {
+        let mut output = format!(
+            "File: {} ({}, {} rows, {} columns)\n\nSchema:\n",
+            self.path.display(),
+            self.format.label(),
+            self.total_rows,
+            self.schema.len()
+        );
+        for (name, kind) in &self.schema {
+            let _ = writeln!(output, "  {name}: {kind}");
+        }
+        if self.rows.is_empty() {
+            output.push_str("\nNo rows");
+            return output;
+        }
+
+        let header: Vec<String> = self.schema.iter().map(|(name, _)| name.clone()).collect();
+        let _ = write!(
+            output,
+            "\nFirst {} of {} rows:\n{}",
+            self.rows.len(),
+            self.total_rows,
+            render_table(&header, &self.rows)
+        );
+        output
+    }
+}
+
+#[derive(Default)]
+pub struct PreviewDataTool;
+
+impl PreviewDataTool {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TypedTool for PreviewDataTool {
+    type Input = PreviewDataInput;
+
+    fn name(&self) -> &'static str {
+        "preview_data"
+    }
+
+    fn description(&self) -> &'static str {
+        "Preview a CSV, TSV or Parquet file: column schema, total row count and the first rows as a table. Use this instead of reading whole datasets. The path must be absolute."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let path = validate_absolute_path(&input.path, &ToolType::PreviewData)?;
+        validate_path_exists(&path, &ToolType::PreviewData)?;
+        validate_is_file(&path, &ToolType::PreviewData)?;
+        let format = DataFormat::from_path(&path).ok_or_else(|| {
+            invalid(format!(
+                "Unsupported data file: {}. preview_data handles .csv, .tsv and .parquet",
+                path.display()
+            ))
+        })?;
+
+        let limit = input.rows.min(PREVIEW_MAX_ROWS);
+        let preview = tokio::task::spawn_blocking(move || match format {
+            DataFormat::Parquet => preview_parquet(&path, limit),
+            DataFormat::Csv | DataFormat::Tsv => preview_delimited(&path, format, limit),
+        })
+        .await
+        .map_err(|e| invalid(format!("Preview failed: {e}")))??;
+
+        Ok(preview.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    async fn preview(path: &Path, rows: usize) -> String {
+        PreviewDataTool::new()
+            .execute_typed(PreviewDataInput {
+                path: path.display().to_string(),
+                rows,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_csv_schema_and_rows() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("prices.csv");
+        std::fs::write(
+            &path,
+            "id,name,price,active\n1,apple,1.5,true\n2,banana,2,false\n3,\"cherry, red\",,true\n",
+        )
+        .unwrap();
+
+        let output = preview(&path, 2).await;
+        assert!(output.starts_with(&format!(
+            "File: {} (CSV, 3 rows, 4 columns)",
+            path.display()
+        )));
+        assert!(
+            output.contains("  id: integer\n  name: string\n  price: float\n  active: boolean\n")
+        );
+        assert!(output.ends_with(
+            "First 2 of 3 rows:\nid | name   | price | active\n1  | apple  | 1.5   | true\n2  | banana | 2     | false"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parquet_schema_and_rows() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("events.parquet");
+        let schema = Arc::new(
+            parse_message_type(
+                "message schema { REQUIRED INT64 id; REQUIRED BYTE_ARRAY kind (UTF8); }",
+            )
+            .unwrap(),
+        );
+        let mut writer =
+            SerializedFileWriter::new(File::create(&path).unwrap(), schema, Arc::default())
+                .unwrap();
+        let mut group = writer.next_row_group().unwrap();
+        let mut column = group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[7, 8, 9], None, None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(
+                &[
+                    ByteArray::from("click"),
+                    ByteArray::from("view"),
+                    ByteArray::from("buy"),
+                ],
+                None,
+                None,
+            )
+            .unwrap();
+        column.close().unwrap();
+        group.close().unwrap();
+        writer.close().unwrap();
+
+        let output = preview(&path, 2).await;
+        assert!(output.contains("(Parquet, 3 rows, 2 columns)"));
+        assert!(output.contains("  id: INT64\n  kind: UTF8\n"));
+        assert!(output.ends_with("id | kind\n7  | click\n8  | view"));
+    }
+}
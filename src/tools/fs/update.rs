@@ -8,7 +8,10 @@ use crate::core::error::{AgentError, Result};
 use crate::core::metadata;
 use crate::tools::{ToolType, TypedTool};
 
-use super::{atomic_write, validate_absolute_path, validate_file_size, validate_path_exists};
+use super::{
+    BackupStore, FilePolicy, FileVersions, LineEnding, format_label, match_line_endings, read_text,
+    validate_absolute_path, validate_file_size, validate_path_exists, write_text,
+};
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct UpdateFileInput {
     pub path: String,
@@ -24,6 +27,7 @@ enum UpdateResult {
         old_content: String,
         new_content: String,
         occurrences: usize,
+        format: Option<String>,
     },
 }
 
@@ -56,10 +60,16 @@ fn execute_update(
     old_string: &str,
     new_string: &str,
     replace_all: bool,
+    backups: Option<&BackupStore>,
+    versions: Option<&FileVersions>,
 ) -> Result<UpdateResult> {
-    let old_content = std::fs::read_to_string(path)?;
+    let (old_content, encoding) = read_text(path)?;
+    if let Some(versions) = versions {
+        versions.check(path, &old_content)?;
+    }
+    let (old_string, new_string) = match_line_endings(&old_content, old_string, new_string);
 
-    let occurrences = match validate_matches(&old_content, old_string, replace_all) {
+    let occurrences = match validate_matches(&old_content, &old_string, replace_all) {
         MatchValidation::NotFound => {
             return Err(AgentError::InvalidToolInput {
                 tool: ToolType::UpdateFile.name().to_string(),
@@ -80,16 +90,20 @@ fn execute_update(
         MatchValidation::Valid(count) => count,
     };
 
-    let new_content = perform_replacement(&old_content, old_string, new_string, replace_all);
+    let new_content = perform_replacement(&old_content, &old_string, &new_string, replace_all);
 
     if old_content == new_content {
         return Ok(UpdateResult::NoChange);
     }
 
-    atomic_write(path, &new_content)?;
+    if let Some(store) = backups {
+        store.backup(path)?;
+    }
+    write_text(path, &new_content, encoding, &ToolType::UpdateFile)?;
 
     Ok(UpdateResult::Updated {
         path: path.to_path_buf(),
+        format: format_label(encoding, LineEnding::detect(&old_content)),
         old_content,
         new_content,
         occurrences,
@@ -108,6 +122,7 @@ fn format_output(result: UpdateResult, path: &Path) -> String {
             old_content,
             new_content,
             occurrences,
+            format,
         } => {
             let plural = if occurrences == 1 { "" } else { "s" };
             let summary = format!(
@@ -120,6 +135,7 @@ fn format_output(result: UpdateResult, path: &Path) -> String {
                     "path": path.to_string_lossy(),
                     "old_content": old_content,
                     "new_content": new_content,
+                    "format": format,
                 }
             });
 
@@ -131,12 +147,34 @@ fn format_output(result: UpdateResult, path: &Path) -> String {
     }
 }
 #[derive(Default)]
-pub struct UpdateFileTool;
+pub struct UpdateFileTool {
+    backups: Option<BackupStore>,
+    versions: Option<FileVersions>,
+    policy: FilePolicy,
+}
 
 impl UpdateFileTool {
     #[must_use]
-    pub const fn new() -> Self {
-        Self
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_backups(mut self, store: BackupStore) -> Self {
+        self.backups = Some(store);
+        self
+    }
+
+    #[must_use]
+    pub fn with_versions(mut self, versions: FileVersions) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+
+    #[must_use]
+    pub fn with_file_policy(mut self, policy: FilePolicy) -> Self {
+        self.policy = policy;
+        self
     }
 }
 
@@ -156,6 +194,7 @@ impl TypedTool for UpdateFileTool {
     async fn execute_typed(&self, input: Self::Input) -> Result<String> {
         let path = validate_absolute_path(&input.path, &ToolType::UpdateFile)?;
         validate_path_exists(&path, &ToolType::UpdateFile)?;
+        self.policy.check(&path, &ToolType::UpdateFile)?;
         validate_file_size(&path, &ToolType::UpdateFile)?;
 
         let result = execute_update(
@@ -163,6 +202,8 @@ impl TypedTool for UpdateFileTool {
             &input.old_string,
             &input.new_string,
             input.replace_all,
+            self.backups.as_ref(),
+            self.versions.as_ref(),
         )?;
 
         Ok(format_output(result, &path))
@@ -172,6 +213,7 @@ impl TypedTool for UpdateFileTool {
 mod tests {
     use super::*;
     use crate::tools::Tool;
+    use crate::tools::fs::TextEncoding;
 
     #[test]
     fn test_update_file_tool_name() {
@@ -333,4 +375,107 @@ mod tests {
         let result = tool.execute_typed(input).await.unwrap();
         assert!(result.contains("No changes made"));
     }
+
+    #[tokio::test]
+    async fn test_update_file_refuses_stale_edit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        std::fs::write(&file_path, "Hello, World!\n").unwrap();
+
+        let versions = FileVersions::new();
+        versions.record_from_disk(&file_path);
+        std::fs::write(&file_path, "Hello, World!\nEdited by the user\n").unwrap();
+
+        let tool = UpdateFileTool::new().with_versions(versions);
+        let input = || UpdateFileInput {
+            path: file_path.to_str().unwrap().to_string(),
+            old_string: "World".to_string(),
+            new_string: "Rust".to_string(),
+            replace_all: false,
+        };
+
+        let err = tool.execute_typed(input()).await.unwrap_err();
+        assert!(matches!(err, AgentError::FileConflict { .. }));
+        assert!(err.to_string().contains("+Edited by the user"));
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "Hello, World!\nEdited by the user\n"
+        );
+
+        tool.execute_typed(input()).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "Hello, Rust!\nEdited by the user\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_file_keeps_original_encoding() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let latin1 = temp_dir.path().join("legacy.txt");
+        std::fs::write(&latin1, b"caf\xe9 noir\n").unwrap();
+        let utf16 = temp_dir.path().join("notes.txt");
+        std::fs::write(
+            &utf16,
+            TextEncoding::Utf16Le.encode("caf\u{e9} noir\n").unwrap(),
+        )
+        .unwrap();
+
+        let input = |path: &Path, new_string: &str| UpdateFileInput {
+            path: path.to_str().unwrap().to_string(),
+            old_string: "noir".to_string(),
+            new_string: new_string.to_string(),
+            replace_all: false,
+        };
+        let tool = UpdateFileTool::new();
+        let err = tool
+            .execute_typed(input(&latin1, "\u{2615}"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Latin-1 encoded"));
+        tool.execute_typed(input(&latin1, "cr\u{e8}me"))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&latin1).unwrap(), b"caf\xe9 cr\xe8me\n");
+        tool.execute_typed(input(&utf16, "cr\u{e8}me"))
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(&utf16).unwrap(),
+            TextEncoding::Utf16Le
+                .encode("caf\u{e9} cr\u{e8}me\n")
+                .unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_update_file_through_workspace_symlink() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = temp_dir.path();
+        std::fs::create_dir(workspace.join("config")).unwrap();
+        let target = workspace.join("config/app.toml");
+        let link = workspace.join("app.toml");
+        std::fs::write(&target, "port = 80").unwrap();
+        std::os::unix::fs::symlink("config/app.toml", &link).unwrap();
+
+        let policy =
+            crate::tools::FilePolicy::new(crate::config::SymlinkPolicy::Workspace, workspace);
+        let tool = UpdateFileTool::new().with_file_policy(policy);
+        let input = UpdateFileInput {
+            path: link.to_str().unwrap().to_string(),
+            old_string: "80".to_string(),
+            new_string: "8080".to_string(),
+            replace_all: false,
+        };
+
+        tool.execute_typed(input).await.unwrap();
+
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "port = 8080");
+        assert_eq!(
+            std::fs::read_dir(workspace.join("config")).unwrap().count(),
+            1
+        );
+    }
 }
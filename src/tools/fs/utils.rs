@@ -1,11 +1,17 @@
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use ignore::WalkBuilder;
+use ignore::gitignore::Gitignore;
+use uuid::Uuid;
 
 use crate::core::error::{AgentError, Result};
 use crate::tools::types::ToolType;
 
 use super::constants::MAX_FILE_SIZE;
+use super::special_file_kind;
+
+pub const SMITHIGNORE: &str = ".smithignore";
 
 pub fn is_absolute_path(path: &str) -> bool {
     Path::new(path).is_absolute()
@@ -32,6 +38,12 @@ pub fn validate_path_exists(path: &Path, tool_type: &ToolType) -> Result<()> {
 }
 
 pub fn validate_is_file(path: &Path, tool_type: &ToolType) -> Result<()> {
+    if let Some(kind) = special_file_kind(path) {
+        return Err(AgentError::InvalidToolInput {
+            tool: tool_type.name().to_string(),
+            reason: format!("Path is a {kind}, not a regular file: {}", path.display()),
+        });
+    }
     if !path.is_file() {
         return Err(AgentError::InvalidToolInput {
             tool: tool_type.name().to_string(),
@@ -51,6 +63,36 @@ pub fn validate_is_dir(path: &Path, tool_type: &ToolType) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_not_ignored(path: &Path, tool_type: &ToolType) -> Result<()> {
+    if is_smithignored(path) {
+        return Err(AgentError::InvalidToolInput {
+            tool: tool_type.name().to_string(),
+            reason: format!("Path is excluded by {SMITHIGNORE}: {}", path.display()),
+        });
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn is_smithignored(path: &Path) -> bool {
+    let is_dir = path.is_dir();
+    for dir in path.ancestors().skip(1) {
+        let file = dir.join(SMITHIGNORE);
+        if !file.is_file() {
+            continue;
+        }
+        let (gitignore, _) = Gitignore::new(&file);
+        let matched = gitignore.matched_path_or_any_parents(path, is_dir);
+        if matched.is_ignore() {
+            return true;
+        }
+        if matched.is_whitelist() {
+            return false;
+        }
+    }
+    false
+}
+
 pub fn validate_file_size(path: &Path, tool_type: &ToolType) -> Result<u64> {
     let metadata = std::fs::metadata(path)?;
     let size = metadata.len();
@@ -63,20 +105,42 @@ pub fn validate_file_size(path: &Path, tool_type: &ToolType) -> Result<u64> {
     Ok(size)
 }
 
-pub fn atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
-    let temp_path = path.with_extension("tmp");
+pub fn atomic_write(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = &link_target(path);
+    let file_name = path
+        .file_name()
+        .map_or_else(|| "file".into(), |name| name.to_string_lossy());
+    let temp_path = path.with_file_name(format!(".{file_name}.{}.tmp", Uuid::new_v4().simple()));
 
-    if let Err(e) = std::fs::write(&temp_path, content) {
+    let result = write_synced(&temp_path, content.as_ref(), path)
+        .and_then(|()| std::fs::rename(&temp_path, path));
+    if result.is_err() {
         let _ = std::fs::remove_file(&temp_path);
-        return Err(e);
     }
+    result
+}
 
-    if let Err(e) = std::fs::rename(&temp_path, path) {
-        let _ = std::fs::remove_file(&temp_path);
-        return Err(e);
+fn link_target(path: &Path) -> PathBuf {
+    const MAX_LINKS: usize = 40;
+    let mut target = path.to_path_buf();
+    for _ in 0..MAX_LINKS {
+        let Ok(link) = std::fs::read_link(&target) else {
+            break;
+        };
+        target = target
+            .parent()
+            .map_or_else(|| link.clone(), |parent| parent.join(&link));
     }
+    target
+}
 
-    Ok(())
+fn write_synced(temp_path: &Path, content: &[u8], original: &Path) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(temp_path)?;
+    file.write_all(content)?;
+    if let Ok(metadata) = std::fs::metadata(original) {
+        file.set_permissions(metadata.permissions())?;
+    }
+    file.sync_all()
 }
 
 #[must_use]
@@ -84,7 +148,8 @@ pub fn walk_builder_with_gitignore(path: &Path, respect_gitignore: bool) -> Walk
     let mut builder = WalkBuilder::new(path);
     builder
         .hidden(false)
-        .parents(respect_gitignore)
+        .parents(true)
+        .add_custom_ignore_filename(SMITHIGNORE)
         .git_ignore(respect_gitignore)
         .git_global(respect_gitignore)
         .git_exclude(respect_gitignore);
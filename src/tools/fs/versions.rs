@@ -0,0 +1,69 @@
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::core::error::{AgentError, Result};
+use crate::ui::diff::unified_diff;
+
+const MAX_TRACKED_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Default)]
+pub struct FileVersions(Arc<Mutex<HashMap<PathBuf, String>>>);
+
+impl FileVersions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, path: &Path, content: &str) {
+        self.0
+            .lock()
+            .insert(path.to_path_buf(), content.to_string());
+    }
+
+    pub fn record_from_disk(&self, path: &Path) {
+        let small = std::fs::metadata(path).is_ok_and(|m| m.len() <= MAX_TRACKED_BYTES);
+        match super::read_text(path) {
+            Ok((content, _)) if small => self.record(path, &content),
+            _ => {
+                self.0.lock().remove(path);
+            }
+        }
+    }
+
+    pub fn check(&self, path: &Path, current: &str) -> Result<()> {
+        let seen = match self.0.lock().get(path) {
+            Some(seen) if seen != current => seen.clone(),
+            _ => return Ok(()),
+        };
+        self.record(path, current);
+        let display = path.display().to_string();
+        let diff = unified_diff(&display, &seen, current);
+        Err(AgentError::FileConflict {
+            path: display,
+            diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_reports_diff_once() {
+        let versions = FileVersions::new();
+        let path = Path::new("/tmp/lib.rs");
+        assert!(versions.check(path, "anything").is_ok());
+
+        versions.record(path, "fn a() {}\n");
+        assert!(versions.check(path, "fn a() {}\n").is_ok());
+
+        let err = versions.check(path, "fn a() {}\nfn b() {}\n").unwrap_err();
+        assert_eq!(err.code(), "ERR_FILE_CONFLICT");
+        assert!(err.to_string().contains("+fn b() {}"));
+        assert!(versions.check(path, "fn a() {}\nfn b() {}\n").is_ok());
+    }
+}
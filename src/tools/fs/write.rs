@@ -9,7 +9,10 @@ use crate::core::metadata;
 use crate::tools::{ToolType, TypedTool};
 
 use super::constants::MAX_WRITE_SIZE;
-use super::{atomic_write, validate_absolute_path};
+use super::{
+    BackupStore, FilePolicy, LineEnding, TextEncoding, format_label, read_text,
+    validate_absolute_path, write_text,
+};
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct WriteFileInput {
     pub path: String,
@@ -27,6 +30,7 @@ struct WriteResult {
     line_count: usize,
     old_content: Option<String>,
     new_content: String,
+    format: Option<String>,
 }
 fn validate_content_size(content: &str) -> Result<()> {
     if content.len() > MAX_WRITE_SIZE {
@@ -65,16 +69,33 @@ fn ensure_parent_directory(path: &Path, create: bool) -> Result<()> {
     Ok(())
 }
 
-fn execute_write(path: &Path, content: String, create_dirs: bool) -> Result<WriteResult> {
+fn execute_write(
+    path: &Path,
+    content: String,
+    create_dirs: bool,
+    backups: Option<&BackupStore>,
+) -> Result<WriteResult> {
     validate_content_size(&content)?;
     ensure_parent_directory(path, create_dirs)?;
 
-    let old_content = path
-        .is_file()
-        .then(|| std::fs::read_to_string(path).ok())
-        .flatten();
+    let existing = path.is_file().then(|| read_text(path).ok()).flatten();
+    let (encoding, line_ending) = existing
+        .as_ref()
+        .map_or((TextEncoding::Utf8, LineEnding::Lf), |(text, encoding)| {
+            (*encoding, LineEnding::detect(text))
+        });
+    let old_content = existing.map(|(text, _)| text);
+    let content = if line_ending == LineEnding::CrLf {
+        line_ending.apply(&content).into_owned()
+    } else {
+        content
+    };
 
-    atomic_write(path, &content)?;
+    if let Some(store) = backups {
+        store.backup(path)?;
+    }
+
+    write_text(path, &content, encoding, &ToolType::WriteFile)?;
 
     Ok(WriteResult {
         path: path.to_path_buf(),
@@ -82,6 +103,7 @@ fn execute_write(path: &Path, content: String, create_dirs: bool) -> Result<Writ
         line_count: content.lines().count(),
         old_content,
         new_content: content,
+        format: format_label(encoding, line_ending),
     })
 }
 
@@ -98,6 +120,7 @@ fn format_output(result: WriteResult) -> String {
             "path": result.path.to_string_lossy(),
             "old_content": result.old_content.unwrap_or_default(),
             "new_content": result.new_content,
+            "format": result.format,
         }
     });
 
@@ -107,12 +130,27 @@ fn format_output(result: WriteResult) -> String {
     )
 }
 #[derive(Default)]
-pub struct WriteFileTool;
+pub struct WriteFileTool {
+    backups: Option<BackupStore>,
+    policy: FilePolicy,
+}
 
 impl WriteFileTool {
     #[must_use]
-    pub const fn new() -> Self {
-        Self
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_backups(mut self, store: BackupStore) -> Self {
+        self.backups = Some(store);
+        self
+    }
+
+    #[must_use]
+    pub fn with_file_policy(mut self, policy: FilePolicy) -> Self {
+        self.policy = policy;
+        self
     }
 }
 
@@ -130,7 +168,13 @@ impl TypedTool for WriteFileTool {
 
     async fn execute_typed(&self, input: Self::Input) -> Result<String> {
         let path = validate_absolute_path(&input.path, &ToolType::WriteFile)?;
-        let result = execute_write(&path, input.content, input.create_dirs)?;
+        self.policy.check(&path, &ToolType::WriteFile)?;
+        let result = execute_write(
+            &path,
+            input.content,
+            input.create_dirs,
+            self.backups.as_ref(),
+        )?;
         Ok(format_output(result))
     }
 }
@@ -243,4 +287,98 @@ mod tests {
         let contents = std::fs::read_to_string(&file_path).unwrap();
         assert_eq!(contents, "Updated");
     }
+
+    #[tokio::test]
+    async fn test_write_file_keeps_line_endings_and_bom() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("App.cs");
+        std::fs::write(&file_path, "\u{feff}class App\r\n{\r\n}\r\n").unwrap();
+
+        let tool = WriteFileTool::new();
+        let input = WriteFileInput {
+            path: file_path.to_str().unwrap().to_string(),
+            content: "class App\n{\n    void Run() {}\n}\n".to_string(),
+            create_dirs: true,
+        };
+
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.contains("CRLF \u{b7} UTF-8 with BOM"));
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "\u{feff}class App\r\n{\r\n    void Run() {}\r\n}\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_keeps_backup_and_no_temp_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        std::fs::write(&file_path, "Initial").unwrap();
+
+        let store = BackupStore::new(
+            crate::config::BackupConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            temp_dir.path().to_path_buf(),
+        );
+        let tool = WriteFileTool::new().with_backups(store);
+        let input = WriteFileInput {
+            path: file_path.to_str().unwrap().to_string(),
+            content: "Updated".to_string(),
+            create_dirs: true,
+        };
+
+        tool.execute_typed(input).await.unwrap();
+
+        let backups: Vec<_> = std::fs::read_dir(temp_dir.path().join(".smith/backups"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), "Initial");
+
+        let leftovers = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_file_through_workspace_symlink() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = temp_dir.path();
+        std::fs::create_dir(workspace.join("config")).unwrap();
+        let target = workspace.join("config/app.toml");
+        let link = workspace.join("app.toml");
+        std::fs::write(&target, "old").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let policy =
+            crate::tools::FilePolicy::new(crate::config::SymlinkPolicy::Workspace, workspace);
+        let tool = WriteFileTool::new().with_file_policy(policy);
+        let input = WriteFileInput {
+            path: link.to_str().unwrap().to_string(),
+            content: "new".to_string(),
+            create_dirs: false,
+        };
+
+        tool.execute_typed(input).await.unwrap();
+
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(
+            std::fs::read_dir(workspace.join("config")).unwrap().count(),
+            1
+        );
+    }
 }
@@ -0,0 +1,351 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::config::GitHubConfig;
+use crate::core::error::{AgentError, Result};
+use crate::tools::WorkingDirectory;
+
+mod tools;
+
+pub use tools::{CreatePrTool, GetIssueTool, ListPrCommentsTool};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_COMMENTS: usize = 50;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub user: User,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub html_url: String,
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Comment {
+    pub user: User,
+    #[serde(default)]
+    pub body: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub line: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    pub body: String,
+    pub head: String,
+    pub base: String,
+    pub draft: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    default_branch: String,
+}
+
+#[must_use]
+pub fn parse_remote(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("https://github.com/"))
+        .or_else(|| url.strip_prefix("http://github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/'))
+        .then(|| format!("{owner}/{name}"))
+}
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    http: reqwest::Client,
+    config: GitHubConfig,
+    cwd: WorkingDirectory,
+}
+
+impl GitHubClient {
+    #[must_use]
+    pub fn new(config: GitHubConfig, cwd: WorkingDirectory) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("smith/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { http, config, cwd }
+    }
+
+    async fn git(&self, args: &[&str]) -> Option<String> {
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(self.cwd.get())
+            .output()
+            .await
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|out| !out.is_empty())
+    }
+
+    async fn token(&self) -> Option<String> {
+        let from_env = [self.config.token_env.as_str(), "GH_TOKEN"]
+            .into_iter()
+            .find_map(|key| std::env::var(key).ok().filter(|v| !v.trim().is_empty()));
+        if from_env.is_some() {
+            return from_env;
+        }
+        let output = tokio::process::Command::new("gh")
+            .args(["auth", "token"])
+            .output()
+            .await
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|token| !token.is_empty())
+    }
+
+    pub async fn repo(&self) -> Result<String> {
+        if let Some(repo) = &self.config.repo {
+            return Ok(repo.clone());
+        }
+        self.git(&["remote", "get-url", "origin"])
+            .await
+            .as_deref()
+            .and_then(parse_remote)
+            .ok_or_else(|| {
+                AgentError::ToolExecution(
+                    "Could not determine the GitHub repository from the origin remote; set tools.github.repo".to_string(),
+                )
+            })
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let mut request = request
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = self.token().await {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AgentError::ToolExecution(format!("GitHub request failed: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|v| v.get("message")?.as_str().map(str::to_string))
+                .unwrap_or(body);
+            return Err(AgentError::ToolExecution(format!(
+                "GitHub API returned {status}: {message}"
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| AgentError::ToolExecution(format!("Invalid GitHub response: {e}")))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{path}", self.config.api_url.trim_end_matches('/'));
+        self.send(self.http.get(url)).await
+    }
+
+    pub async fn issue(&self, number: u64) -> Result<Issue> {
+        let repo = self.repo().await?;
+        self.get(&format!("/repos/{repo}/issues/{number}")).await
+    }
+
+    pub async fn issue_comments(&self, number: u64) -> Result<Vec<Comment>> {
+        let repo = self.repo().await?;
+        self.get(&format!(
+            "/repos/{repo}/issues/{number}/comments?per_page={MAX_COMMENTS}"
+        ))
+        .await
+    }
+
+    pub async fn review_comments(&self, number: u64) -> Result<Vec<Comment>> {
+        let repo = self.repo().await?;
+        self.get(&format!(
+            "/repos/{repo}/pulls/{number}/comments?per_page={MAX_COMMENTS}"
+        ))
+        .await
+    }
+
+    pub async fn default_branch(&self) -> Result<String> {
+        let repo = self.repo().await?;
+        let repository: Repository = self.get(&format!("/repos/{repo}")).await?;
+        Ok(repository.default_branch)
+    }
+
+    pub async fn current_branch(&self) -> Result<String> {
+        self.git(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .await
+            .filter(|branch| branch != "HEAD")
+            .ok_or_else(|| {
+                AgentError::ToolExecution("Not on a git branch; pass `head` explicitly".to_string())
+            })
+    }
+
+    pub async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest> {
+        let repo = self.repo().await?;
+        let url = format!(
+            "{}/repos/{repo}/pulls",
+            self.config.api_url.trim_end_matches('/')
+        );
+        self.send(self.http.post(url).json(pr)).await
+    }
+}
+
+#[must_use]
+pub fn format_issue(issue: &Issue, comments: &[Comment]) -> String {
+    let kind = if issue.pull_request.is_some() {
+        "Pull request"
+    } else {
+        "Issue"
+    };
+    let mut out = format!("{kind} #{}: {}\n", issue.number, issue.title);
+    let _ = writeln!(out, "State: {}", issue.state);
+    let _ = writeln!(out, "Author: {}", issue.user.login);
+    if !issue.labels.is_empty() {
+        let labels: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+        let _ = writeln!(out, "Labels: {}", labels.join(", "));
+    }
+    let _ = writeln!(out, "URL: {}", issue.html_url);
+    let body = issue.body.as_deref().map(str::trim).unwrap_or_default();
+    let _ = write!(
+        out,
+        "\n{}\n",
+        if body.is_empty() {
+            "(no description)"
+        } else {
+            body
+        }
+    );
+    if !comments.is_empty() {
+        let _ = write!(out, "\n{}", format_comments(comments));
+    }
+    out
+}
+
+#[must_use]
+pub fn format_comments(comments: &[Comment]) -> String {
+    if comments.is_empty() {
+        return "No comments".to_string();
+    }
+    let mut out = format!("{} comment(s):\n", comments.len());
+    for comment in comments {
+        let location = match (&comment.path, comment.line) {
+            (Some(path), Some(line)) => format!(" on {path}:{line}"),
+            (Some(path), None) => format!(" on {path}"),
+            _ => String::new(),
+        };
+        let _ = write!(
+            out,
+            "\n--- {} ({}){location}\n{}\n",
+            comment.user.login,
+            comment.created_at,
+            comment.body.trim()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(login: &str, body: &str) -> Comment {
+        Comment {
+            user: User {
+                login: login.to_string(),
+            },
+            body: body.to_string(),
+            created_at: "2026-01-02T03:04:05Z".to_string(),
+            path: None,
+            line: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_remote() {
+        assert_eq!(
+            parse_remote("git@github.com:buyukakyuz/smith.git").as_deref(),
+            Some("buyukakyuz/smith")
+        );
+        assert_eq!(
+            parse_remote("https://github.com/buyukakyuz/smith\n").as_deref(),
+            Some("buyukakyuz/smith")
+        );
+        assert_eq!(
+            parse_remote("ssh://git@github.com/a/b.git").as_deref(),
+            Some("a/b")
+        );
+        assert_eq!(parse_remote("https://gitlab.com/a/b.git"), None);
+        assert_eq!(parse_remote("https://github.com/a"), None);
+    }
+
+    #[test]
+    fn test_format_issue_with_comments() {
+        let issue = Issue {
+            number: 123,
+            title: "Crash on empty input".to_string(),
+            state: "open".to_string(),
+            body: Some("Steps to reproduce...\n".to_string()),
+            user: User {
+                login: "alice".to_string(),
+            },
+            labels: vec![Label {
+                name: "bug".to_string(),
+            }],
+            html_url: "https://github.com/a/b/issues/123".to_string(),
+            pull_request: None,
+        };
+        let mut review = comment("bob", "Handle None here");
+        review.path = Some("src/lib.rs".to_string());
+        review.line = Some(42);
+
+        let text = format_issue(&issue, &[comment("carol", "Same here"), review]);
+
+        assert!(text.starts_with("Issue #123: Crash on empty input\n"));
+        assert!(text.contains("Labels: bug"));
+        assert!(text.contains("\nSteps to reproduce...\n"));
+        assert!(text.contains("2 comment(s):"));
+        assert!(text.contains("--- bob (2026-01-02T03:04:05Z) on src/lib.rs:42\nHandle None here"));
+        assert_eq!(format_comments(&[]), "No comments");
+    }
+}
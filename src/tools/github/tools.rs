@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{GitHubClient, NewPullRequest, format_comments, format_issue};
+use crate::core::error::{AgentError, Result};
+use crate::tools::{ToolType, TypedTool};
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetIssueInput {
+    pub number: u64,
+    #[serde(default = "default_true")]
+    #[schemars(default = "default_true")]
+    pub include_comments: bool,
+}
+
+pub struct GetIssueTool {
+    client: GitHubClient,
+}
+
+impl GetIssueTool {
+    #[must_use]
+    pub const fn new(client: GitHubClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TypedTool for GetIssueTool {
+    type Input = GetIssueInput;
+
+    fn name(&self) -> &'static str {
+        "get_issue"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fetch a GitHub issue or pull request from the current repository by number, including its description, labels and comments."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let issue = self.client.issue(input.number).await?;
+        let comments = if input.include_comments {
+            self.client.issue_comments(input.number).await?
+        } else {
+            Vec::new()
+        };
+        Ok(format_issue(&issue, &comments))
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListPrCommentsInput {
+    pub number: u64,
+}
+
+pub struct ListPrCommentsTool {
+    client: GitHubClient,
+}
+
+impl ListPrCommentsTool {
+    #[must_use]
+    pub const fn new(client: GitHubClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TypedTool for ListPrCommentsTool {
+    type Input = ListPrCommentsInput;
+
+    fn name(&self) -> &'static str {
+        "list_pr_comments"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the conversation and inline review comments on a GitHub pull request, oldest first. Review comments include the file and line they refer to."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let mut comments = self.client.issue_comments(input.number).await?;
+        comments.extend(self.client.review_comments(input.number).await?);
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(format_comments(&comments))
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreatePrInput {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub head: Option<String>,
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+pub struct CreatePrTool {
+    client: GitHubClient,
+}
+
+impl CreatePrTool {
+    #[must_use]
+    pub const fn new(client: GitHubClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TypedTool for CreatePrTool {
+    type Input = CreatePrInput;
+
+    fn name(&self) -> &'static str {
+        "create_pr"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open a GitHub pull request. `head` defaults to the current branch and `base` to the repository's default branch. Push the branch with bash first; reference issues in the body with `Fixes #123`."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        if input.title.trim().is_empty() {
+            return Err(AgentError::InvalidToolInput {
+                tool: ToolType::CreatePr.name().to_string(),
+                reason: "Pull request title must not be empty".to_string(),
+            });
+        }
+        let head = match input.head {
+            Some(head) => head,
+            None => self.client.current_branch().await?,
+        };
+        let base = match input.base {
+            Some(base) => base,
+            None => self.client.default_branch().await?,
+        };
+        if head == base {
+            return Err(AgentError::InvalidToolInput {
+                tool: ToolType::CreatePr.name().to_string(),
+                reason: format!("Head and base are both '{head}'; create a branch first"),
+            });
+        }
+        let pr = self
+            .client
+            .create_pull_request(&NewPullRequest {
+                title: input.title,
+                body: input.body,
+                head: head.clone(),
+                base: base.clone(),
+                draft: input.draft,
+            })
+            .await?;
+        Ok(format!(
+            "Opened pull request #{} ({head} -> {base}): {}",
+            pr.number, pr.html_url
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GitHubConfig;
+    use crate::tools::{Tool, WorkingDirectory};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_create_pr_posts_to_repository() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            while !String::from_utf8_lossy(&request).contains("\"draft\"") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = r#"{"number":7,"html_url":"https://github.com/o/r/pull/7"}"#;
+            let response = format!(
+                "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let config = GitHubConfig {
+            api_url: format!("http://{addr}"),
+            token_env: "SMITH_TEST_UNSET_TOKEN".to_string(),
+            repo: Some("o/r".to_string()),
+            ..GitHubConfig::default()
+        };
+        let tool = CreatePrTool::new(GitHubClient::new(config, WorkingDirectory::current()));
+        let output = tool
+            .execute(serde_json::json!({
+                "title": "Fix crash",
+                "body": "Fixes #123",
+                "head": "fix-crash",
+                "base": "main"
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output,
+            "Opened pull request #7 (fix-crash -> main): https://github.com/o/r/pull/7"
+        );
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /repos/o/r/pulls "));
+        assert!(request.contains(r#""head":"fix-crash""#));
+
+        let same = tool
+            .execute(serde_json::json!({"title": "x", "head": "main", "base": "main"}))
+            .await;
+        assert!(same.is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::core::error::Result;
+use crate::memories::MemoryStore;
+use crate::tools::TypedTool;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SaveMemoryInput {
+    pub memory: String,
+}
+
+pub struct SaveMemoryTool {
+    store: MemoryStore,
+}
+
+impl SaveMemoryTool {
+    #[must_use]
+    pub const fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl TypedTool for SaveMemoryTool {
+    type Input = SaveMemoryInput;
+
+    fn name(&self) -> &'static str {
+        "save_memory"
+    }
+
+    fn description(&self) -> &'static str {
+        "Remember a durable user preference (coding style, tooling, tone) for all future sessions. Only save preferences the user stated or clearly confirmed, as one short sentence; the user is asked to approve every memory."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        if self.store.add(&input.memory)? {
+            Ok(format!("Remembered: {}", input.memory.trim()))
+        } else {
+            Ok("Already remembered".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_memory_appends_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = MemoryStore::new(temp.path().join("memories.md"));
+        let tool = SaveMemoryTool::new(store.clone());
+
+        let first = tool
+            .execute_typed(SaveMemoryInput {
+                memory: "prefer snake_case".to_string(),
+            })
+            .await
+            .unwrap();
+        let second = tool
+            .execute_typed(SaveMemoryInput {
+                memory: "prefer snake_case".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(first, "Remembered: prefer snake_case");
+        assert_eq!(second, "Already remembered");
+        assert_eq!(store.list(), vec!["prefer snake_case"]);
+    }
+}
@@ -1,27 +1,128 @@
 use async_trait::async_trait;
 use schemars::JsonSchema;
+use std::sync::Arc;
 
+use crate::config::ToolsConfig;
 use crate::core::error::Result;
 
+pub mod artifacts;
 pub mod context;
+pub mod delegate;
 pub mod engine;
 pub mod error_hints;
 pub mod events;
 pub mod executor;
 pub mod fs;
+pub mod github;
+pub mod memory;
+pub mod output;
+pub mod pins;
 pub mod registry;
 pub mod result;
+pub mod scratch;
+pub mod screening;
 pub mod shell;
+pub mod sql;
+pub mod table;
 pub mod types;
+pub mod watcher;
 
-pub use context::ToolContext;
+pub use artifacts::{Artifact, ArtifactStore, MarkArtifactTool};
+pub use context::{ToolContext, WorkingDirectory};
+pub use delegate::DelegateTool;
 pub use engine::ToolEngine;
 pub use events::{ToolEventEmitter, ToolEventHandler};
 pub use executor::ToolExecutor;
-pub use fs::{GlobTool, GrepTool, ListDirTool, ReadFileTool, UpdateFileTool, WriteFileTool};
+pub use fs::{
+    BackupStore, DocumentAttachments, FilePolicy, FileVersions, GlobTool, GrepTool, ListDirTool,
+    PreviewDataTool, ReadDocumentTool, ReadFileTool, UpdateFileTool, WriteFileTool,
+};
+pub use github::{CreatePrTool, GetIssueTool, GitHubClient, ListPrCommentsTool};
+pub use memory::SaveMemoryTool;
+pub use output::{ReadToolOutputTool, ToolOutputStore};
+pub use pins::{PinnedFiles, format_pins};
 pub use registry::ToolRegistry;
+pub use scratch::ScratchDir;
+pub use screening::InjectionScreen;
 pub use shell::BashTool;
+pub use sql::SqlQueryTool;
 pub use types::{ToolState, ToolType};
+pub use watcher::FileWatcher;
+
+#[must_use]
+pub fn default_tools() -> Vec<Arc<dyn Tool>> {
+    vec![
+        Arc::new(ReadFileTool::new()),
+        Arc::new(WriteFileTool::new()),
+        Arc::new(UpdateFileTool::new()),
+        Arc::new(ListDirTool::new()),
+        Arc::new(GlobTool::new()),
+        Arc::new(GrepTool::new()),
+        Arc::new(BashTool::new()),
+    ]
+}
+
+#[must_use]
+pub fn configured_tools(config: &ToolsConfig, context: &ToolContext) -> Vec<Arc<dyn Tool>> {
+    if let Some(dir) = &config.bash.working_dir {
+        context.cwd.set(context.cwd.resolve(dir));
+    }
+
+    let backups = BackupStore::new(config.backups.clone(), context.cwd.get());
+    let policy = FilePolicy::new(config.files.symlinks, &context.cwd.get());
+    context.scratch.set_keep(config.scratch.keep);
+    let mut bash = BashTool::with_config(config.bash.clone(), context.cwd.clone())
+        .with_output_store(context.outputs.clone())
+        .with_sandbox_trust(context.sandbox.clone());
+    if config.scratch.enabled {
+        bash = bash.with_scratch(context.scratch.clone());
+    }
+    let mut tools: Vec<Arc<dyn Tool>> = vec![
+        Arc::new(
+            ReadFileTool::new()
+                .with_screening(InjectionScreen::new(config.screening.clone()))
+                .with_file_policy(policy.clone()),
+        ),
+        Arc::new(
+            WriteFileTool::new()
+                .with_backups(backups.clone())
+                .with_file_policy(policy.clone()),
+        ),
+        Arc::new(
+            UpdateFileTool::new()
+                .with_backups(backups)
+                .with_versions(context.versions.clone())
+                .with_file_policy(policy.clone()),
+        ),
+        Arc::new(ReadDocumentTool::new(context.documents.clone()).with_file_policy(policy.clone())),
+        Arc::new(PreviewDataTool::new()),
+        Arc::new(ListDirTool::new()),
+        Arc::new(GlobTool::new()),
+        Arc::new(GrepTool::new().with_file_policy(policy)),
+        Arc::new(bash),
+        Arc::new(ReadToolOutputTool::new(context.outputs.clone())),
+        Arc::new(MarkArtifactTool::new(
+            context.artifacts.clone(),
+            context.cwd.clone(),
+        )),
+    ];
+    if config.github.enabled {
+        let client = GitHubClient::new(config.github.clone(), context.cwd.clone());
+        tools.push(Arc::new(GetIssueTool::new(client.clone())));
+        tools.push(Arc::new(ListPrCommentsTool::new(client.clone())));
+        tools.push(Arc::new(CreatePrTool::new(client)));
+    }
+    if !config.sql.connections.is_empty() {
+        tools.push(Arc::new(SqlQueryTool::new(
+            config.sql.clone(),
+            context.cwd.clone(),
+        )));
+    }
+    if let Some(allowed) = &config.allowed {
+        tools.retain(|tool| allowed.iter().any(|name| name == tool.name()));
+    }
+    tools
+}
 
 #[async_trait]
 pub trait Tool: Send + Sync {
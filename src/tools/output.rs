@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::core::error::{AgentError, Result};
+use crate::tools::{ToolType, TypedTool};
+
+const MAX_STORED_OUTPUTS: usize = 20;
+const DEFAULT_PAGE_BYTES: usize = 20_000;
+
+#[derive(Debug, Default)]
+struct StoredOutputs {
+    next_id: usize,
+    entries: VecDeque<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutputStore(Arc<RwLock<StoredOutputs>>);
+
+impl ToolOutputStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn store(&self, output: String) -> String {
+        let mut stored = self.0.write();
+        stored.next_id += 1;
+        let handle = format!("output-{}", stored.next_id);
+        stored.entries.push_back((handle.clone(), output));
+        if stored.entries.len() > MAX_STORED_OUTPUTS {
+            stored.entries.pop_front();
+        }
+        handle
+    }
+
+    #[must_use]
+    pub fn get(&self, handle: &str) -> Option<String> {
+        self.0
+            .read()
+            .entries
+            .iter()
+            .find(|(id, _)| id == handle)
+            .map(|(_, output)| output.clone())
+    }
+
+    #[must_use]
+    pub fn truncate(&self, output: String, max_bytes: usize) -> (String, bool) {
+        if output.len() <= max_bytes {
+            return (output, false);
+        }
+        let cut = output.floor_char_boundary(max_bytes);
+        let preview = output[..cut].to_string();
+        let total = output.len();
+        let handle = self.store(output);
+        let message = format!(
+            "\n\n[Output truncated: {total} bytes total, showing first {cut} bytes. \
+             Call read_tool_output with handle \"{handle}\" and offset {cut} to read the rest]"
+        );
+        (format!("{preview}{message}"), true)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReadToolOutputInput {
+    pub handle: String,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub struct ReadToolOutputTool {
+    store: ToolOutputStore,
+}
+
+impl ReadToolOutputTool {
+    #[must_use]
+    pub const fn new(store: ToolOutputStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl TypedTool for ReadToolOutputTool {
+    type Input = ReadToolOutputInput;
+
+    fn name(&self) -> &'static str {
+        "read_tool_output"
+    }
+
+    fn description(&self) -> &'static str {
+        "Page through the full output of an earlier tool call that was truncated. Pass the handle from the truncation notice, a byte offset, and optionally a byte limit (default 20000)."
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let output = self
+            .store
+            .get(&input.handle)
+            .ok_or_else(|| AgentError::InvalidToolInput {
+                tool: ToolType::ReadToolOutput.name().to_string(),
+                reason: format!("No stored output for handle \"{}\"", input.handle),
+            })?;
+        let start = output.floor_char_boundary(input.offset);
+        let limit = input.limit.unwrap_or(DEFAULT_PAGE_BYTES).max(1);
+        let end = output.floor_char_boundary(start.saturating_add(limit));
+        let end = if end == start {
+            output.ceil_char_boundary(start + 1)
+        } else {
+            end
+        };
+        let mut page = output[start..end].to_string();
+        if end < output.len() {
+            let _ = write!(
+                page,
+                "\n\n[Bytes {start}-{end} of {}. Continue with offset {end}]",
+                output.len()
+            );
+        } else {
+            let _ = write!(page, "\n\n[End of output, {} bytes]", output.len());
+        }
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_truncated_output_can_be_paged() {
+        let store = ToolOutputStore::new();
+        let (preview, truncated) = store.truncate("short".to_string(), 10);
+        assert_eq!(preview, "short");
+        assert!(!truncated);
+
+        let (preview, truncated) = store.truncate("0123456789abcdef".to_string(), 10);
+        assert!(truncated);
+        assert!(preview.starts_with("0123456789\n"));
+        assert!(preview.contains("handle \"output-1\" and offset 10"));
+
+        let tool = ReadToolOutputTool::new(store);
+        let page = tool
+            .execute_typed(ReadToolOutputInput {
+                handle: "output-1".to_string(),
+                offset: 10,
+                limit: Some(4),
+            })
+            .await
+            .unwrap();
+        assert!(page.starts_with("abcd\n"));
+        assert!(page.contains("Continue with offset 14"));
+
+        let rest = tool
+            .execute_typed(ReadToolOutputInput {
+                handle: "output-1".to_string(),
+                offset: 14,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert!(rest.starts_with("ef\n"));
+        assert!(rest.contains("End of output, 16 bytes"));
+
+        let missing = tool
+            .execute_typed(ReadToolOutputInput {
+                handle: "output-9".to_string(),
+                offset: 0,
+                limit: None,
+            })
+            .await;
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_store_keeps_recent_outputs() {
+        let store = ToolOutputStore::new();
+        for i in 0..=MAX_STORED_OUTPUTS {
+            store.store(i.to_string());
+        }
+        assert_eq!(store.get("output-1"), None);
+        assert_eq!(store.get("output-2").as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,186 @@
+use parking_lot::RwLock;
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::attachment::fit_to_budget;
+use crate::core::error::{AgentError, Result};
+use crate::core::memory::estimate_tokens;
+use crate::tools::fs::read_text;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub path: PathBuf,
+    pub tokens: Option<usize>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PinnedFiles {
+    paths: Arc<RwLock<Vec<PathBuf>>>,
+    budget: Arc<AtomicUsize>,
+}
+
+impl PinnedFiles {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_budget(&self, budget_tokens: usize) {
+        self.budget.store(budget_tokens, Ordering::Relaxed);
+    }
+
+    pub fn pin(&self, path: PathBuf) -> Result<bool> {
+        if !path.is_file() {
+            return Err(AgentError::InvalidState(format!(
+                "{} is not a file",
+                path.display()
+            )));
+        }
+        read_text(&path)?;
+        let mut paths = self.paths.write();
+        if paths.contains(&path) {
+            return Ok(false);
+        }
+        paths.push(path);
+        drop(paths);
+        Ok(true)
+    }
+
+    #[must_use]
+    pub fn unpin(&self, path: &Path) -> bool {
+        let mut paths = self.paths.write();
+        let before = paths.len();
+        paths.retain(|pinned| pinned != path);
+        paths.len() != before
+    }
+
+    #[must_use]
+    pub fn unpin_index(&self, index: usize) -> Option<PathBuf> {
+        let mut paths = self.paths.write();
+        (index < paths.len()).then(|| paths.remove(index))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.read().is_empty()
+    }
+
+    #[must_use]
+    pub fn pins(&self) -> Vec<Pin> {
+        let budget = self.budget.load(Ordering::Relaxed);
+        self.paths
+            .read()
+            .iter()
+            .map(|path| {
+                let content = read_text(path).ok();
+                let fitted = content
+                    .as_ref()
+                    .map(|(text, _)| fit_to_budget(text.trim_end(), budget));
+                Pin {
+                    path: path.clone(),
+                    tokens: fitted.as_ref().map(|text| estimate_tokens(text.len())),
+                    truncated: matches!(fitted, Some(Cow::Owned(_))),
+                }
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn prompt_section(&self) -> Option<String> {
+        let paths = self.paths.read().clone();
+        if paths.is_empty() {
+            return None;
+        }
+        let budget = self.budget.load(Ordering::Relaxed);
+        let mut section = String::from(
+            "# Pinned files\n\nThe user pinned these files. Their contents below are re-read before every request, so they are always current; do not read them again with tools.",
+        );
+        for path in &paths {
+            match read_text(path) {
+                Ok((text, _)) => {
+                    let _ = write!(
+                        section,
+                        "\n\n<pinned_file path=\"{}\">\n{}\n</pinned_file>",
+                        path.display(),
+                        fit_to_budget(text.trim_end(), budget)
+                    );
+                }
+                Err(e) => {
+                    let _ = write!(
+                        section,
+                        "\n\n<pinned_file path=\"{}\">\n(could not be read: {e})\n</pinned_file>",
+                        path.display()
+                    );
+                }
+            }
+        }
+        Some(section)
+    }
+}
+
+#[must_use]
+pub fn format_pins(pins: &[Pin], cwd: &Path) -> String {
+    if pins.is_empty() {
+        return "No pinned files. /pin <path> keeps a file's current contents in every request."
+            .to_string();
+    }
+    let mut output = format!("{} pinned file(s):", pins.len());
+    for (index, pin) in pins.iter().enumerate() {
+        let path = pin.path.strip_prefix(cwd).unwrap_or(&pin.path);
+        let truncated = if pin.truncated { ", truncated" } else { "" };
+        let cost = pin.tokens.map_or_else(
+            || "unreadable".to_string(),
+            |tokens| format!("~{tokens} tokens{truncated}"),
+        );
+        let _ = write!(output, "\n{:>3}. {} ({cost})", index + 1, path.display());
+    }
+    let total: usize = pins.iter().filter_map(|pin| pin.tokens).sum();
+    let _ = write!(
+        output,
+        "\n\n~{total} tokens per request. /unpin <n|path> removes one"
+    );
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pins_refresh_and_unpin() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let notes = temp.path().join("notes.md");
+        let big = temp.path().join("big.log");
+        std::fs::write(&notes, "first draft\n").unwrap();
+        std::fs::write(&big, "line\n".repeat(10_000)).unwrap();
+
+        let pins = PinnedFiles::new();
+        pins.set_budget(100);
+        assert!(pins.prompt_section().is_none());
+        assert!(pins.pin(notes.clone()).unwrap());
+        assert!(!pins.pin(notes.clone()).unwrap());
+        assert!(pins.pin(big.clone()).unwrap());
+        assert!(pins.pin(temp.path().to_path_buf()).is_err());
+
+        std::fs::write(&notes, "second draft\n").unwrap();
+        let section = pins.prompt_section().unwrap();
+        assert!(section.contains(&format!(
+            "<pinned_file path=\"{}\">\nsecond draft\n</pinned_file>",
+            notes.display()
+        )));
+        assert!(section.contains("lines omitted to fit the 100-token attachment budget"));
+
+        let listing = format_pins(&pins.pins(), temp.path());
+        assert!(listing.contains("  1. notes.md (~3 tokens)"));
+        assert!(listing.contains("  2. big.log (~"));
+        assert!(listing.contains("tokens, truncated)"));
+
+        assert!(pins.unpin(&notes));
+        assert_eq!(pins.unpin_index(0), Some(big));
+        assert!(pins.is_empty());
+    }
+}
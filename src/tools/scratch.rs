@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
+
+pub const SCRATCH_ENV: &str = "SCRATCH";
+
+#[derive(Debug)]
+struct ScratchInner {
+    path: PathBuf,
+    keep: AtomicBool,
+}
+
+impl Drop for ScratchInner {
+    fn drop(&mut self) {
+        if !self.path.exists() {
+            return;
+        }
+        if self.keep.load(Ordering::Relaxed) {
+            tracing::info!("Keeping scratch directory {}", self.path.display());
+        } else if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            tracing::warn!(
+                "Failed to remove scratch directory {}: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScratchDir(Arc<ScratchInner>);
+
+impl ScratchDir {
+    #[must_use]
+    pub fn new(base: &Path) -> Self {
+        Self(Arc::new(ScratchInner {
+            path: base.join(format!("smith-scratch-{}", Uuid::new_v4())),
+            keep: AtomicBool::new(false),
+        }))
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    pub fn ensure(&self) -> std::io::Result<&Path> {
+        std::fs::create_dir_all(&self.0.path)?;
+        Ok(&self.0.path)
+    }
+
+    pub fn set_keep(&self, keep: bool) {
+        self.0.keep.store(keep, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn prompt_section(&self) -> String {
+        format!(
+            "# Scratch directory\n\nPut temporary scripts, notes and throwaway artifacts in {} (available as ${SCRATCH_ENV} in bash) instead of the repository. It is private to this session and cleaned up automatically.",
+            self.path().display()
+        )
+    }
+}
+
+impl Default for ScratchDir {
+    fn default() -> Self {
+        Self::new(&std::env::temp_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratch_dir_removed_unless_kept() {
+        let base = tempfile::TempDir::new().unwrap();
+
+        let scratch = ScratchDir::new(base.path());
+        assert!(!scratch.path().exists());
+        let path = scratch.ensure().unwrap().to_path_buf();
+        std::fs::write(path.join("probe.py"), "print(1)").unwrap();
+        let clone = scratch.clone();
+        drop(scratch);
+        assert!(path.exists());
+        drop(clone);
+        assert!(!path.exists());
+
+        let kept = ScratchDir::new(base.path());
+        kept.set_keep(true);
+        let path = kept.ensure().unwrap().to_path_buf();
+        assert!(kept.prompt_section().contains("$SCRATCH"));
+        drop(kept);
+        assert!(path.exists());
+    }
+}
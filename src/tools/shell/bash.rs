@@ -4,19 +4,25 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::LazyLock;
 use std::time::Duration;
 use tokio::io::AsyncReadExt;
-use tokio::process::Command;
 use tokio::time::timeout;
+use uuid::Uuid;
 
+use super::sandbox::{
+    self, ContainerBackend, ExecutionBackend, HostBackend, Invocation, SandboxTrust,
+};
+use crate::config::{BashConfig, SandboxOverride};
 use crate::core::error::{AgentError, Result};
-use crate::tools::{ToolType, TypedTool};
+use crate::tools::scratch::{SCRATCH_ENV, ScratchDir};
+use crate::tools::{ToolOutputStore, ToolType, TypedTool, WorkingDirectory};
 
 const DEFAULT_TIMEOUT_SECS: u64 = 120;
 const MAX_OUTPUT_SIZE: usize = 1024 * 1024;
+const CWD_FILE: &str = "cwd";
 
 #[allow(clippy::expect_used)]
 static ANSI_REGEX: LazyLock<Regex> =
@@ -35,43 +41,148 @@ pub struct BashInput {
     pub timeout_secs: Option<u64>,
     #[serde(default)]
     pub env: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub unsandboxed: bool,
 }
 
-pub struct BashTool;
+pub struct BashTool {
+    cwd: WorkingDirectory,
+    workspace: PathBuf,
+    host: HostBackend,
+    backend: Box<dyn ExecutionBackend>,
+    untrusted: Option<SandboxOverride>,
+    trust: Option<SandboxTrust>,
+    outputs: ToolOutputStore,
+    scratch: Option<ScratchDir>,
+}
 
 impl BashTool {
     #[must_use]
-    pub const fn new() -> Self {
-        Self
+    pub fn new() -> Self {
+        Self::with_config(BashConfig::default(), WorkingDirectory::current())
+    }
+
+    #[must_use]
+    pub fn with_config(config: BashConfig, cwd: WorkingDirectory) -> Self {
+        let workspace = cwd.get();
+        let project = config.sandbox.for_project(&workspace);
+        Self {
+            backend: sandbox::backend_for(&config, &project.config),
+            untrusted: project.untrusted,
+            trust: None,
+            host: HostBackend::new(config),
+            cwd,
+            workspace,
+            outputs: ToolOutputStore::new(),
+            scratch: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_output_store(mut self, outputs: ToolOutputStore) -> Self {
+        self.outputs = outputs;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sandbox_trust(mut self, trust: SandboxTrust) -> Self {
+        if let Some(sandbox) = self.untrusted.take() {
+            trust.offer(sandbox);
+        }
+        self.trust = Some(trust);
+        self
+    }
+
+    #[must_use]
+    pub fn with_scratch(mut self, scratch: ScratchDir) -> Self {
+        self.scratch = Some(scratch);
+        self
+    }
+
+    fn track_cwd(&self, state_dir: &Path) {
+        if let Ok(contents) = std::fs::read_to_string(state_dir.join(CWD_FILE)) {
+            let dir = PathBuf::from(contents.trim_end());
+            if dir.is_dir() {
+                self.cwd.set(dir);
+            }
+        }
+        let _ = std::fs::remove_dir_all(state_dir);
+    }
+
+    fn scratch_dir(&self, env: &mut HashMap<String, String>) -> Result<Option<&Path>> {
+        let Some(scratch) = &self.scratch else {
+            return Ok(None);
+        };
+        let dir = scratch.ensure()?;
+        env.insert(SCRATCH_ENV.to_string(), dir.display().to_string());
+        Ok(Some(dir))
+    }
+
+    fn trusted_backend(&self) -> Option<ContainerBackend> {
+        let sandbox = self.trust.as_ref()?.trusted()?;
+        let runtime = sandbox.backend.runtime()?;
+        Some(ContainerBackend::new(runtime, sandbox))
     }
 
     async fn execute_command(
+        &self,
         command: &str,
         working_dir: Option<&Path>,
         timeout_secs: u64,
         env: Option<&HashMap<String, String>>,
+        unsandboxed: bool,
     ) -> Result<String> {
-        let wrapped_command = format!("( {command} ) 2>&1");
-
-        let mut cmd = Command::new("sh");
-        cmd.arg("-c")
-            .arg(&wrapped_command)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null());
+        let id = Uuid::new_v4().to_string();
+        let state_dir = if working_dir.is_none() {
+            let dir = std::env::temp_dir().join(format!("smith-bash-{id}"));
+            std::fs::create_dir_all(&dir)?;
+            Some(dir)
+        } else {
+            None
+        };
+        let wrapped_command = if working_dir.is_some() {
+            format!("( {command} ) 2>&1")
+        } else {
+            format!("trap 'pwd > \"$SMITH_CWD_FILE\"' EXIT\n{{ {command}\n}} 2>&1")
+        };
 
-        if let Some(dir) = working_dir {
-            cmd.current_dir(dir);
+        let mut env = env.cloned().unwrap_or_default();
+        if let Some(dir) = &state_dir {
+            env.insert(
+                "SMITH_CWD_FILE".to_string(),
+                dir.join(CWD_FILE).display().to_string(),
+            );
         }
+        let scratch_dir = self.scratch_dir(&mut env)?;
+        let cwd = working_dir.map_or_else(|| self.cwd.get(), Path::to_path_buf);
+        let trusted = self.trusted_backend();
+        let backend: &dyn ExecutionBackend = match &trusted {
+            _ if unsandboxed => &self.host,
+            Some(trusted) => trusted,
+            None => self.backend.as_ref(),
+        };
 
-        if let Some(env_vars) = env {
-            for (key, value) in env_vars {
-                cmd.env(key, value);
-            }
-        }
+        let mut cmd = backend.command(&Invocation {
+            id: &id,
+            script: &wrapped_command,
+            cwd: &cwd,
+            workspace: &self.workspace,
+            state_dir: state_dir.as_deref(),
+            scratch_dir,
+            env: Some(&env),
+        });
+        cmd.stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
 
         let mut child = cmd
             .spawn()
             .map_err(|e| AgentError::ToolExecution(format!("Failed to spawn command: {e}")))?;
+        let mut running = Running {
+            backend,
+            id: &id,
+            finished: false,
+        };
 
         let mut stdout = child
             .stdout
@@ -87,16 +198,22 @@ impl BashTool {
 
             read_result?;
 
-            let output_str = if output_data.len() > MAX_OUTPUT_SIZE {
-                let truncated = String::from_utf8_lossy(&output_data[..MAX_OUTPUT_SIZE]);
-                format!("{truncated}\n\n[Output truncated: exceeded {MAX_OUTPUT_SIZE} bytes]")
-            } else {
-                String::from_utf8_lossy(&output_data).to_string()
-            };
+            let output_str = self
+                .outputs
+                .truncate(
+                    String::from_utf8_lossy(&output_data).into_owned(),
+                    MAX_OUTPUT_SIZE,
+                )
+                .0;
 
             Ok::<_, AgentError>((status, output_str))
         })
         .await;
+        running.finished = result.is_ok();
+
+        if let Some(dir) = &state_dir {
+            self.track_cwd(dir);
+        }
 
         match result {
             Ok(Ok((status, output_str))) => {
@@ -129,6 +246,20 @@ impl BashTool {
     }
 }
 
+struct Running<'a> {
+    backend: &'a dyn ExecutionBackend,
+    id: &'a str,
+    finished: bool,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.backend.kill(self.id);
+        }
+    }
+}
+
 impl Default for BashTool {
     fn default() -> Self {
         Self::new()
@@ -144,7 +275,7 @@ impl TypedTool for BashTool {
     }
 
     fn description(&self) -> &'static str {
-        "Execute a shell command. Returns stdout, stderr, and exit code. Commands are subject to timeout limits."
+        "Execute a shell command. The working directory persists between calls, so `cd` carries over. Returns stdout, stderr, and exit code. Commands are subject to timeout limits. If a configured sandbox blocks something the task needs, retry with `unsandboxed: true` to ask the user for host access."
     }
 
     async fn execute_typed(&self, input: Self::Input) -> Result<String> {
@@ -190,13 +321,15 @@ impl TypedTool for BashTool {
         };
 
         let timeout_secs = input.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
-        let result = Self::execute_command(
-            &input.command,
-            working_dir,
-            timeout_secs,
-            input.env.as_ref(),
-        )
-        .await;
+        let result = self
+            .execute_command(
+                &input.command,
+                working_dir,
+                timeout_secs,
+                input.env.as_ref(),
+                input.unsandboxed,
+            )
+            .await;
 
         match &result {
             Ok(output) => tracing::info!("Bash command succeeded: {} bytes output", output.len()),
@@ -233,6 +366,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -248,6 +382,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -264,6 +399,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await;
@@ -284,6 +420,7 @@ mod tests {
             working_dir: None,
             timeout_secs: Some(1),
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await;
@@ -304,6 +441,7 @@ mod tests {
             working_dir: Some("relative/path".to_string()),
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await;
@@ -324,6 +462,7 @@ mod tests {
             working_dir: Some("/nonexistent/directory/path".to_string()),
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await;
@@ -348,6 +487,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: Some(env),
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -363,6 +503,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -381,6 +522,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -398,6 +540,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            unsandboxed: false,
         };
 
         let result = tool.execute_typed(input).await.unwrap();
@@ -407,4 +550,57 @@ mod tests {
             "Output should not contain ANSI escape codes"
         );
     }
+
+    fn command(command: &str) -> BashInput {
+        BashInput {
+            command: command.to_string(),
+            working_dir: None,
+            timeout_secs: None,
+            env: None,
+            unsandboxed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bash_tracks_cd_between_commands() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        let cwd = WorkingDirectory::new(root.clone());
+        let tool = BashTool::with_config(BashConfig::default(), cwd.clone());
+
+        tool.execute_typed(command("cd sub")).await.unwrap();
+        assert_eq!(cwd.get(), root.join("sub"));
+
+        let result = tool.execute_typed(command("pwd")).await.unwrap();
+        assert!(result.contains(&root.join("sub").display().to_string()));
+
+        let explicit = BashInput {
+            working_dir: Some(root.display().to_string()),
+            ..command("cd /")
+        };
+        tool.execute_typed(explicit).await.unwrap();
+        assert_eq!(cwd.get(), root.join("sub"));
+    }
+
+    #[tokio::test]
+    async fn test_bash_env_policy() {
+        let config = BashConfig {
+            env_allow: vec!["PATH".to_string(), "SMITH_*".to_string()],
+            env_deny: vec!["SMITH_SECRET".to_string()],
+            ..BashConfig::default()
+        };
+        let tool = BashTool::with_config(config, WorkingDirectory::current());
+        let input = BashInput {
+            env: Some(HashMap::from([(
+                "SMITH_SECRET".to_string(),
+                "explicit".to_string(),
+            )])),
+            ..command("echo \"home=${HOME:-unset} secret=${SMITH_SECRET:-unset}\"")
+        };
+
+        let result = tool.execute_typed(input).await.unwrap();
+        assert!(result.contains("home=unset"));
+        assert!(result.contains("secret=explicit"));
+    }
 }
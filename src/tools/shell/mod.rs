@@ -1,3 +1,5 @@
 mod bash;
+pub mod sandbox;
 
 pub use bash::BashTool;
+pub use sandbox::{ContainerBackend, ExecutionBackend, HostBackend, SandboxTrust};
@@ -0,0 +1,288 @@
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use tokio::process::Command;
+
+use crate::config::{BashConfig, SandboxConfig, SandboxOverride};
+
+pub struct Invocation<'a> {
+    pub id: &'a str,
+    pub script: &'a str,
+    pub cwd: &'a Path,
+    pub workspace: &'a Path,
+    pub state_dir: Option<&'a Path>,
+    pub scratch_dir: Option<&'a Path>,
+    pub env: Option<&'a HashMap<String, String>>,
+}
+
+pub trait ExecutionBackend: Send + Sync {
+    fn name(&self) -> &str;
+    fn is_sandboxed(&self) -> bool;
+    fn command(&self, invocation: &Invocation<'_>) -> Command;
+
+    fn kill(&self, _id: &str) {}
+}
+
+pub struct HostBackend {
+    config: BashConfig,
+}
+
+impl HostBackend {
+    #[must_use]
+    pub const fn new(config: BashConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ExecutionBackend for HostBackend {
+    fn name(&self) -> &'static str {
+        "host"
+    }
+
+    fn is_sandboxed(&self) -> bool {
+        false
+    }
+
+    fn command(&self, invocation: &Invocation<'_>) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(invocation.script)
+            .current_dir(invocation.cwd);
+        if self.config.filters_env() {
+            cmd.env_clear();
+            cmd.envs(std::env::vars().filter(|(key, _)| self.config.allows_env(key)));
+        }
+        if let Some(env) = invocation.env {
+            cmd.envs(env);
+        }
+        cmd
+    }
+}
+
+pub struct ContainerBackend {
+    runtime: &'static str,
+    config: SandboxConfig,
+}
+
+impl ContainerBackend {
+    #[must_use]
+    pub const fn new(runtime: &'static str, config: SandboxConfig) -> Self {
+        Self { runtime, config }
+    }
+}
+
+impl ExecutionBackend for ContainerBackend {
+    fn name(&self) -> &str {
+        self.runtime
+    }
+
+    fn is_sandboxed(&self) -> bool {
+        true
+    }
+
+    fn command(&self, invocation: &Invocation<'_>) -> Command {
+        let mut cmd = Command::new(self.runtime);
+        cmd.args(["run", "--rm", "-i", "--name"])
+            .arg(container_name(invocation.id));
+        if !self.config.network {
+            cmd.args(["--network", "none"]);
+        }
+
+        let mount = |cmd: &mut Command, dir: &Path| {
+            cmd.arg("--volume").arg(format!("{0}:{0}", dir.display()));
+        };
+        mount(&mut cmd, invocation.workspace);
+        for dir in [invocation.state_dir, invocation.scratch_dir]
+            .into_iter()
+            .flatten()
+        {
+            mount(&mut cmd, dir);
+        }
+        cmd.arg("--workdir").arg(invocation.cwd);
+
+        let mut env: Vec<_> = invocation.env.into_iter().flatten().collect();
+        env.sort();
+        for (key, value) in env {
+            cmd.arg("--env").arg(format!("{key}={value}"));
+        }
+
+        cmd.args(&self.config.args)
+            .arg(&self.config.image)
+            .arg("sh")
+            .arg("-c")
+            .arg(invocation.script)
+            .current_dir(invocation.workspace);
+        cmd
+    }
+
+    fn kill(&self, id: &str) {
+        let mut kill = std::process::Command::new(self.runtime);
+        kill.arg("kill")
+            .arg(container_name(id))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        std::thread::spawn(move || {
+            if let Err(e) = kill.status() {
+                tracing::warn!("Failed to stop sandbox container: {e}");
+            }
+        });
+    }
+}
+
+fn container_name(id: &str) -> String {
+    format!("smith-{id}")
+}
+
+#[must_use]
+pub fn backend_for(config: &BashConfig, sandbox: &SandboxConfig) -> Box<dyn ExecutionBackend> {
+    match sandbox.backend.runtime() {
+        Some(runtime) => Box::new(ContainerBackend::new(runtime, sandbox.clone())),
+        None => Box::new(HostBackend::new(config.clone())),
+    }
+}
+
+#[derive(Debug, Default)]
+enum Trust {
+    #[default]
+    None,
+    Pending(SandboxOverride),
+    Trusted(SandboxConfig),
+    Declined,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SandboxTrust(Arc<RwLock<Trust>>);
+
+impl SandboxTrust {
+    pub fn offer(&self, sandbox: SandboxOverride) {
+        *self.0.write() = Trust::Pending(sandbox);
+    }
+
+    #[must_use]
+    pub fn pending(&self) -> Option<SandboxOverride> {
+        match &*self.0.read() {
+            Trust::Pending(sandbox) => Some(sandbox.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn resolve(&self, trusted: bool) {
+        let mut state = self.0.write();
+        if let Trust::Pending(sandbox) = &*state {
+            *state = if trusted {
+                Trust::Trusted(sandbox.config.clone())
+            } else {
+                Trust::Declined
+            };
+        }
+    }
+
+    #[must_use]
+    pub fn trusted(&self) -> Option<SandboxConfig> {
+        match &*self.0.read() {
+            Trust::Trusted(sandbox) => Some(sandbox.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SandboxBackend;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_container_command_isolates_workspace() {
+        let config = BashConfig {
+            sandbox: SandboxConfig {
+                backend: SandboxBackend::Docker,
+                ..SandboxConfig::default()
+            },
+            ..BashConfig::default()
+        };
+        let backend = backend_for(&config, &config.sandbox);
+        let env = HashMap::from([("SMITH_CWD_FILE".to_string(), "/tmp/s/cwd".to_string())]);
+        let cmd = backend.command(&Invocation {
+            id: "0b7e",
+            script: "cargo test",
+            cwd: Path::new("/work/proj/src"),
+            workspace: Path::new("/work/proj"),
+            state_dir: Some(Path::new("/tmp/s")),
+            scratch_dir: Some(Path::new("/tmp/scratch")),
+            env: Some(&env),
+        });
+
+        assert!(backend.is_sandboxed());
+        assert_eq!(cmd.as_std().get_program(), "docker");
+        assert_eq!(
+            args(&cmd),
+            [
+                "run",
+                "--rm",
+                "-i",
+                "--name",
+                "smith-0b7e",
+                "--network",
+                "none",
+                "--volume",
+                "/work/proj:/work/proj",
+                "--volume",
+                "/tmp/s:/tmp/s",
+                "--volume",
+                "/tmp/scratch:/tmp/scratch",
+                "--workdir",
+                "/work/proj/src",
+                "--env",
+                "SMITH_CWD_FILE=/tmp/s/cwd",
+                "debian:stable-slim",
+                "sh",
+                "-c",
+                "cargo test",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_backend_by_default() {
+        let config = BashConfig::default();
+        let backend = backend_for(&config, &config.sandbox);
+        assert!(!backend.is_sandboxed());
+        assert_eq!(backend.name(), "host");
+    }
+
+    #[test]
+    fn test_sandbox_trust_applies_only_after_approval() {
+        let sandbox = SandboxOverride {
+            config: SandboxConfig {
+                backend: SandboxBackend::Podman,
+                ..SandboxConfig::default()
+            },
+            path: ".smith/sandbox.toml".into(),
+            fingerprint: "0123456789abcdef".to_string(),
+            changes: vec!["backend = podman".to_string()],
+        };
+        let trust = SandboxTrust::default();
+        trust.offer(sandbox.clone());
+        assert_eq!(trust.pending(), Some(sandbox.clone()));
+        assert!(trust.trusted().is_none());
+
+        trust.resolve(true);
+        assert!(trust.pending().is_none());
+        assert_eq!(trust.trusted(), Some(sandbox.config.clone()));
+
+        let declined = SandboxTrust::default();
+        declined.offer(sandbox);
+        declined.resolve(false);
+        assert!(declined.pending().is_none());
+        assert!(declined.trusted().is_none());
+    }
+}
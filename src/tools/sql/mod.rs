@@ -0,0 +1,363 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::config::{SqlConfig, SqlConnectionConfig, SqlDriver};
+use crate::core::error::{AgentError, Result};
+use crate::tools::table::render_table;
+use crate::tools::{ToolType, TypedTool, WorkingDirectory};
+
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod sqlite;
+
+const READ_KEYWORDS: &[&str] = &[
+    "SELECT", "WITH", "EXPLAIN", "SHOW", "DESCRIBE", "DESC", "VALUES", "PRAGMA", "TABLE",
+];
+const WRITE_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "REPLACE", "MERGE", "UPSERT", "INTO", "CREATE", "ALTER", "DROP",
+    "TRUNCATE", "RENAME", "GRANT", "REVOKE", "ATTACH", "DETACH", "VACUUM", "REINDEX", "ANALYZE",
+    "COPY", "CALL", "DO", "SET", "LOCK", "LOAD",
+];
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SqlQueryInput {
+    #[serde(default)]
+    pub connection: Option<String>,
+    pub query: String,
+}
+
+#[derive(Debug, Default)]
+struct QueryOutput {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    truncated: bool,
+    affected: Option<u64>,
+}
+
+impl QueryOutput {
+    fn render(&self) -> String {
+        if self.columns.is_empty() {
+            return match self.affected {
+                Some(1) => "1 row affected".to_string(),
+                Some(count) => format!("{count} rows affected"),
+                None => "Statement executed".to_string(),
+            };
+        }
+        if self.rows.is_empty() {
+            return format!("No rows\nColumns: {}", self.columns.join(", "));
+        }
+        let mut output = match (self.truncated, self.rows.len()) {
+            (true, count) => format!("First {count} rows (more available):\n"),
+            (false, 1) => "1 row:\n".to_string(),
+            (false, count) => format!("{count} rows:\n"),
+        };
+        output.push_str(&render_table(&self.columns, &self.rows));
+        if self.truncated {
+            output.push_str("\n\n[Result truncated; add a LIMIT or narrow the query]");
+        }
+        output
+    }
+}
+
+fn db_error(driver: SqlDriver, error: impl std::fmt::Display) -> AgentError {
+    AgentError::ToolExecution(format!("{} error: {error}", driver.name()))
+}
+
+fn invalid(reason: impl Into<String>) -> AgentError {
+    AgentError::InvalidToolInput {
+        tool: ToolType::SqlQuery.name().to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn strip_comments_and_literals(query: &str) -> String {
+    let mut stripped = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&c| c == '\n');
+                stripped.push('\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                stripped.push(' ');
+            }
+            '\'' | '"' | '`' => {
+                chars.by_ref().find(|&c| c == ch);
+                stripped.push_str(" ? ");
+            }
+            _ => stripped.push(ch),
+        }
+    }
+    stripped
+}
+
+fn statements(query: &str) -> Vec<String> {
+    strip_comments_and_literals(query)
+        .split(';')
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[must_use]
+pub fn is_read_query(query: &str) -> bool {
+    if query.contains('\\') {
+        return false;
+    }
+    statements(query).iter().all(|statement| {
+        let words: Vec<String> = statement
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_uppercase)
+            .collect();
+        let Some(first) = words.first() else {
+            return true;
+        };
+        READ_KEYWORDS.contains(&first.as_str())
+            && !words
+                .iter()
+                .any(|word| WRITE_KEYWORDS.contains(&word.as_str()))
+            && !(first == "PRAGMA" && statement.contains('='))
+    })
+}
+
+pub struct SqlQueryTool {
+    config: SqlConfig,
+    cwd: WorkingDirectory,
+    description: String,
+}
+
+impl SqlQueryTool {
+    #[must_use]
+    pub fn new(config: SqlConfig, cwd: WorkingDirectory) -> Self {
+        let mut description = "Run one SQL statement against a configured database connection and get the result as a table. Statements that modify data need the user's approval, and read-only connections reject them. Connections:".to_string();
+        for (name, connection) in &config.connections {
+            let access = if connection.read_only {
+                "read-only"
+            } else {
+                "read-write"
+            };
+            let _ = write!(
+                description,
+                " {name} ({}, {access});",
+                connection.driver.name()
+            );
+        }
+        description.pop();
+        Self {
+            config,
+            cwd,
+            description,
+        }
+    }
+
+    fn connection(&self, name: Option<&str>) -> Result<(&str, &SqlConnectionConfig)> {
+        let names = || {
+            self.config
+                .connections
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match name {
+            Some(name) => self
+                .config
+                .connections
+                .get_key_value(name)
+                .map(|(name, connection)| (name.as_str(), connection))
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "Unknown connection '{name}'. Available: {}",
+                        names()
+                    ))
+                }),
+            None if self.config.connections.len() == 1 => Ok(self
+                .config
+                .connections
+                .iter()
+                .next()
+                .map(|(name, connection)| (name.as_str(), connection))
+                .ok_or_else(|| invalid("No connections configured"))?),
+            None => Err(invalid(format!(
+                "Several connections are configured; pass one of: {}",
+                names()
+            ))),
+        }
+    }
+
+    async fn run(
+        &self,
+        connection: &SqlConnectionConfig,
+        read_only: bool,
+        query: String,
+    ) -> Result<QueryOutput> {
+        let max_rows = self.config.max_rows;
+        match connection.driver {
+            SqlDriver::Sqlite => {
+                let url = connection.url.trim_start_matches("sqlite://");
+                let path = if url == ":memory:" {
+                    url.into()
+                } else {
+                    self.cwd.resolve(Path::new(url))
+                };
+                tokio::task::spawn_blocking(move || sqlite::run(&path, read_only, &query, max_rows))
+                    .await
+                    .map_err(|e| AgentError::ToolExecution(e.to_string()))?
+            }
+            #[cfg(feature = "postgres")]
+            SqlDriver::Postgres => {
+                postgres::run(&connection.url, read_only, &query, max_rows).await
+            }
+            #[cfg(feature = "mysql")]
+            SqlDriver::Mysql => mysql::run(&connection.url, read_only, &query, max_rows).await,
+            #[allow(unreachable_patterns)]
+            driver => Err(AgentError::Config(format!(
+                "This build of smith has no {0} support; rebuild with `--features {0}`",
+                driver.name()
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl TypedTool for SqlQueryTool {
+    type Input = SqlQueryInput;
+
+    fn name(&self) -> &'static str {
+        "sql_query"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn execute_typed(&self, input: Self::Input) -> Result<String> {
+        let (name, connection) = self.connection(input.connection.as_deref())?;
+        if statements(&input.query).len() > 1 {
+            return Err(invalid("Run one statement per call"));
+        }
+        let read_query = is_read_query(&input.query);
+        if connection.read_only && !read_query {
+            return Err(invalid(format!(
+                "Connection '{name}' is read-only; only SELECT-style queries are allowed"
+            )));
+        }
+        let read_only = connection.read_only || read_query;
+        let output = self.run(connection, read_only, input.query).await?;
+        Ok(output.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn tool(dir: &Path, read_only: bool) -> SqlQueryTool {
+        let connection = SqlConnectionConfig {
+            driver: SqlDriver::Sqlite,
+            url: "app.db".to_string(),
+            read_only,
+        };
+        let config = SqlConfig {
+            max_rows: 2,
+            connections: BTreeMap::from([("app".to_string(), connection)]),
+        };
+        SqlQueryTool::new(config, WorkingDirectory::new(dir.to_path_buf()))
+    }
+
+    async fn query(tool: &SqlQueryTool, query: &str) -> Result<String> {
+        tool.execute_typed(SqlQueryInput {
+            connection: None,
+            query: query.to_string(),
+        })
+        .await
+    }
+
+    #[test]
+    fn test_is_read_query() {
+        assert!(is_read_query("SELECT * FROM users"));
+        assert!(is_read_query(
+            "-- recent\nwith r as (select 1) select * from r;"
+        ));
+        assert!(is_read_query("SELECT 'drop table users' AS note"));
+        assert!(is_read_query("PRAGMA table_info(users)"));
+        assert!(!is_read_query("PRAGMA journal_mode = WAL"));
+        assert!(!is_read_query("DELETE FROM users"));
+        assert!(!is_read_query(
+            "WITH x AS (SELECT 1) INSERT INTO t SELECT * FROM x"
+        ));
+        assert!(!is_read_query("SELECT 1; DROP TABLE users"));
+        assert!(!is_read_query("/* harmless */ UPDATE users SET name = 'x'"));
+        assert!(!is_read_query("SELECT '\\''; DROP TABLE users; -- '"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_read_write_and_read_only() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let writer = tool(temp.path(), false);
+        assert_eq!(
+            query(&writer, "CREATE TABLE users (id INTEGER, name TEXT)")
+                .await
+                .unwrap(),
+            "0 rows affected"
+        );
+        assert_eq!(
+            query(
+                &writer,
+                "INSERT INTO users VALUES (1, 'ada'), (2, NULL), (3, 'grace')"
+            )
+            .await
+            .unwrap(),
+            "3 rows affected"
+        );
+        assert_eq!(
+            query(&writer, "SELECT * FROM users ORDER BY id")
+                .await
+                .unwrap(),
+            "First 2 rows (more available):\nid | name\n1  | ada\n2  | NULL\n\n[Result truncated; add a LIMIT or narrow the query]"
+        );
+
+        let reader = tool(temp.path(), true);
+        assert_eq!(
+            query(&reader, "SELECT name FROM users WHERE id = 3")
+                .await
+                .unwrap(),
+            "1 row:\nname\ngrace"
+        );
+        assert!(query(&reader, "DELETE FROM users").await.is_err());
+        assert!(query(&writer, "SELECT 1; SELECT 2").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_approved_query_cannot_write() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let writer = tool(temp.path(), false);
+        query(&writer, "CREATE TABLE users (id INTEGER)")
+            .await
+            .unwrap();
+
+        assert!(is_read_query("PRAGMA user_version(7)"));
+        assert!(query(&writer, "PRAGMA user_version(7)").await.is_err());
+        assert_eq!(
+            query(&writer, "PRAGMA user_version").await.unwrap(),
+            "1 row:\nuser_version\n0"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use mysql_async::prelude::Queryable;
+use mysql_async::{Conn, Row, Value};
+
+use crate::config::SqlDriver;
+use crate::core::error::Result;
+
+use super::{QueryOutput, db_error};
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::NULL => "NULL".to_string(),
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        other => other.as_sql(true),
+    }
+}
+
+pub(super) async fn run(
+    url: &str,
+    read_only: bool,
+    query: &str,
+    max_rows: usize,
+) -> Result<QueryOutput> {
+    let error = |e| db_error(SqlDriver::Mysql, e);
+    let mut conn = Conn::from_url(url).await.map_err(error)?;
+    if read_only {
+        conn.query_drop("SET SESSION TRANSACTION READ ONLY")
+            .await
+            .map_err(error)?;
+    }
+
+    let mut output = QueryOutput::default();
+    {
+        let mut result = conn.query_iter(query).await.map_err(error)?;
+        output.columns = result
+            .columns_ref()
+            .iter()
+            .map(|column| column.name_str().into_owned())
+            .collect();
+        let rows: Vec<Row> = result.collect().await.map_err(error)?;
+        output.truncated = rows.len() > max_rows;
+        output.rows = rows
+            .iter()
+            .take(max_rows)
+            .map(|row| {
+                (0..row.len())
+                    .map(|index| row.as_ref(index).map_or_else(String::new, value_text))
+                    .collect()
+            })
+            .collect();
+        output.affected = Some(result.affected_rows());
+    }
+    conn.disconnect().await.map_err(error)?;
+    Ok(output)
+}
@@ -0,0 +1,54 @@
+use tokio_postgres::{NoTls, SimpleQueryMessage};
+
+use crate::config::SqlDriver;
+use crate::core::error::Result;
+
+use super::{QueryOutput, db_error};
+
+pub(super) async fn run(
+    url: &str,
+    read_only: bool,
+    query: &str,
+    max_rows: usize,
+) -> Result<QueryOutput> {
+    let error = |e| db_error(SqlDriver::Postgres, e);
+    let (client, connection) = tokio_postgres::connect(url, NoTls).await.map_err(error)?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::warn!("Postgres connection closed: {e}");
+        }
+    });
+    if read_only {
+        client
+            .batch_execute("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY")
+            .await
+            .map_err(error)?;
+    }
+
+    let mut output = QueryOutput::default();
+    for message in client.simple_query(query).await.map_err(error)? {
+        match message {
+            SimpleQueryMessage::Row(row) => {
+                if output.columns.is_empty() {
+                    output.columns = row
+                        .columns()
+                        .iter()
+                        .map(|column| column.name().to_string())
+                        .collect();
+                }
+                if output.rows.len() == max_rows {
+                    output.truncated = true;
+                    continue;
+                }
+                output.rows.push(
+                    (0..row.len())
+                        .map(|index| row.get(index).unwrap_or("NULL").to_string())
+                        .collect(),
+                );
+            }
+            SimpleQueryMessage::CommandComplete(count) => output.affected = Some(count),
+            _ => {}
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,68 @@
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+use crate::config::SqlDriver;
+use crate::core::error::Result;
+
+use super::{QueryOutput, db_error};
+
+fn value_text(value: ValueRef<'_>) -> String {
+    match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(value) => value.to_string(),
+        ValueRef::Real(value) => value.to_string(),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+        ValueRef::Blob(blob) => format!("<{} bytes>", blob.len()),
+    }
+}
+
+pub(super) fn run(
+    path: &Path,
+    read_only: bool,
+    query: &str,
+    max_rows: usize,
+) -> Result<QueryOutput> {
+    let error = |e| db_error(SqlDriver::Sqlite, e);
+    let flags = if read_only {
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI
+    } else {
+        OpenFlags::default()
+    };
+    let connection = Connection::open_with_flags(path, flags).map_err(error)?;
+    let mut statement = connection.prepare(query).map_err(error)?;
+    if read_only && !statement.readonly() {
+        return Err(error(rusqlite::Error::InvalidQuery));
+    }
+
+    let columns: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if columns.is_empty() {
+        let affected = statement.execute([]).map_err(error)?;
+        return Ok(QueryOutput {
+            affected: Some(affected as u64),
+            ..QueryOutput::default()
+        });
+    }
+
+    let mut output = QueryOutput {
+        columns,
+        ..QueryOutput::default()
+    };
+    let mut rows = statement.query([]).map_err(error)?;
+    while let Some(row) = rows.next().map_err(error)? {
+        if output.rows.len() == max_rows {
+            output.truncated = true;
+            break;
+        }
+        let values = (0..output.columns.len())
+            .map(|index| row.get_ref(index).map(value_text))
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(error)?;
+        output.rows.push(values);
+    }
+    Ok(output)
+}
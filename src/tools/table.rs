@@ -0,0 +1,72 @@
+use unicode_width::UnicodeWidthStr;
+
+const MAX_CELL_WIDTH: usize = 40;
+
+fn clip(cell: &str) -> String {
+    let cell = cell.replace(['\n', '\r', '\t'], " ");
+    if cell.width() <= MAX_CELL_WIDTH {
+        return cell;
+    }
+    let mut clipped = String::new();
+    for ch in cell.chars() {
+        if clipped.width() + 1 >= MAX_CELL_WIDTH {
+            break;
+        }
+        clipped.push(ch);
+    }
+    clipped.push('…');
+    clipped
+}
+
+#[must_use]
+pub fn render_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let header: Vec<String> = header.iter().map(|name| clip(name)).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| clip(cell)).collect())
+        .collect();
+    let columns = body.iter().map(Vec::len).fold(header.len(), usize::max);
+    let mut widths = vec![0; columns];
+    for row in std::iter::once(&header).chain(&body) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    std::iter::once(&header)
+        .chain(&body)
+        .map(|row| {
+            widths
+                .iter()
+                .enumerate()
+                .map(|(index, width)| {
+                    let cell = row.get(index).map_or("", String::as_str);
+                    format!("{cell}{}", " ".repeat(width - cell.width()))
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table_aligns_and_clips() {
+        let header = vec!["id".to_string(), "note".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "x".repeat(50)],
+            vec!["22".to_string(), "two\nlines".to_string()],
+        ];
+        let table = render_table(&header, &rows);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "id | note");
+        assert_eq!(lines[1], format!("1  | {}…", "x".repeat(39)));
+        assert_eq!(lines[2], "22 | two lines");
+    }
+}
@@ -7,6 +7,16 @@ pub enum ToolType {
     Glob,
     Grep,
     Bash,
+    GetIssue,
+    ListPrComments,
+    CreatePr,
+    ReadToolOutput,
+    Delegate,
+    SaveMemory,
+    ReadDocument,
+    PreviewData,
+    SqlQuery,
+    MarkArtifact,
     Custom(String),
 }
 
@@ -21,6 +31,16 @@ impl ToolType {
             Self::Glob => "glob",
             Self::Grep => "grep",
             Self::Bash => "bash",
+            Self::GetIssue => "get_issue",
+            Self::ListPrComments => "list_pr_comments",
+            Self::CreatePr => "create_pr",
+            Self::ReadToolOutput => "read_tool_output",
+            Self::Delegate => "delegate",
+            Self::SaveMemory => "save_memory",
+            Self::ReadDocument => "read_document",
+            Self::PreviewData => "preview_data",
+            Self::SqlQuery => "sql_query",
+            Self::MarkArtifact => "mark_artifact",
             Self::Custom(name) => name,
         }
     }
@@ -35,6 +55,16 @@ impl ToolType {
             "glob" => Self::Glob,
             "grep" => Self::Grep,
             "bash" => Self::Bash,
+            "get_issue" => Self::GetIssue,
+            "list_pr_comments" => Self::ListPrComments,
+            "create_pr" => Self::CreatePr,
+            "read_tool_output" => Self::ReadToolOutput,
+            "delegate" => Self::Delegate,
+            "save_memory" => Self::SaveMemory,
+            "read_document" => Self::ReadDocument,
+            "preview_data" => Self::PreviewData,
+            "sql_query" => Self::SqlQuery,
+            "mark_artifact" => Self::MarkArtifact,
             other => Self::Custom(other.to_string()),
         }
     }
@@ -43,7 +73,13 @@ impl ToolType {
     pub const fn is_read_only(&self) -> bool {
         matches!(
             self,
-            Self::ReadFile | Self::ListDir | Self::Glob | Self::Grep
+            Self::ReadFile
+                | Self::ListDir
+                | Self::Glob
+                | Self::Grep
+                | Self::ReadToolOutput
+                | Self::ReadDocument
+                | Self::PreviewData
         )
     }
 }
@@ -0,0 +1,176 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Snapshot {
+    modified: Option<SystemTime>,
+    len: Option<u64>,
+}
+
+impl Snapshot {
+    fn of(path: &Path) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        Self {
+            modified: meta.as_ref().and_then(|m| m.modified().ok()),
+            len: meta.map(|m| m.len()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct WatchState {
+    watcher: Option<RecommendedWatcher>,
+    watcher_failed: bool,
+    watched_dirs: HashSet<PathBuf>,
+    snapshots: HashMap<PathBuf, Snapshot>,
+}
+
+#[derive(Clone, Default)]
+pub struct FileWatcher {
+    state: Arc<Mutex<WatchState>>,
+    changed: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl std::fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("tracked", &self.state.lock().snapshots.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileWatcher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn track(&self, path: &Path) {
+        self.changed.lock().remove(path);
+        self.record(&mut self.state.lock(), path);
+    }
+
+    fn record(&self, state: &mut WatchState, path: &Path) {
+        state
+            .snapshots
+            .insert(path.to_path_buf(), Snapshot::of(path));
+        let Some(dir) = path.parent() else {
+            return;
+        };
+        if state.watched_dirs.contains(dir) || state.watcher_failed {
+            return;
+        }
+        if state.watcher.is_none() {
+            match self.create_watcher() {
+                Ok(watcher) => state.watcher = Some(watcher),
+                Err(e) => {
+                    tracing::warn!("File watcher unavailable, checking timestamps instead: {e}");
+                    state.watcher_failed = true;
+                    return;
+                }
+            }
+        }
+        if let Some(watcher) = &mut state.watcher {
+            match watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    state.watched_dirs.insert(dir.to_path_buf());
+                }
+                Err(e) => tracing::debug!("Could not watch {}: {e}", dir.display()),
+            }
+        }
+    }
+
+    fn create_watcher(&self) -> notify::Result<RecommendedWatcher> {
+        let changed = Arc::clone(&self.changed);
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event
+                && matches!(
+                    event.kind,
+                    EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+                )
+            {
+                changed.lock().extend(event.paths);
+            }
+        })
+    }
+
+    #[must_use]
+    pub fn take_stale(&self) -> Vec<PathBuf> {
+        let mut state = self.state.lock();
+        let candidates: Vec<PathBuf> = if state.watcher_failed {
+            state.snapshots.keys().cloned().collect()
+        } else {
+            self.changed.lock().drain().collect()
+        };
+        let mut modified: Vec<PathBuf> = candidates
+            .into_iter()
+            .filter_map(|path| {
+                let recorded = state.snapshots.get_mut(&path)?;
+                let current = Snapshot::of(&path);
+                (current != *recorded).then(|| {
+                    *recorded = current;
+                    path
+                })
+            })
+            .collect();
+        modified.sort();
+        modified
+    }
+
+    #[must_use]
+    pub fn stale_notice(&self) -> Option<String> {
+        let stale = self.take_stale();
+        if stale.is_empty() {
+            return None;
+        }
+        let mut notice = "[These files changed on disk since you last read or edited them. Read them again before editing:".to_string();
+        for path in stale {
+            let _ = write!(notice, "\n- {}", path.display());
+        }
+        notice.push_str("]\n\n");
+        Some(notice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn wait_for_stale(watcher: &FileWatcher) -> Vec<PathBuf> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let stale = watcher.take_stale();
+            if !stale.is_empty() || Instant::now() > deadline {
+                return stale;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn test_external_edit_is_reported_once() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("main.rs");
+        std::fs::write(&path, "fn main() {}\n").unwrap();
+
+        let watcher = FileWatcher::new();
+        watcher.track(&path);
+        assert!(watcher.take_stale().is_empty());
+
+        std::fs::write(&path, "fn main() { println!(\"edited\"); }\n").unwrap();
+        assert_eq!(wait_for_stale(&watcher), vec![path.clone()]);
+        assert_eq!(watcher.stale_notice(), None);
+
+        std::fs::write(&path, "fn main() { println!(\"agent\"); }\n").unwrap();
+        watcher.track(&path);
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(watcher.take_stale().is_empty());
+    }
+}
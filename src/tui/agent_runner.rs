@@ -1,26 +1,139 @@
-use crate::config::{AppConfig, ModelInfo, ModelRegistry};
+use crate::code_blocks::CodeBlock;
+use crate::config::{
+    AgentPreset, AppConfig, AuditConfig, CheckpointsConfig, ClipboardConfig, DuoConfig,
+    FixTestsConfig, GenerationConfig, GenerationParams, InputConfig, KeysConfig, MemoriesConfig,
+    ModelInfo, ModelRegistry, SessionsConfig, ToolsConfig, UpdateConfig,
+};
 use crate::core::augmented_llm::AugmentedLLM;
-use crate::core::error::AgentError;
-use crate::permission::PermissionManager;
+use crate::core::context::{
+    ContextItem, ContextSnapshot, ContextSource, conversation_items, drop_conversation, preview,
+    prompt_items, without_sections,
+};
+use crate::core::error::{AgentError, ErrorReport};
+use crate::core::llm::LLM;
+use crate::core::memory::estimate_tokens;
+use crate::core::observer::AgentObserver;
+use crate::core::run_state::{RunControl, RunState};
+use crate::core::types::Usage;
+use crate::duo::Duo;
+use crate::fix_tests::{FixTestsReport, detect_test_command, fix_prompt, run_tests};
+use crate::git::{
+    Finding, Git, ReviewTarget, format_checkpoints, generate_commit_message, review_diff,
+};
+use crate::permission::{
+    PermissionCheckResult, PermissionManager, PermissionMode, PermissionRequest, PermissionType,
+};
+use crate::providers::mock::{Scenario, ScenarioLLM};
+use crate::providers::{CachedLLM, ResponseCache, create_provider};
+use crate::session::{SessionRecord, SessionStore, generate_title, heuristic_title};
+use crate::shell::run_shell;
+use crate::tools::artifacts::{export_artifacts, format_artifacts};
+use crate::tools::pins::format_pins;
+use crate::tools::{DelegateTool, SaveMemoryTool, ToolContext};
 use crate::tui::TuiToolEventHandler;
 use crate::tui::events::AppEvent;
 use crate::tui::permission_ui::TuiPermissionUI;
+use crate::tui::state::CommitDraft;
+use crate::tui::widgets::ModelTag;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub enum AgentCommand {
-    Run { user_message: String },
-    SwitchModel { model_name: String },
+    Run {
+        user_message: String,
+    },
+    Branch {
+        turns_back: usize,
+        user_message: String,
+    },
+    ForgetTurn {
+        turns_back: usize,
+    },
+    RerunTool {
+        name: String,
+        input: serde_json::Value,
+    },
+    ApplyCode(Vec<CodeBlock>),
+    SwitchModel {
+        model_name: String,
+    },
+    SetGeneration {
+        key: String,
+        value: String,
+    },
+    ShowGeneration,
+    ResumeSession(Box<SessionRecord>),
+    RenameSession {
+        id: Uuid,
+        title: Option<String>,
+    },
+    UpdateSettings {
+        custom_system_prompt: Option<String>,
+        permission_mode: PermissionMode,
+    },
+    SetAutoApprove(bool),
+    DraftCommit,
+    Review(ReviewTarget),
+    FixTests(Option<String>),
+    Duo(String),
+    Shell {
+        command: String,
+        share: bool,
+    },
+    ReloadMemories,
+    Artifacts(Vec<String>),
+    Pins(PinCommand),
+    Checkpoints(Vec<String>),
+    Context,
+    DropContext {
+        messages: usize,
+        sources: Vec<ContextSource>,
+    },
+    Pause,
+    Resume,
     Shutdown,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinCommand {
+    Pin(String),
+    Unpin(String),
+    List,
+}
+
 #[derive(Clone)]
 pub struct AgentConfig {
     pub model_id: Option<String>,
+    pub execution_model: Option<String>,
     pub max_iterations: Option<usize>,
     pub system_prompt: Option<String>,
     pub custom_system_prompt: Option<String>,
+    pub language: Option<String>,
+    pub permission_mode: PermissionMode,
+    pub generation: GenerationConfig,
+    pub tools: ToolsConfig,
+    pub sessions: SessionsConfig,
+    pub audit: AuditConfig,
+    pub updates: UpdateConfig,
+    pub input: InputConfig,
+    pub snippets: HashMap<String, String>,
+    pub aliases: HashMap<String, String>,
+    pub recent_models: Vec<String>,
+    pub keys: KeysConfig,
+    pub clipboard: ClipboardConfig,
+    pub checkpoints: CheckpointsConfig,
+    pub model_badges: bool,
+    pub fix_tests: FixTestsConfig,
+    pub duo: DuoConfig,
+    pub agents: HashMap<String, AgentPreset>,
+    pub memories: MemoriesConfig,
+    pub response_cache: bool,
+    pub auto_approve: bool,
+    pub scenario: Option<PathBuf>,
 }
 
 impl AgentConfig {
@@ -28,9 +141,32 @@ impl AgentConfig {
     pub fn from_model(model_id: Option<String>, config: &AppConfig) -> Self {
         Self {
             model_id,
+            execution_model: config.execution_model.clone(),
             max_iterations: None,
             system_prompt: None,
             custom_system_prompt: config.custom_system_prompt.clone(),
+            language: config.language.clone(),
+            permission_mode: config.permission_mode,
+            generation: config.generation.clone(),
+            tools: config.tools.clone(),
+            sessions: config.sessions.clone(),
+            audit: config.audit.clone(),
+            updates: config.updates.clone(),
+            input: config.input.clone(),
+            snippets: config.snippets.clone(),
+            aliases: config.aliases.clone(),
+            recent_models: config.recent_models.clone(),
+            keys: config.keys.clone(),
+            clipboard: config.clipboard.clone(),
+            checkpoints: config.checkpoints.clone(),
+            model_badges: config.model_badges,
+            fix_tests: config.fix_tests.clone(),
+            duo: config.duo.clone(),
+            agents: config.agents.clone(),
+            memories: config.memories.clone(),
+            response_cache: false,
+            auto_approve: false,
+            scenario: None,
         }
     }
 }
@@ -38,21 +174,130 @@ impl AgentConfig {
 pub struct AgentRunner {
     agent: Option<AugmentedLLM>,
     agent_config: AgentConfig,
+    permission_manager: Option<Arc<PermissionManager>>,
+    session_generation: GenerationParams,
+    model_info: Option<ModelInfo>,
+    session: SessionRecord,
+    store: Option<SessionStore>,
     cmd_rx: mpsc::UnboundedReceiver<AgentCommand>,
+    deferred: VecDeque<AgentCommand>,
+    shared_context: Vec<String>,
+    dropped_sections: HashSet<String>,
+    run_control: RunControl,
+    machine: RunMachine,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+#[derive(Debug, Clone)]
+struct RunMachine {
+    state: Arc<parking_lot::Mutex<RunState>>,
     event_tx: mpsc::UnboundedSender<AppEvent>,
 }
 
+impl RunMachine {
+    fn new(event_tx: mpsc::UnboundedSender<AppEvent>) -> Self {
+        Self {
+            state: Arc::new(parking_lot::Mutex::new(RunState::Idle)),
+            event_tx,
+        }
+    }
+
+    fn state(&self) -> RunState {
+        *self.state.lock()
+    }
+
+    const fn allows(from: RunState, to: RunState) -> bool {
+        use RunState::{AwaitingPermission, ExecutingTools, Idle, Paused, Planning, Streaming};
+        matches!(
+            (from, to),
+            (_, Idle)
+                | (Idle | Paused | ExecutingTools, Planning)
+                | (Planning | Streaming, Streaming)
+                | (Streaming | Paused | AwaitingPermission, ExecutingTools)
+                | (Idle | ExecutingTools, AwaitingPermission)
+                | (Idle | Streaming | ExecutingTools, Paused)
+        )
+    }
+
+    fn transition(&self, to: RunState) -> bool {
+        let mut state = self.state.lock();
+        let from = *state;
+        if from == to {
+            return true;
+        }
+        if !Self::allows(from, to) {
+            tracing::warn!(%from, %to, "Ignoring invalid run state transition");
+            return false;
+        }
+        *state = to;
+        drop(state);
+        let _ = self.event_tx.send(AppEvent::RunStateChanged(to));
+        true
+    }
+
+    fn steer(
+        &self,
+        control: &RunControl,
+        cmd: &AgentCommand,
+    ) -> Result<&'static str, &'static str> {
+        match (cmd, self.state()) {
+            (AgentCommand::Pause, RunState::Idle) => Err("Nothing is running."),
+            (AgentCommand::Pause, _) if control.is_paused() => {
+                Err("Already paused. Type /resume to continue.")
+            }
+            (AgentCommand::Pause, _) => {
+                control.pause();
+                Ok("Pausing after the current step. Type /resume to continue.")
+            }
+            (AgentCommand::Resume, _) if control.is_paused() => {
+                control.resume();
+                Ok("Resuming.")
+            }
+            (AgentCommand::Resume, _) => Err("Nothing is paused."),
+            _ => Err("Not a run control command."),
+        }
+    }
+
+    fn report(&self, result: Result<&'static str, &'static str>) {
+        let event = match result {
+            Ok(message) => AppEvent::RunControlApplied(message.to_string()),
+            Err(message) => AppEvent::RunControlRejected(message.to_string()),
+        };
+        let _ = self.event_tx.send(event);
+    }
+}
+
+impl AgentObserver for RunMachine {
+    fn on_state_change(&self, state: RunState) {
+        self.transition(state);
+    }
+}
+
 impl AgentRunner {
     #[must_use]
     pub fn new(
-        agent_config: AgentConfig,
+        mut agent_config: AgentConfig,
         event_tx: mpsc::UnboundedSender<AppEvent>,
     ) -> (Self, mpsc::UnboundedSender<AgentCommand>) {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        if agent_config.scenario.is_some() {
+            agent_config.sessions.llm_titles = false;
+        }
+        let store = agent_config.sessions.store();
         let runner = Self {
             agent: None,
+            permission_manager: None,
+            session: SessionRecord::new(agent_config.model_id.clone().unwrap_or_default()),
+            store,
             agent_config,
+            session_generation: GenerationParams::default(),
+            model_info: None,
             cmd_rx,
+            deferred: VecDeque::new(),
+            shared_context: Vec::new(),
+            dropped_sections: HashSet::new(),
+            run_control: RunControl::new(),
+            machine: RunMachine::new(event_tx.clone()),
             event_tx,
         };
         (runner, cmd_tx)
@@ -60,39 +305,148 @@ impl AgentRunner {
 
     #[must_use]
     pub fn with_agent(
-        agent: AugmentedLLM,
+        mut agent: AugmentedLLM,
         event_tx: mpsc::UnboundedSender<AppEvent>,
     ) -> (Self, mpsc::UnboundedSender<AgentCommand>) {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let run_control = agent.run_control().clone();
+        let machine = RunMachine::new(event_tx.clone());
+        agent.add_observer(Arc::new(machine.clone()));
         let runner = Self {
+            session: SessionRecord::new(agent.llm().model()),
+            store: None,
             agent: Some(agent),
+            permission_manager: None,
             agent_config: AgentConfig {
                 model_id: None,
+                execution_model: None,
                 max_iterations: None,
                 system_prompt: None,
                 custom_system_prompt: None,
+                language: None,
+                permission_mode: PermissionMode::default(),
+                generation: GenerationConfig::default(),
+                tools: ToolsConfig::default(),
+                sessions: SessionsConfig::default(),
+                audit: AuditConfig::default(),
+                updates: UpdateConfig::default(),
+                input: InputConfig::default(),
+                snippets: HashMap::new(),
+                aliases: HashMap::new(),
+                recent_models: Vec::new(),
+                keys: KeysConfig::default(),
+                clipboard: ClipboardConfig::default(),
+                checkpoints: CheckpointsConfig::default(),
+                model_badges: false,
+                fix_tests: FixTestsConfig::default(),
+                duo: DuoConfig::default(),
+                agents: HashMap::new(),
+                memories: MemoriesConfig::default(),
+                response_cache: false,
+                auto_approve: false,
+                scenario: None,
             },
+            session_generation: GenerationParams::default(),
+            model_info: None,
             cmd_rx,
+            deferred: VecDeque::new(),
+            shared_context: Vec::new(),
+            dropped_sections: HashSet::new(),
+            run_control,
+            machine,
             event_tx,
         };
         (runner, cmd_tx)
     }
 
+    #[must_use]
+    pub fn with_sessions_config(mut self, sessions: SessionsConfig) -> Self {
+        self.store = sessions.store();
+        self.agent_config.sessions = sessions;
+        self
+    }
+
     pub async fn run(mut self) {
-        while let Some(cmd) = self.cmd_rx.recv().await {
+        while let Some(cmd) = self.next_command().await {
             match cmd {
                 AgentCommand::Run { user_message } => {
-                    if self.agent.is_none()
-                        && let Err(e) = self.initialize_agent()
-                    {
-                        let _ = self.event_tx.send(AppEvent::LLMError(e.to_string()));
+                    if let Err(e) = self.ensure_agent() {
+                        let _ = self.event_tx.send(self.error_event(&e));
+                        continue;
+                    }
+                    let user_message = self.take_shared_context(user_message);
+                    self.run_agent_with_events(user_message).await;
+                }
+                AgentCommand::Branch {
+                    turns_back,
+                    user_message,
+                } => {
+                    if let Err(e) = self.ensure_agent() {
+                        let _ = self.event_tx.send(self.error_event(&e));
                         continue;
                     }
+                    if !self.branch_session(turns_back) {
+                        let _ = self.event_tx.send(AppEvent::LLMError(
+                            "That message is no longer in the conversation history".to_string(),
+                        ));
+                        continue;
+                    }
+                    let user_message = self.take_shared_context(user_message);
                     self.run_agent_with_events(user_message).await;
                 }
+                AgentCommand::ForgetTurn { turns_back } => self.forget_turn(turns_back),
+                AgentCommand::RerunTool { name, input } => {
+                    if let Err(e) = self.ensure_agent() {
+                        let _ = self.event_tx.send(self.error_event(&e));
+                        continue;
+                    }
+                    if let Some(agent) = &self.agent {
+                        agent.rerun_tool(&name, input).await;
+                    }
+                }
+                AgentCommand::ApplyCode(blocks) => self.apply_code(blocks).await,
                 AgentCommand::SwitchModel { model_name } => {
                     self.switch_model(&model_name);
                 }
+                AgentCommand::SetGeneration { key, value } => {
+                    self.set_generation(&key, &value);
+                }
+                AgentCommand::ShowGeneration => self.report_generation(),
+                AgentCommand::RenameSession { id, title } => {
+                    if self.session.id == id {
+                        self.session.title.clone_from(&title);
+                        let _ = self.event_tx.send(AppEvent::SessionTitle(title));
+                    }
+                }
+                AgentCommand::ResumeSession(record) => {
+                    self.session = *record;
+                    self.restore_session_messages();
+                }
+                AgentCommand::UpdateSettings {
+                    custom_system_prompt,
+                    permission_mode,
+                } => self.update_settings(custom_system_prompt, permission_mode),
+                AgentCommand::SetAutoApprove(enabled) => self.set_auto_approve(enabled),
+                AgentCommand::DraftCommit => self.draft_commit().await,
+                AgentCommand::Review(target) => self.review(&target).await,
+                AgentCommand::FixTests(command) => self.fix_tests(command).await,
+                AgentCommand::Duo(task) => self.duo(&task).await,
+                AgentCommand::Shell { command, share } => self.shell(command, share).await,
+                AgentCommand::ReloadMemories => self.refresh_system_prompt(),
+                cmd @ (AgentCommand::Artifacts(_)
+                | AgentCommand::Pins(_)
+                | AgentCommand::Checkpoints(_)
+                | AgentCommand::Context) => self.report(cmd),
+                AgentCommand::DropContext { messages, sources } => {
+                    let event = self
+                        .drop_context(messages, &sources)
+                        .map_or_else(AppEvent::ContextFailed, AppEvent::ContextDropped);
+                    let _ = self.event_tx.send(event);
+                }
+                cmd @ (AgentCommand::Pause | AgentCommand::Resume) => {
+                    self.machine
+                        .report(self.machine.steer(&self.run_control, &cmd));
+                }
                 AgentCommand::Shutdown => {
                     tracing::info!("Agent runner shutting down");
                     break;
@@ -101,6 +455,299 @@ impl AgentRunner {
         }
     }
 
+    fn report(&mut self, cmd: AgentCommand) {
+        let event = match cmd {
+            AgentCommand::Artifacts(args) => self
+                .artifacts(&args)
+                .map_or_else(AppEvent::ArtifactsFailed, AppEvent::Artifacts),
+            AgentCommand::Pins(command) => self
+                .pins(command)
+                .map_or_else(AppEvent::PinsFailed, AppEvent::Pins),
+            AgentCommand::Checkpoints(args) => self
+                .checkpoints(&args)
+                .map_or_else(AppEvent::CheckpointsFailed, AppEvent::Checkpoints),
+            AgentCommand::Context => self
+                .context_snapshot()
+                .map_or_else(AppEvent::ContextFailed, AppEvent::Context),
+            _ => return,
+        };
+        let _ = self.event_tx.send(event);
+    }
+
+    async fn next_command(&mut self) -> Option<AgentCommand> {
+        match self.deferred.pop_front() {
+            Some(cmd) => Some(cmd),
+            None => self.cmd_rx.recv().await,
+        }
+    }
+
+    fn ensure_agent(&mut self) -> Result<(), AgentError> {
+        if self.agent.is_none() {
+            self.initialize_agent()?;
+            self.restore_session_messages();
+        }
+        Ok(())
+    }
+
+    async fn draft_commit(&mut self) {
+        let result = match self.ensure_agent() {
+            Ok(()) => self.commit_draft().await,
+            Err(e) => Err(e.to_string()),
+        };
+        let event = match result {
+            Ok(draft) => AppEvent::CommitDraft(draft),
+            Err(e) => AppEvent::CommitFailed(e),
+        };
+        let _ = self.event_tx.send(event);
+    }
+
+    async fn commit_draft(&self) -> Result<CommitDraft, String> {
+        let Some(agent) = &self.agent else {
+            return Err("No model available".to_string());
+        };
+        let git = Git::new(agent.tool_context().cwd.get());
+        let changes = git.changes().map_err(|e| e.to_string())?;
+        let message = generate_commit_message(agent.llm(), &changes)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(CommitDraft {
+            dir: git.dir().to_path_buf(),
+            message,
+            summary: changes.summary(),
+            stage_all: !changes.staged,
+        })
+    }
+
+    async fn review(&mut self, target: &ReviewTarget) {
+        let result = match self.ensure_agent() {
+            Ok(()) => self.review_findings(target).await,
+            Err(e) => Err(e.to_string()),
+        };
+        let event = match result {
+            Ok(findings) => AppEvent::ReviewComplete {
+                title: target.to_string(),
+                findings,
+            },
+            Err(e) => AppEvent::ReviewFailed(e),
+        };
+        let _ = self.event_tx.send(event);
+    }
+
+    async fn review_findings(&self, target: &ReviewTarget) -> Result<Vec<Finding>, String> {
+        let Some(agent) = &self.agent else {
+            return Err("No model available".to_string());
+        };
+        let git = Git::new(agent.tool_context().cwd.get());
+        let diff = target.diff(&git).map_err(|e| e.to_string())?;
+        review_diff(agent.llm(), &diff)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn fix_tests(&mut self, command: Option<String>) {
+        if let Err(e) = self.ensure_agent() {
+            let _ = self.event_tx.send(self.error_event(&e));
+            return;
+        }
+        let Some(cwd) = self
+            .agent
+            .as_ref()
+            .map(|agent| agent.tool_context().cwd.get())
+        else {
+            return;
+        };
+        let Some(command) = command
+            .or_else(|| self.agent_config.fix_tests.command.clone())
+            .or_else(|| detect_test_command(&cwd))
+        else {
+            let _ = self.event_tx.send(AppEvent::FixTestsFailed(
+                "No test command found; pass one or set [fix_tests] command".to_string(),
+            ));
+            return;
+        };
+
+        let git = Git::new(&cwd);
+        let snapshot = git.snapshot().ok();
+        let max_rounds = self.agent_config.fix_tests.max_rounds.max(1);
+        let mut rounds = 0;
+        let run = loop {
+            let run = match run_tests(&command, &cwd).await {
+                Ok(run) => run,
+                Err(e) => {
+                    let _ = self.event_tx.send(AppEvent::FixTestsFailed(format!(
+                        "Could not run `{command}`: {e}"
+                    )));
+                    return;
+                }
+            };
+            if run.passed || rounds == max_rounds {
+                break run;
+            }
+            rounds += 1;
+            let _ = self.event_tx.send(AppEvent::FixTestsProgress(format!(
+                "Fix round {rounds}/{max_rounds}: {}",
+                run.summary()
+            )));
+            let prompt = fix_prompt(&command, &run, rounds, max_rounds);
+            if !self.run_agent_with_events(prompt).await {
+                break run;
+            }
+        };
+
+        let diff = snapshot
+            .and_then(|rev| git.diff_stat_since(&rev).ok())
+            .unwrap_or_default();
+        let _ = self
+            .event_tx
+            .send(AppEvent::FixTestsComplete(FixTestsReport {
+                command,
+                passed: run.passed,
+                rounds,
+                remaining: run.failures.into_iter().map(|f| f.name).collect(),
+                diff,
+            }));
+    }
+
+    async fn shell(&mut self, command: String, share: bool) {
+        if let Err(e) = self.ensure_agent() {
+            let _ = self.event_tx.send(self.error_event(&e));
+            return;
+        }
+        let Some(cwd) = self
+            .agent
+            .as_ref()
+            .map(|agent| agent.tool_context().cwd.get())
+        else {
+            return;
+        };
+        let command = match &self.permission_manager {
+            Some(manager) => {
+                let request = PermissionRequest::new(PermissionType::CommandExecute, &command)
+                    .with_context("Typed by you with ! (runs in your shell)")
+                    .editable();
+                match manager.check_permission(&request) {
+                    Ok(PermissionCheckResult::Allowed) => command,
+                    Ok(PermissionCheckResult::AllowedEdited(edited)) => edited,
+                    Ok(PermissionCheckResult::DeniedWithFeedback(reason)) => {
+                        let _ = self.event_tx.send(AppEvent::ShellFailed(reason));
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = self.event_tx.send(AppEvent::ShellFailed(e.to_string()));
+                        return;
+                    }
+                }
+            }
+            None => command,
+        };
+        let event = match run_shell(&command, &cwd).await {
+            Ok(run) => {
+                if share {
+                    self.shared_context
+                        .push(run.context(self.agent_config.input.attachment_tokens));
+                }
+                AppEvent::ShellOutput { run, shared: share }
+            }
+            Err(e) => AppEvent::ShellFailed(format!("Could not run `{command}`: {e}")),
+        };
+        let _ = self.event_tx.send(event);
+    }
+
+    fn take_shared_context(&mut self, user_message: String) -> String {
+        if self.shared_context.is_empty() {
+            return user_message;
+        }
+        let mut message = self
+            .shared_context
+            .drain(..)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        message.push_str("\n\n");
+        message.push_str(&user_message);
+        message
+    }
+
+    async fn apply_code(&mut self, blocks: Vec<CodeBlock>) {
+        if let Err(e) = self.ensure_agent() {
+            let _ = self.event_tx.send(self.error_event(&e));
+            return;
+        }
+        let Some(agent) = &self.agent else {
+            return;
+        };
+        for block in blocks {
+            let path = agent.tool_context().cwd.resolve(Path::new(&block.path));
+            let input = serde_json::json!({
+                "path": path,
+                "content": block.content,
+            });
+            agent.rerun_tool("write_file", input).await;
+        }
+    }
+
+    async fn duo(&mut self, task: &str) {
+        let result = match self.ensure_agent() {
+            Ok(()) => self.run_duo(task).await,
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(outcome) => {
+                let summary = outcome.render();
+                for file in outcome.applied {
+                    let _ = self.event_tx.send(AppEvent::FileDiff {
+                        path: file.path,
+                        old_content: file.old_content,
+                        new_content: file.new_content,
+                        format: None,
+                    });
+                }
+                let _ = self.event_tx.send(AppEvent::DuoComplete(summary));
+            }
+            Err(e) => {
+                let _ = self.event_tx.send(AppEvent::DuoFailed(e));
+            }
+        }
+    }
+
+    async fn run_duo(&self, task: &str) -> Result<crate::duo::DuoOutcome, String> {
+        let Some(agent) = &self.agent else {
+            return Err("No model available".to_string());
+        };
+        let config = &self.agent_config.duo;
+        let implementer = self.role_llm(config.implementer.model.as_deref())?;
+        let reviewer = self.role_llm(config.reviewer.model.as_deref())?;
+        let cwd = agent.tool_context().cwd.get();
+        let listing = Git::new(&cwd).tracked_files().unwrap_or_default();
+        let event_tx = self.event_tx.clone();
+
+        Duo::new(implementer.as_ref(), reviewer.as_ref(), cwd)
+            .with_max_rounds(config.max_rounds)
+            .with_prompts(
+                config.implementer.system_prompt.clone(),
+                config.reviewer.system_prompt.clone(),
+            )
+            .with_listing(listing)
+            .run(task, |event| {
+                let _ = event_tx.send(AppEvent::DuoProgress(event.describe()));
+            })
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    fn role_llm(&self, model: Option<&str>) -> Result<Arc<dyn LLM>, String> {
+        let registry = ModelRegistry::load();
+        let model_info = match model {
+            Some(name) => registry
+                .resolve(name, &self.agent_config.aliases)
+                .ok_or_else(|| format!("Model '{name}' not found"))?,
+            None => self
+                .model_info
+                .as_ref()
+                .ok_or_else(|| "No model available".to_string())?,
+        };
+        self.create_llm(model_info).map_err(|e| e.to_string())
+    }
+
     fn initialize_agent(&mut self) -> Result<(), AgentError> {
         let registry = ModelRegistry::load();
 
@@ -119,11 +766,9 @@ impl AgentRunner {
 
     fn create_agent_from_model(&mut self, model_info: &ModelInfo) -> Result<(), AgentError> {
         use crate::core::augmented_llm::LoopConfig;
-        use crate::core::prompt::PromptBuilder;
-        use crate::providers::factory::create_provider;
         use crate::tools::ToolEventEmitter;
 
-        let llm = create_provider(model_info)?;
+        let llm = self.create_llm(model_info)?;
 
         let mut loop_config = LoopConfig::default();
         if let Some(max_iter) = self.agent_config.max_iterations {
@@ -133,52 +778,62 @@ impl AgentRunner {
 
         let mut agent = AugmentedLLM::with_config(llm.clone(), loop_config, event_emitter)?;
 
-        let system_prompt = if let Some(prompt) = &self.agent_config.system_prompt {
-            prompt.clone()
-        } else {
-            let template_type = Self::infer_template_type(&llm);
-            let base_prompt = PromptBuilder::new()
-                .with_template(template_type)
-                .with_model(llm.name(), llm.model())
-                .build(agent.tools());
-
-            match self
-                .agent_config
-                .custom_system_prompt
-                .as_deref()
-                .filter(|s| !s.is_empty())
-            {
-                Some(custom) => format!("{base_prompt}\n\n# Custom Instructions\n\n{custom}"),
-                None => base_prompt,
-            }
-        };
+        let system_prompt = self.system_prompt_for(&agent);
         agent.set_system_prompt(&system_prompt);
 
-        let tools: Vec<Arc<dyn crate::tools::Tool>> = vec![
-            Arc::new(crate::tools::ReadFileTool::new()),
-            Arc::new(crate::tools::WriteFileTool::new()),
-            Arc::new(crate::tools::UpdateFileTool::new()),
-            Arc::new(crate::tools::ListDirTool::new()),
-            Arc::new(crate::tools::GlobTool::new()),
-            Arc::new(crate::tools::GrepTool::new()),
-            Arc::new(crate::tools::BashTool::new()),
-        ];
-        for tool in tools {
+        for tool in crate::tools::configured_tools(&self.agent_config.tools, agent.tool_context()) {
             agent.tools_mut().register(tool);
         }
 
         agent
             .register_tool_event_handler(Arc::new(TuiToolEventHandler::new(self.event_tx.clone())));
+        agent.add_observer(Arc::new(self.machine.clone()));
+        agent.set_run_control(self.run_control.clone());
+        agent
+            .tool_context()
+            .pins
+            .set_budget(self.agent_config.input.attachment_tokens);
 
         let permission_ui = Arc::new(TuiPermissionUI::new(self.event_tx.clone()));
+        let audit_log = self.agent_config.audit.log();
         match PermissionManager::new(permission_ui) {
             Ok(pm) => {
-                agent.set_permission_manager(Arc::new(pm));
+                let pm = pm
+                    .with_mode(self.agent_config.permission_mode)
+                    .with_cloud_policy(self.agent_config.tools.bash.cloud);
+                let pm = Arc::new(match audit_log.clone() {
+                    Some(log) => pm.with_audit_log(log),
+                    None => pm,
+                });
+                if let Err(e) = pm.set_auto_approve(self.agent_config.auto_approve) {
+                    tracing::warn!("Auto-approve not enabled: {e}");
+                }
+                agent.set_permission_manager(pm.clone());
+                self.permission_manager = Some(pm);
             }
             Err(e) => {
                 tracing::warn!("Failed to create permission manager: {e}");
             }
         }
+        if let Some(log) = audit_log {
+            agent.set_audit_log(log);
+        }
+        if self.agent_config.memories.save_tool
+            && let Some(store) = self.agent_config.memories.store()
+        {
+            agent
+                .tools_mut()
+                .register(Arc::new(SaveMemoryTool::new(store)));
+        }
+        if !self.agent_config.agents.is_empty() {
+            let delegate = self.delegate_tool(&agent, &llm);
+            agent.tools_mut().register(Arc::new(delegate));
+        }
+        let execution_llm = self.create_execution_llm();
+        let _ = self.event_tx.send(AppEvent::ExecutionModelChanged(
+            execution_llm.as_ref().map(|llm| llm.model().to_string()),
+        ));
+        agent.set_execution_llm(execution_llm);
 
         let provider = llm.name().to_string();
         let model = llm.model().to_string();
@@ -187,10 +842,147 @@ impl AgentRunner {
             .send(AppEvent::ModelChanged { provider, model });
 
         self.agent = Some(agent);
+        self.model_info = Some(model_info.clone());
+        self.apply_generation();
         Ok(())
     }
 
-    fn infer_template_type(llm: &Arc<dyn crate::core::LLM>) -> crate::core::prompt::TemplateType {
+    fn delegate_tool(&self, agent: &AugmentedLLM, llm: &Arc<dyn LLM>) -> DelegateTool {
+        let tool = DelegateTool::new(
+            &self.agent_config.agents,
+            llm.clone(),
+            self.agent_config.tools.clone(),
+            agent.tool_context().cwd.clone(),
+        )
+        .with_aliases(self.agent_config.aliases.clone());
+        match &self.permission_manager {
+            Some(manager) => tool.with_permission_manager(manager.clone()),
+            None => tool,
+        }
+    }
+
+    fn create_execution_llm(&self) -> Option<Arc<dyn LLM>> {
+        let id = self.agent_config.execution_model.as_ref()?;
+        if self.agent_config.scenario.is_some() {
+            return None;
+        }
+        let registry = ModelRegistry::load();
+        let result = registry
+            .resolve(id, &self.agent_config.aliases)
+            .ok_or_else(|| AgentError::Config(format!("Execution model '{id}' not found")))
+            .and_then(|model_info| self.create_llm(model_info));
+        result
+            .inspect_err(|e| {
+                let _ = self.event_tx.send(self.error_event(e));
+            })
+            .ok()
+    }
+
+    fn error_event(&self, err: &AgentError) -> AppEvent {
+        let mut report = ErrorReport::new(err);
+        if let Some(model_info) = &self.model_info {
+            report = report.with_provider(&model_info.provider);
+        }
+        AppEvent::LLMError(report.to_string())
+    }
+
+    fn create_llm(&self, model_info: &ModelInfo) -> Result<Arc<dyn LLM>, AgentError> {
+        let llm = match &self.agent_config.scenario {
+            Some(path) => Arc::new(ScenarioLLM::new(Scenario::load(path)?)),
+            None => create_provider(model_info)?,
+        };
+        if !self.agent_config.response_cache {
+            return Ok(llm);
+        }
+        let Some(cache) = ResponseCache::with_default_dir() else {
+            tracing::warn!("Response cache disabled: could not determine config directory");
+            return Ok(llm);
+        };
+        let event_tx = self.event_tx.clone();
+        Ok(Arc::new(CachedLLM::new(llm, cache).on_hit(Arc::new(
+            move || {
+                let _ = event_tx.send(AppEvent::ResponseCached);
+            },
+        ))))
+    }
+
+    fn system_prompt_for(&self, agent: &AugmentedLLM) -> String {
+        let mut prompt = self.base_system_prompt(agent);
+        if self.agent_config.tools.scratch.enabled {
+            prompt = format!(
+                "{prompt}\n\n{}",
+                agent.tool_context().scratch.prompt_section()
+            );
+        }
+        if let Some(memories) = self
+            .agent_config
+            .memories
+            .store()
+            .and_then(|store| store.prompt_section())
+        {
+            prompt = format!("{prompt}\n\n{memories}");
+        }
+        without_sections(&prompt, &self.dropped_sections)
+    }
+
+    fn base_system_prompt(&self, agent: &AugmentedLLM) -> String {
+        use crate::core::prompt::PromptBuilder;
+        use crate::ui::i18n::language_name;
+
+        if let Some(prompt) = &self.agent_config.system_prompt {
+            return prompt.clone();
+        }
+        let llm = agent.llm();
+        let base_prompt = PromptBuilder::new()
+            .with_template(Self::infer_template_type(llm))
+            .with_model(llm.name(), llm.model())
+            .with_language(self.agent_config.language.as_deref().map(language_name))
+            .build(agent.tools());
+
+        match self
+            .agent_config
+            .custom_system_prompt
+            .as_deref()
+            .filter(|s| !s.is_empty())
+        {
+            Some(custom) => format!("{base_prompt}\n\n# Custom Instructions\n\n{custom}"),
+            None => base_prompt,
+        }
+    }
+
+    fn refresh_system_prompt(&mut self) {
+        let Some(agent) = &self.agent else {
+            return;
+        };
+        let prompt = self.system_prompt_for(agent);
+        if let Some(agent) = &mut self.agent {
+            agent.set_system_prompt(prompt);
+        }
+    }
+
+    fn update_settings(
+        &mut self,
+        custom_system_prompt: Option<String>,
+        permission_mode: PermissionMode,
+    ) {
+        self.agent_config.custom_system_prompt = custom_system_prompt;
+        self.agent_config.permission_mode = permission_mode;
+        if let Some(pm) = &self.permission_manager {
+            pm.set_mode(permission_mode);
+        }
+        self.refresh_system_prompt();
+    }
+
+    fn set_auto_approve(&mut self, enabled: bool) {
+        self.agent_config.auto_approve = enabled;
+        if let Some(pm) = &self.permission_manager
+            && let Err(e) = pm.set_auto_approve(enabled)
+        {
+            let _ = self.event_tx.send(self.error_event(&e));
+        }
+    }
+
+    fn infer_template_type(llm: &dyn LLM) -> crate::core::prompt::TemplateType {
         use crate::core::prompt::TemplateType;
         let name_lower = llm.name().to_lowercase();
 
@@ -206,22 +998,26 @@ impl AgentRunner {
     fn switch_model(&mut self, model_id: &str) {
         let registry = ModelRegistry::load();
 
-        let Some(model_info) = registry.get_model(model_id) else {
+        let Some(model_info) = registry.resolve(model_id, &self.agent_config.aliases) else {
             let _ = self.event_tx.send(AppEvent::ModelSwitchError(format!(
                 "Model '{model_id}' not found"
             )));
             return;
         };
 
-        self.agent_config.model_id = Some(model_id.to_string());
+        self.agent_config.model_id = Some(model_info.id.clone());
 
-        if let Some(agent) = &mut self.agent {
-            match crate::providers::factory::create_provider(model_info) {
+        if self.agent.is_some() {
+            match self.create_llm(model_info) {
                 Ok(new_llm) => {
                     let provider = new_llm.name().to_string();
                     let model = new_llm.model().to_string();
-                    agent.set_llm(new_llm);
-                    agent.regenerate_system_prompt();
+                    if let Some(agent) = &mut self.agent {
+                        agent.set_llm(new_llm);
+                    }
+                    self.refresh_system_prompt();
+                    self.model_info = Some(model_info.clone());
+                    self.apply_generation();
                     let _ = self
                         .event_tx
                         .send(AppEvent::ModelChanged { provider, model });
@@ -239,20 +1035,392 @@ impl AgentRunner {
         }
     }
 
-    async fn run_agent_with_events(&mut self, message: String) {
+    fn max_output_limit(&self) -> Option<u32> {
+        self.model_info
+            .as_ref()
+            .map(|model| self.agent_config.generation.max_output_limit(model))
+    }
+
+    fn set_generation(&mut self, key: &str, value: &str) {
+        let mut candidate = self.session_generation.clone();
+        let result = candidate.set(key, value).and_then(|()| {
+            self.max_output_limit()
+                .map_or(Ok(()), |limit| candidate.validate_max_tokens(limit))
+        });
+
+        match result {
+            Ok(()) => {
+                self.session_generation = candidate;
+                self.apply_generation();
+                self.report_generation();
+            }
+            Err(e) => {
+                let _ = self.event_tx.send(AppEvent::GenerationError(e.to_string()));
+            }
+        }
+    }
+
+    fn apply_generation(&mut self) {
+        let limit = self.max_output_limit();
+        let Some(agent) = &mut self.agent else {
+            return;
+        };
+        let model_id = self
+            .model_info
+            .as_ref()
+            .map_or_else(|| agent.llm().model().to_string(), |m| m.id.clone());
+        let params = self
+            .agent_config
+            .generation
+            .for_model(&model_id)
+            .merged(&self.session_generation);
+
+        let config = agent.config_mut();
+        params.apply_to(config);
+        if let Some(limit) = limit {
+            config.max_tokens = config.max_tokens.min(limit);
+        }
+    }
+
+    fn artifacts(&self, args: &[String]) -> Result<String, String> {
+        let context = self.agent.as_ref().map(AugmentedLLM::tool_context);
+        let artifacts = context.map(ToolContext::artifacts).unwrap_or_default();
+        let resolve = |path: &str| {
+            context.map_or_else(|| PathBuf::from(path), |c| c.cwd.resolve(Path::new(path)))
+        };
+        match args {
+            [] => Ok(format_artifacts(&artifacts)),
+            [action, dir] if action == "export" => {
+                if artifacts.is_empty() {
+                    return Err("No artifacts to export".to_string());
+                }
+                let dir = resolve(dir);
+                let written = export_artifacts(&artifacts, &dir).map_err(|e| e.to_string())?;
+                Ok(format!(
+                    "Exported {} artifact(s) to {}",
+                    written.len(),
+                    dir.display()
+                ))
+            }
+            [action, index, path] if action == "copy" => {
+                let artifact = index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| artifacts.get(n.checked_sub(1)?))
+                    .ok_or_else(|| format!("No artifact #{index}; /artifacts lists them"))?;
+                let mut target = resolve(path);
+                if target.is_dir() {
+                    target = target.join(&artifact.name);
+                }
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::copy(&artifact.path, &target).map_err(|e| e.to_string())?;
+                Ok(format!("Copied {} to {}", artifact.name, target.display()))
+            }
+            _ => Err("Usage: /artifacts [export <dir> | copy <n> <path>]".to_string()),
+        }
+    }
+
+    fn checkpoints(&mut self, args: &[String]) -> Result<String, String> {
+        self.ensure_agent().map_err(|e| e.to_string())?;
+        let cwd = self
+            .agent
+            .as_ref()
+            .map(|agent| agent.tool_context().cwd.get())
+            .ok_or_else(|| "No model available".to_string())?;
+        let git = Git::new(cwd);
+        let checkpoints = git.checkpoints().map_err(|e| e.to_string())?;
+        match args {
+            [] if checkpoints.is_empty() && !self.agent_config.checkpoints.enabled => Ok(
+                "No checkpoints. Set enabled = true under [checkpoints] in the config to save one before each turn that changes files".to_string(),
+            ),
+            [] if checkpoints.is_empty() => Ok("No checkpoints yet".to_string()),
+            [] => Ok(format_checkpoints(&checkpoints)),
+            [action, index] if action == "restore" => {
+                let checkpoint = index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .and_then(|n| checkpoints.get(n))
+                    .ok_or_else(|| format!("No checkpoint {index}; /checkpoints lists them"))?;
+                let stat = git
+                    .restore_checkpoint(&checkpoint.id)
+                    .map_err(|e| e.to_string())?;
+                if stat.trim().is_empty() {
+                    return Ok(format!("Working tree already matches checkpoint {index}"));
+                }
+                Ok(format!(
+                    "Restored checkpoint {index} ({}); the previous state was saved as a new checkpoint:\n{}",
+                    checkpoint.message,
+                    stat.trim_end()
+                ))
+            }
+            _ => Err("Usage: /checkpoints [restore <n>]".to_string()),
+        }
+    }
+
+    fn worktree_before_turn(&self) -> Option<(Git, String)> {
+        if !self.agent_config.checkpoints.enabled {
+            return None;
+        }
+        let git = Git::new(self.agent.as_ref()?.tool_context().cwd.get());
+        let tree = git.worktree_tree().ok()?;
+        Some((git, tree))
+    }
+
+    fn pins(&mut self, command: PinCommand) -> Result<String, String> {
+        self.ensure_agent().map_err(|e| e.to_string())?;
+        let context = self
+            .agent
+            .as_ref()
+            .map(AugmentedLLM::tool_context)
+            .ok_or_else(|| "No model available".to_string())?;
+        let cwd = context.cwd.get();
+        let resolve = |path: &str| {
+            let path = context.cwd.resolve(Path::new(path));
+            path.canonicalize().unwrap_or(path)
+        };
+        let shown = |path: &Path| {
+            path.strip_prefix(&cwd)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        match command {
+            PinCommand::List => Ok(format_pins(&context.pins.pins(), &cwd)),
+            PinCommand::Pin(path) => {
+                let path = resolve(&path);
+                if !context.pins.pin(path.clone()).map_err(|e| e.to_string())? {
+                    return Ok(format!("{} is already pinned", shown(&path)));
+                }
+                let tokens = context
+                    .pins
+                    .pins()
+                    .into_iter()
+                    .find(|pin| pin.path == path)
+                    .and_then(|pin| pin.tokens)
+                    .unwrap_or_default();
+                Ok(format!(
+                    "Pinned {} (~{tokens} tokens per request); /pins lists pinned files",
+                    shown(&path)
+                ))
+            }
+            PinCommand::Unpin(target) => {
+                let removed = target.parse::<usize>().map_or_else(
+                    |_| {
+                        let path = resolve(&target);
+                        context.pins.unpin(&path).then_some(path)
+                    },
+                    |n| {
+                        n.checked_sub(1)
+                            .and_then(|index| context.pins.unpin_index(index))
+                    },
+                );
+                removed
+                    .map(|path| format!("Unpinned {}", shown(&path)))
+                    .ok_or_else(|| format!("{target} is not pinned; /pins lists pinned files"))
+            }
+        }
+    }
+
+    fn context_snapshot(&mut self) -> Result<ContextSnapshot, String> {
+        self.ensure_agent().map_err(|e| e.to_string())?;
+        let agent = self
+            .agent
+            .as_ref()
+            .ok_or_else(|| "No model available".to_string())?;
+        let context = agent.tool_context();
+        let cwd = context.cwd.get();
+
+        let mut items = agent.system_prompt().map(prompt_items).unwrap_or_default();
+        items.extend(context.pins.pins().into_iter().map(|pin| {
+            let path = pin.path.strip_prefix(&cwd).unwrap_or(&pin.path);
+            let label = format!("Pinned: {}", path.display());
+            ContextItem::new(
+                ContextSource::Pin(pin.path.clone()),
+                label,
+                pin.tokens.unwrap_or_default(),
+            )
+        }));
+        let definitions = agent.tools().definitions();
+        if !definitions.is_empty() {
+            let bytes = serde_json::to_string(&definitions).map_or(0, |json| json.len());
+            items.push(ContextItem::new(
+                ContextSource::Tools,
+                format!("Tool definitions ({})", definitions.len()),
+                estimate_tokens(bytes),
+            ));
+        }
+        items.extend(conversation_items(agent.messages()));
+        items.extend(self.shared_context.iter().enumerate().map(|(index, text)| {
+            ContextItem::new(
+                ContextSource::Shared(index),
+                format!("Next message: {}", preview(text)),
+                estimate_tokens(text.len()),
+            )
+        }));
+        Ok(ContextSnapshot {
+            items,
+            messages: agent.messages().len(),
+        })
+    }
+
+    fn drop_context(
+        &mut self,
+        messages: usize,
+        sources: &[ContextSource],
+    ) -> Result<String, String> {
+        let agent = self
+            .agent
+            .as_mut()
+            .ok_or_else(|| "No model available".to_string())?;
+        if agent.messages().len() != messages {
+            return Err(
+                "The conversation changed since /context was opened; run /context again"
+                    .to_string(),
+            );
+        }
+
+        let mut conversation = agent.messages().to_vec();
+        drop_conversation(&mut conversation, sources);
+        agent.restore_messages(conversation);
+        let pins = agent.tool_context().pins.clone();
+        let mut shared = Vec::new();
+        for source in sources {
+            match source {
+                ContextSource::Section(title) => {
+                    self.dropped_sections.insert(title.clone());
+                }
+                ContextSource::Pin(path) => {
+                    let _ = pins.unpin(path);
+                }
+                ContextSource::Shared(index) => shared.push(*index),
+                ContextSource::Tools
+                | ContextSource::Turn { .. }
+                | ContextSource::ToolOutput { .. } => {}
+            }
+        }
+        shared.sort_unstable();
+        for index in shared.into_iter().rev() {
+            if index < self.shared_context.len() {
+                self.shared_context.remove(index);
+            }
+        }
+        self.refresh_system_prompt();
+        self.save_messages();
+
+        let total = self.context_snapshot()?.total_tokens();
+        Ok(format!(
+            "Dropped {} item(s) from the context; the next request is ~{total} tokens. The chat above still shows them.",
+            sources.len()
+        ))
+    }
+
+    fn report_generation(&self) {
+        let params = self.agent.as_ref().map_or_else(
+            || self.session_generation.clone(),
+            |agent| GenerationParams::effective(agent.config()),
+        );
+        let _ = self.event_tx.send(AppEvent::GenerationChanged(params));
+    }
+
+    fn restore_session_messages(&mut self) {
+        if let Some(agent) = &mut self.agent {
+            agent.restore_messages(self.session.messages.clone());
+        }
+    }
+
+    fn forget_turn(&mut self, turns_back: usize) {
+        if let Err(e) = self.ensure_agent() {
+            let _ = self.event_tx.send(self.error_event(&e));
+            return;
+        }
+        let Some(agent) = &mut self.agent else {
+            return;
+        };
+        if agent.forget_turn(turns_back) {
+            self.save_messages();
+        }
+    }
+
+    fn save_messages(&mut self) {
+        let Some(agent) = &self.agent else {
+            return;
+        };
+        self.session.messages = agent.messages().to_vec();
+        self.session.touch();
+        if let Some(store) = &self.store
+            && let Err(e) = store.save(&self.session)
+        {
+            tracing::warn!(error = %e, "Failed to save session");
+        }
+    }
+
+    fn branch_session(&mut self, turns_back: usize) -> bool {
+        if let Some(agent) = &self.agent {
+            self.session.messages = agent.messages().to_vec();
+        }
+        let Some(branch) = self.session.branch(turns_back) else {
+            return false;
+        };
+
+        if let Some(store) = &self.store
+            && !self.session.messages.is_empty()
+            && let Err(e) = store.save(&self.session)
+        {
+            tracing::warn!(error = %e, "Failed to save session before branching");
+        }
+        self.session = branch;
+        self.restore_session_messages();
+        true
+    }
+
+    async fn record_turn(&mut self, prompt: &str, reply: &str, usage: Usage) {
+        let Some(agent) = &self.agent else {
+            return;
+        };
+
+        self.session.model = agent.llm().model().to_string();
+        self.session.messages = agent.messages().to_vec();
+        self.session.usage.add(&usage);
+        self.session.touch();
+
+        if self.session.title.is_none() {
+            let title = if self.agent_config.sessions.llm_titles {
+                generate_title(agent.llm(), prompt, reply).await
+            } else {
+                heuristic_title(prompt)
+            };
+            let _ = self
+                .event_tx
+                .send(AppEvent::SessionTitle(Some(title.clone())));
+            self.session.title = Some(title);
+        }
+
+        if let Some(store) = &self.store
+            && let Err(e) = store.save(&self.session)
+        {
+            tracing::warn!(error = %e, "Failed to save session");
+        }
+    }
+
+    async fn run_agent_with_events(&mut self, message: String) -> bool {
         use crate::core::types::{ContentDelta, StreamEvent};
 
+        let before = self.worktree_before_turn();
         let Some(agent) = &mut self.agent else {
             let _ = self
                 .event_tx
                 .send(AppEvent::LLMError("Agent not initialized".to_string()));
-            return;
+            return false;
         };
 
         let event_tx = self.event_tx.clone();
+        let prompt = message.clone();
 
-        let result = agent
-            .run(message, |stream_event| {
+        let result = {
+            let run = agent.run(message, |stream_event| {
                 if let StreamEvent::ContentBlockDelta { delta, .. } = stream_event {
                     match delta {
                         ContentDelta::TextDelta { text } => {
@@ -263,20 +1431,77 @@ impl AgentRunner {
                         | ContentDelta::InputJsonDelta { .. } => {}
                     }
                 }
-            })
-            .await;
+            });
+            tokio::pin!(run);
+            loop {
+                tokio::select! {
+                    result = &mut run => break result,
+                    Some(cmd) = self.cmd_rx.recv() => {
+                        steer_run(&self.machine, &self.run_control, &mut self.deferred, cmd);
+                    }
+                }
+            }
+        };
+        self.run_control.resume();
+        checkpoint_turn(before, &prompt);
 
         match result {
             Ok((message, usage)) => {
-                let _ = self.event_tx.send(AppEvent::LLMComplete(message, usage));
+                let reply = message.first_text().unwrap_or_default().to_string();
+                let model = self
+                    .agent
+                    .as_ref()
+                    .and_then(AugmentedLLM::last_llm)
+                    .map(ModelTag::of);
+                let _ = self
+                    .event_tx
+                    .send(AppEvent::LLMComplete(message, usage, model));
+                self.record_turn(&prompt, &reply, usage).await;
+                true
             }
             Err(e) => {
-                let _ = self.event_tx.send(AppEvent::LLMError(e.to_string()));
+                let _ = self.event_tx.send(self.error_event(&e));
+                false
             }
         }
     }
 }
 
+fn checkpoint_turn(before: Option<(Git, String)>, prompt: &str) {
+    let Some((git, tree)) = before else {
+        return;
+    };
+    if git.worktree_tree().is_ok_and(|after| after == tree) {
+        return;
+    }
+    let summary: String = prompt
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(60)
+        .collect();
+    if let Err(e) = git.save_checkpoint(&tree, &format!("before: {summary}")) {
+        tracing::warn!(error = %e, "Failed to save checkpoint");
+    }
+}
+
+fn steer_run(
+    machine: &RunMachine,
+    control: &RunControl,
+    deferred: &mut VecDeque<AgentCommand>,
+    cmd: AgentCommand,
+) {
+    match cmd {
+        AgentCommand::Pause | AgentCommand::Resume => machine.report(machine.steer(control, &cmd)),
+        AgentCommand::Shutdown => {
+            control.resume();
+            deferred.push_back(cmd);
+        }
+        cmd => deferred.push_back(cmd),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,9 +1515,32 @@ mod tests {
 
         let config = AgentConfig {
             model_id: Some("claude-sonnet-4-5".to_string()),
+            execution_model: None,
             max_iterations: None,
             system_prompt: None,
             custom_system_prompt: None,
+            language: None,
+            permission_mode: PermissionMode::default(),
+            generation: GenerationConfig::default(),
+            tools: ToolsConfig::default(),
+            sessions: SessionsConfig::default(),
+            audit: AuditConfig::default(),
+            updates: UpdateConfig::default(),
+            input: InputConfig::default(),
+            snippets: HashMap::new(),
+            aliases: HashMap::new(),
+            recent_models: Vec::new(),
+            keys: KeysConfig::default(),
+            clipboard: ClipboardConfig::default(),
+            checkpoints: CheckpointsConfig::default(),
+            model_badges: false,
+            fix_tests: FixTestsConfig::default(),
+            duo: DuoConfig::default(),
+            agents: HashMap::new(),
+            memories: MemoriesConfig::default(),
+            response_cache: false,
+            auto_approve: false,
+            scenario: None,
         };
 
         let (runner, _cmd_tx) = AgentRunner::new(config, event_tx);
@@ -318,4 +1566,140 @@ mod tests {
         assert!(runner.agent.is_some());
         assert!(!runner.cmd_rx.is_closed());
     }
+
+    #[test]
+    fn test_set_generation_updates_loop_config() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let agent = AugmentedLLM::with_config(
+            Arc::new(MockLLM::new()),
+            LoopConfig::default(),
+            ToolEventEmitter::new(),
+        )
+        .expect("Failed to create agent");
+        let (mut runner, _cmd_tx) = AgentRunner::with_agent(agent, event_tx);
+
+        runner.session_generation.set("temperature", "0.3").unwrap();
+        runner.session_generation.set("top_p", "0.8").unwrap();
+        runner.apply_generation();
+        runner.report_generation();
+
+        let config = runner.agent.as_ref().unwrap().config();
+        assert!((config.temperature - 0.3).abs() < f32::EPSILON);
+        assert_eq!(config.top_p, Some(0.8));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(AppEvent::GenerationChanged(params)) if params.top_p == Some(0.8)
+        ));
+    }
+
+    #[test]
+    fn test_branch_and_forget_truncate_agent_history() {
+        use crate::core::types::Message;
+
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let mut agent = AugmentedLLM::with_config(
+            Arc::new(MockLLM::new()),
+            LoopConfig::default(),
+            ToolEventEmitter::new(),
+        )
+        .expect("Failed to create agent");
+        agent.restore_messages(vec![
+            Message::user("first"),
+            Message::assistant("one"),
+            Message::user("second"),
+            Message::assistant("two"),
+        ]);
+        let (mut runner, _cmd_tx) = AgentRunner::with_agent(agent, event_tx);
+        let parent = runner.session.id;
+
+        assert!(runner.branch_session(0));
+        assert_eq!(runner.session.parent, Some(parent));
+        assert_eq!(runner.agent.as_ref().unwrap().messages().len(), 2);
+        assert!(!runner.branch_session(1));
+
+        runner.forget_turn(0);
+        assert!(runner.agent.as_ref().unwrap().messages().is_empty());
+        assert!(runner.session.messages.is_empty());
+    }
+
+    #[test]
+    fn test_run_machine_transitions() {
+        use RunState::{AwaitingPermission, ExecutingTools, Idle, Paused, Planning, Streaming};
+
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let machine = RunMachine::new(event_tx);
+        for state in [
+            Planning,
+            Streaming,
+            Paused,
+            ExecutingTools,
+            AwaitingPermission,
+            ExecutingTools,
+            Planning,
+            Streaming,
+            Streaming,
+            Idle,
+        ] {
+            assert!(machine.transition(state), "{} -> {state}", machine.state());
+        }
+        let mut changes = 0;
+        while let Ok(event) = event_rx.try_recv() {
+            assert!(matches!(event, AppEvent::RunStateChanged(_)));
+            changes += 1;
+        }
+        assert_eq!(changes, 9);
+
+        for (from, to) in [
+            (Idle, Streaming),
+            (Idle, ExecutingTools),
+            (Planning, ExecutingTools),
+            (Paused, Streaming),
+            (AwaitingPermission, Paused),
+        ] {
+            *machine.state.lock() = from;
+            assert!(!machine.transition(to), "{from} -> {to}");
+            assert_eq!(machine.state(), from);
+        }
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_pause_and_resume_follow_run_state() {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let machine = RunMachine::new(event_tx);
+        let control = RunControl::new();
+
+        assert!(machine.steer(&control, &AgentCommand::Pause).is_err());
+        assert!(machine.steer(&control, &AgentCommand::Resume).is_err());
+        assert!(!control.is_paused());
+
+        machine.transition(RunState::Planning);
+        assert!(machine.steer(&control, &AgentCommand::Pause).is_ok());
+        assert!(control.is_paused());
+        assert!(machine.steer(&control, &AgentCommand::Pause).is_err());
+        assert!(machine.steer(&control, &AgentCommand::Resume).is_ok());
+        assert!(!control.is_paused());
+        assert!(machine.steer(&control, &AgentCommand::Resume).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pause_while_idle_is_reported() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let agent = AugmentedLLM::with_config(
+            Arc::new(MockLLM::new()),
+            LoopConfig::default(),
+            ToolEventEmitter::new(),
+        )
+        .expect("Failed to create agent");
+        let (runner, cmd_tx) = AgentRunner::with_agent(agent, event_tx);
+        cmd_tx.send(AgentCommand::Pause).unwrap();
+        cmd_tx.send(AgentCommand::Shutdown).unwrap();
+
+        runner.run().await;
+
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(AppEvent::RunControlRejected(message)) if message == "Nothing is running."
+        ));
+    }
 }
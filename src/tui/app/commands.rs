@@ -1,16 +1,68 @@
-pub const HELP_TEXT: &str = r"Available commands:
-/help  - Show this help message
-/clear - Clear the chat history
-/model - Switch to a different model
-/exit  - Exit the application";
+use crate::git::ReviewTarget;
+use crate::tui::agent_runner::PinCommand;
 
-pub const SLASH_COMMANDS: &[&str] = &["/help", "/exit", "/clear", "/model", "/save", "/load"];
+const DEFAULT_AUDIT_ENTRIES: usize = 20;
+
+pub const SLASH_COMMANDS: &[&str] = &[
+    "/help",
+    "/exit",
+    "/clear",
+    "/model",
+    "/config",
+    "/set",
+    "/search",
+    "/sessions",
+    "/memories",
+    "/snippet",
+    "/audit",
+    "/artifacts",
+    "/apply",
+    "/open",
+    "/copy",
+    "/pin",
+    "/pins",
+    "/unpin",
+    "/context",
+    "/auto",
+    "/think",
+    "/pause",
+    "/resume",
+    "/commit",
+    "/checkpoints",
+    "/review",
+    "/fix-tests",
+    "/duo",
+    "/save",
+    "/load",
+];
 
 pub enum SlashCommand {
     Help,
     Exit,
     Clear,
-    Model,
+    Model(Option<String>),
+    Config,
+    Set(Vec<String>),
+    Search(String),
+    Sessions,
+    Memories,
+    Snippet(String),
+    Audit(usize),
+    Artifacts(Vec<String>),
+    Apply(String),
+    Open(String),
+    Copy(String),
+    Pins(PinCommand),
+    Checkpoints(Vec<String>),
+    Context,
+    Auto(Option<bool>),
+    Think(Option<bool>),
+    Pause,
+    Resume,
+    Commit,
+    Review(ReviewTarget),
+    FixTests(Option<String>),
+    Duo(String),
     NotImplemented(String),
     Unknown(String),
 }
@@ -22,7 +74,57 @@ impl SlashCommand {
             "/help" => Self::Help,
             "/exit" => Self::Exit,
             "/clear" => Self::Clear,
-            "/model" => Self::Model,
+            "/model" => Self::Model(input.split_whitespace().nth(1).map(String::from)),
+            "/config" => Self::Config,
+            "/set" => Self::Set(input.split_whitespace().skip(1).map(String::from).collect()),
+            "/sessions" => Self::Sessions,
+            "/memories" => Self::Memories,
+            "/snippet" => Self::Snippet(input.trim()[cmd.len()..].trim().to_string()),
+            "/audit" => Self::Audit(
+                input
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(DEFAULT_AUDIT_ENTRIES),
+            ),
+            "/artifacts" => {
+                Self::Artifacts(input.split_whitespace().skip(1).map(String::from).collect())
+            }
+            "/apply" => Self::Apply(input.trim()[cmd.len()..].trim().to_string()),
+            "/open" => Self::Open(input.trim()[cmd.len()..].trim().to_string()),
+            "/copy" => Self::Copy(input.trim()[cmd.len()..].trim().to_string()),
+            "/pin" => Self::Pins(PinCommand::Pin(
+                input.trim()[cmd.len()..].trim().to_string(),
+            )),
+            "/pins" => Self::Pins(PinCommand::List),
+            "/checkpoints" => {
+                Self::Checkpoints(input.split_whitespace().skip(1).map(String::from).collect())
+            }
+            "/context" => Self::Context,
+            "/unpin" => Self::Pins(PinCommand::Unpin(
+                input.trim()[cmd.len()..].trim().to_string(),
+            )),
+            "/auto" => Self::Auto(match input.split_whitespace().nth(1) {
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                _ => None,
+            }),
+            "/think" => Self::Think(match input.split_whitespace().nth(1) {
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                _ => None,
+            }),
+            "/pause" => Self::Pause,
+            "/resume" => Self::Resume,
+            "/commit" => Self::Commit,
+            "/review" => Self::Review(ReviewTarget::parse(&input.trim()[cmd.len()..])),
+            "/fix-tests" => Self::FixTests(
+                Some(input.trim()[cmd.len()..].trim())
+                    .filter(|command| !command.is_empty())
+                    .map(str::to_string),
+            ),
+            "/duo" => Self::Duo(input.trim()[cmd.len()..].trim().to_string()),
+            "/search" => Self::Search(input.trim()[cmd.len()..].trim().to_string()),
             "/save" | "/load" => Self::NotImplemented(cmd.to_string()),
             _ => Self::Unknown(cmd.to_string()),
         }
@@ -3,6 +3,7 @@ pub struct DiffMetadata {
     pub path: String,
     pub old_content: String,
     pub new_content: String,
+    pub format: Option<String>,
 }
 
 impl DiffMetadata {
@@ -15,6 +16,10 @@ impl DiffMetadata {
             path: diff_obj.get("path")?.as_str()?.to_string(),
             old_content: diff_obj.get("old_content")?.as_str()?.to_string(),
             new_content: diff_obj.get("new_content")?.as_str()?.to_string(),
+            format: diff_obj
+                .get("format")
+                .and_then(|format| format.as_str())
+                .map(ToString::to_string),
         })
     }
 }
@@ -0,0 +1,130 @@
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const DEFAULT_PAGER: &str = "less -R";
+const DEFAULT_EDITOR: &str = "vi";
+const DIFF_PAGER: &str = "delta";
+const DEFAULT_BROWSER: &str = if cfg!(target_os = "macos") {
+    "open"
+} else if cfg!(windows) {
+    "explorer"
+} else {
+    "xdg-open"
+};
+
+pub fn open_in_pager(text: &str) -> io::Result<()> {
+    let pager = env_command(&["PAGER"]).unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    pipe_to(&pager, text)
+}
+
+pub fn open_diff_in_pager(diff: &str) -> io::Result<()> {
+    if is_installed(DIFF_PAGER) {
+        pipe_to(&format!("{DIFF_PAGER} --paging=always"), diff)
+    } else {
+        open_in_pager(diff)
+    }
+}
+
+pub fn open_in_editor(path: &Path, line: usize) -> io::Result<()> {
+    let editor = env_command(&["VISUAL", "EDITOR"]).unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+    let (program, args) = editor_command(&editor, path, line);
+    let status = Command::new(program).args(args).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{editor} exited with {status}")));
+    }
+    Ok(())
+}
+
+pub fn open_in_browser(url: &str) -> io::Result<()> {
+    let browser = env_command(&["BROWSER"]).unwrap_or_else(|| DEFAULT_BROWSER.to_string());
+    let mut parts = browser.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_BROWSER);
+    let mut child = Command::new(program)
+        .args(parts)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn env_command(vars: &[&str]) -> Option<String> {
+    vars.iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+fn pipe_to(command: &str, text: &str) -> io::Result<()> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn editor_command(editor: &str, path: &Path, line: usize) -> (String, Vec<OsString>) {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR).to_string();
+    let mut args: Vec<OsString> = parts.map(OsString::from).collect();
+
+    let name = Path::new(&program)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut located = path.as_os_str().to_owned();
+    located.push(format!(":{line}"));
+    match name.as_str() {
+        "code" | "codium" | "cursor" => {
+            args.extend(["--wait".into(), "--goto".into(), located]);
+        }
+        "subl" | "zed" | "hx" | "helix" => args.push(located),
+        _ => {
+            args.push(format!("+{line}").into());
+            args.push(path.as_os_str().to_owned());
+        }
+    }
+    (program, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command_line_syntax() {
+        let path = Path::new("src/main.rs");
+
+        let (program, args) = editor_command("nvim", path, 12);
+        assert_eq!(program, "nvim");
+        assert_eq!(args, vec![OsString::from("+12"), "src/main.rs".into()]);
+
+        let (_, args) = editor_command("/usr/bin/code -n", path, 3);
+        assert_eq!(
+            args,
+            vec![
+                OsString::from("-n"),
+                "--wait".into(),
+                "--goto".into(),
+                "src/main.rs:3".into()
+            ]
+        );
+
+        let (_, args) = editor_command("hx", path, 7);
+        assert_eq!(args, vec![OsString::from("src/main.rs:7")]);
+    }
+}
@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    #[must_use]
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = spec.split('+').map(str::trim).collect();
+        let key = parts.pop()?.to_ascii_lowercase();
+        for part in parts {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+        let code = match key.as_str() {
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+            c if c.chars().count() == 1 => KeyCode::Char(c.chars().next()?),
+            _ => return None,
+        };
+        Some(Self { code, modifiers })
+    }
+
+    #[must_use]
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.code && key.modifiers == self.modifiers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_binding() {
+        let ctrl_p = KeyBinding::parse("Ctrl+P").unwrap();
+        assert!(ctrl_p.matches(&KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)));
+        assert!(!ctrl_p.matches(&KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE)));
+
+        let alt_f2 = KeyBinding::parse("alt+f2").unwrap();
+        assert!(alt_f2.matches(&KeyEvent::new(KeyCode::F(2), KeyModifiers::ALT)));
+
+        assert_eq!(KeyBinding::parse("hyper+p"), None);
+        assert_eq!(KeyBinding::parse("ctrl+pageup"), None);
+    }
+}
@@ -1,33 +1,66 @@
+mod clipboard;
 mod commands;
 mod diff;
+mod external;
+mod keys;
 
 pub use commands::SLASH_COMMANDS;
 mod render;
 mod terminal;
 
-use crate::config::{ConfigEvent, ConfigEventSender};
+use crate::audit::AuditLog;
+use crate::code_blocks::{CodeBlock, extract_code_blocks, format_code_blocks, last_code_block};
+use crate::config::{AutoCopy, ConfigEvent, ConfigEventSender, ConfigPatch, GENERATION_KEYS};
 use crate::core::error::Result;
+use crate::core::types::Usage;
+use crate::memories::{MemoryStore, parse_remember};
+use crate::permission::PermissionMode;
+use crate::session::SessionStore;
+use crate::shell::parse_shell_input;
+use crate::snippets::SnippetLibrary;
 use crate::tools::ToolType;
-use crate::tui::agent_runner::{AgentCommand, AgentConfig, AgentRunner};
+use crate::tools::result::ToolResult;
+use crate::tui::agent_runner::{AgentCommand, AgentConfig, AgentRunner, PinCommand};
 use crate::tui::events::{AppEvent, terminal_event_loop, tick_loop};
 use crate::tui::layout::calculate_layout;
-use crate::tui::state::AppState;
-use crate::tui::widgets::{ChatWidget, InputAction, InputWidget};
+use crate::tui::state::{
+    AppState, ConfigChanges, ConfigSettings, MAX_RECENT_MODELS, RecentModels, SnippetForm,
+};
+use crate::tui::widgets::{ChatMessage, ChatWidget, InputAction, InputWidget, ModelTag};
+use crate::ui::diff::first_changed_line;
+use crate::ui::i18n::{Msg, t};
+use crate::ui::markdown::format_links;
+use crate::ui::theme::{current_theme, degrade_buffer, set_theme};
+use crate::update::spawn_update_check;
 use crossterm::ExecutableCommand;
 use crossterm::event::{KeyCode, KeyModifiers};
 use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use tokio::sync::mpsc;
 
-use commands::{HELP_TEXT, SlashCommand};
+use clipboard::copy_to_clipboard;
+use commands::SlashCommand;
 use diff::DiffMetadata;
-use render::{render_header, render_model_picker_modal, render_permission_modal, render_status};
-use terminal::{restore_terminal, setup_terminal};
+use external::{open_diff_in_pager, open_in_browser, open_in_editor, open_in_pager};
+use keys::KeyBinding;
+use render::{
+    render_batch_permission_modal, render_commit_modal, render_config_form_modal,
+    render_context_modal, render_header, render_input_estimate, render_memories_modal,
+    render_model_picker_modal, render_paste_viewer, render_permission_modal,
+    render_permission_queue, render_session_browser_modal, render_snippet_form, render_status,
+    render_update_hint,
+};
+use terminal::{restore_terminal, setup_terminal, suspend};
 
 pub use terminal::TerminalGuard;
 
+const DIFF_SCROLL_LINES: usize = 10;
+
 pub struct TuiApp {
     agent_cmd_tx: mpsc::UnboundedSender<AgentCommand>,
     provider_name: String,
@@ -38,7 +71,20 @@ pub struct TuiApp {
     event_tx: mpsc::UnboundedSender<AppEvent>,
     config_event_tx: Option<ConfigEventSender>,
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    input_paused: Arc<AtomicBool>,
     show_model_picker_on_start: bool,
+    show_sessions_on_start: bool,
+    session_store: Option<SessionStore>,
+    memory_store: Option<MemoryStore>,
+    audit_log: Option<AuditLog>,
+    custom_system_prompt: Option<String>,
+    permission_mode: PermissionMode,
+    execution_model: Option<String>,
+    cycle_model_key: Option<KeyBinding>,
+    input_warn_tokens: usize,
+    snippets: SnippetLibrary,
+    auto_copy: AutoCopy,
+    replaying: bool,
 }
 
 impl TuiApp {
@@ -48,6 +94,7 @@ impl TuiApp {
         event_rx: mpsc::UnboundedReceiver<AppEvent>,
         config_event_tx: Option<ConfigEventSender>,
         show_model_picker_on_start: bool,
+        show_sessions_on_start: bool,
     ) -> Result<Self> {
         let terminal = setup_terminal()?;
 
@@ -56,6 +103,28 @@ impl TuiApp {
             |id| (String::new(), id.clone()),
         );
 
+        let session_store = agent_config.sessions.store();
+        let memory_store = agent_config.memories.store();
+        let audit_log = agent_config.audit.log();
+        spawn_update_check(&agent_config.updates, event_tx.clone());
+        let custom_system_prompt = agent_config.custom_system_prompt.clone();
+        let permission_mode = agent_config.permission_mode;
+        let input_warn_tokens = agent_config.input.warn_tokens;
+        let attachment_tokens = agent_config.input.attachment_tokens;
+        let snippets = SnippetLibrary::load(&agent_config.snippets);
+        let auto_copy = agent_config.clipboard.auto_copy;
+        let mut state = AppState::new();
+        state.auto_approve = agent_config.auto_approve;
+        state.model_badges = agent_config.model_badges;
+        state.recent_models = RecentModels::new(agent_config.recent_models.clone());
+        state.recent_models.record(model_name.clone());
+        let cycle_model_key = KeyBinding::parse(&agent_config.keys.cycle_model);
+        if cycle_model_key.is_none() {
+            tracing::warn!(
+                "Ignoring invalid cycle_model key binding: {}",
+                agent_config.keys.cycle_model
+            );
+        }
         let (runner, agent_cmd_tx) = AgentRunner::new(agent_config, event_tx.clone());
         tokio::spawn(async move {
             runner.run().await;
@@ -65,22 +134,42 @@ impl TuiApp {
             agent_cmd_tx,
             provider_name,
             model_name,
-            state: AppState::new(),
-            input_widget: InputWidget::new(),
+            state,
+            input_widget: InputWidget::new().with_attachment_budget(attachment_tokens),
             event_rx,
             event_tx,
             config_event_tx,
             terminal,
+            input_paused: Arc::new(AtomicBool::new(false)),
             show_model_picker_on_start,
+            show_sessions_on_start,
+            session_store,
+            memory_store,
+            audit_log,
+            custom_system_prompt,
+            permission_mode,
+            execution_model: None,
+            cycle_model_key,
+            input_warn_tokens,
+            snippets,
+            auto_copy,
+            replaying: false,
         })
     }
 
+    #[must_use]
+    pub(crate) const fn replaying(mut self) -> Self {
+        self.replaying = true;
+        self
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let tx1 = self.event_tx.clone();
         let tx2 = self.event_tx.clone();
+        let input_paused = Arc::clone(&self.input_paused);
 
         tokio::spawn(async move {
-            let _ = terminal_event_loop(tx1).await;
+            let _ = terminal_event_loop(tx1, input_paused).await;
         });
 
         tokio::spawn(async move {
@@ -92,75 +181,175 @@ impl TuiApp {
             self.show_model_picker_on_start = false;
         }
 
+        if self.show_sessions_on_start {
+            self.open_session_browser();
+            self.show_sessions_on_start = false;
+        }
+
         while !self.state.should_quit {
-            let is_processing = self.state.is_processing;
-            let elapsed = self.state.elapsed();
-            let spinner_frame = self.state.spinner_frame;
-            let last_usage = self.state.last_usage;
-            let session_usage = self.state.session_usage;
-
-            let permission_modal = self.state.permission_modal.as_ref().map(|m| {
-                (
-                    m.request.clone(),
-                    m.selected_index(),
-                    m.is_input_mode(),
-                    m.feedback().to_string(),
-                )
-            });
+            self.draw()?;
 
-            let model_picker_modal = self
-                .state
-                .model_picker_modal
-                .as_ref()
-                .map(|m| (m.models.clone(), m.selected, m.total_count()));
+            if let Some(event) = self.event_rx.recv().await {
+                self.handle_event(event);
+            }
+        }
+
+        let _ = self.agent_cmd_tx.send(AgentCommand::Shutdown);
 
-            self.terminal.draw(|f| {
-                let layout = calculate_layout(f.area());
+        restore_terminal(&mut self.terminal)?;
+
+        Ok(())
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let activity = self.state.is_processing.then_some(self.state.run_state);
+        let progress = self.state.stream_progress();
+        let spinner_frame = self.state.spinner_frame;
+        let last_usage = self.state.last_usage;
+        let session_usage = self.state.session_usage;
+        let queued_permissions = self.state.queued_permissions();
+
+        let permission_modal = self.state.permission_modal.as_ref().map(|m| {
+            (
+                m.request.clone(),
+                m.selected_index(),
+                m.is_input_mode(),
+                m.feedback().to_string(),
+                m.diff_scroll(),
+            )
+        });
 
-                render_header(f, layout.header, &self.provider_name, &self.model_name);
+        let model_picker_modal = self
+            .state
+            .model_picker_modal
+            .as_ref()
+            .map(|m| (m.models.clone(), m.selected, m.total_count()));
 
-                let messages = self.state.messages_with_streaming();
-                let chat_widget = ChatWidget::new(&messages, &mut self.state.scroll, spinner_frame);
-                chat_widget.render(layout.chat, f.buffer_mut());
+        let header_model = self.state.recent_models.pending().map_or_else(
+            || self.model_name.clone(),
+            |next| format!("{} → {next} (next turn)", self.model_name),
+        );
+        self.terminal.draw(|f| {
+            let layout = calculate_layout(f.area());
+
+            render_header(
+                f,
+                layout.header,
+                &self.provider_name,
+                &header_model,
+                self.execution_model.as_deref(),
+                self.state.session_title.as_deref(),
+                self.state.auto_approve,
+            );
 
-                self.input_widget.render(layout.input, f);
+            let messages = self.state.messages_with_streaming();
+            let chat_widget = ChatWidget::new(
+                &messages,
+                &mut self.state.scroll,
+                &mut self.state.line_cache,
+                spinner_frame,
+            )
+            .search(self.state.search.as_ref())
+            .editing(self.state.editing)
+            .selected(self.state.selection)
+            .collapsed(&self.state.collapsed)
+            .expand_tool_groups(self.state.expand_tool_groups)
+            .model_badges(self.state.model_badges);
+            chat_widget.render(layout.chat, f.buffer_mut());
 
-                render_status(
+            self.input_widget.render(layout.input, f);
+            if !self.input_widget.is_empty() {
+                render_input_estimate(
                     f,
-                    layout.status,
-                    is_processing,
-                    elapsed,
-                    spinner_frame,
-                    last_usage.as_ref(),
-                    session_usage,
+                    layout.input,
+                    self.input_widget.estimated_tokens(),
+                    self.input_warn_tokens,
                 );
+            }
 
-                if let Some((request, selected, input_mode, feedback)) = &permission_modal {
-                    render_permission_modal(f, f.area(), request, *selected, *input_mode, feedback);
-                }
+            render_status(
+                f,
+                layout.status,
+                activity,
+                &progress,
+                spinner_frame,
+                last_usage.as_ref(),
+                session_usage,
+            );
+            if let Some(version) = &self.state.update_available {
+                render_update_hint(f, layout.status, version);
+            }
+            if queued_permissions > 0 {
+                render_permission_queue(f, layout.status, queued_permissions);
+            }
 
-                if let Some((models, selected, total)) = &model_picker_modal {
-                    render_model_picker_modal(f, f.area(), models, *selected, *total);
-                }
-            })?;
+            if let Some((request, selected, input_mode, feedback, diff_scroll)) = &permission_modal
+            {
+                render_permission_modal(
+                    f,
+                    f.area(),
+                    request,
+                    *selected,
+                    *input_mode,
+                    feedback,
+                    *diff_scroll,
+                );
+            }
 
-            if let Some(event) = self.event_rx.recv().await {
-                self.handle_event(event);
+            if let Some((models, selected, total)) = &model_picker_modal {
+                render_model_picker_modal(f, f.area(), models, *selected, *total);
             }
-        }
 
-        let _ = self.agent_cmd_tx.send(AgentCommand::Shutdown);
+            render_overlays(f, &self.state);
+            degrade_buffer(f.buffer_mut());
+        })?;
+        Ok(())
+    }
 
-        restore_terminal(&mut self.terminal)?;
+    fn model_changed(&mut self, provider: String, model: String) {
+        self.provider_name.clone_from(&provider);
+        self.model_name.clone_from(&model);
+        self.state
+            .add_system_message(format!("Switched to {provider}/{model}"));
 
-        Ok(())
+        let previous = self.state.recent_models.models().to_vec();
+        self.state.recent_models.record(model.clone());
+        if let Some(ref tx) = self.config_event_tx {
+            let _ = tx.send(ConfigEvent::ModelChanged { provider, model });
+            let recent = self.state.recent_models.models();
+            if recent != previous.as_slice() {
+                let patch = ConfigPatch::default().set("recent_models", recent.to_vec());
+                let _ = tx.send(ConfigEvent::SettingsChanged(patch));
+            }
+        }
+    }
+
+    fn cycle_model(&mut self) {
+        let message = if let Some(model) = self.state.recent_models.cycle_next() {
+            format!("Next message uses {model}.")
+        } else if self.state.recent_models.models().len() > 1 {
+            format!("Next message stays on {}.", self.model_name)
+        } else {
+            format!(
+                "No other recent models yet; the last {MAX_RECENT_MODELS} models you switch to show up here."
+            )
+        };
+        self.state.add_system_message(message);
+    }
+
+    fn execution_model_changed(&mut self, model: Option<String>) {
+        if let Some(model) = &model {
+            self.state.add_system_message(format!(
+                "Tool follow-up turns use {model}; {} plans each reply.",
+                self.model_name
+            ));
+        }
+        self.execution_model = model;
     }
 
     fn handle_event(&mut self, event: AppEvent) {
         match event {
-            AppEvent::Input(key) => {
-                self.handle_key_input(key);
-            }
+            AppEvent::Input(key) => self.handle_key_input(key),
             AppEvent::Paste(text) => {
                 let action = self.input_widget.handle_paste(text);
                 self.handle_input_action(action);
@@ -173,114 +362,263 @@ impl TuiApp {
                     self.state.scroll_down(delta.unsigned_abs().into());
                 }
             }
-            AppEvent::Tick => {
-                self.state.tick();
-            }
+            AppEvent::Tick => self.state.tick(),
             AppEvent::LLMChunk(chunk) => {
                 self.state.append_streaming(&chunk);
             }
-            AppEvent::LLMComplete(_message, usage) => {
-                let text = self.state.finalize_streaming();
-                if !text.is_empty() {
-                    self.state.add_assistant_message(text);
-                }
-                self.state.record_usage(usage);
-                self.state.stop_processing();
-            }
+            AppEvent::LLMComplete(_message, usage, model) => self.finish_response(usage, model),
             AppEvent::LLMError(error) => {
                 self.state.finalize_streaming();
-                self.state.add_system_message_with_level(
-                    format!("Error: {error}"),
-                    crate::tui::widgets::MessageLevel::Error,
-                );
+                self.state.add_error_message(format!("Error: {error}"));
+                self.state.add_turn_summary();
                 self.state.stop_processing();
             }
-            AppEvent::ToolStarted { name, input } => {
-                self.state.start_tool(&name, input);
-            }
-            AppEvent::ToolCompleted { name, result } => {
-                if (name == ToolType::WriteFile.name() || name == ToolType::UpdateFile.name())
-                    && let Some(output) = result.output()
-                    && let Some(metadata) = DiffMetadata::extract(output)
-                {
-                    let _ = self.event_tx.send(AppEvent::FileDiff {
-                        path: metadata.path,
-                        old_content: metadata.old_content,
-                        new_content: metadata.new_content,
-                    });
-                }
-                self.state.complete_tool(&name, &result);
-            }
+            AppEvent::ToolStarted { name, input } => self.state.start_tool(&name, input),
+            AppEvent::ToolCompleted { name, result } => self.handle_tool_completed(&name, &result),
             AppEvent::ToolFailed { name, error } => {
                 self.state.fail_tool(&name, error);
             }
             AppEvent::PermissionRequired {
                 request,
                 response_tx,
-            } => {
-                self.state.show_permission_modal(request, response_tx);
+            } => self.state.show_permission_modal(request, response_tx),
+            AppEvent::RunStateChanged(state) => self.state.run_state = state,
+            AppEvent::ReplayPrompt(text) => {
+                self.state.add_user_message(text);
+                self.state.start_processing();
             }
+            AppEvent::BatchPermissionRequired {
+                requests,
+                response_tx,
+            } => self
+                .state
+                .show_batch_permission_modal(requests, response_tx),
             AppEvent::FileDiff {
                 path,
                 old_content,
                 new_content,
+                format,
             } => {
-                self.state.add_file_diff(path, old_content, new_content);
+                self.state
+                    .add_file_diff(path, old_content, new_content, format);
+            }
+            AppEvent::ModelChanged { provider, model } => self.model_changed(provider, model),
+            AppEvent::ExecutionModelChanged(model) => self.execution_model_changed(model),
+            AppEvent::ModelSwitchError(error) => {
+                self.state
+                    .add_error_message(format!("Failed to switch model: {error}"));
             }
-            AppEvent::ModelChanged { provider, model } => {
-                self.provider_name.clone_from(&provider);
-                self.model_name.clone_from(&model);
+            AppEvent::SessionTitle(title) => {
+                self.state.session_title = title;
+            }
+            AppEvent::ResponseCached => {
                 self.state
-                    .add_system_message(format!("Switched to {provider}/{model}"));
+                    .add_system_message("Response served from cache".to_string());
+            }
+            AppEvent::UpdateAvailable(version) => self.state.update_available = Some(version),
+            event @ (AppEvent::GenerationChanged(_)
+            | AppEvent::GenerationError(_)
+            | AppEvent::CommitDraft(_)
+            | AppEvent::CommitFailed(_)
+            | AppEvent::ReviewComplete { .. }
+            | AppEvent::ReviewFailed(_)
+            | AppEvent::FixTestsProgress(_)
+            | AppEvent::FixTestsComplete(_)
+            | AppEvent::FixTestsFailed(_)
+            | AppEvent::DuoProgress(_)
+            | AppEvent::DuoComplete(_)
+            | AppEvent::DuoFailed(_)
+            | AppEvent::ShellOutput { .. }
+            | AppEvent::ShellFailed(_)
+            | AppEvent::Artifacts(_)
+            | AppEvent::ArtifactsFailed(_)
+            | AppEvent::Pins(_)
+            | AppEvent::PinsFailed(_)
+            | AppEvent::Checkpoints(_)
+            | AppEvent::CheckpointsFailed(_)
+            | AppEvent::RunControlApplied(_)
+            | AppEvent::RunControlRejected(_)
+            | AppEvent::Context(_)
+            | AppEvent::ContextDropped(_)
+            | AppEvent::ContextFailed(_)) => self.handle_workflow_event(event),
+        }
+    }
 
-                if let Some(ref tx) = self.config_event_tx {
-                    let _ = tx.send(ConfigEvent::ModelChanged { provider, model });
+    fn handle_workflow_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Artifacts(report)
+            | AppEvent::Pins(report)
+            | AppEvent::Checkpoints(report)
+            | AppEvent::RunControlApplied(report)
+            | AppEvent::RunControlRejected(report)
+            | AppEvent::ContextDropped(report) => {
+                self.state.add_system_message(report);
+            }
+            AppEvent::ArtifactsFailed(error)
+            | AppEvent::PinsFailed(error)
+            | AppEvent::CheckpointsFailed(error)
+            | AppEvent::ContextFailed(error) => {
+                self.state.add_error_message(error);
+            }
+            AppEvent::Context(snapshot) => self.state.show_context_modal(snapshot),
+            AppEvent::GenerationChanged(params) => {
+                self.state
+                    .add_system_message(format!("Generation settings: {params}"));
+            }
+            AppEvent::GenerationError(error) => {
+                self.state
+                    .add_error_message(format!("Failed to update settings: {error}"));
+            }
+            AppEvent::CommitDraft(draft) => self.state.show_commit_modal(draft),
+            AppEvent::CommitFailed(error) => self
+                .state
+                .add_error_message(format!("Cannot commit: {error}")),
+            AppEvent::ReviewComplete { title, findings } => {
+                self.state.stop_processing();
+                self.state.add_review(title, findings);
+            }
+            AppEvent::ReviewFailed(error) => {
+                self.state.stop_processing();
+                self.state
+                    .add_error_message(format!("Review failed: {error}"));
+            }
+            AppEvent::FixTestsProgress(status) => {
+                self.state.start_processing();
+                self.state.add_system_message(status);
+            }
+            AppEvent::FixTestsComplete(report) => {
+                self.state.stop_processing();
+                self.state.add_system_message(report.render());
+                self.state.add_turn_summary();
+            }
+            AppEvent::FixTestsFailed(error) => {
+                self.state.stop_processing();
+                self.state
+                    .add_error_message(format!("Cannot fix tests: {error}"));
+            }
+            AppEvent::DuoProgress(status) => self.state.add_system_message(status),
+            AppEvent::DuoComplete(summary) => {
+                self.state.stop_processing();
+                self.state.add_system_message(summary);
+                self.state.add_turn_summary();
+            }
+            AppEvent::DuoFailed(error) => {
+                self.state.stop_processing();
+                self.state.add_error_message(format!("Duo failed: {error}"));
+            }
+            AppEvent::ShellOutput { run, shared } => {
+                self.state.stop_processing();
+                self.state.add_shell_run(&run);
+                if shared {
+                    self.state.add_system_message(
+                        "The output goes to the model with your next message.".to_string(),
+                    );
                 }
             }
-            AppEvent::ModelSwitchError(error) => {
-                self.state.add_system_message_with_level(
-                    format!("Failed to switch model: {error}"),
-                    crate::tui::widgets::MessageLevel::Error,
-                );
+            AppEvent::ShellFailed(error) => {
+                self.state.stop_processing();
+                self.state
+                    .add_error_message(format!("Shell command not run: {error}"));
             }
+            _ => {}
         }
     }
 
+    fn handle_tool_completed(&mut self, name: &str, result: &ToolResult) {
+        if (name == ToolType::WriteFile.name() || name == ToolType::UpdateFile.name())
+            && let Some(output) = result.output()
+            && let Some(metadata) = DiffMetadata::extract(output)
+        {
+            let _ = self.event_tx.send(AppEvent::FileDiff {
+                path: metadata.path,
+                old_content: metadata.old_content,
+                new_content: metadata.new_content,
+                format: metadata.format,
+            });
+        }
+        self.state.complete_tool(name, result);
+    }
+
     fn handle_key_input(&mut self, key: crossterm::event::KeyEvent) {
         if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-            if self.state.has_model_picker() {
-                self.state.model_picker_cancel();
-                return;
-            }
-            if self.state.has_modal() {
-                self.state.permission_cancel();
+            if self.close_overlay() {
                 return;
             }
             if !self.input_widget.is_empty() {
                 self.input_widget.clear();
+                self.state.cancel_edit();
                 return;
             }
             self.state.quit();
             return;
         }
         if key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::CONTROL) {
-            if self.input_widget.is_empty() && !self.state.has_modal() {
+            if self.input_widget.is_empty()
+                && !self.state.has_modal()
+                && !self.state.has_session_browser()
+                && !self.state.has_config_form()
+                && !self.state.has_commit_modal()
+                && !self.state.has_memories_modal()
+                && !self.state.has_context_modal()
+                && !self.state.has_paste_viewer()
+                && !self.state.has_snippet_form()
+            {
                 self.state.quit();
             }
             return;
         }
-        if self.state.has_model_picker() {
-            self.handle_model_picker_input(key);
+        if self.handle_overlay_input(key)
+            || self.handle_edit_key(key)
+            || self.handle_selection_key(key)
+        {
             return;
         }
-        if self.state.has_modal() {
-            self.handle_modal_input(key);
+        if self
+            .cycle_model_key
+            .is_some_and(|binding| binding.matches(&key))
+        {
+            self.cycle_model();
             return;
         }
         if key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL) {
             self.state.clear_messages();
             return;
         }
+        if key.code == KeyCode::Char('o') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.state.expand_tool_groups = !self.state.expand_tool_groups;
+            return;
+        }
+        if key.code == KeyCode::Char('f') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.input_widget.set_text("/search ");
+            return;
+        }
+        if key.code == KeyCode::Char('t')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && let Some((id, content)) = self.input_widget.pasted_block_near_cursor()
+        {
+            self.state.show_paste_viewer(id, content.to_string());
+            return;
+        }
+        if self.state.has_search() {
+            let navigate = key.modifiers.contains(KeyModifiers::ALT)
+                || (self.state.is_search_navigating() && self.input_widget.is_empty());
+            match key.code {
+                KeyCode::Char('n') if navigate => {
+                    self.state.search_older();
+                    return;
+                }
+                KeyCode::Char('N') if navigate => {
+                    self.state.search_newer();
+                    return;
+                }
+                KeyCode::Esc if self.input_widget.is_empty() => {
+                    self.state.clear_search();
+                    return;
+                }
+                KeyCode::Char(_) => self.state.stop_search_navigation(),
+                _ => {}
+            }
+        }
         match key.code {
             KeyCode::PageUp => {
                 self.state.scroll_up(10);
@@ -304,12 +642,380 @@ impl TuiApp {
         self.handle_input_action(action);
     }
 
+    fn handle_edit_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        if self.state.is_editing() && key.code == KeyCode::Esc {
+            self.state.cancel_edit();
+            self.input_widget.clear();
+            return true;
+        }
+        if !key.modifiers.contains(KeyModifiers::ALT)
+            || self.state.is_processing
+            || !(self.input_widget.is_empty() || self.state.is_editing())
+        {
+            return false;
+        }
+        let text = match key.code {
+            KeyCode::Up => self.state.edit_older(),
+            KeyCode::Down => self.state.edit_newer(),
+            _ => return false,
+        };
+        match text {
+            Some(text) => self.input_widget.set_text(&text),
+            None => self.input_widget.clear(),
+        }
+        true
+    }
+
+    fn handle_selection_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        if !self.state.is_selecting() {
+            return key.code == KeyCode::Esc
+                && key.modifiers.is_empty()
+                && self.input_widget.is_empty()
+                && !self.state.has_search()
+                && self.state.start_selection();
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.state.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+            KeyCode::Char(' ') | KeyCode::Enter => self.state.toggle_collapse_selected(),
+            KeyCode::Char('y') => self.copy_selected(),
+            KeyCode::Char('r') => self.rerun_selected(),
+            KeyCode::Char('a') => self.apply_selected(),
+            KeyCode::Char('p') => self.page_selected(),
+            KeyCode::Char('e') => self.edit_selected_file(),
+            KeyCode::Char('d') => self.forget_selected(),
+            KeyCode::Esc | KeyCode::Char('v' | 'q') => self.state.exit_selection(),
+            _ => {
+                self.state.exit_selection();
+                return false;
+            }
+        }
+        true
+    }
+
+    fn finish_response(&mut self, usage: Usage, model: Option<ModelTag>) {
+        let text = self.state.finalize_streaming();
+        let copied = match self.auto_copy {
+            _ if text.is_empty() || self.replaying => None,
+            AutoCopy::Off => None,
+            AutoCopy::Reply => Some(text.clone()),
+            AutoCopy::Code => last_code_block(&text),
+        };
+        if !text.is_empty() {
+            self.state.add_assistant_message(text, model);
+        }
+        self.state.record_usage(usage);
+        self.state.add_turn_summary();
+        self.state.stop_processing();
+        if let Some(copied) = copied {
+            self.copy_text(&copied);
+        }
+    }
+
+    fn copy_reply(&mut self, arg: &str) {
+        let Some(reply) = self
+            .state
+            .messages
+            .iter()
+            .rev()
+            .find_map(|message| match message {
+                ChatMessage::Assistant { text, .. } => Some(text),
+                _ => None,
+            })
+        else {
+            self.state
+                .add_system_message("No reply to copy yet.".to_string());
+            return;
+        };
+        let text = match arg {
+            "" | "last" => reply.clone(),
+            "code" => {
+                let Some(code) = last_code_block(reply) else {
+                    self.state
+                        .add_system_message("The last reply has no code block.".to_string());
+                    return;
+                };
+                code
+            }
+            _ => {
+                self.state
+                    .add_system_message("Usage: /copy [last|code]".to_string());
+                return;
+            }
+        };
+        self.copy_text(&text);
+    }
+
+    fn copy_selected(&mut self) {
+        let Some(text) = self.state.selected_message().map(ChatMessage::plain_text) else {
+            return;
+        };
+        self.copy_text(&text);
+    }
+
+    fn copy_text(&mut self, text: &str) {
+        match copy_to_clipboard(text) {
+            Ok(()) => self.state.add_system_message(format!(
+                "Copied {} characters to the clipboard.",
+                text.chars().count()
+            )),
+            Err(e) => self.state.add_error_message(format!("Failed to copy: {e}")),
+        }
+    }
+
+    fn rerun_selected(&mut self) {
+        let Some(ChatMessage::ToolExecution {
+            tool_type, input, ..
+        }) = self.state.selected_message()
+        else {
+            self.state
+                .add_system_message("Only tool calls can be re-run.".to_string());
+            return;
+        };
+        if self.state.is_processing {
+            self.state
+                .add_system_message("Wait for the current response to finish.".to_string());
+            return;
+        }
+        let name = tool_type.name().to_string();
+        match serde_json::from_str(input) {
+            Ok(input) => {
+                let _ = self
+                    .agent_cmd_tx
+                    .send(AgentCommand::RerunTool { name, input });
+            }
+            Err(e) => self
+                .state
+                .add_error_message(format!("Cannot re-run {name}: {e}")),
+        }
+    }
+
+    fn apply_selected(&mut self) {
+        let blocks =
+            if let Some(ChatMessage::Assistant { text, .. }) = self.state.selected_message() {
+                extract_code_blocks(text)
+            } else {
+                Vec::new()
+            };
+        if blocks.is_empty() {
+            self.state.add_system_message(
+                "Select a reply with a code block that names its file (```rust src/main.rs)."
+                    .to_string(),
+            );
+            return;
+        }
+        self.send_code_blocks(blocks);
+    }
+
+    fn apply_code(&mut self, arg: &str) {
+        let Some(blocks) = self
+            .state
+            .messages
+            .iter()
+            .rev()
+            .filter_map(|message| match message {
+                ChatMessage::Assistant { text, .. } => Some(extract_code_blocks(text)),
+                _ => None,
+            })
+            .find(|blocks| !blocks.is_empty())
+        else {
+            self.state.add_system_message(
+                "No recent reply has a code block that names its file (```rust src/main.rs or a path on the line before)."
+                    .to_string(),
+            );
+            return;
+        };
+        let chosen = match arg {
+            "" if blocks.len() > 1 => {
+                self.state.add_system_message(format_code_blocks(&blocks));
+                return;
+            }
+            "" | "all" => blocks,
+            n => {
+                let Some(block) = n
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .and_then(|index| blocks.get(index))
+                else {
+                    self.state
+                        .add_error_message(format!("No code block {n}; /apply lists them"));
+                    return;
+                };
+                vec![block.clone()]
+            }
+        };
+        self.send_code_blocks(chosen);
+    }
+
+    fn send_code_blocks(&mut self, blocks: Vec<CodeBlock>) {
+        if self.state.is_processing {
+            self.state
+                .add_system_message("Wait for the current response to finish.".to_string());
+            return;
+        }
+        let _ = self.agent_cmd_tx.send(AgentCommand::ApplyCode(blocks));
+    }
+
+    fn open_link(&mut self, arg: &str) {
+        let Some(links) = self
+            .state
+            .messages
+            .iter()
+            .rev()
+            .map(ChatMessage::links)
+            .find(|links| !links.is_empty())
+        else {
+            self.state
+                .add_system_message("No recent reply has a link.".to_string());
+            return;
+        };
+        let index = match arg {
+            "" if links.len() > 1 => {
+                self.state.add_system_message(format_links(&links));
+                return;
+            }
+            "" => Some(0),
+            n => n.parse::<usize>().ok().and_then(|n| n.checked_sub(1)),
+        };
+        let Some(link) = index.and_then(|index| links.get(index)) else {
+            self.state
+                .add_error_message(format!("No link {arg}; /open lists them"));
+            return;
+        };
+        match open_in_browser(&link.url) {
+            Ok(()) => self
+                .state
+                .add_system_message(format!("Opened {}", link.url)),
+            Err(e) => self
+                .state
+                .add_error_message(format!("Cannot open {}: {e}", link.url)),
+        }
+    }
+
+    fn page_selected(&mut self) {
+        let Some(message) = self.state.selected_message() else {
+            return;
+        };
+        let is_diff = matches!(
+            message,
+            ChatMessage::FileDiff { .. } | ChatMessage::DiffSummary { .. }
+        );
+        let text = message.plain_text();
+        let result = suspend(&mut self.terminal, &self.input_paused, || {
+            if is_diff {
+                open_diff_in_pager(&text)
+            } else {
+                open_in_pager(&text)
+            }
+        });
+        if let Err(e) = result.and_then(|result| result) {
+            self.state
+                .add_error_message(format!("Failed to open pager: {e}"));
+        }
+    }
+
+    fn edit_selected_file(&mut self) {
+        let Some(ChatMessage::FileDiff {
+            path,
+            old_content,
+            new_content,
+            ..
+        }) = self.state.selected_message()
+        else {
+            self.state
+                .add_system_message("Select a file diff to open it in $EDITOR.".to_string());
+            return;
+        };
+        let path = PathBuf::from(path);
+        let line = first_changed_line(old_content, new_content);
+        let result = suspend(&mut self.terminal, &self.input_paused, || {
+            open_in_editor(&path, line)
+        });
+        if let Err(e) = result.and_then(|result| result) {
+            self.state
+                .add_error_message(format!("Failed to open editor: {e}"));
+        }
+    }
+
+    fn forget_selected(&mut self) {
+        if self.state.is_processing {
+            self.state
+                .add_system_message("Wait for the current response to finish.".to_string());
+            return;
+        }
+        if let Some(turns_back) = self.state.forget_selected() {
+            let _ = self
+                .agent_cmd_tx
+                .send(AgentCommand::ForgetTurn { turns_back });
+        }
+    }
+
+    fn close_overlay(&mut self) -> bool {
+        if self.state.has_session_browser() {
+            self.state.close_session_browser();
+        } else if self.state.has_config_form() {
+            self.state.close_config_form();
+        } else if self.state.has_commit_modal() {
+            self.state.close_commit_modal();
+        } else if self.state.has_memories_modal() {
+            self.state.close_memories_modal();
+        } else if self.state.has_context_modal() {
+            self.state.close_context_modal();
+        } else if self.state.has_paste_viewer() {
+            self.state.close_paste_viewer();
+        } else if self.state.has_snippet_form() {
+            self.state.close_snippet_form();
+        } else if self.state.has_model_picker() {
+            self.state.model_picker_cancel();
+        } else if self.state.has_batch_permission() {
+            self.state.batch_permission_cancel();
+        } else if self.state.has_modal() {
+            self.state.permission_cancel();
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn handle_overlay_input(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        if self.state.has_session_browser() {
+            self.handle_session_browser_input(key);
+        } else if self.state.has_config_form() {
+            self.handle_config_form_input(key);
+        } else if self.state.has_commit_modal() {
+            self.handle_commit_input(key);
+        } else if self.state.has_memories_modal() {
+            self.handle_memories_input(key);
+        } else if self.state.has_context_modal() {
+            self.handle_context_input(key);
+        } else if self.state.has_paste_viewer() {
+            self.handle_paste_viewer_input(key);
+        } else if self.state.has_snippet_form() {
+            self.handle_snippet_form_input(key);
+        } else if self.state.has_model_picker() {
+            self.handle_model_picker_input(key);
+        } else if self.state.has_batch_permission() {
+            self.handle_batch_permission_input(key);
+        } else if self.state.has_modal() {
+            self.handle_modal_input(key);
+        } else {
+            return false;
+        }
+        true
+    }
+
     fn handle_modal_input(&mut self, key: crossterm::event::KeyEvent) {
+        if matches!(key.code, KeyCode::PageUp | KeyCode::PageDown) {
+            self.state
+                .permission_scroll_diff(key.code == KeyCode::PageUp, DIFF_SCROLL_LINES);
+            return;
+        }
         if self.state.permission_in_input_mode() {
             match key.code {
                 KeyCode::Enter => {
-                    if let Some(feedback) = self.state.permission_confirm() {
-                        self.state.add_system_message(format!("Denied: {feedback}"));
+                    if let Some(message) = self.state.permission_confirm() {
+                        self.state.add_system_message(message);
                     }
                 }
                 KeyCode::Esc => {
@@ -339,8 +1045,8 @@ impl TuiApp {
                 self.state.permission_select_next();
             }
             KeyCode::Enter => {
-                if let Some(feedback) = self.state.permission_confirm() {
-                    self.state.add_system_message(format!("Denied: {feedback}"));
+                if let Some(message) = self.state.permission_confirm() {
+                    self.state.add_system_message(message);
                 }
             }
             KeyCode::Esc => {
@@ -350,6 +1056,484 @@ impl TuiApp {
         }
     }
 
+    fn handle_batch_permission_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(modal) = &mut self.state.batch_permission else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => modal.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => modal.select_next(),
+            KeyCode::Char(' ') => modal.toggle_selected(),
+            KeyCode::Char('a') => modal.approve_all(),
+            KeyCode::Enter => {
+                if let Some(message) = self.state.batch_permission_confirm() {
+                    self.state.add_system_message(message);
+                }
+            }
+            KeyCode::Esc => self.state.batch_permission_cancel(),
+            _ => {}
+        }
+    }
+
+    fn set_thinking(&mut self, enabled: Option<bool>) {
+        let Some(enabled) = enabled else {
+            self.state
+                .add_system_message("Usage: /think on|off".to_string());
+            return;
+        };
+        let _ = self.agent_cmd_tx.send(AgentCommand::SetGeneration {
+            key: "thinking".to_string(),
+            value: if enabled { "on" } else { "off" }.to_string(),
+        });
+    }
+
+    fn set_auto_approve(&mut self, enabled: Option<bool>) {
+        let Some(enabled) = enabled else {
+            let status = if self.state.auto_approve { "on" } else { "off" };
+            self.state
+                .add_system_message(format!("Auto-approve is {status}. Usage: /auto on|off"));
+            return;
+        };
+        if enabled && self.audit_log.is_none() {
+            self.state.add_error_message(
+                "Auto-approve requires the audit log ([audit] enabled = true).".to_string(),
+            );
+            return;
+        }
+        self.state.auto_approve = enabled;
+        let _ = self
+            .agent_cmd_tx
+            .send(AgentCommand::SetAutoApprove(enabled));
+        if enabled {
+            self.state.add_error_message(
+                "Auto-approve is on: every tool call runs without asking. Decisions are still written to the audit log; /auto off to stop.".to_string(),
+            );
+        } else {
+            self.state
+                .add_system_message("Auto-approve is off; tool calls will ask again.".to_string());
+        }
+    }
+
+    fn show_audit_log(&mut self, limit: usize) {
+        let Some(log) = &self.audit_log else {
+            self.state
+                .add_system_message("Audit logging is disabled ([audit] enabled = false).".into());
+            return;
+        };
+        match log.recent(limit) {
+            Ok(entries) if entries.is_empty() => {
+                self.state.add_system_message(format!(
+                    "No audit entries yet ({}).",
+                    log.path().display()
+                ));
+            }
+            Ok(entries) => {
+                let lines: Vec<String> = entries
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{}  {}",
+                            entry
+                                .timestamp
+                                .with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M:%S"),
+                            entry.event
+                        )
+                    })
+                    .collect();
+                self.state.add_system_message(format!(
+                    "Last {} audit entries ({}):\n{}",
+                    entries.len(),
+                    log.path().display(),
+                    lines.join("\n")
+                ));
+            }
+            Err(e) => self
+                .state
+                .add_error_message(format!("Failed to read audit log: {e}")),
+        }
+    }
+
+    fn open_session_browser(&mut self) {
+        let Some(store) = self.session_store.clone() else {
+            self.state.add_system_message(
+                "Session saving is disabled ([sessions] enabled = false).".to_string(),
+            );
+            return;
+        };
+        if let Err(e) = self.state.show_session_browser(store) {
+            self.state
+                .add_error_message(format!("Failed to load sessions: {e}"));
+        }
+    }
+
+    fn handle_session_browser_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(browser) = &mut self.state.session_browser else {
+            return;
+        };
+
+        if browser.is_renaming() {
+            match key.code {
+                KeyCode::Enter => {
+                    let renamed = browser
+                        .commit_rename()
+                        .map(|session| session.map(|s| (s.id, s.title.clone())));
+                    match renamed {
+                        Ok(Some((id, title))) => {
+                            let _ = self
+                                .agent_cmd_tx
+                                .send(AgentCommand::RenameSession { id, title });
+                        }
+                        Ok(None) => {}
+                        Err(e) => self.report_session_error("rename", &e),
+                    }
+                }
+                KeyCode::Esc => browser.cancel_rename(),
+                KeyCode::Backspace => browser.rename_backspace(),
+                KeyCode::Char(c) => browser.rename_char(c),
+                _ => {}
+            }
+            return;
+        }
+
+        if key.code != KeyCode::Char('d') {
+            browser.confirm_delete = false;
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => browser.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => browser.select_next(),
+            KeyCode::Char('r') => browser.start_rename(),
+            KeyCode::Char('d') => {
+                if let Err(e) = browser.delete_selected() {
+                    self.report_session_error("delete", &e);
+                }
+            }
+            KeyCode::Char('e') => {
+                let exported = std::env::current_dir()
+                    .map_err(Into::into)
+                    .and_then(|dir| browser.export_selected(&dir));
+                match exported {
+                    Ok(Some(path)) => {
+                        self.state
+                            .add_system_message(format!("Exported session to {}", path.display()));
+                    }
+                    Ok(None) => {}
+                    Err(e) => self.report_session_error("export", &e),
+                }
+            }
+            KeyCode::Enter => {
+                let record = browser.selected_session().cloned();
+                self.state.close_session_browser();
+                if let Some(record) = record {
+                    self.state.restore_session(&record);
+                    self.state.add_system_message(format!(
+                        "Resumed session \"{}\".",
+                        record.display_title()
+                    ));
+                    let _ = self
+                        .agent_cmd_tx
+                        .send(AgentCommand::ResumeSession(Box::new(record)));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.state.close_session_browser(),
+            _ => {}
+        }
+    }
+
+    fn open_memories(&mut self) {
+        match self.memory_store.clone() {
+            Some(store) => self.state.show_memories_modal(store),
+            None => self.state.add_system_message(
+                "Memories are disabled ([memories] enabled = false).".to_string(),
+            ),
+        }
+    }
+
+    fn remember(&mut self, memory: &str) {
+        let Some(store) = &self.memory_store else {
+            self.state.add_system_message(
+                "Memories are disabled ([memories] enabled = false).".to_string(),
+            );
+            return;
+        };
+        match store.add(memory) {
+            Ok(true) => {
+                self.state
+                    .add_system_message(format!("Remembered: {memory}"));
+                let _ = self.agent_cmd_tx.send(AgentCommand::ReloadMemories);
+            }
+            Ok(false) => self
+                .state
+                .add_system_message("Already remembered.".to_string()),
+            Err(e) => self
+                .state
+                .add_error_message(format!("Failed to save memory: {e}")),
+        }
+    }
+
+    fn handle_memories_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(modal) = &mut self.state.memories_modal else {
+            return;
+        };
+
+        let result = if modal.is_editing() {
+            match key.code {
+                KeyCode::Enter => modal.commit_edit(),
+                KeyCode::Esc => {
+                    modal.cancel_edit();
+                    Ok(false)
+                }
+                KeyCode::Backspace => {
+                    modal.input_backspace();
+                    Ok(false)
+                }
+                KeyCode::Char(c) => {
+                    modal.input_char(c);
+                    Ok(false)
+                }
+                _ => Ok(false),
+            }
+        } else {
+            if key.code != KeyCode::Char('d') {
+                modal.confirm_delete = false;
+            }
+            match key.code {
+                KeyCode::Char('d') => modal.delete_selected().map(|removed| removed.is_some()),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    modal.select_prev();
+                    Ok(false)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    modal.select_next();
+                    Ok(false)
+                }
+                KeyCode::Char('a') => {
+                    modal.start_add();
+                    Ok(false)
+                }
+                KeyCode::Char('e') | KeyCode::Enter => {
+                    modal.start_edit();
+                    Ok(false)
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.state.close_memories_modal();
+                    Ok(false)
+                }
+                _ => Ok(false),
+            }
+        };
+
+        match result {
+            Ok(true) => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::ReloadMemories);
+            }
+            Ok(false) => {}
+            Err(e) => self
+                .state
+                .add_error_message(format!("Failed to update memories: {e}")),
+        }
+    }
+
+    fn handle_context_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(modal) = &mut self.state.context_modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => modal.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => modal.select_next(),
+            KeyCode::Char(' ') => modal.toggle_selected(),
+            KeyCode::Enter => {
+                let sources = modal.dropped_sources();
+                let messages = modal.snapshot.messages;
+                self.state.close_context_modal();
+                if !sources.is_empty() {
+                    let _ = self
+                        .agent_cmd_tx
+                        .send(AgentCommand::DropContext { messages, sources });
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.state.close_context_modal(),
+            _ => {}
+        }
+    }
+
+    fn report_session_error(&mut self, action: &str, error: &crate::core::error::AgentError) {
+        self.state
+            .add_error_message(format!("Failed to {action} session: {error}"));
+    }
+
+    fn open_config_form(&mut self) {
+        self.state.show_config_form(ConfigSettings {
+            model: Some(self.model_name.clone()),
+            custom_system_prompt: self.custom_system_prompt.clone(),
+            permission_mode: self.permission_mode,
+            theme: current_theme(),
+        });
+    }
+
+    fn handle_config_form_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(form) = &mut self.state.config_form else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::BackTab => form.prev_field(),
+            KeyCode::Down | KeyCode::Tab => form.next_field(),
+            KeyCode::Left => form.cycle(false),
+            KeyCode::Right => form.cycle(true),
+            KeyCode::Backspace => form.backspace(),
+            KeyCode::Char(c) => form.input_char(c),
+            KeyCode::Enter => {
+                if let Ok(changes) = form.submit() {
+                    self.state.close_config_form();
+                    self.apply_config_changes(changes);
+                }
+            }
+            KeyCode::Esc => self.state.close_config_form(),
+            _ => {}
+        }
+    }
+
+    fn apply_config_changes(&mut self, changes: ConfigChanges) {
+        let ConfigChanges { model, patch } = changes;
+        if model.is_none() && patch.is_empty() {
+            self.state
+                .add_system_message("Settings unchanged.".to_string());
+            return;
+        }
+
+        if let Some(theme) = patch.theme {
+            set_theme(theme);
+        }
+        if let Some(model_name) = model {
+            let _ = self
+                .agent_cmd_tx
+                .send(AgentCommand::SwitchModel { model_name });
+        }
+        if patch.custom_system_prompt.is_some() || patch.permission_mode.is_some() {
+            if let Some(prompt) = &patch.custom_system_prompt {
+                self.custom_system_prompt = Some(prompt.clone()).filter(|p| !p.is_empty());
+            }
+            if let Some(mode) = patch.permission_mode {
+                self.permission_mode = mode;
+            }
+            let _ = self.agent_cmd_tx.send(AgentCommand::UpdateSettings {
+                custom_system_prompt: self.custom_system_prompt.clone(),
+                permission_mode: self.permission_mode,
+            });
+        }
+        if !patch.is_empty()
+            && let Some(tx) = &self.config_event_tx
+        {
+            let _ = tx.send(ConfigEvent::SettingsChanged(patch));
+        }
+        self.state.add_system_message("Settings saved.".to_string());
+    }
+
+    fn handle_commit_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(modal) = &mut self.state.commit_modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => modal.newline(),
+            KeyCode::Enter
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT) =>
+            {
+                modal.newline();
+            }
+            KeyCode::Enter => {
+                if let Some(summary) = modal.commit() {
+                    self.state.close_commit_modal();
+                    self.state
+                        .add_system_message(format!("Committed {summary}"));
+                }
+            }
+            KeyCode::Backspace => modal.backspace(),
+            KeyCode::Char(c) => modal.input_char(c),
+            KeyCode::Esc => {
+                self.state.close_commit_modal();
+                self.state
+                    .add_system_message("Commit cancelled.".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_paste_viewer_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(viewer) = &mut self.state.paste_viewer else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => viewer.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => viewer.scroll_down(1),
+            KeyCode::PageUp => viewer.scroll_up(DIFF_SCROLL_LINES),
+            KeyCode::PageDown => viewer.scroll_down(DIFF_SCROLL_LINES),
+            KeyCode::Char('d') => {
+                let id = viewer.id;
+                self.state.close_paste_viewer();
+                if self.input_widget.remove_pasted_block(id) {
+                    self.state
+                        .add_system_message(format!("Removed pasted block #{id} from the input."));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.state.close_paste_viewer(),
+            _ => {}
+        }
+    }
+
+    fn expand_snippet(&mut self, args: &str) -> bool {
+        let args = args.trim();
+        let (name, args) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let Some(snippet) = self.snippets.get(name) else {
+            return false;
+        };
+        let values = snippet.values_from_args(args);
+        if values.len() == snippet.placeholders().len() {
+            self.input_widget.set_text(&snippet.fill(&values));
+        } else {
+            self.state.snippet_form = Some(SnippetForm::new(snippet, &values));
+        }
+        true
+    }
+
+    fn show_snippets(&mut self) {
+        if self.snippets.is_empty() {
+            self.state.add_system_message(
+                "No snippets yet. Add them under [snippets] in the config or as files in the snippets directory.".to_string(),
+            );
+            return;
+        }
+        let names: Vec<String> = self
+            .snippets
+            .names()
+            .map(|name| format!("#{name}"))
+            .collect();
+        self.state
+            .add_system_message(format!("Snippets: {}", names.join(", ")));
+    }
+
+    fn handle_snippet_form_input(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(form) = &mut self.state.snippet_form else {
+            return;
+        };
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => form.select_next(),
+            KeyCode::BackTab | KeyCode::Up => form.select_prev(),
+            KeyCode::Enter if !form.is_last() => form.select_next(),
+            KeyCode::Enter => {
+                let filled = form.filled();
+                self.state.close_snippet_form();
+                self.input_widget.set_text(&filled);
+            }
+            KeyCode::Backspace => form.backspace(),
+            KeyCode::Char(c) => form.input_char(c),
+            KeyCode::Esc => self.state.close_snippet_form(),
+            _ => {}
+        }
+    }
+
     fn handle_model_picker_input(&mut self, key: crossterm::event::KeyEvent) {
         match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
@@ -377,16 +1561,55 @@ impl TuiApp {
             InputAction::Continue | InputAction::Clear => {}
 
             InputAction::Submit(text) => {
-                if text.starts_with('/') {
+                if self.replaying
+                    && !matches!(
+                        SlashCommand::parse(&text),
+                        SlashCommand::Help | SlashCommand::Exit | SlashCommand::Search(_)
+                    )
+                {
+                    self.state.add_system_message(
+                        "This is a replay; only /search, /help and /exit are available."
+                            .to_string(),
+                    );
+                } else if text.starts_with('/') {
                     self.handle_slash_command(&text);
-                } else {
+                } else if let Some(input) = parse_shell_input(&text) {
+                    let command = AgentCommand::Shell {
+                        command: input.command.to_string(),
+                        share: input.share,
+                    };
+                    self.submit_shell(text, command);
+                } else if let Some(memory) = parse_remember(&text) {
+                    self.remember(memory);
+                } else if !text
+                    .strip_prefix('#')
+                    .is_some_and(|shortcut| self.expand_snippet(shortcut))
+                {
+                    let command = match self.state.take_edit_branch() {
+                        Some(turns_back) => {
+                            self.state.add_system_message(
+                                "Branched the conversation; the previous version stays in /sessions."
+                                    .to_string(),
+                            );
+                            AgentCommand::Branch {
+                                turns_back,
+                                user_message: text.clone(),
+                            }
+                        }
+                        None => AgentCommand::Run {
+                            user_message: text.clone(),
+                        },
+                    };
+                    if let Some(model_name) = self.state.recent_models.take_pending() {
+                        let _ = self
+                            .agent_cmd_tx
+                            .send(AgentCommand::SwitchModel { model_name });
+                    }
                     self.state.add_to_history(text.clone());
-                    self.state.add_user_message(text.clone());
+                    self.state.add_user_message(text);
                     self.state.start_processing();
 
-                    let _ = self
-                        .agent_cmd_tx
-                        .send(AgentCommand::Run { user_message: text });
+                    let _ = self.agent_cmd_tx.send(command);
                 }
             }
 
@@ -406,10 +1629,48 @@ impl TuiApp {
         }
     }
 
+    fn submit_shell(&mut self, text: String, command: AgentCommand) {
+        self.state.add_to_history(text.clone());
+        self.state.add_user_message(text);
+        self.state.start_processing();
+        let _ = self.agent_cmd_tx.send(command);
+    }
+
+    fn set_generation(&mut self, args: &[String]) {
+        match args {
+            [] => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::ShowGeneration);
+            }
+            [key, value] => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::SetGeneration {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+            _ => {
+                self.state.add_system_message(format!(
+                    "Usage: /set <{}> <value|default>",
+                    GENERATION_KEYS.join("|")
+                ));
+            }
+        }
+    }
+
+    fn search_chat(&mut self, query: &str) {
+        if query.is_empty() {
+            self.state.clear_search();
+            self.state
+                .add_system_message("Usage: /search <query>".to_string());
+        } else if self.state.start_search(query) == 0 {
+            self.state
+                .add_system_message(format!("No matches for '{query}'."));
+        }
+    }
+
     fn handle_slash_command(&mut self, command: &str) {
         match SlashCommand::parse(command) {
             SlashCommand::Help => {
-                self.state.add_system_message(HELP_TEXT.to_string());
+                self.state.add_system_message(t(Msg::Help).to_string());
             }
             SlashCommand::Exit => {
                 self.state.quit();
@@ -419,9 +1680,83 @@ impl TuiApp {
                 self.state
                     .add_system_message("Chat history cleared.".to_string());
             }
-            SlashCommand::Model => {
+            SlashCommand::Model(None) => {
                 self.state.show_model_picker();
             }
+            SlashCommand::Model(Some(model_name)) => {
+                let _ = self
+                    .agent_cmd_tx
+                    .send(AgentCommand::SwitchModel { model_name });
+            }
+            SlashCommand::Config => self.open_config_form(),
+            SlashCommand::Set(args) => self.set_generation(&args),
+            SlashCommand::Search(query) => self.search_chat(&query),
+            SlashCommand::Sessions => self.open_session_browser(),
+            SlashCommand::Memories => self.open_memories(),
+            SlashCommand::Audit(limit) => self.show_audit_log(limit),
+            SlashCommand::Apply(arg) => self.apply_code(&arg),
+            SlashCommand::Open(arg) => self.open_link(&arg),
+            SlashCommand::Copy(arg) => self.copy_reply(&arg),
+            SlashCommand::Artifacts(args) => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Artifacts(args));
+            }
+            SlashCommand::Pins(PinCommand::Pin(arg) | PinCommand::Unpin(arg)) if arg.is_empty() => {
+                self.state
+                    .add_system_message("Usage: /pin <path>, /unpin <n|path>".to_string());
+            }
+            SlashCommand::Pins(command) => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Pins(command));
+            }
+            SlashCommand::Checkpoints(args) => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Checkpoints(args));
+            }
+            SlashCommand::Context => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Context);
+            }
+            SlashCommand::Auto(enabled) => self.set_auto_approve(enabled),
+            SlashCommand::Think(enabled) => self.set_thinking(enabled),
+            SlashCommand::Pause => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Pause);
+            }
+            SlashCommand::Resume => {
+                let _ = self.agent_cmd_tx.send(AgentCommand::Resume);
+            }
+            SlashCommand::Snippet(args) if args.is_empty() => self.show_snippets(),
+            SlashCommand::Snippet(args) => {
+                if !self.expand_snippet(&args) {
+                    self.state.add_system_message(format!(
+                        "Unknown snippet: {args}. Type /snippet to list them."
+                    ));
+                }
+            }
+            SlashCommand::Review(target) => {
+                self.state
+                    .add_system_message(format!("Reviewing {target}..."));
+                self.state.start_processing();
+                let _ = self.agent_cmd_tx.send(AgentCommand::Review(target));
+            }
+            SlashCommand::FixTests(command) => {
+                self.state
+                    .add_system_message("Running tests...".to_string());
+                self.state.start_processing();
+                let _ = self.agent_cmd_tx.send(AgentCommand::FixTests(command));
+            }
+            SlashCommand::Duo(task) if task.is_empty() => {
+                self.state
+                    .add_system_message("Usage: /duo <task>".to_string());
+            }
+            SlashCommand::Duo(task) => {
+                self.state.add_system_message(
+                    "Implementer and reviewer are working on it...".to_string(),
+                );
+                self.state.start_processing();
+                let _ = self.agent_cmd_tx.send(AgentCommand::Duo(task));
+            }
+            SlashCommand::Commit => {
+                self.state
+                    .add_system_message("Drafting commit message...".to_string());
+                let _ = self.agent_cmd_tx.send(AgentCommand::DraftCommit);
+            }
             SlashCommand::NotImplemented(cmd) => {
                 self.state
                     .add_system_message(format!("Command '{cmd}' is not yet implemented."));
@@ -435,6 +1770,40 @@ impl TuiApp {
     }
 }
 
+fn render_overlays(f: &mut ratatui::Frame, state: &AppState) {
+    if let Some(modal) = &state.batch_permission {
+        render_batch_permission_modal(f, f.area(), modal);
+    }
+
+    if let Some(browser) = &state.session_browser {
+        render_session_browser_modal(f, f.area(), browser);
+    }
+
+    if let Some(form) = &state.config_form {
+        render_config_form_modal(f, f.area(), form);
+    }
+
+    if let Some(form) = &state.snippet_form {
+        render_snippet_form(f, f.area(), form);
+    }
+
+    if let Some(viewer) = &state.paste_viewer {
+        render_paste_viewer(f, f.area(), viewer);
+    }
+
+    if let Some(modal) = &state.commit_modal {
+        render_commit_modal(f, f.area(), modal);
+    }
+
+    if let Some(modal) = &state.memories_modal {
+        render_memories_modal(f, f.area(), modal);
+    }
+
+    if let Some(modal) = &state.context_modal {
+        render_context_modal(f, f.area(), modal);
+    }
+}
+
 impl Drop for TuiApp {
     fn drop(&mut self) {
         use crossterm::event::DisableBracketedPaste;
@@ -1,16 +1,46 @@
+use std::fmt::Write as _;
+
 use crate::ui::theme::{BoxChars, Theme};
 use ratatui::Frame;
 use ratatui::layout::Rect;
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-pub fn render_header(frame: &mut Frame, area: Rect, provider_name: &str, model_name: &str) {
+pub fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    provider_name: &str,
+    model_name: &str,
+    execution_model: Option<&str>,
+    session_title: Option<&str>,
+    auto_approve: bool,
+) {
     let title = format!("Smith v{}", env!("CARGO_PKG_VERSION"));
-    let subtitle = format!("Provider: {provider_name} | Model: {model_name}");
+    let mut title_spans = vec![Span::raw("  "), Span::styled(title, Theme::primary_bold())];
+    if let Some(session_title) = session_title {
+        title_spans.push(Span::styled(" · ", Theme::muted()));
+        title_spans.push(Span::styled(session_title.to_string(), Theme::primary()));
+    }
+    if auto_approve {
+        title_spans.push(Span::raw("  "));
+        title_spans.push(Span::styled(
+            " AUTO-APPROVE ",
+            Theme::error().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+        ));
+    }
+    let mut subtitle = format!("Provider: {provider_name} | Model: {model_name}");
+    if let Some(execution_model) = execution_model {
+        let _ = write!(subtitle, " | Tool turns: {execution_model}");
+    }
 
     let block = Block::default()
         .borders(Borders::BOTTOM)
-        .border_style(Theme::border())
+        .border_style(if auto_approve {
+            Theme::error()
+        } else {
+            Theme::border()
+        })
         .border_set(ratatui::symbols::border::Set {
             bottom_left: BoxChars::ROUND_BOTTOM_LEFT,
             bottom_right: BoxChars::ROUND_BOTTOM_RIGHT,
@@ -18,10 +48,7 @@ pub fn render_header(frame: &mut Frame, area: Rect, provider_name: &str, model_n
         });
 
     let lines = vec![
-        Line::from(vec![
-            Span::raw("  "),
-            Span::styled(title, Theme::primary_bold()),
-        ]),
+        Line::from(title_spans),
         Line::from(vec![
             Span::raw("  "),
             Span::styled(subtitle, Theme::muted()),
@@ -3,5 +3,11 @@ mod modals;
 mod status;
 
 pub use header::render_header;
-pub use modals::{render_model_picker_modal, render_permission_modal};
-pub use status::render_status;
+pub use modals::{
+    render_batch_permission_modal, render_commit_modal, render_config_form_modal,
+    render_context_modal, render_memories_modal, render_model_picker_modal, render_paste_viewer,
+    render_permission_modal, render_session_browser_modal, render_snippet_form,
+};
+pub use status::{
+    render_input_estimate, render_permission_queue, render_status, render_update_hint,
+};
@@ -0,0 +1,91 @@
+use crate::tui::state::BatchPermissionModal;
+use crate::ui::diff::DiffWidget;
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_VISIBLE: usize = 15;
+
+pub fn render_batch_permission_modal(frame: &mut Frame, area: Rect, modal: &BatchPermissionModal) {
+    let rows = modal.requests.len().clamp(1, MAX_VISIBLE) as u16;
+    let modal_height = rows.min(area.height.saturating_sub(8)) + 5;
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 100.0, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let title = format!("Write {} files?", modal.requests.len());
+    let block = create_modal_block(&title, Theme::warning());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    render_request_list(frame, chunks[0], modal);
+
+    let summary = Line::from(Span::styled(
+        format!(
+            "  {} of {} selected",
+            modal.approved_count(),
+            modal.requests.len()
+        ),
+        Theme::secondary(),
+    ));
+    frame.render_widget(Paragraph::new(summary), chunks[1]);
+    render_hint(
+        frame,
+        chunks[2],
+        "↑/↓: move • Space: toggle • a: approve all • Enter: confirm • Esc: deny all",
+    );
+}
+
+fn render_request_list(frame: &mut Frame, chunk: Rect, modal: &BatchPermissionModal) {
+    let visible = chunk.height as usize;
+    let offset = (modal.selected + 1).saturating_sub(visible);
+
+    let lines: Vec<Line> = modal
+        .requests
+        .iter()
+        .zip(&modal.approved)
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, (request, &approved))| {
+            let (prefix, style) = if index == modal.selected {
+                ("  ▸ ", Theme::primary_bold())
+            } else {
+                ("    ", Style::default())
+            };
+            let mark = if approved { "[x] " } else { "[ ] " };
+            let mut spans = vec![
+                Span::styled(prefix, style),
+                Span::styled(mark, style),
+                Span::styled(request.target.clone(), style),
+            ];
+            if let Some(change) = &request.change {
+                let diff =
+                    DiffWidget::new(&request.target, &change.old_content, &change.new_content);
+                spans.push(Span::styled(
+                    format!("  +{}", diff.additions()),
+                    Theme::success(),
+                ));
+                spans.push(Span::styled(
+                    format!(" \u{2212}{}", diff.deletions()),
+                    Theme::error(),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunk);
+}
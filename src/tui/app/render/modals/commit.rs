@@ -0,0 +1,75 @@
+use crate::tui::state::CommitModal;
+use crate::ui::i18n::{Msg, t};
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_SUMMARY_LINES: usize = 8;
+
+pub fn render_commit_modal(frame: &mut Frame, area: Rect, modal: &CommitModal) {
+    let summary: Vec<&str> = modal.draft.summary.lines().collect();
+    let shown = summary.len().min(MAX_SUMMARY_LINES);
+    let message_lines = modal.message().lines().count().max(1) + 1;
+    let height = (shown + message_lines + 7) as u16;
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 100.0, height.min(area.height));
+
+    frame.render_widget(Clear, modal_area);
+
+    let title = if modal.draft.stage_all {
+        t(Msg::CommitAllTitle)
+    } else {
+        t(Msg::CommitStagedTitle)
+    };
+    let block = create_modal_block(title, Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(shown as u16 + 1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    let mut lines: Vec<Line> = summary
+        .iter()
+        .take(shown)
+        .map(|line| Line::from(Span::styled(format!(" {line}"), Theme::muted())))
+        .collect();
+    if summary.len() > shown {
+        lines.push(Line::from(Span::styled(
+            format!(" … {} more", summary.len() - shown),
+            Theme::muted(),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let mut message: Vec<Line> = modal
+        .message()
+        .split('\n')
+        .map(|line| Line::from(Span::styled(format!(" {line}"), Theme::white())))
+        .collect();
+    if let Some(last) = message.last_mut() {
+        last.push_span(Span::styled("▏", Theme::primary()));
+    }
+    frame.render_widget(
+        Paragraph::new(message).wrap(Wrap { trim: false }),
+        chunks[1],
+    );
+
+    if let Some(error) = &modal.error {
+        let line = Line::from(Span::styled(format!(" {error}"), Theme::error()));
+        frame.render_widget(Paragraph::new(line), chunks[2]);
+    }
+
+    render_hint(
+        frame,
+        chunks[3],
+        "type to edit • Ctrl+J: new line • Enter: commit • Esc: cancel",
+    );
+}
@@ -0,0 +1,97 @@
+use crate::tui::state::{ConfigField, ConfigFormModal};
+use crate::ui::i18n::{Msg, t};
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const LABEL_WIDTH: usize = 16;
+
+pub fn render_config_form_modal(frame: &mut Frame, area: Rect, form: &ConfigFormModal) {
+    let modal_height = (ConfigField::ALL.len() as u16 + 5).min(area.height);
+    let modal_area = calc_centered_modal(area, 0.6, 50.0, 90.0, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = create_modal_block(t(Msg::SettingsTitle), Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    let value_width = (chunks[0].width as usize).saturating_sub(LABEL_WIDTH + 8);
+    let lines: Vec<Line> = ConfigField::ALL
+        .iter()
+        .map(|&field| field_line(form, field, value_width))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some(error) = &form.error {
+        let line = Line::from(Span::styled(format!("  {error}"), Theme::error()));
+        frame.render_widget(Paragraph::new(line), chunks[1]);
+    }
+
+    render_hint(frame, chunks[2], t(Msg::HintSettings));
+}
+
+fn field_line(form: &ConfigFormModal, field: ConfigField, value_width: usize) -> Line<'static> {
+    let focused = form.focused == field;
+    let (prefix, label_style) = if focused {
+        ("  ▸ ", Theme::primary_bold())
+    } else {
+        ("    ", Theme::secondary())
+    };
+    let label = format!("{:<LABEL_WIDTH$}", field.label());
+
+    let mut spans = vec![
+        Span::styled(prefix, label_style),
+        Span::styled(label, label_style),
+    ];
+
+    let value = match field {
+        ConfigField::Model => form.model().unwrap_or("(not set)").to_string(),
+        ConfigField::PermissionMode => form.permission_mode.to_string(),
+        ConfigField::Theme => form.theme.to_string(),
+        ConfigField::SystemPrompt => {
+            if form.system_prompt.is_empty() && !focused {
+                spans.push(Span::styled("(none)", Theme::muted()));
+            } else {
+                spans.push(Span::raw(tail(&form.system_prompt, value_width)));
+            }
+            if focused {
+                spans.push(Span::styled("▏", Theme::primary()));
+            }
+            return Line::from(spans);
+        }
+    };
+    let arrow_style = if focused {
+        Theme::primary()
+    } else {
+        Style::default()
+    };
+    spans.extend([
+        Span::styled(if focused { "◂ " } else { "  " }, arrow_style),
+        Span::styled(value, Theme::white()),
+        Span::styled(if focused { " ▸" } else { "" }, arrow_style),
+    ]);
+    Line::from(spans)
+}
+
+fn tail(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().skip(count - width.saturating_sub(1)).collect();
+        format!("…{kept}")
+    }
+}
@@ -0,0 +1,86 @@
+use crate::tui::state::ContextModal;
+use crate::ui::i18n::{Msg, t};
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_VISIBLE: usize = 20;
+
+pub fn render_context_modal(frame: &mut Frame, area: Rect, modal: &ContextModal) {
+    let rows = modal.snapshot.items.len().clamp(1, MAX_VISIBLE) as u16;
+    let modal_height = rows.min(area.height.saturating_sub(8)) + 4;
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 110.0, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let total = modal.snapshot.total_tokens();
+    let kept = modal.kept_tokens();
+    let title = if kept == total {
+        format!("{} · ~{total} tokens", t(Msg::ContextTitle))
+    } else {
+        format!("{} · ~{kept} of ~{total} tokens", t(Msg::ContextTitle))
+    };
+    let block = create_modal_block(&title, Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner_area);
+    render_context_items(frame, chunks[0], modal);
+    render_hint(frame, chunks[1], t(Msg::HintContext));
+}
+
+fn render_context_items(frame: &mut Frame, chunk: Rect, modal: &ContextModal) {
+    let visible = chunk.height as usize;
+    let offset = (modal.selected + 1).saturating_sub(visible);
+    let width = chunk.width as usize;
+
+    let lines: Vec<Line> = modal
+        .snapshot
+        .items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, item)| {
+            let dropped = modal.dropped.contains(&index);
+            let (prefix, mut style) = if index == modal.selected {
+                ("  ▸ ", Theme::primary_bold())
+            } else {
+                ("    ", Style::default())
+            };
+            if dropped {
+                style = Theme::muted().add_modifier(Modifier::CROSSED_OUT);
+            }
+            let mark = match (item.droppable(), dropped) {
+                (false, _) => "    ",
+                (true, false) => "[✓] ",
+                (true, true) => "[ ] ",
+            };
+            let indent = if item.is_nested() { "  ↳ " } else { "" };
+            let tokens = format!(" ~{}", item.tokens);
+            let room = width
+                .saturating_sub(prefix.len() + 4 + indent.chars().count() + tokens.len() + 1)
+                .max(10);
+            let label = if item.label.chars().count() > room {
+                let cut: String = item.label.chars().take(room - 1).collect();
+                format!("{cut}…")
+            } else {
+                format!("{:<room$}", item.label)
+            };
+            Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(mark, Theme::secondary()),
+                Span::styled(indent, Theme::muted()),
+                Span::styled(label, style),
+                Span::styled(tokens, Theme::muted()),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunk);
+}
@@ -0,0 +1,87 @@
+use crate::tui::state::MemoriesModal;
+use crate::ui::i18n::{Msg, t};
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_VISIBLE: usize = 15;
+
+pub fn render_memories_modal(frame: &mut Frame, area: Rect, modal: &MemoriesModal) {
+    let rows = modal.entries.len().clamp(1, MAX_VISIBLE) as u16;
+    let modal_height = rows.min(area.height.saturating_sub(8)) + 5;
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 100.0, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = create_modal_block(t(Msg::MemoriesTitle), Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    render_memory_list(frame, chunks[0], modal);
+
+    if let Some(input) = &modal.input {
+        let label = if modal.adding { "  New: " } else { "  Edit: " };
+        let line = Line::from(vec![
+            Span::styled(label, Theme::secondary()),
+            Span::raw(input.as_str()),
+            Span::styled("▏", Theme::primary()),
+        ]);
+        frame.render_widget(Paragraph::new(line), chunks[1]);
+        render_hint(frame, chunks[2], t(Msg::HintSaveCancel));
+    } else if modal.confirm_delete {
+        render_hint(frame, chunks[2], t(Msg::ConfirmForgetMemory));
+    } else {
+        render_hint(frame, chunks[2], t(Msg::HintMemories));
+    }
+}
+
+fn render_memory_list(frame: &mut Frame, chunk: Rect, modal: &MemoriesModal) {
+    if modal.entries.is_empty() {
+        let line = Line::from(Span::styled(
+            format!("  {}", t(Msg::NoMemories)),
+            Theme::muted(),
+        ));
+        frame.render_widget(Paragraph::new(line), chunk);
+        return;
+    }
+
+    let visible = chunk.height as usize;
+    let offset = (modal.selected + 1).saturating_sub(visible);
+    let width = (chunk.width as usize).saturating_sub(5).max(10);
+
+    let lines: Vec<Line> = modal
+        .entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, entry)| {
+            let (prefix, style) = if index == modal.selected {
+                ("  ▸ ", Theme::primary_bold())
+            } else {
+                ("    ", Style::default())
+            };
+            let text = if entry.chars().count() > width {
+                let cut: String = entry.chars().take(width - 1).collect();
+                format!("{cut}…")
+            } else {
+                entry.clone()
+            };
+            Line::from(vec![Span::styled(prefix, style), Span::styled(text, style)])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunk);
+}
@@ -1,10 +1,26 @@
 #![allow(clippy::cast_sign_loss)]
 
+mod batch_permission;
+mod commit;
+mod config_form;
+mod context;
+mod memories;
 mod model_picker;
+mod paste_viewer;
 mod permission;
+mod session_browser;
+mod snippet_form;
 
+pub use batch_permission::render_batch_permission_modal;
+pub use commit::render_commit_modal;
+pub use config_form::render_config_form_modal;
+pub use context::render_context_modal;
+pub use memories::render_memories_modal;
 pub use model_picker::render_model_picker_modal;
+pub use paste_viewer::render_paste_viewer;
 pub use permission::render_permission_modal;
+pub use session_browser::render_session_browser_modal;
+pub use snippet_form::render_snippet_form;
 
 use crate::ui::theme::Theme;
 use ratatui::Frame;
@@ -1,5 +1,6 @@
 use crate::config::ProviderType;
 use crate::tui::state::PickerModel;
+use crate::ui::i18n::{Msg, t};
 use crate::ui::theme::Theme;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
@@ -21,18 +22,14 @@ pub fn render_model_picker_modal(
 
     frame.render_widget(Clear, modal_area);
 
-    let block = create_modal_block("Select Model", Theme::primary());
+    let block = create_modal_block(t(Msg::SelectModelTitle), Theme::primary());
     let inner_area = block.inner(modal_area);
     frame.render_widget(block, modal_area);
 
     let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner_area);
 
     render_model_list(frame, chunks[0], models, selected);
-    render_hint(
-        frame,
-        chunks[1],
-        "↑/↓: navigate • Enter: select • Esc: cancel",
-    );
+    render_hint(frame, chunks[1], t(Msg::HintModelPicker));
 }
 
 fn render_model_list(
@@ -0,0 +1,49 @@
+use crate::tui::state::PasteViewer;
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+pub fn render_paste_viewer(frame: &mut Frame, area: Rect, viewer: &PasteViewer) {
+    let height = (viewer.line_count() as u16 + 3).clamp(8, area.height.saturating_sub(2));
+    let modal_area = calc_centered_modal(area, 0.85, 40.0, 160.0, height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let title = format!(
+        "Pasted block #{} · {} lines, {} chars",
+        viewer.id,
+        viewer.line_count(),
+        viewer.content.chars().count()
+    );
+    let block = create_modal_block(&title, Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner_area);
+
+    let number_width = viewer.line_count().to_string().len();
+    let lines: Vec<Line> = viewer
+        .content
+        .lines()
+        .enumerate()
+        .skip(viewer.scroll)
+        .take(chunks[0].height as usize)
+        .map(|(i, line)| {
+            Line::from(vec![
+                Span::styled(format!(" {:>number_width$} ", i + 1), Theme::muted()),
+                Span::styled(line.to_string(), Theme::white()),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    render_hint(
+        frame,
+        chunks[1],
+        "↑↓/PgUp/PgDn: scroll • d: remove from input • Esc: close",
+    );
+}
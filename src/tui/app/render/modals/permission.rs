@@ -1,7 +1,12 @@
-use crate::permission::types::{PermissionRequest, PermissionType};
+use crate::permission::Grant;
+use crate::permission::security::{command_warnings, split_command};
+use crate::permission::types::{PermissionRequest, PermissionType, ProposedChange};
+use crate::ui::diff::DiffWidget;
+use crate::ui::i18n::{Msg, t};
 use crate::ui::theme::Theme;
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Clear, Paragraph};
 
@@ -14,8 +19,28 @@ pub fn render_permission_modal(
     selected: usize,
     input_mode: bool,
     feedback_input: &str,
+    diff_scroll: usize,
 ) {
-    let modal_area = calc_centered_modal(area, 0.6, 50.0, 80.0, if input_mode { 14 } else { 12 });
+    let mut base_height: u16 = if input_mode || request.editable {
+        14
+    } else {
+        13
+    };
+    let command_details = is_command(request).then(|| {
+        let width = calc_centered_modal(area, 0.7, 60.0, 100.0, 0).width;
+        command_details(request, width.saturating_sub(4) as usize)
+    });
+    let header_height = command_details.as_ref().map_or(2, |lines| {
+        let max = area.height.saturating_sub(base_height + 1).max(2);
+        (lines.len() as u16).clamp(2, max)
+    });
+    base_height += header_height - 2;
+
+    let modal_area = match (&request.change, &command_details) {
+        (Some(_), _) => calc_centered_modal(area, 0.8, 60.0, 120.0, area.height.saturating_sub(2)),
+        (None, Some(_)) => calc_centered_modal(area, 0.7, 60.0, 100.0, base_height),
+        (None, None) => calc_centered_modal(area, 0.6, 50.0, 80.0, base_height),
+    };
     frame.render_widget(Clear, modal_area);
 
     let border_style = if is_dangerous_operation(request) {
@@ -24,16 +49,34 @@ pub fn render_permission_modal(
         Theme::primary()
     };
     let block = create_modal_block(&request.operation_type.to_string(), border_style);
-    let inner_area = block.inner(modal_area);
+    let mut inner_area = block.inner(modal_area);
     frame.render_widget(block, modal_area);
 
-    let chunks = create_layout(inner_area, input_mode);
-    render_target_section(frame, chunks[0], request, inner_area.width);
+    if let Some(change) = &request.change {
+        let [main, diff] =
+            Layout::vertical([Constraint::Length(base_height - 2), Constraint::Min(0)])
+                .areas(inner_area);
+        render_change_preview(frame, diff, change, &request.target, diff_scroll);
+        inner_area = main;
+    }
+
+    let chunks = create_layout(inner_area, input_mode, header_height);
+    match command_details {
+        Some(lines) => frame.render_widget(
+            Paragraph::new(lines),
+            Rect {
+                x: chunks[0].x + 1,
+                width: chunks[0].width.saturating_sub(2),
+                ..chunks[0]
+            },
+        ),
+        None => render_target_section(frame, chunks[0], request, inner_area.width),
+    }
 
     if input_mode {
-        render_input_mode(frame, &chunks, feedback_input);
+        render_input_mode(frame, &chunks, feedback_input, selected == EDIT_OPTION);
     } else {
-        render_options_mode(frame, &chunks, selected);
+        render_options_mode(frame, &chunks, selected, request);
     }
 }
 
@@ -43,13 +86,123 @@ const fn is_dangerous_operation(request: &PermissionRequest) -> bool {
         PermissionType::FileDelete
             | PermissionType::CommandExecute
             | PermissionType::SystemModification
+            | PermissionType::DatabaseWrite
+            | PermissionType::CloudMutate
+    )
+}
+
+const fn is_command(request: &PermissionRequest) -> bool {
+    matches!(
+        request.operation_type,
+        PermissionType::CommandExecute
+            | PermissionType::SystemModification
+            | PermissionType::CloudRead
+            | PermissionType::CloudMutate
     )
 }
 
-fn create_layout(inner_area: Rect, input_mode: bool) -> std::rc::Rc<[Rect]> {
+fn command_details(request: &PermissionRequest, width: usize) -> Vec<Line<'static>> {
+    let mut lines = highlight_command(&request.target, width);
+
+    let parts = split_command(&request.target);
+    if parts.len() > 1 {
+        lines.push(Line::from(Span::styled("Runs, in order:", Theme::muted())));
+        for (index, part) in parts.iter().enumerate() {
+            let number = format!("  {}. ", index + 1);
+            let room = width.saturating_sub(number.len() + 2);
+            let command = if part.command.chars().count() > room {
+                let cut: String = part.command.chars().take(room.saturating_sub(1)).collect();
+                format!("{cut}…")
+            } else {
+                part.command.clone()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(number, Theme::muted()),
+                Span::styled(command, Theme::white()),
+                Span::styled(if part.piped { " |" } else { "" }, Theme::warning()),
+            ]));
+        }
+    }
+
+    for warning in command_warnings(&request.target) {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {}: {}", warning.pattern, warning.note),
+            Theme::warning(),
+        )));
+    }
+    if let Some(context) = &request.context {
+        lines.push(Line::from(Span::styled(context.clone(), Theme::muted())));
+    }
+    lines
+}
+
+fn highlight_command(command: &str, width: usize) -> Vec<Line<'static>> {
+    let mut styled: Vec<(char, Style)> = Vec::with_capacity(command.len());
+    let mut quote = None;
+    let mut word_style = None;
+    let mut at_command = true;
+    let mut in_variable = false;
+    let mut prev = ' ';
+
+    for c in command.chars() {
+        let style = if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            Theme::success()
+        } else if matches!(c, '\'' | '"') {
+            quote = Some(c);
+            Theme::success()
+        } else if c.is_whitespace() {
+            word_style = None;
+            in_variable = false;
+            Style::default()
+        } else if matches!(c, ';' | '&' | '|' | '<' | '>' | '(' | ')') {
+            word_style = None;
+            in_variable = false;
+            if matches!(c, ';' | '|' | '(') || (c == '&' && !matches!(prev, '<' | '>')) {
+                at_command = true;
+            }
+            Theme::warning()
+        } else if c == '$' || (in_variable && (c.is_alphanumeric() || "_{}".contains(c))) {
+            in_variable = true;
+            Theme::warning()
+        } else {
+            in_variable = false;
+            *word_style.get_or_insert_with(|| {
+                if std::mem::take(&mut at_command) {
+                    Theme::primary_bold()
+                } else if c == '-' {
+                    Theme::secondary()
+                } else {
+                    Theme::white()
+                }
+            })
+        };
+        styled.push((c, style));
+        prev = c;
+    }
+
+    let mut lines = Vec::new();
+    for row in styled.split(|(c, _)| *c == '\n') {
+        for chunk in row.chunks(width.max(1)) {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            for &(c, style) in chunk {
+                match spans.last_mut() {
+                    Some(span) if span.style == style => span.content.to_mut().push(c),
+                    _ => spans.push(Span::styled(c.to_string(), style)),
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
+}
+
+fn create_layout(inner_area: Rect, input_mode: bool, header_height: u16) -> std::rc::Rc<[Rect]> {
     if input_mode {
         Layout::vertical([
-            Constraint::Length(2),
+            Constraint::Length(header_height),
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
@@ -59,7 +212,7 @@ fn create_layout(inner_area: Rect, input_mode: bool) -> std::rc::Rc<[Rect]> {
         .split(inner_area)
     } else {
         Layout::vertical([
-            Constraint::Length(2),
+            Constraint::Length(header_height),
             Constraint::Length(1),
             Constraint::Min(5),
             Constraint::Length(1),
@@ -107,11 +260,49 @@ fn render_target_section(
     }
 }
 
-fn render_input_mode(frame: &mut Frame, chunks: &[Rect], feedback_input: &str) {
-    let prompt_line = Line::from(Span::styled(
-        "Tell the model what to do instead:",
-        Theme::secondary(),
-    ));
+fn render_change_preview(
+    frame: &mut Frame,
+    area: Rect,
+    change: &ProposedChange,
+    path: &str,
+    scroll: usize,
+) {
+    if area.height < 2 {
+        return;
+    }
+    let width = area.width.saturating_sub(2);
+    let lines =
+        DiffWidget::new(path, &change.old_content, &change.new_content).render_to_lines(width);
+    let visible = area.height as usize - 1;
+    let offset = scroll.min(lines.len().saturating_sub(visible));
+    let hidden = lines.len().saturating_sub(offset + visible);
+
+    let mut shown: Vec<Line> = lines.into_iter().skip(offset).take(visible).collect();
+    shown.push(Line::from(Span::styled(
+        if hidden > 0 || offset > 0 {
+            format!("{hidden} more lines • PgUp/PgDn: scroll diff")
+        } else {
+            String::new()
+        },
+        Theme::muted(),
+    )));
+    frame.render_widget(
+        Paragraph::new(shown),
+        Rect {
+            x: area.x + 1,
+            width,
+            ..area
+        },
+    );
+}
+
+fn render_input_mode(frame: &mut Frame, chunks: &[Rect], feedback_input: &str, editing: bool) {
+    let prompt = if editing {
+        t(Msg::EditCommandPrompt)
+    } else {
+        t(Msg::DenyFeedbackPrompt)
+    };
+    let prompt_line = Line::from(Span::styled(prompt, Theme::secondary()));
     frame.render_widget(
         Paragraph::new(prompt_line),
         Rect {
@@ -132,19 +323,34 @@ fn render_input_mode(frame: &mut Frame, chunks: &[Rect], feedback_input: &str) {
         },
     );
 
-    render_hint(frame, chunks[5], "Enter: submit • Esc: cancel");
+    render_hint(frame, chunks[5], t(Msg::HintSubmitCancel));
 }
 
-fn render_options_mode(frame: &mut Frame, chunks: &[Rect], selected: usize) {
-    const OPTIONS: [(&str, &str, &str); 3] = [
-        ("1", "Allow once", "Allow this single operation"),
-        ("2", "Allow for session", "Allow all similar operations"),
-        ("3", "Deny", "Tell the model to try something else"),
+const ALWAYS_OPTION: usize = 2;
+const EDIT_OPTION: usize = 3;
+
+fn render_options_mode(
+    frame: &mut Frame,
+    chunks: &[Rect],
+    selected: usize,
+    request: &PermissionRequest,
+) {
+    const OPTIONS: [(Msg, Msg); 5] = [
+        (Msg::AllowOnce, Msg::AllowOnceDescription),
+        (Msg::AllowSession, Msg::AllowSessionDescription),
+        (Msg::AlwaysAllow, Msg::AlwaysAllowDescription),
+        (Msg::EditAndAllow, Msg::EditAndAllowDescription),
+        (Msg::Deny, Msg::DenyDescription),
     ];
 
-    for (i, (key, label, desc)) in OPTIONS.iter().enumerate() {
+    let shown = OPTIONS
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| request.editable || *i != EDIT_OPTION);
+    for (row, (i, (label, desc))) in shown.enumerate() {
         let is_selected = i == selected;
-        let y = chunks[2].y + i as u16;
+        let y = chunks[2].y + row as u16;
+        let key = row + 1;
 
         let (prefix, key_style, label_style) = if is_selected {
             ("▸ ", Theme::primary_bold(), Theme::primary())
@@ -152,11 +358,16 @@ fn render_options_mode(frame: &mut Frame, chunks: &[Rect], selected: usize) {
             ("  ", Theme::muted(), ratatui::style::Style::default())
         };
 
+        let description = if i == ALWAYS_OPTION {
+            format!(" - {}: {}", t(*desc), Grant::for_request(request).pattern)
+        } else {
+            format!(" - {}", t(*desc))
+        };
         let line = Line::from(vec![
             Span::styled(prefix, key_style),
             Span::styled(format!("[{key}] "), key_style),
-            Span::styled(*label, label_style),
-            Span::styled(format!(" - {desc}"), Theme::muted()),
+            Span::styled(t(*label), label_style),
+            Span::styled(description, Theme::muted()),
         ]);
 
         frame.render_widget(
@@ -170,9 +381,5 @@ fn render_options_mode(frame: &mut Frame, chunks: &[Rect], selected: usize) {
         );
     }
 
-    render_hint(
-        frame,
-        chunks[3],
-        "↑/↓: navigate • Enter: confirm • Esc: cancel • y: allow • 3/n: deny",
-    );
+    render_hint(frame, chunks[3], t(Msg::HintPermission));
 }
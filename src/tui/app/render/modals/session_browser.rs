@@ -0,0 +1,132 @@
+use crate::session::SessionRecord;
+use crate::tui::state::SessionBrowserModal;
+use crate::ui::i18n::{Msg, t};
+use crate::ui::theme::Theme;
+use chrono::{DateTime, Utc};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+
+use super::super::status::format_tokens;
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_VISIBLE: usize = 15;
+
+pub fn render_session_browser_modal(frame: &mut Frame, area: Rect, modal: &SessionBrowserModal) {
+    let rows = modal.sessions.len().clamp(1, MAX_VISIBLE) as u16;
+    let modal_height = rows.min(area.height.saturating_sub(8)) + 5;
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 100.0, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = create_modal_block(t(Msg::SessionsTitle), Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    render_session_list(frame, chunks[0], modal);
+
+    if let Some(input) = &modal.rename_input {
+        let line = Line::from(vec![
+            Span::styled("  Title: ", Theme::secondary()),
+            Span::raw(input.as_str()),
+            Span::styled("▏", Theme::primary()),
+        ]);
+        frame.render_widget(Paragraph::new(line), chunks[1]);
+        render_hint(frame, chunks[2], t(Msg::HintSaveCancel));
+    } else if modal.confirm_delete {
+        render_hint(frame, chunks[2], t(Msg::ConfirmDeleteSession));
+    } else {
+        render_hint(frame, chunks[2], t(Msg::HintSessions));
+    }
+}
+
+fn render_session_list(frame: &mut Frame, chunk: Rect, modal: &SessionBrowserModal) {
+    if modal.sessions.is_empty() {
+        let line = Line::from(Span::styled(
+            format!("  {}", t(Msg::NoSessions)),
+            Theme::muted(),
+        ));
+        frame.render_widget(Paragraph::new(line), chunk);
+        return;
+    }
+
+    let visible = chunk.height as usize;
+    let offset = (modal.selected + 1).saturating_sub(visible);
+    let now = Utc::now();
+    let title_width = (chunk.width as usize).saturating_sub(40).max(10);
+
+    let lines: Vec<Line> = modal
+        .sessions
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, session)| session_line(session, index == modal.selected, now, title_width))
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunk);
+}
+
+fn session_line(
+    session: &SessionRecord,
+    selected: bool,
+    now: DateTime<Utc>,
+    title_width: usize,
+) -> Line<'static> {
+    let (prefix, style) = if selected {
+        ("  ▸ ", Theme::primary_bold())
+    } else {
+        ("    ", Style::default())
+    };
+    let title = if session.parent.is_some() {
+        fit(&format!("↳ {}", session.display_title()), title_width)
+    } else {
+        fit(session.display_title(), title_width)
+    };
+    let details = format!(
+        "  {} · {} · {} tokens",
+        session.model,
+        format_age(session.updated_at, now),
+        format_tokens(session.usage.total())
+    );
+
+    Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(title, style),
+        Span::styled(details, Theme::muted()),
+    ])
+}
+
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        format!("{text:width$}")
+    } else {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{cut}…")
+    }
+}
+
+fn format_age(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(at);
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_days() < 30 {
+        format!("{}d ago", elapsed.num_days())
+    } else {
+        at.format("%Y-%m-%d").to_string()
+    }
+}
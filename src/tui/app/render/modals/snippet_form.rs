@@ -0,0 +1,81 @@
+use crate::tui::state::SnippetForm;
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+use super::{calc_centered_modal, create_modal_block, render_hint};
+
+const MAX_PREVIEW_LINES: u16 = 8;
+
+pub fn render_snippet_form(frame: &mut Frame, area: Rect, form: &SnippetForm) {
+    let filled = form.filled();
+    let preview_lines = (filled.lines().count() as u16).clamp(1, MAX_PREVIEW_LINES);
+    let fields = form.fields.len() as u16;
+    let height = (fields + preview_lines + 6).min(area.height);
+    let modal_area = calc_centered_modal(area, 0.7, 50.0, 100.0, height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = create_modal_block(&format!("Snippet: {}", form.snippet.name), Theme::primary());
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(fields),
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+
+    let label_width = form
+        .fields
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, (name, value))| {
+            let selected = i == form.selected;
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        " {} {name:<label_width$}  ",
+                        if selected { "›" } else { " " }
+                    ),
+                    if selected {
+                        Theme::primary_bold()
+                    } else {
+                        Theme::muted()
+                    },
+                ),
+                Span::styled(value.clone(), Theme::white()),
+            ];
+            if selected {
+                spans.push(Span::styled("▏", Theme::primary()));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let preview: Vec<Line> = filled
+        .lines()
+        .map(|line| Line::from(Span::styled(format!(" {line}"), Theme::muted())))
+        .collect();
+    frame.render_widget(
+        Paragraph::new(preview).wrap(Wrap { trim: false }),
+        chunks[2],
+    );
+
+    render_hint(
+        frame,
+        chunks[3],
+        "Tab/↑↓: switch field • Enter: next / insert into input • Esc: cancel",
+    );
+}
@@ -1,14 +1,17 @@
+use crate::core::run_state::RunState;
 use crate::core::types::Usage;
+use crate::tui::state::StreamProgress;
 use crate::ui::theme::{Spinners, Theme};
 use ratatui::Frame;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
+use std::fmt::Write as _;
 use std::time::Duration;
 
 const HINTS: &str = "/ commands | PgUp/PgDn scroll";
 
-fn format_tokens(count: u32) -> String {
+pub(super) fn format_tokens(count: u32) -> String {
     if count >= 1_000_000 {
         format!("{:.1}M", f64::from(count) / 1_000_000.0)
     } else if count >= 1_000 {
@@ -23,6 +26,42 @@ fn render_hints(buf: &mut Buffer, area: Rect) {
     buf.set_line(area.x, area.y, &line, HINTS.len() as u16 + 2);
 }
 
+pub fn render_update_hint(frame: &mut Frame, area: Rect, version: &str) {
+    let hint = format!("· smith {version} available, run `smith update`");
+    let x = area.x + HINTS.len() as u16 + 2;
+    let line = Line::from(Span::styled(hint, Theme::muted()));
+    frame
+        .buffer_mut()
+        .set_line(x, area.y, &line, area.width.saturating_sub(x - area.x));
+}
+
+pub fn render_permission_queue(frame: &mut Frame, area: Rect, queued: usize) {
+    let noun = if queued == 1 { "request" } else { "requests" };
+    let text = format!(" ⏸ {queued} more permission {noun} waiting");
+    let line = Line::from(Span::styled(text, Theme::warning()));
+    frame
+        .buffer_mut()
+        .set_line(area.x, area.y, &line, area.width);
+}
+
+pub fn render_input_estimate(frame: &mut Frame, area: Rect, tokens: usize, warn_tokens: usize) {
+    let count = |n: usize| format_tokens(u32::try_from(n).unwrap_or(u32::MAX));
+    let (text, style) = if tokens > warn_tokens {
+        (
+            format!(
+                " ⚠ ~{} tokens, over the {} limit ",
+                count(tokens),
+                count(warn_tokens)
+            ),
+            Theme::warning(),
+        )
+    } else {
+        (format!(" ~{} tokens ", count(tokens)), Theme::muted())
+    };
+    let line = Line::from(vec![Span::styled(text, style), Span::raw(" ")]);
+    render_right_text(frame.buffer_mut(), area, &line);
+}
+
 fn render_right_text(buf: &mut Buffer, area: Rect, line: &Line) {
     let width = (line.width() + 1) as u16;
     let x = area.x + area.width.saturating_sub(width);
@@ -42,7 +81,24 @@ fn format_elapsed(elapsed: Option<Duration>) -> String {
         .unwrap_or_default()
 }
 
+fn format_rate(progress: &StreamProgress) -> Option<String> {
+    let rate = progress.tokens_per_sec?;
+    let mut text = format!(" · {rate:.0} tok/s");
+    if let Some(eta) = progress.eta.filter(|_| progress.stalled.is_none()) {
+        let _ = write!(text, " · ~{}s left", eta.as_secs().max(1));
+    }
+    Some(text)
+}
+
 fn format_usage(last_usage: Option<&Usage>, session_usage: Usage) -> Option<String> {
+    let text = format_tokens_used(last_usage, session_usage)?;
+    Some(match session_usage.cost {
+        Some(cost) => format!("{text} | ${cost:.4}"),
+        None => text,
+    })
+}
+
+fn format_tokens_used(last_usage: Option<&Usage>, session_usage: Usage) -> Option<String> {
     match last_usage {
         Some(usage) if usage.input_tokens > 0 || usage.output_tokens > 0 => Some(format!(
             "Last: {}in/{}out | Session: {}",
@@ -61,8 +117,8 @@ fn format_usage(last_usage: Option<&Usage>, session_usage: Usage) -> Option<Stri
 pub fn render_status(
     frame: &mut Frame,
     area: Rect,
-    is_processing: bool,
-    elapsed: Option<Duration>,
+    activity: Option<RunState>,
+    progress: &StreamProgress,
     spinner_frame: usize,
     last_usage: Option<&Usage>,
     session_usage: Usage,
@@ -70,11 +126,32 @@ pub fn render_status(
     let buf = frame.buffer_mut();
     render_hints(buf, area);
 
-    if is_processing {
-        let spinner = Spinners::BRAILLE[spinner_frame % Spinners::BRAILLE.len()];
-        let status = format!("{spinner} Processing{}", format_elapsed(elapsed));
-        let line = Line::from(vec![Span::styled(status, Theme::warning()), Span::raw(" ")]);
+    if activity == Some(RunState::Paused) {
+        let line = Line::from(vec![
+            Span::styled("⏸ Paused · /resume to continue", Theme::warning()),
+            Span::raw(" "),
+        ]);
         render_right_text(buf, area, &line);
+    } else if let Some(state) = activity {
+        let frames = Spinners::frames(Spinners::BRAILLE);
+        let spinner = frames[spinner_frame % frames.len()];
+        let label = match state {
+            RunState::Idle => "Processing".to_string(),
+            state => state.to_string(),
+        };
+        let status = format!("{spinner} {label}{}", format_elapsed(progress.elapsed));
+        let mut spans = vec![Span::styled(status, Theme::warning())];
+        if let Some(rate) = format_rate(progress) {
+            spans.push(Span::styled(rate, Theme::muted()));
+        }
+        if let Some(idle) = progress.stalled {
+            spans.push(Span::styled(
+                format!(" · no output for {}s", idle.as_secs()),
+                Theme::error(),
+            ));
+        }
+        spans.push(Span::raw(" "));
+        render_right_text(buf, area, &Line::from(spans));
     } else if let Some(usage) = format_usage(last_usage, session_usage) {
         let line = Line::from(vec![Span::styled(usage, Theme::muted()), Span::raw(" ")]);
         render_right_text(buf, area, &line);
@@ -6,6 +6,9 @@ use crossterm::terminal::{
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::tui::events::POLL_TIMEOUT;
 
 pub struct TerminalGuard {
     _private: (),
@@ -43,3 +46,22 @@ pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io
     terminal.backend_mut().execute(LeaveAlternateScreen)?;
     Ok(())
 }
+
+pub fn suspend<T>(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    input_paused: &AtomicBool,
+    run: impl FnOnce() -> T,
+) -> io::Result<T> {
+    input_paused.store(true, Ordering::Release);
+    std::thread::sleep(POLL_TIMEOUT);
+    restore_terminal(terminal)?;
+
+    let result = run();
+
+    enable_raw_mode()?;
+    terminal.backend_mut().execute(EnterAlternateScreen)?;
+    terminal.backend_mut().execute(EnableBracketedPaste)?;
+    terminal.clear()?;
+    input_paused.store(false, Ordering::Release);
+    Ok(result)
+}
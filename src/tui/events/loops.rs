@@ -1,10 +1,19 @@
 use super::{AppEvent, POLL_TIMEOUT, SCROLL_DELTA, TICK_INTERVAL};
 use crate::core::error::Result;
 use crossterm::event::{self, Event as CrosstermEvent, MouseEventKind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::mpsc::UnboundedSender;
 
-pub async fn terminal_event_loop(tx: UnboundedSender<AppEvent>) -> Result<()> {
+pub async fn terminal_event_loop(
+    tx: UnboundedSender<AppEvent>,
+    paused: Arc<AtomicBool>,
+) -> Result<()> {
     loop {
+        if paused.load(Ordering::Acquire) {
+            tokio::time::sleep(POLL_TIMEOUT).await;
+            continue;
+        }
         if event::poll(POLL_TIMEOUT)? {
             let app_event = match event::read()? {
                 CrosstermEvent::Key(key) => Some(AppEvent::Input(key)),
@@ -4,10 +4,18 @@ mod loops;
 pub use handler::TuiToolEventHandler;
 pub use loops::{terminal_event_loop, tick_loop};
 
+use crate::config::GenerationParams;
+use crate::core::context::ContextSnapshot;
+use crate::core::run_state::RunState;
 use crate::core::types::{Message, Usage};
+use crate::fix_tests::FixTestsReport;
+use crate::git::Finding;
 use crate::permission::types::{PermissionRequest, PermissionResponse};
+use crate::shell::ShellRun;
 use crate::tools::events::ToolEvent;
 use crate::tools::result::ToolResult;
+use crate::tui::state::CommitDraft;
+use crate::tui::widgets::ModelTag;
 use crossterm::event::KeyEvent;
 use std::time::Duration;
 use tokio::sync::oneshot;
@@ -23,7 +31,7 @@ pub enum AppEvent {
     MouseScroll(i16),
     Resize(u16, u16),
     LLMChunk(String),
-    LLMComplete(Message, Usage),
+    LLMComplete(Message, Usage, Option<ModelTag>),
     LLMError(String),
     ToolStarted {
         name: String,
@@ -41,10 +49,19 @@ pub enum AppEvent {
         request: PermissionRequest,
         response_tx: oneshot::Sender<PermissionResponse>,
     },
+    RunStateChanged(RunState),
+    RunControlApplied(String),
+    RunControlRejected(String),
+    ReplayPrompt(String),
+    BatchPermissionRequired {
+        requests: Vec<PermissionRequest>,
+        response_tx: oneshot::Sender<Vec<PermissionResponse>>,
+    },
     FileDiff {
         path: String,
         old_content: String,
         new_content: String,
+        format: Option<String>,
     },
     Tick,
     ModelChanged {
@@ -52,6 +69,39 @@ pub enum AppEvent {
         model: String,
     },
     ModelSwitchError(String),
+    ExecutionModelChanged(Option<String>),
+    GenerationChanged(GenerationParams),
+    GenerationError(String),
+    SessionTitle(Option<String>),
+    ResponseCached,
+    UpdateAvailable(String),
+    CommitDraft(CommitDraft),
+    CommitFailed(String),
+    Artifacts(String),
+    ArtifactsFailed(String),
+    Pins(String),
+    PinsFailed(String),
+    Checkpoints(String),
+    CheckpointsFailed(String),
+    Context(ContextSnapshot),
+    ContextDropped(String),
+    ContextFailed(String),
+    ReviewComplete {
+        title: String,
+        findings: Vec<Finding>,
+    },
+    ReviewFailed(String),
+    FixTestsProgress(String),
+    FixTestsComplete(FixTestsReport),
+    FixTestsFailed(String),
+    DuoProgress(String),
+    DuoComplete(String),
+    DuoFailed(String),
+    ShellOutput {
+        run: ShellRun,
+        shared: bool,
+    },
+    ShellFailed(String),
 }
 
 impl From<ToolEvent> for AppEvent {
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::config::{SessionsConfig, ToolsConfig};
+use crate::core::augmented_llm::{AugmentedLLM, LoopConfig};
+use crate::core::error::{AgentError, Result};
+use crate::providers::mock::{Scenario, ScenarioLLM};
+use crate::tools::ToolEventEmitter;
+use crate::tui::TuiToolEventHandler;
+use crate::tui::agent_runner::{AgentCommand, AgentRunner};
+use crate::tui::events::AppEvent;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub struct TurnOutcome {
+    pub text: String,
+    pub error: Option<String>,
+    pub events: Vec<AppEvent>,
+}
+
+impl TurnOutcome {
+    #[must_use]
+    pub fn tools_started(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                AppEvent::ToolStarted { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn tool_outputs(&self) -> Vec<String> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                AppEvent::ToolCompleted { result, .. } => Some(result.to_llm_string()),
+                AppEvent::ToolFailed { error, .. } => Some(error.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+pub struct ScenarioHarness {
+    llm: ScenarioLLM,
+    cmd_tx: mpsc::UnboundedSender<AgentCommand>,
+    event_rx: mpsc::UnboundedReceiver<AppEvent>,
+    timeout: Duration,
+}
+
+impl ScenarioHarness {
+    pub fn new(scenario: Scenario) -> Result<Self> {
+        Self::with_tools_config(scenario, &ToolsConfig::default())
+    }
+
+    pub fn with_tools_config(scenario: Scenario, tools: &ToolsConfig) -> Result<Self> {
+        let llm = ScenarioLLM::new(scenario);
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        let mut agent = AugmentedLLM::with_config(
+            Arc::new(llm.clone()),
+            LoopConfig::default(),
+            ToolEventEmitter::new(),
+        )?;
+        for tool in crate::tools::configured_tools(tools, agent.tool_context()) {
+            agent.tools_mut().register(tool);
+        }
+        agent.register_tool_event_handler(Arc::new(TuiToolEventHandler::new(event_tx.clone())));
+
+        let (runner, cmd_tx) = AgentRunner::with_agent(agent, event_tx);
+        let runner = runner.with_sessions_config(SessionsConfig {
+            enabled: false,
+            llm_titles: false,
+            ..SessionsConfig::default()
+        });
+        tokio::spawn(runner.run());
+
+        Ok(Self {
+            llm,
+            cmd_tx,
+            event_rx,
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn llm(&self) -> &ScenarioLLM {
+        &self.llm
+    }
+
+    pub async fn send(&mut self, prompt: impl Into<String>) -> Result<TurnOutcome> {
+        self.cmd_tx
+            .send(AgentCommand::Run {
+                user_message: prompt.into(),
+            })
+            .map_err(|_| AgentError::InvalidState("Agent runner has stopped".to_string()))?;
+
+        let mut outcome = TurnOutcome::default();
+        loop {
+            let event = tokio::time::timeout(self.timeout, self.event_rx.recv())
+                .await
+                .map_err(|_| {
+                    AgentError::Timeout(format!("No reply from agent within {:?}", self.timeout))
+                })?
+                .ok_or_else(|| AgentError::InvalidState("Agent runner has stopped".to_string()))?;
+
+            match &event {
+                AppEvent::LLMChunk(chunk) => outcome.text.push_str(chunk),
+                AppEvent::LLMError(error) => outcome.error = Some(error.clone()),
+                _ => {}
+            }
+            let done = matches!(event, AppEvent::LLMComplete(..) | AppEvent::LLMError(_));
+            outcome.events.push(event);
+            if done {
+                return Ok(outcome);
+            }
+        }
+    }
+}
+
+impl Drop for ScenarioHarness {
+    fn drop(&mut self) {
+        let _ = self.cmd_tx.send(AgentCommand::Shutdown);
+    }
+}
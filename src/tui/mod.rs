@@ -1,22 +1,30 @@
 pub mod agent_runner;
 pub mod app;
 pub mod events;
+pub mod harness;
 pub mod layout;
 pub mod permission_ui;
+pub mod replay;
 pub mod state;
 pub mod widgets;
 
 pub use agent_runner::AgentConfig;
 pub use app::TuiApp;
 pub use events::TuiToolEventHandler;
+pub use harness::{ScenarioHarness, TurnOutcome};
 pub use permission_ui::TuiPermissionUI;
 
 use crate::config::{ConfigEventHandler, ConfigPersister};
 use crate::core::error::Result;
+use crate::session::SessionRecord;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-pub async fn run_tui(agent_config: AgentConfig, show_model_picker: bool) -> Result<()> {
+pub async fn run_tui(
+    agent_config: AgentConfig,
+    show_model_picker: bool,
+    show_sessions: bool,
+) -> Result<()> {
     let (event_tx, event_rx) = mpsc::unbounded_channel();
 
     let config_event_tx = ConfigPersister::with_default_path().map_or_else(
@@ -39,6 +47,20 @@ pub async fn run_tui(agent_config: AgentConfig, show_model_picker: bool) -> Resu
         event_rx,
         config_event_tx,
         show_model_picker,
+        show_sessions,
     )?;
     app.run().await
 }
+
+pub async fn run_replay(
+    agent_config: AgentConfig,
+    record: &SessionRecord,
+    speed: f64,
+) -> Result<()> {
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let mut app =
+        TuiApp::with_lazy_agent(agent_config, event_tx.clone(), event_rx, None, false, false)?
+            .replaying();
+    tokio::spawn(replay::play(replay::replay_steps(record, speed), event_tx));
+    app.run().await
+}
@@ -16,20 +16,15 @@ impl TuiPermissionUI {
     }
 }
 
-impl PermissionUI for TuiPermissionUI {
-    fn prompt_user(&self, request: &PermissionRequest) -> Result<PermissionResponse> {
+impl TuiPermissionUI {
+    fn ask<T>(&self, event: impl FnOnce(oneshot::Sender<T>) -> AppEvent) -> Result<T> {
         let (response_tx, response_rx) = oneshot::channel();
 
-        self.event_tx
-            .send(AppEvent::PermissionRequired {
-                request: request.clone(),
-                response_tx,
-            })
-            .map_err(|e| {
-                crate::core::error::AgentError::InvalidState(format!(
-                    "Failed to send permission request to TUI: {e}"
-                ))
-            })?;
+        self.event_tx.send(event(response_tx)).map_err(|e| {
+            crate::core::error::AgentError::InvalidState(format!(
+                "Failed to send permission request to TUI: {e}"
+            ))
+        })?;
 
         let response =
             tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(response_rx))
@@ -42,3 +37,19 @@ impl PermissionUI for TuiPermissionUI {
         Ok(response)
     }
 }
+
+impl PermissionUI for TuiPermissionUI {
+    fn prompt_user(&self, request: &PermissionRequest) -> Result<PermissionResponse> {
+        self.ask(|response_tx| AppEvent::PermissionRequired {
+            request: request.clone(),
+            response_tx,
+        })
+    }
+
+    fn prompt_batch(&self, requests: &[PermissionRequest]) -> Result<Vec<PermissionResponse>> {
+        self.ask(|response_tx| AppEvent::BatchPermissionRequired {
+            requests: requests.to_vec(),
+            response_tx,
+        })
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::core::types::{ContentBlock, Message, Role, Usage};
+use crate::session::SessionRecord;
+use crate::tools::result::ToolResult;
+use crate::tui::events::AppEvent;
+
+const PROMPT_DELAY: Duration = Duration::from_millis(600);
+const WORD_DELAY: Duration = Duration::from_millis(25);
+const TOOL_DELAY: Duration = Duration::from_millis(400);
+
+pub struct ReplayStep {
+    pub delay: Duration,
+    pub event: AppEvent,
+}
+
+struct Timeline {
+    steps: Vec<ReplayStep>,
+    speed: f64,
+}
+
+impl Timeline {
+    fn push(&mut self, delay: Duration, event: AppEvent) {
+        self.steps.push(ReplayStep {
+            delay: delay.div_f64(self.speed),
+            event,
+        });
+    }
+
+    fn stream_text(&mut self, text: &str) {
+        for word in text.split_inclusive(char::is_whitespace) {
+            self.push(WORD_DELAY, AppEvent::LLMChunk(word.to_string()));
+        }
+    }
+
+    fn complete(&mut self, reply: Option<Message>) {
+        if let Some(reply) = reply {
+            self.push(
+                Duration::ZERO,
+                AppEvent::LLMComplete(reply, Usage::default(), None),
+            );
+        }
+    }
+}
+
+#[must_use]
+pub fn replay_steps(record: &SessionRecord, speed: f64) -> Vec<ReplayStep> {
+    let mut timeline = Timeline {
+        steps: Vec::new(),
+        speed: if speed > 0.0 { speed } else { 1.0 },
+    };
+    timeline.push(Duration::ZERO, AppEvent::SessionTitle(record.title.clone()));
+
+    let mut tool_names = HashMap::new();
+    let mut reply: Option<Message> = None;
+    for message in &record.messages {
+        match message.role {
+            Role::User => {
+                timeline.complete(reply.take());
+                let text = message.first_text().unwrap_or_default().to_string();
+                timeline.push(PROMPT_DELAY, AppEvent::ReplayPrompt(text));
+            }
+            Role::Assistant => {
+                reply = Some(message.clone());
+                for block in &message.content {
+                    match block {
+                        ContentBlock::Text { text } => timeline.stream_text(text),
+                        ContentBlock::ToolUse {
+                            id, name, input, ..
+                        } => {
+                            tool_names.insert(id.clone(), name.clone());
+                            timeline.push(
+                                TOOL_DELAY,
+                                AppEvent::ToolStarted {
+                                    name: name.clone(),
+                                    input: input.to_string(),
+                                },
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Role::Tool => {
+                for block in &message.content {
+                    let ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        is_error,
+                    } = block
+                    else {
+                        continue;
+                    };
+                    let name = tool_names.get(tool_use_id).cloned().unwrap_or_default();
+                    let event = if is_error.unwrap_or(false) {
+                        AppEvent::ToolFailed {
+                            name,
+                            error: content.clone(),
+                        }
+                    } else {
+                        AppEvent::ToolCompleted {
+                            name,
+                            result: ToolResult::success(content.clone()),
+                        }
+                    };
+                    timeline.push(TOOL_DELAY, event);
+                }
+            }
+            Role::System => {}
+        }
+    }
+    timeline.complete(reply);
+    timeline.steps
+}
+
+pub async fn play(steps: Vec<ReplayStep>, event_tx: UnboundedSender<AppEvent>) {
+    for step in steps {
+        tokio::time::sleep(step.delay).await;
+        if event_tx.send(step.event).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_steps_follow_the_conversation() {
+        let mut record = SessionRecord::new("gpt-5.2");
+        let tool_use = ContentBlock::tool_use("read_file", serde_json::json!({"path": "a.rs"}));
+        let ContentBlock::ToolUse { id, .. } = &tool_use else {
+            unreachable!();
+        };
+        record.messages = vec![
+            Message::user("read a.rs"),
+            Message::new(
+                Role::Assistant,
+                vec![ContentBlock::text("Reading it."), tool_use.clone()],
+            ),
+            Message::new(Role::Tool, vec![ContentBlock::tool_error(id, "missing")]),
+            Message::assistant("It does not exist."),
+        ];
+
+        let steps = replay_steps(&record, 2.0);
+        let kinds: Vec<&str> = steps
+            .iter()
+            .map(|step| match &step.event {
+                AppEvent::SessionTitle(_) => "title",
+                AppEvent::ReplayPrompt(_) => "prompt",
+                AppEvent::LLMChunk(_) => "chunk",
+                AppEvent::ToolStarted { .. } => "start",
+                AppEvent::ToolFailed { name, .. } if name == "read_file" => "failed",
+                AppEvent::LLMComplete(..) => "complete",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "title", "prompt", "chunk", "chunk", "start", "failed", "chunk", "chunk", "chunk",
+                "chunk", "complete"
+            ]
+        );
+        assert_eq!(steps[1].delay, PROMPT_DELAY / 2);
+    }
+}
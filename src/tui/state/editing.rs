@@ -0,0 +1,98 @@
+use crate::tui::widgets::ChatMessage;
+
+use super::AppState;
+
+impl AppState {
+    #[must_use]
+    pub const fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    pub fn edit_older(&mut self) -> Option<String> {
+        let end = self.editing.unwrap_or(self.messages.len());
+        let index = self.messages[..end]
+            .iter()
+            .rposition(|message| matches!(message, ChatMessage::User(_)))
+            .or(self.editing)?;
+        self.select_edit(index)
+    }
+
+    pub fn edit_newer(&mut self) -> Option<String> {
+        let start = self.editing? + 1;
+        let Some(offset) = self.messages[start..]
+            .iter()
+            .position(|message| matches!(message, ChatMessage::User(_)))
+        else {
+            self.cancel_edit();
+            return None;
+        };
+        self.select_edit(start + offset)
+    }
+
+    fn select_edit(&mut self, index: usize) -> Option<String> {
+        let ChatMessage::User(text) = &self.messages[index] else {
+            return None;
+        };
+        let text = text.clone();
+        self.editing = Some(index);
+        self.scroll.request_jump();
+        Some(text)
+    }
+
+    pub const fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    pub fn take_edit_branch(&mut self) -> Option<usize> {
+        let index = self.editing.take()?;
+        let turns_back = self.messages[index + 1..]
+            .iter()
+            .filter(|message| matches!(message, ChatMessage::User(_)))
+            .count();
+        self.messages.truncate(index);
+        self.collapsed.retain(|&i| i < index);
+        self.selection = None;
+        self.search = None;
+        self.scroll.reset_manual_scroll();
+        Some(turns_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> AppState {
+        let mut state = AppState::new();
+        state.add_user_message("first".to_string());
+        state.add_assistant_message("one".to_string(), None);
+        state.add_user_message("second".to_string());
+        state.add_assistant_message("two".to_string(), None);
+        state
+    }
+
+    #[test]
+    fn test_edit_selection_walks_user_messages() {
+        let mut state = state();
+        assert_eq!(state.edit_newer(), None);
+
+        assert_eq!(state.edit_older().as_deref(), Some("second"));
+        assert_eq!(state.edit_older().as_deref(), Some("first"));
+        assert_eq!(state.edit_older().as_deref(), Some("first"));
+        assert_eq!(state.edit_newer().as_deref(), Some("second"));
+        assert_eq!(state.edit_newer(), None);
+        assert!(!state.is_editing());
+    }
+
+    #[test]
+    fn test_take_edit_branch_truncates_chat() {
+        let mut state = state();
+        state.edit_older();
+        state.edit_older();
+
+        assert_eq!(state.take_edit_branch(), Some(1));
+        assert!(state.messages.is_empty());
+        assert!(!state.is_editing());
+        assert_eq!(state.take_edit_branch(), None);
+    }
+}
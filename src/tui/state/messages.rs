@@ -1,4 +1,5 @@
-use crate::tui::widgets::{ChatMessage, ScrollState};
+use crate::git::Finding;
+use crate::tui::widgets::{ChatMessage, FileChange, ModelTag, ScrollState};
 use crate::ui::output_widget::MessageLevel;
 
 use super::AppState;
@@ -9,8 +10,8 @@ impl AppState {
         self.scroll.reset_manual_scroll();
     }
 
-    pub fn add_assistant_message(&mut self, text: String) {
-        self.messages.push(ChatMessage::Assistant(text));
+    pub fn add_assistant_message(&mut self, text: String, model: Option<ModelTag>) {
+        self.messages.push(ChatMessage::Assistant { text, model });
         self.scroll.reset_manual_scroll();
     }
 
@@ -18,21 +19,61 @@ impl AppState {
         self.add_system_message_with_level(text, MessageLevel::Info);
     }
 
+    pub fn add_review(&mut self, title: String, findings: Vec<Finding>) {
+        self.messages.push(ChatMessage::Review { title, findings });
+        self.scroll.reset_manual_scroll();
+    }
+
+    pub fn add_error_message(&mut self, text: String) {
+        self.add_system_message_with_level(text, MessageLevel::Error);
+    }
+
     pub fn add_system_message_with_level(&mut self, text: String, level: MessageLevel) {
         self.messages.push(ChatMessage::System { text, level });
         self.scroll.reset_manual_scroll();
     }
 
-    pub fn add_file_diff(&mut self, path: String, old_content: String, new_content: String) {
+    pub fn add_file_diff(
+        &mut self,
+        path: String,
+        old_content: String,
+        new_content: String,
+        format: Option<String>,
+    ) {
+        match self
+            .turn_changes
+            .iter_mut()
+            .find(|change| change.path == path)
+        {
+            Some(change) => change.new_content.clone_from(&new_content),
+            None => self.turn_changes.push(FileChange {
+                path: path.clone(),
+                old_content: old_content.clone(),
+                new_content: new_content.clone(),
+            }),
+        }
         self.messages.push(ChatMessage::FileDiff {
             path,
             old_content,
             new_content,
+            format,
             collapsed: false,
         });
         self.scroll.reset_manual_scroll();
     }
 
+    pub fn add_turn_summary(&mut self) {
+        let files = std::mem::take(&mut self.turn_changes);
+        if files.is_empty() {
+            return;
+        }
+        self.messages.push(ChatMessage::DiffSummary {
+            files,
+            collapsed: true,
+        });
+        self.scroll.reset_manual_scroll();
+    }
+
     #[must_use]
     pub fn messages_with_streaming(&self) -> Vec<ChatMessage> {
         let mut all_messages = self.messages.clone();
@@ -57,5 +98,9 @@ impl AppState {
     pub fn clear_messages(&mut self) {
         self.messages.clear();
         self.scroll = ScrollState::new();
+        self.search = None;
+        self.editing = None;
+        self.selection = None;
+        self.collapsed.clear();
     }
 }
@@ -1,20 +1,40 @@
+mod editing;
 mod history;
 mod messages;
 mod modals;
+mod recent_models;
+mod search;
+mod selection;
+mod sessions;
 mod streaming;
 mod tools;
 
 pub use history::InputHistory;
-pub use modals::{ModelPickerModal, PermissionModal, PickerModel};
+pub use modals::{
+    BatchPermissionModal, CommitDraft, CommitModal, ConfigChanges, ConfigField, ConfigFormModal,
+    ConfigSettings, ContextModal, MemoriesModal, ModelPickerModal, PasteViewer, PermissionModal,
+    PickerModel, SessionBrowserModal, SnippetForm,
+};
+pub use recent_models::{MAX_RECENT_MODELS, RecentModels};
+pub use streaming::{StreamProgress, StreamStats};
 pub use tools::ToolExecution;
 
+use crate::core::context::ContextSnapshot;
+use crate::core::run_state::RunState;
 use crate::core::types::Usage;
+use crate::memories::MemoryStore;
 use crate::permission::types::{PermissionRequest, PermissionResponse};
-use crate::tui::widgets::{ChatMessage, ScrollState};
-use std::collections::HashMap;
+use crate::tui::widgets::{ChatMessage, FileChange, LineCache, ScrollState, SearchState};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
+enum PendingPermission {
+    Single(PermissionModal),
+    Batch(BatchPermissionModal),
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct AppState {
     pub should_quit: bool,
     pub frame: usize,
@@ -23,15 +43,38 @@ pub struct AppState {
     pub history: InputHistory,
     pub messages: Vec<ChatMessage>,
     pub scroll: ScrollState,
+    pub line_cache: LineCache,
+    pub search: Option<SearchState>,
+    pub editing: Option<usize>,
+    pub selection: Option<usize>,
+    pub collapsed: HashSet<usize>,
+    pub expand_tool_groups: bool,
+    pub model_badges: bool,
     pub streaming_response: Option<String>,
     pub active_tools: HashMap<String, ToolExecution>,
     pub permission_modal: Option<PermissionModal>,
+    pub batch_permission: Option<BatchPermissionModal>,
     pub model_picker_modal: Option<ModelPickerModal>,
+    pub session_browser: Option<SessionBrowserModal>,
+    pub config_form: Option<ConfigFormModal>,
+    pub commit_modal: Option<CommitModal>,
+    pub memories_modal: Option<MemoriesModal>,
+    pub context_modal: Option<ContextModal>,
+    pub paste_viewer: Option<PasteViewer>,
+    pub snippet_form: Option<SnippetForm>,
     pub last_usage: Option<Usage>,
     pub session_usage: Usage,
+    pub session_title: Option<String>,
+    pub update_available: Option<String>,
+    pub auto_approve: bool,
+    pub recent_models: RecentModels,
+    pub run_state: RunState,
 
     spinner_last_update: Option<Instant>,
     request_start: Option<Instant>,
+    stream_stats: StreamStats,
+    turn_changes: Vec<FileChange>,
+    permission_queue: VecDeque<PendingPermission>,
 }
 
 impl AppState {
@@ -44,15 +87,38 @@ impl AppState {
             spinner_last_update: None,
             is_processing: false,
             request_start: None,
+            stream_stats: StreamStats::default(),
+            turn_changes: Vec::new(),
+            permission_queue: VecDeque::new(),
             history: InputHistory::new(),
             messages: Vec::new(),
             scroll: ScrollState::new(),
+            line_cache: LineCache::new(),
+            search: None,
+            editing: None,
+            selection: None,
+            collapsed: HashSet::new(),
+            expand_tool_groups: false,
+            model_badges: false,
             streaming_response: None,
             active_tools: HashMap::new(),
             permission_modal: None,
+            batch_permission: None,
             model_picker_modal: None,
+            session_browser: None,
+            config_form: None,
+            commit_modal: None,
+            memories_modal: None,
+            context_modal: None,
+            paste_viewer: None,
+            snippet_form: None,
             last_usage: None,
             session_usage: Usage::default(),
+            session_title: None,
+            update_available: None,
+            auto_approve: false,
+            recent_models: RecentModels::default(),
+            run_state: RunState::Idle,
         }
     }
 
@@ -84,11 +150,13 @@ impl AppState {
     pub fn start_processing(&mut self) {
         self.is_processing = true;
         self.request_start = Some(Instant::now());
+        self.stream_stats = StreamStats::default();
     }
 
     pub const fn stop_processing(&mut self) {
         self.is_processing = false;
         self.request_start = None;
+        self.run_state = RunState::Idle;
     }
 
     #[must_use]
@@ -98,7 +166,9 @@ impl AppState {
 
     #[must_use]
     pub const fn has_modal(&self) -> bool {
-        self.permission_modal.is_some() || self.model_picker_modal.is_some()
+        self.permission_modal.is_some()
+            || self.batch_permission.is_some()
+            || self.model_picker_modal.is_some()
     }
 
     #[must_use]
@@ -120,6 +190,80 @@ impl AppState {
         self.model_picker_modal = None;
     }
 
+    #[must_use]
+    pub const fn has_config_form(&self) -> bool {
+        self.config_form.is_some()
+    }
+
+    pub fn show_config_form(&mut self, current: ConfigSettings) {
+        self.config_form = Some(ConfigFormModal::from_registry(current));
+    }
+
+    pub fn close_config_form(&mut self) {
+        self.config_form = None;
+    }
+
+    #[must_use]
+    pub const fn has_commit_modal(&self) -> bool {
+        self.commit_modal.is_some()
+    }
+
+    pub fn show_commit_modal(&mut self, draft: CommitDraft) {
+        self.commit_modal = Some(CommitModal::new(draft));
+    }
+
+    pub fn close_commit_modal(&mut self) {
+        self.commit_modal = None;
+    }
+
+    #[must_use]
+    pub const fn has_memories_modal(&self) -> bool {
+        self.memories_modal.is_some()
+    }
+
+    pub fn show_memories_modal(&mut self, store: MemoryStore) {
+        self.memories_modal = Some(MemoriesModal::new(store));
+    }
+
+    pub fn close_memories_modal(&mut self) {
+        self.memories_modal = None;
+    }
+
+    #[must_use]
+    pub const fn has_context_modal(&self) -> bool {
+        self.context_modal.is_some()
+    }
+
+    pub fn show_context_modal(&mut self, snapshot: ContextSnapshot) {
+        self.context_modal = Some(ContextModal::new(snapshot));
+    }
+
+    pub fn close_context_modal(&mut self) {
+        self.context_modal = None;
+    }
+
+    #[must_use]
+    pub const fn has_paste_viewer(&self) -> bool {
+        self.paste_viewer.is_some()
+    }
+
+    pub fn show_paste_viewer(&mut self, id: usize, content: String) {
+        self.paste_viewer = Some(PasteViewer::new(id, content));
+    }
+
+    pub fn close_paste_viewer(&mut self) {
+        self.paste_viewer = None;
+    }
+
+    #[must_use]
+    pub const fn has_snippet_form(&self) -> bool {
+        self.snippet_form.is_some()
+    }
+
+    pub fn close_snippet_form(&mut self) {
+        self.snippet_form = None;
+    }
+
     pub const fn model_picker_select_prev(&mut self) {
         if let Some(modal) = &mut self.model_picker_modal {
             modal.select_prev();
@@ -137,20 +281,81 @@ impl AppState {
         request: PermissionRequest,
         response_tx: oneshot::Sender<PermissionResponse>,
     ) {
-        self.permission_modal = Some(PermissionModal::new(request, response_tx));
+        self.enqueue_permission(PendingPermission::Single(PermissionModal::new(
+            request,
+            response_tx,
+        )));
+    }
+
+    #[must_use]
+    pub const fn has_batch_permission(&self) -> bool {
+        self.batch_permission.is_some()
+    }
+
+    pub fn show_batch_permission_modal(
+        &mut self,
+        requests: Vec<PermissionRequest>,
+        response_tx: oneshot::Sender<Vec<PermissionResponse>>,
+    ) {
+        self.enqueue_permission(PendingPermission::Batch(BatchPermissionModal::new(
+            requests,
+            response_tx,
+        )));
+    }
+
+    pub fn batch_permission_confirm(&mut self) -> Option<String> {
+        let message = self
+            .batch_permission
+            .take()
+            .map(BatchPermissionModal::confirm);
+        self.next_permission();
+        message
+    }
+
+    pub fn batch_permission_cancel(&mut self) {
+        if let Some(modal) = self.batch_permission.take() {
+            modal.cancel();
+        }
+        self.next_permission();
+    }
+
+    #[must_use]
+    pub fn queued_permissions(&self) -> usize {
+        self.permission_queue.len()
+    }
+
+    fn enqueue_permission(&mut self, pending: PendingPermission) {
+        self.permission_queue.push_back(pending);
+        if self.permission_modal.is_none() && self.batch_permission.is_none() {
+            self.next_permission();
+        }
+    }
+
+    fn next_permission(&mut self) {
+        match self.permission_queue.pop_front() {
+            Some(PendingPermission::Single(modal)) => self.permission_modal = Some(modal),
+            Some(PendingPermission::Batch(modal)) => self.batch_permission = Some(modal),
+            None => {}
+        }
     }
 
     pub fn permission_confirm(&mut self) -> Option<String> {
-        self.permission_modal
+        let message = self
+            .permission_modal
             .take()
-            .and_then(PermissionModal::confirm)
+            .and_then(PermissionModal::confirm);
+        self.next_permission();
+        message
     }
 
     pub fn permission_cancel(&mut self) -> bool {
-        self.permission_modal
+        let cancelled = self
+            .permission_modal
             .take()
             .map(PermissionModal::cancel)
-            .is_some()
+            .is_some();
+        self.next_permission();
+        cancelled
     }
 
     #[must_use]
@@ -184,6 +389,16 @@ impl AppState {
         }
     }
 
+    pub const fn permission_scroll_diff(&mut self, up: bool, lines: usize) {
+        if let Some(modal) = &mut self.permission_modal {
+            if up {
+                modal.scroll_diff_up(lines);
+            } else {
+                modal.scroll_diff_down(lines);
+            }
+        }
+    }
+
     pub fn permission_set_selection(&mut self, index: usize) {
         if let Some(modal) = &mut self.permission_modal {
             modal.set_selection(index);
@@ -218,7 +433,7 @@ impl AppState {
         self.history.move_forward()
     }
 
-    pub const fn record_usage(&mut self, usage: Usage) {
+    pub fn record_usage(&mut self, usage: Usage) {
         self.last_usage = Some(usage);
         self.session_usage.add(&usage);
     }
@@ -264,4 +479,63 @@ mod tests {
         assert!(!state.is_processing);
         assert!(state.elapsed().is_none());
     }
+
+    #[test]
+    fn concurrent_permission_requests_are_queued() {
+        use crate::permission::PermissionType;
+
+        let mut state = AppState::new();
+        let (first_tx, mut first_rx) = oneshot::channel();
+        let (batch_tx, mut batch_rx) = oneshot::channel();
+        let (last_tx, mut last_rx) = oneshot::channel();
+        let request = |target| PermissionRequest::new(PermissionType::CommandExecute, target);
+        state.show_permission_modal(request("make"), first_tx);
+        state.show_batch_permission_modal(vec![request("a"), request("b")], batch_tx);
+        state.show_permission_modal(request("make test"), last_tx);
+        assert_eq!(state.queued_permissions(), 2);
+        assert!(!state.has_batch_permission());
+
+        let _ = state.permission_confirm();
+        assert_eq!(first_rx.try_recv().unwrap(), PermissionResponse::AllowOnce);
+        assert!(state.has_batch_permission());
+        assert!(state.permission_modal.is_none());
+
+        state.batch_permission_cancel();
+        assert_eq!(batch_rx.try_recv().unwrap().len(), 2);
+        assert_eq!(state.queued_permissions(), 0);
+        assert_eq!(
+            state.permission_modal.as_ref().unwrap().request.target,
+            "make test"
+        );
+        assert!(state.permission_cancel());
+        assert!(last_rx.try_recv().is_ok());
+        assert!(!state.has_modal());
+    }
+
+    #[test]
+    fn turn_summary_aggregates_file_diffs() {
+        let mut state = AppState::new();
+        state.add_turn_summary();
+        assert!(state.messages.is_empty());
+
+        state.add_file_diff("a.rs".into(), "one\n".into(), "two\n".into(), None);
+        state.add_file_diff("a.rs".into(), "two\n".into(), "two\nthree\n".into(), None);
+        state.add_file_diff("b.rs".into(), String::new(), "new\n".into(), None);
+        state.add_turn_summary();
+
+        let Some(summary @ ChatMessage::DiffSummary { files, collapsed }) = state.messages.last()
+        else {
+            panic!("expected a diff summary");
+        };
+        assert!(*collapsed);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].old_content, "one\n");
+        let lines = summary.render_to_lines(80, 0);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].to_string(), "● 2 files changed, +3 \u{2212}1");
+
+        let count = state.messages.len();
+        state.add_turn_summary();
+        assert_eq!(state.messages.len(), count);
+    }
 }
@@ -0,0 +1,120 @@
+use crate::permission::types::{PermissionRequest, PermissionResponse};
+use tokio::sync::oneshot;
+
+pub struct BatchPermissionModal {
+    pub requests: Vec<PermissionRequest>,
+    pub approved: Vec<bool>,
+    pub selected: usize,
+    response_tx: oneshot::Sender<Vec<PermissionResponse>>,
+}
+
+impl BatchPermissionModal {
+    #[must_use]
+    pub fn new(
+        requests: Vec<PermissionRequest>,
+        response_tx: oneshot::Sender<Vec<PermissionResponse>>,
+    ) -> Self {
+        let approved = vec![true; requests.len()];
+        Self {
+            requests,
+            approved,
+            selected: 0,
+            response_tx,
+        }
+    }
+
+    pub const fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected + 1 < self.requests.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(approved) = self.approved.get_mut(self.selected) {
+            *approved = !*approved;
+        }
+    }
+
+    pub fn approve_all(&mut self) {
+        self.approved.fill(true);
+    }
+
+    #[must_use]
+    pub fn approved_count(&self) -> usize {
+        self.approved.iter().filter(|approved| **approved).count()
+    }
+
+    #[must_use]
+    pub fn confirm(self) -> String {
+        let message = format!(
+            "Approved {} of {} file writes",
+            self.approved_count(),
+            self.requests.len()
+        );
+        let responses = self
+            .approved
+            .iter()
+            .map(|&approved| {
+                if approved {
+                    PermissionResponse::AllowOnce
+                } else {
+                    PermissionResponse::TellModelDifferently(
+                        "User skipped this file in the batch approval".to_string(),
+                    )
+                }
+            })
+            .collect();
+        let _ = self.response_tx.send(responses);
+        message
+    }
+
+    pub fn cancel(self) {
+        let responses = self
+            .requests
+            .iter()
+            .map(|_| {
+                PermissionResponse::TellModelDifferently("User cancelled the operation".to_string())
+            })
+            .collect();
+        let _ = self.response_tx.send(responses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permission::PermissionType;
+
+    #[test]
+    fn test_toggle_and_confirm() {
+        let requests = ["src/a.rs", "src/b.rs", "Cargo.lock"]
+            .map(|path| PermissionRequest::new(PermissionType::FileWrite, path))
+            .to_vec();
+        let (tx, mut rx) = oneshot::channel();
+        let mut modal = BatchPermissionModal::new(requests, tx);
+        assert_eq!(modal.approved_count(), 3);
+
+        modal.select_next();
+        modal.toggle_selected();
+        modal.select_next();
+        modal.toggle_selected();
+        modal.select_next();
+        assert_eq!(modal.selected, 2);
+        assert_eq!(modal.approved_count(), 1);
+        modal.approve_all();
+        modal.toggle_selected();
+
+        assert_eq!(modal.confirm(), "Approved 2 of 3 file writes");
+        let responses = rx.try_recv().unwrap();
+        assert_eq!(responses[0], PermissionResponse::AllowOnce);
+        assert_eq!(responses[1], PermissionResponse::AllowOnce);
+        assert!(matches!(
+            responses[2],
+            PermissionResponse::TellModelDifferently(_)
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use crate::git::Git;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitDraft {
+    pub dir: PathBuf,
+    pub message: String,
+    pub summary: String,
+    pub stage_all: bool,
+}
+
+pub struct CommitModal {
+    pub draft: CommitDraft,
+    pub error: Option<String>,
+}
+
+impl CommitModal {
+    #[must_use]
+    pub const fn new(draft: CommitDraft) -> Self {
+        Self { draft, error: None }
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.draft.message
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        self.error = None;
+        self.draft.message.push(c);
+    }
+
+    pub fn newline(&mut self) {
+        self.input_char('\n');
+    }
+
+    pub fn backspace(&mut self) {
+        self.error = None;
+        self.draft.message.pop();
+    }
+
+    pub fn commit(&mut self) -> Option<String> {
+        let message = self.draft.message.trim();
+        if message.is_empty() {
+            self.error = Some("Commit message is empty".to_string());
+            return None;
+        }
+        match Git::new(&self.draft.dir).commit(message, self.draft.stage_all) {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing_message() {
+        let mut modal = CommitModal::new(CommitDraft {
+            dir: PathBuf::from("."),
+            message: "feat: ad".to_string(),
+            summary: String::new(),
+            stage_all: false,
+        });
+        modal.input_char('d');
+        modal.newline();
+        modal.newline();
+        "Body".chars().for_each(|c| modal.input_char(c));
+        modal.backspace();
+        assert_eq!(modal.message(), "feat: add\n\nBod");
+
+        modal.draft.message = "  \n".to_string();
+        assert_eq!(modal.commit(), None);
+        assert_eq!(modal.error.as_deref(), Some("Commit message is empty"));
+    }
+}
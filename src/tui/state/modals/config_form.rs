@@ -0,0 +1,264 @@
+use crate::config::{ConfigPatch, ConfigResult, ModelRegistry};
+use crate::permission::PermissionMode;
+use crate::ui::theme::ThemeName;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigField {
+    Model,
+    SystemPrompt,
+    PermissionMode,
+    Theme,
+}
+
+impl ConfigField {
+    pub const ALL: [Self; 4] = [
+        Self::Model,
+        Self::SystemPrompt,
+        Self::PermissionMode,
+        Self::Theme,
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Model => "Default model",
+            Self::SystemPrompt => "System prompt",
+            Self::PermissionMode => "Permissions",
+            Self::Theme => "Theme",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&f| f == self).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSettings {
+    pub model: Option<String>,
+    pub custom_system_prompt: Option<String>,
+    pub permission_mode: PermissionMode,
+    pub theme: ThemeName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChanges {
+    pub model: Option<String>,
+    pub patch: ConfigPatch,
+}
+
+pub struct ConfigFormModal {
+    pub models: Vec<String>,
+    pub model_index: Option<usize>,
+    pub system_prompt: String,
+    pub permission_mode: PermissionMode,
+    pub theme: ThemeName,
+    pub focused: ConfigField,
+    pub error: Option<String>,
+    initial: ConfigSettings,
+}
+
+impl ConfigFormModal {
+    #[must_use]
+    pub fn new(models: Vec<String>, current: ConfigSettings) -> Self {
+        let model_index = current
+            .model
+            .as_ref()
+            .and_then(|id| models.iter().position(|m| m == id));
+        Self {
+            models,
+            model_index,
+            system_prompt: current.custom_system_prompt.clone().unwrap_or_default(),
+            permission_mode: current.permission_mode,
+            theme: current.theme,
+            focused: ConfigField::Model,
+            error: None,
+            initial: current,
+        }
+    }
+
+    #[must_use]
+    pub fn from_registry(current: ConfigSettings) -> Self {
+        let models = ModelRegistry::load()
+            .all_models()
+            .iter()
+            .map(|m| m.id.clone())
+            .collect();
+        Self::new(models, current)
+    }
+
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        self.model_index
+            .and_then(|i| self.models.get(i))
+            .map(String::as_str)
+            .or(self.initial.model.as_deref())
+    }
+
+    pub fn next_field(&mut self) {
+        let next = (self.focused.index() + 1) % ConfigField::ALL.len();
+        self.focused = ConfigField::ALL[next];
+    }
+
+    pub fn prev_field(&mut self) {
+        let len = ConfigField::ALL.len();
+        self.focused = ConfigField::ALL[(self.focused.index() + len - 1) % len];
+    }
+
+    pub fn cycle(&mut self, forward: bool) {
+        self.error = None;
+        match self.focused {
+            ConfigField::Model if !self.models.is_empty() => {
+                let len = self.models.len();
+                self.model_index = Some(match (self.model_index, forward) {
+                    (None, true) => 0,
+                    (None, false) => len - 1,
+                    (Some(i), true) => (i + 1) % len,
+                    (Some(i), false) => (i + len - 1) % len,
+                });
+            }
+            ConfigField::PermissionMode => {
+                self.permission_mode = step(&PermissionMode::ALL, self.permission_mode, forward);
+            }
+            ConfigField::Theme => self.theme = step(&ThemeName::ALL, self.theme, forward),
+            ConfigField::Model | ConfigField::SystemPrompt => {}
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if self.focused == ConfigField::SystemPrompt {
+            self.error = None;
+            self.system_prompt.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if self.focused == ConfigField::SystemPrompt {
+            self.error = None;
+            self.system_prompt.pop();
+        }
+    }
+
+    #[must_use]
+    pub fn changes(&self) -> ConfigChanges {
+        let model = self
+            .model()
+            .filter(|&m| self.initial.model.as_deref() != Some(m))
+            .map(str::to_string);
+        let prompt = self.system_prompt.trim();
+        let custom_system_prompt = (self.initial.custom_system_prompt.as_deref().unwrap_or("")
+            != prompt)
+            .then(|| prompt.to_string());
+        ConfigChanges {
+            model,
+            patch: ConfigPatch {
+                custom_system_prompt,
+                permission_mode: (self.permission_mode != self.initial.permission_mode)
+                    .then_some(self.permission_mode),
+                theme: (self.theme != self.initial.theme).then_some(self.theme),
+                ..ConfigPatch::default()
+            },
+        }
+    }
+
+    pub fn submit(&mut self) -> ConfigResult<ConfigChanges> {
+        let changes = self.changes();
+        if let Err(e) = changes.patch.validate() {
+            self.error = Some(e.to_string());
+            return Err(e);
+        }
+        Ok(changes)
+    }
+}
+
+fn step<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let len = values.len();
+    let index = values.iter().position(|&v| v == current).unwrap_or(0);
+    if forward {
+        values[(index + 1) % len]
+    } else {
+        values[(index + len - 1) % len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MAX_CUSTOM_PROMPT_CHARS;
+
+    fn form() -> ConfigFormModal {
+        ConfigFormModal::new(
+            vec!["model-a".to_string(), "model-b".to_string()],
+            ConfigSettings {
+                model: Some("model-a".to_string()),
+                custom_system_prompt: Some("Be brief.".to_string()),
+                ..ConfigSettings::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_unchanged_form_has_no_changes() {
+        let mut form = form();
+        let changes = form.submit().unwrap();
+        assert_eq!(changes.model, None);
+        assert!(changes.patch.is_empty());
+    }
+
+    #[test]
+    fn test_edits_become_patch() {
+        let mut form = form();
+        form.cycle(true);
+        assert_eq!(form.model(), Some("model-b"));
+
+        form.next_field();
+        for _ in 0.."Be brief.".len() {
+            form.backspace();
+        }
+        "Use British spelling."
+            .chars()
+            .for_each(|c| form.input_char(c));
+
+        form.next_field();
+        form.cycle(false);
+        form.next_field();
+        form.cycle(true);
+        form.input_char('x');
+
+        let changes = form.submit().unwrap();
+        assert_eq!(changes.model.as_deref(), Some("model-b"));
+        assert_eq!(
+            changes.patch.custom_system_prompt.as_deref(),
+            Some("Use British spelling.")
+        );
+        assert_eq!(
+            changes.patch.permission_mode,
+            Some(PermissionMode::ReadOnly)
+        );
+        assert_eq!(changes.patch.theme, Some(ThemeName::Light));
+
+        form.prev_field();
+        form.prev_field();
+        form.prev_field();
+        form.prev_field();
+        assert_eq!(form.focused, ConfigField::Theme);
+    }
+
+    #[test]
+    fn test_invalid_prompt_is_rejected() {
+        let mut form = form();
+        form.focused = ConfigField::SystemPrompt;
+        form.system_prompt = "x".repeat(MAX_CUSTOM_PROMPT_CHARS + 1);
+        assert!(form.submit().is_err());
+        assert!(
+            form.error
+                .as_deref()
+                .unwrap()
+                .contains("custom_system_prompt")
+        );
+
+        form.backspace();
+        assert!(form.error.is_none());
+        assert!(form.submit().is_ok());
+    }
+}
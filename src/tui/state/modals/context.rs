@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use crate::core::context::{ContextSnapshot, ContextSource};
+
+pub struct ContextModal {
+    pub snapshot: ContextSnapshot,
+    pub selected: usize,
+    pub dropped: HashSet<usize>,
+}
+
+impl ContextModal {
+    #[must_use]
+    pub fn new(snapshot: ContextSnapshot) -> Self {
+        Self {
+            snapshot,
+            selected: 0,
+            dropped: HashSet::new(),
+        }
+    }
+
+    pub const fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected + 1 < self.snapshot.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn toggle_selected(&mut self) {
+        let Some(item) = self.snapshot.items.get(self.selected) else {
+            return;
+        };
+        if item.droppable() && !self.dropped.remove(&self.selected) {
+            self.dropped.insert(self.selected);
+        }
+    }
+
+    #[must_use]
+    pub fn kept_tokens(&self) -> usize {
+        self.snapshot.kept_tokens(&self.dropped)
+    }
+
+    #[must_use]
+    pub fn dropped_sources(&self) -> Vec<ContextSource> {
+        self.snapshot
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.dropped.contains(index))
+            .map(|(_, item)| item.source.clone())
+            .collect()
+    }
+}
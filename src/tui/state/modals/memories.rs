@@ -0,0 +1,138 @@
+use crate::core::error::Result;
+use crate::memories::MemoryStore;
+
+pub struct MemoriesModal {
+    store: MemoryStore,
+    pub entries: Vec<String>,
+    pub selected: usize,
+    pub input: Option<String>,
+    pub adding: bool,
+    pub confirm_delete: bool,
+}
+
+impl MemoriesModal {
+    #[must_use]
+    pub fn new(store: MemoryStore) -> Self {
+        let entries = store.list();
+        Self {
+            store,
+            entries,
+            selected: 0,
+            input: None,
+            adding: false,
+            confirm_delete: false,
+        }
+    }
+
+    pub const fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    #[must_use]
+    pub const fn is_editing(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn start_edit(&mut self) {
+        self.adding = false;
+        self.input = self.entries.get(self.selected).cloned();
+    }
+
+    pub fn start_add(&mut self) {
+        self.adding = true;
+        self.input = Some(String::new());
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if let Some(input) = &mut self.input {
+            input.push(c);
+        }
+    }
+
+    pub fn input_backspace(&mut self) {
+        if let Some(input) = &mut self.input {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.input = None;
+    }
+
+    pub fn commit_edit(&mut self) -> Result<bool> {
+        let Some(input) = self.input.take() else {
+            return Ok(false);
+        };
+        let changed = if self.adding {
+            self.store.add(&input)?
+        } else {
+            self.store.update(self.selected, &input)?
+        };
+        self.reload();
+        if self.adding {
+            self.selected = self.entries.len().saturating_sub(1);
+        }
+        Ok(changed)
+    }
+
+    pub fn delete_selected(&mut self) -> Result<Option<String>> {
+        if !self.confirm_delete {
+            self.confirm_delete = !self.entries.is_empty();
+            return Ok(None);
+        }
+        self.confirm_delete = false;
+        let removed = self.store.remove(self.selected)?;
+        self.reload();
+        Ok(removed)
+    }
+
+    fn reload(&mut self) {
+        self.entries = self.store.list();
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_edit_and_delete() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = MemoryStore::new(temp.path().join("memories.md"));
+        store.add("prefer snake_case").unwrap();
+        let mut modal = MemoriesModal::new(store.clone());
+
+        modal.start_add();
+        for c in "use tabs".chars() {
+            modal.input_char(c);
+        }
+        assert!(modal.commit_edit().unwrap());
+        assert_eq!(modal.selected, 1);
+
+        modal.start_edit();
+        modal.input_backspace();
+        modal.input_backspace();
+        modal.input_backspace();
+        modal.input_backspace();
+        for c in "spaces".chars() {
+            modal.input_char(c);
+        }
+        modal.commit_edit().unwrap();
+        assert_eq!(store.list(), vec!["prefer snake_case", "use spaces"]);
+
+        modal.select_prev();
+        assert!(modal.delete_selected().unwrap().is_none());
+        assert_eq!(
+            modal.delete_selected().unwrap().as_deref(),
+            Some("prefer snake_case")
+        );
+        assert_eq!(modal.entries, vec!["use spaces"]);
+    }
+}
@@ -1,5 +1,21 @@
+mod batch_permission;
+mod commit;
+mod config_form;
+mod context;
+mod memories;
 mod model_picker;
+mod paste_viewer;
 mod permission;
+mod session_browser;
+mod snippet_form;
 
+pub use batch_permission::BatchPermissionModal;
+pub use commit::{CommitDraft, CommitModal};
+pub use config_form::{ConfigChanges, ConfigField, ConfigFormModal, ConfigSettings};
+pub use context::ContextModal;
+pub use memories::MemoriesModal;
 pub use model_picker::{ModelPickerModal, PickerModel};
+pub use paste_viewer::PasteViewer;
 pub use permission::PermissionModal;
+pub use session_browser::SessionBrowserModal;
+pub use snippet_form::SnippetForm;
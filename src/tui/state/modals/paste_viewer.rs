@@ -0,0 +1,45 @@
+pub struct PasteViewer {
+    pub id: usize,
+    pub content: String,
+    pub scroll: usize,
+}
+
+impl PasteViewer {
+    #[must_use]
+    pub const fn new(id: usize, content: String) -> Self {
+        Self {
+            id,
+            content,
+            scroll: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count().max(1)
+    }
+
+    pub const fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.line_count() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let mut viewer = PasteViewer::new(0, "a\nb\nc".to_string());
+        viewer.scroll_down(10);
+        assert_eq!(viewer.scroll, 2);
+        viewer.scroll_up(1);
+        assert_eq!(viewer.scroll, 1);
+        viewer.scroll_up(5);
+        assert_eq!(viewer.scroll, 0);
+    }
+}
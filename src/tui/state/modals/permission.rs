@@ -5,7 +5,9 @@ use tokio::sync::oneshot;
 enum Selection {
     AllowOnce = 0,
     AllowSession = 1,
-    Deny = 2,
+    AllowAlways = 2,
+    Edit = 3,
+    Deny = 4,
 }
 
 impl Selection {
@@ -13,6 +15,8 @@ impl Selection {
         match index {
             0 => Self::AllowOnce,
             1 => Self::AllowSession,
+            2 => Self::AllowAlways,
+            3 => Self::Edit,
             _ => Self::Deny,
         }
     }
@@ -24,6 +28,7 @@ pub struct PermissionModal {
     response_tx: oneshot::Sender<PermissionResponse>,
     input_mode: bool,
     feedback_input: String,
+    diff_scroll: usize,
 }
 
 impl PermissionModal {
@@ -38,6 +43,7 @@ impl PermissionModal {
             response_tx,
             input_mode: false,
             feedback_input: String::new(),
+            diff_scroll: 0,
         }
     }
 
@@ -56,6 +62,21 @@ impl PermissionModal {
         &self.feedback_input
     }
 
+    #[must_use]
+    pub const fn diff_scroll(&self) -> usize {
+        self.diff_scroll
+    }
+
+    pub const fn scroll_diff_up(&mut self, lines: usize) {
+        self.diff_scroll = self.diff_scroll.saturating_sub(lines);
+    }
+
+    pub const fn scroll_diff_down(&mut self, lines: usize) {
+        if self.request.change.is_some() {
+            self.diff_scroll += lines;
+        }
+    }
+
     pub fn input_char(&mut self, c: char) {
         if self.input_mode {
             self.feedback_input.push(c);
@@ -69,49 +90,81 @@ impl PermissionModal {
     }
 
     pub fn set_selection(&mut self, index: usize) {
-        self.selected = index.min(2);
+        let index = index.min(Selection::Deny as usize);
+        self.selected = if index == Selection::Edit as usize && !self.request.editable {
+            Selection::Deny as usize
+        } else {
+            index
+        };
         self.update_input_mode();
     }
 
     pub fn select_prev(&mut self) {
         self.selected = self.selected.saturating_sub(1);
+        if self.selected == Selection::Edit as usize && !self.request.editable {
+            self.selected -= 1;
+        }
         self.update_input_mode();
     }
 
     pub fn select_next(&mut self) {
-        self.selected = (self.selected + 1).min(2);
+        self.selected = (self.selected + 1).min(Selection::Deny as usize);
+        if self.selected == Selection::Edit as usize && !self.request.editable {
+            self.selected += 1;
+        }
         self.update_input_mode();
     }
 
     fn update_input_mode(&mut self) {
-        let is_deny = self.selected == Selection::Deny as usize;
-        self.input_mode = is_deny;
-
-        if !is_deny {
-            self.feedback_input.clear();
+        match Selection::from_index(self.selected) {
+            Selection::Edit => {
+                self.input_mode = true;
+                self.feedback_input.clone_from(&self.request.target);
+            }
+            Selection::Deny => {
+                self.input_mode = true;
+                self.feedback_input.clear();
+            }
+            _ => {
+                self.input_mode = false;
+                self.feedback_input.clear();
+            }
         }
     }
 
     #[must_use]
     pub fn confirm(self) -> Option<String> {
-        let (response, feedback) = match Selection::from_index(self.selected) {
+        let (response, message) = match Selection::from_index(self.selected) {
             Selection::AllowOnce => (PermissionResponse::AllowOnce, None),
             Selection::AllowSession => (PermissionResponse::AllowSession, None),
+            Selection::AllowAlways => (PermissionResponse::AllowAlways, None),
+            Selection::Edit => {
+                let edited = self.feedback_input.trim();
+                if edited.is_empty() || edited == self.request.target {
+                    (PermissionResponse::AllowOnce, None)
+                } else {
+                    (
+                        PermissionResponse::AllowEdited(edited.to_string()),
+                        Some(format!("Approved with edit: {edited}")),
+                    )
+                }
+            }
             Selection::Deny => {
                 let feedback = if self.feedback_input.trim().is_empty() {
                     "User declined the operation. Please ask what to do instead.".to_string()
                 } else {
                     self.feedback_input.clone()
                 };
+                let message = format!("Denied: {feedback}");
                 (
-                    PermissionResponse::TellModelDifferently(feedback.clone()),
-                    Some(feedback),
+                    PermissionResponse::TellModelDifferently(feedback),
+                    Some(message),
                 )
             }
         };
 
         let _ = self.response_tx.send(response);
-        feedback
+        message
     }
 
     pub fn cancel(self) {
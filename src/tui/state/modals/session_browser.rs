@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::error::Result;
+use crate::session::{SessionRecord, SessionStore};
+
+pub struct SessionBrowserModal {
+    store: SessionStore,
+    pub sessions: Vec<SessionRecord>,
+    pub selected: usize,
+    pub rename_input: Option<String>,
+    pub confirm_delete: bool,
+}
+
+impl SessionBrowserModal {
+    pub fn new(store: SessionStore) -> Result<Self> {
+        let sessions = store.list()?;
+        Ok(Self {
+            store,
+            sessions,
+            selected: 0,
+            rename_input: None,
+            confirm_delete: false,
+        })
+    }
+
+    #[must_use]
+    pub fn selected_session(&self) -> Option<&SessionRecord> {
+        self.sessions.get(self.selected)
+    }
+
+    pub const fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.confirm_delete = false;
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected + 1 < self.sessions.len() {
+            self.selected += 1;
+        }
+        self.confirm_delete = false;
+    }
+
+    #[must_use]
+    pub const fn is_renaming(&self) -> bool {
+        self.rename_input.is_some()
+    }
+
+    pub fn start_rename(&mut self) {
+        self.confirm_delete = false;
+        self.rename_input = self
+            .selected_session()
+            .map(|session| session.title.clone().unwrap_or_default());
+    }
+
+    pub fn rename_char(&mut self, c: char) {
+        if let Some(input) = &mut self.rename_input {
+            input.push(c);
+        }
+    }
+
+    pub fn rename_backspace(&mut self) {
+        if let Some(input) = &mut self.rename_input {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_rename(&mut self) {
+        self.rename_input = None;
+    }
+
+    pub fn commit_rename(&mut self) -> Result<Option<&SessionRecord>> {
+        let Some(input) = self.rename_input.take() else {
+            return Ok(None);
+        };
+        let Some(session) = self.sessions.get_mut(self.selected) else {
+            return Ok(None);
+        };
+        let title = input.trim();
+        session.title = (!title.is_empty()).then(|| title.to_string());
+        self.store.save(session)?;
+        Ok(Some(session))
+    }
+
+    pub fn delete_selected(&mut self) -> Result<Option<SessionRecord>> {
+        if !self.confirm_delete {
+            self.confirm_delete = !self.sessions.is_empty();
+            return Ok(None);
+        }
+        self.confirm_delete = false;
+        if self.selected >= self.sessions.len() {
+            return Ok(None);
+        }
+        let session = self.sessions.remove(self.selected);
+        self.store.delete(session.id)?;
+        self.selected = self.selected.min(self.sessions.len().saturating_sub(1));
+        Ok(Some(session))
+    }
+
+    pub fn export_selected(&self, dir: &Path) -> Result<Option<PathBuf>> {
+        let Some(session) = self.selected_session() else {
+            return Ok(None);
+        };
+        let path = dir.join(session.export_file_name());
+        std::fs::write(&path, session.to_markdown())?;
+        Ok(Some(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Message;
+
+    fn modal_with_sessions(dir: &Path, count: usize) -> SessionBrowserModal {
+        let store = SessionStore::new(dir.join("sessions"));
+        for i in 0..count {
+            let mut record = SessionRecord::new("gpt-5.2");
+            record.title = Some(format!("Session {i}"));
+            record.messages.push(Message::user(format!("question {i}")));
+            record
+                .messages
+                .push(Message::assistant(format!("answer {i}")));
+            store.save(&record).unwrap();
+        }
+        SessionBrowserModal::new(store).unwrap()
+    }
+
+    #[test]
+    fn test_rename_and_delete() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut modal = modal_with_sessions(temp.path(), 2);
+        let id = modal.selected_session().unwrap().id;
+
+        modal.start_rename();
+        modal.rename_input = Some(String::new());
+        for c in "Parser fix".chars() {
+            modal.rename_char(c);
+        }
+        modal.commit_rename().unwrap();
+        assert_eq!(modal.store.load(id).unwrap().display_title(), "Parser fix");
+
+        assert!(modal.delete_selected().unwrap().is_none());
+        assert!(modal.confirm_delete);
+        let deleted = modal.delete_selected().unwrap().unwrap();
+        assert_eq!(deleted.id, id);
+        assert_eq!(modal.sessions.len(), 1);
+        assert_eq!(modal.store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_export_writes_markdown() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let modal = modal_with_sessions(temp.path(), 1);
+
+        let path = modal.export_selected(temp.path()).unwrap().unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("# Session 0"));
+        assert!(content.contains("question 0"));
+        assert!(content.contains("answer 0"));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::snippets::Snippet;
+
+pub struct SnippetForm {
+    pub snippet: Snippet,
+    pub fields: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+impl SnippetForm {
+    #[must_use]
+    pub fn new(snippet: Snippet, values: &[(String, String)]) -> Self {
+        let fields: Vec<(String, String)> = snippet
+            .placeholders()
+            .into_iter()
+            .map(|name| {
+                let value = values
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.clone())
+                    .unwrap_or_default();
+                (name, value)
+            })
+            .collect();
+        let selected = fields
+            .iter()
+            .position(|(_, value)| value.is_empty())
+            .unwrap_or(0);
+        Self {
+            snippet,
+            fields,
+            selected,
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if let Some((_, value)) = self.fields.get_mut(self.selected) {
+            value.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some((_, value)) = self.fields.get_mut(self.selected) {
+            value.pop();
+        }
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected + 1 < self.fields.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub const fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    #[must_use]
+    pub const fn is_last(&self) -> bool {
+        self.selected + 1 >= self.fields.len()
+    }
+
+    #[must_use]
+    pub fn filled(&self) -> String {
+        self.snippet.fill(&self.fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_starts_at_first_missing_field() {
+        let snippet = Snippet::new("t", "Test {{file}} for {{cases}}");
+        let mut form = SnippetForm::new(snippet, &[("file".to_string(), "a.rs".to_string())]);
+        assert_eq!(form.selected, 1);
+        assert!(form.is_last());
+
+        "edge".chars().for_each(|c| form.input_char(c));
+        form.backspace();
+        assert_eq!(form.filled(), "Test a.rs for edg");
+
+        form.select_prev();
+        form.select_prev();
+        assert_eq!(form.selected, 0);
+        assert!(!form.is_last());
+    }
+}
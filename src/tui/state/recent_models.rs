@@ -0,0 +1,87 @@
+pub const MAX_RECENT_MODELS: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct RecentModels {
+    models: Vec<String>,
+    cycle: Option<usize>,
+}
+
+impl RecentModels {
+    #[must_use]
+    pub fn new(models: Vec<String>) -> Self {
+        let mut recent = Self::default();
+        for model in models.into_iter().rev() {
+            recent.record(model);
+        }
+        recent
+    }
+
+    pub fn record(&mut self, model: String) {
+        self.models.retain(|m| *m != model);
+        self.models.insert(0, model);
+        self.models.truncate(MAX_RECENT_MODELS);
+        self.cycle = None;
+    }
+
+    #[must_use]
+    pub fn models(&self) -> &[String] {
+        &self.models
+    }
+
+    pub fn cycle_next(&mut self) -> Option<&str> {
+        if self.models.len() < 2 {
+            return None;
+        }
+        let next = self.cycle.map_or(1, |i| (i + 1) % self.models.len());
+        self.cycle = Some(next);
+        self.pending()
+    }
+
+    #[must_use]
+    pub fn pending(&self) -> Option<&str> {
+        self.cycle
+            .filter(|&i| i > 0)
+            .and_then(|i| self.models.get(i))
+            .map(String::as_str)
+    }
+
+    pub fn take_pending(&mut self) -> Option<String> {
+        let pending = self.pending().map(str::to_string);
+        self.cycle = None;
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_through_recent_models() {
+        let mut recent = RecentModels::new(vec!["fast".into(), "smart".into(), "old".into()]);
+        assert_eq!(recent.models(), ["fast", "smart", "old"]);
+        assert_eq!(recent.pending(), None);
+
+        assert_eq!(recent.cycle_next(), Some("smart"));
+        assert_eq!(recent.cycle_next(), Some("old"));
+        assert_eq!(recent.cycle_next(), None);
+        assert_eq!(recent.cycle_next(), Some("smart"));
+        assert_eq!(recent.take_pending().as_deref(), Some("smart"));
+        assert_eq!(recent.take_pending(), None);
+
+        recent.record("smart".into());
+        assert_eq!(recent.models(), ["smart", "fast", "old"]);
+        assert_eq!(recent.cycle_next(), Some("fast"));
+    }
+
+    #[test]
+    fn test_record_keeps_the_most_recent() {
+        let mut recent = RecentModels::default();
+        assert_eq!(recent.cycle_next(), None);
+        for i in 0..=MAX_RECENT_MODELS {
+            recent.record(format!("m{i}"));
+        }
+        assert_eq!(recent.models().len(), MAX_RECENT_MODELS);
+        assert_eq!(recent.models()[0], format!("m{MAX_RECENT_MODELS}"));
+    }
+}
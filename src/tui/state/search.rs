@@ -0,0 +1,51 @@
+use crate::tui::widgets::SearchState;
+
+use super::AppState;
+
+impl AppState {
+    pub fn start_search(&mut self, query: &str) -> usize {
+        let search = SearchState::new(query, &self.messages);
+        let hits = search.hit_count();
+        if hits == 0 {
+            self.search = None;
+        } else {
+            self.search = Some(search);
+            self.scroll.request_jump();
+        }
+        hits
+    }
+
+    #[must_use]
+    pub const fn has_search(&self) -> bool {
+        self.search.is_some()
+    }
+
+    #[must_use]
+    pub fn is_search_navigating(&self) -> bool {
+        self.search.as_ref().is_some_and(SearchState::is_navigating)
+    }
+
+    pub const fn stop_search_navigation(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.stop_navigating();
+        }
+    }
+
+    pub const fn search_older(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.older();
+            self.scroll.request_jump();
+        }
+    }
+
+    pub const fn search_newer(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.newer();
+            self.scroll.request_jump();
+        }
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = None;
+    }
+}
@@ -0,0 +1,151 @@
+use crate::tui::widgets::ChatMessage;
+
+use super::AppState;
+
+impl AppState {
+    #[must_use]
+    pub const fn is_selecting(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    pub const fn start_selection(&mut self) -> bool {
+        let Some(last) = self.messages.len().checked_sub(1) else {
+            return false;
+        };
+        self.selection = Some(last);
+        self.scroll.request_jump();
+        true
+    }
+
+    pub const fn exit_selection(&mut self) {
+        self.selection = None;
+    }
+
+    pub const fn select_prev(&mut self) {
+        if let Some(index) = &mut self.selection {
+            *index = index.saturating_sub(1);
+            self.scroll.request_jump();
+        }
+    }
+
+    pub const fn select_next(&mut self) {
+        if let Some(index) = &mut self.selection {
+            if *index + 1 < self.messages.len() {
+                *index += 1;
+            }
+            self.scroll.request_jump();
+        }
+    }
+
+    #[must_use]
+    pub fn selected_message(&self) -> Option<&ChatMessage> {
+        self.messages.get(self.selection?)
+    }
+
+    pub fn toggle_collapse_selected(&mut self) {
+        let Some(index) = self.selection else {
+            return;
+        };
+        match self.messages.get_mut(index) {
+            Some(
+                ChatMessage::FileDiff { collapsed, .. }
+                | ChatMessage::DiffSummary { collapsed, .. },
+            ) => *collapsed = !*collapsed,
+            Some(_) if !self.collapsed.remove(&index) => {
+                self.collapsed.insert(index);
+            }
+            Some(_) | None => {}
+        }
+    }
+
+    pub fn forget_selected(&mut self) -> Option<usize> {
+        let index = self.selection?;
+        let is_user = |message: &ChatMessage| matches!(message, ChatMessage::User(_));
+        let turn_start = self.messages[..=index].iter().rposition(is_user);
+        let (start, end) = match turn_start {
+            Some(start) => {
+                let end = self.messages[index + 1..]
+                    .iter()
+                    .position(is_user)
+                    .map_or(self.messages.len(), |offset| index + 1 + offset);
+                (start, end)
+            }
+            None => (index, index + 1),
+        };
+        let turns_back =
+            turn_start.map(|_| self.messages[end..].iter().filter(|m| is_user(m)).count());
+
+        self.messages.drain(start..end);
+        let removed = end - start;
+        self.collapsed = self
+            .collapsed
+            .iter()
+            .filter(|&&i| i < start || i >= end)
+            .map(|&i| if i >= end { i - removed } else { i })
+            .collect();
+        self.search = None;
+        self.selection = self
+            .messages
+            .len()
+            .checked_sub(1)
+            .map(|last| start.min(last));
+        turns_back
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::output_widget::MessageLevel;
+
+    fn state() -> AppState {
+        let mut state = AppState::new();
+        state.add_system_message("welcome".to_string());
+        state.add_user_message("first".to_string());
+        state.add_assistant_message("one".to_string(), None);
+        state.add_user_message("second".to_string());
+        state.add_assistant_message("two".to_string(), None);
+        state
+    }
+
+    #[test]
+    fn test_selection_moves_within_bounds() {
+        let mut state = AppState::new();
+        assert!(!state.start_selection());
+
+        let mut state = self::state();
+        assert!(state.start_selection());
+        state.select_next();
+        assert_eq!(state.selection, Some(4));
+        (0..10).for_each(|_| state.select_prev());
+        assert!(matches!(
+            state.selected_message(),
+            Some(ChatMessage::System {
+                level: MessageLevel::Info,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_forget_selected_removes_whole_turn() {
+        let mut state = state();
+        state.start_selection();
+        state.collapsed.insert(4);
+        state.collapsed.insert(0);
+        state.select_prev();
+        state.select_prev();
+        state.select_prev();
+
+        assert_eq!(state.forget_selected(), Some(1));
+
+        assert_eq!(state.messages.len(), 3);
+        assert!(matches!(&state.messages[1], ChatMessage::User(text) if text == "second"));
+        assert_eq!(state.selection, Some(1));
+        assert!(state.collapsed.contains(&2) && state.collapsed.contains(&0));
+
+        state.selection = Some(0);
+        assert_eq!(state.forget_selected(), None);
+        assert_eq!(state.messages.len(), 2);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::core::error::Result;
+use crate::core::types::{ContentBlock, Role};
+use crate::session::{SessionRecord, SessionStore};
+use crate::tui::widgets::ScrollState;
+
+use super::{AppState, SessionBrowserModal};
+
+impl AppState {
+    #[must_use]
+    pub const fn has_session_browser(&self) -> bool {
+        self.session_browser.is_some()
+    }
+
+    pub fn show_session_browser(&mut self, store: SessionStore) -> Result<()> {
+        self.session_browser = Some(SessionBrowserModal::new(store)?);
+        Ok(())
+    }
+
+    pub fn close_session_browser(&mut self) {
+        self.session_browser = None;
+    }
+
+    pub fn restore_session(&mut self, record: &SessionRecord) {
+        self.messages.clear();
+        self.scroll = ScrollState::new();
+        self.search = None;
+        self.editing = None;
+        self.selection = None;
+        self.collapsed.clear();
+
+        for message in &record.messages {
+            let text: Vec<&str> = message
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text } if !text.trim().is_empty() => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if text.is_empty() {
+                continue;
+            }
+            match message.role {
+                Role::User => self.add_user_message(text.join("\n\n")),
+                Role::Assistant => self.add_assistant_message(text.join("\n\n"), None),
+                Role::Tool | Role::System => {}
+            }
+        }
+
+        self.session_title.clone_from(&record.title);
+        self.session_usage = record.usage;
+        self.last_usage = None;
+    }
+}
@@ -1,7 +1,77 @@
+use std::time::{Duration, Instant};
+
 use super::AppState;
+use crate::core::memory::estimate_tokens;
+
+const STALL_AFTER: Duration = Duration::from_secs(5);
+const MIN_RATE_WINDOW: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Clone)]
+pub struct StreamStats {
+    first_chunk: Option<Instant>,
+    last_chunk: Option<Instant>,
+    bytes: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StreamProgress {
+    pub elapsed: Option<Duration>,
+    pub tokens_per_sec: Option<f64>,
+    pub eta: Option<Duration>,
+    pub stalled: Option<Duration>,
+}
+
+fn token_count(tokens: usize) -> f64 {
+    f64::from(u32::try_from(tokens).unwrap_or(u32::MAX))
+}
+
+impl StreamStats {
+    pub fn record(&mut self, chunk: &str, now: Instant) {
+        self.first_chunk.get_or_insert(now);
+        self.last_chunk = Some(now);
+        self.bytes += chunk.len();
+    }
+
+    #[must_use]
+    pub const fn tokens(&self) -> usize {
+        estimate_tokens(self.bytes)
+    }
+
+    #[must_use]
+    pub fn tokens_per_sec(&self) -> Option<f64> {
+        let window = self.last_chunk?.duration_since(self.first_chunk?);
+        if window < MIN_RATE_WINDOW {
+            return None;
+        }
+        Some(token_count(self.tokens()) / window.as_secs_f64())
+    }
+
+    #[must_use]
+    pub fn progress(
+        &self,
+        elapsed: Option<Duration>,
+        expected_tokens: u32,
+        now: Instant,
+    ) -> StreamProgress {
+        let tokens_per_sec = self.tokens_per_sec().filter(|rate| *rate > 0.0);
+        let remaining = (expected_tokens as usize).saturating_sub(self.tokens());
+        StreamProgress {
+            elapsed,
+            tokens_per_sec,
+            eta: tokens_per_sec
+                .filter(|_| remaining > 0)
+                .map(|rate| Duration::from_secs_f64(token_count(remaining) / rate)),
+            stalled: self
+                .last_chunk
+                .map(|last| now.duration_since(last))
+                .filter(|idle| *idle >= STALL_AFTER),
+        }
+    }
+}
 
 impl AppState {
     pub fn append_streaming(&mut self, chunk: &str) {
+        self.stream_stats.record(chunk, Instant::now());
         match &mut self.streaming_response {
             Some(existing) => existing.push_str(chunk),
             None => self.streaming_response = Some(chunk.to_string()),
@@ -20,4 +90,36 @@ impl AppState {
     pub const fn is_streaming(&self) -> bool {
         self.streaming_response.is_some()
     }
+
+    #[must_use]
+    pub fn stream_progress(&self) -> StreamProgress {
+        let expected = self.last_usage.map_or(0, |usage| usage.output_tokens);
+        self.stream_stats
+            .progress(self.elapsed(), expected, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_progress_rate_eta_and_stall() {
+        let start = Instant::now();
+        let mut stats = StreamStats::default();
+        stats.record(&"x".repeat(40), start);
+        assert_eq!(stats.tokens_per_sec(), None);
+
+        stats.record(&"x".repeat(40), start + Duration::from_secs(2));
+        assert_eq!(stats.tokens(), 20);
+        assert_eq!(stats.tokens_per_sec(), Some(10.0));
+
+        let progress = stats.progress(None, 50, start + Duration::from_secs(3));
+        assert_eq!(progress.eta, Some(Duration::from_secs(3)));
+        assert_eq!(progress.stalled, None);
+
+        let progress = stats.progress(None, 10, start + Duration::from_secs(9));
+        assert_eq!(progress.eta, None);
+        assert_eq!(progress.stalled, Some(Duration::from_secs(7)));
+    }
 }
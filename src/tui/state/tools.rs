@@ -1,6 +1,7 @@
 use std::time::Instant;
 
 use crate::core::metadata;
+use crate::shell::ShellRun;
 use crate::tools::result::ToolResult;
 use crate::tools::{ToolState, ToolType};
 use crate::tui::widgets::ChatMessage;
@@ -44,6 +45,29 @@ impl AppState {
         self.scroll.reset_manual_scroll();
     }
 
+    pub fn add_shell_run(&mut self, run: &ShellRun) {
+        let status = run.status();
+        let output = if run.output.trim().is_empty() {
+            format!("({status}, no output)")
+        } else if run.success() {
+            run.output.trim_end().to_string()
+        } else {
+            format!("{}\n({status})", run.output.trim_end())
+        };
+        self.messages.push(ChatMessage::ToolExecution {
+            tool_type: ToolType::Bash,
+            input: run.command.clone(),
+            output: Some(output),
+            elapsed: Some(run.elapsed),
+            state: if run.success() {
+                ToolState::Success
+            } else {
+                ToolState::Error
+            },
+        });
+        self.scroll.reset_manual_scroll();
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     pub fn fail_tool(&mut self, name: &str, error: String) {
         let Some(execution) = self.active_tools.remove(name) else {
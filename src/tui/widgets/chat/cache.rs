@@ -0,0 +1,95 @@
+use ratatui::text::Line;
+
+use super::ChatMessage;
+use crate::ui::markdown::Link;
+use crate::ui::theme::{ThemeName, current_theme};
+
+#[derive(Debug, Default)]
+pub struct LineCache {
+    width: u16,
+    theme: Option<ThemeName>,
+    entries: Vec<Option<Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: u64,
+    lines: Vec<Line<'static>>,
+    links: Vec<Link>,
+}
+
+impl LineCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prepare(&mut self, width: u16, messages: usize) {
+        let theme = current_theme();
+        if self.width != width || self.theme != Some(theme) {
+            self.entries.clear();
+            self.width = width;
+            self.theme = Some(theme);
+        }
+        self.entries.resize_with(messages, || None);
+    }
+
+    pub fn fill(&mut self, index: usize, message: &ChatMessage, spinner_frame: usize) {
+        let key = message.fingerprint(spinner_frame);
+        let Some(entry) = self.entries.get_mut(index) else {
+            return;
+        };
+        if entry.as_ref().is_none_or(|cached| cached.key != key) {
+            *entry = Some(Entry {
+                key,
+                lines: message.render_to_lines(self.width, spinner_frame),
+                links: message.links(),
+            });
+        }
+    }
+
+    #[must_use]
+    pub fn lines(&self, index: usize) -> &[Line<'static>] {
+        self.entries
+            .get(index)
+            .and_then(Option::as_ref)
+            .map_or(&[], |entry| &entry.lines)
+    }
+
+    #[must_use]
+    pub fn links(&self, index: usize) -> &[Link] {
+        self.entries
+            .get(index)
+            .and_then(Option::as_ref)
+            .map_or(&[], |entry| &entry.links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_rerenders_only_changed_messages() {
+        let mut cache = LineCache::new();
+        let long = ChatMessage::Assistant {
+            text: "line\n\n".repeat(200),
+            model: None,
+        };
+        cache.prepare(80, 2);
+        cache.fill(0, &long, 0);
+        cache.fill(1, &ChatMessage::StreamingAssistant("Hel".to_string()), 0);
+        let first = cache.lines(0).as_ptr();
+        assert!(cache.lines(0).len() >= 200);
+
+        cache.prepare(80, 2);
+        cache.fill(0, &long, 1);
+        cache.fill(1, &ChatMessage::StreamingAssistant("Hello".to_string()), 1);
+        assert_eq!(cache.lines(0).as_ptr(), first);
+        assert!(cache.lines(1)[0].to_string().contains("Hello"));
+
+        cache.prepare(40, 1);
+        assert!(cache.lines(0).is_empty());
+        assert!(cache.lines(1).is_empty());
+    }
+}
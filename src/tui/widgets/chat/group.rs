@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use ratatui::text::{Line, Span};
+
+use super::ChatMessage;
+use crate::tools::ToolState;
+use crate::ui::theme::Theme;
+
+pub const MIN_GROUP_SIZE: usize = 2;
+
+const fn is_groupable(message: &ChatMessage) -> bool {
+    matches!(
+        message,
+        ChatMessage::ToolExecution {
+            state: ToolState::Success | ToolState::Error,
+            ..
+        }
+    )
+}
+
+#[must_use]
+pub fn group_end(messages: &[ChatMessage], start: usize) -> usize {
+    messages[start..]
+        .iter()
+        .position(|message| !is_groupable(message))
+        .map_or(messages.len(), |offset| start + offset)
+}
+
+#[must_use]
+pub fn render_group_summary(messages: &[ChatMessage]) -> Vec<Line<'static>> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    let mut failed = 0;
+    let mut total_elapsed = Duration::ZERO;
+
+    for message in messages {
+        let ChatMessage::ToolExecution {
+            tool_type,
+            elapsed,
+            state,
+            ..
+        } = message
+        else {
+            continue;
+        };
+
+        match counts
+            .iter_mut()
+            .find(|(name, _)| *name == tool_type.name())
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((tool_type.name(), 1)),
+        }
+        if *state == ToolState::Error {
+            failed += 1;
+        }
+        total_elapsed += elapsed.unwrap_or_default();
+    }
+
+    let breakdown = counts
+        .iter()
+        .map(|(name, count)| {
+            if *count == 1 {
+                (*name).to_string()
+            } else {
+                format!("{name} ×{count}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut spans = vec![
+        Span::styled("▸ ", Theme::primary()),
+        Span::styled(
+            format!("{} tools executed", messages.len()),
+            Theme::primary_bold(),
+        ),
+        Span::styled(format!(" · {breakdown}"), Theme::off_white()),
+    ];
+    if failed > 0 {
+        spans.push(Span::styled(format!(" · {failed} failed"), Theme::error()));
+    }
+    spans.push(Span::styled(
+        format!(" · {:.2}s", total_elapsed.as_secs_f64()),
+        Theme::muted(),
+    ));
+    spans.push(Span::styled("  (Ctrl+O to expand)", Theme::muted()));
+
+    vec![Line::from(spans)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolType;
+
+    fn tool(tool_type: ToolType, state: ToolState) -> ChatMessage {
+        ChatMessage::ToolExecution {
+            tool_type,
+            input: String::new(),
+            output: None,
+            elapsed: Some(Duration::from_millis(500)),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_group_end_stops_at_other_messages() {
+        let messages = vec![
+            ChatMessage::User("explore".to_string()),
+            tool(ToolType::ReadFile, ToolState::Success),
+            tool(ToolType::Grep, ToolState::Success),
+            ChatMessage::Assistant {
+                text: "done".to_string(),
+                model: None,
+            },
+            tool(ToolType::Bash, ToolState::InProgress),
+        ];
+
+        assert_eq!(group_end(&messages, 0), 0);
+        assert_eq!(group_end(&messages, 1), 3);
+        assert_eq!(group_end(&messages, 4), 4);
+    }
+
+    #[test]
+    fn test_summary_counts_tools_and_failures() {
+        let messages = vec![
+            tool(ToolType::ReadFile, ToolState::Success),
+            tool(ToolType::Grep, ToolState::Error),
+            tool(ToolType::ReadFile, ToolState::Success),
+        ];
+
+        let lines = render_group_summary(&messages);
+        let text: String = lines[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+
+        assert!(text.contains("3 tools executed"));
+        assert!(text.contains("read_file ×2, grep"));
+        assert!(text.contains("1 failed"));
+        assert!(text.contains("1.50s"));
+    }
+}
@@ -1,12 +1,71 @@
+use std::fmt::{self, Write as _};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::mem::discriminant;
 use std::time::Duration;
 
+use crate::core::llm::LLM;
+use crate::git::Finding;
 use crate::tools::{ToolState, ToolType};
+use crate::ui::diff::{DiffWidget, unified_diff};
+use crate::ui::markdown::{Link, extract_links};
 use crate::ui::output_widget::MessageLevel;
 
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: String,
+    pub old_content: String,
+    pub new_content: String,
+}
+
+impl FileChange {
+    #[must_use]
+    pub fn diff(&self) -> DiffWidget {
+        DiffWidget::new(&self.path, &self.old_content, &self.new_content)
+    }
+}
+
+#[must_use]
+pub fn summarize_changes(files: &[FileChange]) -> String {
+    let (additions, deletions) = files.iter().fold((0, 0), |(a, d), file| {
+        let diff = file.diff();
+        (a + diff.additions(), d + diff.deletions())
+    });
+    let noun = if files.len() == 1 { "file" } else { "files" };
+    format!(
+        "{} {noun} changed, +{additions} \u{2212}{deletions}",
+        files.len()
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelTag {
+    pub provider: String,
+    pub model: String,
+}
+
+impl ModelTag {
+    #[must_use]
+    pub fn of(llm: &dyn LLM) -> Self {
+        Self {
+            provider: llm.name().to_string(),
+            model: llm.model().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ModelTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.provider, self.model)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ChatMessage {
     User(String),
-    Assistant(String),
+    Assistant {
+        text: String,
+        model: Option<ModelTag>,
+    },
     System {
         text: String,
         level: MessageLevel,
@@ -23,6 +82,173 @@ pub enum ChatMessage {
         path: String,
         old_content: String,
         new_content: String,
+        format: Option<String>,
         collapsed: bool,
     },
+    DiffSummary {
+        files: Vec<FileChange>,
+        collapsed: bool,
+    },
+    Review {
+        title: String,
+        findings: Vec<Finding>,
+    },
+}
+
+impl ChatMessage {
+    #[must_use]
+    pub fn contains_ignore_case(&self, needle: &str) -> bool {
+        let matches = |text: &str| text.to_ascii_lowercase().contains(needle);
+        match self {
+            Self::User(text)
+            | Self::Assistant { text, .. }
+            | Self::StreamingAssistant(text)
+            | Self::System { text, .. } => matches(text),
+            Self::ToolExecution { input, output, .. } => {
+                matches(input) || output.as_deref().is_some_and(matches)
+            }
+            Self::FileDiff {
+                path,
+                old_content,
+                new_content,
+                ..
+            } => matches(path) || matches(old_content) || matches(new_content),
+            Self::DiffSummary { files, .. } => files.iter().any(|file| {
+                matches(&file.path) || matches(&file.old_content) || matches(&file.new_content)
+            }),
+            Self::Review { title, findings } => {
+                matches(title)
+                    || findings
+                        .iter()
+                        .any(|f| matches(&f.path) || matches(&f.message))
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn links(&self) -> Vec<Link> {
+        match self {
+            Self::Assistant { text, .. } | Self::StreamingAssistant(text) => extract_links(text),
+            _ => Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        match self {
+            Self::User(text)
+            | Self::Assistant { text, .. }
+            | Self::StreamingAssistant(text)
+            | Self::System { text, .. } => text.clone(),
+            Self::ToolExecution {
+                tool_type,
+                input,
+                output,
+                ..
+            } => {
+                let mut text = format!("{} {input}", tool_type.name());
+                if let Some(output) = output {
+                    let _ = write!(text, "\n{output}");
+                }
+                text
+            }
+            Self::FileDiff {
+                path,
+                old_content,
+                new_content,
+                ..
+            } => unified_diff(path, old_content, new_content),
+            Self::DiffSummary { files, .. } => {
+                let mut text = summarize_changes(files);
+                for file in files {
+                    text.push('\n');
+                    text.push_str(&unified_diff(
+                        &file.path,
+                        &file.old_content,
+                        &file.new_content,
+                    ));
+                }
+                text
+            }
+            Self::Review { title, findings } => {
+                let mut text = format!("Review of {title}");
+                for finding in findings {
+                    let _ = write!(
+                        text,
+                        "\n{} {}{} - {}",
+                        format!("{:?}", finding.severity).to_ascii_uppercase(),
+                        finding.path,
+                        finding
+                            .line
+                            .map(|line| format!(":{line}"))
+                            .unwrap_or_default(),
+                        finding.message
+                    );
+                }
+                text
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn fingerprint(&self, spinner_frame: usize) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        discriminant(self).hash(&mut hasher);
+        match self {
+            Self::User(text) | Self::Assistant { text, .. } | Self::StreamingAssistant(text) => {
+                text.hash(&mut hasher);
+            }
+            Self::System { text, level } => {
+                text.hash(&mut hasher);
+                discriminant(level).hash(&mut hasher);
+            }
+            Self::ToolExecution {
+                tool_type,
+                input,
+                output,
+                elapsed,
+                state,
+            } => {
+                tool_type.name().hash(&mut hasher);
+                input.hash(&mut hasher);
+                output.hash(&mut hasher);
+                elapsed.hash(&mut hasher);
+                discriminant(state).hash(&mut hasher);
+                if matches!(state, ToolState::Starting | ToolState::InProgress) {
+                    spinner_frame.hash(&mut hasher);
+                }
+            }
+            Self::FileDiff {
+                path,
+                old_content,
+                new_content,
+                format,
+                collapsed,
+            } => {
+                path.hash(&mut hasher);
+                old_content.hash(&mut hasher);
+                new_content.hash(&mut hasher);
+                format.hash(&mut hasher);
+                collapsed.hash(&mut hasher);
+            }
+            Self::DiffSummary { files, collapsed } => {
+                for file in files {
+                    file.path.hash(&mut hasher);
+                    file.old_content.hash(&mut hasher);
+                    file.new_content.hash(&mut hasher);
+                }
+                collapsed.hash(&mut hasher);
+            }
+            Self::Review { title, findings } => {
+                title.hash(&mut hasher);
+                for finding in findings {
+                    finding.path.hash(&mut hasher);
+                    finding.line.hash(&mut hasher);
+                    finding.message.hash(&mut hasher);
+                    discriminant(&finding.severity).hash(&mut hasher);
+                }
+            }
+        }
+        hasher.finish()
+    }
 }
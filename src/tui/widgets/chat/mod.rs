@@ -1,21 +1,36 @@
+mod cache;
+mod group;
 mod message;
 mod render;
 mod scroll_state;
+mod search;
 
-pub use message::ChatMessage;
+pub use cache::LineCache;
+pub use message::{ChatMessage, FileChange, ModelTag};
 pub use scroll_state::ScrollState;
+pub use search::SearchState;
+
+use std::collections::HashSet;
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget};
 
-use crate::ui::theme::Theme;
+use crate::ui::hyperlink;
+use crate::ui::theme::{BoxChars, Theme};
 
 pub struct ChatWidget<'a> {
     messages: &'a [ChatMessage],
     scroll: &'a mut ScrollState,
+    cache: &'a mut LineCache,
     spinner_frame: usize,
+    search: Option<&'a SearchState>,
+    editing: Option<usize>,
+    selected: Option<usize>,
+    collapsed: Option<&'a HashSet<usize>>,
+    expand_tool_groups: bool,
+    model_badges: bool,
 }
 
 impl<'a> ChatWidget<'a> {
@@ -23,67 +38,228 @@ impl<'a> ChatWidget<'a> {
     pub const fn new(
         messages: &'a [ChatMessage],
         scroll: &'a mut ScrollState,
+        cache: &'a mut LineCache,
         spinner_frame: usize,
     ) -> Self {
         Self {
             messages,
             scroll,
+            cache,
             spinner_frame,
+            search: None,
+            editing: None,
+            selected: None,
+            collapsed: None,
+            expand_tool_groups: false,
+            model_badges: false,
         }
     }
 
-    pub fn render(self, area: Rect, buf: &mut Buffer) {
+    #[must_use]
+    pub const fn search(mut self, search: Option<&'a SearchState>) -> Self {
+        self.search = search;
+        self
+    }
+
+    #[must_use]
+    pub const fn editing(mut self, editing: Option<usize>) -> Self {
+        self.editing = editing;
+        self
+    }
+
+    #[must_use]
+    pub const fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    #[must_use]
+    pub const fn collapsed(mut self, collapsed: &'a HashSet<usize>) -> Self {
+        self.collapsed = Some(collapsed);
+        self
+    }
+
+    #[must_use]
+    pub const fn expand_tool_groups(mut self, expand: bool) -> Self {
+        self.expand_tool_groups = expand;
+        self
+    }
+
+    #[must_use]
+    pub const fn model_badges(mut self, enabled: bool) -> Self {
+        self.model_badges = enabled;
+        self
+    }
+
+    pub fn render(mut self, area: Rect, buf: &mut Buffer) {
         if self.messages.is_empty() {
             Self::render_empty_state(area, buf);
             return;
         }
 
         let content_width = area.width.saturating_sub(4);
-        let all_lines = self.collect_all_lines(content_width);
+        let segments = self.layout(content_width);
+        let hit_line = self.hit_line(&segments);
 
-        self.update_scroll_and_render(area, buf, &all_lines, content_width);
+        self.update_scroll_and_render(area, buf, &segments, hit_line, content_width);
     }
 
-    fn collect_all_lines(&self, width: u16) -> Vec<Line<'static>> {
-        let mut lines = Vec::new();
+    fn focused(&self) -> Option<usize> {
+        self.editing.or(self.selected)
+    }
+
+    fn current_hit(&self) -> Option<usize> {
+        self.focused()
+            .or_else(|| self.search.and_then(SearchState::current_message))
+    }
+
+    fn layout(&mut self, width: u16) -> Vec<Segment> {
+        let current_hit = self.current_hit();
+        self.cache.prepare(width, self.messages.len());
+
+        let mut segments = Vec::new();
+        let mut idx = 0;
+        while idx < self.messages.len() {
+            let end = group::group_end(self.messages, idx);
+
+            if !self.expand_tool_groups
+                && end - idx >= group::MIN_GROUP_SIZE
+                && !current_hit.is_some_and(|hit| (idx..end).contains(&hit))
+            {
+                segments.push(Segment::Plain(group::render_group_summary(
+                    &self.messages[idx..end],
+                )));
+                idx = end;
+            } else {
+                self.cache
+                    .fill(idx, &self.messages[idx], self.spinner_frame);
+                if self.model_badges
+                    && let ChatMessage::Assistant {
+                        model: Some(model), ..
+                    } = &self.messages[idx]
+                {
+                    segments.push(Segment::Plain(vec![model_badge(model)]));
+                }
+                if self
+                    .collapsed
+                    .is_some_and(|collapsed| collapsed.contains(&idx))
+                {
+                    segments.push(Segment::Collapsed(
+                        idx,
+                        collapse_lines(self.cache.lines(idx)),
+                    ));
+                } else {
+                    segments.push(Segment::Message(idx));
+                }
+                idx += 1;
+            }
+
+            if idx < self.messages.len() {
+                segments.push(Segment::Plain(vec![Line::from("")]));
+            }
+        }
 
-        for (idx, message) in self.messages.iter().enumerate() {
-            lines.extend(message.render_to_lines(width, self.spinner_frame));
+        segments
+    }
 
-            if idx < self.messages.len() - 1 {
-                lines.push(Line::from(""));
+    fn hit_line(&self, segments: &[Segment]) -> Option<usize> {
+        let current_hit = self.current_hit()?;
+        let mut offset = 0;
+        for segment in segments {
+            let lines = segment.lines(self.cache);
+            if segment.message() == Some(current_hit) {
+                let found = self
+                    .search
+                    .filter(|_| self.focused().is_none())
+                    .and_then(|search| {
+                        lines
+                            .iter()
+                            .position(|line| search.highlight_line(line.clone(), true).1)
+                    });
+                return Some(offset + found.unwrap_or(0));
             }
+            offset += lines.len();
         }
+        None
+    }
 
-        lines
+    fn styled_line(&self, line: &Line<'static>, message: Option<usize>) -> Line<'static> {
+        let Some(idx) = message else {
+            return line.clone();
+        };
+        match self.search {
+            _ if self.focused() == Some(idx) => line.clone().patch_style(Theme::search_match()),
+            Some(search) => {
+                search
+                    .highlight_line(line.clone(), self.current_hit() == Some(idx))
+                    .0
+            }
+            None => line.clone(),
+        }
     }
 
     fn update_scroll_and_render(
         self,
         area: Rect,
         buf: &mut Buffer,
-        lines: &[Line<'static>],
+        segments: &[Segment],
+        hit_line: Option<usize>,
         content_width: u16,
     ) {
-        let total_lines = lines.len();
+        let total_lines = segments
+            .iter()
+            .map(|segment| segment.lines(self.cache).len())
+            .sum();
         let viewport_height = area.height as usize;
 
         self.scroll.update(total_lines, viewport_height);
 
+        if self.scroll.take_jump()
+            && let Some(line) = hit_line
+        {
+            self.scroll.scroll_to_line(line);
+        }
+
         if !self.scroll.is_manual_scroll() {
             self.scroll.scroll_to_bottom();
         }
 
         let offset = self.scroll.position();
-        let end = (offset + viewport_height).min(total_lines);
-
-        for (i, line) in lines[offset..end].iter().enumerate() {
-            buf.set_line(area.x + 2, area.y + i as u16, line, content_width);
+        let mut row = 0;
+        let mut start = 0;
+        for segment in segments {
+            if row >= viewport_height {
+                break;
+            }
+            let lines = segment.lines(self.cache);
+            let skip = offset.saturating_sub(start);
+            start += lines.len();
+            let hyperlinks = segment
+                .message()
+                .filter(|_| hyperlink::enabled())
+                .map_or(&[][..], |idx| self.cache.links(idx));
+            for line in lines.iter().skip(skip).take(viewport_height - row) {
+                let line = self.styled_line(line, segment.message());
+                let y = area.y + row as u16;
+                buf.set_line(area.x + 2, y, &line, content_width);
+                if !hyperlinks.is_empty() {
+                    hyperlink::apply(buf, Rect::new(area.x + 2, y, content_width, 1), hyperlinks);
+                }
+                row += 1;
+            }
         }
 
         if !self.scroll.is_at_bottom() {
             Self::render_scroll_indicator(area, buf);
         }
+
+        if self.editing.is_some() {
+            Self::render_indicator(area, buf, Self::editing_indicator());
+        } else if self.selected.is_some() {
+            Self::render_indicator(area, buf, Self::selection_indicator());
+        } else if let Some(search) = self.search {
+            Self::render_indicator(area, buf, search.indicator());
+        }
     }
 
     fn render_empty_state(area: Rect, buf: &mut Buffer) {
@@ -117,4 +293,123 @@ impl<'a> ChatWidget<'a> {
         let indicator = Line::from(Span::styled("↓ More", Theme::warning()));
         Paragraph::new(indicator).render(indicator_area, buf);
     }
+
+    fn editing_indicator() -> Line<'static> {
+        Line::from(Span::styled(
+            " editing · Alt+↑/↓ · Enter: branch from here · Esc ",
+            Theme::warning(),
+        ))
+    }
+
+    fn selection_indicator() -> Line<'static> {
+        Line::from(Span::styled(
+            " select · ↑/↓ · y: copy · Space: fold · r: re-run · a: apply · p: pager · e: edit file · d: forget · Esc ",
+            Theme::warning(),
+        ))
+    }
+
+    fn render_indicator(area: Rect, buf: &mut Buffer, indicator: Line<'static>) {
+        let width = u16::try_from(indicator.width())
+            .unwrap_or(u16::MAX)
+            .min(area.width);
+        let indicator_area = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height: 1,
+        };
+
+        Paragraph::new(indicator).render(indicator_area, buf);
+    }
+}
+
+enum Segment {
+    Message(usize),
+    Collapsed(usize, Vec<Line<'static>>),
+    Plain(Vec<Line<'static>>),
+}
+
+impl Segment {
+    const fn message(&self) -> Option<usize> {
+        match self {
+            Self::Message(idx) | Self::Collapsed(idx, _) => Some(*idx),
+            Self::Plain(_) => None,
+        }
+    }
+
+    fn lines<'c>(&'c self, cache: &'c LineCache) -> &'c [Line<'static>] {
+        match self {
+            Self::Message(idx) => cache.lines(*idx),
+            Self::Collapsed(_, lines) | Self::Plain(lines) => lines,
+        }
+    }
+}
+
+fn model_badge(model: &ModelTag) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("{} {model}", BoxChars::DOT),
+        Theme::muted(),
+    ))
+}
+
+fn collapse_lines(lines: &[Line<'static>]) -> Vec<Line<'static>> {
+    if lines.len() <= 1 {
+        return lines.to_vec();
+    }
+    vec![
+        lines[0].clone(),
+        Line::from(Span::styled(
+            format!("  … {} more lines", lines.len() - 1),
+            Theme::muted(),
+        )),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered(badges: bool) -> Vec<String> {
+        let messages = [
+            ChatMessage::User("hi".to_string()),
+            ChatMessage::Assistant {
+                text: "hello".to_string(),
+                model: Some(ModelTag {
+                    provider: "anthropic".to_string(),
+                    model: "claude-sonnet-4-5".to_string(),
+                }),
+            },
+        ];
+        let mut scroll = ScrollState::default();
+        let mut cache = LineCache::new();
+        let area = Rect::new(0, 0, 50, 6);
+        let mut buf = Buffer::empty(area);
+        ChatWidget::new(&messages, &mut scroll, &mut cache, 0)
+            .model_badges(badges)
+            .render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_model_badge_precedes_tagged_reply() {
+        let lines = rendered(true);
+        let badge = lines
+            .iter()
+            .position(|line| line.contains("anthropic/claude-sonnet-4-5"))
+            .unwrap();
+        assert!(lines[badge + 1].contains("hello"));
+        assert!(
+            !rendered(false)
+                .iter()
+                .any(|line| line.contains("anthropic"))
+        );
+    }
 }
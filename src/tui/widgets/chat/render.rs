@@ -1,8 +1,12 @@
 use std::time::Duration;
 
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
 use super::ChatMessage;
+use super::message::{FileChange, summarize_changes};
+use crate::config::Severity;
+use crate::git::Finding;
 use crate::tools::{ToolState, ToolType};
 use crate::ui::diff::DiffWidget;
 use crate::ui::markdown::MarkdownWidget;
@@ -10,14 +14,12 @@ use crate::ui::output_widget::MessageLevel;
 use crate::ui::theme::Theme;
 use crate::ui::tool_card::ToolCard;
 
-const MAX_MESSAGE_LINES: usize = 50;
-
 impl ChatMessage {
     #[must_use]
     pub fn render_to_lines(&self, width: u16, spinner_frame: usize) -> Vec<Line<'static>> {
         match self {
             Self::User(text) => render_user(text, width),
-            Self::Assistant(text) => render_assistant(text, width, false),
+            Self::Assistant { text, .. } => render_assistant(text, width, false),
             Self::StreamingAssistant(text) => render_assistant(text, width, true),
             Self::System { text, level } => render_system(text, *level),
             Self::ToolExecution {
@@ -39,8 +41,18 @@ impl ChatMessage {
                 path,
                 old_content,
                 new_content,
+                format,
                 collapsed,
-            } => render_diff(path, old_content, new_content, *collapsed, width),
+            } => render_diff(
+                path,
+                old_content,
+                new_content,
+                format.as_deref(),
+                *collapsed,
+                width,
+            ),
+            Self::DiffSummary { files, collapsed } => render_diff_summary(files, *collapsed, width),
+            Self::Review { title, findings } => render_review(title, findings, width),
         }
     }
 }
@@ -75,8 +87,6 @@ fn render_assistant(text: &str, width: u16, streaming: bool) -> Vec<Line<'static
 
     if streaming {
         append_cursor(&mut lines);
-    } else {
-        truncate_with_indicator(&mut lines);
     }
 
     lines
@@ -121,14 +131,92 @@ fn render_diff(
     path: &str,
     old_content: &str,
     new_content: &str,
+    format: Option<&str>,
     collapsed: bool,
     width: u16,
 ) -> Vec<Line<'static>> {
     DiffWidget::new(path, old_content, new_content)
+        .with_format(format)
         .collapsed(collapsed)
         .render_to_lines(width)
 }
 
+fn render_diff_summary(files: &[FileChange], collapsed: bool, width: u16) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled("● ", Theme::primary()),
+        Span::styled(summarize_changes(files), Theme::primary_bold()),
+    ])];
+    for file in files {
+        let diff = file.diff();
+        if collapsed {
+            let change_type = diff.change_type();
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", change_type.icon()), change_type.style()),
+                Span::styled(file.path.clone(), Theme::white()),
+                Span::styled(format!("  +{}", diff.additions()), Theme::success()),
+                Span::styled(format!(" \u{2212}{}", diff.deletions()), Theme::error()),
+            ]));
+        } else {
+            lines.extend(diff.render_to_lines(width));
+        }
+    }
+    lines
+}
+
+fn severity_label(severity: Severity) -> (&'static str, Style) {
+    match severity {
+        Severity::High => ("high", Theme::error()),
+        Severity::Medium => ("med ", Theme::warning()),
+        Severity::Low => ("low ", Theme::muted()),
+    }
+}
+
+fn render_review(title: &str, findings: &[Finding], width: u16) -> Vec<Line<'static>> {
+    let count = match findings.len() {
+        0 => "no findings".to_string(),
+        1 => "1 finding".to_string(),
+        n => format!("{n} findings"),
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("● ", Theme::primary()),
+        Span::styled(format!("Review of {title}: "), Theme::primary_bold()),
+        Span::styled(count, Theme::muted()),
+    ])];
+
+    let mut current_path: Option<&str> = None;
+    for finding in findings {
+        if current_path != Some(finding.path.as_str()) {
+            current_path = Some(&finding.path);
+            lines.push(Line::from(Span::styled(
+                format!("  {}", finding.path),
+                Theme::white().add_modifier(Modifier::BOLD),
+            )));
+        }
+        let (label, style) = severity_label(finding.severity);
+        let location = finding
+            .line
+            .map_or_else(|| "     ".to_string(), |line| format!("{line:>5}"));
+        let indent = 4 + label.len() + location.len() + 2;
+        let wrapped = textwrap::wrap(&finding.message, (width as usize).saturating_sub(indent));
+        for (i, text) in wrapped.iter().enumerate() {
+            let prefix = if i == 0 {
+                vec![
+                    Span::raw("    "),
+                    Span::styled(label, style),
+                    Span::styled(location.clone(), Theme::muted()),
+                    Span::raw("  "),
+                ]
+            } else {
+                vec![Span::raw(" ".repeat(indent))]
+            };
+            let mut spans = prefix;
+            spans.push(Span::styled(text.to_string(), Theme::off_white()));
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
+}
+
 fn render_markdown_with_prefix(text: &str, width: u16) -> Vec<Line<'static>> {
     if is_simple_text(text) {
         return render_plaintext_fallback(text, width);
@@ -154,6 +242,10 @@ fn is_simple_text(text: &str) -> bool {
         && !trimmed.contains('`')
         && !trimmed.contains('#')
         && !trimmed.contains('[')
+        && !trimmed.contains('$')
+        && !trimmed.starts_with('>')
+        && !trimmed.contains("://")
+        && !trimmed.contains('\\')
 }
 
 fn is_content_lost(original: &str, rendered: &[Line<'_>]) -> bool {
@@ -187,7 +279,8 @@ fn add_prefix_to_lines(lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
             let mut spans = vec![Span::styled(prefix, Theme::off_white())];
 
             for span in line.spans {
-                spans.push(Span::styled(span.content, Theme::off_white()));
+                let style = Theme::off_white().add_modifier(span.style.add_modifier);
+                spans.push(Span::styled(span.content, style));
             }
 
             Line::from(spans)
@@ -200,7 +293,6 @@ fn render_plaintext_fallback(text: &str, width: u16) -> Vec<Line<'static>> {
 
     wrapped
         .iter()
-        .take(MAX_MESSAGE_LINES)
         .enumerate()
         .map(|(i, line)| {
             if i == 0 {
@@ -228,19 +320,10 @@ fn append_cursor(lines: &mut Vec<Line<'static>>) {
     }
 }
 
-fn truncate_with_indicator(lines: &mut Vec<Line<'static>>) {
-    if lines.len() > MAX_MESSAGE_LINES {
-        lines.truncate(MAX_MESSAGE_LINES);
-        lines.push(Line::from(Span::styled(
-            "  ... (message truncated)",
-            Theme::muted(),
-        )));
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fmt::Write as _;
 
     #[test]
     fn user_message_has_prefix() {
@@ -273,19 +356,53 @@ mod tests {
     }
 
     #[test]
-    fn assistant_truncates_long_messages() {
-        let long_text = "Line\n".repeat(100);
-        let msg = ChatMessage::Assistant(long_text);
+    fn assistant_renders_long_messages_in_full() {
+        let mut long_text = String::new();
+        for n in 1..=100 {
+            let _ = write!(long_text, "Line {n}\n\n");
+        }
+        let msg = ChatMessage::Assistant {
+            text: long_text,
+            model: None,
+        };
         let lines = msg.render_to_lines(80, 0);
 
-        assert!(lines.len() <= MAX_MESSAGE_LINES + 1);
+        let last_line = lines.last().unwrap().to_string();
+        assert!(last_line.contains("Line 100"));
+        assert!(
+            !lines
+                .iter()
+                .any(|line| line.to_string().contains("truncated"))
+        );
+    }
 
-        let last_line = lines.last().unwrap();
-        let has_truncation = last_line
-            .spans
+    #[test]
+    fn review_groups_findings_by_file() {
+        let finding = |path: &str, severity, line| Finding {
+            path: path.to_string(),
+            line,
+            severity,
+            message: "check this".to_string(),
+        };
+        let msg = ChatMessage::Review {
+            title: "changes since main".to_string(),
+            findings: vec![
+                finding("src/a.rs", Severity::High, Some(12)),
+                finding("src/a.rs", Severity::Low, None),
+                finding("src/b.rs", Severity::Medium, Some(3)),
+            ],
+        };
+        let lines: Vec<String> = msg
+            .render_to_lines(80, 0)
             .iter()
-            .any(|s| s.content.contains("truncated"));
-        assert!(has_truncation);
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].contains("Review of changes since main: 3 findings"));
+        assert_eq!(lines[1], "  src/a.rs");
+        assert_eq!(lines[2], "    high   12  check this");
+        assert_eq!(lines[4], "  src/b.rs");
     }
 
     #[test]
@@ -294,6 +411,7 @@ mod tests {
             path: "/test.rs".into(),
             old_content: "a\nb\nc\n".into(),
             new_content: "a\nmodified\nc\nd\n".into(),
+            format: None,
             collapsed: false,
         };
 
@@ -301,6 +419,7 @@ mod tests {
             path: "/test.rs".into(),
             old_content: "a\nb\nc\n".into(),
             new_content: "a\nmodified\nc\nd\n".into(),
+            format: Some("CRLF".into()),
             collapsed: true,
         };
 
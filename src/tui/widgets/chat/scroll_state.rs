@@ -4,6 +4,7 @@ pub struct ScrollState {
     total_lines: usize,
     viewport_height: usize,
     manual_scroll: bool,
+    pending_jump: bool,
 }
 
 impl ScrollState {
@@ -14,6 +15,7 @@ impl ScrollState {
             total_lines: 0,
             viewport_height: 0,
             manual_scroll: false,
+            pending_jump: false,
         }
     }
 
@@ -66,6 +68,24 @@ impl ScrollState {
         self.manual_scroll = false;
     }
 
+    pub const fn scroll_to_line(&mut self, line: usize) {
+        let target = line.saturating_sub(self.viewport_height / 3);
+        self.position = if target < self.max_scroll() {
+            target
+        } else {
+            self.max_scroll()
+        };
+        self.manual_scroll = true;
+    }
+
+    pub const fn request_jump(&mut self) {
+        self.pending_jump = true;
+    }
+
+    pub const fn take_jump(&mut self) -> bool {
+        std::mem::replace(&mut self.pending_jump, false)
+    }
+
     const fn max_scroll(&self) -> usize {
         self.total_lines.saturating_sub(self.viewport_height)
     }
@@ -141,4 +161,17 @@ mod tests {
         state.update(20, 10);
         assert_eq!(state.position(), 10);
     }
+
+    #[test]
+    fn scroll_to_line_keeps_context_above() {
+        let mut state = ScrollState::new();
+        state.update(100, 30);
+
+        state.scroll_to_line(50);
+        assert_eq!(state.position(), 40);
+        assert!(state.is_manual_scroll());
+
+        state.scroll_to_line(95);
+        assert_eq!(state.position(), 70);
+    }
 }
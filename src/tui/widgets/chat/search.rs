@@ -0,0 +1,215 @@
+use ratatui::text::{Line, Span};
+
+use super::ChatMessage;
+use crate::ui::theme::Theme;
+
+#[derive(Debug, Clone)]
+pub struct SearchState {
+    query: String,
+    needle: String,
+    hits: Vec<usize>,
+    current: usize,
+    navigating: bool,
+}
+
+impl SearchState {
+    #[must_use]
+    pub fn new(query: &str, messages: &[ChatMessage]) -> Self {
+        let needle = query.to_ascii_lowercase();
+        let hits: Vec<usize> = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| message.contains_ignore_case(&needle))
+            .map(|(idx, _)| idx)
+            .collect();
+        let current = hits.len().saturating_sub(1);
+
+        Self {
+            query: query.to_string(),
+            needle,
+            hits,
+            current,
+            navigating: true,
+        }
+    }
+
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    #[must_use]
+    pub const fn hit_count(&self) -> usize {
+        self.hits.len()
+    }
+
+    #[must_use]
+    pub fn current_message(&self) -> Option<usize> {
+        self.hits.get(self.current).copied()
+    }
+
+    #[must_use]
+    pub const fn current_position(&self) -> usize {
+        self.current + 1
+    }
+
+    #[must_use]
+    pub const fn is_navigating(&self) -> bool {
+        self.navigating
+    }
+
+    pub const fn stop_navigating(&mut self) {
+        self.navigating = false;
+    }
+
+    pub const fn older(&mut self) {
+        if !self.hits.is_empty() {
+            self.current = (self.current + self.hits.len() - 1) % self.hits.len();
+        }
+    }
+
+    pub const fn newer(&mut self) {
+        if !self.hits.is_empty() {
+            self.current = (self.current + 1) % self.hits.len();
+        }
+    }
+
+    #[must_use]
+    pub fn highlight_line(&self, line: Line<'static>, current: bool) -> (Line<'static>, bool) {
+        if self.needle.is_empty() {
+            return (line, false);
+        }
+
+        let match_style = if current {
+            Theme::search_current()
+        } else {
+            Theme::search_match()
+        };
+        let mut found = false;
+        let mut spans = Vec::with_capacity(line.spans.len());
+
+        for span in line.spans {
+            let content = span.content.into_owned();
+            let lower = content.to_ascii_lowercase();
+            let mut last = 0;
+
+            for (start, _) in lower.match_indices(&self.needle) {
+                if start < last {
+                    continue;
+                }
+                let end = start + self.needle.len();
+                if start > last {
+                    spans.push(Span::styled(content[last..start].to_string(), span.style));
+                }
+                spans.push(Span::styled(
+                    content[start..end].to_string(),
+                    span.style.patch(match_style),
+                ));
+                last = end;
+                found = true;
+            }
+
+            if last == 0 {
+                spans.push(Span::styled(content, span.style));
+            } else if last < content.len() {
+                spans.push(Span::styled(content[last..].to_string(), span.style));
+            }
+        }
+
+        let mut highlighted = Line::from(spans).style(line.style);
+        highlighted.alignment = line.alignment;
+        (highlighted, found)
+    }
+
+    #[must_use]
+    pub fn indicator(&self) -> Line<'static> {
+        let keys = if self.navigating { "n/N" } else { "Alt+n/N" };
+        let text = format!(
+            " /{}: {}/{} · {keys} · Esc ",
+            self.query,
+            self.current_position(),
+            self.hit_count()
+        );
+        Line::from(Span::styled(text, Theme::warning()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::{ToolState, ToolType};
+
+    fn messages() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::User("Where is Config.toml loaded?".to_string()),
+            ChatMessage::Assistant {
+                text: "Let me check.".to_string(),
+                model: None,
+            },
+            ChatMessage::ToolExecution {
+                tool_type: ToolType::Grep,
+                input: "load".to_string(),
+                output: Some("src/config/mod.rs: reads config.toml".to_string()),
+                elapsed: None,
+                state: ToolState::Success,
+            },
+            ChatMessage::Assistant {
+                text: "It is loaded in src/config/mod.rs".to_string(),
+                model: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_finds_messages_and_tool_output() {
+        let search = SearchState::new("config.TOML", &messages());
+
+        assert_eq!(search.hit_count(), 2);
+        assert_eq!(search.current_message(), Some(2));
+    }
+
+    #[test]
+    fn test_navigation_wraps() {
+        let mut search = SearchState::new("config", &messages());
+        assert_eq!(search.hit_count(), 3);
+        assert_eq!(search.current_message(), Some(3));
+
+        search.older();
+        assert_eq!(search.current_message(), Some(2));
+        search.newer();
+        search.newer();
+        assert_eq!(search.current_message(), Some(0));
+        assert_eq!(search.current_position(), 1);
+    }
+
+    #[test]
+    fn test_navigation_mode_ends_once_stopped() {
+        let mut search = SearchState::new("config", &messages());
+        assert!(search.is_navigating());
+        assert!(search.indicator().to_string().contains("· n/N ·"));
+
+        search.stop_navigating();
+        assert!(!search.is_navigating());
+        assert!(search.indicator().to_string().contains("Alt+n/N"));
+    }
+
+    #[test]
+    fn test_highlight_splits_spans() {
+        let search = SearchState::new("mod", &[]);
+        let line = Line::from(vec![
+            Span::raw("src/config/"),
+            Span::styled("mod.rs and MOD", Theme::primary()),
+        ]);
+
+        let (highlighted, found) = search.highlight_line(line, true);
+
+        assert!(found);
+        let contents: Vec<&str> = highlighted
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(contents, vec!["src/config/", "mod", ".rs and ", "MOD"]);
+        assert_eq!(highlighted.spans[1].style.bg, Theme::search_current().bg);
+    }
+}
@@ -30,6 +30,13 @@ impl InputWidget<'_> {
                 InputAction::Clear
             }
 
+            (KeyCode::Backspace, _) if self.delete_placeholder_at_cursor(false) => {
+                InputAction::Continue
+            }
+            (KeyCode::Delete, _) if self.delete_placeholder_at_cursor(true) => {
+                InputAction::Continue
+            }
+
             (KeyCode::Char(_), KeyModifiers::NONE | KeyModifiers::SHIFT)
             | (KeyCode::Backspace, _) => {
                 self.textarea.input(key);
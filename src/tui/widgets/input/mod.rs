@@ -4,6 +4,8 @@ mod render;
 
 pub use action::InputAction;
 
+use crate::core::attachment::fit_to_budget;
+use crate::core::memory::estimate_tokens;
 use crate::ui::theme::Theme;
 use ratatui::style::Style;
 use tui_textarea::TextArea;
@@ -23,6 +25,7 @@ pub struct InputWidget<'a> {
     selected_suggestion: usize,
     pasted_blocks: Vec<PastedBlock>,
     next_paste_id: usize,
+    attachment_tokens: usize,
 }
 
 impl InputWidget<'_> {
@@ -35,16 +38,27 @@ impl InputWidget<'_> {
             selected_suggestion: 0,
             pasted_blocks: Vec::new(),
             next_paste_id: 0,
+            attachment_tokens: 0,
         }
     }
 
+    #[must_use]
+    pub const fn with_attachment_budget(mut self, tokens: usize) -> Self {
+        self.attachment_tokens = tokens;
+        self
+    }
+
     #[must_use]
     pub fn text(&self) -> String {
+        self.expand_pastes(0)
+    }
+
+    fn expand_pastes(&self, budget_tokens: usize) -> String {
         let mut result = self.textarea.lines().join("\n");
 
         for block in &self.pasted_blocks {
             let placeholder = Self::make_placeholder(block.placeholder_id, block.content.len());
-            result = result.replace(&placeholder, &block.content);
+            result = result.replace(&placeholder, &fit_to_budget(&block.content, budget_tokens));
         }
 
         result
@@ -70,8 +84,96 @@ impl InputWidget<'_> {
         self.textarea.lines().iter().all(String::is_empty)
     }
 
+    #[must_use]
+    pub fn estimated_tokens(&self) -> usize {
+        let lines = self.textarea.lines();
+        let typed = lines.iter().map(String::len).sum::<usize>() + lines.len().saturating_sub(1);
+        let pasted: usize = self
+            .pasted_blocks
+            .iter()
+            .map(|block| {
+                let placeholder = Self::make_placeholder(block.placeholder_id, block.content.len());
+                fit_to_budget(&block.content, self.attachment_tokens)
+                    .len()
+                    .saturating_sub(placeholder.len())
+            })
+            .sum();
+        estimate_tokens(typed + pasted)
+    }
+
+    #[must_use]
+    pub fn pasted_block_near_cursor(&self) -> Option<(usize, &str)> {
+        self.placeholder_at_cursor()
+            .map(|(index, _, _)| &self.pasted_blocks[index])
+            .or_else(|| self.pasted_blocks.last())
+            .map(|block| (block.placeholder_id, block.content.as_str()))
+    }
+
+    pub fn remove_pasted_block(&mut self, id: usize) -> bool {
+        let Some(index) = self
+            .pasted_blocks
+            .iter()
+            .position(|block| block.placeholder_id == id)
+        else {
+            return false;
+        };
+        let block = self.pasted_blocks.remove(index);
+        let placeholder = Self::make_placeholder(block.placeholder_id, block.content.len());
+        let (row, col) = self.textarea.cursor();
+        let mut cursor = (row, col);
+        let lines: Vec<String> = self
+            .textarea
+            .lines()
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let Some(start) = line.find(&placeholder) else {
+                    return line.clone();
+                };
+                let start_col = line[..start].chars().count();
+                if i == row && col > start_col {
+                    cursor.1 = start_col.max(col.saturating_sub(placeholder.len()));
+                }
+                line.replacen(&placeholder, "", 1)
+            })
+            .collect();
+        self.textarea = TextArea::new(lines);
+        self.configure_textarea();
+        self.textarea.move_cursor(tui_textarea::CursorMove::Jump(
+            u16::try_from(cursor.0).unwrap_or(u16::MAX),
+            u16::try_from(cursor.1).unwrap_or(u16::MAX),
+        ));
+        true
+    }
+
+    fn placeholder_at_cursor(&self) -> Option<(usize, usize, usize)> {
+        let (row, col) = self.textarea.cursor();
+        let line = self.textarea.lines().get(row)?;
+        self.pasted_blocks
+            .iter()
+            .enumerate()
+            .find_map(|(index, block)| {
+                let placeholder = Self::make_placeholder(block.placeholder_id, block.content.len());
+                let start = line[..line.find(&placeholder)?].chars().count();
+                let end = start + placeholder.len();
+                (start..=end).contains(&col).then_some((index, start, end))
+            })
+    }
+
+    fn delete_placeholder_at_cursor(&mut self, forward: bool) -> bool {
+        let Some((index, start, end)) = self.placeholder_at_cursor() else {
+            return false;
+        };
+        let col = self.textarea.cursor().1;
+        if (forward && col == end) || (!forward && col == start) {
+            return false;
+        }
+        let id = self.pasted_blocks[index].placeholder_id;
+        self.remove_pasted_block(id)
+    }
+
     pub fn take(&mut self) -> String {
-        let text = self.text();
+        let text = self.expand_pastes(self.attachment_tokens);
         self.clear();
         text
     }
@@ -99,3 +201,51 @@ impl Default for InputWidget<'_> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    #[test]
+    fn test_estimated_tokens_include_pasted_blocks() {
+        let mut widget = InputWidget::new();
+        assert_eq!(widget.estimated_tokens(), 0);
+
+        widget.set_text("fix\nthis");
+        assert_eq!(widget.estimated_tokens(), 2);
+
+        widget.clear();
+        let log = "x".repeat(4000);
+        widget.handle_paste(log.clone());
+        assert_eq!(widget.estimated_tokens(), 1000);
+        assert_eq!(widget.text(), log);
+
+        let mut widget = InputWidget::new().with_attachment_budget(100);
+        widget.handle_paste("x\n".repeat(2000));
+        assert!(widget.estimated_tokens() < 150);
+        let sent = widget.take();
+        assert!(sent.contains("lines omitted to fit the 100-token attachment budget"));
+    }
+
+    #[test]
+    fn test_backspace_removes_whole_pasted_block() {
+        let mut widget = InputWidget::new();
+        widget.handle_paste("a".repeat(300));
+        widget.handle_paste("b".repeat(300));
+        "ok".chars().for_each(|c| widget.textarea.insert_char(c));
+        assert_eq!(widget.pasted_block_near_cursor().map(|(id, _)| id), Some(1));
+
+        widget.textarea.move_cursor(tui_textarea::CursorMove::Back);
+        widget.textarea.move_cursor(tui_textarea::CursorMove::Back);
+        widget.handle_key(KeyEvent::from(KeyCode::Backspace));
+
+        assert_eq!(widget.text(), format!("{}ok", "a".repeat(300)));
+        assert_eq!(widget.textarea.cursor(), (0, 13));
+
+        assert!(widget.remove_pasted_block(0));
+        assert!(!widget.remove_pasted_block(0));
+        assert_eq!(widget.text(), "ok");
+        assert_eq!(widget.pasted_block_near_cursor(), None);
+    }
+}
@@ -1,7 +1,9 @@
 pub mod chat;
 pub mod input;
 
-pub use chat::{ChatMessage, ChatWidget, ScrollState};
+pub use chat::{
+    ChatMessage, ChatWidget, FileChange, LineCache, ModelTag, ScrollState, SearchState,
+};
 pub use input::{InputAction, InputWidget};
 
 pub use crate::ui::output_widget::MessageLevel;
@@ -15,6 +15,7 @@ pub struct LineBuilder<'a> {
     deletions: usize,
     lines: &'a [DiffLine],
     show_line_numbers: bool,
+    format: Option<&'a str>,
 }
 
 impl<'a> LineBuilder<'a> {
@@ -25,6 +26,7 @@ impl<'a> LineBuilder<'a> {
         deletions: usize,
         lines: &'a [DiffLine],
         show_line_numbers: bool,
+        format: Option<&'a str>,
     ) -> Self {
         Self {
             path,
@@ -33,6 +35,7 @@ impl<'a> LineBuilder<'a> {
             deletions,
             lines,
             show_line_numbers,
+            format,
         }
     }
 
@@ -55,12 +58,16 @@ impl<'a> LineBuilder<'a> {
     }
 
     fn build_header(&self) -> Line<'static> {
-        let text = format!(
+        let mut text = format!(
             " {} {} ({})",
             self.change_type.icon(),
             self.change_type.label(),
             self.path
         );
+        if let Some(format) = self.format {
+            text.push_str(" \u{b7} ");
+            text.push_str(format);
+        }
 
         Line::from(Span::styled(
             text,
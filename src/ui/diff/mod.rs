@@ -3,6 +3,7 @@ mod parser;
 mod types;
 
 use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use similar::{ChangeTag, TextDiff};
 
 use line_builder::LineBuilder;
 use parser::ParsedDiff;
@@ -18,6 +19,7 @@ pub struct DiffWidget {
     lines: Vec<types::DiffLine>,
     show_line_numbers: bool,
     collapsed: bool,
+    format: Option<String>,
 }
 
 impl DiffWidget {
@@ -33,9 +35,16 @@ impl DiffWidget {
             lines: parsed.lines,
             show_line_numbers: true,
             collapsed: false,
+            format: None,
         }
     }
 
+    #[must_use]
+    pub fn with_format(mut self, format: Option<&str>) -> Self {
+        self.format = format.map(ToString::to_string);
+        self
+    }
+
     #[must_use]
     pub const fn collapsed(mut self, collapsed: bool) -> Self {
         self.collapsed = collapsed;
@@ -80,10 +89,28 @@ impl DiffWidget {
             self.deletions,
             &self.lines,
             self.show_line_numbers,
+            self.format.as_deref(),
         )
     }
 }
 
+#[must_use]
+pub fn unified_diff(path: &str, old_content: &str, new_content: &str) -> String {
+    TextDiff::from_lines(old_content, new_content)
+        .unified_diff()
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+#[must_use]
+pub fn first_changed_line(old_content: &str, new_content: &str) -> usize {
+    TextDiff::from_lines(old_content, new_content)
+        .iter_all_changes()
+        .take_while(|change| change.tag() == ChangeTag::Equal)
+        .count()
+        + 1
+}
+
 impl Widget for DiffWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if area.width < 20 || area.height < 3 {
@@ -130,10 +157,27 @@ mod tests {
         assert_eq!(diff.deletions(), 1);
     }
 
+    #[test]
+    fn unified_diff_has_headers_and_hunks() {
+        let diff = unified_diff("src/file.rs", "a\nb\n", "a\nc\n");
+        assert!(diff.starts_with("--- a/src/file.rs\n+++ b/src/file.rs\n@@"));
+        assert!(diff.contains("-b\n+c\n"));
+    }
+
+    #[test]
+    fn first_changed_line_points_at_new_content() {
+        assert_eq!(first_changed_line("a\nb\nc\n", "a\nb\nx\n"), 3);
+        assert_eq!(first_changed_line("", "new\n"), 1);
+        assert_eq!(first_changed_line("a\nb\n", "a\n"), 2);
+    }
+
     #[test]
     fn collapsed_renders_header_only() {
-        let diff = DiffWidget::new("src/file.rs", "old\n", "new\n").collapsed(true);
+        let diff = DiffWidget::new("src/file.rs", "old\n", "new\n")
+            .with_format(Some("CRLF"))
+            .collapsed(true);
         let lines = diff.render_to_lines(80);
         assert_eq!(lines.len(), 2);
+        assert!(lines[0].to_string().ends_with("(src/file.rs) \u{b7} CRLF"));
     }
 }
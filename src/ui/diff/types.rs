@@ -28,7 +28,7 @@ impl ChangeType {
     }
 
     #[must_use]
-    pub const fn style(self) -> Style {
+    pub fn style(self) -> Style {
         match self {
             Self::Create => Theme::success(),
             Self::Update => Theme::primary(),
@@ -62,7 +62,7 @@ impl LineTag {
     }
 
     #[must_use]
-    pub const fn style(self) -> Style {
+    pub fn style(self) -> Style {
         match self {
             Self::Added => Theme::success(),
             Self::Removed => Theme::error(),
@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+
+use super::markdown::Link;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+const TERM_PROGRAMS: [&str; 6] = [
+    "iTerm.app",
+    "WezTerm",
+    "vscode",
+    "ghostty",
+    "Hyper",
+    "Tabby",
+];
+const TERMS: [&str; 5] = ["kitty", "foot", "alacritty", "wezterm", "ghostty"];
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[must_use]
+pub fn detect() -> bool {
+    supported(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+}
+
+fn supported(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(forced) = var("FORCE_HYPERLINK") {
+        return forced != "0";
+    }
+    if var("TMUX").is_some() {
+        return false;
+    }
+    let term = var("TERM").unwrap_or_default();
+    var("TERM_PROGRAM").is_some_and(|program| TERM_PROGRAMS.contains(&program.as_str()))
+        || var("WT_SESSION").is_some()
+        || var("KITTY_WINDOW_ID").is_some()
+        || var("DOMTERM").is_some()
+        || var("VTE_VERSION").is_some_and(|version| version.parse::<u32>().is_ok_and(|v| v >= 5000))
+        || TERMS.iter().any(|name| term.contains(name))
+}
+
+pub fn apply(buf: &mut Buffer, row: Rect, links: &[Link]) {
+    let y = row.y;
+    let end = row.right().min(buf.area.right());
+    let mut x = row.x;
+    while x < end {
+        if !is_link_cell(buf, x, y) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < end && is_link_cell(buf, x, y) {
+            x += 1;
+        }
+        let text: String = (start..x).map(|x| buf[(x, y)].symbol()).collect();
+        let text = text.trim();
+        if let Some(link) = links
+            .iter()
+            .find(|link| !text.is_empty() && link.text.contains(text))
+            && !link.url.contains(char::is_control)
+        {
+            link_cells(buf, start, x, y, &link.url);
+        }
+    }
+}
+
+fn is_link_cell(buf: &Buffer, x: u16, y: u16) -> bool {
+    buf.cell((x, y))
+        .is_some_and(|cell| cell.modifier.contains(Modifier::UNDERLINED))
+}
+
+fn link_cells(buf: &mut Buffer, start: u16, end: u16, y: u16, url: &str) {
+    if end - start < 2 {
+        return;
+    }
+    let mut x = start;
+    while x < end {
+        let chunk = if end - x == 3 { 3 } else { 2 };
+        let text: String = (x..x + chunk).map(|x| buf[(x, y)].symbol()).collect();
+        buf[(x, y)].set_symbol(&format!("\x1b]8;;{url}\x07{text}\x1b]8;;\x07"));
+        for skipped in x + 1..x + chunk {
+            buf[(skipped, y)].set_skip(true);
+        }
+        x += chunk;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::text::{Line, Span};
+
+    use super::*;
+
+    #[test]
+    fn test_apply_wraps_underlined_runs_in_osc8() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 1));
+        let line = Line::from(vec![
+            Span::raw("see "),
+            Span::styled("docs", Modifier::UNDERLINED),
+            Span::raw(" now"),
+        ]);
+        buf.set_line(0, 0, &line, 20);
+        let links = [Link {
+            text: "docs".to_string(),
+            url: "https://docs.rs".to_string(),
+        }];
+        let area = buf.area;
+        apply(&mut buf, area, &links);
+
+        assert_eq!(buf[(3, 0)].symbol(), " ");
+        assert_eq!(
+            buf[(4, 0)].symbol(),
+            "\x1b]8;;https://docs.rs\x07do\x1b]8;;\x07"
+        );
+        assert!(buf[(5, 0)].skip);
+        assert_eq!(
+            buf[(6, 0)].symbol(),
+            "\x1b]8;;https://docs.rs\x07cs\x1b]8;;\x07"
+        );
+        assert!(!buf[(8, 0)].skip);
+
+        let env = |vars: &'static [(&str, &str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        };
+        assert!(supported(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supported(env(&[("VTE_VERSION", "7600")])));
+        assert!(!supported(env(&[("TERM", "xterm-256color")])));
+        assert!(!supported(env(&[("TERM", "xterm-kitty"), ("TMUX", "1")])));
+        assert!(supported(env(&[("TMUX", "1"), ("FORCE_HYPERLINK", "1")])));
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::config::get_config_dir;
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+const HELP_TEXT: &str = r"Available commands:
+/help  - Show this help message
+/clear - Clear the chat history
+/model [name] - Switch to a different model by id or alias, or pick one from a list
+/config - Edit default model, system prompt, permission mode and theme
+/set   - Show or change generation settings (e.g. /set temperature 0.2)
+!cmd   - Run cmd in your shell and show the output; !!cmd also sends the output with your next message
+/search - Search the conversation (Ctrl+F); n/N jump between matches until you type, then Alt+n/N; Esc closes
+Ctrl+T - View the pasted block under the cursor (d removes it); Backspace deletes a whole paste
+Ctrl+P - Cycle through recently used models; the pick applies to the next message ([keys] cycle_model)
+Alt+Up/Down - Pick an earlier message to edit; sending it branches the conversation
+Esc    - Select messages: y copy, Space fold, r re-run tool, a apply a reply's code blocks to their files,
+         p open in $PAGER (delta for diffs), e open a diff's file in $EDITOR at the change, d forget exchange
+/snippet [name] [args] - List snippets or insert one (also #name args); missing {{fields}} open a form
+/sessions - Browse, resume, rename, delete or export saved sessions
+/memories - View, add, edit or delete remembered preferences (add one with: # remember: ...)
+/audit [n] - Show the last n audit log entries (default 20)
+/artifacts [export <dir> | copy <n> <path>] - List files the agent produced, or copy them out
+/apply [n|all] - Write a code block from the last reply to the file it names (```rust src/main.rs), with the usual permission prompt and diff
+/open [n] - Open a link from the last reply in the browser (lists them when there are several)
+/copy [last|code] - Copy the last reply, or its last code block, to the clipboard
+/pin <path> - Keep a file's current contents in every request; /pins lists pins and their token cost, /unpin <n|path> drops one
+/context - See what the next request sends (system prompt sections, pinned files, turns, tool outputs) with token counts; Space drops items
+/review [ref|#pr] - Review the changes since a base branch (default main) or a pull request
+/think on|off - Toggle extended thinking for Claude models ([generation] thinking_budget)
+/auto on|off - Approve every tool call without asking (logged to the audit log)
+/pause, /resume - Pause a running agent after its current step, and continue it later
+/fix-tests [command] - Run the tests, let the agent fix the failures and repeat until they pass
+/duo <task> - An implementer proposes changes and a reviewer critiques them until approved ([duo] config)
+/commit - Draft a commit message for the staged (or all) changes and commit
+/checkpoints [restore <n>] - List the working tree snapshots taken before each turn that changed files, or roll back to one ([checkpoints] enabled)
+/exit  - Exit the application";
+
+const BUILTIN_LOCALES: &[(&str, &str)] = &[("es", include_str!("locales/es.toml"))];
+
+const LANGUAGES: &[(&str, &str)] = &[
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("zh", "Chinese"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    Help,
+    SettingsTitle,
+    SelectModelTitle,
+    SessionsTitle,
+    MemoriesTitle,
+    ContextTitle,
+    CommitAllTitle,
+    CommitStagedTitle,
+    HintSaveCancel,
+    HintSubmitCancel,
+    HintSettings,
+    HintModelPicker,
+    HintSessions,
+    HintMemories,
+    HintContext,
+    HintPermission,
+    ConfirmDeleteSession,
+    ConfirmForgetMemory,
+    NoSessions,
+    NoMemories,
+    AllowOnce,
+    AllowOnceDescription,
+    AllowSession,
+    AllowSessionDescription,
+    AlwaysAllow,
+    AlwaysAllowDescription,
+    EditAndAllow,
+    EditAndAllowDescription,
+    Deny,
+    DenyDescription,
+    EditCommandPrompt,
+    DenyFeedbackPrompt,
+}
+
+impl Msg {
+    pub const ALL: [Self; 32] = [
+        Self::Help,
+        Self::SettingsTitle,
+        Self::SelectModelTitle,
+        Self::SessionsTitle,
+        Self::MemoriesTitle,
+        Self::ContextTitle,
+        Self::CommitAllTitle,
+        Self::CommitStagedTitle,
+        Self::HintSaveCancel,
+        Self::HintSubmitCancel,
+        Self::HintSettings,
+        Self::HintModelPicker,
+        Self::HintSessions,
+        Self::HintMemories,
+        Self::HintContext,
+        Self::HintPermission,
+        Self::ConfirmDeleteSession,
+        Self::ConfirmForgetMemory,
+        Self::NoSessions,
+        Self::NoMemories,
+        Self::AllowOnce,
+        Self::AllowOnceDescription,
+        Self::AllowSession,
+        Self::AllowSessionDescription,
+        Self::AlwaysAllow,
+        Self::AlwaysAllowDescription,
+        Self::EditAndAllow,
+        Self::EditAndAllowDescription,
+        Self::Deny,
+        Self::DenyDescription,
+        Self::EditCommandPrompt,
+        Self::DenyFeedbackPrompt,
+    ];
+
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Help => "help",
+            Self::SettingsTitle => "settings_title",
+            Self::SelectModelTitle => "select_model_title",
+            Self::SessionsTitle => "sessions_title",
+            Self::MemoriesTitle => "memories_title",
+            Self::ContextTitle => "context_title",
+            Self::CommitAllTitle => "commit_all_title",
+            Self::CommitStagedTitle => "commit_staged_title",
+            Self::HintSaveCancel => "hint_save_cancel",
+            Self::HintSubmitCancel => "hint_submit_cancel",
+            Self::HintSettings => "hint_settings",
+            Self::HintModelPicker => "hint_model_picker",
+            Self::HintSessions => "hint_sessions",
+            Self::HintMemories => "hint_memories",
+            Self::HintContext => "hint_context",
+            Self::HintPermission => "hint_permission",
+            Self::ConfirmDeleteSession => "confirm_delete_session",
+            Self::ConfirmForgetMemory => "confirm_forget_memory",
+            Self::NoSessions => "no_sessions",
+            Self::NoMemories => "no_memories",
+            Self::AllowOnce => "allow_once",
+            Self::AllowOnceDescription => "allow_once_description",
+            Self::AllowSession => "allow_session",
+            Self::AllowSessionDescription => "allow_session_description",
+            Self::AlwaysAllow => "always_allow",
+            Self::AlwaysAllowDescription => "always_allow_description",
+            Self::EditAndAllow => "edit_and_allow",
+            Self::EditAndAllowDescription => "edit_and_allow_description",
+            Self::Deny => "deny",
+            Self::DenyDescription => "deny_description",
+            Self::EditCommandPrompt => "edit_command_prompt",
+            Self::DenyFeedbackPrompt => "deny_feedback_prompt",
+        }
+    }
+
+    #[must_use]
+    pub const fn english(self) -> &'static str {
+        match self {
+            Self::Help => HELP_TEXT,
+            Self::SettingsTitle => "Settings",
+            Self::SelectModelTitle => "Select Model",
+            Self::SessionsTitle => "Sessions",
+            Self::MemoriesTitle => "Memories",
+            Self::ContextTitle => "Context",
+            Self::CommitAllTitle => "Commit all changes",
+            Self::CommitStagedTitle => "Commit staged changes",
+            Self::HintSaveCancel => "Enter: save • Esc: cancel",
+            Self::HintSubmitCancel => "Enter: submit • Esc: cancel",
+            Self::HintSettings => {
+                "↑/↓: field • ←/→: change • type to edit • Enter: save • Esc: cancel"
+            }
+            Self::HintModelPicker => "↑/↓: navigate • Enter: select • Esc: cancel",
+            Self::HintSessions => {
+                "↑/↓: navigate • Enter: resume • r: rename • d: delete • e: export • Esc: close"
+            }
+            Self::HintMemories => "↑/↓: navigate • a: add • e/Enter: edit • d: delete • Esc: close",
+            Self::HintContext => "↑/↓: navigate • Space: drop/keep • Enter: apply • Esc: cancel",
+            Self::HintPermission => {
+                "↑/↓: navigate • Enter: confirm • Esc: cancel • y: allow • 4/n: deny"
+            }
+            Self::ConfirmDeleteSession => {
+                "Press d again to delete this session • any other key cancels"
+            }
+            Self::ConfirmForgetMemory => {
+                "Press d again to forget this memory • any other key cancels"
+            }
+            Self::NoSessions => "No saved sessions yet",
+            Self::NoMemories => {
+                "Nothing remembered yet. Type \"# remember: ...\" or press a to add one"
+            }
+            Self::AllowOnce => "Allow once",
+            Self::AllowOnceDescription => "Allow this single operation",
+            Self::AllowSession => "Allow for session",
+            Self::AllowSessionDescription => "Allow all similar operations",
+            Self::AlwaysAllow => "Always allow here",
+            Self::AlwaysAllowDescription => "Remember this for the current directory",
+            Self::EditAndAllow => "Edit and allow",
+            Self::EditAndAllowDescription => "Change it before it runs",
+            Self::Deny => "Deny",
+            Self::DenyDescription => "Tell the model to try something else",
+            Self::EditCommandPrompt => "Edit, then press Enter to approve what runs:",
+            Self::DenyFeedbackPrompt => "Tell the model what to do instead:",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        Ok(Self {
+            messages: toml::from_str(content)?,
+        })
+    }
+
+    #[must_use]
+    pub fn load(language: &str) -> Self {
+        let Some(code) = locale_code(language) else {
+            return Self::default();
+        };
+        let mut catalog = BUILTIN_LOCALES
+            .iter()
+            .find(|(builtin, _)| *builtin == code)
+            .and_then(|(_, content)| Self::parse(content).ok())
+            .unwrap_or_default();
+        let community =
+            get_config_dir().map(|dir| dir.join("locales").join(format!("{code}.toml")));
+        if let Some(path) = community
+            && let Ok(content) = std::fs::read_to_string(&path)
+        {
+            match Self::parse(&content) {
+                Ok(overrides) => catalog.messages.extend(overrides.messages),
+                Err(e) => tracing::warn!("Ignoring translation {}: {e}", path.display()),
+            }
+        }
+        catalog
+    }
+
+    #[must_use]
+    pub fn get(&self, msg: Msg) -> &str {
+        self.messages
+            .get(msg.key())
+            .map_or_else(|| msg.english(), String::as_str)
+    }
+
+    #[must_use]
+    pub fn missing(&self) -> Vec<Msg> {
+        Msg::ALL
+            .into_iter()
+            .filter(|msg| !self.messages.contains_key(msg.key()))
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn locale_code(language: &str) -> Option<String> {
+    let language = language.trim().to_lowercase();
+    if let Some((code, _)) = LANGUAGES
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(&language))
+    {
+        return Some((*code).to_string());
+    }
+    let code = language.split(['-', '_', '.']).next()?;
+    (code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic())).then(|| code.to_string())
+}
+
+#[must_use]
+pub fn language_name(language: &str) -> String {
+    let language = language.trim();
+    locale_code(language)
+        .and_then(|code| LANGUAGES.iter().find(|(known, _)| *known == code))
+        .map_or_else(|| language.to_string(), |(_, name)| (*name).to_string())
+}
+
+pub fn set_language(language: &str) {
+    let _ = CATALOG.set(Catalog::load(language));
+}
+
+#[must_use]
+pub fn t(msg: Msg) -> &'static str {
+    CATALOG
+        .get()
+        .map_or_else(|| msg.english(), |catalog| catalog.get(msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_and_language_names() {
+        assert_eq!(locale_code("es_ES.UTF-8").as_deref(), Some("es"));
+        assert_eq!(locale_code("German").as_deref(), Some("de"));
+        assert_eq!(locale_code("Klingon"), None);
+        assert_eq!(language_name("pt-BR"), "Portuguese");
+        assert_eq!(language_name("Klingon"), "Klingon");
+
+        for (code, content) in BUILTIN_LOCALES {
+            let catalog = Catalog::parse(content).unwrap();
+            assert_eq!(catalog.missing(), Vec::new(), "{code} is incomplete");
+        }
+
+        let catalog = Catalog::parse("settings_title = \"Einstellungen\"").unwrap();
+        assert_eq!(catalog.get(Msg::SettingsTitle), "Einstellungen");
+        assert_eq!(catalog.get(Msg::Deny), "Deny");
+        assert_eq!(Catalog::default().get(Msg::Help), Msg::Help.english());
+    }
+}
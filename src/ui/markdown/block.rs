@@ -1,14 +1,18 @@
-use markdown::mdast::{Code, Heading, List, ListItem, Node, Paragraph};
+use markdown::mdast::{Blockquote, Code, Heading, List, ListItem, Math, Node, Paragraph};
 use ratatui::{
     style::{Modifier, Style},
     text::{Line, Span},
 };
-use textwrap::wrap;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::ui::theme::{BoxChars, Theme};
 
 use super::context::RenderContext;
-use super::inline::{collect_inline_spans, collect_text_from_nodes};
+use super::inline::{collect_inline_spans, collect_styled_spans, collect_text_from_nodes};
+use super::math::latex_to_unicode;
+use super::table::render_table;
+
+const QUOTE_BAR: &str = "│ ";
 
 pub fn render_node(node: &Node, ctx: RenderContext) -> Vec<Line<'static>> {
     match node {
@@ -24,9 +28,15 @@ pub fn render_node(node: &Node, ctx: RenderContext) -> Vec<Line<'static>> {
 
         Node::Code(code) => with_trailing_blank(render_code_block(code, ctx)),
 
-        Node::Break(_) | Node::ThematicBreak(_) => vec![Line::default()],
+        Node::Math(math) => with_trailing_blank(render_math_block(math, ctx)),
+
+        Node::Table(table) => with_trailing_blank(render_table(table, ctx)),
+
+        Node::Break(_) => vec![Line::default()],
 
-        Node::Blockquote(quote) => render_children(&quote.children, ctx.nested()),
+        Node::ThematicBreak(_) => with_trailing_blank(render_thematic_break(ctx)),
+
+        Node::Blockquote(quote) => with_trailing_blank(render_blockquote(quote, ctx)),
 
         _ => Vec::new(),
     }
@@ -54,10 +64,18 @@ fn render_heading(heading: &Heading, ctx: RenderContext) -> Vec<Line<'static>> {
 }
 
 fn render_paragraph(para: &Paragraph, ctx: RenderContext) -> Vec<Line<'static>> {
-    let spans = collect_inline_spans(&para.children);
-    let full_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let indent = ctx.indent();
+    let available = ctx.available_width();
 
-    wrap_text_to_lines(&full_text, ctx)
+    if available == 0 {
+        return Vec::new();
+    }
+
+    let spans = collect_inline_spans(&para.children);
+    wrap_spans(spans, ctx.width.map(|_| available))
+        .into_iter()
+        .map(|spans| Line::from([vec![Span::raw(indent.clone())], spans].concat()))
+        .collect()
 }
 
 fn render_list(list: &List, ctx: RenderContext) -> Vec<Line<'static>> {
@@ -65,18 +83,44 @@ fn render_list(list: &List, ctx: RenderContext) -> Vec<Line<'static>> {
 }
 
 fn render_list_item(item: &ListItem, ctx: RenderContext) -> Vec<Line<'static>> {
-    let bullet = format!("{} ", BoxChars::DOT);
-    let bullet_width = bullet.len();
-    let indent = ctx.indent();
-
-    let text_content = extract_paragraph_text(&item.children);
+    let (glyph, bullet_style, text_style) = match item.checked {
+        Some(true) => (
+            BoxChars::CHECKBOX_CHECKED,
+            Theme::success(),
+            Theme::muted().add_modifier(Modifier::CROSSED_OUT),
+        ),
+        Some(false) => (BoxChars::CHECKBOX, Theme::primary(), Style::default()),
+        None => (BoxChars::DOT, Theme::primary(), Style::default()),
+    };
+    let bullet = Span::styled(format!("{glyph} "), bullet_style);
+    let continuation = " ".repeat(ctx.indent_width() + bullet.width());
+
+    let spans = item
+        .children
+        .iter()
+        .find_map(|child| match child {
+            Node::Paragraph(para) => Some(collect_styled_spans(&para.children, text_style)),
+            _ => None,
+        })
+        .unwrap_or_default();
 
     let available_width = ctx
         .width
         .unwrap_or(80)
-        .saturating_sub(ctx.indent_width() + bullet_width);
-
-    let mut lines = render_bulleted_text(&text_content, &indent, &bullet, available_width, ctx);
+        .saturating_sub(ctx.indent_width() + bullet.width());
+
+    let mut lines: Vec<Line<'static>> = wrap_spans(spans, Some(available_width).filter(|w| *w > 0))
+        .into_iter()
+        .enumerate()
+        .map(|(i, spans)| {
+            let prefix = if i == 0 {
+                vec![Span::raw(ctx.indent()), bullet.clone()]
+            } else {
+                vec![Span::raw(continuation.clone())]
+            };
+            Line::from([prefix, spans].concat())
+        })
+        .collect();
 
     lines.extend(
         item.children
@@ -91,50 +135,41 @@ fn render_list_item(item: &ListItem, ctx: RenderContext) -> Vec<Line<'static>> {
     lines
 }
 
-fn extract_paragraph_text(children: &[Node]) -> String {
-    children
-        .iter()
-        .find_map(|child| match child {
-            Node::Paragraph(para) => Some(collect_text_from_nodes(&para.children)),
-            _ => None,
+fn render_blockquote(quote: &Blockquote, ctx: RenderContext) -> Vec<Line<'static>> {
+    let width = ctx
+        .width
+        .map(|_| ctx.available_width().saturating_sub(QUOTE_BAR.width()));
+    let mut lines = render_children(&quote.children, RenderContext::new(0, width));
+    while lines.last().is_some_and(|line| line.spans.is_empty()) {
+        lines.pop();
+    }
+
+    let indent = ctx.indent();
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = vec![
+                Span::raw(indent.clone()),
+                Span::styled(QUOTE_BAR, Theme::border()),
+            ];
+            spans.extend(
+                line.spans
+                    .into_iter()
+                    .map(|span| span.patch_style(Modifier::DIM)),
+            );
+            Line::from(spans)
         })
-        .unwrap_or_default()
+        .collect()
 }
 
-fn render_bulleted_text(
-    text: &str,
-    indent: &str,
-    bullet: &str,
-    available_width: usize,
-    ctx: RenderContext,
-) -> Vec<Line<'static>> {
-    if available_width > 0 && text.len() > available_width {
-        wrap(text, available_width)
-            .into_iter()
-            .enumerate()
-            .map(|(i, line_text)| {
-                if i == 0 {
-                    Line::from(vec![
-                        Span::raw(indent.to_owned()),
-                        Span::styled(bullet.to_owned(), Theme::primary()),
-                        Span::raw(line_text.to_string()),
-                    ])
-                } else {
-                    let continuation = " ".repeat(ctx.indent_width() + bullet.len());
-                    Line::from(vec![
-                        Span::raw(continuation),
-                        Span::raw(line_text.to_string()),
-                    ])
-                }
-            })
-            .collect()
-    } else {
-        vec![Line::from(vec![
-            Span::raw(indent.to_owned()),
-            Span::styled(bullet.to_owned(), Theme::primary()),
-            Span::raw(text.to_owned()),
-        ])]
-    }
+fn render_thematic_break(ctx: RenderContext) -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw(ctx.indent()),
+        Span::styled(
+            BoxChars::HORIZONTAL.repeat(ctx.available_width()),
+            Theme::border(),
+        ),
+    ])]
 }
 
 fn render_code_block(code: &Code, ctx: RenderContext) -> Vec<Line<'static>> {
@@ -166,32 +201,131 @@ fn render_code_block(code: &Code, ctx: RenderContext) -> Vec<Line<'static>> {
     lines
 }
 
-fn wrap_text_to_lines(text: &str, ctx: RenderContext) -> Vec<Line<'static>> {
-    let indent = ctx.indent();
-    let available = ctx.available_width();
+fn render_math_block(math: &Math, ctx: RenderContext) -> Vec<Line<'static>> {
+    let indent = format!("{}  ", ctx.indent());
 
-    if available == 0 {
-        return Vec::new();
-    }
-
-    text.lines()
-        .flat_map(|line| {
-            if line.len() <= available || ctx.width.is_none() {
-                vec![Line::from(vec![
-                    Span::raw(indent.clone()),
-                    Span::styled(line.to_string(), Style::default()),
-                ])]
-            } else {
-                wrap(line, available)
-                    .into_iter()
-                    .map(|wrapped| {
-                        Line::from(vec![
-                            Span::raw(indent.clone()),
-                            Span::styled(wrapped.to_string(), Style::default()),
-                        ])
-                    })
-                    .collect()
-            }
+    latex_to_unicode(&math.value)
+        .lines()
+        .map(|line| {
+            Line::from(vec![
+                Span::raw(indent.clone()),
+                Span::styled(line.to_string(), Theme::secondary()),
+            ])
         })
         .collect()
 }
+
+fn wrap_spans(spans: Vec<Span<'static>>, width: Option<usize>) -> Vec<Vec<Span<'static>>> {
+    let mut lines = vec![Vec::new()];
+    let mut used = 0;
+    for span in spans {
+        for (index, segment) in span.content.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(Vec::new());
+                used = 0;
+            }
+            for word in segment.split_inclusive(' ') {
+                if let Some(width) = width
+                    && used > 0
+                    && used + word.trim_end().width() > width
+                {
+                    lines.push(Vec::new());
+                    used = 0;
+                }
+                let mut word = if used == 0 { word.trim_start() } else { word };
+                while let Some(width) = width
+                    && used == 0
+                    && word.trim_end().width() > width
+                {
+                    let (head, tail) = split_at_width(word, width);
+                    push_text(&mut lines, head, span.style);
+                    lines.push(Vec::new());
+                    word = tail;
+                }
+                if !word.is_empty() {
+                    used += word.width();
+                    push_text(&mut lines, word, span.style);
+                }
+            }
+        }
+    }
+
+    for line in &mut lines {
+        if let Some(last) = line.last_mut() {
+            let trimmed = last.content.trim_end().len();
+            last.content.to_mut().truncate(trimmed);
+        }
+    }
+    lines
+}
+
+fn push_text(lines: &mut [Vec<Span<'static>>], text: &str, style: Style) {
+    let Some(line) = lines.last_mut() else {
+        return;
+    };
+    match line.last_mut() {
+        Some(last) if last.style == style => last.content.to_mut().push_str(text),
+        _ => line.push(Span::styled(text.to_string(), style)),
+    }
+}
+
+fn split_at_width(text: &str, width: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (index, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width && index > 0 {
+            return text.split_at(index);
+        }
+    }
+    (text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Modifier;
+
+    use crate::ui::markdown::MarkdownWidget;
+
+    #[test]
+    fn test_task_list_renders_checkboxes() {
+        let lines = MarkdownWidget::new("- [ ] write tests\n- [x] ~~ship~~ it\n- plain")
+            .render_to_lines()
+            .unwrap();
+
+        assert_eq!(lines[0].to_string(), "☐ write tests");
+        assert_eq!(lines[1].to_string(), "☑ ship it");
+        assert_eq!(lines[2].to_string(), "• plain");
+        assert!(
+            lines[1].spans[2]
+                .style
+                .add_modifier
+                .contains(Modifier::CROSSED_OUT)
+        );
+        assert!(
+            !lines[0].spans[2]
+                .style
+                .add_modifier
+                .contains(Modifier::CROSSED_OUT)
+        );
+    }
+
+    #[test]
+    fn test_quotes_rules_and_nested_emphasis() {
+        let lines = MarkdownWidget::new("> quoted **bold *both***\n\n---\n\nafter")
+            .width(20)
+            .render_to_lines()
+            .unwrap();
+
+        assert_eq!(lines[0].to_string(), "│ quoted bold both");
+        assert!(lines[0].spans[2].style.add_modifier.contains(Modifier::DIM));
+        let both = lines[0].spans.last().unwrap();
+        assert_eq!(both.content, "both");
+        assert!(
+            both.style
+                .add_modifier
+                .contains(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(lines[2].to_string(), "─".repeat(20));
+        assert_eq!(lines[4].to_string(), "after");
+    }
+}
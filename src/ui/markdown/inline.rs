@@ -1,10 +1,15 @@
 use std::borrow::Cow;
 
 use markdown::mdast::Node;
-use ratatui::{style::Modifier, text::Span};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
 
 use crate::ui::theme::Theme;
 
+use super::math::latex_to_unicode;
+
 pub fn collect_text_from_nodes(nodes: &[Node]) -> String {
     nodes.iter().map(collect_text).collect()
 }
@@ -13,33 +18,61 @@ pub fn collect_text(node: &Node) -> Cow<'static, str> {
     match node {
         Node::Text(text) => Cow::Owned(text.value.clone()),
         Node::InlineCode(code) => Cow::Owned(code.value.clone()),
+        Node::InlineMath(math) => Cow::Owned(latex_to_unicode(&math.value)),
         Node::Strong(strong) => Cow::Owned(collect_text_from_nodes(&strong.children)),
         Node::Emphasis(em) => Cow::Owned(collect_text_from_nodes(&em.children)),
+        Node::Delete(delete) => Cow::Owned(collect_text_from_nodes(&delete.children)),
         Node::Link(link) => Cow::Owned(collect_text_from_nodes(&link.children)),
         _ => Cow::Borrowed(""),
     }
 }
 
 pub fn collect_inline_spans(nodes: &[Node]) -> Vec<Span<'static>> {
-    nodes.iter().flat_map(render_inline).collect()
+    collect_styled_spans(nodes, Style::default())
+}
+
+pub fn collect_styled_spans(nodes: &[Node], style: Style) -> Vec<Span<'static>> {
+    nodes
+        .iter()
+        .flat_map(|node| render_inline(node, style))
+        .collect()
 }
 
-fn render_inline(node: &Node) -> Vec<Span<'static>> {
+fn render_inline(node: &Node, style: Style) -> Vec<Span<'static>> {
     match node {
-        Node::Text(text) => vec![Span::raw(text.value.clone())],
+        Node::Text(text) => vec![Span::styled(text.value.clone(), style)],
+
+        Node::Break(_) => vec![Span::styled("\n", style)],
 
         Node::Strong(strong) => {
-            let text = collect_text_from_nodes(&strong.children);
-            vec![Span::styled(text, Modifier::BOLD)]
+            collect_styled_spans(&strong.children, style.add_modifier(Modifier::BOLD))
+        }
+
+        Node::Emphasis(em) => {
+            collect_styled_spans(&em.children, style.add_modifier(Modifier::ITALIC))
         }
 
-        Node::Emphasis(em) => collect_inline_spans(&em.children),
+        Node::Delete(delete) => {
+            collect_styled_spans(&delete.children, style.add_modifier(Modifier::CROSSED_OUT))
+        }
 
         Node::InlineCode(code) => {
-            vec![Span::styled(code.value.clone(), Theme::secondary())]
+            vec![Span::styled(
+                code.value.clone(),
+                style.patch(Theme::secondary()),
+            )]
         }
 
-        Node::Link(link) => collect_inline_spans(&link.children),
+        Node::InlineMath(math) => {
+            vec![Span::styled(
+                latex_to_unicode(&math.value),
+                style.patch(Theme::secondary()),
+            )]
+        }
+
+        Node::Link(link) => {
+            collect_styled_spans(&link.children, style.add_modifier(Modifier::UNDERLINED))
+        }
 
         _ => Vec::new(),
     }
@@ -0,0 +1,80 @@
+use std::fmt::Write as _;
+
+use markdown::mdast::Node;
+
+use super::inline::collect_text_from_nodes;
+use super::widget::parse;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+}
+
+#[must_use]
+pub fn extract_links(content: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    if let Ok(ast) = parse(content) {
+        collect_links(&ast, &mut links);
+    }
+    links
+}
+
+#[must_use]
+pub fn format_links(links: &[Link]) -> String {
+    let mut output = format!("{} link(s) in the last reply:", links.len());
+    for (index, link) in links.iter().enumerate() {
+        if link.text == link.url {
+            let _ = write!(output, "\n{:>3}. {}", index + 1, link.url);
+        } else {
+            let _ = write!(output, "\n{:>3}. {} — {}", index + 1, link.text, link.url);
+        }
+    }
+    output.push_str("\n\n/open <n> opens one in the browser");
+    output
+}
+
+fn collect_links(node: &Node, links: &mut Vec<Link>) {
+    if let Node::Link(link) = node {
+        let text = collect_text_from_nodes(&link.children);
+        if !links.iter().any(|known| known.url == link.url) {
+            links.push(Link {
+                text: if text.is_empty() {
+                    link.url.clone()
+                } else {
+                    text
+                },
+                url: link.url.clone(),
+            });
+        }
+        return;
+    }
+    for child in node.children().into_iter().flatten() {
+        collect_links(child, links);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links_dedupes_and_skips_code() {
+        let links = extract_links(
+            "See [the docs](https://docs.rs/ratatui) and https://example.com/a.\n\n```\nhttps://ignored.dev\n```\n\nAgain: [docs](https://docs.rs/ratatui)",
+        );
+        assert_eq!(
+            links,
+            vec![
+                Link {
+                    text: "the docs".to_string(),
+                    url: "https://docs.rs/ratatui".to_string(),
+                },
+                Link {
+                    text: "https://example.com/a".to_string(),
+                    url: "https://example.com/a".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use std::borrow::Cow;
+
+const SYMBOLS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("coprod", "∐"),
+    ("int", "∫"),
+    ("iint", "∬"),
+    ("oint", "∮"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("infty", "∞"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("cdot", "·"),
+    ("ast", "∗"),
+    ("circ", "∘"),
+    ("bullet", "•"),
+    ("star", "⋆"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("ne", "≠"),
+    ("approx", "≈"),
+    ("sim", "∼"),
+    ("simeq", "≃"),
+    ("equiv", "≡"),
+    ("propto", "∝"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("ni", "∋"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("setminus", "∖"),
+    ("emptyset", "∅"),
+    ("varnothing", "∅"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("lnot", "¬"),
+    ("land", "∧"),
+    ("wedge", "∧"),
+    ("lor", "∨"),
+    ("vee", "∨"),
+    ("oplus", "⊕"),
+    ("otimes", "⊗"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("gets", "←"),
+    ("leftrightarrow", "↔"),
+    ("Rightarrow", "⇒"),
+    ("implies", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"),
+    ("iff", "⇔"),
+    ("mapsto", "↦"),
+    ("uparrow", "↑"),
+    ("downarrow", "↓"),
+    ("ldots", "…"),
+    ("dots", "…"),
+    ("cdots", "⋯"),
+    ("vdots", "⋮"),
+    ("ddots", "⋱"),
+    ("prime", "′"),
+    ("angle", "∠"),
+    ("perp", "⊥"),
+    ("parallel", "∥"),
+    ("mid", "∣"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("lceil", "⌈"),
+    ("rceil", "⌉"),
+    ("lfloor", "⌊"),
+    ("rfloor", "⌋"),
+    ("hbar", "ℏ"),
+    ("ell", "ℓ"),
+    ("Re", "ℜ"),
+    ("Im", "ℑ"),
+    ("aleph", "ℵ"),
+    ("top", "⊤"),
+    ("bot", "⊥"),
+    ("vert", "|"),
+    ("Vert", "‖"),
+    ("lvert", "|"),
+    ("rvert", "|"),
+    ("lVert", "‖"),
+    ("rVert", "‖"),
+    ("quad", "  "),
+    ("qquad", "    "),
+];
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('f', 'ᶠ'),
+    ('g', 'ᵍ'),
+    ('h', 'ʰ'),
+    ('i', 'ⁱ'),
+    ('j', 'ʲ'),
+    ('k', 'ᵏ'),
+    ('l', 'ˡ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('o', 'ᵒ'),
+    ('p', 'ᵖ'),
+    ('r', 'ʳ'),
+    ('s', 'ˢ'),
+    ('t', 'ᵗ'),
+    ('u', 'ᵘ'),
+    ('v', 'ᵛ'),
+    ('w', 'ʷ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+    ('T', 'ᵀ'),
+    ('′', '′'),
+    ('∗', '*'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+const IGNORED: &[&str] = &[
+    "left",
+    "right",
+    "big",
+    "Big",
+    "bigg",
+    "Bigg",
+    "bigl",
+    "bigr",
+    "Bigl",
+    "Bigr",
+    "displaystyle",
+    "textstyle",
+    "limits",
+    "nolimits",
+];
+
+const STYLES: &[&str] = &[
+    "text",
+    "textrm",
+    "textbf",
+    "textit",
+    "mathrm",
+    "mathbf",
+    "mathit",
+    "mathsf",
+    "mathtt",
+    "mathcal",
+    "boldsymbol",
+    "operatorname",
+    "mbox",
+];
+
+#[must_use]
+pub fn normalize_math(text: &str) -> Cow<'_, str> {
+    if !text.contains(['$', '\\']) {
+        return Cow::Borrowed(text);
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut fence: Option<&str> = None;
+    let mut in_math = false;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            output.push_str(line);
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            output.push_str(line);
+        } else if matches!(trimmed, "$$" | "\\[" | "\\]") {
+            in_math = !in_math;
+            output.push_str("$$\n");
+        } else if in_math {
+            output.push_str(line);
+        } else {
+            output.push_str(&normalize_inline(line));
+        }
+    }
+    Cow::Owned(output)
+}
+
+fn normalize_inline(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut output = String::with_capacity(line.len());
+    let mut in_code = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code && let Some((inner, end)) = math_span(&chars, i) {
+            output.push_str("$$");
+            output.extend(inner);
+            output.push_str("$$");
+            i = end;
+            continue;
+        }
+        output.push(c);
+        i += 1;
+    }
+    output
+}
+
+fn math_span(chars: &[char], start: usize) -> Option<(&[char], usize)> {
+    let at = |i: usize| chars.get(i).copied();
+    match (at(start)?, at(start + 1)) {
+        ('\\', Some(open @ ('(' | '['))) => {
+            let close = if open == '(' { ')' } else { ']' };
+            let end = (start + 2..chars.len().saturating_sub(1))
+                .find(|&i| chars[i] == '\\' && chars[i + 1] == close)?;
+            Some((&chars[start + 2..end], end + 2))
+        }
+        ('$', Some('$')) => {
+            let end = (start + 2..chars.len().saturating_sub(1))
+                .find(|&i| chars[i] == '$' && chars[i + 1] == '$')?;
+            Some((&chars[start + 2..end], end + 2))
+        }
+        ('$', Some(next)) if !next.is_whitespace() && (start == 0 || chars[start - 1] != '\\') => {
+            let end =
+                (start + 2..chars.len()).find(|&i| chars[i] == '$' && chars[i - 1] != '\\')?;
+            let closes = !chars[end - 1].is_whitespace()
+                && !at(end + 1).is_some_and(|c| c.is_ascii_digit() || c == '$');
+            closes.then_some((&chars[start + 1..end], end + 1))
+        }
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn latex_to_unicode(latex: &str) -> String {
+    let mut parser = Parser {
+        chars: latex.chars().collect(),
+        pos: 0,
+    };
+    let text = parser.sequence(None);
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn sequence(&mut self, until: Option<char>) -> String {
+        let mut output = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if Some(c) == until {
+                break;
+            }
+            match c {
+                '\\' => output.push_str(&self.command()),
+                '{' => output.push_str(&self.sequence(Some('}'))),
+                '^' => output.push_str(&script(&self.argument(), SUPERSCRIPTS, '^')),
+                '_' => output.push_str(&script(&self.argument(), SUBSCRIPTS, '_')),
+                '&' => {}
+                '~' => output.push(' '),
+                c => output.push(c),
+            }
+        }
+        output
+    }
+
+    fn argument(&mut self) -> String {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        let Some(c) = self.peek() else {
+            return String::new();
+        };
+        self.pos += 1;
+        match c {
+            '{' => self.sequence(Some('}')),
+            '\\' => self.command(),
+            c => c.to_string(),
+        }
+    }
+
+    fn optional_argument(&mut self) -> Option<String> {
+        if self.peek() != Some('[') {
+            return None;
+        }
+        self.pos += 1;
+        Some(self.sequence(Some(']')))
+    }
+
+    fn command(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            let Some(c) = self.peek() else {
+                return String::new();
+            };
+            self.pos += 1;
+            return match c {
+                ',' | ';' | ':' | ' ' => " ".to_string(),
+                '!' => String::new(),
+                '\\' => "\n".to_string(),
+                c => c.to_string(),
+            };
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                format!("{}/{}", group(&numerator), group(&denominator))
+            }
+            "sqrt" => {
+                let root = match self.optional_argument().as_deref() {
+                    Some("3") => "∛",
+                    Some("4") => "∜",
+                    _ => "√",
+                };
+                format!("{root}{}", group(&self.argument()))
+            }
+            "mathbb" => self.argument().chars().map(double_struck).collect(),
+            "hat" | "widehat" => accent(&self.argument(), '\u{302}'),
+            "bar" | "overline" => accent(&self.argument(), '\u{305}'),
+            "vec" => accent(&self.argument(), '\u{20d7}'),
+            "tilde" | "widetilde" => accent(&self.argument(), '\u{303}'),
+            "dot" => accent(&self.argument(), '\u{307}'),
+            "ddot" => accent(&self.argument(), '\u{308}'),
+            "begin" | "end" => {
+                self.argument();
+                String::new()
+            }
+            name if STYLES.contains(&name) => self.argument(),
+            name if IGNORED.contains(&name) => {
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                }
+                String::new()
+            }
+            name => SYMBOLS
+                .iter()
+                .find(|(command, _)| *command == name)
+                .map_or(name, |(_, symbol)| symbol)
+                .to_string(),
+        }
+    }
+}
+
+fn group(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= 1 || text.chars().all(char::is_alphanumeric) {
+        text.to_string()
+    } else {
+        format!("({text})")
+    }
+}
+
+fn script(text: &str, map: &[(char, char)], marker: char) -> String {
+    let mapped: Option<String> = text
+        .trim()
+        .chars()
+        .map(|c| map.iter().find(|(from, _)| *from == c).map(|(_, to)| *to))
+        .collect();
+    mapped.unwrap_or_else(|| format!("{marker}{}", group(text)))
+}
+
+fn accent(text: &str, mark: char) -> String {
+    let mut output = text.trim().to_string();
+    output.push(mark);
+    output
+}
+
+const fn double_struck(c: char) -> char {
+    match c {
+        'C' => 'ℂ',
+        'H' => 'ℍ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        'E' => '𝔼',
+        '1' => '𝟙',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::markdown::MarkdownWidget;
+
+    #[test]
+    fn test_math_renders_as_unicode() {
+        assert_eq!(
+            latex_to_unicode(r"\sum_{i=1}^{n} x_i^2 \leq \frac{a+b}{2}"),
+            "∑ᵢ₌₁ⁿ xᵢ² ≤ (a+b)/2"
+        );
+        assert_eq!(
+            latex_to_unicode(r"\mathbb{E}[X] = \sqrt{\sigma^2} \cdot \alpha"),
+            "𝔼[X] = √σ² · α"
+        );
+        assert_eq!(
+            latex_to_unicode(r"\left( W^T x \right)_{\text{out}}"),
+            "( Wᵀ x )ₒᵤₜ"
+        );
+        assert_eq!(
+            normalize_math("costs $5 or $10, but $x^2$ and \\(y\\) are math; `$HOME`"),
+            "costs $5 or $10, but $$x^2$$ and $$y$$ are math; `$HOME`"
+        );
+
+        let rendered: Vec<String> = MarkdownWidget::new(
+            "The loss is $L = -\\log p$:\n\n\\[\n\\theta \\gets \\theta - \\eta \\nabla L\n\\]",
+        )
+        .render_to_lines()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(rendered[0], "The loss is L = -log p:");
+        assert!(rendered.iter().any(|line| line.contains("θ ← θ - η ∇ L")));
+    }
+}
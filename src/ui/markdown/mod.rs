@@ -2,7 +2,11 @@ mod block;
 mod context;
 mod error;
 mod inline;
+mod links;
+mod math;
+mod table;
 mod widget;
 
 pub use error::MarkdownError;
+pub use links::{Link, extract_links, format_links};
 pub use widget::MarkdownWidget;
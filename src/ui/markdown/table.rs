@@ -0,0 +1,147 @@
+use markdown::mdast::{AlignKind, Node, Table};
+use ratatui::{
+    style::Modifier,
+    text::{Line, Span},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::ui::theme::{BoxChars, Theme};
+
+use super::context::RenderContext;
+use super::inline::collect_text_from_nodes;
+
+const SEPARATOR: &str = " │ ";
+const MIN_COLUMN_WIDTH: usize = 3;
+
+pub fn render_table(table: &Table, ctx: RenderContext) -> Vec<Line<'static>> {
+    let rows: Vec<Vec<String>> = table
+        .children
+        .iter()
+        .map(|row| {
+            row.children()
+                .map_or_else(Vec::new, |cells| cells.iter().map(cell_text).collect())
+        })
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return Vec::new();
+    }
+
+    let mut widths = vec![1; columns];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+    let budget = ctx
+        .available_width()
+        .saturating_sub(SEPARATOR.width() * (columns - 1));
+    fit_widths(&mut widths, budget);
+
+    let indent = ctx.indent();
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (index, row) in rows.iter().enumerate() {
+        let style = if index == 0 {
+            Theme::primary().add_modifier(Modifier::BOLD)
+        } else {
+            Theme::off_white()
+        };
+        let mut spans = vec![Span::raw(indent.clone())];
+        for (column, width) in widths.iter().enumerate() {
+            if column > 0 {
+                spans.push(Span::styled(SEPARATOR, Theme::border()));
+            }
+            let text = row.get(column).map_or("", String::as_str);
+            let align = table.align.get(column).copied().unwrap_or(AlignKind::None);
+            spans.push(Span::styled(
+                pad(&truncate(text, *width), *width, align),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+
+        if index == 0 {
+            let rule = widths
+                .iter()
+                .map(|width| BoxChars::HORIZONTAL.repeat(*width))
+                .collect::<Vec<_>>()
+                .join(&format!("{0}┼{0}", BoxChars::HORIZONTAL));
+            lines.push(Line::from(vec![
+                Span::raw(indent.clone()),
+                Span::styled(rule, Theme::border()),
+            ]));
+        }
+    }
+    lines
+}
+
+fn cell_text(cell: &Node) -> String {
+    cell.children()
+        .map_or_else(String::new, |children| collect_text_from_nodes(children))
+        .replace('\n', " ")
+        .trim()
+        .to_string()
+}
+
+fn fit_widths(widths: &mut [usize], budget: usize) {
+    while widths.iter().sum::<usize>() > budget {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|width| **width > MIN_COLUMN_WIDTH)
+            .max_by_key(|width| **width)
+        else {
+            break;
+        };
+        *widest -= 1;
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut output = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width + 1 > width {
+            break;
+        }
+        used += char_width;
+        output.push(c);
+    }
+    output.push('…');
+    output
+}
+
+fn pad(text: &str, width: usize, align: AlignKind) -> String {
+    let space = width.saturating_sub(text.width());
+    let (left, right) = match align {
+        AlignKind::Right => (space, 0),
+        AlignKind::Center => (space / 2, space - space / 2),
+        AlignKind::Left | AlignKind::None => (0, space),
+    };
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::markdown::MarkdownWidget;
+
+    #[test]
+    fn test_table_renders_aligned_columns() {
+        let markdown = "| Name | Count | Notes |\n|:-----|------:|:-----:|\n| `alpha` | 1 | short |\n| beta | 200 | a much longer note that will not fit |";
+        let lines: Vec<String> = MarkdownWidget::new(markdown)
+            .width(40)
+            .render_to_lines()
+            .unwrap()
+            .iter()
+            .map(|line| line.to_string().trim_end().to_string())
+            .collect();
+
+        assert_eq!(lines[0], format!("Name  │ Count │ {}Notes", " ".repeat(9)));
+        assert_eq!(lines[1], format!("──────┼───────┼{}", "─".repeat(25)));
+        assert_eq!(lines[2], format!("alpha │     1 │ {}short", " ".repeat(9)));
+        assert_eq!(lines[3], "beta  │   200 │ a much longer note that…");
+    }
+}
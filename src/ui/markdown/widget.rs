@@ -1,4 +1,4 @@
-use markdown::{ParseOptions, to_mdast};
+use markdown::{Constructs, ParseOptions, mdast::Node, to_mdast};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -11,6 +11,7 @@ use crate::ui::theme::Theme;
 use super::block::render_node;
 use super::context::RenderContext;
 use super::error::MarkdownError;
+use super::math::normalize_math;
 
 #[derive(Debug, Clone)]
 pub struct MarkdownWidget {
@@ -42,8 +43,7 @@ impl MarkdownWidget {
     }
 
     pub fn render_to_lines(&self) -> Result<Vec<Line<'static>>, MarkdownError> {
-        let ast = to_mdast(&self.content, &ParseOptions::default())
-            .map_err(|e| MarkdownError::Parse(e.to_string()))?;
+        let ast = parse(&self.content)?;
 
         let ctx = RenderContext::new(self.indent_level, self.width);
 
@@ -51,6 +51,23 @@ impl MarkdownWidget {
     }
 }
 
+pub(super) fn parse(content: &str) -> Result<Node, MarkdownError> {
+    let options = ParseOptions {
+        constructs: Constructs {
+            gfm_autolink_literal: true,
+            gfm_strikethrough: true,
+            gfm_table: true,
+            gfm_task_list_item: true,
+            math_flow: true,
+            math_text: true,
+            ..Constructs::default()
+        },
+        math_text_single_dollar: false,
+        ..ParseOptions::default()
+    };
+    to_mdast(&normalize_math(content), &options).map_err(|e| MarkdownError::Parse(e.to_string()))
+}
+
 impl Widget for MarkdownWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let lines = self.render_to_lines().unwrap_or_else(|_| {
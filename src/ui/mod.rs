@@ -1,4 +1,6 @@
 pub mod diff;
+pub mod hyperlink;
+pub mod i18n;
 pub mod markdown;
 pub mod output_widget;
 pub mod theme;
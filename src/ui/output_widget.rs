@@ -16,7 +16,7 @@ impl MessageLevel {
     }
 
     #[must_use]
-    pub const fn style(&self) -> ratatui::style::Style {
+    pub fn style(&self) -> ratatui::style::Style {
         match self {
             Self::Info => Theme::primary(),
             Self::Error => Theme::error(),
@@ -1,4 +1,269 @@
+use ratatui::buffer::Buffer;
 use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static CURRENT_THEME: AtomicU8 = AtomicU8::new(ThemeName::Dark as u8);
+static CAPABILITIES: AtomicU8 = AtomicU8::new(Capabilities::FULL.to_bits());
+
+const ASCII_TERMS: [&str; 4] = ["dumb", "vt100", "vt220", "ansi"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub const ALL: [Self; 3] = [Self::Dark, Self::Light, Self::HighContrast];
+
+    const fn palette(self) -> Palette {
+        match self {
+            Self::Dark => Palette {
+                primary: BrandColors::CYAN,
+                secondary: BrandColors::PURPLE,
+                success: BrandColors::GREEN,
+                warning: BrandColors::AMBER,
+                error: BrandColors::RED,
+                muted: BrandColors::GRAY,
+                border: BrandColors::DARK_GRAY,
+                text: BrandColors::WHITE,
+                text_dim: BrandColors::OFF_WHITE,
+                highlight: BrandColors::DARK_GRAY,
+            },
+            Self::Light => Palette {
+                primary: Color::Rgb(2, 132, 199),
+                secondary: Color::Rgb(124, 58, 237),
+                success: Color::Rgb(5, 150, 105),
+                warning: Color::Rgb(180, 83, 9),
+                error: Color::Rgb(220, 38, 38),
+                muted: Color::Rgb(107, 114, 128),
+                border: Color::Rgb(209, 213, 219),
+                text: Color::Rgb(17, 24, 39),
+                text_dim: Color::Rgb(75, 85, 99),
+                highlight: Color::Rgb(229, 231, 235),
+            },
+            Self::HighContrast => Palette {
+                primary: Color::LightCyan,
+                secondary: Color::LightMagenta,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                error: Color::LightRed,
+                muted: Color::Gray,
+                border: Color::White,
+                text: Color::White,
+                text_dim: Color::White,
+                highlight: Color::Blue,
+            },
+        }
+    }
+
+    const fn from_index(index: u8) -> Self {
+        match index {
+            1 => Self::Light,
+            2 => Self::HighContrast,
+            _ => Self::Dark,
+        }
+    }
+}
+
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dark => write!(f, "dark"),
+            Self::Light => write!(f, "light"),
+            Self::HighContrast => write!(f, "high contrast"),
+        }
+    }
+}
+
+pub fn set_theme(theme: ThemeName) {
+    CURRENT_THEME.store(theme as u8, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn current_theme() -> ThemeName {
+    ThemeName::from_index(CURRENT_THEME.load(Ordering::Relaxed))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Palette {
+    primary: Color,
+    secondary: Color,
+    success: Color,
+    warning: Color,
+    error: Color,
+    muted: Color,
+    border: Color,
+    text: Color,
+    text_dim: Color,
+    highlight: Color,
+}
+
+fn palette() -> Palette {
+    if capabilities().color == ColorSupport::Ansi16 {
+        return ThemeName::HighContrast.palette();
+    }
+    current_theme().palette()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    None,
+    Ansi16,
+    TrueColor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub unicode: bool,
+    pub color: ColorSupport,
+}
+
+impl Capabilities {
+    pub const FULL: Self = Self {
+        unicode: true,
+        color: ColorSupport::TrueColor,
+    };
+
+    #[must_use]
+    pub fn detect(ascii: bool, no_color: bool) -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let locale = var("LC_ALL")
+            .or_else(|| var("LC_CTYPE"))
+            .or_else(|| var("LANG"));
+        let mut detected = Self::from_env(
+            var("TERM").as_deref(),
+            var("COLORTERM").as_deref(),
+            locale.as_deref(),
+        );
+        if ascii {
+            detected.unicode = false;
+        }
+        if no_color || var("NO_COLOR").is_some() {
+            detected.color = ColorSupport::None;
+        }
+        detected
+    }
+
+    #[must_use]
+    pub fn from_env(term: Option<&str>, colorterm: Option<&str>, locale: Option<&str>) -> Self {
+        let term = term.unwrap_or_default().to_ascii_lowercase();
+        let color = if term == "dumb" {
+            ColorSupport::None
+        } else if matches!(colorterm, Some("truecolor" | "24bit"))
+            || ["256color", "truecolor", "direct"]
+                .iter()
+                .any(|suffix| term.contains(suffix))
+        {
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::Ansi16
+        };
+        let utf8_locale = locale.is_none_or(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+        Self {
+            unicode: utf8_locale && !ASCII_TERMS.contains(&term.as_str()),
+            color,
+        }
+    }
+
+    const fn to_bits(self) -> u8 {
+        let color = match self.color {
+            ColorSupport::None => 0,
+            ColorSupport::Ansi16 => 1,
+            ColorSupport::TrueColor => 2,
+        };
+        color | if self.unicode { 4 } else { 0 }
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        Self {
+            unicode: bits & 4 != 0,
+            color: match bits & 3 {
+                0 => ColorSupport::None,
+                1 => ColorSupport::Ansi16,
+                _ => ColorSupport::TrueColor,
+            },
+        }
+    }
+}
+
+pub fn set_capabilities(capabilities: Capabilities) {
+    CAPABILITIES.store(capabilities.to_bits(), Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn capabilities() -> Capabilities {
+    Capabilities::from_bits(CAPABILITIES.load(Ordering::Relaxed))
+}
+
+pub fn degrade_buffer(buf: &mut Buffer) {
+    let capabilities = capabilities();
+    if capabilities == Capabilities::FULL {
+        return;
+    }
+    for cell in &mut buf.content {
+        if !capabilities.unicode
+            && let Some(ascii) = BoxChars::ascii_fallback(cell.symbol())
+        {
+            cell.set_symbol(ascii);
+        }
+        match capabilities.color {
+            ColorSupport::None => {
+                if cell.bg != Color::Reset {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+            ColorSupport::Ansi16 => {
+                cell.fg = to_ansi16(cell.fg);
+                cell.bg = to_ansi16(cell.bg);
+            }
+            ColorSupport::TrueColor => {}
+        }
+    }
+}
+
+fn to_ansi16(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let max = r.max(g).max(b);
+    if max - r.min(g).min(b) < 40 {
+        return match max {
+            0..=60 => Color::Black,
+            61..=150 => Color::DarkGray,
+            151..=220 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+    let lit = |channel: u8| u16::from(channel) * 3 > u16::from(max) * 2;
+    let bright = max > 200;
+    match (lit(r), lit(g), lit(b)) {
+        (true, false, false) if bright => Color::LightRed,
+        (true, false, false) => Color::Red,
+        (false, true, false) if bright => Color::LightGreen,
+        (false, true, false) => Color::Green,
+        (true, true, false) if bright => Color::LightYellow,
+        (true, true, false) => Color::Yellow,
+        (false, false, true) if bright => Color::LightBlue,
+        (false, false, true) => Color::Blue,
+        (true, false, true) if bright => Color::LightMagenta,
+        (true, false, true) => Color::Magenta,
+        (false, true, true) if bright => Color::LightCyan,
+        (false, true, true) => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
 pub struct BrandColors;
 
 impl BrandColors {
@@ -24,8 +289,33 @@ impl BoxChars {
     pub const VERTICAL: &'static str = "│";
     pub const DIVIDER_LIGHT: &'static str = "┄";
     pub const DOT: &'static str = "•";
+    pub const CHECKBOX: &'static str = "☐";
+    pub const CHECKBOX_CHECKED: &'static str = "☑";
     pub const ELLIPSIS: &'static str = "⋮";
     pub const ARROW_RIGHT: &'static str = "❯";
+
+    #[must_use]
+    pub fn ascii_fallback(symbol: &str) -> Option<&'static str> {
+        let ascii = match symbol {
+            "╭" | "╮" | "╰" | "╯" | "┌" | "┐" | "└" | "┘" | "├" | "┤" | "┬" | "┴" | "┼" => {
+                "+"
+            }
+            "─" | "━" | "┄" | "┈" | "═" | "╌" => "-",
+            "│" | "┃" | "║" | "▏" | "▕" | "┆" | "┊" => "|",
+            "•" | "●" | "◦" | "·" => "*",
+            "⋮" | "…" => ".",
+            "❯" | "›" | "→" | "▶" | "▸" => ">",
+            "←" | "◀" => "<",
+            "↑" | "▲" => "^",
+            "↓" | "▼" | "✓" | "✔" => "v",
+            "✗" | "✘" | "✕" | "☑" => "x",
+            "☐" => "o",
+            "⚠" => "!",
+            "█" | "▌" | "▐" | "░" | "▒" | "▓" => "#",
+            _ => return None,
+        };
+        Some(ascii)
+    }
 }
 
 pub struct Spinners;
@@ -34,56 +324,117 @@ impl Spinners {
     pub const BRAILLE: &'static [&'static str] =
         &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
     pub const CIRCLES: &'static [&'static str] = &["◐", "◓", "◑", "◒"];
+    pub const ASCII: &'static [&'static str] = &["|", "/", "-", "\\"];
+
+    #[must_use]
+    pub fn frames(unicode: &'static [&'static str]) -> &'static [&'static str] {
+        if capabilities().unicode {
+            unicode
+        } else {
+            Self::ASCII
+        }
+    }
 }
 
 pub struct Theme;
 
 impl Theme {
     #[must_use]
-    pub const fn primary() -> Style {
-        Style::new().fg(BrandColors::CYAN)
+    pub fn primary() -> Style {
+        Style::new().fg(palette().primary)
     }
     #[must_use]
-    pub const fn secondary() -> Style {
-        Style::new().fg(BrandColors::PURPLE)
+    pub fn secondary() -> Style {
+        Style::new().fg(palette().secondary)
     }
     #[must_use]
-    pub const fn success() -> Style {
-        Style::new().fg(BrandColors::GREEN)
+    pub fn success() -> Style {
+        Style::new().fg(palette().success)
     }
     #[must_use]
-    pub const fn warning() -> Style {
-        Style::new().fg(BrandColors::AMBER)
+    pub fn warning() -> Style {
+        Style::new().fg(palette().warning)
     }
     #[must_use]
-    pub const fn error() -> Style {
-        Style::new().fg(BrandColors::RED)
+    pub fn error() -> Style {
+        Style::new().fg(palette().error)
     }
 
     #[must_use]
-    pub const fn muted() -> Style {
-        Style::new().fg(BrandColors::GRAY)
+    pub fn muted() -> Style {
+        Style::new().fg(palette().muted)
     }
 
     #[must_use]
-    pub const fn border() -> Style {
-        Style::new().fg(BrandColors::DARK_GRAY)
+    pub fn border() -> Style {
+        Style::new().fg(palette().border)
     }
 
     #[must_use]
-    pub const fn white() -> Style {
-        Style::new().fg(BrandColors::WHITE)
+    pub fn white() -> Style {
+        Style::new().fg(palette().text)
+    }
+
+    #[must_use]
+    pub fn off_white() -> Style {
+        Style::new().fg(palette().text_dim)
+    }
+
+    #[must_use]
+    pub fn primary_bold() -> Style {
+        Style::new()
+            .fg(palette().primary)
+            .add_modifier(Modifier::BOLD)
     }
 
     #[must_use]
-    pub const fn off_white() -> Style {
-        Style::new().fg(BrandColors::OFF_WHITE)
+    pub fn search_match() -> Style {
+        let palette = palette();
+        Style::new().fg(palette.text).bg(palette.highlight)
     }
 
     #[must_use]
-    pub const fn primary_bold() -> Style {
+    pub fn search_current() -> Style {
         Style::new()
-            .fg(BrandColors::CYAN)
+            .fg(Color::Black)
+            .bg(palette().warning)
             .add_modifier(Modifier::BOLD)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_from_env() {
+        let full = Capabilities::from_env(Some("xterm-256color"), None, Some("en_US.UTF-8"));
+        assert_eq!(full, Capabilities::FULL);
+
+        let minimal = Capabilities::from_env(Some("vt100"), None, Some("C"));
+        assert!(!minimal.unicode);
+        assert_eq!(minimal.color, ColorSupport::Ansi16);
+
+        let dumb = Capabilities::from_env(Some("dumb"), Some("truecolor"), None);
+        assert_eq!(dumb.color, ColorSupport::None);
+        assert!(!dumb.unicode);
+
+        for caps in [full, minimal, dumb] {
+            assert_eq!(Capabilities::from_bits(caps.to_bits()), caps);
+        }
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        assert_eq!(
+            BoxChars::ascii_fallback(BoxChars::ROUND_TOP_LEFT),
+            Some("+")
+        );
+        assert_eq!(BoxChars::ascii_fallback(BoxChars::VERTICAL), Some("|"));
+        assert_eq!(BoxChars::ascii_fallback("a"), None);
+        assert_eq!(to_ansi16(BrandColors::RED), Color::LightRed);
+        assert_eq!(to_ansi16(BrandColors::CYAN), Color::LightCyan);
+        assert_eq!(to_ansi16(BrandColors::GRAY), Color::DarkGray);
+        assert_eq!(to_ansi16(Color::Yellow), Color::Yellow);
+    }
+}
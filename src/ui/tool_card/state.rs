@@ -3,7 +3,7 @@ use crate::ui::theme::{Spinners, Theme};
 use ratatui::style::Style;
 
 #[must_use]
-pub const fn state_style(state: &ToolState) -> Style {
+pub fn state_style(state: &ToolState) -> Style {
     match state {
         ToolState::Starting | ToolState::InProgress => Theme::primary(),
         ToolState::Success => Theme::success(),
@@ -15,7 +15,7 @@ pub const fn state_style(state: &ToolState) -> Style {
 pub fn state_spinner_frame(state: &ToolState, frame_index: usize) -> &'static str {
     match state {
         ToolState::Starting | ToolState::InProgress => {
-            let frames = Spinners::CIRCLES;
+            let frames = Spinners::frames(Spinners::CIRCLES);
             frames[frame_index % frames.len()]
         }
         ToolState::Success => "ok",
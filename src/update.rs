@@ -0,0 +1,336 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::config::{UpdateConfig, get_config_dir};
+use crate::tools::fs::atomic_write;
+use crate::tui::events::AppEvent;
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const REPOSITORY: &str = "https://github.com/buyukakyuz/smith";
+
+const RELEASES_URL: &str = "https://api.github.com/repos/buyukakyuz/smith/releases/latest";
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+const STATE_FILE: &str = "update_check.json";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(
+        "No prebuilt binary named {0} in the latest release; reinstall with `cargo install --git {REPOSITORY} --force`"
+    )]
+    NoAsset(String),
+
+    #[error("The latest release has no {CHECKSUMS_ASSET} entry for {0}; refusing to install it")]
+    NoChecksum(String),
+
+    #[error("Checksum mismatch for {0}; the download was not installed")]
+    ChecksumMismatch(String),
+}
+
+pub type UpdateResult<T> = std::result::Result<T, UpdateError>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    #[must_use]
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckState {
+    checked_at: DateTime<Utc>,
+    latest: Option<String>,
+}
+
+fn parse_version(version: &str) -> Option<(u64, u64, u64, bool)> {
+    let version = version.trim().trim_start_matches('v');
+    let (core, pre) = version
+        .split_once('-')
+        .map_or((version, false), |(core, _)| (core, true));
+    let mut parts = core.split('.').map(str::parse::<u64>);
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch, !pre))
+}
+
+#[must_use]
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+#[must_use]
+pub fn asset_name() -> String {
+    format!(
+        "smith-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+pub struct UpdateChecker {
+    client: reqwest::Client,
+    state_path: Option<PathBuf>,
+    interval: chrono::Duration,
+}
+
+impl UpdateChecker {
+    #[must_use]
+    pub fn new(config: &UpdateConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(format!("smith/{CURRENT_VERSION}"))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            state_path: get_config_dir().map(|dir| dir.join(STATE_FILE)),
+            interval: chrono::Duration::hours(i64::from(config.interval_hours)),
+        }
+    }
+
+    #[must_use]
+    pub fn with_state_path(mut self, path: PathBuf) -> Self {
+        self.state_path = Some(path);
+        self
+    }
+
+    pub async fn latest_release(&self) -> UpdateResult<Release> {
+        let release: Release = self
+            .client
+            .get(RELEASES_URL)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        self.save_state(Some(release.version().to_string()));
+        Ok(release)
+    }
+
+    pub async fn available_update(&self) -> Option<String> {
+        let latest = match self.recent_check() {
+            Some(state) => state.latest,
+            None => match self.latest_release().await {
+                Ok(release) => Some(release.version().to_string()),
+                Err(e) => {
+                    tracing::debug!("Update check failed: {e}");
+                    self.save_state(None);
+                    None
+                }
+            },
+        };
+        latest.filter(|version| is_newer(version, CURRENT_VERSION))
+    }
+
+    pub async fn install(&self, release: &Release) -> UpdateResult<PathBuf> {
+        let name = asset_name();
+        let find = |name: &str| release.assets.iter().find(|asset| asset.name == name);
+        let asset = find(&name).ok_or_else(|| UpdateError::NoAsset(name.clone()))?;
+        let checksums =
+            find(CHECKSUMS_ASSET).ok_or_else(|| UpdateError::NoChecksum(name.clone()))?;
+
+        let checksums = self.download(&checksums.browser_download_url).await?;
+        let bytes = self.download(&asset.browser_download_url).await?;
+        verify_checksum(&bytes, &String::from_utf8_lossy(&checksums), &name)?;
+
+        let target = std::env::current_exe()?;
+        replace_executable(&target, &bytes)?;
+        Ok(target)
+    }
+
+    async fn download(&self, url: &str) -> UpdateResult<bytes::Bytes> {
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?)
+    }
+
+    fn recent_check(&self) -> Option<CheckState> {
+        let content = fs::read_to_string(self.state_path.as_ref()?).ok()?;
+        let state: CheckState = serde_json::from_str(&content).ok()?;
+        (Utc::now().signed_duration_since(state.checked_at) < self.interval).then_some(state)
+    }
+
+    fn save_state(&self, latest: Option<String>) {
+        let Some(path) = &self.state_path else {
+            return;
+        };
+        let state = CheckState {
+            checked_at: Utc::now(),
+            latest,
+        };
+        let result = serde_json::to_string(&state)
+            .map_err(io::Error::from)
+            .and_then(|json| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                atomic_write(path, &json)
+            });
+        if let Err(e) = result {
+            tracing::debug!("Failed to record update check: {e}");
+        }
+    }
+}
+
+pub fn spawn_update_check(config: &UpdateConfig, event_tx: mpsc::UnboundedSender<AppEvent>) {
+    if !config.check {
+        return;
+    }
+    let checker = UpdateChecker::new(config);
+    tokio::spawn(async move {
+        if let Some(version) = checker.available_update().await {
+            let _ = event_tx.send(AppEvent::UpdateAvailable(version));
+        }
+    });
+}
+
+fn verify_checksum(bytes: &[u8], checksums: &str, name: &str) -> UpdateResult<()> {
+    let expected = checksums
+        .lines()
+        .find_map(|line| {
+            let (hash, file) = line.trim().split_once(char::is_whitespace)?;
+            (file.trim_start().trim_start_matches('*') == name).then_some(hash)
+        })
+        .ok_or_else(|| UpdateError::NoChecksum(name.to_string()))?;
+    if sha256_hex(bytes).eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(UpdateError::ChecksumMismatch(name.to_string()))
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn replace_executable(target: &Path, bytes: &[u8]) -> io::Result<()> {
+    let staged = target.with_extension("new");
+    fs::write(&staged, bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(windows)]
+    {
+        let previous = target.with_extension("old");
+        let _ = fs::remove_file(&previous);
+        fs::rename(target, &previous)?;
+    }
+    fs::rename(&staged, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_version_comparison() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("v1.0.0", "0.9.12"));
+        assert!(is_newer("0.1.1", "0.1.1-rc.1"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.2.0-beta", "0.2.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+
+    #[tokio::test]
+    async fn test_recent_check_is_reused() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(STATE_FILE);
+        let checker = UpdateChecker::new(&UpdateConfig::default()).with_state_path(path.clone());
+
+        let state = CheckState {
+            checked_at: Utc::now(),
+            latest: Some("99.0.0".to_string()),
+        };
+        fs::write(&path, serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(checker.available_update().await.as_deref(), Some("99.0.0"));
+
+        let state = CheckState {
+            checked_at: Utc::now(),
+            latest: Some(CURRENT_VERSION.to_string()),
+        };
+        fs::write(&path, serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(checker.available_update().await, None);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let name = "smith-x86_64-linux";
+        let hash = sha256_hex(b"binary");
+        let checksums = format!("{}  smith-aarch64-macos\n{hash} *{name}\n", "0".repeat(64));
+
+        assert!(verify_checksum(b"binary", &checksums, name).is_ok());
+        assert!(matches!(
+            verify_checksum(b"tampered", &checksums, name),
+            Err(UpdateError::ChecksumMismatch(_))
+        ));
+        assert!(matches!(
+            verify_checksum(b"binary", &checksums, "smith-x86_64-windows.exe"),
+            Err(UpdateError::NoChecksum(_))
+        ));
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("smith");
+        fs::write(&target, "old").unwrap();
+
+        replace_executable(&target, b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert!(!temp.path().join("smith.new").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&target).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+}
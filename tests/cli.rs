@@ -34,3 +34,49 @@ fn test_config_where() {
 fn test_invalid_subcommand() {
     smith_cmd().arg("invalid-command").assert().failure();
 }
+
+#[test]
+fn test_serve_help() {
+    smith_cmd()
+        .args(["serve", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--addr"));
+}
+
+#[test]
+fn test_batch_writes_transcripts_and_summary() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(temp.path().join("repo")).unwrap();
+    std::fs::write(
+        temp.path().join("scenario.yaml"),
+        "turns:\n  - events:\n      - type: text\n        text: All done.\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp.path().join("tasks.yaml"),
+        "tasks:\n  - name: Tidy\n    prompt: Tidy up\n    dir: repo\n  - name: Lost\n    prompt: Look around\n    dir: missing\n",
+    )
+    .unwrap();
+    let out = temp.path().join("out");
+
+    smith_cmd()
+        .current_dir(temp.path())
+        .env("XDG_CONFIG_HOME", temp.path().join("config"))
+        .args([
+            "--scenario",
+            "scenario.yaml",
+            "batch",
+            "tasks.yaml",
+            "--out",
+        ])
+        .arg(&out)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 of 2 tasks succeeded"));
+
+    let transcript = std::fs::read_to_string(out.join("01-tidy.md")).unwrap();
+    assert!(transcript.contains("All done."));
+    let summary = std::fs::read_to_string(out.join("summary.json")).unwrap();
+    assert!(summary.contains("\"success\": false"));
+}
@@ -0,0 +1,71 @@
+use smith::providers::mock::{Scenario, ScenarioTurn, ScriptedEvent};
+use smith::tui::ScenarioHarness;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_fixture_drives_tool_loop() {
+    let scenario =
+        Scenario::load(Path::new("tests/fixtures/scenarios/find_manifest.yaml")).unwrap();
+    let mut harness = ScenarioHarness::new(scenario).unwrap();
+
+    let outcome = harness.send("Where is the manifest?").await.unwrap();
+
+    assert_eq!(outcome.error, None);
+    assert_eq!(
+        outcome.text,
+        "Let me look for the manifest.The manifest is Cargo.toml."
+    );
+    assert_eq!(outcome.tools_started(), ["glob"]);
+    assert!(outcome.tool_outputs()[0].contains("Cargo.toml"));
+    assert_eq!(harness.llm().remaining_turns(), 0);
+}
+
+#[tokio::test]
+async fn test_write_then_answer_across_prompts() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("notes.txt");
+    let scenario = Scenario::new(vec![
+        ScenarioTurn::tool_call(
+            "write_file",
+            serde_json::json!({"path": path.to_string_lossy(), "content": "hello"}),
+        )
+        .expecting("write"),
+        ScenarioTurn::text("Done.").expecting("Wrote"),
+        ScenarioTurn::text("It says hello.").expecting("what does it say"),
+    ]);
+    let mut harness = ScenarioHarness::new(scenario).unwrap();
+
+    let first = harness.send("write a note").await.unwrap();
+    assert_eq!(first.text, "Done.");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello");
+
+    let second = harness.send("what does it say?").await.unwrap();
+    assert_eq!(second.text, "It says hello.");
+    assert_eq!(harness.llm().request_history().len(), 3);
+}
+
+#[tokio::test]
+async fn test_scripted_failures_reach_the_ui() {
+    let scenario = Scenario::new(vec![
+        ScenarioTurn {
+            error: Some("API error 529: overloaded".to_string()),
+            ..ScenarioTurn::default()
+        },
+        ScenarioTurn {
+            events: vec![ScriptedEvent::Error {
+                message: "Stream error: connection reset".to_string(),
+            }],
+            ..ScenarioTurn::default()
+        },
+        ScenarioTurn::text("Recovered."),
+    ]);
+    let mut harness = ScenarioHarness::new(scenario).unwrap();
+
+    let failed = harness.send("hi").await.unwrap();
+    assert!(failed.error.unwrap().contains("overloaded"));
+
+    let retried = harness.send("hi again").await.unwrap();
+    assert_eq!(retried.error, None);
+    assert_eq!(retried.text, "Recovered.");
+}